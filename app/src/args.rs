@@ -182,6 +182,8 @@ pub enum CliBackend {
     VulkanNative,
     /// No-op backend for testing and CI environments.
     Dummy,
+    /// CPU software rasterizer (headless rendering without a GPU).
+    Software,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -192,6 +194,7 @@ impl From<CliBackend> for BackendType {
             CliBackend::Wgpu => BackendType::Wgpu,
             CliBackend::VulkanNative => BackendType::Vulkan,
             CliBackend::Dummy => BackendType::Dummy,
+            CliBackend::Software => BackendType::Software,
         }
     }
 }
@@ -385,6 +388,8 @@ mod native {
             \n\
             • dummy: No-op backend for testing without GPU.\n\
             \n\
+            • software: CPU rasterizer for headless rendering without GPU.\n\
+            \n\
             EXAMPLES:\n\
               # Use wgpu with platform default (recommended)\n\
               ./app --backend wgpu\n\
@@ -602,11 +607,12 @@ pub fn parse_backend(value: &str) -> BackendType {
         "wgpu" => BackendType::Wgpu,
         "vulkan" | "vulkan-native" => BackendType::Vulkan,
         "dummy" => BackendType::Dummy,
+        "software" => BackendType::Software,
         "auto" => BackendType::Auto,
         _ => {
             log::warn!(
                 "Unknown backend '{}', falling back to auto. \
-                Valid options: auto, wgpu, vulkan-native, dummy",
+                Valid options: auto, wgpu, vulkan-native, dummy, software",
                 value
             );
             BackendType::Auto
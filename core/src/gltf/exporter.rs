@@ -13,6 +13,7 @@ use gltf_dep::json as gj;
 use crate::material::{AlphaMode, CpuMaterialInstance, TextureRef, TextureSource};
 use crate::mesh::{
     CpuMesh, IndexFormat, PrimitiveTopology, VertexAttributeFormat, VertexAttributeSemantic,
+    VertexLayout,
 };
 use crate::sampler::{AddressMode, CpuSampler, FilterMode};
 use crate::scene::{
@@ -307,16 +308,20 @@ impl ExportContext {
             let scene_camera_offset = self.root.cameras.len() as u32;
             self.build_cameras(&scene.cameras);
 
-            let scene_node_offset = self.root.nodes.len() as u32;
-            let root_indices =
-                self.flatten_scene_nodes(&scene.nodes, scene_mesh_offset, scene_camera_offset);
+            let mut node_map = Vec::new();
+            let root_indices = self.flatten_scene_nodes(
+                &scene.nodes,
+                scene_mesh_offset,
+                scene_camera_offset,
+                &mut node_map,
+            );
 
             let scene_skin_offset = self.root.skins.len() as u32;
-            self.build_skins(&scene.skins, scene_node_offset)?;
+            self.build_skins(&scene.skins, &node_map)?;
 
-            self.patch_skin_refs(&scene.nodes, scene_node_offset, scene_skin_offset);
+            self.patch_skin_refs(&scene.nodes, &node_map, scene_skin_offset);
 
-            self.build_animations(&scene.animations, scene_node_offset)?;
+            self.build_animations(&scene.animations, &node_map)?;
 
             self.root.scenes.push(gj::Scene {
                 name: scene.name.clone(),
@@ -337,8 +342,14 @@ impl ExportContext {
         for mesh in meshes {
             let layout = mesh.layout();
             let stride = layout.buffer_stride(0);
-            let vertex_data = mesh.vertex_buffer_data(0).unwrap_or(&[]);
             let vertex_count = mesh.vertex_count();
+            let vertex_data = narrow_uint4_attributes(
+                mesh.vertex_buffer_data(0).unwrap_or(&[]),
+                layout,
+                stride,
+                vertex_count,
+            );
+            let vertex_data = vertex_data.as_slice();
 
             let vtx_view_idx = if !vertex_data.is_empty() {
                 Some(self.push_buffer_view_with_stride(
@@ -374,10 +385,12 @@ impl ExportContext {
                     accessor_type,
                     min,
                     max,
-                    false,
+                    attr.format == VertexAttributeFormat::Unorm8x4,
                 );
 
-                let semantic = map_semantic(attr.semantic);
+                let Some(semantic) = map_semantic(attr.semantic) else {
+                    continue;
+                };
                 attributes.insert(
                     gj::validation::Checked::Valid(semantic),
                     gj::Index::new(acc_idx),
@@ -482,28 +495,41 @@ impl ExportContext {
         }
     }
 
+    /// Flatten the scene's node trees into the glTF node list.
+    ///
+    /// `node_map` receives the glTF node index of every scene node in
+    /// depth-first pre-order, which is how skins and animation channels
+    /// refer to nodes.
     fn flatten_scene_nodes(
         &mut self,
         roots: &[SceneNode],
         mesh_offset: u32,
         camera_offset: u32,
+        node_map: &mut Vec<u32>,
     ) -> Vec<u32> {
         let mut root_indices = Vec::new();
         for node in roots {
-            let idx = self.flatten_node(node, mesh_offset, camera_offset);
+            let idx = self.flatten_node(node, mesh_offset, camera_offset, node_map);
             root_indices.push(idx);
         }
         root_indices
     }
 
-    fn flatten_node(&mut self, node: &SceneNode, mesh_offset: u32, camera_offset: u32) -> u32 {
+    fn flatten_node(
+        &mut self,
+        node: &SceneNode,
+        mesh_offset: u32,
+        camera_offset: u32,
+        node_map: &mut Vec<u32>,
+    ) -> u32 {
         let my_index = self.root.nodes.len() as u32;
         self.root.nodes.push(gj::Node::default());
+        node_map.push(my_index);
 
         let child_indices: Vec<u32> = node
             .children
             .iter()
-            .map(|c| self.flatten_node(c, mesh_offset, camera_offset))
+            .map(|c| self.flatten_node(c, mesh_offset, camera_offset, node_map))
             .collect();
 
         let mesh = if !node.meshes.is_empty() {
@@ -562,21 +588,22 @@ impl ExportContext {
         my_index
     }
 
-    fn patch_skin_refs(&mut self, roots: &[SceneNode], node_offset: u32, skin_offset: u32) {
-        let mut flat_idx = node_offset;
+    fn patch_skin_refs(&mut self, roots: &[SceneNode], node_map: &[u32], skin_offset: u32) {
+        let mut scene_idx = 0;
         for node in roots {
-            self.patch_skin_refs_recursive(node, &mut flat_idx, skin_offset);
+            self.patch_skin_refs_recursive(node, node_map, &mut scene_idx, skin_offset);
         }
     }
 
     fn patch_skin_refs_recursive(
         &mut self,
         node: &SceneNode,
-        flat_idx: &mut u32,
+        node_map: &[u32],
+        scene_idx: &mut usize,
         skin_offset: u32,
     ) {
-        let my_idx = *flat_idx;
-        *flat_idx += 1;
+        let my_idx = node_map[*scene_idx];
+        *scene_idx += 1;
 
         if let Some(skin_idx) = node.skin {
             self.root.nodes[my_idx as usize].skin =
@@ -584,21 +611,26 @@ impl ExportContext {
         }
 
         for child in &node.children {
-            self.patch_skin_refs_recursive(child, flat_idx, skin_offset);
-        }
-
-        // Skip extra child nodes for multi-mesh nodes
-        if node.meshes.len() > 1 {
-            *flat_idx += (node.meshes.len() - 1) as u32;
+            self.patch_skin_refs_recursive(child, node_map, scene_idx, skin_offset);
         }
     }
 
     fn build_skins(
         &mut self,
         skins: &[crate::scene::SceneSkin],
-        node_offset: u32,
+        node_map: &[u32],
     ) -> Result<(), GltfError> {
         for skin in skins {
+            let joints = skin
+                .joints
+                .iter()
+                .map(|&j| scene_node_index(node_map, j))
+                .collect::<Result<Vec<_>, _>>()?;
+            let skeleton = skin
+                .skeleton
+                .map(|s| scene_node_index(node_map, s))
+                .transpose()?;
+
             let ibm_accessor = if !skin.inverse_bind_matrices.is_empty() {
                 let data: Vec<u8> = skin
                     .inverse_bind_matrices
@@ -624,14 +656,8 @@ impl ExportContext {
             self.root.skins.push(gj::Skin {
                 name: skin.name.clone(),
                 inverse_bind_matrices: ibm_accessor,
-                joints: skin
-                    .joints
-                    .iter()
-                    .map(|&j| gj::Index::new(node_offset + j as u32))
-                    .collect(),
-                skeleton: skin
-                    .skeleton
-                    .map(|s| gj::Index::new(node_offset + s as u32)),
+                joints,
+                skeleton,
                 extensions: None,
                 extras: gj::Extras::default(),
             });
@@ -642,7 +668,7 @@ impl ExportContext {
     fn build_animations(
         &mut self,
         animations: &[Animation],
-        node_offset: u32,
+        node_map: &[u32],
     ) -> Result<(), GltfError> {
         for anim in animations {
             let mut channels = Vec::new();
@@ -729,7 +755,7 @@ impl ExportContext {
                 channels.push(gj::animation::Channel {
                     sampler: gj::Index::new(sampler_idx),
                     target: gj::animation::Target {
-                        node: gj::Index::new(node_offset + channel.target_node as u32),
+                        node: scene_node_index(node_map, channel.target_node)?,
                         path: gj::validation::Checked::Valid(path),
                         extensions: None,
                         extras: gj::Extras::default(),
@@ -901,6 +927,14 @@ impl ExportContext {
 // Helper functions
 // ---------------------------------------------------------------------------
 
+/// Translate a scene node index (depth-first pre-order) to a glTF node index.
+fn scene_node_index(node_map: &[u32], index: usize) -> Result<gj::Index<gj::Node>, GltfError> {
+    node_map
+        .get(index)
+        .map(|&i| gj::Index::new(i))
+        .ok_or_else(|| GltfError::ExportError(format!("node index {index} is not in the scene")))
+}
+
 fn encode_texture_to_png(texture: &CpuTexture) -> Result<Vec<u8>, GltfError> {
     let img = image::ImageBuffer::<image::Rgba<u8>, _>::from_raw(
         texture.width,
@@ -954,8 +988,9 @@ fn map_attribute_format(
     }
 }
 
-fn map_semantic(semantic: VertexAttributeSemantic) -> gj::mesh::Semantic {
-    match semantic {
+/// glTF semantic of a vertex attribute; per-instance attributes have none.
+fn map_semantic(semantic: VertexAttributeSemantic) -> Option<gj::mesh::Semantic> {
+    Some(match semantic {
         VertexAttributeSemantic::Position => gj::mesh::Semantic::Positions,
         VertexAttributeSemantic::Normal => gj::mesh::Semantic::Normals,
         VertexAttributeSemantic::Tangent => gj::mesh::Semantic::Tangents,
@@ -964,7 +999,11 @@ fn map_semantic(semantic: VertexAttributeSemantic) -> gj::mesh::Semantic {
         VertexAttributeSemantic::Color => gj::mesh::Semantic::Colors(0),
         VertexAttributeSemantic::Joints => gj::mesh::Semantic::Joints(0),
         VertexAttributeSemantic::Weights => gj::mesh::Semantic::Weights(0),
-    }
+        VertexAttributeSemantic::InstanceTransform0
+        | VertexAttributeSemantic::InstanceTransform1
+        | VertexAttributeSemantic::InstanceTransform2
+        | VertexAttributeSemantic::InstanceTransform3 => return None,
+    })
 }
 
 fn map_topology(topology: PrimitiveTopology) -> gj::mesh::Mode {
@@ -1010,6 +1049,36 @@ fn map_interpolation(interp: Interpolation) -> gj::animation::Interpolation {
     }
 }
 
+/// Rewrite `Uint4` attributes (u32x4) in place as u16x4, the widest joint
+/// index type glTF allows. The remaining 8 bytes of each slot are zeroed.
+fn narrow_uint4_attributes(
+    vertex_data: &[u8],
+    layout: &VertexLayout,
+    stride: u32,
+    vertex_count: u32,
+) -> Vec<u8> {
+    let mut data = vertex_data.to_vec();
+    for attr in layout.attributes_for_buffer(0) {
+        if attr.format != VertexAttributeFormat::Uint4 {
+            continue;
+        }
+        for v in 0..vertex_count as usize {
+            let base = v * stride as usize + attr.offset as usize;
+            if base + 16 > data.len() {
+                break;
+            }
+            let mut narrowed = [0u8; 16];
+            for c in 0..4 {
+                let bytes = &data[base + c * 4..base + c * 4 + 4];
+                let value = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+                narrowed[c * 2..c * 2 + 2].copy_from_slice(&(value as u16).to_le_bytes());
+            }
+            data[base..base + 16].copy_from_slice(&narrowed);
+        }
+    }
+    data
+}
+
 /// Build a JSON array of f32 values (for accessor min/max).
 fn json_f32_array(values: &[f32]) -> gj::Value {
     gj::Value::Array(values.iter().map(|&v| gj::Value::from(v as f64)).collect())
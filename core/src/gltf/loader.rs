@@ -13,7 +13,7 @@ use crate::scene::{
     Animation, AnimationChannel, AnimationProperty, CameraProjection, Interpolation, NodeTransform,
     Scene, SceneCamera, SceneNode, SceneSkin,
 };
-use crate::texture::{CpuTexture, TextureFormat};
+use crate::texture::{CpuTexture, TextureContainer, TextureFormat};
 
 use super::error::GltfError;
 use super::types::GltfMaterial;
@@ -63,64 +63,94 @@ impl LoadContext {
     /// Stores the results in `self.texture_arcs`, indexed by glTF texture
     /// index. Used by `resolve_texture_sampler` to embed textures directly
     /// into [`TextureRef`].
+    ///
+    /// Textures with a `KHR_texture_basisu` source load its KTX2 image
+    /// first, falling back to the core `source` image with a warning if it
+    /// can't be decoded. Basis Universal payloads, which the extension
+    /// normally carries, are not transcoded and always fall back.
     pub fn load_textures(&mut self) -> Result<(), GltfError> {
-        let images = self.decode_images()?;
-
-        self.texture_arcs = self
-            .document
-            .textures()
-            .map(|tex| {
-                let image_index = tex.source().index();
-                let image = &images[image_index];
-                let mut cpu_tex = CpuTexture::new(
-                    image.width,
-                    image.height,
-                    TextureFormat::Rgba8Unorm,
-                    image.data.clone(),
-                );
-                if let Some(name) = tex.name() {
-                    cpu_tex = cpu_tex.with_name(name);
+        let mut images: HashMap<usize, CpuTexture> = HashMap::new();
+        let mut texture_arcs = Vec::new();
+
+        for tex in self.document.textures() {
+            let mut decoded = None;
+            let mut error = None;
+            let candidates = texture_image_candidates(&tex);
+            for (i, &image_index) in candidates.iter().enumerate() {
+                if let Some(image) = images.get(&image_index) {
+                    decoded = Some(image.clone());
+                    break;
                 }
-                Arc::new(cpu_tex)
-            })
-            .collect();
+                match self.decode_image_at(image_index) {
+                    Ok(image) => {
+                        images.insert(image_index, image.clone());
+                        decoded = Some(image);
+                        break;
+                    }
+                    Err(e) => {
+                        match candidates.get(i + 1) {
+                            Some(fallback) => log::warn!(
+                                "texture {}: KHR_texture_basisu image {image_index} not loaded \
+                                 ({e}); Basis Universal transcoding is not supported, \
+                                 using image {fallback} instead",
+                                tex.index()
+                            ),
+                            None => {
+                                log::debug!("texture {}: image {image_index}: {e}", tex.index())
+                            }
+                        }
+                        error = Some(e);
+                    }
+                }
+            }
+
+            let mut cpu_tex = decoded.ok_or_else(|| {
+                error.unwrap_or_else(|| {
+                    GltfError::ImageDecode(format!("texture {} has no image source", tex.index()))
+                })
+            })?;
+            if let Some(name) = tex.name() {
+                cpu_tex = cpu_tex.with_name(name);
+            }
+            texture_arcs.push(Arc::new(cpu_tex));
+        }
 
+        self.texture_arcs = texture_arcs;
         Ok(())
     }
 
-    /// Decode all images to RGBA8.
-    fn decode_images(&self) -> Result<Vec<DecodedImage>, GltfError> {
-        let mut images = Vec::new();
-
-        for image in self.document.images() {
-            match image.source() {
-                gltf_dep::image::Source::View { view, mime_type } => {
-                    let buffer_index = view.buffer().index();
-                    let buffer_data = self.buffers.get(buffer_index).ok_or_else(|| {
-                        GltfError::BufferError(format!(
-                            "image buffer index {buffer_index} out of range"
-                        ))
-                    })?;
-                    let start = view.offset();
-                    let end = start + view.length();
-                    let image_bytes = &buffer_data[start..end];
-
-                    images.push(decode_image(image_bytes, mime_type)?);
-                }
-                gltf_dep::image::Source::Uri { uri, mime_type } => {
-                    if let Some(data) = parse_data_uri(uri) {
-                        let mime = mime_type.unwrap_or("image/png");
-                        images.push(decode_image(&data, mime)?);
-                    } else {
-                        return Err(GltfError::ImageDecode(format!(
-                            "external URI images not supported: {uri}"
-                        )));
-                    }
+    /// Decode the image at glTF image index `index`.
+    fn decode_image_at(&self, index: usize) -> Result<CpuTexture, GltfError> {
+        let image =
+            self.document.images().nth(index).ok_or_else(|| {
+                GltfError::ImageDecode(format!("image index {index} out of range"))
+            })?;
+
+        match image.source() {
+            gltf_dep::image::Source::View { view, mime_type } => {
+                let buffer_index = view.buffer().index();
+                let buffer_data = self.buffers.get(buffer_index).ok_or_else(|| {
+                    GltfError::BufferError(format!(
+                        "image buffer index {buffer_index} out of range"
+                    ))
+                })?;
+                let start = view.offset();
+                let end = start + view.length();
+                let image_bytes = &buffer_data[start..end];
+
+                decode_image(image_bytes, mime_type)
+            }
+            gltf_dep::image::Source::Uri { uri, mime_type } => {
+                if let Some(data) = parse_data_uri(uri) {
+                    let mime = mime_type.unwrap_or("image/png");
+                    decode_image(&data, mime)
+                } else {
+                    Err(GltfError::ImageDecode(format!(
+                        "external URI images not supported: {uri}"
+                    )))
                 }
             }
         }
-
-        Ok(images)
     }
 
     /// Load all samplers via user-provided callback.
@@ -519,6 +549,9 @@ impl LoadContext {
     ///
     /// Must be called after `load_meshes` so that `mesh_index_map` is populated.
     /// Takes ownership of document-level resources and embeds them into each scene.
+    /// Skin joints and animation targets are converted from glTF node indices
+    /// to scene-local depth-first pre-order indices; animation channels that
+    /// target nodes outside a scene are dropped from it.
     pub fn load_scenes(
         &self,
         mut meshes: Vec<CpuMesh>,
@@ -534,6 +567,17 @@ impl LoadContext {
             .enumerate()
             .map(|(i, scene)| {
                 let is_last = i == scene_count - 1;
+                let node_order = scene_node_order(&scene, self.document.nodes().len());
+                let scene_skins = if is_last {
+                    std::mem::take(&mut skins)
+                } else {
+                    skins.clone()
+                };
+                let scene_animations = if is_last {
+                    std::mem::take(&mut animations)
+                } else {
+                    animations.clone()
+                };
                 Scene {
                     name: scene.name().map(String::from),
                     nodes: scene
@@ -555,16 +599,14 @@ impl LoadContext {
                     } else {
                         cameras.clone()
                     },
-                    skins: if is_last {
-                        std::mem::take(&mut skins)
-                    } else {
-                        skins.clone()
-                    },
-                    animations: if is_last {
-                        std::mem::take(&mut animations)
-                    } else {
-                        animations.clone()
-                    },
+                    skins: scene_skins
+                        .into_iter()
+                        .map(|skin| remap_skin(skin, &node_order))
+                        .collect(),
+                    animations: scene_animations
+                        .into_iter()
+                        .map(|animation| remap_animation(animation, &node_order))
+                        .collect(),
                 }
             })
             .collect()
@@ -614,24 +656,92 @@ fn load_node(node: &gltf_dep::Node<'_>, mesh_index_map: &[Vec<usize>]) -> SceneN
     }
 }
 
-/// Decoded image data (internal, not exposed).
-struct DecodedImage {
-    data: Vec<u8>,
-    width: u32,
-    height: u32,
+/// Map glTF node indices to depth-first pre-order indices within `scene`.
+///
+/// Nodes that are not part of the scene map to `None`.
+fn scene_node_order(scene: &gltf_dep::Scene<'_>, node_count: usize) -> Vec<Option<usize>> {
+    fn visit(node: &gltf_dep::Node<'_>, order: &mut [Option<usize>], next: &mut usize) {
+        order[node.index()] = Some(*next);
+        *next += 1;
+        for child in node.children() {
+            visit(&child, order, next);
+        }
+    }
+
+    let mut order = vec![None; node_count];
+    let mut next = 0;
+    for node in scene.nodes() {
+        visit(&node, &mut order, &mut next);
+    }
+    order
+}
+
+/// Convert a skin's node references to scene-local indices.
+///
+/// Joints outside the scene become `usize::MAX` so that joint indices in
+/// vertex data stay aligned with the inverse bind matrices.
+fn remap_skin(mut skin: SceneSkin, node_order: &[Option<usize>]) -> SceneSkin {
+    for joint in &mut skin.joints {
+        *joint = node_order
+            .get(*joint)
+            .copied()
+            .flatten()
+            .unwrap_or(usize::MAX);
+    }
+    skin.skeleton = skin
+        .skeleton
+        .and_then(|s| node_order.get(s).copied().flatten());
+    skin
+}
+
+/// Convert animation targets to scene-local indices, dropping channels whose
+/// target is outside the scene.
+fn remap_animation(mut animation: Animation, node_order: &[Option<usize>]) -> Animation {
+    animation.channels.retain_mut(|channel| {
+        match node_order.get(channel.target_node).copied().flatten() {
+            Some(index) => {
+                channel.target_node = index;
+                true
+            }
+            None => false,
+        }
+    });
+    animation
+}
+
+/// Image indices to try for a texture, in order of preference: the
+/// `KHR_texture_basisu` KTX2 image, then the core `source` image.
+fn texture_image_candidates(tex: &gltf_dep::Texture<'_>) -> Vec<usize> {
+    let basisu = tex
+        .extension_value("KHR_texture_basisu")
+        .and_then(|ext| ext.get("source"))
+        .and_then(|source| source.as_u64())
+        .map(|index| index as usize);
+    basisu
+        .into_iter()
+        .chain(tex.source().map(|image| image.index()))
+        .collect()
 }
 
-fn decode_image(bytes: &[u8], _mime_type: &str) -> Result<DecodedImage, GltfError> {
+/// Decode an image: KTX2 and DDS containers keep their format and mip
+/// chain, everything else is decoded to RGBA8.
+fn decode_image(bytes: &[u8], mime_type: &str) -> Result<CpuTexture, GltfError> {
+    if mime_type == "image/ktx2" || TextureContainer::detect(bytes).is_some() {
+        return CpuTexture::from_container(bytes)
+            .map_err(|e| GltfError::ImageDecode(format!("{e}")));
+    }
+
     let img = image::load_from_memory(bytes).map_err(|e| GltfError::ImageDecode(format!("{e}")))?;
 
     let rgba = img.to_rgba8();
     let (width, height) = rgba.dimensions();
 
-    Ok(DecodedImage {
-        data: rgba.into_raw(),
+    Ok(CpuTexture::new(
         width,
         height,
-    })
+        TextureFormat::Rgba8Unorm,
+        rgba.into_raw(),
+    ))
 }
 
 /// Parse a data URI (e.g., `data:image/png;base64,...`) and return the decoded bytes.
@@ -294,3 +294,70 @@ fn test_roundtrip_animations() {
         }
     }
 }
+
+#[test]
+fn test_roundtrip_skin_and_animation_node_indices() {
+    use crate::mesh::{CpuMesh, VertexLayout};
+    use crate::scene::{
+        Animation, AnimationChannel, AnimationProperty, Interpolation, Scene, SceneSkin,
+    };
+
+    fn names(nodes: &[SceneNode], out: &mut Vec<Option<String>>) {
+        for node in nodes {
+            out.push(node.name.clone());
+            names(&node.children, out);
+        }
+    }
+
+    let triangle = || {
+        CpuMesh::new(VertexLayout::position_only())
+            .with_vertex_data(0, bytemuck::cast_slice(&[0.0f32; 9]).to_vec())
+    };
+
+    // The root holds two meshes, so the exporter adds an extra child node
+    // after its real children, shifting `tail` in the flattened node list.
+    let root = SceneNode::new()
+        .with_name("root")
+        .with_meshes(vec![0, 1])
+        .with_children(vec![
+            SceneNode::new()
+                .with_name("upper")
+                .with_children(vec![SceneNode::new().with_name("lower")]),
+        ]);
+    let mut skinned = SceneNode::new().with_name("tail");
+    skinned.skin = Some(0);
+
+    let mut scene = Scene::new().with_nodes(vec![root, skinned]);
+    scene.meshes = vec![triangle(), triangle()];
+    scene.skins = vec![SceneSkin {
+        name: None,
+        joints: vec![1, 2],
+        inverse_bind_matrices: vec![],
+        skeleton: Some(1),
+    }];
+    scene.animations = vec![Animation::new().with_channels(vec![AnimationChannel {
+        target_node: 3,
+        property: AnimationProperty::Translation,
+        interpolation: Interpolation::Linear,
+        timestamps: vec![0.0, 1.0],
+        values: vec![0.0; 6],
+    }])];
+
+    let glb_bytes = save_gltf(&[&scene], Some(0)).expect("export");
+    let reloaded = load_gltf(&glb_bytes, default_pbr_material, default_sampler_fn).expect("reload");
+    let rs = &reloaded.scenes[0];
+
+    let mut node_names = Vec::new();
+    names(&rs.nodes, &mut node_names);
+    let name_of = |i: usize| node_names[i].as_deref();
+
+    let skin = &rs.skins[0];
+    assert_eq!(name_of(skin.joints[0]), Some("upper"));
+    assert_eq!(name_of(skin.joints[1]), Some("lower"));
+    assert_eq!(skin.skeleton.and_then(name_of), Some("upper"));
+    assert_eq!(
+        name_of(rs.animations[0].channels[0].target_node),
+        Some("tail")
+    );
+    assert_eq!(rs.nodes[1].skin, Some(0));
+}
@@ -33,8 +33,11 @@ fn accessor_format(accessor: &gltf_dep::Accessor) -> Option<VertexAttributeForma
         (Dimensions::Vec2, DataType::F32) => Some(VertexAttributeFormat::Float2),
         (Dimensions::Vec3, DataType::F32) => Some(VertexAttributeFormat::Float3),
         (Dimensions::Vec4, DataType::F32) => Some(VertexAttributeFormat::Float4),
-        (Dimensions::Vec4, DataType::U8) => Some(VertexAttributeFormat::Unorm8x4),
-        (Dimensions::Vec4, DataType::U16) => Some(VertexAttributeFormat::Uint4),
+        (Dimensions::Vec4, DataType::U8) if accessor.normalized() => {
+            Some(VertexAttributeFormat::Unorm8x4)
+        }
+        // Integer data (joint indices) is widened to u32 by `interleave_vertices`
+        (Dimensions::Vec4, DataType::U8 | DataType::U16) => Some(VertexAttributeFormat::Uint4),
         _ => None,
     }
 }
@@ -247,6 +250,12 @@ pub(crate) fn interleave_vertices(
             accessor.data_type() == gltf_dep::accessor::DataType::U8 && accessor.normalized();
         let needs_u16_to_f32 =
             accessor.data_type() == gltf_dep::accessor::DataType::U16 && accessor.normalized();
+        let needs_widen_to_u32 = attr.format == VertexAttributeFormat::Uint4
+            && !accessor.normalized()
+            && matches!(
+                accessor.data_type(),
+                gltf_dep::accessor::DataType::U8 | gltf_dep::accessor::DataType::U16
+            );
 
         if needs_u8_to_f32 && attr.format == VertexAttributeFormat::Float4 {
             // Convert normalized u8x4 → float4
@@ -278,6 +287,26 @@ pub(crate) fn interleave_vertices(
                     }
                 }
             }
+        } else if needs_widen_to_u32 {
+            // Widen u8x4 / u16x4 joint indices → uint4
+            for v in 0..vertex_count as usize {
+                let src_offset = v * src_stride;
+                let dst_offset = v * stride as usize + attr.offset as usize;
+                if src_offset + element_size <= src_data.len() && dst_offset + 16 <= result.len() {
+                    for c in 0..4 {
+                        let val = if component_size == 1 {
+                            src_data[src_offset + c] as u32
+                        } else {
+                            u16::from_le_bytes([
+                                src_data[src_offset + c * 2],
+                                src_data[src_offset + c * 2 + 1],
+                            ]) as u32
+                        };
+                        result[dst_offset + c * 4..dst_offset + c * 4 + 4]
+                            .copy_from_slice(&val.to_le_bytes());
+                    }
+                }
+            }
         } else {
             // Direct copy (most common path: f32 data)
             for v in 0..vertex_count as usize {
@@ -181,6 +181,198 @@ impl Aabb {
             ],
         }
     }
+
+    /// Center point of the box.
+    pub fn center(&self) -> Vec3 {
+        Vec3::new(
+            (self.min[0] + self.max[0]) * 0.5,
+            (self.min[1] + self.max[1]) * 0.5,
+            (self.min[2] + self.max[2]) * 0.5,
+        )
+    }
+
+    /// Total area of the six faces (the cost metric of BVH builders).
+    pub fn surface_area(&self) -> f32 {
+        let dx = (self.max[0] - self.min[0]).max(0.0);
+        let dy = (self.max[1] - self.min[1]).max(0.0);
+        let dz = (self.max[2] - self.min[2]).max(0.0);
+        2.0 * (dx * dy + dy * dz + dz * dx)
+    }
+
+    /// Whether the two boxes overlap (touching counts as overlapping).
+    pub fn intersects(&self, other: &Aabb) -> bool {
+        (0..3).all(|i| self.min[i] <= other.max[i] && other.min[i] <= self.max[i])
+    }
+
+    /// Whether `other` lies entirely inside this box.
+    pub fn contains(&self, other: &Aabb) -> bool {
+        (0..3).all(|i| self.min[i] <= other.min[i] && other.max[i] <= self.max[i])
+    }
+
+    /// Transform the box by a 4x4 matrix, returning the axis-aligned box
+    /// enclosing its eight transformed corners.
+    pub fn transform(&self, matrix: &Mat4) -> Aabb {
+        let mut min = [f32::MAX; 3];
+        let mut max = [f32::MIN; 3];
+        for corner in 0..8 {
+            let local = Vec4::new(
+                if corner & 1 == 0 {
+                    self.min[0]
+                } else {
+                    self.max[0]
+                },
+                if corner & 2 == 0 {
+                    self.min[1]
+                } else {
+                    self.max[1]
+                },
+                if corner & 4 == 0 {
+                    self.min[2]
+                } else {
+                    self.max[2]
+                },
+                1.0,
+            );
+            let world = matrix * local;
+            for axis in 0..3 {
+                min[axis] = min[axis].min(world[axis]);
+                max[axis] = max[axis].max(world[axis]);
+            }
+        }
+        Aabb::new(min, max)
+    }
+}
+
+// ===== Frustum & Ray =====
+
+/// View frustum as six planes, used for visibility culling.
+///
+/// Each plane is stored as `(nx, ny, nz, d)` with a unit normal pointing
+/// into the frustum: a point `p` is inside when `dot(n, p) + d >= 0` for
+/// every plane.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Frustum {
+    /// Left, right, bottom, top, near and far planes.
+    pub planes: [Vec4; 6],
+}
+
+impl Frustum {
+    /// Extract the frustum of a view-projection matrix with a `[0, 1]`
+    /// depth range, as built from [`perspective_rh`] or [`orthographic_rh`].
+    pub fn from_view_projection(view_projection: &Mat4) -> Self {
+        let row = |i: usize| view_projection.row(i).transpose();
+        let (x, y, z, w) = (row(0), row(1), row(2), row(3));
+        let planes = [w + x, w - x, w + y, w - y, z, w - z].map(|plane| {
+            let length = plane.xyz().norm();
+            if length > 0.0 { plane / length } else { plane }
+        });
+        Self { planes }
+    }
+
+    /// Whether any part of `aabb` may be inside the frustum.
+    ///
+    /// Conservative: boxes near a frustum corner can be reported visible
+    /// even though they are outside.
+    pub fn intersects_aabb(&self, aabb: &Aabb) -> bool {
+        self.planes.iter().all(|plane| {
+            // Corner furthest along the plane normal.
+            let p = Vec3::new(
+                if plane.x >= 0.0 {
+                    aabb.max[0]
+                } else {
+                    aabb.min[0]
+                },
+                if plane.y >= 0.0 {
+                    aabb.max[1]
+                } else {
+                    aabb.min[1]
+                },
+                if plane.z >= 0.0 {
+                    aabb.max[2]
+                } else {
+                    aabb.min[2]
+                },
+            );
+            plane.xyz().dot(&p) + plane.w >= 0.0
+        })
+    }
+
+    /// Whether `aabb` lies entirely inside the frustum.
+    pub fn contains_aabb(&self, aabb: &Aabb) -> bool {
+        self.planes.iter().all(|plane| {
+            // Corner furthest against the plane normal.
+            let n = Vec3::new(
+                if plane.x >= 0.0 {
+                    aabb.min[0]
+                } else {
+                    aabb.max[0]
+                },
+                if plane.y >= 0.0 {
+                    aabb.min[1]
+                } else {
+                    aabb.max[1]
+                },
+                if plane.z >= 0.0 {
+                    aabb.min[2]
+                } else {
+                    aabb.max[2]
+                },
+            );
+            plane.xyz().dot(&n) + plane.w >= 0.0
+        })
+    }
+}
+
+/// Half-line from `origin` along a unit `direction`, used for picking.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ray {
+    /// Start point.
+    pub origin: Vec3,
+    /// Unit direction.
+    pub direction: Vec3,
+}
+
+impl Ray {
+    /// Create a ray, normalizing `direction`.
+    pub fn new(origin: Vec3, direction: Vec3) -> Self {
+        Self {
+            origin,
+            direction: direction.normalize(),
+        }
+    }
+
+    /// Ray through a point in normalized device coordinates (`-1..1`, y up)
+    /// from the near to the far plane of a `[0, 1]` depth range projection.
+    pub fn from_ndc(inverse_view_projection: &Mat4, ndc: [f32; 2]) -> Self {
+        let unproject = |depth: f32| {
+            let p = inverse_view_projection * Vec4::new(ndc[0], ndc[1], depth, 1.0);
+            p.xyz() / p.w
+        };
+        let near = unproject(0.0);
+        let far = unproject(1.0);
+        Self::new(near, far - near)
+    }
+
+    /// Point at distance `t` along the ray.
+    pub fn at(&self, t: f32) -> Vec3 {
+        self.origin + self.direction * t
+    }
+
+    /// Distance at which the ray enters `aabb` (zero if it starts inside),
+    /// or `None` if it misses.
+    pub fn intersect_aabb(&self, aabb: &Aabb) -> Option<f32> {
+        let mut t_min = 0.0f32;
+        let mut t_max = f32::INFINITY;
+        for axis in 0..3 {
+            let inv = 1.0 / self.direction[axis];
+            let t0 = (aabb.min[axis] - self.origin[axis]) * inv;
+            let t1 = (aabb.max[axis] - self.origin[axis]) * inv;
+            // `min`/`max` ignore the NaN of a ray lying in a slab's plane.
+            t_min = t_min.max(t0.min(t1));
+            t_max = t_max.min(t0.max(t1));
+        }
+        (t_min <= t_max).then_some(t_min)
+    }
 }
 
 // ===== Physics math (precision-aware) =====
@@ -302,4 +494,79 @@ mod tests {
         assert_eq!(cols[2], [0.0, 0.0, 1.0, 0.0]);
         assert_eq!(cols[3], [0.0, 0.0, 0.0, 1.0]);
     }
+
+    #[test]
+    fn aabb_transform_rotated() {
+        let aabb = Aabb::new([-1.0, -1.0, -2.0], [1.0, 1.0, 2.0]);
+        let m = mat4_from_scale_rotation_translation(
+            Vec3::new(1.0, 1.0, 1.0),
+            quat_from_rotation_y(FRAC_PI_2),
+            Vec3::new(10.0, 0.0, 0.0),
+        );
+        let world = aabb.transform(&m);
+        assert!((world.min[0] - 8.0).abs() < 1e-5);
+        assert!((world.max[0] - 12.0).abs() < 1e-5);
+        assert!((world.min[2] + 1.0).abs() < 1e-5);
+        assert!((world.max[2] - 1.0).abs() < 1e-5);
+        assert!(world.contains(&Aabb::new([9.0, 0.0, 0.0], [11.0, 0.5, 0.5])));
+        assert!(!world.intersects(&Aabb::new([13.0, 0.0, 0.0], [14.0, 1.0, 1.0])));
+    }
+
+    #[test]
+    fn frustum_culls_boxes() {
+        let proj = perspective_rh(FRAC_PI_2, 1.0, 0.1, 100.0);
+        let view = look_at_rh(
+            &Vec3::zeros(),
+            &Vec3::new(0.0, 0.0, -1.0),
+            &Vec3::new(0.0, 1.0, 0.0),
+        );
+        let frustum = Frustum::from_view_projection(&(proj * view));
+
+        let ahead = Aabb::new([-1.0, -1.0, -11.0], [1.0, 1.0, -9.0]);
+        assert!(frustum.intersects_aabb(&ahead));
+        assert!(frustum.contains_aabb(&ahead));
+
+        let behind = Aabb::new([-1.0, -1.0, 9.0], [1.0, 1.0, 11.0]);
+        assert!(!frustum.intersects_aabb(&behind));
+
+        let beyond_far = Aabb::new([-1.0, -1.0, -202.0], [1.0, 1.0, -200.0]);
+        assert!(!frustum.intersects_aabb(&beyond_far));
+
+        let left = Aabb::new([-30.0, -1.0, -11.0], [-20.0, 1.0, -9.0]);
+        assert!(!frustum.intersects_aabb(&left));
+
+        let straddling = Aabb::new([-20.0, -1.0, -11.0], [0.0, 1.0, -9.0]);
+        assert!(frustum.intersects_aabb(&straddling));
+        assert!(!frustum.contains_aabb(&straddling));
+    }
+
+    #[test]
+    fn ray_aabb_intersection() {
+        let aabb = Aabb::new([-1.0, -1.0, -1.0], [1.0, 1.0, 1.0]);
+        let hit = Ray::new(Vec3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 0.0, -2.0));
+        assert_eq!(hit.intersect_aabb(&aabb), Some(4.0));
+
+        let inside = Ray::new(Vec3::zeros(), Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(inside.intersect_aabb(&aabb), Some(0.0));
+
+        let away = Ray::new(Vec3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 0.0, 1.0));
+        assert_eq!(away.intersect_aabb(&aabb), None);
+
+        let beside = Ray::new(Vec3::new(3.0, 0.0, 5.0), Vec3::new(0.0, 0.0, -1.0));
+        assert_eq!(beside.intersect_aabb(&aabb), None);
+    }
+
+    #[test]
+    fn ray_from_ndc_center() {
+        let proj = perspective_rh(FRAC_PI_2, 1.0, 0.1, 100.0);
+        let view = look_at_rh(
+            &Vec3::new(0.0, 0.0, 5.0),
+            &Vec3::zeros(),
+            &Vec3::new(0.0, 1.0, 0.0),
+        );
+        let inverse = (proj * view).try_inverse().unwrap();
+        let ray = Ray::from_ndc(&inverse, [0.0, 0.0]);
+        assert!((ray.direction - Vec3::new(0.0, 0.0, -1.0)).norm() < 1e-4);
+        assert!((ray.origin - Vec3::new(0.0, 0.0, 4.9)).norm() < 1e-3);
+    }
 }
@@ -8,7 +8,7 @@
 
 use std::sync::Arc;
 
-use super::layout::VertexLayout;
+use super::layout::{VertexAttributeFormat, VertexLayout};
 
 /// Primitive topology describing how vertices are assembled into primitives.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
@@ -392,6 +392,72 @@ impl CpuMesh {
         }
         desc
     }
+
+    /// Copy this mesh's vertex data into a different vertex layout.
+    ///
+    /// Attributes are matched by semantic. Matching attributes with the same
+    /// format are copied as-is, `Unorm8x4` is widened to `Float4`, and
+    /// attributes that are missing from this mesh (or have an incompatible
+    /// format) are zero-filled. Indices, topology, material and label are
+    /// kept.
+    ///
+    /// Useful for bringing loaded meshes into a fixed layout such as
+    /// [`VertexLayout::animated_pbr`].
+    pub fn with_layout(&self, layout: Arc<VertexLayout>) -> CpuMesh {
+        let count = self.vertex_count as usize;
+        let mut vertex_buffers: Vec<Vec<u8>> = layout
+            .buffers
+            .iter()
+            .map(|b| vec![0u8; b.stride as usize * count])
+            .collect();
+
+        for dst in &layout.attributes {
+            let Some(src) = self.layout.get_attribute(dst.semantic) else {
+                continue;
+            };
+            let Some(src_data) = self.vertex_buffers.get(src.buffer_index as usize) else {
+                continue;
+            };
+            let src_stride = self.layout.buffer_stride(src.buffer_index as usize) as usize;
+            let dst_stride = layout.buffer_stride(dst.buffer_index as usize) as usize;
+            let dst_data = &mut vertex_buffers[dst.buffer_index as usize];
+
+            for v in 0..count {
+                let s = v * src_stride + src.offset as usize;
+                let d = v * dst_stride + dst.offset as usize;
+                if s + src.format.size() > src_data.len() || d + dst.format.size() > dst_data.len()
+                {
+                    break;
+                }
+                match (src.format, dst.format) {
+                    (a, b) if a == b => {
+                        let size = a.size();
+                        dst_data[d..d + size].copy_from_slice(&src_data[s..s + size]);
+                    }
+                    (VertexAttributeFormat::Unorm8x4, VertexAttributeFormat::Float4) => {
+                        for c in 0..4 {
+                            let value = src_data[s + c] as f32 / 255.0;
+                            dst_data[d + c * 4..d + c * 4 + 4]
+                                .copy_from_slice(&value.to_le_bytes());
+                        }
+                    }
+                    _ => break,
+                }
+            }
+        }
+
+        CpuMesh {
+            layout,
+            topology: self.topology,
+            vertex_buffers,
+            vertex_count: self.vertex_count,
+            index_data: self.index_data.clone(),
+            index_format: self.index_format,
+            index_count: self.index_count,
+            material: self.material,
+            label: self.label.clone(),
+        }
+    }
 }
 
 impl std::fmt::Debug for CpuMesh {
@@ -508,4 +574,48 @@ mod tests {
         assert_eq!(desc.index_format, Some(IndexFormat::Uint32));
         assert_eq!(desc.label.as_deref(), Some("desc_test"));
     }
+
+    #[test]
+    fn test_cpu_mesh_with_layout_repacks_by_semantic() {
+        use crate::mesh::{VertexAttribute, VertexAttributeSemantic, VertexBufferLayout};
+
+        let source = Arc::new(
+            VertexLayout::new()
+                .with_buffer(VertexBufferLayout::new(32))
+                .with_attribute(VertexAttribute::position(0))
+                .with_attribute(VertexAttribute::new(
+                    VertexAttributeSemantic::Weights,
+                    VertexAttributeFormat::Unorm8x4,
+                    12,
+                    0,
+                ))
+                .with_attribute(VertexAttribute::joints(16)),
+        );
+        let mut data = Vec::new();
+        for v in 0..2u32 {
+            data.extend_from_slice(bytemuck::cast_slice(&[v as f32, 1.0, 2.0]));
+            data.extend_from_slice(&[255, 0, 0, 0]);
+            data.extend_from_slice(bytemuck::cast_slice(&[v, 1, 2, 3]));
+        }
+        let mesh = CpuMesh::new(source)
+            .with_vertex_data(0, data)
+            .with_indices_u16(&[0, 1, 0]);
+
+        let converted = mesh.with_layout(VertexLayout::animated_pbr());
+        assert_eq!(converted.vertex_count(), 2);
+        assert_eq!(converted.buffer_count(), 3);
+        assert_eq!(converted.index_count(), 3);
+
+        // Missing texcoords are zero-filled
+        assert_eq!(converted.vertex_buffer_data(0).unwrap(), &[0u8; 16]);
+
+        let geometry: &[f32] = bytemuck::cast_slice(converted.vertex_buffer_data(1).unwrap());
+        assert_eq!(&geometry[10..13], &[1.0, 1.0, 2.0]);
+
+        let skin = converted.vertex_buffer_data(2).unwrap();
+        let joints: &[u32] = bytemuck::cast_slice(&skin[32..48]);
+        let weights: &[f32] = bytemuck::cast_slice(&skin[48..64]);
+        assert_eq!(joints, &[1, 1, 2, 3]);
+        assert_eq!(weights, &[1.0, 0.0, 0.0, 0.0]);
+    }
 }
@@ -72,6 +72,14 @@ pub enum VertexAttributeSemantic {
     Joints,
     /// Bone weights for skinning (typically float4).
     Weights,
+    /// Column 0 of a per-instance model matrix (float4).
+    InstanceTransform0,
+    /// Column 1 of a per-instance model matrix (float4).
+    InstanceTransform1,
+    /// Column 2 of a per-instance model matrix (float4).
+    InstanceTransform2,
+    /// Column 3 of a per-instance model matrix (float4).
+    InstanceTransform3,
 }
 
 impl VertexAttributeSemantic {
@@ -86,8 +94,25 @@ impl VertexAttributeSemantic {
             Self::Color => 5,
             Self::Joints => 6,
             Self::Weights => 7,
+            Self::InstanceTransform0 => 8,
+            Self::InstanceTransform1 => 9,
+            Self::InstanceTransform2 => 10,
+            Self::InstanceTransform3 => 11,
         }
     }
+
+    /// The four per-instance model matrix columns, in order.
+    pub const INSTANCE_TRANSFORM: [Self; 4] = [
+        Self::InstanceTransform0,
+        Self::InstanceTransform1,
+        Self::InstanceTransform2,
+        Self::InstanceTransform3,
+    ];
+
+    /// Whether this semantic is read per instance rather than per vertex.
+    pub fn is_per_instance(&self) -> bool {
+        Self::INSTANCE_TRANSFORM.contains(self)
+    }
 }
 
 /// Format of a vertex attribute.
@@ -419,6 +444,38 @@ impl VertexLayout {
             .collect()
     }
 
+    /// Stride of the per-instance buffer added by [`instanced`](Self::instanced):
+    /// one column-major 4x4 float matrix.
+    pub const INSTANCE_TRANSFORM_STRIDE: u32 = 64;
+
+    /// Copy of this layout with an extra per-instance buffer holding a model
+    /// matrix per instance (the `InstanceTransform*` semantics).
+    ///
+    /// Materials built with the instanced layout draw meshes of this layout
+    /// with the instance buffer bound after the mesh's own buffers.
+    pub fn instanced(&self) -> Self {
+        let buffer_index = self.buffers.len() as u32;
+        let mut layout = self.clone().with_buffer(VertexBufferLayout::per_instance(
+            Self::INSTANCE_TRANSFORM_STRIDE,
+        ));
+        for (column, semantic) in VertexAttributeSemantic::INSTANCE_TRANSFORM
+            .into_iter()
+            .enumerate()
+        {
+            layout = layout.with_attribute(VertexAttribute::new(
+                semantic,
+                VertexAttributeFormat::Float4,
+                column as u32 * 16,
+                buffer_index,
+            ));
+        }
+        layout.label = self
+            .label
+            .as_ref()
+            .map(|label| format!("{label}_instanced"));
+        layout
+    }
+
     /// Validate the layout (check that all attributes reference valid buffers).
     pub fn validate(&self) -> Result<(), String> {
         for attr in &self.attributes {
@@ -599,6 +656,31 @@ mod tests {
         assert_eq!(buffer1_attrs.len(), 2);
     }
 
+    #[test]
+    fn test_instanced_layout() {
+        let layout = VertexLayout::position_normal().instanced();
+        assert_eq!(layout.buffer_count(), 2);
+        assert_eq!(layout.buffers[1].step_mode, VertexStepMode::Instance);
+        assert_eq!(
+            layout.buffer_stride(1),
+            VertexLayout::INSTANCE_TRANSFORM_STRIDE
+        );
+        assert!(layout.validate().is_ok());
+        assert_eq!(layout.label.as_deref(), Some("position_normal_instanced"));
+
+        let column = layout
+            .get_attribute(VertexAttributeSemantic::InstanceTransform3)
+            .unwrap();
+        assert_eq!(column.offset, 48);
+        assert_eq!(column.buffer_index, 1);
+        assert!(column.semantic.is_per_instance());
+        assert!(!VertexAttributeSemantic::Position.is_per_instance());
+
+        // The mesh alone lacks the instance attributes; with them it matches.
+        assert!(!layout.is_compatible_with(&VertexLayout::position_normal()));
+        assert!(layout.is_compatible_with(&layout));
+    }
+
     #[test]
     fn test_vertex_layout_validation() {
         let invalid_layout = VertexLayout::new()
@@ -0,0 +1,269 @@
+//! Keyframe sampling for [`Animation`] channels.
+
+use super::{Animation, AnimationChannel, AnimationProperty, Interpolation};
+
+impl Animation {
+    /// Length of the animation in seconds (the last keyframe of any channel).
+    pub fn duration(&self) -> f32 {
+        self.channels
+            .iter()
+            .filter_map(|c| c.timestamps.last().copied())
+            .fold(0.0, f32::max)
+    }
+}
+
+impl AnimationProperty {
+    /// Number of floats per keyframe value, or `None` for morph target
+    /// weights, whose count depends on the mesh.
+    pub fn components(self) -> Option<usize> {
+        match self {
+            Self::Translation | Self::Scale => Some(3),
+            Self::Rotation => Some(4),
+            Self::MorphTargetWeights => None,
+        }
+    }
+}
+
+impl AnimationChannel {
+    /// Number of floats in one sampled value.
+    ///
+    /// For cubic spline channels the in/out tangents stored alongside each
+    /// keyframe are not counted.
+    pub fn stride(&self) -> usize {
+        let keys = self.timestamps.len();
+        if keys == 0 {
+            return 0;
+        }
+        let per_key = match self.interpolation {
+            Interpolation::CubicSpline => 3,
+            Interpolation::Linear | Interpolation::Step => 1,
+        };
+        self.values.len() / (keys * per_key)
+    }
+
+    /// Length of the channel in seconds.
+    pub fn duration(&self) -> f32 {
+        self.timestamps.last().copied().unwrap_or(0.0)
+    }
+
+    /// Sample the channel at `time` (seconds) into `out`.
+    ///
+    /// `out` must hold at least [`stride`](Self::stride) floats. Times
+    /// outside the keyframe range clamp to the first or last keyframe.
+    /// Rotations are spherically interpolated and returned normalized.
+    /// Does nothing if the channel has no keyframes.
+    pub fn sample(&self, time: f32, out: &mut [f32]) {
+        let stride = self.stride();
+        let keys = self.timestamps.len();
+        if stride == 0 {
+            return;
+        }
+        let out = &mut out[..stride];
+
+        let next = self.timestamps.partition_point(|&t| t <= time);
+        if next == 0 || next == keys {
+            let key = if next == 0 { 0 } else { keys - 1 };
+            out.copy_from_slice(self.value(key, stride));
+            return;
+        }
+
+        let prev = next - 1;
+        let t0 = self.timestamps[prev];
+        let dt = self.timestamps[next] - t0;
+        let u = if dt > 0.0 { (time - t0) / dt } else { 0.0 };
+        let is_rotation = self.property == AnimationProperty::Rotation;
+
+        match self.interpolation {
+            Interpolation::Step => out.copy_from_slice(self.value(prev, stride)),
+            Interpolation::Linear => {
+                let a = self.value(prev, stride);
+                let b = self.value(next, stride);
+                if is_rotation && stride == 4 {
+                    slerp(a, b, u, out);
+                } else {
+                    for i in 0..stride {
+                        out[i] = a[i] + (b[i] - a[i]) * u;
+                    }
+                }
+            }
+            Interpolation::CubicSpline => {
+                // Keyframes are stored as [in_tangent, value, out_tangent].
+                let v0 = &self.values[(prev * 3 + 1) * stride..][..stride];
+                let b0 = &self.values[(prev * 3 + 2) * stride..][..stride];
+                let a1 = &self.values[(next * 3) * stride..][..stride];
+                let v1 = &self.values[(next * 3 + 1) * stride..][..stride];
+
+                let u2 = u * u;
+                let u3 = u2 * u;
+                let h00 = 2.0 * u3 - 3.0 * u2 + 1.0;
+                let h10 = (u3 - 2.0 * u2 + u) * dt;
+                let h01 = -2.0 * u3 + 3.0 * u2;
+                let h11 = (u3 - u2) * dt;
+                for i in 0..stride {
+                    out[i] = h00 * v0[i] + h10 * b0[i] + h01 * v1[i] + h11 * a1[i];
+                }
+                if is_rotation {
+                    normalize(out);
+                }
+            }
+        }
+    }
+
+    /// The keyframe value at `key`, skipping cubic spline tangents.
+    fn value(&self, key: usize, stride: usize) -> &[f32] {
+        let index = match self.interpolation {
+            Interpolation::CubicSpline => key * 3 + 1,
+            Interpolation::Linear | Interpolation::Step => key,
+        };
+        &self.values[index * stride..][..stride]
+    }
+}
+
+/// Spherical interpolation between two `[x, y, z, w]` quaternions along the
+/// shortest arc.
+fn slerp(a: &[f32], b: &[f32], t: f32, out: &mut [f32]) {
+    let mut dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let sign = if dot < 0.0 {
+        dot = -dot;
+        -1.0
+    } else {
+        1.0
+    };
+
+    // Nearly parallel: fall back to normalized lerp to avoid dividing by ~0
+    let (wa, wb) = if dot > 0.9995 {
+        (1.0 - t, t)
+    } else {
+        let theta = dot.acos();
+        let sin_theta = theta.sin();
+        (
+            ((1.0 - t) * theta).sin() / sin_theta,
+            (t * theta).sin() / sin_theta,
+        )
+    };
+
+    for i in 0..4 {
+        out[i] = wa * a[i] + sign * wb * b[i];
+    }
+    normalize(out);
+}
+
+fn normalize(v: &mut [f32]) {
+    let len = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if len > f32::EPSILON {
+        v.iter_mut().for_each(|x| *x /= len);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn channel(
+        property: AnimationProperty,
+        interpolation: Interpolation,
+        timestamps: Vec<f32>,
+        values: Vec<f32>,
+    ) -> AnimationChannel {
+        AnimationChannel {
+            target_node: 0,
+            property,
+            interpolation,
+            timestamps,
+            values,
+        }
+    }
+
+    fn sample(channel: &AnimationChannel, time: f32) -> Vec<f32> {
+        let mut out = vec![0.0; channel.stride()];
+        channel.sample(time, &mut out);
+        out
+    }
+
+    fn assert_close(a: &[f32], b: &[f32]) {
+        for (x, y) in a.iter().zip(b) {
+            assert!((x - y).abs() < 1e-4, "{a:?} != {b:?}");
+        }
+    }
+
+    #[test]
+    fn linear_translation_and_clamping() {
+        let c = channel(
+            AnimationProperty::Translation,
+            Interpolation::Linear,
+            vec![1.0, 3.0],
+            vec![0.0, 0.0, 0.0, 2.0, 4.0, 6.0],
+        );
+        assert_eq!(c.stride(), 3);
+        assert_close(&sample(&c, 0.0), &[0.0, 0.0, 0.0]);
+        assert_close(&sample(&c, 2.0), &[1.0, 2.0, 3.0]);
+        assert_close(&sample(&c, 5.0), &[2.0, 4.0, 6.0]);
+    }
+
+    #[test]
+    fn step_holds_previous_key() {
+        let c = channel(
+            AnimationProperty::Scale,
+            Interpolation::Step,
+            vec![0.0, 1.0],
+            vec![1.0, 1.0, 1.0, 2.0, 2.0, 2.0],
+        );
+        assert_close(&sample(&c, 0.99), &[1.0, 1.0, 1.0]);
+        assert_close(&sample(&c, 1.0), &[2.0, 2.0, 2.0]);
+    }
+
+    #[test]
+    fn linear_rotation_slerps_shortest_arc() {
+        let half = std::f32::consts::FRAC_1_SQRT_2;
+        // identity -> 90 degrees around Y, stored with a negated w so the
+        // short path requires a hemisphere flip
+        let c = channel(
+            AnimationProperty::Rotation,
+            Interpolation::Linear,
+            vec![0.0, 1.0],
+            vec![0.0, 0.0, 0.0, 1.0, 0.0, -half, 0.0, -half],
+        );
+        let q = sample(&c, 0.5);
+        let angle = std::f32::consts::FRAC_PI_8;
+        assert_close(&q, &[0.0, angle.sin(), 0.0, angle.cos()]);
+    }
+
+    #[test]
+    fn cubic_spline_hits_keys_and_uses_tangents() {
+        // [in, value, out] per key, zero tangents -> smoothstep
+        let c = channel(
+            AnimationProperty::Translation,
+            Interpolation::CubicSpline,
+            vec![0.0, 2.0],
+            vec![
+                0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, //
+                0.0, 0.0, 0.0, 4.0, 0.0, 0.0, 0.0, 0.0, 0.0,
+            ],
+        );
+        assert_eq!(c.stride(), 3);
+        assert_close(&sample(&c, 0.0), &[0.0, 0.0, 0.0]);
+        assert_close(&sample(&c, 1.0), &[2.0, 0.0, 0.0]);
+        assert_close(&sample(&c, 0.5), &[4.0 * 0.15625, 0.0, 0.0]);
+        assert_close(&sample(&c, 2.0), &[4.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn duration_is_last_keyframe() {
+        let animation = Animation::new().with_channels(vec![
+            channel(
+                AnimationProperty::Scale,
+                Interpolation::Step,
+                vec![0.0, 1.5],
+                vec![0.0; 6],
+            ),
+            channel(
+                AnimationProperty::Translation,
+                Interpolation::Linear,
+                vec![0.0, 2.5],
+                vec![0.0; 6],
+            ),
+        ]);
+        assert_eq!(animation.duration(), 2.5);
+        assert_eq!(Animation::new().duration(), 0.0);
+    }
+}
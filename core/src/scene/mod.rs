@@ -10,6 +10,8 @@
 //! - [`SceneSkin`] — Skeletal animation skin
 //! - [`Animation`] / [`AnimationChannel`] — Keyframe animations
 
+mod animation;
+
 use std::sync::Arc;
 
 use crate::material::CpuMaterialInstance;
@@ -289,11 +291,13 @@ pub enum CameraProjection {
 pub struct SceneSkin {
     /// Skin name.
     pub name: Option<String>,
-    /// Joint node indices (referencing nodes in the scene).
+    /// Joint node indices, in depth-first pre-order over the scene's node
+    /// trees (the same order [`Scene::nodes`] is walked in). `usize::MAX`
+    /// marks a joint that is not part of this scene.
     pub joints: Vec<usize>,
     /// Inverse bind matrices (column-major 4x4, one per joint).
     pub inverse_bind_matrices: Vec<[f32; 16]>,
-    /// Root skeleton node index, if specified.
+    /// Root skeleton node index (same indexing as `joints`), if specified.
     pub skeleton: Option<usize>,
 }
 
@@ -302,7 +306,8 @@ pub struct SceneSkin {
 /// An animation containing one or more channels.
 ///
 /// Each channel targets a specific node property (translation, rotation,
-/// scale, or morph weights) with keyframed data.
+/// scale, or morph weights) with keyframed data. Use
+/// [`AnimationChannel::sample`] to evaluate a channel at a point in time.
 #[derive(Debug, Clone)]
 pub struct Animation {
     /// Animation name.
@@ -344,7 +349,8 @@ impl Default for Animation {
 /// A single animation channel targeting a node property.
 #[derive(Debug, Clone)]
 pub struct AnimationChannel {
-    /// Target node index in the scene.
+    /// Target node index, in depth-first pre-order over the scene's node
+    /// trees (see [`SceneSkin::joints`]).
     pub target_node: usize,
     /// The property being animated.
     pub property: AnimationProperty,
@@ -0,0 +1,354 @@
+//! DirectDraw Surface (DDS) container decoding.
+//!
+//! Supports the legacy header (DXTn/ATI FourCCs, common RGBA masks and
+//! D3DFMT float codes) and the DX10 header extension with DXGI formats.
+//! DDS stores each array layer (or cube face) with its full mip chain; the
+//! decoder reorders the data into the mip-major layout of [`CpuTexture`].
+
+use super::{CpuTexture, TextureDecodeError, TextureDimension, TextureFormat};
+
+/// File magic: `DDS `.
+pub(super) const MAGIC: &[u8] = b"DDS ";
+
+/// Size of the magic and the legacy header.
+const HEADER_SIZE: usize = 128;
+/// Size of the DX10 header extension.
+const DX10_HEADER_SIZE: usize = 20;
+
+const DDSD_MIPMAPCOUNT: u32 = 0x2_0000;
+const DDPF_FOURCC: u32 = 0x4;
+const DDPF_RGB: u32 = 0x40;
+const DDPF_LUMINANCE: u32 = 0x2_0000;
+const DDSCAPS2_CUBEMAP: u32 = 0x200;
+const DDSCAPS2_VOLUME: u32 = 0x20_0000;
+
+const D3D10_RESOURCE_DIMENSION_TEXTURE1D: u32 = 2;
+const D3D10_RESOURCE_DIMENSION_TEXTURE3D: u32 = 4;
+const D3D10_RESOURCE_MISC_TEXTURECUBE: u32 = 0x4;
+
+/// Decode a DDS file.
+pub(super) fn decode(bytes: &[u8]) -> Result<CpuTexture, TextureDecodeError> {
+    if !bytes.starts_with(MAGIC) {
+        return Err(invalid("missing DDS magic"));
+    }
+    if bytes.len() < HEADER_SIZE || read_u32(bytes, 4) != 124 {
+        return Err(invalid("truncated or malformed header"));
+    }
+
+    let flags = read_u32(bytes, 8);
+    let height = read_u32(bytes, 12).max(1);
+    let width = read_u32(bytes, 16);
+    let depth = read_u32(bytes, 24).max(1);
+    let mip_level_count = if flags & DDSD_MIPMAPCOUNT != 0 {
+        read_u32(bytes, 28).max(1)
+    } else {
+        1
+    };
+    let pf_flags = read_u32(bytes, 80);
+    let four_cc = &bytes[84..88];
+    let caps2 = read_u32(bytes, 112);
+    if width == 0 {
+        return Err(invalid("zero width"));
+    }
+
+    let (format, dimension, array_size, data_offset) =
+        if pf_flags & DDPF_FOURCC != 0 && four_cc == b"DX10" {
+            if bytes.len() < HEADER_SIZE + DX10_HEADER_SIZE {
+                return Err(invalid("truncated DX10 header"));
+            }
+            let dxgi_format = read_u32(bytes, 128);
+            let resource_dimension = read_u32(bytes, 132);
+            let misc_flags = read_u32(bytes, 136);
+            let array_size = read_u32(bytes, 140).max(1);
+            let format = format_from_dxgi(dxgi_format).ok_or_else(|| {
+                TextureDecodeError::Unsupported(format!("unsupported DXGI format {dxgi_format}"))
+            })?;
+            let cube = misc_flags & D3D10_RESOURCE_MISC_TEXTURECUBE != 0;
+            let dimension = match resource_dimension {
+                D3D10_RESOURCE_DIMENSION_TEXTURE1D if array_size > 1 => TextureDimension::D1Array,
+                D3D10_RESOURCE_DIMENSION_TEXTURE1D => TextureDimension::D1,
+                D3D10_RESOURCE_DIMENSION_TEXTURE3D => TextureDimension::D3,
+                _ if cube && array_size > 1 => TextureDimension::CubeArray,
+                _ if cube => TextureDimension::Cube,
+                _ if array_size > 1 => TextureDimension::D2Array,
+                _ => TextureDimension::D2,
+            };
+            (
+                format,
+                dimension,
+                array_size,
+                HEADER_SIZE + DX10_HEADER_SIZE,
+            )
+        } else {
+            let format = legacy_format(bytes, pf_flags, four_cc)?;
+            let dimension = if caps2 & DDSCAPS2_CUBEMAP != 0 {
+                TextureDimension::Cube
+            } else if caps2 & DDSCAPS2_VOLUME != 0 {
+                TextureDimension::D3
+            } else {
+                TextureDimension::D2
+            };
+            (format, dimension, 1, HEADER_SIZE)
+        };
+
+    let mut texture = CpuTexture::new(width, height, format, Vec::new())
+        .with_dimension(dimension)
+        .with_depth(match dimension {
+            TextureDimension::D3 => depth,
+            _ => array_size,
+        })
+        .with_mip_levels(mip_level_count);
+
+    if mip_level_count > texture.max_mip_levels() {
+        return Err(invalid(&format!(
+            "{mip_level_count} mip levels exceed the full chain of {}",
+            texture.max_mip_levels()
+        )));
+    }
+
+    // Gather the layer-major images into mip-major order.
+    let layer_count = texture.layer_count() as usize;
+    let (level_sizes, total_size) = texture
+        .checked_mip_level_sizes()
+        .ok_or_else(|| invalid("image size overflows"))?;
+    let level_sizes: Vec<usize> = level_sizes.iter().map(|size| size / layer_count).collect();
+    let layer_size: usize = level_sizes.iter().sum();
+    let payload = &bytes[data_offset..];
+    if payload.len() < total_size {
+        return Err(invalid(&format!(
+            "holds {} bytes of image data, expected {total_size}",
+            payload.len(),
+        )));
+    }
+
+    let mut data = Vec::with_capacity(total_size);
+    let mut level_offset = 0;
+    for &level_size in &level_sizes {
+        for layer in 0..layer_count {
+            let start = layer * layer_size + level_offset;
+            data.extend_from_slice(&payload[start..start + level_size]);
+        }
+        level_offset += level_size;
+    }
+    texture.data = data;
+    Ok(texture)
+}
+
+/// Determine the format of a file without the DX10 header extension.
+fn legacy_format(
+    bytes: &[u8],
+    pf_flags: u32,
+    four_cc: &[u8],
+) -> Result<TextureFormat, TextureDecodeError> {
+    use TextureFormat::*;
+
+    if pf_flags & DDPF_FOURCC != 0 {
+        let format = match four_cc {
+            b"DXT1" => Some(Bc1RgbaUnorm),
+            b"DXT2" | b"DXT3" => Some(Bc2RgbaUnorm),
+            b"DXT4" | b"DXT5" => Some(Bc3RgbaUnorm),
+            b"ATI1" | b"BC4U" => Some(Bc4RUnorm),
+            b"BC4S" => Some(Bc4RSnorm),
+            b"ATI2" | b"BC5U" => Some(Bc5RgUnorm),
+            b"BC5S" => Some(Bc5RgSnorm),
+            // D3DFMT codes stored as numbers in the FourCC field.
+            _ => match read_u32(four_cc, 0) {
+                111 => Some(R16Float),
+                112 => Some(Rg16Float),
+                113 => Some(Rgba16Float),
+                114 => Some(R32Float),
+                115 => Some(Rg32Float),
+                116 => Some(Rgba32Float),
+                _ => None,
+            },
+        };
+        return format.ok_or_else(|| {
+            TextureDecodeError::Unsupported(format!(
+                "unsupported FourCC {:?}",
+                String::from_utf8_lossy(four_cc)
+            ))
+        });
+    }
+
+    let bit_count = read_u32(bytes, 88);
+    let masks = [
+        read_u32(bytes, 92),
+        read_u32(bytes, 96),
+        read_u32(bytes, 100),
+        read_u32(bytes, 104),
+    ];
+    let format = if pf_flags & DDPF_RGB != 0 && bit_count == 32 {
+        match masks {
+            [0xFF, 0xFF00, 0xFF_0000, _] => Some(Rgba8Unorm),
+            [0xFF_0000, 0xFF00, 0xFF, _] => Some(Bgra8Unorm),
+            _ => None,
+        }
+    } else if pf_flags & DDPF_LUMINANCE != 0 && bit_count == 8 {
+        Some(R8Unorm)
+    } else {
+        None
+    };
+    format.ok_or_else(|| {
+        TextureDecodeError::Unsupported(format!(
+            "unsupported {bit_count}-bit pixel format with masks {masks:x?}"
+        ))
+    })
+}
+
+fn invalid(msg: &str) -> TextureDecodeError {
+    TextureDecodeError::Invalid(format!("DDS: {msg}"))
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+}
+
+/// Map a `DXGI_FORMAT` value to a [`TextureFormat`].
+fn format_from_dxgi(dxgi_format: u32) -> Option<TextureFormat> {
+    use TextureFormat::*;
+    Some(match dxgi_format {
+        2 => Rgba32Float,
+        10 => Rgba16Float,
+        16 => Rg32Float,
+        24 => Rgba10a2Unorm,
+        28 => Rgba8Unorm,
+        29 => Rgba8UnormSrgb,
+        34 => Rg16Float,
+        41 => R32Float,
+        42 => R32Uint,
+        49 => Rg8Unorm,
+        54 => R16Float,
+        56 => R16Unorm,
+        61 => R8Unorm,
+        62 => R8Uint,
+        63 => R8Snorm,
+        64 => R8Sint,
+        71 => Bc1RgbaUnorm,
+        72 => Bc1RgbaUnormSrgb,
+        74 => Bc2RgbaUnorm,
+        75 => Bc2RgbaUnormSrgb,
+        77 => Bc3RgbaUnorm,
+        78 => Bc3RgbaUnormSrgb,
+        80 => Bc4RUnorm,
+        81 => Bc4RSnorm,
+        83 => Bc5RgUnorm,
+        84 => Bc5RgSnorm,
+        87 => Bgra8Unorm,
+        91 => Bgra8UnormSrgb,
+        95 => Bc6hRgbUfloat,
+        96 => Bc6hRgbFloat,
+        98 => Bc7RgbaUnorm,
+        99 => Bc7RgbaUnormSrgb,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a DDS header. `dx10` is `(dxgi_format, resource_dimension,
+    /// misc_flags, array_size)`.
+    fn header(
+        width: u32,
+        height: u32,
+        mips: u32,
+        four_cc: &[u8; 4],
+        caps2: u32,
+        dx10: Option<[u32; 4]>,
+    ) -> Vec<u8> {
+        let mut file = MAGIC.to_vec();
+        file.resize(HEADER_SIZE, 0);
+        file[4..8].copy_from_slice(&124u32.to_le_bytes());
+        file[8..12].copy_from_slice(&DDSD_MIPMAPCOUNT.to_le_bytes());
+        file[12..16].copy_from_slice(&height.to_le_bytes());
+        file[16..20].copy_from_slice(&width.to_le_bytes());
+        file[28..32].copy_from_slice(&mips.to_le_bytes());
+        file[76..80].copy_from_slice(&32u32.to_le_bytes());
+        file[80..84].copy_from_slice(&DDPF_FOURCC.to_le_bytes());
+        file[84..88].copy_from_slice(four_cc);
+        file[112..116].copy_from_slice(&caps2.to_le_bytes());
+        if let Some(dx10) = dx10 {
+            for value in dx10 {
+                file.extend_from_slice(&value.to_le_bytes());
+            }
+            file.extend_from_slice(&0u32.to_le_bytes());
+        }
+        file
+    }
+
+    #[test]
+    fn decodes_dxt1_mip_chain() {
+        // 8x8 BC1: 4 blocks of 8 bytes, then 1 block each for 4x4, 2x2, 1x1.
+        let mut file = header(8, 8, 4, b"DXT1", 0, None);
+        file.extend(std::iter::repeat_n(1, 32));
+        file.extend(std::iter::repeat_n(2, 24));
+        let texture = CpuTexture::from_container(&file).unwrap();
+
+        assert_eq!(texture.format, TextureFormat::Bc1RgbaUnorm);
+        assert_eq!(texture.mip_level_count, 4);
+        assert_eq!(texture.data.len(), 56);
+        assert_eq!(texture.mip_level_data(3).unwrap(), &[2; 8]);
+    }
+
+    #[test]
+    fn reorders_cube_faces_to_mip_major() {
+        // 2x2 RGBA8 cube with 2 mips: face f has mip 0 filled with f and
+        // mip 1 filled with 10 + f.
+        let mut file = header(2, 2, 2, b"DX10", 0, Some([28, 3, 0x4, 1]));
+        for face in 0..6u8 {
+            file.extend(std::iter::repeat_n(face, 16));
+            file.extend(std::iter::repeat_n(10 + face, 4));
+        }
+        let texture = CpuTexture::from_container(&file).unwrap();
+
+        assert_eq!(texture.dimension, TextureDimension::Cube);
+        assert_eq!(texture.format, TextureFormat::Rgba8Unorm);
+        let mip0 = texture.mip_level_data(0).unwrap();
+        assert_eq!(&mip0[16..32], &[1; 16]);
+        let mip1 = texture.mip_level_data(1).unwrap();
+        assert_eq!(&mip1[..4], &[10; 4]);
+        assert_eq!(&mip1[20..], &[15; 4]);
+    }
+
+    #[test]
+    fn decodes_bc7_array() {
+        let mut file = header(4, 4, 1, b"DX10", 0, Some([99, 3, 0, 3]));
+        file.extend(std::iter::repeat_n(0, 48));
+        let texture = CpuTexture::from_container(&file).unwrap();
+
+        assert_eq!(texture.dimension, TextureDimension::D2Array);
+        assert_eq!(texture.format, TextureFormat::Bc7RgbaUnormSrgb);
+        assert_eq!(texture.layer_count(), 3);
+    }
+
+    #[test]
+    fn rejects_short_payload() {
+        let mut file = header(8, 8, 1, b"DXT5", 0, None);
+        file.extend(std::iter::repeat_n(0, 16));
+        assert!(matches!(
+            CpuTexture::from_container(&file),
+            Err(TextureDecodeError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_excess_mip_count() {
+        let mut file = header(8, 8, u32::MAX, b"DXT5", 0, None);
+        file.extend(std::iter::repeat_n(0, 64));
+        assert!(matches!(
+            CpuTexture::from_container(&file),
+            Err(TextureDecodeError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_oversized_cube_array() {
+        let mut file = header(4, 4, 1, b"DX10", 0, Some([28, 3, 0x4, u32::MAX]));
+        file.extend(std::iter::repeat_n(0, 64));
+        assert!(matches!(
+            CpuTexture::from_container(&file),
+            Err(TextureDecodeError::Invalid(_))
+        ));
+    }
+}
@@ -0,0 +1,366 @@
+//! KTX 2.0 container decoding.
+//!
+//! Supports uncompressed and Zstandard-supercompressed files (the latter
+//! with the `ktx2-zstd` feature) in any format [`TextureFormat`] can
+//! represent. Basis Universal payloads (BasisLZ or UASTC, as used by
+//! `KHR_texture_basisu`) are recognized but rejected, as they need to be
+//! transcoded first.
+
+use super::{CpuTexture, TextureDecodeError, TextureDimension, TextureFormat};
+
+/// File identifier: `«KTX 20»\r\n\x1A\n`.
+pub(super) const IDENTIFIER: [u8; 12] = [
+    0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A,
+];
+
+/// Size of the header and index preceding the level index.
+const HEADER_SIZE: usize = 80;
+/// Size of one level index entry.
+const LEVEL_INDEX_ENTRY_SIZE: usize = 24;
+
+const SUPERCOMPRESSION_NONE: u32 = 0;
+const SUPERCOMPRESSION_BASIS_LZ: u32 = 1;
+const SUPERCOMPRESSION_ZSTD: u32 = 2;
+
+/// Decode a KTX2 file.
+pub(super) fn decode(bytes: &[u8]) -> Result<CpuTexture, TextureDecodeError> {
+    if !bytes.starts_with(&IDENTIFIER) {
+        return Err(invalid("missing KTX2 identifier"));
+    }
+    if bytes.len() < HEADER_SIZE {
+        return Err(invalid("truncated header"));
+    }
+
+    let vk_format = read_u32(bytes, 12);
+    let width = read_u32(bytes, 20);
+    let height = read_u32(bytes, 24);
+    let depth = read_u32(bytes, 28);
+    let layer_count = read_u32(bytes, 32);
+    let face_count = read_u32(bytes, 36);
+    let level_count = read_u32(bytes, 40).max(1);
+    let supercompression = read_u32(bytes, 44);
+
+    if vk_format == 0 || supercompression == SUPERCOMPRESSION_BASIS_LZ {
+        return Err(TextureDecodeError::Unsupported(
+            "Basis Universal textures must be transcoded before loading".to_owned(),
+        ));
+    }
+    let format = format_from_vk(vk_format).ok_or_else(|| {
+        TextureDecodeError::Unsupported(format!("unsupported VkFormat {vk_format}"))
+    })?;
+    if supercompression != SUPERCOMPRESSION_NONE && supercompression != SUPERCOMPRESSION_ZSTD {
+        return Err(TextureDecodeError::Unsupported(format!(
+            "unsupported supercompression scheme {supercompression}"
+        )));
+    }
+    if width == 0 {
+        return Err(invalid("zero width"));
+    }
+
+    let dimension = match (height, depth, layer_count, face_count) {
+        (_, _, _, 6) if layer_count > 0 => TextureDimension::CubeArray,
+        (_, _, _, 6) => TextureDimension::Cube,
+        (_, _, _, 1) if depth > 0 => TextureDimension::D3,
+        (0, _, 0, 1) => TextureDimension::D1,
+        (0, _, _, 1) => TextureDimension::D1Array,
+        (_, _, 0, 1) => TextureDimension::D2,
+        (_, _, _, 1) => TextureDimension::D2Array,
+        _ => return Err(invalid(&format!("invalid face count {face_count}"))),
+    };
+    let mut texture = CpuTexture::new(width, height.max(1), format, Vec::new())
+        .with_dimension(dimension)
+        .with_depth(match dimension {
+            TextureDimension::D3 => depth,
+            _ => layer_count.max(1),
+        })
+        .with_mip_levels(level_count);
+
+    if level_count > texture.max_mip_levels() {
+        return Err(invalid(&format!(
+            "{level_count} mip levels exceed the full chain of {}",
+            texture.max_mip_levels()
+        )));
+    }
+    let level_index_end = HEADER_SIZE + level_count as usize * LEVEL_INDEX_ENTRY_SIZE;
+    if bytes.len() < level_index_end {
+        return Err(invalid("truncated level index"));
+    }
+    let (level_sizes, total_size) = texture
+        .checked_mip_level_sizes()
+        .ok_or_else(|| invalid("image size overflows"))?;
+    // Uncompressed levels are stored verbatim, so the file must hold them.
+    if supercompression == SUPERCOMPRESSION_NONE && total_size > bytes.len() {
+        return Err(invalid(&format!(
+            "holds {} bytes, expected at least {total_size} of image data",
+            bytes.len()
+        )));
+    }
+    let mut data = Vec::with_capacity(total_size.min(bytes.len()));
+    for (level, &expected) in level_sizes.iter().enumerate() {
+        let entry = HEADER_SIZE + level * LEVEL_INDEX_ENTRY_SIZE;
+        let offset = read_u64(bytes, entry) as usize;
+        let length = read_u64(bytes, entry + 8) as usize;
+        let stored = offset
+            .checked_add(length)
+            .and_then(|end| bytes.get(offset..end))
+            .ok_or_else(|| invalid(&format!("level {level} data out of bounds")))?;
+
+        let level_data = match supercompression {
+            SUPERCOMPRESSION_ZSTD => decompress_zstd(stored, expected)?,
+            _ => stored.to_vec(),
+        };
+        if level_data.len() < expected {
+            return Err(invalid(&format!(
+                "level {level} holds {} bytes, expected {expected}",
+                level_data.len()
+            )));
+        }
+        data.extend_from_slice(&level_data[..expected]);
+    }
+    texture.data = data;
+    Ok(texture)
+}
+
+/// Decompress at most `limit` bytes of a supercompressed level.
+#[cfg(feature = "ktx2-zstd")]
+fn decompress_zstd(stored: &[u8], limit: usize) -> Result<Vec<u8>, TextureDecodeError> {
+    use std::io::Read;
+
+    let decoder = ruzstd::decoding::StreamingDecoder::new(stored)
+        .map_err(|e| invalid(&format!("zstd: {e}")))?;
+    let mut data = Vec::new();
+    decoder
+        .take(limit as u64)
+        .read_to_end(&mut data)
+        .map_err(|e| invalid(&format!("zstd: {e}")))?;
+    Ok(data)
+}
+
+#[cfg(not(feature = "ktx2-zstd"))]
+fn decompress_zstd(_stored: &[u8], _limit: usize) -> Result<Vec<u8>, TextureDecodeError> {
+    Err(TextureDecodeError::Unsupported(
+        "Zstandard supercompression requires the `ktx2-zstd` feature".to_owned(),
+    ))
+}
+
+fn invalid(msg: &str) -> TextureDecodeError {
+    TextureDecodeError::Invalid(format!("KTX2: {msg}"))
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_u64(bytes: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap())
+}
+
+/// Map a `VkFormat` value to a [`TextureFormat`].
+fn format_from_vk(vk_format: u32) -> Option<TextureFormat> {
+    use TextureFormat::*;
+    Some(match vk_format {
+        9 => R8Unorm,
+        10 => R8Snorm,
+        13 => R8Uint,
+        14 => R8Sint,
+        16 => Rg8Unorm,
+        37 => Rgba8Unorm,
+        43 => Rgba8UnormSrgb,
+        44 => Bgra8Unorm,
+        50 => Bgra8UnormSrgb,
+        58 => Bgra10a2Unorm,
+        64 => Rgba10a2Unorm,
+        70 => R16Unorm,
+        76 => R16Float,
+        83 => Rg16Float,
+        97 => Rgba16Float,
+        98 => R32Uint,
+        100 => R32Float,
+        103 => Rg32Float,
+        109 => Rgba32Float,
+        131 | 133 => Bc1RgbaUnorm,
+        132 | 134 => Bc1RgbaUnormSrgb,
+        135 => Bc2RgbaUnorm,
+        136 => Bc2RgbaUnormSrgb,
+        137 => Bc3RgbaUnorm,
+        138 => Bc3RgbaUnormSrgb,
+        139 => Bc4RUnorm,
+        140 => Bc4RSnorm,
+        141 => Bc5RgUnorm,
+        142 => Bc5RgSnorm,
+        143 => Bc6hRgbUfloat,
+        144 => Bc6hRgbFloat,
+        145 => Bc7RgbaUnorm,
+        146 => Bc7RgbaUnormSrgb,
+        147 => Etc2Rgb8Unorm,
+        148 => Etc2Rgb8UnormSrgb,
+        149 => Etc2Rgb8A1Unorm,
+        150 => Etc2Rgb8A1UnormSrgb,
+        151 => Etc2Rgba8Unorm,
+        152 => Etc2Rgba8UnormSrgb,
+        153 => EacR11Unorm,
+        154 => EacR11Snorm,
+        155 => EacRg11Unorm,
+        156 => EacRg11Snorm,
+        157 => Astc4x4Unorm,
+        158 => Astc4x4UnormSrgb,
+        159 => Astc5x4Unorm,
+        160 => Astc5x4UnormSrgb,
+        161 => Astc5x5Unorm,
+        162 => Astc5x5UnormSrgb,
+        163 => Astc6x5Unorm,
+        164 => Astc6x5UnormSrgb,
+        165 => Astc6x6Unorm,
+        166 => Astc6x6UnormSrgb,
+        167 => Astc8x5Unorm,
+        168 => Astc8x5UnormSrgb,
+        169 => Astc8x6Unorm,
+        170 => Astc8x6UnormSrgb,
+        171 => Astc8x8Unorm,
+        172 => Astc8x8UnormSrgb,
+        173 => Astc10x5Unorm,
+        174 => Astc10x5UnormSrgb,
+        175 => Astc10x6Unorm,
+        176 => Astc10x6UnormSrgb,
+        177 => Astc10x8Unorm,
+        178 => Astc10x8UnormSrgb,
+        179 => Astc10x10Unorm,
+        180 => Astc10x10UnormSrgb,
+        181 => Astc12x10Unorm,
+        182 => Astc12x10UnormSrgb,
+        183 => Astc12x12Unorm,
+        184 => Astc12x12UnormSrgb,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a KTX2 file from raw per-level data.
+    fn build(
+        vk_format: u32,
+        size: [u32; 3],
+        layers: u32,
+        faces: u32,
+        supercompression: u32,
+        levels: &[Vec<u8>],
+    ) -> Vec<u8> {
+        let mut file = IDENTIFIER.to_vec();
+        for value in [
+            vk_format,
+            1,
+            size[0],
+            size[1],
+            size[2],
+            layers,
+            faces,
+            levels.len() as u32,
+            supercompression,
+        ] {
+            file.extend_from_slice(&value.to_le_bytes());
+        }
+        file.resize(HEADER_SIZE, 0);
+
+        let mut offset = HEADER_SIZE + levels.len() * LEVEL_INDEX_ENTRY_SIZE;
+        for level in levels {
+            file.extend_from_slice(&(offset as u64).to_le_bytes());
+            file.extend_from_slice(&(level.len() as u64).to_le_bytes());
+            file.extend_from_slice(&(level.len() as u64).to_le_bytes());
+            offset += level.len();
+        }
+        for level in levels {
+            file.extend_from_slice(level);
+        }
+        file
+    }
+
+    #[test]
+    fn decodes_rgba8_mip_chain() {
+        let levels = vec![vec![1; 4 * 4 * 4], vec![2; 2 * 2 * 4], vec![3; 4]];
+        let file = build(37, [4, 4, 0], 0, 1, 0, &levels);
+        let texture = CpuTexture::from_container(&file).unwrap();
+
+        assert_eq!((texture.width, texture.height), (4, 4));
+        assert_eq!(texture.format, TextureFormat::Rgba8Unorm);
+        assert_eq!(texture.dimension, TextureDimension::D2);
+        assert_eq!(texture.mip_level_count, 3);
+        assert_eq!(texture.mip_level_data(1).unwrap(), &levels[1][..]);
+        assert_eq!(texture.mip_level_data(2).unwrap(), &levels[2][..]);
+    }
+
+    #[test]
+    fn decodes_bc7_cubemap() {
+        // 8x8 BC7: 2x2 blocks of 16 bytes per face, 1 block at mip 1.
+        let levels = vec![vec![7; 6 * 64], vec![8; 6 * 16]];
+        let file = build(145, [8, 8, 0], 0, 6, 0, &levels);
+        let texture = CpuTexture::from_container(&file).unwrap();
+
+        assert_eq!(texture.dimension, TextureDimension::Cube);
+        assert_eq!(texture.layer_count(), 6);
+        assert_eq!(texture.format, TextureFormat::Bc7RgbaUnorm);
+        assert_eq!(texture.data.len(), 6 * 64 + 6 * 16);
+    }
+
+    #[test]
+    fn decodes_array_layers() {
+        let levels = vec![vec![5; 2 * 2 * 3]];
+        let file = build(9, [2, 2, 0], 3, 1, 0, &levels);
+        let texture = CpuTexture::from_container(&file).unwrap();
+
+        assert_eq!(texture.dimension, TextureDimension::D2Array);
+        assert_eq!(texture.depth, 3);
+        assert_eq!(texture.format, TextureFormat::R8Unorm);
+    }
+
+    #[cfg(feature = "ktx2-zstd")]
+    #[test]
+    fn decodes_zstd_supercompression() {
+        let level = (0..64u8).collect::<Vec<_>>();
+        let compressed = ruzstd::encoding::compress_to_vec(
+            &level[..],
+            ruzstd::encoding::CompressionLevel::Fastest,
+        );
+        let file = build(37, [4, 4, 0], 0, 1, 2, &[compressed]);
+        let texture = CpuTexture::from_container(&file).unwrap();
+        assert_eq!(texture.data, level);
+    }
+
+    #[test]
+    fn rejects_basis_universal() {
+        let file = build(0, [4, 4, 0], 0, 1, 1, &[vec![0; 16]]);
+        assert!(matches!(
+            CpuTexture::from_container(&file),
+            Err(TextureDecodeError::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_truncated_level() {
+        let file = build(37, [4, 4, 0], 0, 1, 0, &[vec![0; 16]]);
+        assert!(matches!(
+            CpuTexture::from_container(&file),
+            Err(TextureDecodeError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_excess_level_count() {
+        let mut file = build(37, [4, 4, 0], 0, 1, 0, &[vec![0; 64]]);
+        file[40..44].copy_from_slice(&40u32.to_le_bytes());
+        assert!(matches!(
+            CpuTexture::from_container(&file),
+            Err(TextureDecodeError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_oversized_extent() {
+        let file = build(109, [u32::MAX, u32::MAX, 0], 0, 1, 0, &[vec![0; 16]]);
+        assert!(matches!(
+            CpuTexture::from_container(&file),
+            Err(TextureDecodeError::Invalid(_))
+        ));
+    }
+}
@@ -2,7 +2,11 @@
 //!
 //! Provides [`CpuTexture`] for holding raw pixel data, along with
 //! [`TextureFormat`] and [`TextureDimension`] enums shared between
-//! CPU and GPU code.
+//! CPU and GPU code. KTX2 and DDS container files are decoded with
+//! [`CpuTexture::from_container`].
+
+mod dds;
+mod ktx2;
 
 /// Texture dimension enumeration.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
@@ -33,7 +37,7 @@ impl TextureDimension {
         match self {
             Self::D1 | Self::D1Array | Self::D2 | Self::D2Array | Self::D3 => depth_or_array_layers,
             Self::Cube => 6,
-            Self::CubeArray => depth_or_array_layers * 6,
+            Self::CubeArray => depth_or_array_layers.saturating_mul(6),
         }
     }
 
@@ -491,12 +495,71 @@ impl TextureFormat {
             | Self::Astc12x12UnormSrgb => 16,
         }
     }
+
+    /// Returns the number of bytes in one row of blocks of a `width` texels
+    /// wide image.
+    pub fn bytes_per_row(&self, width: u32) -> u32 {
+        width.div_ceil(self.block_dimensions().0) * self.block_size()
+    }
+
+    /// Returns the number of block rows of a `height` texels tall image.
+    pub fn rows_per_image(&self, height: u32) -> u32 {
+        height.div_ceil(self.block_dimensions().1)
+    }
+}
+
+/// Errors that can occur when decoding a texture container file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TextureDecodeError {
+    /// The data is not a well-formed container file.
+    Invalid(String),
+    /// The file is well-formed but uses a format or feature that cannot be
+    /// loaded (e.g. Basis Universal payloads, which need transcoding).
+    Unsupported(String),
+}
+
+impl std::fmt::Display for TextureDecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Invalid(msg) => write!(f, "invalid texture file: {msg}"),
+            Self::Unsupported(msg) => write!(f, "unsupported texture file: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for TextureDecodeError {}
+
+/// Texture container file formats holding GPU-ready data with prebuilt mip
+/// chains.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextureContainer {
+    /// Khronos KTX 2.0.
+    Ktx2,
+    /// DirectDraw Surface, including the DX10 header extension.
+    Dds,
+}
+
+impl TextureContainer {
+    /// Detect the container format from the leading magic bytes of a file.
+    pub fn detect(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(&ktx2::IDENTIFIER) {
+            Some(Self::Ktx2)
+        } else if bytes.starts_with(dds::MAGIC) {
+            Some(Self::Dds)
+        } else {
+            None
+        }
+    }
 }
 
 /// CPU-side texture data.
 ///
 /// Holds raw pixel data along with dimensions and format metadata.
 /// This is the CPU-side counterpart to a GPU texture resource.
+///
+/// `data` holds every mip level, largest first. Each level holds all array
+/// layers (cube faces in +X, -X, +Y, -Y, +Z, -Z order) or depth slices one
+/// after another, each a tightly packed image of rows of texel blocks.
 #[derive(Debug, Clone)]
 pub struct CpuTexture {
     /// Texture name.
@@ -507,6 +570,11 @@ pub struct CpuTexture {
     pub width: u32,
     /// Height in pixels.
     pub height: u32,
+    /// Depth in pixels for 3D textures, layer count for array textures or
+    /// cube count for cube arrays. 1 otherwise.
+    pub depth: u32,
+    /// Number of mip levels stored in `data`.
+    pub mip_level_count: u32,
     /// Pixel format.
     pub format: TextureFormat,
     /// Texture dimension.
@@ -521,11 +589,27 @@ impl CpuTexture {
             data,
             width,
             height,
+            depth: 1,
+            mip_level_count: 1,
             format,
             dimension: TextureDimension::D2,
         }
     }
 
+    /// Decode a KTX2 or DDS file, detected from its magic bytes.
+    ///
+    /// All mip levels, array layers and cube faces stored in the file are
+    /// kept.
+    pub fn from_container(bytes: &[u8]) -> Result<Self, TextureDecodeError> {
+        match TextureContainer::detect(bytes) {
+            Some(TextureContainer::Ktx2) => ktx2::decode(bytes),
+            Some(TextureContainer::Dds) => dds::decode(bytes),
+            None => Err(TextureDecodeError::Invalid(
+                "not a KTX2 or DDS file".to_owned(),
+            )),
+        }
+    }
+
     /// Set the texture name.
     pub fn with_name(mut self, name: impl Into<String>) -> Self {
         self.name = Some(name.into());
@@ -537,4 +621,84 @@ impl CpuTexture {
         self.dimension = dimension;
         self
     }
+
+    /// Set the depth, layer count or cube count (see [`depth`](Self::depth)).
+    pub fn with_depth(mut self, depth: u32) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Set the number of mip levels stored in the data.
+    pub fn with_mip_levels(mut self, count: u32) -> Self {
+        self.mip_level_count = count;
+        self
+    }
+
+    /// Returns the number of array layers, counting each cube face.
+    pub fn layer_count(&self) -> u32 {
+        match self.dimension {
+            TextureDimension::D3 => 1,
+            dimension => dimension.layer_count(self.depth),
+        }
+    }
+
+    /// Returns the size in pixels of mip level `level`.
+    pub fn mip_extent(&self, level: u32) -> (u32, u32, u32) {
+        let shrink = |size: u32| size.checked_shr(level).unwrap_or(0).max(1);
+        let depth = match self.dimension {
+            TextureDimension::D3 => shrink(self.depth),
+            _ => 1,
+        };
+        (shrink(self.width), shrink(self.height), depth)
+    }
+
+    /// Returns the number of levels in a full mip chain down to 1x1x1.
+    pub fn max_mip_levels(&self) -> u32 {
+        let depth = match self.dimension {
+            TextureDimension::D3 => self.depth,
+            _ => 1,
+        };
+        32 - self.width.max(self.height).max(depth).leading_zeros()
+    }
+
+    /// Returns the size in bytes of mip level `level`, all layers included.
+    pub fn mip_level_size(&self, level: u32) -> usize {
+        let (width, height, depth) = self.mip_extent(level);
+        self.format.bytes_per_row(width) as usize
+            * self.format.rows_per_image(height) as usize
+            * depth as usize
+            * self.layer_count() as usize
+    }
+
+    /// Returns the size in bytes of every mip level, or `None` if a size or
+    /// their sum overflows `usize`.
+    ///
+    /// Container decoders use this before trusting header extents.
+    pub(super) fn checked_mip_level_sizes(&self) -> Option<(Vec<usize>, usize)> {
+        let (block_width, block_height) = self.format.block_dimensions();
+        let mut total = 0usize;
+        let sizes = (0..self.mip_level_count)
+            .map(|level| {
+                let (width, height, depth) = self.mip_extent(level);
+                let size = (width.div_ceil(block_width) as usize)
+                    .checked_mul(self.format.block_size() as usize)?
+                    .checked_mul(height.div_ceil(block_height) as usize)?
+                    .checked_mul(depth as usize)?
+                    .checked_mul(self.layer_count() as usize)?;
+                total = total.checked_add(size)?;
+                Some(size)
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some((sizes, total))
+    }
+
+    /// Returns the data of mip level `level`, or `None` if `data` is too
+    /// short to hold it.
+    pub fn mip_level_data(&self, level: u32) -> Option<&[u8]> {
+        if level >= self.mip_level_count {
+            return None;
+        }
+        let offset: usize = (0..level).map(|l| self.mip_level_size(l)).sum();
+        self.data.get(offset..offset + self.mip_level_size(level))
+    }
 }
@@ -19,6 +19,11 @@ use syn::{Data, DeriveInput, Fields, Meta, parse_macro_input};
 /// serialize/deserialize methods (they will use the default "not serializable"
 /// implementations from the trait).
 ///
+/// Use `#[component(version = N)]` to set the serialized schema version
+/// (`Component::VERSION`, default 0). Bump it when fields are renamed or
+/// restructured and register a migration from the previous version with
+/// `World::register_component_migration`.
+///
 /// # Example
 ///
 /// ```ignore
@@ -32,8 +37,15 @@ use syn::{Data, DeriveInput, Fields, Meta, parse_macro_input};
 /// #[derive(Component)]
 /// #[skip_serialization]
 /// struct RenderMesh(pub Arc<Mesh>);
+///
+/// // Schema version 2; data from versions 0 and 1 is migrated on load:
+/// #[derive(Component)]
+/// #[component(version = 2)]
+/// struct Stats {
+///     strength: u32,
+/// }
 /// ```
-#[proc_macro_derive(Component, attributes(require, skip_serialization))]
+#[proc_macro_derive(Component, attributes(require, skip_serialization, component))]
 pub fn derive_component(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let name = &input.ident;
@@ -62,6 +74,26 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
         }
     }
 
+    // Parse the schema version from #[component(version = N)]
+    let mut version = None;
+    for attr in &input.attrs {
+        if attr.path().is_ident("component") {
+            let result = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("version") {
+                    let lit: syn::LitInt = meta.value()?.parse()?;
+                    version = Some(lit.base10_parse::<u32>()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported component attribute, expected `version = N`"))
+                }
+            });
+            if let Err(e) = result {
+                return e.to_compile_error().into();
+            }
+        }
+    }
+    let version_const = version.map(|v| quote! { const VERSION: u32 = #v; });
+
     let (inspect_body, collect_body, remap_body, serialize_body, deserialize_body) = match &input
         .data
     {
@@ -380,6 +412,7 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
     let expanded = quote! {
         impl #impl_generics redlilium_ecs::Component for #name #ty_generics #where_clause {
             const NAME: &'static str = #name_str;
+            #version_const
 
             fn inspect_ui(&self, ui: &mut redlilium_ecs::egui::Ui, _world: &redlilium_ecs::World, _entity: redlilium_ecs::Entity) -> redlilium_ecs::InspectResult {
                 #[allow(unused_imports)]
@@ -4,8 +4,9 @@
 //! integrated egui inspector support via [`inspect_ui`](Component::inspect_ui).
 //!
 //! Components can be any `Send + Sync + 'static` type. Types that also implement
-//! [`bytemuck::Pod`] additionally support byte-level serialization, GPU upload,
-//! and snapshot/rollback.
+//! [`bytemuck::Pod`] additionally support byte-level serialization and GPU upload,
+//! and are copied with a plain memcpy by [`World::snapshot`](crate::World::snapshot).
+//! Other components are captured for snapshot/rollback through [`Clone`].
 //!
 //! Use `#[derive(Component)]` from [`ecs_macro`] to auto-implement the trait.
 
@@ -43,6 +44,13 @@ pub trait Component: Clone + Send + Sync + 'static {
     /// without requiring an instance.
     const NAME: &'static str;
 
+    /// Schema version of the serialized data.
+    ///
+    /// Bump it (via `#[component(version = N)]` on the derive) when fields
+    /// are renamed or restructured, and register a migration from the old
+    /// version with [`World::register_component_migration`](crate::World::register_component_migration).
+    const VERSION: u32 = 0;
+
     /// Returns the struct name (e.g. `"Transform"`).
     fn component_name(&self) -> &'static str {
         Self::NAME
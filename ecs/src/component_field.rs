@@ -593,6 +593,40 @@ impl ComponentField for Mat4 {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Vec<u8> — binary blobs (e.g. vertex data)
+// ---------------------------------------------------------------------------
+
+impl ComponentField for Vec<u8> {
+    fn inspect_field(&self, name: &str, ui: &mut egui::Ui) -> Option<Self> {
+        ui.horizontal(|ui| {
+            ui.label(name);
+            ui.weak(format!("[{} bytes]", self.len()));
+        });
+        None // read-only
+    }
+
+    fn serialize_field(
+        &self,
+        name: &str,
+        ctx: &mut SerializeContext<'_>,
+    ) -> Result<(), SerializeError> {
+        ctx.write_field(name, crate::serialize::Value::Bytes(self.clone()))
+    }
+
+    fn deserialize_field(
+        name: &str,
+        ctx: &mut DeserializeContext<'_>,
+    ) -> Result<Self, DeserializeError> {
+        // Take the blob as-is; only other representations (e.g. a list of
+        // integers from a text format) go through serde.
+        match ctx.read_field(name)? {
+            crate::serialize::Value::Bytes(bytes) => Ok(bytes),
+            other => crate::serialize::value::from_value(other),
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Arc<T> — deduplicating serialization, opaque inspection
 // ---------------------------------------------------------------------------
@@ -671,6 +705,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn round_trip_bytes() {
+        let bytes = vec![0u8, 1, 255];
+        assert_eq!(round_trip_serde(bytes.clone(), "blob"), bytes);
+    }
+
+    #[test]
+    fn bytes_from_integer_list() {
+        let mut world = World::new();
+        let mut dctx = DeserializeContext::new(&mut world);
+        let data = Value::Map(vec![(
+            "blob".into(),
+            Value::List(vec![Value::U64(7), Value::U64(255)]),
+        )]);
+        dctx.load_data(&data).unwrap();
+        assert_eq!(
+            Vec::<u8>::deserialize_field("blob", &mut dctx).unwrap(),
+            [7, 255]
+        );
+    }
+
     #[test]
     fn round_trip_vec3() {
         let v = Vec3::new(1.0, 2.0, 3.0);
@@ -102,6 +102,7 @@ impl std::fmt::Display for Entity {
 ///
 /// A slot is considered dead when its `generation` entry equals
 /// `Entity::INVALID_INDEX` (as a sentinel).
+#[derive(Clone)]
 pub struct Entities {
     /// Truncated spawn tick per slot (matches the 40-bit value in Entity handles).
     /// Dead slots store `DEAD_TICK`.
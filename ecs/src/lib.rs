@@ -76,6 +76,7 @@ mod resource;
 mod runner;
 mod schedule;
 pub mod serialize;
+pub mod snapshot;
 mod sparse_set;
 mod state;
 #[allow(clippy::module_inception)]
@@ -113,6 +114,7 @@ pub use redlilium_core::compute::{
     set_yield_interval, yield_now,
 };
 pub use resource::{Resource, ResourceRef, ResourceRefMut};
+pub use snapshot::WorldSnapshot;
 pub use sparse_set::{Mut, Ref, RefMut, SparseSetInner};
 pub use world::{ComponentNotRegistered, InspectResult, World, set_component_actions};
 
@@ -164,27 +166,35 @@ pub use self::std::spawn::spawn_scene;
 pub use self::std::systems;
 #[cfg(feature = "rendering")]
 pub use self::std::systems::DrawGrid;
+pub use self::std::systems::{
+    AnimateTransforms, UpdateCameraMatrices, UpdateFreeFlyCamera, UpdateGlobalTransforms,
+    UpdateSkinJoints,
+};
 #[cfg(feature = "rendering")]
 pub use self::std::systems::{DrawSelectionAabb, SelectionAabbMode};
-pub use self::std::systems::{UpdateCameraMatrices, UpdateFreeFlyCamera, UpdateGlobalTransforms};
 
 // Rendering components, resources, and systems (feature-gated)
 #[cfg(feature = "rendering")]
 pub use redlilium_debug_drawer::{DebugDrawer, DebugDrawerRenderer};
 #[cfg(feature = "rendering")]
 pub use rendering::{
-    CameraTarget, CpuBundleInfo, EditorForwardRenderSystem, ForwardRenderSystem,
-    InitializeRenderEntities, MaterialBundle, MaterialManager, MaterialManagerError, MeshManager,
-    PerEntityBuffers, RenderMaterial, RenderMesh, RenderPassType, RenderSchedule,
-    SyncMaterialUniforms, TextureManager, UpdatePerEntityUniforms, pack_uniform_bytes,
-    register_rendering_components, shaders,
+    AssetServer, CameraTarget, CpuBundleInfo, CullingStats, DeferredRenderSystem, DeferredRenderer,
+    DrawBatcher, EditorDeferredRenderSystem, EditorForwardRenderSystem, EnvironmentLighting,
+    ForwardRenderSystem, GBuffer, GatherLights, InitializeRenderEntities, MaterialBundle,
+    MaterialManager, MaterialManagerError, MeshManager, PerEntityBuffers, PostEffect,
+    PostProcessRenderer, PostProcessStack, RayHit, ReloadMaterialShaders, RenderMaterial,
+    RenderMesh, RenderPassType, RenderQueue, RenderSchedule, SceneLighting, ShadowMaps,
+    ShadowRenderSystem, Skybox, SpatialIndex, SyncMaterialUniforms, SyncPrefabInstances,
+    TextureManager, ToneMapping, UpdateAssetServer, UpdatePerEntityUniforms, UpdateSpatialIndex,
+    insert_render_resources, pack_uniform_bytes, register_rendering_components, shaders,
 };
 
 /// Register all standard component types with the world.
 ///
 /// Registers storage, inspector metadata, clone support, and (where
-/// applicable) default insertion support. Call this before running
-/// systems or using the inspector.
+/// applicable) default insertion support, and inserts the
+/// [`PrefabInstanceIndex`] resource. Call this before running systems or
+/// using the inspector.
 pub fn register_std_components(world: &mut World) {
     // Inspector-enabled components (support "Add Component" via Default)
     world.register_inspector_default::<Transform>();
@@ -194,15 +204,22 @@ pub fn register_std_components(world: &mut World) {
     world.register_inspector_default::<DirectionalLight>();
     world.register_inspector_default::<PointLight>();
     world.register_inspector_default::<SpotLight>();
+    world.register_inspector_default::<ShadowSettings>();
 
     // Inspector-enabled, readonly (no Default — constructed with parameters)
     world.register_inspector::<Camera>();
     world.register_inspector_default::<FreeFlyCamera>();
+    world.register_inspector::<PrefabInstance>();
+    components::track_prefab_instances(world);
 
     // Hierarchy components (inspector-enabled for clone/remap support)
     world.register_inspector::<Parent>();
     world.register_inspector_default::<Children>();
 
+    // Animation state (runtime only, filled by spawn_scene)
+    world.register_component::<AnimationPlayer>();
+    world.register_component::<SkinnedMesh>();
+
     // Inspector display order: Name first, then hierarchy, then transforms
     world.set_inspector_order::<Name>(0);
     world.set_inspector_order::<Parent>(10);
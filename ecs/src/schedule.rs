@@ -416,6 +416,30 @@ impl Schedules {
         world.advance_tick();
     }
 
+    /// Runs a frame with the game clock stopped.
+    ///
+    /// [`Time`] reports a zero delta and only [`PostUpdate`] runs:
+    /// [`PreUpdate`], state transitions, [`FixedUpdate`] and [`Update`] are
+    /// skipped, and the fixed-step accumulator keeps its value. Use this to
+    /// pause a game while cameras, transforms and rendering stay live.
+    pub fn run_paused_frame(&mut self, world: &mut World, runner: &EcsRunner) {
+        if !world.has_resource::<Time>() {
+            world.insert_resource(Time::new(self.fixed_timestep));
+        }
+        {
+            let mut time = world.resource_mut::<Time>();
+            time.delta = 0.0;
+            time.frame_delta = 0.0;
+            time.fixed_delta = self.fixed_timestep;
+        }
+
+        if let Some(schedule) = self.schedules.get(&ScheduleId::of::<PostUpdate>()) {
+            runner.run(world, schedule);
+        }
+
+        world.advance_tick();
+    }
+
     /// Runs a specific schedule by label.
     ///
     /// Does nothing if no systems have been added to this schedule.
@@ -600,6 +624,34 @@ mod tests {
         assert_eq!(counter.load(Ordering::SeqCst), 3);
     }
 
+    #[test]
+    fn paused_frame_runs_only_post_update() {
+        let game = Arc::new(AtomicU32::new(0));
+        let post = Arc::new(AtomicU32::new(0));
+        let mut world = World::new();
+        let mut schedules = Schedules::new();
+        let runner = EcsRunner::single_thread();
+
+        schedules
+            .get_mut::<PreUpdate>()
+            .add(IncrementSystem(game.clone()));
+        schedules
+            .get_mut::<FixedUpdate>()
+            .add(IncrementSystem(game.clone()));
+        schedules
+            .get_mut::<Update>()
+            .add(IncrementSystem(game.clone()));
+        schedules
+            .get_mut::<PostUpdate>()
+            .add(IncrementSystem(post.clone()));
+
+        schedules.run_paused_frame(&mut world, &runner);
+        schedules.run_paused_frame(&mut world, &runner);
+        assert_eq!(game.load(Ordering::SeqCst), 0);
+        assert_eq!(post.load(Ordering::SeqCst), 2);
+        assert_eq!(world.resource::<Time>().delta(), 0.0);
+    }
+
     #[test]
     fn fixed_update_time_delta() {
         let fixed_dt = 1.0 / 60.0;
@@ -48,6 +48,17 @@ pub enum DeserializeError {
     FormatError(String),
     /// Arc reference ID not found in the deduplication cache.
     InvalidArcRef { id: u32 },
+    /// The data was written by a newer schema version than this build supports.
+    UnsupportedVersion {
+        what: String,
+        found: u32,
+        supported: u32,
+    },
+    /// No migration is registered to upgrade a component from this version.
+    MissingMigration {
+        component: String,
+        from_version: u32,
+    },
 }
 
 impl fmt::Display for DeserializeError {
@@ -79,6 +90,25 @@ impl fmt::Display for DeserializeError {
             Self::InvalidArcRef { id } => {
                 write!(f, "invalid Arc reference id {id}")
             }
+            Self::UnsupportedVersion {
+                what,
+                found,
+                supported,
+            } => {
+                write!(
+                    f,
+                    "{what} has version {found}, newer than supported version {supported}"
+                )
+            }
+            Self::MissingMigration {
+                component,
+                from_version,
+            } => {
+                write!(
+                    f,
+                    "no migration registered for component '{component}' from version {from_version}"
+                )
+            }
         }
     }
 }
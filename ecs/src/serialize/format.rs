@@ -1,7 +1,12 @@
 //! Format-specific encoding and decoding (feature-gated).
 //!
 //! Provides [`encode`] and [`decode`] functions that convert between
-//! serde-serializable types and byte buffers in RON or bincode format.
+//! serde-serializable types and byte buffers in RON, bincode, JSON or
+//! MessagePack format.
+//!
+//! [`Value::Bytes`](super::Value::Bytes) blobs (e.g. vertex data) are written
+//! as base64 strings in the text formats and as raw byte strings in the
+//! binary formats, never as arrays of numbers.
 
 use super::error::{DeserializeError, SerializeError};
 
@@ -14,6 +19,36 @@ pub enum Format {
     /// Bincode — compact binary format.
     #[cfg(feature = "serialize-bincode")]
     Bincode,
+    /// JSON — human-readable text format for external tools.
+    #[cfg(feature = "serialize-json")]
+    Json,
+    /// MessagePack — compact, self-describing binary format. Structs are
+    /// written as maps keyed by field name, so fields can be added without
+    /// breaking old data.
+    #[cfg(feature = "serialize-msgpack")]
+    MessagePack,
+}
+
+impl Format {
+    /// Pick a format from a file extension (without the leading dot).
+    ///
+    /// `ron`, `prefab`, `scene` and `component` map to RON; `bin` and `bincode` map to
+    /// bincode; `json` maps to JSON; `msgpack` and `mpk` map to MessagePack.
+    /// Returns `None` for unknown extensions and for formats whose feature is
+    /// disabled.
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            #[cfg(feature = "serialize-ron")]
+            "ron" | "prefab" | "scene" | "component" => Some(Self::Ron),
+            #[cfg(feature = "serialize-bincode")]
+            "bin" | "bincode" => Some(Self::Bincode),
+            #[cfg(feature = "serialize-json")]
+            "json" => Some(Self::Json),
+            #[cfg(feature = "serialize-msgpack")]
+            "msgpack" | "mpk" => Some(Self::MessagePack),
+            _ => None,
+        }
+    }
 }
 
 /// Encode a serde-serializable value to bytes in the given format.
@@ -28,6 +63,14 @@ pub fn encode<T: serde::Serialize>(value: &T, format: Format) -> Result<Vec<u8>,
         Format::Bincode => {
             bincode::serialize(value).map_err(|e| SerializeError::FormatError(e.to_string()))
         }
+        #[cfg(feature = "serialize-json")]
+        Format::Json => {
+            serde_json::to_vec_pretty(value).map_err(|e| SerializeError::FormatError(e.to_string()))
+        }
+        #[cfg(feature = "serialize-msgpack")]
+        Format::MessagePack => {
+            rmp_serde::to_vec_named(value).map_err(|e| SerializeError::FormatError(e.to_string()))
+        }
     }
 }
 
@@ -48,5 +91,103 @@ pub fn decode<T: serde::de::DeserializeOwned>(
         Format::Bincode => {
             bincode::deserialize(bytes).map_err(|e| DeserializeError::FormatError(e.to_string()))
         }
+        #[cfg(feature = "serialize-json")]
+        Format::Json => {
+            serde_json::from_slice(bytes).map_err(|e| DeserializeError::FormatError(e.to_string()))
+        }
+        #[cfg(feature = "serialize-msgpack")]
+        Format::MessagePack => {
+            rmp_serde::from_slice(bytes).map_err(|e| DeserializeError::FormatError(e.to_string()))
+        }
+    }
+}
+
+#[cfg(all(
+    test,
+    any(
+        feature = "serialize-ron",
+        feature = "serialize-bincode",
+        feature = "serialize-json",
+        feature = "serialize-msgpack"
+    )
+))]
+mod tests {
+    use super::*;
+    use crate::serialize::{SerializedComponent, SerializedEntity, SerializedPrefab, Value};
+
+    fn blob_prefab() -> SerializedPrefab {
+        SerializedPrefab {
+            entities: vec![SerializedEntity {
+                entity_index: 3,
+                entity_spawn_tick: 7,
+                entity_flags: 0,
+                components: vec![SerializedComponent {
+                    type_name: "Blob".to_owned(),
+                    version: 1,
+                    data: Value::Map(vec![
+                        ("vertices".into(), Value::Bytes((0..=255).collect())),
+                        ("scale".into(), Value::F32(0.5)),
+                        (
+                            "parent".into(),
+                            Value::Entity {
+                                index: 1,
+                                spawn_tick: 2,
+                            },
+                        ),
+                        (
+                            "mesh".into(),
+                            Value::ArcValue {
+                                id: 0,
+                                inner: Box::new(Value::String("cube".into())),
+                            },
+                        ),
+                        ("none".into(), Value::Null),
+                    ]),
+                }],
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn assert_round_trip(format: Format) -> Vec<u8> {
+        let prefab = blob_prefab();
+        let bytes = encode(&prefab, format).unwrap();
+        let decoded: SerializedPrefab = decode(&bytes, format).unwrap();
+        assert_eq!(decoded.format_version, prefab.format_version);
+        assert_eq!(
+            decoded.entities[0].components[0].data,
+            prefab.entities[0].components[0].data
+        );
+        bytes
+    }
+
+    #[cfg(feature = "serialize-json")]
+    #[test]
+    fn json_round_trip_encodes_bytes_as_base64() {
+        let bytes = assert_round_trip(Format::Json);
+        let text = String::from_utf8(bytes).unwrap();
+        assert!(text.contains("\"AAECAwQFBgc"));
+    }
+
+    #[cfg(feature = "serialize-msgpack")]
+    #[test]
+    fn msgpack_round_trip_encodes_bytes_raw() {
+        let bytes = assert_round_trip(Format::MessagePack);
+        // 256 blob bytes plus a small amount of framing, not ~2 bytes per element
+        assert!(bytes.len() < 512);
+    }
+
+    #[cfg(feature = "serialize-ron")]
+    #[test]
+    fn ron_reads_legacy_byte_lists() {
+        assert_round_trip(Format::Ron);
+        let value: Value = decode(b"Bytes([1, 2, 3])", Format::Ron).unwrap();
+        assert_eq!(value, Value::Bytes(vec![1, 2, 3]));
+    }
+
+    #[cfg(feature = "serialize-bincode")]
+    #[test]
+    fn bincode_round_trip() {
+        assert_round_trip(Format::Bincode);
     }
 }
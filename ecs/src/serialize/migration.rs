@@ -0,0 +1,87 @@
+//! Schema versions and migrations for serialized components.
+//!
+//! Every [`SerializedComponent`](super::SerializedComponent) records the
+//! [`Component::VERSION`](crate::Component::VERSION) it was written with.
+//! When a component's fields are renamed or restructured, bump its version
+//! with `#[component(version = N)]` and register one migration per step via
+//! [`World::register_component_migration`](crate::World::register_component_migration).
+//! Before [`deserialize_component`](crate::Component::deserialize_component)
+//! runs, old data is upgraded step by step (`0 → 1 → … → N`).
+//!
+//! # Example
+//!
+//! ```ignore
+//! #[derive(Component, Clone)]
+//! #[component(version = 1)]
+//! struct Health {
+//!     current: f32, // was `hp` in version 0
+//! }
+//!
+//! world.register_component_migration::<Health>(0, |mut data| {
+//!     data.rename_field("hp", "current");
+//!     Ok(data)
+//! });
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+
+use super::error::DeserializeError;
+use super::value::Value;
+
+/// Upgrades a component's serialized data by one version.
+///
+/// Receives the data written with version `N` and returns it in the shape
+/// of version `N + 1`.
+pub type ComponentMigrationFn = fn(Value) -> Result<Value, DeserializeError>;
+
+/// Registered component migrations, by component name and source version.
+#[derive(Default)]
+pub(crate) struct ComponentMigrations {
+    steps: HashMap<&'static str, BTreeMap<u32, ComponentMigrationFn>>,
+}
+
+impl ComponentMigrations {
+    /// Registers the migration of `component` from `from_version` to `from_version + 1`.
+    pub fn register(
+        &mut self,
+        component: &'static str,
+        from_version: u32,
+        migration: ComponentMigrationFn,
+    ) {
+        self.steps
+            .entry(component)
+            .or_default()
+            .insert(from_version, migration);
+    }
+
+    /// Upgrades `data` of `component` from version `from` to version `to`.
+    ///
+    /// Fails if the data is newer than `to` or a step is missing.
+    pub fn migrate(
+        &self,
+        component: &str,
+        from: u32,
+        to: u32,
+        data: &Value,
+    ) -> Result<Value, DeserializeError> {
+        if from > to {
+            return Err(DeserializeError::UnsupportedVersion {
+                what: format!("component '{component}'"),
+                found: from,
+                supported: to,
+            });
+        }
+        let steps = self.steps.get(component);
+        let mut data = data.clone();
+        for version in from..to {
+            let step = steps.and_then(|s| s.get(&version)).ok_or_else(|| {
+                DeserializeError::MissingMigration {
+                    component: component.to_owned(),
+                    from_version: version,
+                }
+            })?;
+            data = step(data)?;
+        }
+        Ok(data)
+    }
+}
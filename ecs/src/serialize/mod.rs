@@ -8,6 +8,8 @@
 //!   using the same method-resolution trick as [`Inspect`](crate::inspect::Inspect)
 //! - [`Value`] — format-agnostic intermediate representation
 //! - [`SerializedPrefab`] — on-disk entity tree representation
+//! - [`SerializedScene`] — on-disk world representation (root trees + resources)
+//! - [`migration`] — per-component schema versions and upgrade functions
 //! - [`Format`] / [`encode`] / [`decode`] — format-specific I/O (feature-gated)
 //!
 //! # Derive macro integration
@@ -15,6 +17,8 @@
 //! `#[derive(Component)]` generates `serialize_component` and
 //! `deserialize_component` by default. Use `#[skip_serialization]` to
 //! opt out for components with non-serializable fields (e.g., GPU resources).
+//! Use `#[component(version = N)]` to bump the schema version after changing
+//! a component's fields.
 //!
 //! # Custom serialization
 //!
@@ -27,6 +31,7 @@ mod context;
 mod error;
 pub mod field;
 mod format;
+pub mod migration;
 mod prefab_io;
 pub mod value;
 
@@ -36,7 +41,11 @@ pub use field::{
     DeserializeField, DeserializeFieldFallback, SerializeField, SerializeFieldFallback,
 };
 pub use format::Format;
-pub use prefab_io::{SerializedComponent, SerializedEntity, SerializedPrefab};
+pub use migration::ComponentMigrationFn;
+pub use prefab_io::{
+    PREFAB_FORMAT_VERSION, SerializedComponent, SerializedEntity, SerializedPrefab,
+    SerializedResource, SerializedScene,
+};
 pub use value::Value;
 
 // Re-export format functions
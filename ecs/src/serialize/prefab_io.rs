@@ -1,20 +1,61 @@
-//! Serialized prefab data structures for file I/O.
+//! Serialized prefab and scene data structures for file I/O.
 //!
 //! A [`SerializedPrefab`] is the on-disk representation of an entity
-//! tree. It can be encoded to RON or bincode via the [`format`](super::format)
-//! module.
+//! tree. A [`SerializedScene`] holds every root tree of a world together with
+//! the resources that opted into serialization. Both can be encoded to RON or
+//! bincode via the [`format`](super::format) module.
+//!
+//! Files carry two kinds of version numbers: the container layout version
+//! ([`PREFAB_FORMAT_VERSION`]) and a schema version per component (see the
+//! [`migration`](super::migration) module). Files written before versioning
+//! was introduced read as version 0. Bincode is not self-describing, so old
+//! bincode prefabs must be read with [`SerializedPrefab::from_bytes`], which
+//! recognizes their layout.
 
 use serde::{Deserialize, Serialize};
 
+use super::error::DeserializeError;
+use super::format::Format;
 use super::value::Value;
 
+/// Current layout version of [`SerializedPrefab`] and [`SerializedScene`].
+///
+/// [`World::deserialize_prefab`](crate::World::deserialize_prefab) rejects
+/// files with a newer version.
+pub const PREFAB_FORMAT_VERSION: u32 = 1;
+
 /// A fully serialized prefab (entity tree), suitable for file I/O.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SerializedPrefab {
+    /// Layout version the prefab was written with. Missing in files older
+    /// than versioning, which read as 0.
+    #[serde(default)]
+    pub format_version: u32,
     /// Serialized entities in BFS order. Index 0 is the root.
     pub entities: Vec<SerializedEntity>,
 }
 
+impl Default for SerializedPrefab {
+    fn default() -> Self {
+        Self {
+            format_version: PREFAB_FORMAT_VERSION,
+            entities: Vec::new(),
+        }
+    }
+}
+
+impl SerializedPrefab {
+    /// Decode a prefab file in `format`, including bincode files written
+    /// before versioning, which read as version 0.
+    pub fn from_bytes(bytes: &[u8], format: Format) -> Result<Self, DeserializeError> {
+        #[cfg(feature = "serialize-bincode")]
+        if format == Format::Bincode {
+            return legacy::decode_bincode(bytes);
+        }
+        super::format::decode(bytes, format)
+    }
+}
+
 /// A single entity's serialized component data.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SerializedEntity {
@@ -33,6 +74,148 @@ pub struct SerializedEntity {
 pub struct SerializedComponent {
     /// The component type name (matches [`Component::NAME`](crate::Component::NAME)).
     pub type_name: String,
+    /// Schema version the data was written with
+    /// (matches [`Component::VERSION`](crate::Component::VERSION)).
+    #[serde(default)]
+    pub version: u32,
     /// The serialized field data.
     pub data: Value,
 }
+
+/// A fully serialized scene, suitable for file I/O.
+///
+/// Produced by [`World::serialize_scene`](crate::World::serialize_scene) and
+/// restored with [`World::deserialize_scene`](crate::World::deserialize_scene).
+/// Assets are never embedded: asset handles are stored as VFS paths and GPU
+/// resources as their manager names.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SerializedScene {
+    /// All scene entities. Each root is followed by its subtree in BFS order;
+    /// entity references between trees are remapped on load.
+    pub prefab: SerializedPrefab,
+    /// Indices into `prefab.entities` of the root entities.
+    pub roots: Vec<u32>,
+    /// Resources registered with
+    /// [`World::register_serializable_resource`](crate::World::register_serializable_resource).
+    pub resources: Vec<SerializedResource>,
+}
+
+/// A single resource's serialized data.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SerializedResource {
+    /// The name the resource type was registered under.
+    pub type_name: String,
+    /// The serialized resource data.
+    pub data: Value,
+}
+
+/// Bincode layout of prefabs written before versioning.
+#[cfg(feature = "serialize-bincode")]
+mod legacy {
+    use bincode::Options;
+    use serde::{Deserialize, Serialize};
+
+    use super::{SerializedComponent, SerializedEntity, SerializedPrefab};
+    use crate::serialize::error::DeserializeError;
+    use crate::serialize::value::Value;
+
+    #[derive(Serialize, Deserialize)]
+    pub(super) struct LegacyPrefab {
+        pub entities: Vec<LegacyEntity>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub(super) struct LegacyEntity {
+        pub entity_index: u32,
+        pub entity_spawn_tick: u64,
+        pub entity_flags: u32,
+        pub components: Vec<LegacyComponent>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub(super) struct LegacyComponent {
+        pub type_name: String,
+        pub data: Value,
+    }
+
+    /// Decode the current layout, falling back to the legacy one.
+    ///
+    /// Both attempts reject trailing bytes: a legacy file can parse as a
+    /// short prefix of the current layout, but never consumes it exactly.
+    pub(super) fn decode_bincode(bytes: &[u8]) -> Result<SerializedPrefab, DeserializeError> {
+        // `bincode::deserialize` settings, minus the tolerance for trailing bytes.
+        let options = || {
+            bincode::DefaultOptions::new()
+                .with_fixint_encoding()
+                .reject_trailing_bytes()
+        };
+        match options().deserialize::<SerializedPrefab>(bytes) {
+            Ok(prefab) => Ok(prefab),
+            Err(err) => options()
+                .deserialize::<LegacyPrefab>(bytes)
+                .map(SerializedPrefab::from)
+                .map_err(|_| DeserializeError::FormatError(err.to_string())),
+        }
+    }
+
+    impl From<LegacyPrefab> for SerializedPrefab {
+        fn from(legacy: LegacyPrefab) -> Self {
+            Self {
+                format_version: 0,
+                entities: legacy
+                    .entities
+                    .into_iter()
+                    .map(|entity| SerializedEntity {
+                        entity_index: entity.entity_index,
+                        entity_spawn_tick: entity.entity_spawn_tick,
+                        entity_flags: entity.entity_flags,
+                        components: entity
+                            .components
+                            .into_iter()
+                            .map(|component| SerializedComponent {
+                                type_name: component.type_name,
+                                version: 0,
+                                data: component.data,
+                            })
+                            .collect(),
+                    })
+                    .collect(),
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::serialize::format::Format;
+
+        #[test]
+        fn reads_unversioned_bincode_prefabs() {
+            let legacy = LegacyPrefab {
+                entities: vec![LegacyEntity {
+                    entity_index: 0,
+                    entity_spawn_tick: 3,
+                    entity_flags: 0,
+                    components: vec![LegacyComponent {
+                        type_name: "Name".into(),
+                        data: Value::String("root".into()),
+                    }],
+                }],
+            };
+            let bytes = bincode::serialize(&legacy).unwrap();
+            let prefab = SerializedPrefab::from_bytes(&bytes, Format::Bincode).unwrap();
+            assert_eq!(prefab.format_version, 0);
+            assert_eq!(prefab.entities.len(), 1);
+            assert_eq!(prefab.entities[0].entity_spawn_tick, 3);
+            assert_eq!(prefab.entities[0].components[0].version, 0);
+
+            let current = SerializedPrefab::default();
+            let bytes = bincode::serialize(&current).unwrap();
+            let prefab = SerializedPrefab::from_bytes(&bytes, Format::Bincode).unwrap();
+            assert_eq!(
+                prefab.format_version,
+                crate::serialize::PREFAB_FORMAT_VERSION
+            );
+        }
+    }
+}
@@ -23,6 +23,9 @@ pub enum Value {
     F32(f32),
     F64(f64),
     String(String),
+    /// A binary blob. Written as a base64 string in human-readable formats
+    /// and as a raw byte string in binary formats.
+    #[serde(with = "bytes_repr")]
     Bytes(Vec<u8>),
     List(Vec<Value>),
     Map(Vec<(String, Value)>),
@@ -40,6 +43,135 @@ pub enum Value {
     ArcRef(u32),
 }
 
+// ---------------------------------------------------------------------------
+// Bytes representation in encoded files
+// ---------------------------------------------------------------------------
+
+/// Compact encoding of [`Value::Bytes`].
+///
+/// Reading also accepts the number arrays written before blobs had a
+/// dedicated representation.
+mod bytes_repr {
+    use std::fmt;
+
+    use base64::Engine;
+    use base64::engine::general_purpose::STANDARD;
+    use serde::de::{self, SeqAccess, Visitor};
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&STANDARD.encode(bytes))
+        } else {
+            serializer.serialize_bytes(bytes)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(BytesVisitor)
+        } else {
+            deserializer.deserialize_byte_buf(BytesVisitor)
+        }
+    }
+
+    struct BytesVisitor;
+
+    impl<'de> Visitor<'de> for BytesVisitor {
+        type Value = Vec<u8>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a base64 string, byte string or list of bytes")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Vec<u8>, E> {
+            STANDARD.decode(v).map_err(E::custom)
+        }
+
+        fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Vec<u8>, E> {
+            Ok(v.to_vec())
+        }
+
+        fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Vec<u8>, E> {
+            Ok(v)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+            let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+            while let Some(byte) = seq.next_element()? {
+                bytes.push(byte);
+            }
+            Ok(bytes)
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Map helpers (used by component migrations)
+// ---------------------------------------------------------------------------
+
+impl Value {
+    /// Returns the field `key` of a [`Value::Map`].
+    ///
+    /// Returns `None` if the key is absent or `self` is not a map.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Map(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// Returns the field `key` of a [`Value::Map`] mutably.
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Value> {
+        match self {
+            Value::Map(entries) => entries.iter_mut().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// Sets the field `key` of a [`Value::Map`], returning the previous value.
+    ///
+    /// New fields are appended. Does nothing if `self` is not a map.
+    pub fn insert(&mut self, key: impl Into<String>, value: Value) -> Option<Value> {
+        let Value::Map(entries) = self else {
+            return None;
+        };
+        let key = key.into();
+        match entries.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, slot)) => Some(std::mem::replace(slot, value)),
+            None => {
+                entries.push((key, value));
+                None
+            }
+        }
+    }
+
+    /// Removes the field `key` from a [`Value::Map`], returning its value.
+    pub fn remove(&mut self, key: &str) -> Option<Value> {
+        let Value::Map(entries) = self else {
+            return None;
+        };
+        let index = entries.iter().position(|(k, _)| k == key)?;
+        Some(entries.remove(index).1)
+    }
+
+    /// Renames the field `from` of a [`Value::Map`] to `to`, keeping its position.
+    ///
+    /// Returns `false` if the field does not exist.
+    pub fn rename_field(&mut self, from: &str, to: impl Into<String>) -> bool {
+        let Value::Map(entries) = self else {
+            return false;
+        };
+        match entries.iter_mut().find(|(k, _)| k == from) {
+            Some((key, _)) => {
+                *key = to.into();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // to_value: T -> Value  (via custom serde::Serializer)
 // ---------------------------------------------------------------------------
@@ -522,6 +654,14 @@ impl<'de> serde::Deserializer<'de> for ValueDeserializer {
             Value::List(v) => visitor.visit_seq(ValueSeqAccess {
                 iter: v.into_iter(),
             }),
+            // Blobs deserialize into `Vec<u8>` and other byte sequences
+            Value::Bytes(v) => visitor.visit_seq(ValueSeqAccess {
+                iter: v
+                    .into_iter()
+                    .map(|b| Value::U64(b as u64))
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+            }),
             _ => Err(ValueError("expected list".into())),
         }
     }
@@ -819,4 +959,23 @@ mod tests {
         let restored: Outer = from_value(val).unwrap();
         assert_eq!(restored, o);
     }
+
+    #[test]
+    fn map_helpers() {
+        let mut map = Value::Map(vec![
+            ("a".into(), Value::I64(1)),
+            ("b".into(), Value::Bool(true)),
+        ]);
+        assert_eq!(map.get("a"), Some(&Value::I64(1)));
+        assert!(map.rename_field("a", "x"));
+        assert!(!map.rename_field("a", "y"));
+        assert_eq!(map.insert("x", Value::I64(2)), Some(Value::I64(1)));
+        assert_eq!(map.insert("c", Value::Null), None);
+        assert_eq!(map.remove("b"), Some(Value::Bool(true)));
+        assert_eq!(
+            map,
+            Value::Map(vec![("x".into(), Value::I64(2)), ("c".into(), Value::Null)])
+        );
+        assert_eq!(Value::I64(0).get("a"), None);
+    }
 }
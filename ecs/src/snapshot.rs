@@ -0,0 +1,115 @@
+//! Whole-world snapshots for rollback and replays.
+//!
+//! [`World::snapshot`] captures the complete simulation state of a world —
+//! entity allocator (including spawn ticks, free list and entity flags),
+//! component storages with their change ticks, the world tick and opted-in
+//! resources — into a [`WorldSnapshot`]. [`World::restore`] rolls the world
+//! back to that state. A snapshot can be restored any number of times.
+//!
+//! Components are captured through [`Clone`]. [`bytemuck::Pod`] components
+//! are `Copy`, so their storages are copied with a plain memcpy.
+//!
+//! # What is captured
+//!
+//! - Components registered with [`register_inspector`](World::register_inspector),
+//!   [`register_inspector_default`](World::register_inspector_default) or
+//!   [`register_snapshot_component`](World::register_snapshot_component).
+//! - Resources registered with [`register_snapshot_resource`](World::register_snapshot_resource).
+//!
+//! Other component storages (e.g. GPU handles) are not captured. On restore
+//! they keep their data only for entities that are alive both before and
+//! after the rollback. Lifecycle hooks and observers do not fire on restore.
+//!
+//! # Example
+//!
+//! ```ignore
+//! world.register_snapshot_resource::<Score>();
+//!
+//! let snapshot = world.snapshot();
+//! schedules.run_frame(&mut world, &runner, dt);
+//!
+//! // Roll back and re-simulate with corrected inputs
+//! world.restore(&snapshot);
+//! schedules.run_frame(&mut world, &runner, dt);
+//! ```
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+
+use crate::entity::Entities;
+use crate::resource::Resource;
+use crate::sparse_set::StorageSnapshot;
+use crate::world::World;
+
+/// A captured copy of a [`World`]'s simulation state.
+///
+/// Created by [`World::snapshot`] and applied with [`World::restore`].
+pub struct WorldSnapshot {
+    /// World tick at capture time.
+    pub(crate) tick: u64,
+    /// Entity allocator state, including entity flags.
+    pub(crate) entities: Entities,
+    /// Captured component storages, by component type.
+    pub(crate) components: HashMap<TypeId, Box<dyn StorageSnapshot>>,
+    /// Captured opted-in resources, by resource type. `None` if the
+    /// resource was not present.
+    pub(crate) resources: HashMap<TypeId, Option<Box<dyn Any + Send + Sync>>>,
+}
+
+impl WorldSnapshot {
+    /// World tick at the time the snapshot was taken.
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Number of entities alive at the time the snapshot was taken.
+    pub fn entity_count(&self) -> u32 {
+        self.entities.count()
+    }
+}
+
+/// Type-erased resource capture registered via
+/// [`World::register_snapshot_resource`].
+#[derive(Clone, Copy)]
+pub(crate) struct ResourceSnapshotter {
+    /// Clones the resource, or returns `None` if it is not present.
+    pub snapshot_fn: fn(&World) -> Option<Box<dyn Any + Send + Sync>>,
+    /// Overwrites, inserts or removes the resource to match the capture.
+    pub restore_fn: fn(&mut World, Option<&(dyn Any + Send + Sync)>),
+}
+
+impl ResourceSnapshotter {
+    /// Creates the snapshotter for resource type `T`.
+    pub fn of<T: Resource + Clone>() -> Self {
+        Self {
+            snapshot_fn: snapshot_resource::<T>,
+            restore_fn: restore_resource::<T>,
+        }
+    }
+}
+
+fn snapshot_resource<T: Resource + Clone>(world: &World) -> Option<Box<dyn Any + Send + Sync>> {
+    if !world.has_resource::<T>() {
+        return None;
+    }
+    Some(Box::new(T::clone(&world.resource::<T>())))
+}
+
+/// Restores a captured resource.
+///
+/// Existing resources are overwritten in place so that external handles
+/// returned by [`World::insert_resource`] stay valid.
+fn restore_resource<T: Resource + Clone>(
+    world: &mut World,
+    value: Option<&(dyn Any + Send + Sync)>,
+) {
+    match value.and_then(|v| v.downcast_ref::<T>()) {
+        Some(value) if world.has_resource::<T>() => *world.resource_mut::<T>() = value.clone(),
+        Some(value) => {
+            world.insert_resource(value.clone());
+        }
+        None => {
+            world.remove_resource::<T>();
+        }
+    }
+}
@@ -97,7 +97,7 @@ pub(crate) type SerializeComponentFn =
 /// Type-erased deserialize: deserializes a component and inserts it on an entity.
 pub(crate) type DeserializeComponentFn = fn(
     Entity,
-    &crate::serialize::Value,
+    &crate::serialize::SerializedComponent,
     &mut crate::serialize::DeserializeContext<'_>,
 ) -> Result<(), crate::serialize::DeserializeError>;
 
@@ -417,6 +417,23 @@ impl<T: 'static> Default for SparseSetInner<T> {
     }
 }
 
+impl<T: Clone + 'static> Clone for SparseSetInner<T> {
+    /// Clones the component values together with their change ticks.
+    ///
+    /// For `Copy` (e.g. [`bytemuck::Pod`]) components the dense array is
+    /// copied with a single memcpy.
+    fn clone(&self) -> Self {
+        Self {
+            sparse: self.sparse.clone(),
+            dense: self.dense.clone(),
+            entities: self.entities.clone(),
+            ticks_added: self.ticks_added.clone(),
+            ticks_changed: self.ticks_changed.clone(),
+            membership: self.membership.clone(),
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // ErasedSparseSet — trait for type-erased sparse set operations
 // ---------------------------------------------------------------------------
@@ -441,6 +458,9 @@ pub(crate) trait ErasedSparseSet: Send + Sync {
     /// Checks if the component was added since (strictly after) `since_tick`.
     fn added_since(&self, entity_index: u32, since_tick: u64) -> bool;
 
+    /// Returns the entity indices that have this component, in dense order.
+    fn entity_indices(&self) -> &[u32];
+
     /// Downcast to `&dyn Any` for typed access.
     fn as_any(&self) -> &dyn Any;
 
@@ -469,6 +489,10 @@ impl<T: Send + Sync + 'static> ErasedSparseSet for SparseSetInner<T> {
         SparseSetInner::added_since(self, entity_index, since_tick)
     }
 
+    fn entity_indices(&self) -> &[u32] {
+        SparseSetInner::entities(self)
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -511,6 +535,9 @@ pub(crate) struct ComponentStorage {
     /// Type-erased component metadata (inspection, serialization, cloning, etc.).
     /// Present only for components registered via `register_inspector` / `register_inspector_default`.
     pub(crate) meta: Option<ComponentMeta>,
+    /// Captures the storage contents for [`World::snapshot`](crate::World::snapshot).
+    /// `None` if the component type is not known to be `Clone`.
+    pub(crate) snapshot_fn: Option<SnapshotStorageFn>,
 }
 
 impl ComponentStorage {
@@ -525,6 +552,7 @@ impl ComponentStorage {
             on_remove: None,
             required_components: Vec::new(),
             meta: None,
+            snapshot_fn: None,
         }
     }
 
@@ -609,6 +637,72 @@ impl ComponentStorage {
     pub fn clear_removed(&mut self) {
         self.removed_ticks.clear();
     }
+
+    /// Enables [`snapshot`](Self::snapshot) for this storage.
+    ///
+    /// # Panics
+    ///
+    /// Panics (on snapshot) if `T` is not the stored component type.
+    pub(crate) fn enable_snapshot<T: Clone + Send + Sync + 'static>(&mut self) {
+        self.snapshot_fn = Some(snapshot_storage::<T>);
+    }
+
+    /// Captures the component values, change ticks and removal records.
+    ///
+    /// Returns `None` if snapshots are not enabled for this storage.
+    pub(crate) fn snapshot(&self) -> Option<Box<dyn StorageSnapshot>> {
+        self.snapshot_fn.map(|f| f(self))
+    }
+
+    /// Removes the components of every entity index for which `keep`
+    /// returns `false`, without recording removals.
+    pub(crate) fn retain_untyped(&mut self, mut keep: impl FnMut(u32) -> bool) {
+        let dropped: Vec<u32> = self
+            .inner
+            .entity_indices()
+            .iter()
+            .copied()
+            .filter(|&index| !keep(index))
+            .collect();
+        for index in dropped {
+            self.inner.remove(index);
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Storage snapshots
+// ---------------------------------------------------------------------------
+
+/// Type-erased capture function stored in [`ComponentStorage`].
+pub(crate) type SnapshotStorageFn = fn(&ComponentStorage) -> Box<dyn StorageSnapshot>;
+
+/// A captured copy of one component storage.
+pub(crate) trait StorageSnapshot: Send + Sync {
+    /// Overwrite `storage` with the captured contents.
+    fn restore(&self, storage: &mut ComponentStorage);
+}
+
+/// Captured contents of a [`SparseSetInner<T>`] and its removal records.
+struct TypedStorageSnapshot<T: 'static> {
+    set: SparseSetInner<T>,
+    removed_ticks: Vec<(u32, u64)>,
+}
+
+impl<T: Clone + Send + Sync + 'static> StorageSnapshot for TypedStorageSnapshot<T> {
+    fn restore(&self, storage: &mut ComponentStorage) {
+        *storage.typed_mut::<T>() = self.set.clone();
+        storage.removed_ticks.clone_from(&self.removed_ticks);
+    }
+}
+
+fn snapshot_storage<T: Clone + Send + Sync + 'static>(
+    storage: &ComponentStorage,
+) -> Box<dyn StorageSnapshot> {
+    Box::new(TypedStorageSnapshot {
+        set: storage.typed::<T>().clone(),
+        removed_ticks: storage.removed_ticks.clone(),
+    })
 }
 
 /// Shared read access to a component storage.
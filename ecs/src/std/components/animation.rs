@@ -0,0 +1,234 @@
+use std::sync::Arc;
+
+use redlilium_core::math::Mat4;
+use redlilium_core::scene::Animation;
+
+use crate::Entity;
+
+/// What a clip does when its playhead reaches the end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RepeatMode {
+    /// Wrap around to the start.
+    #[default]
+    Loop,
+    /// Stop at the last keyframe and hold the final pose.
+    Once,
+}
+
+/// A clip currently playing on an [`AnimationPlayer`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ActiveClip {
+    /// Index into [`AnimationPlayer::clips`].
+    pub clip: usize,
+    /// Playhead position in seconds.
+    pub time: f32,
+    /// Playback speed multiplier (negative plays backwards).
+    pub speed: f32,
+    /// Current blend weight.
+    pub weight: f32,
+    /// Weight the clip is fading towards.
+    pub target_weight: f32,
+    /// Weight change per second while fading. Zero applies `target_weight`
+    /// immediately.
+    pub fade_rate: f32,
+    /// End-of-clip behaviour.
+    pub repeat: RepeatMode,
+}
+
+/// Plays keyframe [`Animation`]s on a hierarchy of entities.
+///
+/// Channels target scene nodes by index; `targets` maps those node indices
+/// to the entities whose [`Transform`](crate::Transform) is animated.
+/// [`spawn_scene`](crate::spawn_scene) fills both lists for glTF scenes.
+///
+/// Several clips can play at once. Their samples are blended by weight, and
+/// [`crossfade`](Self::crossfade) fades one clip in while fading the others
+/// out. The [`AnimateTransforms`](crate::systems::AnimateTransforms) system
+/// advances the player and writes the sampled poses.
+#[derive(Debug, Clone, Default, crate::Component)]
+#[skip_serialization]
+pub struct AnimationPlayer {
+    /// Available clips.
+    pub clips: Vec<Arc<Animation>>,
+    /// Entity animated by each node index referenced by the clips.
+    pub targets: Vec<Entity>,
+    /// Whether playback is paused (poses are still applied).
+    pub paused: bool,
+    active: Vec<ActiveClip>,
+}
+
+impl AnimationPlayer {
+    /// Create a player for `clips` animating `targets`.
+    pub fn new(clips: Vec<Arc<Animation>>, targets: Vec<Entity>) -> Self {
+        Self {
+            clips,
+            targets,
+            paused: false,
+            active: Vec::new(),
+        }
+    }
+
+    /// Find a clip index by animation name.
+    pub fn clip_by_name(&self, name: &str) -> Option<usize> {
+        self.clips
+            .iter()
+            .position(|c| c.name.as_deref() == Some(name))
+    }
+
+    /// Play `clip` at full weight from the start, stopping all other clips.
+    pub fn play(&mut self, clip: usize) -> &mut ActiveClip {
+        self.active.clear();
+        self.start(clip, 1.0, 1.0, 0.0)
+    }
+
+    /// Fade `clip` in over `duration` seconds while fading all other clips
+    /// out. The clip restarts unless it is already playing.
+    pub fn crossfade(&mut self, clip: usize, duration: f32) -> &mut ActiveClip {
+        let rate = if duration > 0.0 { 1.0 / duration } else { 0.0 };
+        for active in &mut self.active {
+            if active.clip != clip {
+                active.target_weight = 0.0;
+                active.fade_rate = rate;
+            }
+        }
+        match self.active.iter().position(|a| a.clip == clip) {
+            Some(index) => {
+                let active = &mut self.active[index];
+                active.target_weight = 1.0;
+                active.fade_rate = rate;
+                active
+            }
+            None => {
+                let weight = if rate > 0.0 { 0.0 } else { 1.0 };
+                self.start(clip, weight, 1.0, rate)
+            }
+        }
+    }
+
+    /// Play `clip` alongside the current clips with a fixed blend `weight`.
+    /// Updates the weight if the clip is already playing.
+    pub fn blend(&mut self, clip: usize, weight: f32) -> &mut ActiveClip {
+        match self.active.iter().position(|a| a.clip == clip) {
+            Some(index) => {
+                let active = &mut self.active[index];
+                active.weight = weight;
+                active.target_weight = weight;
+                active.fade_rate = 0.0;
+                active
+            }
+            None => self.start(clip, weight, weight, 0.0),
+        }
+    }
+
+    /// Stop `clip` immediately.
+    pub fn stop(&mut self, clip: usize) {
+        self.active.retain(|a| a.clip != clip);
+    }
+
+    /// Stop all clips.
+    pub fn stop_all(&mut self) {
+        self.active.clear();
+    }
+
+    /// Whether `clip` is currently playing.
+    pub fn is_playing(&self, clip: usize) -> bool {
+        self.active.iter().any(|a| a.clip == clip)
+    }
+
+    /// The playing clip state for `clip`, if any.
+    pub fn active_clip(&self, clip: usize) -> Option<&ActiveClip> {
+        self.active.iter().find(|a| a.clip == clip)
+    }
+
+    /// Mutable playing clip state for `clip` (speed, repeat mode, time).
+    pub fn active_clip_mut(&mut self, clip: usize) -> Option<&mut ActiveClip> {
+        self.active.iter_mut().find(|a| a.clip == clip)
+    }
+
+    /// All playing clips.
+    pub fn active_clips(&self) -> &[ActiveClip] {
+        &self.active
+    }
+
+    /// Advance all playing clips by `dt` seconds: move playheads, apply
+    /// fades, and drop clips that have faded out.
+    pub fn advance(&mut self, dt: f32) {
+        if self.paused {
+            return;
+        }
+        let clips = &self.clips;
+        self.active.retain_mut(|active| {
+            let duration = clips.get(active.clip).map_or(0.0, |c| c.duration());
+            active.time += dt * active.speed;
+            match active.repeat {
+                RepeatMode::Loop if duration > 0.0 => {
+                    active.time = active.time.rem_euclid(duration);
+                }
+                _ => active.time = active.time.clamp(0.0, duration),
+            }
+
+            if active.fade_rate > 0.0 {
+                let step = active.fade_rate * dt;
+                if active.weight < active.target_weight {
+                    active.weight = (active.weight + step).min(active.target_weight);
+                } else {
+                    active.weight = (active.weight - step).max(active.target_weight);
+                }
+            } else {
+                active.weight = active.target_weight;
+            }
+
+            active.weight > 0.0 || active.target_weight > 0.0
+        });
+    }
+
+    fn start(
+        &mut self,
+        clip: usize,
+        weight: f32,
+        target_weight: f32,
+        fade_rate: f32,
+    ) -> &mut ActiveClip {
+        self.active.retain(|a| a.clip != clip);
+        self.active.push(ActiveClip {
+            clip,
+            time: 0.0,
+            speed: 1.0,
+            weight,
+            target_weight,
+            fade_rate,
+            repeat: RepeatMode::Loop,
+        });
+        self.active.last_mut().unwrap()
+    }
+}
+
+/// Skeleton binding of a skinned mesh entity.
+///
+/// `joints[i]` is the entity driving joint `i` in the mesh's joint indices.
+/// The [`UpdateSkinJoints`](crate::systems::UpdateSkinJoints) system fills
+/// `joint_matrices` each frame (mesh space, `inverse(mesh) * joint *
+/// inverse_bind`); rendering uploads them for GPU skinning.
+#[derive(Debug, Clone, crate::Component)]
+#[skip_serialization]
+pub struct SkinnedMesh {
+    /// Joint entities, in joint index order.
+    pub joints: Vec<Entity>,
+    /// Inverse bind matrix for each joint.
+    pub inverse_bind_matrices: Arc<Vec<Mat4>>,
+    /// Skinning matrix for each joint, computed from the joints' global
+    /// transforms.
+    pub joint_matrices: Vec<Mat4>,
+}
+
+impl SkinnedMesh {
+    /// Create a skin from joint entities and their inverse bind matrices.
+    pub fn new(joints: Vec<Entity>, inverse_bind_matrices: Arc<Vec<Mat4>>) -> Self {
+        let joint_matrices = vec![Mat4::identity(); joints.len()];
+        Self {
+            joints,
+            inverse_bind_matrices,
+            joint_matrices,
+        }
+    }
+}
@@ -127,6 +127,89 @@ impl Default for SpotLight {
     }
 }
 
+/// Shadow casting parameters for a light.
+///
+/// A [`DirectionalLight`], [`PointLight`] or [`SpotLight`] casts shadows only
+/// when its entity also has this component. Directional lights use
+/// `cascade_count` cascades fitted to the first camera's view frustum up to
+/// `max_distance`; point lights render six cube faces and spot lights a single
+/// frustum, both reaching to the light's range (or `max_distance` when the
+/// range is infinite).
+#[derive(Debug, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable, crate::Component)]
+#[repr(C)]
+pub struct ShadowSettings {
+    /// Resolution in texels of each shadow map view (cascade, cube face or spot).
+    pub resolution: u32,
+    /// Number of directional light cascades (1 to 4). Ignored by point and spot lights.
+    pub cascade_count: u32,
+    /// Furthest distance from the camera (directional) or the light (point/spot
+    /// with infinite range) that receives shadows.
+    pub max_distance: f32,
+    /// Blend between uniform (0) and logarithmic (1) cascade splits.
+    pub cascade_split_lambda: f32,
+    /// Near plane of point and spot light projections.
+    pub near: f32,
+    /// Constant depth bias applied when comparing against the shadow map.
+    pub depth_bias: f32,
+    /// Receiver offset along the surface normal, in shadow map texels.
+    pub normal_bias: f32,
+    /// PCF kernel radius in texels (0 = single hardware-filtered tap).
+    pub pcf_radius: f32,
+}
+
+impl ShadowSettings {
+    /// Maximum number of directional light cascades.
+    pub const MAX_CASCADES: u32 = 4;
+
+    /// Set the per-view shadow map resolution.
+    #[must_use]
+    pub fn with_resolution(mut self, resolution: u32) -> Self {
+        self.resolution = resolution;
+        self
+    }
+
+    /// Set the number of directional light cascades.
+    #[must_use]
+    pub fn with_cascades(mut self, cascade_count: u32) -> Self {
+        self.cascade_count = cascade_count;
+        self
+    }
+
+    /// Set the maximum shadow distance.
+    #[must_use]
+    pub fn with_max_distance(mut self, max_distance: f32) -> Self {
+        self.max_distance = max_distance;
+        self
+    }
+
+    /// Set the PCF kernel radius in texels.
+    #[must_use]
+    pub fn with_pcf_radius(mut self, pcf_radius: f32) -> Self {
+        self.pcf_radius = pcf_radius;
+        self
+    }
+
+    /// Number of cascades clamped to `1..=MAX_CASCADES`.
+    pub fn clamped_cascade_count(&self) -> u32 {
+        self.cascade_count.clamp(1, Self::MAX_CASCADES)
+    }
+}
+
+impl Default for ShadowSettings {
+    fn default() -> Self {
+        Self {
+            resolution: 2048,
+            cascade_count: 4,
+            max_distance: 100.0,
+            cascade_split_lambda: 0.75,
+            near: 0.05,
+            depth_bias: 0.0005,
+            normal_bias: 1.5,
+            pcf_radius: 1.0,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -151,4 +234,21 @@ mod tests {
         assert_eq!(light.inner_cone_angle, std::f32::consts::FRAC_PI_8);
         assert_eq!(light.outer_cone_angle, std::f32::consts::FRAC_PI_4);
     }
+
+    #[test]
+    fn shadow_cascade_count_is_clamped() {
+        assert_eq!(ShadowSettings::default().clamped_cascade_count(), 4);
+        assert_eq!(
+            ShadowSettings::default()
+                .with_cascades(0)
+                .clamped_cascade_count(),
+            1
+        );
+        assert_eq!(
+            ShadowSettings::default()
+                .with_cascades(9)
+                .clamped_cascade_count(),
+            ShadowSettings::MAX_CASCADES
+        );
+    }
 }
@@ -1,3 +1,4 @@
+mod animation;
 mod camera;
 mod free_fly_camera;
 #[cfg(feature = "rendering")]
@@ -5,17 +6,21 @@ pub(crate) mod grid;
 mod hierarchy;
 mod light;
 mod name;
+mod prefab_instance;
 mod transform;
 mod visibility;
 mod window_input;
 
+pub use animation::{ActiveClip, AnimationPlayer, RepeatMode, SkinnedMesh};
 pub use camera::Camera;
 pub use free_fly_camera::FreeFlyCamera;
 #[cfg(feature = "rendering")]
 pub use grid::GridConfig;
 pub use hierarchy::{Children, Parent};
-pub use light::{DirectionalLight, PointLight, SpotLight};
+pub use light::{DirectionalLight, PointLight, ShadowSettings, SpotLight};
 pub use name::Name;
+pub(crate) use prefab_instance::track_prefab_instances;
+pub use prefab_instance::{PrefabInstance, PrefabInstanceIndex};
 pub use transform::{GlobalTransform, Transform};
 pub use visibility::Visibility;
 pub use window_input::WindowInput;
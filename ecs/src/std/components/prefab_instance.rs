@@ -0,0 +1,91 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{Entity, World};
+
+/// Marks the root of an entity tree spawned from a prefab file.
+///
+/// `source` is the VFS path of the prefab. When the file changes on disk and
+/// hot reload is enabled,
+/// [`SyncPrefabInstances`](crate::std::rendering::SyncPrefabInstances)
+/// rebuilds the tree from the new contents, keeping the root entity with its
+/// [`Transform`](crate::Transform) and parent.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, crate::Component)]
+pub struct PrefabInstance {
+    /// VFS path of the prefab file.
+    pub source: String,
+}
+
+impl PrefabInstance {
+    /// Create a prefab instance marker for the given prefab path.
+    pub fn new(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+        }
+    }
+}
+
+/// Resource indexing [`PrefabInstance`] roots by prefab path.
+///
+/// Kept up to date by component hooks installed by
+/// [`register_std_components`](crate::register_std_components), and rolled
+/// back together with the world by [`World::restore`].
+#[derive(Debug, Clone, Default)]
+pub struct PrefabInstanceIndex {
+    instances: HashMap<String, HashSet<Entity>>,
+}
+
+impl PrefabInstanceIndex {
+    /// Iterate over the instance roots of the prefab at `source`.
+    pub fn instances(&self, source: &str) -> impl Iterator<Item = Entity> + '_ {
+        self.instances.get(source).into_iter().flatten().copied()
+    }
+
+    /// Iterate over the prefab paths with at least one instance.
+    pub fn sources(&self) -> impl Iterator<Item = &str> {
+        self.instances.keys().map(String::as_str)
+    }
+}
+
+/// Insert the [`PrefabInstanceIndex`] resource and the hooks maintaining it.
+pub(crate) fn track_prefab_instances(world: &mut World) {
+    if world.has_resource::<PrefabInstanceIndex>() {
+        return;
+    }
+    world.insert_resource(PrefabInstanceIndex::default());
+    world.register_snapshot_resource::<PrefabInstanceIndex>();
+    world
+        .set_on_insert::<PrefabInstance>(index_instance)
+        .set_on_replace::<PrefabInstance>(unindex_instance)
+        .set_on_remove::<PrefabInstance>(unindex_instance);
+}
+
+fn index_instance(world: &mut World, entity: Entity) {
+    let Some(source) = world
+        .get::<PrefabInstance>(entity)
+        .map(|i| i.source.clone())
+    else {
+        return;
+    };
+    world
+        .resource_mut::<PrefabInstanceIndex>()
+        .instances
+        .entry(source)
+        .or_default()
+        .insert(entity);
+}
+
+fn unindex_instance(world: &mut World, entity: Entity) {
+    let Some(source) = world
+        .get::<PrefabInstance>(entity)
+        .map(|i| i.source.clone())
+    else {
+        return;
+    };
+    let mut index = world.resource_mut::<PrefabInstanceIndex>();
+    if let Some(instances) = index.instances.get_mut(&source) {
+        instances.remove(&entity);
+        if instances.is_empty() {
+            index.instances.remove(&source);
+        }
+    }
+}
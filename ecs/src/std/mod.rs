@@ -25,4 +25,7 @@ pub use hierarchy::{
 pub use spawn::spawn_scene;
 #[cfg(feature = "rendering")]
 pub use systems::DrawGrid;
-pub use systems::{UpdateCameraMatrices, UpdateFreeFlyCamera, UpdateGlobalTransforms};
+pub use systems::{
+    AnimateTransforms, UpdateCameraMatrices, UpdateFreeFlyCamera, UpdateGlobalTransforms,
+    UpdateSkinJoints,
+};
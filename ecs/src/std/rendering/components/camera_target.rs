@@ -2,13 +2,17 @@
 
 use std::sync::Arc;
 
-use redlilium_graphics::{Buffer, Texture};
+use redlilium_graphics::{
+    Buffer, GraphicsDevice, GraphicsError, Texture, TextureDescriptor, TextureFormat, TextureUsage,
+};
 
 /// Per-entity GPU uniform buffers for transform data (VP + model matrix).
 ///
-/// Holds the forward-pass uniform buffer and an optional entity-index pass
-/// buffer. The [`UpdatePerEntityUniforms`](super::super::UpdatePerEntityUniforms)
-/// system writes camera and transform data into these buffers each frame.
+/// Holds the forward-pass uniform buffer, an optional entity-index pass
+/// buffer and, for skinned meshes, a joint matrix storage buffer. The
+/// [`UpdatePerEntityUniforms`](super::super::UpdatePerEntityUniforms)
+/// system writes camera, transform and joint data into these buffers each
+/// frame.
 #[derive(Debug, Clone, crate::Component)]
 #[skip_serialization]
 pub struct PerEntityBuffers {
@@ -16,6 +20,9 @@ pub struct PerEntityBuffers {
     pub forward_buffer: Arc<Buffer>,
     /// Entity-index pass uniform buffer (VP + model + entity index), if present.
     pub entity_index_buffer: Option<Arc<Buffer>>,
+    /// Joint matrix storage buffer for GPU skinning, if present. Filled from
+    /// the entity's [`SkinnedMesh`](crate::SkinnedMesh).
+    pub joint_buffer: Option<Arc<Buffer>>,
 }
 
 impl PerEntityBuffers {
@@ -24,6 +31,7 @@ impl PerEntityBuffers {
         Self {
             forward_buffer,
             entity_index_buffer: None,
+            joint_buffer: None,
         }
     }
 
@@ -35,6 +43,17 @@ impl PerEntityBuffers {
         Self {
             forward_buffer,
             entity_index_buffer: Some(entity_index_buffer),
+            joint_buffer: None,
+        }
+    }
+
+    /// Create per-entity buffers with a forward pass and a joint matrix
+    /// buffer for skinning.
+    pub fn with_joints(forward_buffer: Arc<Buffer>, joint_buffer: Arc<Buffer>) -> Self {
+        Self {
+            forward_buffer,
+            entity_index_buffer: None,
+            joint_buffer: Some(joint_buffer),
         }
     }
 }
@@ -45,6 +64,10 @@ impl PerEntityBuffers {
 /// that already has a [`Camera`](crate::Camera) component. The forward render
 /// system will create a graphics pass for each camera that has a `CameraTarget`.
 ///
+/// A target with a [`GBuffer`] is rendered by the
+/// [`DeferredRenderSystem`](crate::DeferredRenderSystem) instead; switch a
+/// camera between forward and deferred rendering by replacing its target.
+///
 /// The color and depth textures must be created with `TextureUsage::RENDER_ATTACHMENT`.
 #[derive(Debug, Clone, crate::Component)]
 #[skip_serialization]
@@ -55,6 +78,8 @@ pub struct CameraTarget {
     pub depth: Arc<Texture>,
     /// Clear color (RGBA) applied at the start of the render pass.
     pub clear_color: [f32; 4],
+    /// G-buffer attachments for deferred rendering, if any.
+    pub gbuffer: Option<GBuffer>,
 }
 
 impl CameraTarget {
@@ -64,6 +89,92 @@ impl CameraTarget {
             color,
             depth,
             clear_color,
+            gbuffer: None,
         }
     }
+
+    /// Render this target with the deferred path using `gbuffer`.
+    ///
+    /// The G-buffer must match the size of the color and depth textures.
+    pub fn with_gbuffer(mut self, gbuffer: GBuffer) -> Self {
+        self.gbuffer = Some(gbuffer);
+        self
+    }
+
+    /// Whether this target is rendered with the deferred path.
+    pub fn is_deferred(&self) -> bool {
+        self.gbuffer.is_some()
+    }
+}
+
+/// G-buffer attachments written by the deferred geometry pass.
+///
+/// Layout matches the `fs_gbuffer` entry point of the standard PBR shader:
+///
+/// | Attachment | RGB | A |
+/// |------------|-----|---|
+/// | `albedo` | Base color | Ambient occlusion |
+/// | `normal_metallic` | World normal (zero = background) | Metallic |
+/// | `position_roughness` | World position | Roughness |
+/// | `emissive` | Emitted radiance | Unused |
+#[derive(Debug, Clone)]
+pub struct GBuffer {
+    /// Base color and occlusion.
+    pub albedo: Arc<Texture>,
+    /// World-space normal and metallic.
+    pub normal_metallic: Arc<Texture>,
+    /// World-space position and roughness.
+    pub position_roughness: Arc<Texture>,
+    /// Emitted radiance.
+    pub emissive: Arc<Texture>,
+}
+
+impl GBuffer {
+    /// Format of the albedo attachment.
+    pub const ALBEDO_FORMAT: TextureFormat = TextureFormat::Rgba8UnormSrgb;
+    /// Format of the normal, position and emissive attachments.
+    pub const DATA_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+
+    /// Color formats of the attachments, in render target order.
+    pub const FORMATS: [TextureFormat; 4] = [
+        Self::ALBEDO_FORMAT,
+        Self::DATA_FORMAT,
+        Self::DATA_FORMAT,
+        Self::DATA_FORMAT,
+    ];
+
+    /// Create G-buffer attachments of the given size.
+    pub fn new(
+        device: &Arc<GraphicsDevice>,
+        width: u32,
+        height: u32,
+    ) -> Result<Self, GraphicsError> {
+        let attachment = |format: TextureFormat, label: &str| {
+            device.create_texture(
+                &TextureDescriptor::new_2d(
+                    width,
+                    height,
+                    format,
+                    TextureUsage::RENDER_ATTACHMENT | TextureUsage::TEXTURE_BINDING,
+                )
+                .with_label(label),
+            )
+        };
+        Ok(Self {
+            albedo: attachment(Self::ALBEDO_FORMAT, "gbuffer_albedo")?,
+            normal_metallic: attachment(Self::DATA_FORMAT, "gbuffer_normal_metallic")?,
+            position_roughness: attachment(Self::DATA_FORMAT, "gbuffer_position_roughness")?,
+            emissive: attachment(Self::DATA_FORMAT, "gbuffer_emissive")?,
+        })
+    }
+
+    /// The attachments in render target order.
+    pub fn attachments(&self) -> [&Arc<Texture>; 4] {
+        [
+            &self.albedo,
+            &self.normal_metallic,
+            &self.position_roughness,
+            &self.emissive,
+        ]
+    }
 }
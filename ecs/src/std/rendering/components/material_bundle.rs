@@ -3,6 +3,7 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use redlilium_core::material::AlphaMode;
 use redlilium_graphics::MaterialInstance;
 
 use super::super::resources::TextureManager;
@@ -57,6 +58,33 @@ impl RenderPassType {
     }
 }
 
+/// Queue a draw is rendered in within a pass, from its material's
+/// [`AlphaMode`].
+///
+/// Queues are drawn in order: opaque and alpha-tested draws front-to-back,
+/// then blended draws back-to-front.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub enum RenderQueue {
+    /// Opaque surfaces.
+    #[default]
+    Opaque,
+    /// Alpha-tested surfaces, discarding fragments below a cutoff.
+    Mask,
+    /// Alpha-blended surfaces, drawn without depth writes.
+    Blend,
+}
+
+impl RenderQueue {
+    /// The queue of materials with `alpha_mode`.
+    pub fn from_alpha_mode(alpha_mode: AlphaMode) -> Self {
+        match alpha_mode {
+            AlphaMode::Opaque => Self::Opaque,
+            AlphaMode::Mask { .. } => Self::Mask,
+            AlphaMode::Blend => Self::Blend,
+        }
+    }
+}
+
 /// A collection of [`MaterialInstance`]s for different render passes.
 ///
 /// All instances in a bundle share the same binding groups (textures, uniform
@@ -122,6 +150,73 @@ impl MaterialBundle {
     pub fn label(&self) -> Option<&str> {
         self.label.as_deref()
     }
+
+    /// Build a copy of this bundle with every instance of `old` switched to
+    /// `new`, keeping binding groups and labels.
+    ///
+    /// Returns `None` if no pass uses `old`. Used when a material pipeline
+    /// is recompiled after a shader change.
+    pub fn with_material_replaced(
+        &self,
+        old: &Arc<redlilium_graphics::Material>,
+        new: &Arc<redlilium_graphics::Material>,
+    ) -> Option<Self> {
+        if !self
+            .passes
+            .values()
+            .any(|instance| Arc::ptr_eq(instance.material(), old))
+        {
+            return None;
+        }
+
+        let mut bundle = self.clone();
+        for instance in bundle.passes.values_mut() {
+            if !Arc::ptr_eq(instance.material(), old) {
+                continue;
+            }
+            let mut replaced = MaterialInstance::new(Arc::clone(new));
+            replaced.set_binding_groups(instance.binding_groups().to_vec());
+            if let Some(label) = instance.label() {
+                replaced = replaced.with_label(label);
+            }
+            *instance = Arc::new(replaced);
+        }
+        Some(bundle)
+    }
+
+    /// Build a copy of this bundle with its shared binding groups swapped
+    /// for `bindings`, keeping every other group.
+    ///
+    /// Entities built from copies of one bundle share its material groups,
+    /// so the render systems can draw them in one instanced batch.
+    pub fn with_shared_bindings_replaced(
+        &self,
+        bindings: Vec<Arc<redlilium_graphics::BindingGroup>>,
+    ) -> Self {
+        let mut bundle = self.clone();
+        for instance in bundle.passes.values_mut() {
+            let groups = instance
+                .binding_groups()
+                .iter()
+                .map(|group| {
+                    self.shared_bindings
+                        .iter()
+                        .position(|shared| Arc::ptr_eq(shared, group))
+                        .and_then(|index| bindings.get(index))
+                        .unwrap_or(group)
+                        .clone()
+                })
+                .collect();
+            let mut replaced = MaterialInstance::new(Arc::clone(instance.material()));
+            replaced.set_binding_groups(groups);
+            if let Some(label) = instance.label() {
+                replaced = replaced.with_label(label);
+            }
+            *instance = Arc::new(replaced);
+        }
+        bundle.shared_bindings = bindings;
+        bundle
+    }
 }
 
 impl Default for MaterialBundle {
@@ -2,10 +2,14 @@
 
 mod camera_target;
 mod material_bundle;
+mod post_process;
 mod render_material;
 mod render_mesh;
+mod skybox;
 
-pub use camera_target::{CameraTarget, PerEntityBuffers};
-pub use material_bundle::{MaterialBundle, RenderPassType};
+pub use camera_target::{CameraTarget, GBuffer, PerEntityBuffers};
+pub use material_bundle::{MaterialBundle, RenderPassType, RenderQueue};
+pub use post_process::{PostEffect, PostProcessStack, ToneMapping};
 pub use render_material::RenderMaterial;
 pub use render_mesh::RenderMesh;
+pub use skybox::Skybox;
@@ -0,0 +1,75 @@
+//! Post-processing stack component.
+
+use std::sync::Arc;
+
+use redlilium_graphics::Texture;
+
+/// Tone mapping curve compressing HDR color into display range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ToneMapping {
+    /// ACES filmic curve (Stephen Hill's RRT + ODT fit).
+    #[default]
+    Aces,
+    /// AgX with the default look; desaturates bright highlights more gently.
+    Agx,
+}
+
+/// One effect of a [`PostProcessStack`].
+#[derive(Debug, Clone)]
+pub enum PostEffect {
+    /// Scale scene color by `2^ev`.
+    Exposure(f32),
+    /// Map HDR color to display range.
+    ToneMapping(ToneMapping),
+    /// Add a blurred copy of the pixels brighter than `threshold`, scaled
+    /// by `intensity`. Rendered at half resolution.
+    Bloom { threshold: f32, intensity: f32 },
+    /// Fast approximate anti-aliasing. Expects display range input.
+    Fxaa,
+    /// Darken the image towards its corners. `smoothness` is the width of
+    /// the falloff, from 0 (hard edge) to 1.
+    Vignette { intensity: f32, smoothness: f32 },
+    /// Remap colors through a 3D lookup table stored as a horizontal strip
+    /// of `N` slices of `N x N` texels (a `N * N` by `N` texture, blue
+    /// selecting the slice). `strength` blends between the input (0) and
+    /// the graded color (1). Expects display range input.
+    ColorGrading { lut: Arc<Texture>, strength: f32 },
+}
+
+/// Ordered chain of post-processing effects run on a camera's image.
+///
+/// Attach to an entity with a [`Camera`](crate::Camera) and a
+/// [`CameraTarget`](super::CameraTarget). The scene is rendered into the
+/// target's color texture as usual, which then becomes the input of the
+/// first effect (so it must also be created with
+/// `TextureUsage::TEXTURE_BINDING`, and is best an HDR format such as
+/// `Rgba16Float`). Each effect reads the previous one's output, and the
+/// last writes to [`output`](Self::output). An empty stack copies the
+/// scene to the output.
+///
+/// Effects run in order as written: put [`PostEffect::Fxaa`] and
+/// [`PostEffect::ColorGrading`] after tone mapping.
+#[derive(Debug, Clone, crate::Component)]
+#[skip_serialization]
+pub struct PostProcessStack {
+    /// Texture receiving the final image (`TextureUsage::RENDER_ATTACHMENT`).
+    pub output: Arc<Texture>,
+    /// Effects in the order they run.
+    pub effects: Vec<PostEffect>,
+}
+
+impl PostProcessStack {
+    /// Create an empty stack writing to `output`.
+    pub fn new(output: Arc<Texture>) -> Self {
+        Self {
+            output,
+            effects: Vec::new(),
+        }
+    }
+
+    /// Append an effect to the chain.
+    pub fn with_effect(mut self, effect: PostEffect) -> Self {
+        self.effects.push(effect);
+        self
+    }
+}
@@ -6,7 +6,8 @@ use redlilium_core::material::{CpuMaterialInstance, MaterialValue};
 use redlilium_graphics::{Buffer, MaterialInstance};
 
 use super::material_bundle::{
-    MaterialBundle, RenderPassType, deserialize_material_value, serialize_material_value,
+    MaterialBundle, RenderPassType, RenderQueue, deserialize_material_value,
+    serialize_material_value,
 };
 use crate::serialize::Value;
 
@@ -297,6 +298,29 @@ impl RenderMaterial {
         self.bundle.get(pass_type)
     }
 
+    /// Get the queue the `pass_type` instance is drawn in.
+    ///
+    /// Instances whose material has a blend state are blended; the others
+    /// follow the CPU material's alpha mode if present, except that blended
+    /// CPU materials without a GPU blend state (e.g. in a G-buffer pass)
+    /// draw as opaque.
+    pub fn queue(&self, pass_type: RenderPassType) -> RenderQueue {
+        if self
+            .pass(pass_type)
+            .is_some_and(|instance| instance.material().blend_state().is_some())
+        {
+            return RenderQueue::Blend;
+        }
+        match self
+            .cpu_instance
+            .as_ref()
+            .map(|cpu| RenderQueue::from_alpha_mode(cpu.material.alpha_mode))
+        {
+            Some(RenderQueue::Mask) => RenderQueue::Mask,
+            _ => RenderQueue::Opaque,
+        }
+    }
+
     /// Get the CPU-side material instance, if present.
     pub fn cpu_instance(&self) -> Option<&Arc<CpuMaterialInstance>> {
         self.cpu_instance.as_ref()
@@ -0,0 +1,45 @@
+//! Skybox component.
+
+use std::sync::Arc;
+
+use redlilium_graphics::Texture;
+
+/// Environment cube map drawn behind everything a camera renders.
+///
+/// Attach to an entity with a [`Camera`](crate::Camera) and a
+/// [`CameraTarget`](super::CameraTarget). Both the forward and deferred
+/// render systems draw it after opaque geometry, so it only covers pixels
+/// nothing else was drawn to.
+#[derive(Debug, Clone, crate::Component)]
+#[skip_serialization]
+pub struct Skybox {
+    /// Cube map to sample (linear color, e.g. `Rgba16Float`).
+    pub cubemap: Arc<Texture>,
+    /// Brightness multiplier.
+    pub intensity: f32,
+    /// Mip level sampled; higher levels give a blurred background.
+    pub lod: f32,
+}
+
+impl Skybox {
+    /// Create a skybox drawing the sharpest mip of `cubemap`.
+    pub fn new(cubemap: Arc<Texture>) -> Self {
+        Self {
+            cubemap,
+            intensity: 1.0,
+            lod: 0.0,
+        }
+    }
+
+    /// Set the brightness multiplier.
+    pub fn with_intensity(mut self, intensity: f32) -> Self {
+        self.intensity = intensity;
+        self
+    }
+
+    /// Set the sampled mip level.
+    pub fn with_lod(mut self, lod: f32) -> Self {
+        self.lod = lod;
+        self
+    }
+}
@@ -7,17 +7,46 @@
 //!
 //! - [`RenderMesh`] — GPU mesh attached to an entity
 //! - [`RenderMaterial`] — GPU material instance attached to an entity
-//! - [`CameraTarget`] — Render target textures for a camera entity
+//! - [`CameraTarget`] — Render target textures for a camera entity, with an
+//!   optional [`GBuffer`] selecting the deferred path
+//! - [`Skybox`] — Environment cube map drawn behind a camera's scene
+//! - [`PostProcessStack`] — Ordered post-processing effects (exposure, tone
+//!   mapping, bloom, FXAA, vignette, color grading) run on a camera's image
 //!
 //! # Resources
 //!
 //! - [`TextureManager`] — Caches GPU textures and samplers
+//! - [`AssetServer`] — Loads textures, scenes, prefabs and shaders through the VFS
 //! - [`RenderSchedule`] — Holds the current frame's [`FrameSchedule`](redlilium_graphics::FrameSchedule)
+//! - [`ShadowMaps`] — Shadow map atlases of shadow casting lights
+//! - [`SceneLighting`] — Gathered lights, shadow atlases and image-based
+//!   ambient lighting sampled by lit materials (see [`shaders::pbr`]); also
+//!   convolves environment cube maps on the GPU (see [`shaders::ibl_precompute`])
+//! - [`DeferredRenderer`] — Resolve materials and G-buffer bindings of the
+//!   deferred path
+//! - [`SpatialIndex`] — BVH over rendered entities' world bounds for frustum
+//!   culling, ray and box queries, with per-camera [`CullingStats`]
+//! - [`DrawBatcher`] — Merges draws sharing a mesh and material into
+//!   instanced draws and orders each pass's draws by depth
+//! - [`PostProcessRenderer`] — Effect materials and intermediate targets of
+//!   camera [`PostProcessStack`]s
 //!
 //! # Systems
 //!
 //! - [`ForwardRenderSystem`] — Collects renderable entities and submits
 //!   draw commands for each camera with a render target
+//! - [`DeferredRenderSystem`] — Renders cameras with a [`GBuffer`] through a
+//!   geometry pass, a lighting resolve and a forward pass for the rest
+//! - [`ShadowRenderSystem`] — Renders cascaded, cube and spot shadow maps for
+//!   lights with [`ShadowSettings`](crate::ShadowSettings) before the forward pass
+//! - [`GatherLights`] — Packs visible lights and their shadow maps into
+//!   [`SceneLighting`] between the shadow and forward passes
+//! - [`ReloadMaterialShaders`] / [`SyncPrefabInstances`] — Apply hot-reloaded
+//!   shaders and prefabs loaded through the [`AssetServer`]
+//! - [`UpdateSpatialIndex`] — Refits the [`SpatialIndex`] from moved and
+//!   changed meshes before the render systems cull with it
+//! - [`UpdatePerEntityUniforms`] — Uploads transforms and, for skinned meshes
+//!   (see [`shaders::skinned_color`]), joint matrices
 //!
 //! # Feature Gate
 //!
@@ -31,17 +60,28 @@ pub mod shaders;
 pub mod systems;
 
 pub use components::{
-    CameraTarget, MaterialBundle, PerEntityBuffers, RenderMaterial, RenderMesh, RenderPassType,
+    CameraTarget, GBuffer, MaterialBundle, PerEntityBuffers, PostEffect, PostProcessStack,
+    RenderMaterial, RenderMesh, RenderPassType, RenderQueue, Skybox, ToneMapping,
 };
 pub use resources::{
-    CpuBundleInfo, MaterialManager, MaterialManagerError, MeshManager, RenderSchedule,
+    Asset, AssetError, AssetManagers, AssetServer, CpuBundleInfo, CullingStats, DeferredRenderer,
+    DrawBatcher, EnvironmentLighting, GpuLight, GpuShadowView, Handle, INSTANCED_DEFINE,
+    LightingViewUniforms, LoadState, MAX_SHADOW_ATLASES, MaterialManager, MaterialManagerError,
+    MeshManager, PostProcessRenderer, RayHit, RenderSchedule, SHADOW_DEPTH_FORMAT, SceneAsset,
+    SceneLighting, ShaderAsset, ShadowMap, ShadowMapKind, ShadowMaps, ShadowView, SpatialIndex,
     TextureManager, TextureManagerError, pack_uniform_bytes,
 };
 pub use systems::{
-    EditorForwardRenderSystem, ForwardRenderSystem, InitializeRenderEntities, SyncMaterialUniforms,
-    UpdatePerEntityUniforms,
+    DeferredRenderSystem, EditorDeferredRenderSystem, EditorForwardRenderSystem,
+    ForwardRenderSystem, GatherLights, InitializeRenderEntities, ReloadMaterialShaders,
+    ShadowRenderSystem, SyncMaterialUniforms, SyncPrefabInstances, UpdateAssetServer,
+    UpdatePerEntityUniforms, UpdateSpatialIndex,
 };
 
+use std::sync::Arc;
+
+use redlilium_graphics::GraphicsDevice;
+
 use crate::World;
 
 /// Register rendering component types with the world.
@@ -52,5 +92,20 @@ pub fn register_rendering_components(world: &mut World) {
     world.register_inspector::<RenderMesh>();
     world.register_inspector::<RenderMaterial>();
     world.register_component::<CameraTarget>();
+    world.register_component::<Skybox>();
+    world.register_component::<PostProcessStack>();
     world.register_component::<PerEntityBuffers>();
 }
+
+/// Insert the resources shared by the forward and deferred render systems.
+///
+/// Inserts [`SceneLighting`], [`SpatialIndex`], [`PostProcessRenderer`] and
+/// [`DrawBatcher`], which [`ForwardRenderSystem`] and
+/// [`DeferredRenderSystem`] lock every frame. Shadows and the deferred
+/// path additionally need [`ShadowMaps`] and [`DeferredRenderer`].
+pub fn insert_render_resources(world: &mut World, device: Arc<GraphicsDevice>) {
+    world.insert_resource(SceneLighting::new(device.clone()));
+    world.insert_resource(SpatialIndex::new());
+    world.insert_resource(PostProcessRenderer::new(device));
+    world.insert_resource(DrawBatcher::new());
+}
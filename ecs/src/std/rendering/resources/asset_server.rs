@@ -0,0 +1,1137 @@
+//! Asynchronous asset loading through the virtual file system.
+//!
+//! The [`AssetServer`] resource reads files through a [`Vfs`] on the
+//! [`IoRuntime`], decodes them off the main thread and hands out typed
+//! [`Handle`]s that resolve once loading finishes. GPU uploads happen in
+//! [`AssetServer::update`], driven each frame by the
+//! [`UpdateAssetServer`](crate::std::rendering::UpdateAssetServer) system.
+//!
+//! # Asset types
+//!
+//! - [`Texture`] — PNG/JPEG images, uploaded through [`TextureManager`]
+//! - [`SceneAsset`] — glTF/GLB documents, meshes uploaded through [`MeshManager`]
+//! - [`SerializedPrefab`] — prefab files, decoded by extension via [`Format::from_extension`]
+//! - [`ShaderAsset`] — WGSL or Slang source text
+//!
+//! Custom asset types implement the [`Asset`] trait.
+//!
+//! # Caching and unloading
+//!
+//! Loads are deduplicated by asset type and normalized VFS path: loading the
+//! same file twice returns handles to the same slot. Once every handle to an
+//! asset has been dropped, the next [`update`](AssetServer::update) removes it
+//! from the server and from the managers it was uploaded to, so its GPU data
+//! is freed as soon as no component references it any more.
+//!
+//! # Hot reload
+//!
+//! After [`watch_for_changes`](AssetServer::watch_for_changes), the server
+//! listens to [`Vfs::watch`] notifications and reloads cached assets whose
+//! file changed. Handles keep pointing at the same slot; they observe the new
+//! asset once it is ready and their [`version`](Handle::version) increases.
+//! If the new file fails to load, the previous version stays in place.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let vfs = Vfs::new();
+//! vfs.mount("assets", FileSystemProvider::new("./assets"));
+//! world.insert_resource(AssetServer::new(vfs, runner.io().clone()));
+//!
+//! let brick: Handle<Texture> = world
+//!     .resource_mut::<AssetServer>()
+//!     .load("assets/textures/brick.png");
+//!
+//! // A few frames later, after UpdateAssetServer has run:
+//! if let Some(texture) = brick.get() {
+//!     // use the GPU texture
+//! }
+//! ```
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::task::{Context, Poll};
+
+use parking_lot::{Mutex, RwLock};
+use redlilium_core::compute::{IoHandle, IoRunner};
+use redlilium_core::gltf::{GltfDocument, GltfMaterial, load_gltf};
+use redlilium_core::material::{CpuMaterial, CpuMaterialInstance, MaterialValue};
+use redlilium_core::mesh::VertexLayout;
+use redlilium_core::scene::Scene;
+use redlilium_graphics::{
+    CpuSampler, GraphicsError, Mesh, ShaderSource, ShaderSourceLanguage, ShaderStage, Texture,
+};
+use redlilium_vfs::{Vfs, VfsChange, VfsChangeKind, VfsError, VfsWatcher};
+
+use super::mesh_manager::MeshManager;
+use super::texture_manager::TextureManager;
+use crate::IoRuntime;
+use crate::serialize::{Format, SerializedPrefab};
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+/// Errors that can occur while loading an asset.
+#[derive(Debug)]
+pub enum AssetError {
+    /// Reading the file through the VFS failed.
+    Vfs(VfsError),
+    /// The file contents could not be decoded.
+    Decode(String),
+    /// No decoder is available for the file extension.
+    UnsupportedFormat(String),
+    /// GPU resource creation error.
+    Graphics(GraphicsError),
+    /// The IO task was dropped before producing a result.
+    Cancelled,
+}
+
+impl fmt::Display for AssetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Vfs(err) => write!(f, "VFS error: {err}"),
+            Self::Decode(msg) => write!(f, "decode error: {msg}"),
+            Self::UnsupportedFormat(path) => write!(f, "unsupported asset format: {path}"),
+            Self::Graphics(err) => write!(f, "graphics error: {err}"),
+            Self::Cancelled => write!(f, "asset load was cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for AssetError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Vfs(err) => Some(err),
+            Self::Graphics(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<VfsError> for AssetError {
+    fn from(err: VfsError) -> Self {
+        Self::Vfs(err)
+    }
+}
+
+impl From<GraphicsError> for AssetError {
+    fn from(err: GraphicsError) -> Self {
+        Self::Graphics(err)
+    }
+}
+
+// ============================================================================
+// Asset trait
+// ============================================================================
+
+/// Managers an asset may create GPU resources in while finishing a load.
+pub struct AssetManagers<'a> {
+    /// Texture and sampler cache.
+    pub textures: &'a mut TextureManager,
+    /// Mesh cache.
+    pub meshes: &'a mut MeshManager,
+}
+
+/// A type that can be loaded by the [`AssetServer`].
+///
+/// Loading happens in two steps: [`decode`](Asset::decode) turns the raw file
+/// bytes into CPU data on the IO runtime, then [`finish`](Asset::finish)
+/// creates the final asset on the thread running [`AssetServer::update`],
+/// where the managers are available for GPU uploads.
+pub trait Asset: Send + Sync + Sized + 'static {
+    /// CPU-side data produced by [`decode`](Asset::decode).
+    type Decoded: Send + 'static;
+
+    /// Decode the raw file contents. Runs on the IO runtime.
+    fn decode(path: &str, bytes: Vec<u8>) -> Result<Self::Decoded, AssetError>;
+
+    /// Build the asset from decoded data, uploading GPU resources if needed.
+    fn finish(
+        path: &str,
+        decoded: Self::Decoded,
+        managers: &mut AssetManagers<'_>,
+    ) -> Result<Arc<Self>, AssetError>;
+
+    /// Release manager entries created by [`finish`](Asset::finish).
+    ///
+    /// Called when the last handle to a loaded asset has been dropped.
+    fn unload(_path: &str, _asset: &Arc<Self>, _managers: &mut AssetManagers<'_>) {}
+
+    /// Build a new version of an already loaded asset after its file changed.
+    ///
+    /// Defaults to [`finish`](Asset::finish). Implementations may instead
+    /// update `current` in place and return it. On error the server keeps
+    /// `current`.
+    fn reload(
+        path: &str,
+        decoded: Self::Decoded,
+        _current: &Arc<Self>,
+        managers: &mut AssetManagers<'_>,
+    ) -> Result<Arc<Self>, AssetError> {
+        Self::finish(path, decoded, managers)
+    }
+}
+
+// ============================================================================
+// Handles
+// ============================================================================
+
+/// Load state of the asset behind a [`Handle`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadState {
+    /// The file is still being read or decoded.
+    Loading,
+    /// The asset is ready; [`Handle::get`] returns it.
+    Loaded,
+    /// Loading failed; [`Handle::error`] returns the reason.
+    Failed,
+}
+
+enum SlotState<T> {
+    Loading,
+    Loaded(Arc<T>),
+    Failed(Arc<AssetError>),
+}
+
+/// Shared state of one asset, referenced by every handle to it.
+struct AssetSlot<T> {
+    path: String,
+    state: RwLock<SlotState<T>>,
+    /// Number of times the slot received a successfully loaded asset.
+    version: AtomicU64,
+}
+
+/// Typed, reference-counted handle to an asset loaded by the [`AssetServer`].
+///
+/// Handles are cheap to clone and all clones observe the same load. The asset
+/// stays cached while at least one handle exists.
+pub struct Handle<T> {
+    slot: Arc<AssetSlot<T>>,
+}
+
+impl<T> Handle<T> {
+    fn new(path: String, state: SlotState<T>) -> Self {
+        Self {
+            slot: Arc::new(AssetSlot {
+                path,
+                state: RwLock::new(state),
+                version: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// The canonical VFS path the asset was loaded from.
+    ///
+    /// See [`Vfs::canonical_path`].
+    pub fn path(&self) -> &str {
+        &self.slot.path
+    }
+
+    /// How many times the asset has been loaded successfully.
+    ///
+    /// Zero until the first load finishes, then incremented by every hot
+    /// reload. Compare against a previously seen value to detect changes.
+    pub fn version(&self) -> u64 {
+        self.slot.version.load(Ordering::Acquire)
+    }
+
+    /// Current load state.
+    pub fn load_state(&self) -> LoadState {
+        match &*self.slot.state.read() {
+            SlotState::Loading => LoadState::Loading,
+            SlotState::Loaded(_) => LoadState::Loaded,
+            SlotState::Failed(_) => LoadState::Failed,
+        }
+    }
+
+    /// Returns `true` once the asset has finished loading successfully.
+    pub fn is_loaded(&self) -> bool {
+        self.load_state() == LoadState::Loaded
+    }
+
+    /// Get the loaded asset, or `None` while loading or after a failure.
+    pub fn get(&self) -> Option<Arc<T>> {
+        match &*self.slot.state.read() {
+            SlotState::Loaded(asset) => Some(Arc::clone(asset)),
+            _ => None,
+        }
+    }
+
+    /// Get the error that made loading fail, if any.
+    pub fn error(&self) -> Option<Arc<AssetError>> {
+        match &*self.slot.state.read() {
+            SlotState::Failed(err) => Some(Arc::clone(err)),
+            _ => None,
+        }
+    }
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            slot: Arc::clone(&self.slot),
+        }
+    }
+}
+
+impl<T> PartialEq for Handle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.slot, &other.slot)
+    }
+}
+
+impl<T> Eq for Handle<T> {}
+
+impl<T> fmt::Debug for Handle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Handle")
+            .field("path", &self.slot.path)
+            .field("state", &self.load_state())
+            .finish()
+    }
+}
+
+/// Handles are serialized as their VFS path and reloaded through the world's
+/// [`AssetServer`] on deserialization, so scenes and prefabs reference assets
+/// instead of embedding them.
+impl<T: Asset> crate::ComponentField for Handle<T> {
+    fn inspect_field(&self, name: &str, ui: &mut crate::egui::Ui) -> Option<Self> {
+        ui.horizontal(|ui| {
+            ui.label(name);
+            ui.weak(self.path());
+        });
+        None
+    }
+
+    fn serialize_field(
+        &self,
+        name: &str,
+        ctx: &mut crate::serialize::SerializeContext<'_>,
+    ) -> Result<(), crate::serialize::SerializeError> {
+        ctx.write_serde(name, &self.path())
+    }
+
+    fn deserialize_field(
+        name: &str,
+        ctx: &mut crate::serialize::DeserializeContext<'_>,
+    ) -> Result<Self, crate::serialize::DeserializeError> {
+        let path: String = ctx.read_serde(name)?;
+        let world = ctx.world();
+        if !world.has_resource::<AssetServer>() {
+            return Err(crate::serialize::DeserializeError::FormatError(
+                "AssetServer resource not found".into(),
+            ));
+        }
+        Ok(world.resource_mut::<AssetServer>().load(&path))
+    }
+}
+
+// ============================================================================
+// Server
+// ============================================================================
+
+type PendingLoad<T> = IoHandle<Result<<T as Asset>::Decoded, AssetError>>;
+
+/// Type-erased cache entry of the [`AssetServer`].
+trait AssetEntry: Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+
+    /// Whether the IO task has not delivered its result yet.
+    fn is_pending(&self) -> bool;
+
+    /// Whether the server holds the only reference to the slot.
+    fn is_unused(&self) -> bool;
+
+    /// Resolve the slot if the IO task has finished.
+    fn poll(&mut self, managers: &mut AssetManagers<'_>);
+
+    /// Read and decode the file again, replacing any in-flight task.
+    fn reload(&mut self, vfs: &Vfs, io: &IoRuntime);
+
+    /// Release the asset's manager entries.
+    fn unload(&mut self, managers: &mut AssetManagers<'_>);
+}
+
+struct Entry<T: Asset> {
+    slot: Arc<AssetSlot<T>>,
+    /// In-flight IO task. Wrapped in a mutex only to make the entry `Sync`.
+    pending: Mutex<Option<PendingLoad<T>>>,
+    /// Whether the in-flight task replaces an asset that already finished
+    /// loading once (successfully or not).
+    reloading: bool,
+}
+
+impl<T: Asset> AssetEntry for Entry<T> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn is_pending(&self) -> bool {
+        self.pending.lock().is_some()
+    }
+
+    fn is_unused(&self) -> bool {
+        Arc::strong_count(&self.slot) == 1
+    }
+
+    fn poll(&mut self, managers: &mut AssetManagers<'_>) {
+        let pending = self.pending.get_mut();
+        let Some(io_handle) = pending else {
+            return;
+        };
+
+        let waker = crate::compute::noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        let result = match Pin::new(io_handle).poll(&mut cx) {
+            Poll::Pending => return,
+            Poll::Ready(Some(result)) => result,
+            Poll::Ready(None) => Err(AssetError::Cancelled),
+        };
+        *pending = None;
+
+        let path = &self.slot.path;
+        let current = match &*self.slot.state.read() {
+            SlotState::Loaded(asset) => Some(Arc::clone(asset)),
+            _ => None,
+        };
+        let result = result.and_then(|decoded| match &current {
+            Some(current) => T::reload(path, decoded, current, managers),
+            None => T::finish(path, decoded, managers),
+        });
+
+        let state = match result {
+            Ok(asset) => {
+                if self.reloading {
+                    log::info!("Reloaded asset '{path}'");
+                }
+                self.slot.version.fetch_add(1, Ordering::AcqRel);
+                SlotState::Loaded(asset)
+            }
+            Err(err) if current.is_some() => {
+                log::warn!("Failed to reload asset '{path}', keeping previous version: {err}");
+                return;
+            }
+            Err(err) => {
+                log::error!("Failed to load asset '{path}': {err}");
+                SlotState::Failed(Arc::new(err))
+            }
+        };
+        *self.slot.state.write() = state;
+    }
+
+    fn reload(&mut self, vfs: &Vfs, io: &IoRuntime) {
+        self.reloading = !matches!(*self.slot.state.read(), SlotState::Loading);
+        *self.pending.get_mut() = Some(spawn_load::<T>(vfs, io, &self.slot.path));
+    }
+
+    fn unload(&mut self, managers: &mut AssetManagers<'_>) {
+        if let SlotState::Loaded(asset) = &*self.slot.state.read() {
+            T::unload(&self.slot.path, asset, managers);
+        }
+    }
+}
+
+/// Resource that loads assets through a [`Vfs`] on the [`IoRuntime`].
+///
+/// See the [module documentation](self) for an overview.
+pub struct AssetServer {
+    vfs: Vfs,
+    io: IoRuntime,
+    entries: HashMap<(TypeId, String), Box<dyn AssetEntry>>,
+    /// Change notifications driving hot reload, if enabled.
+    watcher: Option<VfsWatcher>,
+}
+
+/// Start an IO task that reads and decodes an asset file.
+fn spawn_load<T: Asset>(vfs: &Vfs, io: &IoRuntime, path: &str) -> PendingLoad<T> {
+    let read = vfs.read(path);
+    let path = path.to_owned();
+    io.run(async move {
+        let bytes = read.await?;
+        T::decode(&path, bytes)
+    })
+}
+
+impl AssetServer {
+    /// Create an asset server reading from the given VFS.
+    pub fn new(vfs: Vfs, io: IoRuntime) -> Self {
+        Self {
+            vfs,
+            io,
+            entries: HashMap::new(),
+            watcher: None,
+        }
+    }
+
+    /// Get the VFS assets are read from.
+    pub fn vfs(&self) -> &Vfs {
+        &self.vfs
+    }
+
+    /// Enable hot reload: reload cached assets whose file changes.
+    ///
+    /// Only mounts whose provider supports [watching](redlilium_vfs::VfsProvider::watch)
+    /// report changes. Calling this again restarts the watcher.
+    pub fn watch_for_changes(&mut self) {
+        self.watcher = Some(self.vfs.watch());
+    }
+
+    /// Returns `true` if hot reload is enabled.
+    pub fn is_watching(&self) -> bool {
+        self.watcher.is_some()
+    }
+
+    /// Start reloads for file changes reported by a watcher the caller owns.
+    ///
+    /// Use this instead of [`watch_for_changes`](Self::watch_for_changes) to
+    /// share one [`Vfs::watch`] watcher with other consumers; remote mounts
+    /// poll on a thread per watcher. Removed files are ignored so the last
+    /// loaded version stays available.
+    pub fn apply_changes(&mut self, changes: &[VfsChange]) {
+        for change in changes {
+            if change.kind != VfsChangeKind::Removed {
+                self.reload(&change.path);
+            }
+        }
+    }
+
+    /// Reload every cached asset loaded from the given path.
+    ///
+    /// Returns the number of assets a reload was started for. Handles keep
+    /// the current version until the new one finishes loading during a
+    /// later [`update`](Self::update).
+    pub fn reload(&mut self, path: &str) -> usize {
+        let Ok(path) = self.vfs.canonical_path(path) else {
+            return 0;
+        };
+        let mut count = 0;
+        for ((_, entry_path), entry) in &mut self.entries {
+            if *entry_path == path {
+                entry.reload(&self.vfs, &self.io);
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Start loading an asset, or return the existing handle if it is
+    /// already cached.
+    ///
+    /// The returned handle resolves during a later [`update`](Self::update).
+    /// Invalid paths produce a handle that has already failed.
+    pub fn load<T: Asset>(&mut self, path: &str) -> Handle<T> {
+        let path = match self.vfs.canonical_path(path) {
+            Ok(path) => path,
+            Err(err) => {
+                return Handle::new(path.to_owned(), SlotState::Failed(Arc::new(err.into())));
+            }
+        };
+
+        if let Some(handle) = self.get_handle(&path) {
+            return handle;
+        }
+
+        let pending = spawn_load::<T>(&self.vfs, &self.io, &path);
+        let handle = Handle::new(path.clone(), SlotState::Loading);
+        let entry = Entry::<T> {
+            slot: Arc::clone(&handle.slot),
+            pending: Mutex::new(Some(pending)),
+            reloading: false,
+        };
+        self.entries
+            .insert((TypeId::of::<T>(), path), Box::new(entry));
+        handle
+    }
+
+    /// Get a handle to an asset that is already cached, without loading it.
+    pub fn get_handle<T: Asset>(&self, path: &str) -> Option<Handle<T>> {
+        let path = self.vfs.canonical_path(path).ok()?;
+        let entry = self.entries.get(&(TypeId::of::<T>(), path))?;
+        let entry = entry.as_any().downcast_ref::<Entry<T>>()?;
+        Some(Handle {
+            slot: Arc::clone(&entry.slot),
+        })
+    }
+
+    /// Finish completed loads and unload assets without handles.
+    ///
+    /// When [watching for changes](Self::watch_for_changes), also starts
+    /// reloads for changed files. Removed files are ignored so the last
+    /// loaded version stays available.
+    ///
+    /// Call once per frame, or schedule
+    /// [`UpdateAssetServer`](crate::std::rendering::UpdateAssetServer).
+    pub fn update(&mut self, managers: &mut AssetManagers<'_>) {
+        let changes = self
+            .watcher
+            .as_ref()
+            .map(VfsWatcher::poll)
+            .unwrap_or_default();
+        self.apply_changes(&changes);
+
+        self.entries.retain(|_, entry| {
+            if entry.is_unused() {
+                entry.unload(managers);
+                return false;
+            }
+            entry.poll(managers);
+            true
+        });
+    }
+
+    /// Returns the number of cached assets, including those still loading.
+    pub fn asset_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns the number of assets still waiting for their IO task.
+    pub fn pending_count(&self) -> usize {
+        self.entries.values().filter(|e| e.is_pending()).count()
+    }
+}
+
+// ============================================================================
+// Built-in asset types
+// ============================================================================
+
+/// Returns the lowercase file extension of a VFS path.
+fn extension(path: &str) -> String {
+    std::path::Path::new(path)
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default()
+}
+
+impl Asset for Texture {
+    type Decoded = redlilium_graphics::CpuTexture;
+
+    fn decode(path: &str, bytes: Vec<u8>) -> Result<Self::Decoded, AssetError> {
+        TextureManager::decode_image(&bytes, path)
+            .map_err(|err| AssetError::Decode(err.to_string()))
+    }
+
+    fn finish(
+        _path: &str,
+        decoded: Self::Decoded,
+        managers: &mut AssetManagers<'_>,
+    ) -> Result<Arc<Self>, AssetError> {
+        Ok(managers.textures.create_texture(&decoded)?)
+    }
+
+    fn unload(path: &str, asset: &Arc<Self>, managers: &mut AssetManagers<'_>) {
+        if managers
+            .textures
+            .get_texture(path)
+            .is_some_and(|texture| Arc::ptr_eq(texture, asset))
+        {
+            managers.textures.remove_texture(path);
+        }
+    }
+
+    /// Re-upload the pixels into the existing texture when the size and
+    /// format are unchanged, so materials referencing it pick up the new
+    /// contents without being rebuilt.
+    fn reload(
+        path: &str,
+        decoded: Self::Decoded,
+        current: &Arc<Self>,
+        managers: &mut AssetManagers<'_>,
+    ) -> Result<Arc<Self>, AssetError> {
+        if (current.width(), current.height()) == (decoded.width, decoded.height)
+            && current.format() == decoded.format
+        {
+            managers
+                .textures
+                .device()
+                .write_texture(current, &decoded.data)?;
+            return Ok(Arc::clone(current));
+        }
+        let texture = Self::finish(path, decoded, managers)?;
+        Self::unload(path, current, managers);
+        Ok(texture)
+    }
+}
+
+/// Shader source text loaded from a `.wgsl` or `.slang` file.
+#[derive(Debug, Clone)]
+pub struct ShaderAsset {
+    /// The shader source code.
+    pub source: String,
+    /// Source language, derived from the file extension.
+    pub language: ShaderSourceLanguage,
+}
+
+impl ShaderAsset {
+    /// Build a [`ShaderSource`] for one entry point of this shader.
+    ///
+    /// Defines are only used by Slang sources.
+    pub fn shader_source(
+        &self,
+        stage: ShaderStage,
+        entry_point: impl Into<String>,
+        defines: Vec<(String, String)>,
+    ) -> ShaderSource {
+        match self.language {
+            ShaderSourceLanguage::Wgsl => {
+                ShaderSource::new(stage, self.source.as_bytes(), entry_point)
+            }
+            ShaderSourceLanguage::Slang => {
+                ShaderSource::slang(stage, self.source.as_bytes(), entry_point, defines)
+            }
+        }
+    }
+}
+
+impl Asset for ShaderAsset {
+    type Decoded = Self;
+
+    fn decode(path: &str, bytes: Vec<u8>) -> Result<Self::Decoded, AssetError> {
+        let language = match extension(path).as_str() {
+            "wgsl" => ShaderSourceLanguage::Wgsl,
+            "slang" => ShaderSourceLanguage::Slang,
+            _ => return Err(AssetError::UnsupportedFormat(path.to_owned())),
+        };
+        let source = String::from_utf8(bytes).map_err(|err| AssetError::Decode(err.to_string()))?;
+        Ok(Self { source, language })
+    }
+
+    fn finish(
+        _path: &str,
+        decoded: Self::Decoded,
+        _managers: &mut AssetManagers<'_>,
+    ) -> Result<Arc<Self>, AssetError> {
+        Ok(Arc::new(decoded))
+    }
+}
+
+impl Asset for SerializedPrefab {
+    type Decoded = Self;
+
+    fn decode(path: &str, bytes: Vec<u8>) -> Result<Self::Decoded, AssetError> {
+        let format = Format::from_extension(&extension(path))
+            .ok_or_else(|| AssetError::UnsupportedFormat(path.to_owned()))?;
+        SerializedPrefab::from_bytes(&bytes, format)
+            .map_err(|err| AssetError::Decode(err.to_string()))
+    }
+
+    fn finish(
+        _path: &str,
+        decoded: Self::Decoded,
+        _managers: &mut AssetManagers<'_>,
+    ) -> Result<Arc<Self>, AssetError> {
+        Ok(Arc::new(decoded))
+    }
+}
+
+/// A glTF document with its meshes uploaded to the GPU.
+///
+/// Materials are standard PBR metallic-roughness instances
+/// ([`CpuMaterial::pbr_metallic_roughness`]) with textures kept on the CPU;
+/// they are uploaded when a bundle is created through the material manager.
+pub struct SceneAsset {
+    /// The loaded document with CPU-side scenes, materials, skins and animations.
+    pub document: GltfDocument,
+    /// GPU meshes per scene, parallel to each scene's `meshes` list.
+    pub meshes: Vec<Vec<Arc<Mesh>>>,
+}
+
+impl SceneAsset {
+    /// Name under which a scene mesh is registered in the [`MeshManager`].
+    pub fn mesh_name(path: &str, scene: usize, mesh: usize) -> String {
+        format!("{path}#{scene}/{mesh}")
+    }
+
+    /// The document's default scene, or the first scene if none is set.
+    pub fn default_scene(&self) -> Option<&Scene> {
+        let index = self.document.default_scene.unwrap_or(0);
+        self.document.scenes.get(index)
+    }
+}
+
+impl Asset for SceneAsset {
+    type Decoded = GltfDocument;
+
+    fn decode(path: &str, bytes: Vec<u8>) -> Result<Self::Decoded, AssetError> {
+        let mut document = load_gltf(&bytes, pbr_material_instance, |sampler: &CpuSampler| {
+            Arc::new(sampler.clone())
+        })
+        .map_err(|err| AssetError::Decode(err.to_string()))?;
+
+        // Label meshes with unique names so the MeshManager can key them
+        for (scene_index, scene) in document.scenes.iter_mut().enumerate() {
+            scene.meshes = std::mem::take(&mut scene.meshes)
+                .into_iter()
+                .enumerate()
+                .map(|(mesh_index, mesh)| {
+                    mesh.with_label(Self::mesh_name(path, scene_index, mesh_index))
+                })
+                .collect();
+        }
+        Ok(document)
+    }
+
+    fn finish(
+        _path: &str,
+        decoded: Self::Decoded,
+        managers: &mut AssetManagers<'_>,
+    ) -> Result<Arc<Self>, AssetError> {
+        let meshes = decoded
+            .scenes
+            .iter()
+            .map(|scene| {
+                scene
+                    .meshes
+                    .iter()
+                    .map(|mesh| managers.meshes.create_mesh(mesh))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Arc::new(Self {
+            document: decoded,
+            meshes,
+        }))
+    }
+
+    fn unload(path: &str, asset: &Arc<Self>, managers: &mut AssetManagers<'_>) {
+        for (scene_index, meshes) in asset.meshes.iter().enumerate() {
+            for (mesh_index, mesh) in meshes.iter().enumerate() {
+                let name = Self::mesh_name(path, scene_index, mesh_index);
+                if managers
+                    .meshes
+                    .get_mesh(&name)
+                    .is_some_and(|m| Arc::ptr_eq(m, mesh))
+                {
+                    managers.meshes.remove_mesh(&name);
+                }
+            }
+        }
+    }
+
+    fn reload(
+        path: &str,
+        decoded: Self::Decoded,
+        current: &Arc<Self>,
+        managers: &mut AssetManagers<'_>,
+    ) -> Result<Arc<Self>, AssetError> {
+        let asset = Self::finish(path, decoded, managers)?;
+        // Replaced meshes are no longer registered; this drops leftovers only
+        Self::unload(path, current, managers);
+        Ok(asset)
+    }
+}
+
+/// Build a PBR metallic-roughness instance from parsed glTF material properties.
+fn pbr_material_instance(mat: &GltfMaterial, layout: &VertexLayout) -> Arc<CpuMaterialInstance> {
+    let declaration = CpuMaterial::pbr_metallic_roughness(
+        Arc::new(layout.clone()),
+        mat.alpha_mode,
+        mat.double_sided,
+        mat.base_color_texture.is_some(),
+        mat.metallic_roughness_texture.is_some(),
+        mat.normal_texture.is_some(),
+        mat.occlusion_texture.is_some(),
+        mat.emissive_texture.is_some(),
+    );
+
+    let mut values = vec![
+        MaterialValue::Vec4(mat.base_color_factor),
+        MaterialValue::Float(mat.metallic_factor),
+        MaterialValue::Float(mat.roughness_factor),
+        MaterialValue::Vec3(mat.emissive_factor),
+        MaterialValue::Float(mat.normal_scale),
+        MaterialValue::Float(mat.occlusion_strength),
+    ];
+    values.extend(
+        [
+            &mat.base_color_texture,
+            &mat.metallic_roughness_texture,
+            &mat.normal_texture,
+            &mat.occlusion_texture,
+            &mat.emissive_texture,
+        ]
+        .into_iter()
+        .flatten()
+        .map(|texture| MaterialValue::Texture(texture.clone())),
+    );
+
+    Arc::new(CpuMaterialInstance {
+        material: Arc::new(declaration),
+        name: mat.name.clone(),
+        values,
+    })
+}
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use redlilium_graphics::{GraphicsDevice, GraphicsInstance};
+    use redlilium_vfs::MemoryProvider;
+
+    struct Fixture {
+        memory: MemoryProvider,
+        server: AssetServer,
+        textures: TextureManager,
+        meshes: MeshManager,
+    }
+
+    impl Fixture {
+        fn new(files: &[(&str, Vec<u8>)]) -> Self {
+            let memory = MemoryProvider::new();
+            for (path, data) in files {
+                memory.insert(*path, data.clone());
+            }
+            let vfs = Vfs::new();
+            vfs.mount("assets", memory.clone());
+
+            let device: Arc<GraphicsDevice> =
+                GraphicsInstance::new().unwrap().create_device().unwrap();
+            Self {
+                memory,
+                server: AssetServer::new(vfs, IoRuntime::new()),
+                textures: TextureManager::new(Arc::clone(&device)),
+                meshes: MeshManager::new(device),
+            }
+        }
+
+        fn update(&mut self) {
+            self.server.update(&mut AssetManagers {
+                textures: &mut self.textures,
+                meshes: &mut self.meshes,
+            });
+        }
+
+        /// Run updates until the handle leaves the loading state.
+        fn wait<T>(&mut self, handle: &Handle<T>) -> LoadState {
+            for _ in 0..1000 {
+                self.update();
+                if handle.load_state() != LoadState::Loading {
+                    return handle.load_state();
+                }
+                std::thread::sleep(std::time::Duration::from_millis(1));
+            }
+            panic!("asset '{}' did not finish loading", handle.path());
+        }
+
+        /// Run updates until no IO task is in flight.
+        fn settle(&mut self) {
+            for _ in 0..1000 {
+                self.update();
+                if self.server.pending_count() == 0 {
+                    return;
+                }
+                std::thread::sleep(std::time::Duration::from_millis(1));
+            }
+            panic!("assets did not settle");
+        }
+    }
+
+    fn encode_png(width: u32, height: u32) -> Vec<u8> {
+        let image = image::RgbaImage::from_pixel(width, height, image::Rgba([255, 0, 0, 255]));
+        let mut bytes = std::io::Cursor::new(Vec::new());
+        image.write_to(&mut bytes, image::ImageFormat::Png).unwrap();
+        bytes.into_inner()
+    }
+
+    #[test]
+    fn handle_field_round_trips_as_path() {
+        use crate::ComponentField;
+        use crate::serialize::{DeserializeContext, SerializeContext, Value};
+
+        let fx = Fixture::new(&[("shaders/unlit.wgsl", b"@vertex fn vs() {}".to_vec())]);
+        let mut world = crate::World::new();
+        world.insert_resource(fx.server);
+        let handle: Handle<ShaderAsset> = world
+            .resource_mut::<AssetServer>()
+            .load("assets/shaders/unlit.wgsl");
+
+        let mut ctx = SerializeContext::new(&world);
+        ctx.begin_struct("Test").unwrap();
+        handle.serialize_field("shader", &mut ctx).unwrap();
+        let value = ctx.end_struct().unwrap();
+        let Value::Map(fields) = &value else {
+            panic!("expected Map");
+        };
+        assert_eq!(
+            fields[0].1,
+            Value::String("assets/shaders/unlit.wgsl".into())
+        );
+
+        let mut ctx = DeserializeContext::new(&mut world);
+        ctx.load_data(&value).unwrap();
+        ctx.begin_struct("Test").unwrap();
+        let restored = Handle::<ShaderAsset>::deserialize_field("shader", &mut ctx).unwrap();
+        assert_eq!(restored, handle);
+    }
+
+    #[test]
+    fn load_shader() {
+        let mut fx = Fixture::new(&[("shaders/unlit.wgsl", b"@vertex fn vs() {}".to_vec())]);
+        let handle: Handle<ShaderAsset> = fx.server.load("assets/shaders/unlit.wgsl");
+        assert_eq!(handle.load_state(), LoadState::Loading);
+
+        assert_eq!(fx.wait(&handle), LoadState::Loaded);
+        let shader = handle.get().unwrap();
+        assert_eq!(shader.language, ShaderSourceLanguage::Wgsl);
+        assert_eq!(shader.source, "@vertex fn vs() {}");
+    }
+
+    #[test]
+    fn load_deduplicates_by_path() {
+        let mut fx = Fixture::new(&[("a.slang", b"// shader".to_vec())]);
+        let first: Handle<ShaderAsset> = fx.server.load("assets/a.slang");
+        let second: Handle<ShaderAsset> = fx.server.load("assets//./a.slang");
+        assert_eq!(first, second);
+        assert_eq!(first.path(), "assets/a.slang");
+        assert_eq!(fx.server.asset_count(), 1);
+        assert!(
+            fx.server
+                .get_handle::<ShaderAsset>("assets/a.slang")
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn missing_file_fails() {
+        let mut fx = Fixture::new(&[]);
+        let handle: Handle<ShaderAsset> = fx.server.load("assets/missing.wgsl");
+        assert_eq!(fx.wait(&handle), LoadState::Failed);
+        assert!(matches!(
+            handle.error().as_deref(),
+            Some(AssetError::Vfs(VfsError::NotFound(_)))
+        ));
+        assert!(handle.get().is_none());
+    }
+
+    #[test]
+    fn unsupported_extension_fails() {
+        let mut fx = Fixture::new(&[("shader.txt", b"text".to_vec())]);
+        let handle: Handle<ShaderAsset> = fx.server.load("assets/shader.txt");
+        assert_eq!(fx.wait(&handle), LoadState::Failed);
+        assert!(matches!(
+            handle.error().as_deref(),
+            Some(AssetError::UnsupportedFormat(_))
+        ));
+    }
+
+    #[test]
+    fn texture_unloads_with_last_handle() {
+        let mut fx = Fixture::new(&[("brick.png", encode_png(4, 2))]);
+        let handle: Handle<Texture> = fx.server.load("assets/brick.png");
+        assert_eq!(fx.wait(&handle), LoadState::Loaded);
+
+        let texture = handle.get().unwrap();
+        assert_eq!((texture.width(), texture.height()), (4, 2));
+        assert!(fx.textures.get_texture("assets/brick.png").is_some());
+
+        let clone = handle.clone();
+        drop(handle);
+        fx.update();
+        assert_eq!(fx.server.asset_count(), 1);
+
+        drop(clone);
+        fx.update();
+        assert_eq!(fx.server.asset_count(), 0);
+        assert!(fx.textures.get_texture("assets/brick.png").is_none());
+    }
+
+    #[test]
+    fn hot_reload_replaces_changed_asset() {
+        let mut fx = Fixture::new(&[("unlit.wgsl", b"// v1".to_vec())]);
+        fx.server.watch_for_changes();
+        let handle: Handle<ShaderAsset> = fx.server.load("assets/unlit.wgsl");
+        assert_eq!(fx.wait(&handle), LoadState::Loaded);
+        assert_eq!(handle.version(), 1);
+
+        fx.memory.insert("unlit.wgsl", b"// v2".to_vec());
+        fx.settle();
+
+        assert_eq!(handle.version(), 2);
+        assert_eq!(handle.get().unwrap().source, "// v2");
+    }
+
+    #[test]
+    fn hot_reload_updates_texture_in_place() {
+        let mut fx = Fixture::new(&[("brick.png", encode_png(4, 2))]);
+        fx.server.watch_for_changes();
+        let handle: Handle<Texture> = fx.server.load("assets/brick.png");
+        assert_eq!(fx.wait(&handle), LoadState::Loaded);
+        let original = handle.get().unwrap();
+
+        fx.memory.insert("brick.png", encode_png(4, 2));
+        fx.settle();
+        assert_eq!(handle.version(), 2);
+        assert!(Arc::ptr_eq(&handle.get().unwrap(), &original));
+
+        // A different size needs a new texture, registered under the same name
+        fx.memory.insert("brick.png", encode_png(8, 8));
+        fx.settle();
+        let resized = handle.get().unwrap();
+        assert_eq!(handle.version(), 3);
+        assert_eq!((resized.width(), resized.height()), (8, 8));
+        assert!(Arc::ptr_eq(
+            fx.textures.get_texture("assets/brick.png").unwrap(),
+            &resized
+        ));
+    }
+
+    #[test]
+    fn failed_reload_keeps_previous_version() {
+        let mut fx = Fixture::new(&[("brick.png", encode_png(4, 2))]);
+        fx.server.watch_for_changes();
+        let handle: Handle<Texture> = fx.server.load("assets/brick.png");
+        assert_eq!(fx.wait(&handle), LoadState::Loaded);
+
+        fx.memory.insert("brick.png", b"not a png".to_vec());
+        fx.settle();
+
+        assert_eq!(handle.load_state(), LoadState::Loaded);
+        assert_eq!(handle.version(), 1);
+        assert!(fx.textures.get_texture("assets/brick.png").is_some());
+    }
+
+    #[test]
+    fn manual_reload_without_watcher() {
+        let mut fx = Fixture::new(&[("a.slang", b"// v1".to_vec())]);
+        let handle: Handle<ShaderAsset> = fx.server.load("assets/a.slang");
+        assert_eq!(fx.wait(&handle), LoadState::Loaded);
+
+        fx.memory.insert("a.slang", b"// v2".to_vec());
+        fx.settle();
+        assert_eq!(handle.version(), 1);
+
+        assert_eq!(fx.server.reload("assets/a.slang"), 1);
+        fx.settle();
+        assert_eq!(handle.get().unwrap().source, "// v2");
+    }
+
+    #[test]
+    fn shared_watcher_changes_reload() {
+        let mut fx = Fixture::new(&[("a.slang", b"// v1".to_vec())]);
+        let watcher = fx.server.vfs().watch();
+        let handle: Handle<ShaderAsset> = fx.server.load("assets/a.slang");
+        assert_eq!(fx.wait(&handle), LoadState::Loaded);
+
+        fx.memory.insert("a.slang", b"// v2".to_vec());
+        fx.server.apply_changes(&watcher.poll());
+        fx.settle();
+        assert_eq!(handle.get().unwrap().source, "// v2");
+    }
+
+    #[cfg(feature = "serialize-ron")]
+    #[test]
+    fn load_prefab() {
+        let prefab = SerializedPrefab::default();
+        let bytes = crate::serialize::encode(&prefab, Format::Ron).unwrap();
+        let mut fx = Fixture::new(&[("empty.prefab", bytes)]);
+        let handle: Handle<SerializedPrefab> = fx.server.load("assets/empty.prefab");
+        assert_eq!(fx.wait(&handle), LoadState::Loaded);
+        assert!(handle.get().unwrap().entities.is_empty());
+    }
+}
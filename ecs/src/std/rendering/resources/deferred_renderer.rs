@@ -0,0 +1,107 @@
+//! Deferred renderer resource: resolve materials and G-buffer bindings.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use redlilium_graphics::{
+    BindingGroup, GraphicsDevice, Material, MaterialInstance, Mesh, TextureFormat,
+};
+
+use crate::std::rendering::components::GBuffer;
+use crate::std::rendering::shaders::{
+    create_deferred_resolve_material, create_fullscreen_triangle, gbuffer_binding,
+};
+
+/// GPU state of the [`DeferredRenderSystem`](crate::DeferredRenderSystem).
+///
+/// Caches the lighting resolve material per color format and each
+/// camera's G-buffer binding group, rebuilt when the camera's [`GBuffer`]
+/// is replaced (e.g. after a resize).
+pub struct DeferredRenderer {
+    device: Arc<GraphicsDevice>,
+    fullscreen_triangle: Option<Arc<Mesh>>,
+    resolve_materials: HashMap<TextureFormat, Arc<Material>>,
+    gbuffer_bindings: HashMap<u32, (GBuffer, Arc<BindingGroup>)>,
+}
+
+impl DeferredRenderer {
+    /// Create the resource. GPU objects are created on first use.
+    pub fn new(device: Arc<GraphicsDevice>) -> Self {
+        Self {
+            device,
+            fullscreen_triangle: None,
+            resolve_materials: HashMap::new(),
+            gbuffer_bindings: HashMap::new(),
+        }
+    }
+
+    /// Get the graphics device.
+    pub fn device(&self) -> &Arc<GraphicsDevice> {
+        &self.device
+    }
+
+    /// Build the lighting resolve draw for the camera with entity index
+    /// `camera_entity`, shading `gbuffer` into a `color_format` target with
+    /// the camera's scene `lighting` group.
+    ///
+    /// Returns `None` if a GPU resource could not be created.
+    pub fn resolve_draw(
+        &mut self,
+        camera_entity: u32,
+        gbuffer: &GBuffer,
+        color_format: TextureFormat,
+        lighting: &Arc<BindingGroup>,
+    ) -> Option<(Arc<Mesh>, Arc<MaterialInstance>)> {
+        let mesh = match &self.fullscreen_triangle {
+            Some(mesh) => Arc::clone(mesh),
+            None => {
+                let mesh = create_fullscreen_triangle(&self.device)
+                    .inspect_err(|err| log::warn!("Failed to create fullscreen triangle: {err}"))
+                    .ok()?;
+                self.fullscreen_triangle = Some(Arc::clone(&mesh));
+                mesh
+            }
+        };
+        let material = match self.resolve_materials.get(&color_format) {
+            Some(material) => Arc::clone(material),
+            None => {
+                let material = create_deferred_resolve_material(&self.device, color_format)
+                    .inspect_err(|err| {
+                        log::warn!("Failed to create deferred resolve material: {err}")
+                    })
+                    .ok()?;
+                self.resolve_materials
+                    .insert(color_format, Arc::clone(&material));
+                material
+            }
+        };
+
+        let gbuffer_group = match self.gbuffer_bindings.get(&camera_entity) {
+            Some((cached, group)) if same_attachments(cached, gbuffer) => Arc::clone(group),
+            _ => {
+                let group = Arc::new(gbuffer_binding(gbuffer));
+                self.gbuffer_bindings
+                    .insert(camera_entity, (gbuffer.clone(), Arc::clone(&group)));
+                group
+            }
+        };
+
+        let instance = MaterialInstance::new(material)
+            .with_binding_group(gbuffer_group)
+            .with_binding_group(Arc::clone(lighting))
+            .with_label(format!("deferred_resolve_{camera_entity}"));
+        Some((mesh, Arc::new(instance)))
+    }
+
+    /// Drop the cached state of cameras for which `keep` returns `false`.
+    pub fn retain_cameras(&mut self, mut keep: impl FnMut(u32) -> bool) {
+        self.gbuffer_bindings.retain(|camera, _| keep(*camera));
+    }
+}
+
+fn same_attachments(a: &GBuffer, b: &GBuffer) -> bool {
+    a.attachments()
+        .into_iter()
+        .zip(b.attachments())
+        .all(|(a, b)| Arc::ptr_eq(a, b))
+}
@@ -0,0 +1,326 @@
+//! Draw batching resource.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{Arc, Weak};
+
+use redlilium_core::math::{Mat4, mat4_to_cols_array_2d};
+use redlilium_core::mesh::VertexLayout;
+use redlilium_graphics::{
+    BufferUsage, DrawCommand, FrameSchedule, GraphicsError, GraphicsPass, Material,
+    MaterialInstance, Mesh,
+};
+
+use crate::std::rendering::components::RenderQueue;
+
+/// Shader define enabling per-instance model matrices.
+///
+/// The standard opaque color and PBR shaders read the model matrix from the
+/// [`VertexAttributeSemantic::INSTANCE_TRANSFORM`](redlilium_core::mesh::VertexAttributeSemantic::INSTANCE_TRANSFORM)
+/// attributes instead of the transform group when it is set.
+pub const INSTANCED_DEFINE: &str = "INSTANCED";
+
+/// A draw collected by the render systems before batching.
+pub(crate) struct QueuedDraw {
+    pub mesh: Arc<Mesh>,
+    pub instance: Arc<MaterialInstance>,
+    pub model: Mat4,
+    /// Distance along the camera's view direction.
+    pub depth: f32,
+    pub queue: RenderQueue,
+}
+
+/// Resource grouping a pass's draws into instanced draws and ordering them.
+///
+/// The render systems hand every visible entity of a pass to the batcher.
+/// Entities sharing a mesh and a material instance's non-transform binding
+/// groups (see
+/// [`create_pbr_entity_sharing`](crate::shaders::create_pbr_entity_sharing))
+/// are drawn with a single instanced draw if their material has an
+/// instanced variant, registered with
+/// [`enable_instancing`](Self::enable_instancing). Their model matrices are
+/// written into the frame's ring buffer, which must be created with
+/// [`BufferUsage::VERTEX`] (see
+/// [`FramePipeline::create_ring_buffers`](redlilium_graphics::FramePipeline::create_ring_buffers));
+/// without one, every entity keeps its own draw.
+///
+/// Draws are ordered by [`RenderQueue`]. Opaque and alpha-tested draws are
+/// ordered front-to-back, batch by batch, so early depth testing rejects
+/// hidden fragments. Blended draws are ordered back-to-front after them, and
+/// only neighbours in that order are merged into a batch.
+#[derive(Default)]
+pub struct DrawBatcher {
+    /// Instanced variant per material, keyed by the material pointer.
+    instanced_materials: HashMap<usize, (Arc<Material>, Arc<Material>)>,
+    /// Instanced view of a mesh per (mesh, ring buffer) pointer pair.
+    instanced_meshes: HashMap<(usize, usize), (Weak<Mesh>, Arc<Mesh>)>,
+}
+
+impl DrawBatcher {
+    /// Create a batcher without instanced materials.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create and register the instanced variant of `material`.
+    ///
+    /// The variant compiles the same shaders with [`INSTANCED_DEFINE`] set
+    /// and an extra per-instance vertex buffer holding the model matrices
+    /// (see [`VertexLayout::instanced`]). Returns the existing variant if
+    /// one is registered already.
+    pub fn enable_instancing(
+        &mut self,
+        material: &Arc<Material>,
+    ) -> Result<Arc<Material>, GraphicsError> {
+        if let Some(instanced) = self.instanced_material(material) {
+            return Ok(Arc::clone(instanced));
+        }
+        let mut descriptor = material.descriptor().clone();
+        for shader in &mut descriptor.shaders {
+            shader
+                .defines
+                .push((INSTANCED_DEFINE.to_owned(), "1".to_owned()));
+        }
+        descriptor.vertex_layout = Arc::new(descriptor.vertex_layout.instanced());
+        descriptor.label = descriptor.label.map(|label| format!("{label}_instanced"));
+        let instanced = material.device().create_material(&descriptor)?;
+        self.register_instanced(material, Arc::clone(&instanced));
+        Ok(instanced)
+    }
+
+    /// Register `instanced` as the instanced variant of `material`.
+    ///
+    /// Its vertex layout must be `material`'s layout made
+    /// [`instanced`](VertexLayout::instanced), and it must expect the same
+    /// binding groups.
+    pub fn register_instanced(&mut self, material: &Arc<Material>, instanced: Arc<Material>) {
+        self.instanced_materials.insert(
+            Arc::as_ptr(material) as usize,
+            (Arc::clone(material), instanced),
+        );
+    }
+
+    /// Remove the instanced variant of `material`, e.g. after its pipeline
+    /// was recompiled.
+    pub fn unregister_instanced(&mut self, material: &Arc<Material>) {
+        self.instanced_materials
+            .remove(&(Arc::as_ptr(material) as usize));
+    }
+
+    /// The instanced variant of `material`, if registered.
+    pub fn instanced_material(&self, material: &Arc<Material>) -> Option<&Arc<Material>> {
+        self.instanced_materials
+            .get(&(Arc::as_ptr(material) as usize))
+            .map(|(_, instanced)| instanced)
+    }
+
+    /// Add `draws` to `pass`, batched and in draw order.
+    pub(crate) fn add_draws(
+        &mut self,
+        pass: &mut GraphicsPass,
+        draws: Vec<QueuedDraw>,
+        schedule: &mut FrameSchedule,
+    ) {
+        let instancing = schedule.ring_buffer().is_some_and(|ring| {
+            ring.buffer()
+                .descriptor()
+                .usage
+                .contains(BufferUsage::VERTEX)
+        });
+        let keys: Vec<Option<Vec<usize>>> = draws
+            .iter()
+            .map(|draw| instancing.then(|| self.batch_key(draw)).flatten())
+            .collect();
+        let depths: Vec<f32> = draws.iter().map(|draw| draw.depth).collect();
+        let queues: Vec<RenderQueue> = draws.iter().map(|draw| draw.queue).collect();
+
+        for batch in plan_batches(&keys, &depths, &queues) {
+            if batch.len() < 2 || !self.add_instanced(pass, &draws, &batch, schedule) {
+                for index in batch {
+                    let draw = &draws[index];
+                    pass.add_draw(Arc::clone(&draw.mesh), Arc::clone(&draw.instance));
+                }
+            }
+        }
+    }
+
+    /// Key of draws that can share an instanced draw with `draw`: its mesh,
+    /// material and every binding group but the per-entity transform.
+    fn batch_key(&self, draw: &QueuedDraw) -> Option<Vec<usize>> {
+        let material = draw.instance.material();
+        self.instanced_material(material)?;
+        let mut key = vec![
+            Arc::as_ptr(&draw.mesh) as usize,
+            Arc::as_ptr(material) as usize,
+        ];
+        key.extend(
+            draw.instance
+                .binding_groups()
+                .iter()
+                .skip(1)
+                .map(|group| Arc::as_ptr(group) as usize),
+        );
+        Some(key)
+    }
+
+    /// Write the model matrices of `batch` into the ring buffer and add one
+    /// instanced draw for it. Returns `false` if the ring buffer is full.
+    fn add_instanced(
+        &mut self,
+        pass: &mut GraphicsPass,
+        draws: &[QueuedDraw],
+        batch: &[usize],
+        schedule: &mut FrameSchedule,
+    ) -> bool {
+        let first = &draws[batch[0]];
+        let Some(instanced_material) = self.instanced_material(first.instance.material()).cloned()
+        else {
+            return false;
+        };
+        let stride = u64::from(VertexLayout::INSTANCE_TRANSFORM_STRIDE);
+        let Some(allocation) = schedule.allocate_aligned(batch.len() as u64 * stride, stride)
+        else {
+            return false;
+        };
+        let Some(ring) = schedule.ring_buffer() else {
+            return false;
+        };
+        let buffer = Arc::clone(ring.buffer());
+
+        let models: Vec<[[f32; 4]; 4]> = batch
+            .iter()
+            .map(|&index| mat4_to_cols_array_2d(&draws[index].model))
+            .collect();
+        let device = instanced_material.device();
+        if let Err(e) =
+            device.write_buffer(&buffer, allocation.offset, bytemuck::cast_slice(&models))
+        {
+            log::error!("Failed to write instance transforms: {e}");
+            return false;
+        }
+
+        let mesh_key = (
+            Arc::as_ptr(&first.mesh) as usize,
+            Arc::as_ptr(&buffer) as usize,
+        );
+        let mesh = match self.instanced_meshes.get(&mesh_key) {
+            Some((source, mesh)) if source.strong_count() > 0 => Arc::clone(mesh),
+            _ => {
+                self.instanced_meshes
+                    .retain(|_, (source, _)| source.strong_count() > 0);
+                let mesh = Arc::new(first.mesh.with_instance_buffer(buffer));
+                self.instanced_meshes
+                    .insert(mesh_key, (Arc::downgrade(&first.mesh), Arc::clone(&mesh)));
+                mesh
+            }
+        };
+
+        // The transform group of the first entity stays bound; instanced
+        // shaders only read its view data.
+        let mut instance = MaterialInstance::new(instanced_material);
+        instance.set_binding_groups(first.instance.binding_groups().to_vec());
+        if let Some(label) = first.instance.label() {
+            instance = instance.with_label(label);
+        }
+
+        pass.add_draw_command(
+            DrawCommand::new(mesh, Arc::new(instance))
+                .with_instance_count(batch.len() as u32)
+                .with_first_instance((allocation.offset / stride) as u32),
+        );
+        true
+    }
+}
+
+/// Group draws into batches and order them for drawing.
+///
+/// `keys[i]` is the batch key of draw `i` (`None` never batches), `depths`
+/// its view depth and `queues` its render queue. Queues are drawn in order.
+/// Opaque and masked draws are grouped by key, each group sorted
+/// front-to-back and the groups ordered by their nearest draw. Blended
+/// draws are sorted back-to-front, with only consecutive draws of equal
+/// keys merged.
+pub(crate) fn plan_batches<K: Eq + Hash>(
+    keys: &[Option<K>],
+    depths: &[f32],
+    queues: &[RenderQueue],
+) -> Vec<Vec<usize>> {
+    let mut batches: Vec<Vec<usize>> = Vec::new();
+    for queue in [RenderQueue::Opaque, RenderQueue::Mask, RenderQueue::Blend] {
+        let mut draws: Vec<usize> = (0..keys.len()).filter(|&i| queues[i] == queue).collect();
+        if queue == RenderQueue::Blend {
+            draws.sort_by(|&a, &b| depths[b].total_cmp(&depths[a]));
+            let mut previous: Option<&K> = None;
+            for index in draws {
+                let key = keys[index].as_ref();
+                match (key, previous) {
+                    (Some(key), Some(previous)) if key == previous => {
+                        batches.last_mut().expect("previous batch").push(index);
+                    }
+                    _ => batches.push(vec![index]),
+                }
+                previous = key;
+            }
+            continue;
+        }
+
+        draws.sort_by(|&a, &b| depths[a].total_cmp(&depths[b]));
+        let mut by_key: HashMap<&K, usize> = HashMap::new();
+        for index in draws {
+            match &keys[index] {
+                Some(key) => match by_key.get(key) {
+                    Some(&batch) => batches[batch].push(index),
+                    None => {
+                        by_key.insert(key, batches.len());
+                        batches.push(vec![index]);
+                    }
+                },
+                None => batches.push(vec![index]),
+            }
+        }
+    }
+    batches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OPAQUE: RenderQueue = RenderQueue::Opaque;
+    const MASK: RenderQueue = RenderQueue::Mask;
+    const BLEND: RenderQueue = RenderQueue::Blend;
+
+    #[test]
+    fn opaque_batches_front_to_back() {
+        let keys = [Some(1), Some(2), Some(1), None, Some(2)];
+        let depths = [5.0, 1.0, 2.0, 3.0, 9.0];
+        let batches = plan_batches(&keys, &depths, &[OPAQUE; 5]);
+        assert_eq!(batches, vec![vec![1, 4], vec![2, 0], vec![3]]);
+    }
+
+    #[test]
+    fn blended_draws_follow_back_to_front() {
+        let keys = [Some(1), Some(1), Some(1), Some(2), Some(1)];
+        let depths = [1.0, 4.0, 2.0, 3.0, 8.0];
+        let queues = [OPAQUE, BLEND, BLEND, BLEND, BLEND];
+        let batches = plan_batches(&keys, &depths, &queues);
+        // Draw 3 sits between the blended key-1 draws, splitting them.
+        assert_eq!(batches, vec![vec![0], vec![4, 1], vec![3], vec![2]]);
+    }
+
+    #[test]
+    fn masked_draws_follow_opaque_ones() {
+        let keys = [Some(1), Some(1), Some(1), Some(1)];
+        let depths = [4.0, 1.0, 3.0, 2.0];
+        let queues = [OPAQUE, MASK, OPAQUE, BLEND];
+        let batches = plan_batches(&keys, &depths, &queues);
+        assert_eq!(batches, vec![vec![2, 0], vec![1], vec![3]]);
+    }
+
+    #[test]
+    fn unkeyed_draws_never_merge() {
+        let keys: [Option<u32>; 3] = [None, None, None];
+        let batches = plan_batches(&keys, &[3.0, 1.0, 2.0], &[OPAQUE, BLEND, BLEND]);
+        assert_eq!(batches, vec![vec![0], vec![2], vec![1]]);
+    }
+}
@@ -1,6 +1,6 @@
 //! GPU material management.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::sync::Arc;
 
@@ -9,9 +9,10 @@ use redlilium_core::material::{
 };
 use redlilium_graphics::{
     BindingGroup, Buffer, BufferDescriptor, BufferUsage, CpuSampler, GraphicsDevice, GraphicsError,
-    Material, MaterialInstance, Sampler, Texture,
+    Material, MaterialInstance, Sampler, ShaderLibrary, ShaderStage, Texture,
 };
 
+use super::asset_server::{Handle, ShaderAsset};
 use super::texture_manager::TextureManager;
 use crate::std::rendering::components::{MaterialBundle, RenderPassType};
 
@@ -72,13 +73,40 @@ pub struct CpuBundleInfo {
     pub pass_materials: Vec<(RenderPassType, String)>,
 }
 
+/// Shader file one stage of a registered material is compiled from.
+struct StageShader {
+    stage: ShaderStage,
+    shader: Handle<ShaderAsset>,
+    /// Shader version the current pipeline was built from.
+    version: u64,
+}
+
+/// Shader file a Slang library module is loaded from.
+struct LibraryModule {
+    shader: Handle<ShaderAsset>,
+    /// Shader version last handed to the device.
+    version: u64,
+}
+
 /// Resource for managing GPU materials and converting between CPU and GPU representations.
+///
+/// Material stages can be tied to a [`ShaderAsset`] with
+/// [`set_material_shader`](Self::set_material_shader), and Slang library
+/// modules with [`set_library_module`](Self::set_library_module). When either
+/// is hot reloaded, [`reload_shaders`](Self::reload_shaders) recompiles the
+/// affected pipelines; the
+/// [`ReloadMaterialShaders`](crate::std::rendering::ReloadMaterialShaders)
+/// system then switches render materials over to them.
 pub struct MaterialManager {
     device: Arc<GraphicsDevice>,
     /// Registered materials: name → (cpu declaration, gpu pipeline).
     materials: HashMap<String, (Arc<CpuMaterial>, Arc<Material>)>,
     /// CPU bundle info keyed by MaterialBundle Arc pointer.
     cpu_bundles: HashMap<usize, CpuBundleInfo>,
+    /// Shader files material stages are compiled from, keyed by material name.
+    shader_dependencies: HashMap<String, Vec<StageShader>>,
+    /// Shader files library modules are loaded from, keyed by module name.
+    library_modules: HashMap<String, LibraryModule>,
 }
 
 impl MaterialManager {
@@ -88,6 +116,8 @@ impl MaterialManager {
             device,
             materials: HashMap::new(),
             cpu_bundles: HashMap::new(),
+            shader_dependencies: HashMap::new(),
+            library_modules: HashMap::new(),
         }
     }
 
@@ -129,9 +159,171 @@ impl MaterialManager {
 
     /// Remove a registered material by name.
     pub fn remove_material(&mut self, name: &str) -> Option<(Arc<CpuMaterial>, Arc<Material>)> {
+        self.shader_dependencies.remove(name);
         self.materials.remove(name)
     }
 
+    // --- Shader hot reload ---
+
+    /// Tie every shader stage of a registered material to the shader file it
+    /// is compiled from.
+    ///
+    /// Stages are recompiled from the asset's source whenever its
+    /// [version](Handle::version) changes; entry points, defines and all other
+    /// pipeline state are kept. Does nothing if no material is registered
+    /// under `name`.
+    pub fn set_material_shader(&mut self, name: &str, shader: Handle<ShaderAsset>) {
+        let Some((_, material)) = self.materials.get(name) else {
+            log::warn!(
+                "Cannot watch shader '{}': material '{name}' not found",
+                shader.path()
+            );
+            return;
+        };
+        let stages: Vec<ShaderStage> = material
+            .descriptor()
+            .shaders
+            .iter()
+            .map(|s| s.stage)
+            .collect();
+        for stage in stages {
+            self.set_stage_shader(name, stage, shader.clone());
+        }
+    }
+
+    /// Tie one shader stage of a material to the shader file it is compiled
+    /// from, replacing the file previously set for that stage.
+    ///
+    /// Only this stage's source is replaced when the asset is hot reloaded.
+    pub fn set_stage_shader(
+        &mut self,
+        name: impl Into<String>,
+        stage: ShaderStage,
+        shader: Handle<ShaderAsset>,
+    ) {
+        let stages = self.shader_dependencies.entry(name.into()).or_default();
+        stages.retain(|dependency| dependency.stage != stage);
+        stages.push(StageShader {
+            stage,
+            version: shader.version(),
+            shader,
+        });
+    }
+
+    /// Load the Slang library module `name` from a shader file.
+    ///
+    /// Whenever the asset changes, the module is replaced on the device
+    /// ([`GraphicsDevice::set_shader_library_module`]) and every registered
+    /// material importing it, directly or through other modules, is
+    /// recompiled.
+    pub fn set_library_module(&mut self, name: impl Into<String>, shader: Handle<ShaderAsset>) {
+        self.library_modules.insert(
+            name.into(),
+            LibraryModule {
+                version: shader.version(),
+                shader,
+            },
+        );
+    }
+
+    /// Recompile materials whose shader assets or imported library modules
+    /// changed.
+    ///
+    /// Returns `(previous, recompiled)` pipeline pairs so that bundles using
+    /// the previous pipeline can be switched over. If compilation fails the
+    /// error is logged and the previous pipeline stays registered.
+    pub fn reload_shaders(&mut self) -> Vec<(Arc<Material>, Arc<Material>)> {
+        let affected_modules = self.reload_library_modules();
+
+        let mut replaced = Vec::new();
+        for (name, (_, material)) in &mut self.materials {
+            let mut descriptor = material.descriptor().clone();
+            let mut changed = Vec::new();
+            for dependency in self.shader_dependencies.get_mut(name).into_iter().flatten() {
+                let version = dependency.shader.version();
+                if version == dependency.version {
+                    continue;
+                }
+                dependency.version = version;
+                let Some(shader) = dependency.shader.get() else {
+                    continue;
+                };
+                for source in &mut descriptor.shaders {
+                    if source.stage == dependency.stage
+                        && (source.source != shader.source.as_bytes()
+                            || source.language != shader.language)
+                    {
+                        source.source = shader.source.as_bytes().to_vec();
+                        source.language = shader.language;
+                        changed.push(dependency.shader.path().to_owned());
+                    }
+                }
+            }
+
+            let imports_changed = !affected_modules.is_empty()
+                && descriptor.shaders.iter().any(|source| {
+                    ShaderLibrary::imports(&String::from_utf8_lossy(&source.source))
+                        .any(|module| affected_modules.contains(module))
+                });
+            if changed.is_empty() && !imports_changed {
+                continue;
+            }
+            changed.dedup();
+            let cause = if changed.is_empty() {
+                "library modules".to_owned()
+            } else {
+                format!("'{}'", changed.join("', '"))
+            };
+
+            match self.device.create_material(&descriptor) {
+                Ok(recompiled) => {
+                    log::info!("Recompiled material '{name}' from {cause}");
+                    let previous = std::mem::replace(material, Arc::clone(&recompiled));
+                    replaced.push((previous, recompiled));
+                }
+                Err(err) => {
+                    log::error!(
+                        "Failed to recompile material '{name}' from {cause}, keeping previous pipeline: {err}"
+                    );
+                }
+            }
+        }
+        replaced
+    }
+
+    /// Hand changed library modules to the device.
+    ///
+    /// Returns the changed modules plus all modules importing them.
+    fn reload_library_modules(&mut self) -> HashSet<String> {
+        let mut library = self.device.shader_library();
+        let mut changed = Vec::new();
+        for (name, module) in &mut self.library_modules {
+            let version = module.shader.version();
+            if version == module.version {
+                continue;
+            }
+            module.version = version;
+            let Some(shader) = module.shader.get() else {
+                continue;
+            };
+            if library.module(name) == Some(shader.source.as_str()) {
+                continue;
+            }
+            log::info!(
+                "Reloaded shader library module '{name}' from '{}'",
+                module.shader.path()
+            );
+            self.device
+                .set_shader_library_module(name, shader.source.clone());
+            library.set_module(name, shader.source.clone());
+            changed.push(name.as_str());
+        }
+        if changed.is_empty() {
+            return HashSet::new();
+        }
+        library.dependents(changed)
+    }
+
     // --- Bundle creation ---
 
     /// Create a [`MaterialBundle`] from a [`CpuMaterialInstance`] and a set of
@@ -247,6 +439,13 @@ impl MaterialManager {
         );
     }
 
+    /// Move the CPU source data of `old` over to its replacement `new`.
+    pub fn replace_bundle(&mut self, old: &Arc<MaterialBundle>, new: &Arc<MaterialBundle>) {
+        if let Some(info) = self.cpu_bundles.remove(&(Arc::as_ptr(old) as usize)) {
+            self.cpu_bundles.insert(Arc::as_ptr(new) as usize, info);
+        }
+    }
+
     /// Get the CPU source data for a material bundle (for serialization).
     pub fn get_cpu_bundle(&self, bundle: &Arc<MaterialBundle>) -> Option<&CpuBundleInfo> {
         let ptr = Arc::as_ptr(bundle) as usize;
@@ -321,9 +520,10 @@ impl MaterialManager {
             return Ok(None);
         }
 
+        // Shaders round uniform blocks up to 16 bytes; size the buffer to match.
         let buffer = self.device.create_buffer(
             &BufferDescriptor::new(
-                uniform_data.len() as u64,
+                uniform_data.len().next_multiple_of(16) as u64,
                 BufferUsage::UNIFORM | BufferUsage::COPY_DST,
             )
             .with_label("material_uniforms"),
@@ -402,3 +602,76 @@ pub fn pack_uniform_bytes(
     }
     data
 }
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use crate::std::rendering::resources::{AssetManagers, AssetServer, MeshManager};
+    use redlilium_graphics::{GraphicsInstance, MaterialDescriptor, ShaderSource};
+    use redlilium_vfs::{MemoryProvider, Vfs};
+
+    const VERTEX: &str = "@vertex fn vs() -> @builtin(position) vec4<f32> { return vec4<f32>(); }";
+    const FRAGMENT: &str = "@fragment fn fs() -> @location(0) vec4<f32> { return vec4<f32>(); }";
+
+    fn settle(server: &mut AssetServer, textures: &mut TextureManager, meshes: &mut MeshManager) {
+        for _ in 0..1000 {
+            server.update(&mut AssetManagers { textures, meshes });
+            if server.pending_count() == 0 {
+                return;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        panic!("assets did not settle");
+    }
+
+    #[test]
+    fn reload_replaces_only_changed_stage() {
+        let memory = MemoryProvider::new();
+        memory.insert("vertex.wgsl", VERTEX.as_bytes().to_vec());
+        memory.insert("fragment.wgsl", FRAGMENT.as_bytes().to_vec());
+        let vfs = Vfs::new();
+        vfs.mount("assets", memory.clone());
+
+        let device: Arc<GraphicsDevice> = GraphicsInstance::new().unwrap().create_device().unwrap();
+        let mut textures = TextureManager::new(Arc::clone(&device));
+        let mut meshes = MeshManager::new(Arc::clone(&device));
+        let mut server = AssetServer::new(vfs, crate::IoRuntime::new());
+        server.watch_for_changes();
+
+        let material = device
+            .create_material(
+                &MaterialDescriptor::new()
+                    .with_shader(ShaderSource::new(ShaderStage::Vertex, VERTEX, "vs"))
+                    .with_shader(ShaderSource::new(ShaderStage::Fragment, FRAGMENT, "fs")),
+            )
+            .unwrap();
+        let mut manager = MaterialManager::new(device);
+        manager.register_material("unlit", Arc::new(CpuMaterial::new()), material);
+        manager.set_stage_shader(
+            "unlit",
+            ShaderStage::Vertex,
+            server.load("assets/vertex.wgsl"),
+        );
+        manager.set_stage_shader(
+            "unlit",
+            ShaderStage::Fragment,
+            server.load("assets/fragment.wgsl"),
+        );
+
+        // The first load matches the compiled sources and rebuilds nothing.
+        settle(&mut server, &mut textures, &mut meshes);
+        assert!(manager.reload_shaders().is_empty());
+
+        let edited = FRAGMENT.replace("vec4<f32>()", "vec4<f32>(1.0)");
+        memory.insert("fragment.wgsl", edited.as_bytes().to_vec());
+        settle(&mut server, &mut textures, &mut meshes);
+
+        let replaced = manager.reload_shaders();
+        assert_eq!(replaced.len(), 1);
+        let shaders = &replaced[0].1.descriptor().shaders;
+        assert_eq!(shaders[0].source, VERTEX.as_bytes());
+        assert_eq!(shaders[1].source, edited.as_bytes());
+        assert!(manager.reload_shaders().is_empty());
+    }
+}
@@ -59,6 +59,7 @@ impl MeshManager {
 
     /// Remove a mesh from the cache by name, returning it if present.
     pub fn remove_mesh(&mut self, name: &str) -> Option<Arc<Mesh>> {
+        self.aabbs.remove(name);
         self.meshes.remove(name)
     }
 
@@ -1,13 +1,33 @@
 //! Rendering resource types.
 
+mod asset_server;
+mod deferred_renderer;
+mod draw_batcher;
 mod material_manager;
 mod mesh_manager;
+mod post_process_renderer;
 mod render_schedule;
+mod scene_lighting;
+mod shadow_maps;
+mod spatial_index;
 mod texture_manager;
 
+pub use asset_server::{
+    Asset, AssetError, AssetManagers, AssetServer, Handle, LoadState, SceneAsset, ShaderAsset,
+};
+pub use deferred_renderer::DeferredRenderer;
+pub(crate) use draw_batcher::QueuedDraw;
+pub use draw_batcher::{DrawBatcher, INSTANCED_DEFINE};
 pub use material_manager::{CpuBundleInfo, MaterialManager, MaterialManagerError};
 pub use mesh_manager::MeshManager;
+pub use post_process_renderer::PostProcessRenderer;
 pub use render_schedule::RenderSchedule;
+pub use scene_lighting::{
+    EnvironmentLighting, GpuLight, GpuShadowView, LightingViewUniforms, MAX_SHADOW_ATLASES,
+    SceneLighting,
+};
+pub use shadow_maps::{SHADOW_DEPTH_FORMAT, ShadowMap, ShadowMapKind, ShadowMaps, ShadowView};
+pub use spatial_index::{CullingStats, RayHit, SpatialIndex};
 pub use texture_manager::{TextureManager, TextureManagerError};
 
 // Re-export pack_uniform_bytes at module level
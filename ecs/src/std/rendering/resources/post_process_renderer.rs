@@ -0,0 +1,437 @@
+//! Post-processing renderer resource: effect materials and intermediate targets.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use redlilium_graphics::{
+    AddressMode, BindingGroup, Buffer, BufferDescriptor, BufferUsage, ColorAttachment,
+    GraphicsDevice, GraphicsPass, LoadOp, Material, MaterialInstance, Mesh, RenderTargetConfig,
+    Sampler, SamplerDescriptor, StoreOp, Texture, TextureDescriptor, TextureFormat, TextureUsage,
+};
+
+use crate::std::rendering::components::{PostEffect, PostProcessStack, ToneMapping};
+use crate::std::rendering::shaders::{
+    PostProcessUniforms, create_fullscreen_triangle, create_post_process_material,
+};
+
+/// Format of the intermediate targets between effects.
+const INTERMEDIATE_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+
+/// Image a post-processing step reads or writes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Slot {
+    /// The camera's scene color texture.
+    Source,
+    /// Full resolution intermediate targets, alternated between effects.
+    Ping,
+    Pong,
+    /// Half resolution bloom targets.
+    BloomA,
+    BloomB,
+    /// The stack's output texture.
+    Output,
+}
+
+/// One fullscreen pass of an expanded effect chain.
+#[derive(Debug, Clone, PartialEq)]
+struct PostStep {
+    entry_point: &'static str,
+    input: Slot,
+    aux: Option<Slot>,
+    output: Slot,
+    params: [f32; 4],
+    /// Index of the effect the step belongs to, `None` for the plain copy.
+    effect: Option<usize>,
+}
+
+impl PostStep {
+    fn new(entry_point: &'static str, input: Slot, output: Slot, params: [f32; 4]) -> Self {
+        Self {
+            entry_point,
+            input,
+            aux: None,
+            output,
+            params,
+            effect: None,
+        }
+    }
+}
+
+/// Expand `effects` into passes, alternating between the intermediate
+/// targets and writing the last pass to [`Slot::Output`].
+fn plan_steps(effects: &[PostEffect]) -> Vec<PostStep> {
+    if effects.is_empty() {
+        return vec![PostStep::new(
+            "fs_copy",
+            Slot::Source,
+            Slot::Output,
+            [0.0; 4],
+        )];
+    }
+
+    let mut steps = Vec::new();
+    let mut current = Slot::Source;
+    for (index, effect) in effects.iter().enumerate() {
+        let output = if index + 1 == effects.len() {
+            Slot::Output
+        } else if current == Slot::Ping {
+            Slot::Pong
+        } else {
+            Slot::Ping
+        };
+        let mut step = match effect {
+            PostEffect::Exposure(ev) => {
+                PostStep::new("fs_exposure", current, output, [ev.exp2(), 0.0, 0.0, 0.0])
+            }
+            PostEffect::ToneMapping(ToneMapping::Aces) => {
+                PostStep::new("fs_tonemap_aces", current, output, [0.0; 4])
+            }
+            PostEffect::ToneMapping(ToneMapping::Agx) => {
+                PostStep::new("fs_tonemap_agx", current, output, [0.0; 4])
+            }
+            PostEffect::Bloom {
+                threshold,
+                intensity,
+            } => {
+                let bloom = [
+                    PostStep::new(
+                        "fs_bloom_extract",
+                        current,
+                        Slot::BloomA,
+                        [*threshold, 0.0, 0.0, 0.0],
+                    ),
+                    PostStep::new("fs_blur", Slot::BloomA, Slot::BloomB, [1.0, 0.0, 0.0, 0.0]),
+                    PostStep::new("fs_blur", Slot::BloomB, Slot::BloomA, [0.0, 1.0, 0.0, 0.0]),
+                ];
+                steps.extend(bloom.into_iter().map(|step| PostStep {
+                    effect: Some(index),
+                    ..step
+                }));
+                PostStep {
+                    aux: Some(Slot::BloomA),
+                    ..PostStep::new(
+                        "fs_bloom_composite",
+                        current,
+                        output,
+                        [*intensity, 0.0, 0.0, 0.0],
+                    )
+                }
+            }
+            PostEffect::Fxaa => PostStep::new("fs_fxaa", current, output, [0.0; 4]),
+            PostEffect::Vignette {
+                intensity,
+                smoothness,
+            } => PostStep::new(
+                "fs_vignette",
+                current,
+                output,
+                [*intensity, *smoothness, 0.0, 0.0],
+            ),
+            PostEffect::ColorGrading { lut, strength } => PostStep::new(
+                "fs_color_grading",
+                current,
+                output,
+                [*strength, lut.height() as f32, 0.0, 0.0],
+            ),
+        };
+        step.effect = Some(index);
+        steps.push(step);
+        current = output;
+    }
+    steps
+}
+
+/// Intermediate targets and uniform buffers of one camera's chain.
+struct CameraPostState {
+    size: (u32, u32),
+    targets: HashMap<Slot, Arc<Texture>>,
+    uniforms: Vec<Arc<Buffer>>,
+}
+
+/// GPU state of camera [`PostProcessStack`]s.
+///
+/// Caches one material per effect and target format, and per camera the
+/// intermediate targets (recreated when the output size changes) and the
+/// uniform buffers of each pass. The render systems append the passes
+/// returned by [`build_passes`](Self::build_passes) after a camera's scene
+/// passes.
+pub struct PostProcessRenderer {
+    device: Arc<GraphicsDevice>,
+    fullscreen_triangle: Option<Arc<Mesh>>,
+    sampler: Option<Arc<Sampler>>,
+    materials: HashMap<(&'static str, TextureFormat), Arc<Material>>,
+    cameras: HashMap<u32, CameraPostState>,
+}
+
+impl PostProcessRenderer {
+    /// Create the resource. GPU objects are created on first use.
+    pub fn new(device: Arc<GraphicsDevice>) -> Self {
+        Self {
+            device,
+            fullscreen_triangle: None,
+            sampler: None,
+            materials: HashMap::new(),
+            cameras: HashMap::new(),
+        }
+    }
+
+    /// Get the graphics device.
+    pub fn device(&self) -> &Arc<GraphicsDevice> {
+        &self.device
+    }
+
+    /// Build the passes running `stack` on `source`, the scene color of the
+    /// camera with entity index `camera_entity`, in execution order.
+    ///
+    /// Each pass reads the previous one's target; the caller orders them in
+    /// the render graph. Returns no passes if a GPU resource could not be
+    /// created.
+    pub fn build_passes(
+        &mut self,
+        camera_entity: u32,
+        source: &Arc<Texture>,
+        stack: &PostProcessStack,
+        name_prefix: &str,
+    ) -> Vec<GraphicsPass> {
+        self.try_build_passes(camera_entity, source, stack, name_prefix)
+            .unwrap_or_default()
+    }
+
+    fn try_build_passes(
+        &mut self,
+        camera_entity: u32,
+        source: &Arc<Texture>,
+        stack: &PostProcessStack,
+        name_prefix: &str,
+    ) -> Option<Vec<GraphicsPass>> {
+        let mesh = match &self.fullscreen_triangle {
+            Some(mesh) => Arc::clone(mesh),
+            None => {
+                let mesh = create_fullscreen_triangle(&self.device)
+                    .inspect_err(|err| log::warn!("Failed to create fullscreen triangle: {err}"))
+                    .ok()?;
+                self.fullscreen_triangle = Some(Arc::clone(&mesh));
+                mesh
+            }
+        };
+        let sampler = match &self.sampler {
+            Some(sampler) => Arc::clone(sampler),
+            None => {
+                let sampler = self
+                    .device
+                    .create_sampler(
+                        &SamplerDescriptor::linear()
+                            .with_address_mode(AddressMode::ClampToEdge)
+                            .with_label("post_process_sampler"),
+                    )
+                    .inspect_err(|err| log::warn!("Failed to create post-process sampler: {err}"))
+                    .ok()?;
+                self.sampler = Some(Arc::clone(&sampler));
+                sampler
+            }
+        };
+
+        let size = (stack.output.width(), stack.output.height());
+        let state = self
+            .cameras
+            .entry(camera_entity)
+            .or_insert_with(|| CameraPostState {
+                size,
+                targets: HashMap::new(),
+                uniforms: Vec::new(),
+            });
+        if state.size != size {
+            state.size = size;
+            state.targets.clear();
+        }
+
+        let steps = plan_steps(&stack.effects);
+        let mut passes = Vec::with_capacity(steps.len());
+        for (index, step) in steps.iter().enumerate() {
+            let input = self.slot_texture(camera_entity, step.input, source, stack)?;
+            let output = self.slot_texture(camera_entity, step.output, source, stack)?;
+            let aux = match (step.aux, step.effect.map(|effect| &stack.effects[effect])) {
+                (Some(slot), _) => self.slot_texture(camera_entity, slot, source, stack)?,
+                (None, Some(PostEffect::ColorGrading { lut, .. })) => Arc::clone(lut),
+                (None, _) => Arc::clone(&input),
+            };
+            let material = self.material(step.entry_point, output.format())?;
+            let buffer = self.uniform_buffer(camera_entity, index)?;
+
+            let uniforms = PostProcessUniforms::new(step.params, input.width(), input.height());
+            let _ = self
+                .device
+                .write_buffer(&buffer, 0, bytemuck::bytes_of(&uniforms));
+
+            let group = BindingGroup::new()
+                .with_buffer(0, buffer)
+                .with_texture(1, input)
+                .with_sampler(2, Arc::clone(&sampler))
+                .with_texture(3, aux);
+            let instance = MaterialInstance::new(material)
+                .with_binding_group(Arc::new(group))
+                .with_label(format!("post_{}_{camera_entity}", step.entry_point));
+
+            let mut pass = GraphicsPass::new(format!(
+                "{name_prefix}post_{index}_{}_{camera_entity}",
+                step.entry_point.trim_start_matches("fs_")
+            ));
+            pass.set_render_targets(
+                RenderTargetConfig::new().with_color(
+                    ColorAttachment::from_texture(output)
+                        .with_load_op(LoadOp::DontCare)
+                        .with_store_op(StoreOp::Store),
+                ),
+            );
+            pass.add_draw(Arc::clone(&mesh), Arc::new(instance));
+            passes.push(pass);
+        }
+        Some(passes)
+    }
+
+    /// Drop the cached state of cameras for which `keep` returns `false`.
+    pub fn retain_cameras(&mut self, mut keep: impl FnMut(u32) -> bool) {
+        self.cameras.retain(|camera, _| keep(*camera));
+    }
+
+    fn material(
+        &mut self,
+        entry_point: &'static str,
+        format: TextureFormat,
+    ) -> Option<Arc<Material>> {
+        if let Some(material) = self.materials.get(&(entry_point, format)) {
+            return Some(Arc::clone(material));
+        }
+        let material = create_post_process_material(&self.device, entry_point, format)
+            .inspect_err(|err| log::warn!("Failed to create post-process material: {err}"))
+            .ok()?;
+        self.materials
+            .insert((entry_point, format), Arc::clone(&material));
+        Some(material)
+    }
+
+    fn slot_texture(
+        &mut self,
+        camera_entity: u32,
+        slot: Slot,
+        source: &Arc<Texture>,
+        stack: &PostProcessStack,
+    ) -> Option<Arc<Texture>> {
+        let state = self.cameras.get_mut(&camera_entity)?;
+        let (width, height, label) = match slot {
+            Slot::Source => return Some(Arc::clone(source)),
+            Slot::Output => return Some(Arc::clone(&stack.output)),
+            Slot::Ping => (state.size.0, state.size.1, "post_ping"),
+            Slot::Pong => (state.size.0, state.size.1, "post_pong"),
+            Slot::BloomA => (state.size.0 / 2, state.size.1 / 2, "post_bloom_a"),
+            Slot::BloomB => (state.size.0 / 2, state.size.1 / 2, "post_bloom_b"),
+        };
+        if let Some(texture) = state.targets.get(&slot) {
+            return Some(Arc::clone(texture));
+        }
+        let texture = self
+            .device
+            .create_texture(
+                &TextureDescriptor::new_2d(
+                    width.max(1),
+                    height.max(1),
+                    INTERMEDIATE_FORMAT,
+                    TextureUsage::RENDER_ATTACHMENT | TextureUsage::TEXTURE_BINDING,
+                )
+                .with_label(format!("{label}_{camera_entity}")),
+            )
+            .inspect_err(|err| log::warn!("Failed to create post-process target: {err}"))
+            .ok()?;
+        state.targets.insert(slot, Arc::clone(&texture));
+        Some(texture)
+    }
+
+    fn uniform_buffer(&mut self, camera_entity: u32, index: usize) -> Option<Arc<Buffer>> {
+        let state = self.cameras.get_mut(&camera_entity)?;
+        while state.uniforms.len() <= index {
+            let buffer = self
+                .device
+                .create_buffer(
+                    &BufferDescriptor::new(
+                        std::mem::size_of::<PostProcessUniforms>() as u64,
+                        BufferUsage::UNIFORM | BufferUsage::COPY_DST,
+                    )
+                    .with_label(format!(
+                        "post_process_{camera_entity}_{}",
+                        state.uniforms.len()
+                    )),
+                )
+                .inspect_err(|err| log::warn!("Failed to create post-process buffer: {err}"))
+                .ok()?;
+            state.uniforms.push(buffer);
+        }
+        Some(Arc::clone(&state.uniforms[index]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chain(steps: &[PostStep]) -> Vec<(&'static str, Slot, Slot)> {
+        steps
+            .iter()
+            .map(|step| (step.entry_point, step.input, step.output))
+            .collect()
+    }
+
+    #[test]
+    fn empty_stack_copies_to_output() {
+        let steps = plan_steps(&[]);
+        assert_eq!(chain(&steps), vec![("fs_copy", Slot::Source, Slot::Output)]);
+        assert_eq!(steps[0].effect, None);
+    }
+
+    #[test]
+    fn effects_alternate_intermediate_targets() {
+        let steps = plan_steps(&[
+            PostEffect::Exposure(1.0),
+            PostEffect::ToneMapping(ToneMapping::Agx),
+            PostEffect::Vignette {
+                intensity: 0.5,
+                smoothness: 0.4,
+            },
+            PostEffect::Fxaa,
+        ]);
+        assert_eq!(
+            chain(&steps),
+            vec![
+                ("fs_exposure", Slot::Source, Slot::Ping),
+                ("fs_tonemap_agx", Slot::Ping, Slot::Pong),
+                ("fs_vignette", Slot::Pong, Slot::Ping),
+                ("fs_fxaa", Slot::Ping, Slot::Output),
+            ]
+        );
+        assert_eq!(steps[0].params[0], 2.0);
+        assert_eq!(steps[2].params, [0.5, 0.4, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn bloom_blurs_at_half_resolution_and_composites() {
+        let steps = plan_steps(&[
+            PostEffect::Bloom {
+                threshold: 1.0,
+                intensity: 0.3,
+            },
+            PostEffect::ToneMapping(ToneMapping::Aces),
+        ]);
+        assert_eq!(
+            chain(&steps),
+            vec![
+                ("fs_bloom_extract", Slot::Source, Slot::BloomA),
+                ("fs_blur", Slot::BloomA, Slot::BloomB),
+                ("fs_blur", Slot::BloomB, Slot::BloomA),
+                ("fs_bloom_composite", Slot::Source, Slot::Ping),
+                ("fs_tonemap_aces", Slot::Ping, Slot::Output),
+            ]
+        );
+        assert_eq!(steps[3].aux, Some(Slot::BloomA));
+        assert!(steps[..4].iter().all(|step| step.effect == Some(0)));
+        assert_eq!(steps[4].effect, Some(1));
+    }
+}
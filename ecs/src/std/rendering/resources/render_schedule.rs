@@ -1,13 +1,18 @@
 //! Frame schedule resource.
 
-use redlilium_graphics::FrameSchedule;
+use redlilium_graphics::{FrameSchedule, GraphHandle};
 
 /// Resource wrapping a [`FrameSchedule`] for the current frame.
 ///
 /// The application layer inserts this before running ECS systems and
 /// extracts it after, using [`take`](Self::take).
+///
+/// Systems that render inputs for later passes (such as shadow maps) record
+/// their graphs with [`add_dependency`](Self::add_dependency); camera passes
+/// wait for every graph in [`dependencies`](Self::dependencies).
 pub struct RenderSchedule {
     schedule: Option<FrameSchedule>,
+    dependencies: Vec<GraphHandle>,
 }
 
 impl RenderSchedule {
@@ -15,21 +20,27 @@ impl RenderSchedule {
     pub fn new(schedule: FrameSchedule) -> Self {
         Self {
             schedule: Some(schedule),
+            dependencies: Vec::new(),
         }
     }
 
     /// Create an empty render schedule (no active frame).
     pub fn empty() -> Self {
-        Self { schedule: None }
+        Self {
+            schedule: None,
+            dependencies: Vec::new(),
+        }
     }
 
     /// Take the frame schedule out, leaving this resource empty.
     pub fn take(&mut self) -> Option<FrameSchedule> {
+        self.dependencies.clear();
         self.schedule.take()
     }
 
     /// Replace the current schedule with a new one.
     pub fn set(&mut self, schedule: FrameSchedule) {
+        self.dependencies.clear();
         self.schedule = Some(schedule);
     }
 
@@ -47,4 +58,15 @@ impl RenderSchedule {
     pub fn is_active(&self) -> bool {
         self.schedule.is_some()
     }
+
+    /// Record a graph that camera passes submitted later this frame must
+    /// wait for.
+    pub fn add_dependency(&mut self, graph: GraphHandle) {
+        self.dependencies.push(graph);
+    }
+
+    /// Graphs recorded with [`add_dependency`](Self::add_dependency) this frame.
+    pub fn dependencies(&self) -> &[GraphHandle] {
+        &self.dependencies
+    }
 }
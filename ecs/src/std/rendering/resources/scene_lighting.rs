@@ -0,0 +1,735 @@
+//! Scene lighting resource: gathered lights, shadow maps and environment.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use redlilium_core::math::mat4_to_cols_array_2d;
+use redlilium_graphics::{
+    AddressMode, BindingGroup, Buffer, BufferDescriptor, BufferUsage, CompareFunction, FilterMode,
+    FrameSchedule, GraphHandle, GraphicsDevice, GraphicsError, Material, MaterialInstance, Mesh,
+    Sampler, SamplerDescriptor, Texture, TextureDescriptor, TextureFormat, TextureUsage,
+};
+
+use super::shadow_maps::{SHADOW_DEPTH_FORMAT, ShadowView};
+use crate::std::components::{Camera, GlobalTransform};
+use crate::std::rendering::components::Skybox;
+use crate::std::rendering::shaders::{
+    IblPrecompute, SkyboxUniforms, create_fullscreen_triangle, create_skybox_material,
+};
+
+/// Maximum number of shadow map atlases lit materials can sample per frame.
+///
+/// Shadow casting lights beyond this limit are lit without shadows.
+pub const MAX_SHADOW_ATLASES: usize = 4;
+
+/// Resolution of the generated split-sum BRDF lookup table.
+const BRDF_LUT_SIZE: u32 = 32;
+
+/// Importance samples per BRDF lookup table texel.
+const BRDF_LUT_SAMPLES: u32 = 128;
+
+/// One light in the light storage buffer (std430 layout, 96 bytes).
+///
+/// Matches the `Light` struct of the standard `pbr` shader.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct GpuLight {
+    /// World-space position (point and spot lights).
+    pub position: [f32; 3],
+    /// Range beyond which the light has no effect; zero means infinite.
+    pub range: f32,
+    /// World-space direction the light travels (directional and spot lights).
+    pub direction: [f32; 3],
+    /// [`GpuLight::DIRECTIONAL`], [`GpuLight::POINT`] or [`GpuLight::SPOT`].
+    pub kind: u32,
+    /// Linear RGB color.
+    pub color: [f32; 3],
+    /// Intensity (lux for directional lights, candela otherwise).
+    pub intensity: f32,
+    /// Spot cone attenuation scale: `1 / (cos(inner) - cos(outer))`.
+    pub spot_scale: f32,
+    /// Spot cone attenuation offset: `-cos(outer) * spot_scale`.
+    pub spot_offset: f32,
+    /// Shadow atlas slot, or `-1` if the light has no shadow map.
+    pub shadow_atlas: i32,
+    /// Index of the light's first view in the shadow view buffer.
+    pub first_shadow_view: u32,
+    /// Number of shadow views (cascades, cube faces or 1 for spot lights).
+    pub shadow_view_count: u32,
+    /// Constant depth bias.
+    pub depth_bias: f32,
+    /// Normal offset bias in shadow map texels.
+    pub normal_bias: f32,
+    /// PCF kernel radius in texels.
+    pub pcf_radius: f32,
+    /// Far view distance of each directional cascade.
+    pub cascade_splits: [f32; 4],
+}
+
+impl GpuLight {
+    /// Directional light kind.
+    pub const DIRECTIONAL: u32 = 0;
+    /// Point light kind.
+    pub const POINT: u32 = 1;
+    /// Spot light kind.
+    pub const SPOT: u32 = 2;
+}
+
+/// One shadow view in the shadow view storage buffer (std430 layout).
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct GpuShadowView {
+    /// World to light clip space.
+    pub view_projection: [[f32; 4]; 4],
+    /// UV rect of the view in its atlas: `[x, y, width, height]`.
+    pub atlas_rect: [f32; 4],
+    /// `x` = world-space texel size; the rest is padding.
+    pub params: [f32; 4],
+}
+
+impl From<&ShadowView> for GpuShadowView {
+    fn from(view: &ShadowView) -> Self {
+        Self {
+            view_projection: mat4_to_cols_array_2d(&view.view_projection),
+            atlas_rect: view.atlas_rect,
+            params: [view.texel_world_size, 0.0, 0.0, 0.0],
+        }
+    }
+}
+
+/// Per-camera lighting uniforms (binding 0 of the lighting group).
+#[repr(C)]
+#[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct LightingViewUniforms {
+    pub view_projection: [[f32; 4]; 4],
+    pub view: [[f32; 4]; 4],
+    pub camera_position: [f32; 4],
+    /// `rgb` = environment color times intensity, `w` = roughest pre-filtered mip.
+    pub environment: [f32; 4],
+    pub light_count: u32,
+    pub _padding: [u32; 3],
+}
+
+/// Image-based ambient lighting.
+///
+/// Holds a diffuse irradiance cube map, a specular cube map pre-filtered
+/// by roughness across its mip chain, and the split-sum BRDF lookup table.
+/// All three are sampled with a single linear, mipmapped sampler and scaled
+/// by `color * intensity`.
+#[derive(Clone)]
+pub struct EnvironmentLighting {
+    irradiance: Arc<Texture>,
+    prefiltered: Arc<Texture>,
+    brdf_lut: Arc<Texture>,
+    sampler: Arc<Sampler>,
+    color: [f32; 3],
+    intensity: f32,
+}
+
+impl EnvironmentLighting {
+    /// Create environment lighting from precomputed cube maps.
+    ///
+    /// The roughest surfaces sample the last mip of `prefiltered`. The BRDF
+    /// lookup table is integrated on the CPU; use
+    /// [`with_brdf_lut`](Self::with_brdf_lut) to supply a precomputed one.
+    pub fn new(
+        device: &Arc<GraphicsDevice>,
+        irradiance: Arc<Texture>,
+        prefiltered: Arc<Texture>,
+        intensity: f32,
+    ) -> Result<Self, GraphicsError> {
+        Ok(Self {
+            irradiance,
+            prefiltered,
+            brdf_lut: create_brdf_lut(device)?,
+            sampler: create_environment_sampler(device)?,
+            color: [1.0, 1.0, 1.0],
+            intensity,
+        })
+    }
+
+    /// Uniform ambient light of a single color from every direction.
+    pub fn uniform(
+        device: &Arc<GraphicsDevice>,
+        color: [f32; 3],
+        intensity: f32,
+    ) -> Result<Self, GraphicsError> {
+        let white = create_white_cube(device)?;
+        Ok(Self::new(device, Arc::clone(&white), white, intensity)?.with_color(color))
+    }
+
+    /// Replace the BRDF lookup table (`x` = N·V, `y` = roughness).
+    #[must_use]
+    pub fn with_brdf_lut(mut self, brdf_lut: Arc<Texture>) -> Self {
+        self.brdf_lut = brdf_lut;
+        self
+    }
+
+    /// Set the color the environment is tinted with.
+    #[must_use]
+    pub fn with_color(mut self, color: [f32; 3]) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Diffuse irradiance cube map.
+    pub fn irradiance(&self) -> &Arc<Texture> {
+        &self.irradiance
+    }
+
+    /// Roughness pre-filtered specular cube map.
+    pub fn prefiltered(&self) -> &Arc<Texture> {
+        &self.prefiltered
+    }
+
+    /// Split-sum BRDF lookup table.
+    pub fn brdf_lut(&self) -> &Arc<Texture> {
+        &self.brdf_lut
+    }
+
+    /// Sampler used for all environment textures.
+    pub fn sampler(&self) -> &Arc<Sampler> {
+        &self.sampler
+    }
+
+    /// Environment tint color.
+    pub fn color(&self) -> [f32; 3] {
+        self.color
+    }
+
+    /// Environment intensity.
+    pub fn intensity(&self) -> f32 {
+        self.intensity
+    }
+
+    /// Set the environment intensity.
+    pub fn set_intensity(&mut self, intensity: f32) {
+        self.intensity = intensity;
+    }
+
+    /// Mip level of the pre-filtered map used for roughness 1.
+    pub fn max_reflection_lod(&self) -> f32 {
+        self.prefiltered.mip_level_count().saturating_sub(1) as f32
+    }
+}
+
+/// Resource holding everything lit materials need besides their own
+/// properties: the gathered lights, shadow map atlases and environment.
+///
+/// Filled each frame by the [`GatherLights`](crate::GatherLights) system.
+/// The forward render systems call [`view_binding`](Self::view_binding) for
+/// every camera and append the returned binding group to draws whose
+/// material declares the lighting group (see
+/// [`shaders::pbr`](crate::shaders::pbr)).
+///
+/// # Lighting group layout
+///
+/// | Binding | Resource |
+/// |---------|----------|
+/// | 0 | [`LightingViewUniforms`] |
+/// | 1 | [`GpuLight`] storage buffer |
+/// | 2 | [`GpuShadowView`] storage buffer |
+/// | 3-6 | Shadow atlases (depth textures) |
+/// | 7 | Shadow comparison sampler |
+/// | 8 | Irradiance cube map |
+/// | 9 | Pre-filtered cube map |
+/// | 10 | BRDF lookup table |
+/// | 11 | Environment sampler |
+///
+/// The resource also draws [`Skybox`] components for the render systems
+/// (see [`skybox_draw`](Self::skybox_draw)) and runs GPU environment
+/// precomputation (see [`precompute_environment`](Self::precompute_environment)).
+pub struct SceneLighting {
+    device: Arc<GraphicsDevice>,
+    light_buffer: Arc<Buffer>,
+    light_count: u32,
+    shadow_view_buffer: Arc<Buffer>,
+    shadow_atlases: Vec<Arc<Texture>>,
+    empty_shadow_atlas: Arc<Texture>,
+    shadow_sampler: Arc<Sampler>,
+    environment: EnvironmentLighting,
+    pending_environment: Option<(IblPrecompute, f32)>,
+    view_buffers: HashMap<u32, Arc<Buffer>>,
+    skybox_materials: HashMap<(TextureFormat, TextureFormat), Arc<Material>>,
+    skybox_buffers: HashMap<u32, Arc<Buffer>>,
+    fullscreen_triangle: Option<Arc<Mesh>>,
+}
+
+impl SceneLighting {
+    /// Create the resource with no lights and a dim neutral ambient.
+    pub fn new(device: Arc<GraphicsDevice>) -> Self {
+        let light_buffer = create_storage_buffer::<GpuLight>(&device, 1, "scene_lights")
+            .expect("Failed to create light buffer");
+        let shadow_view_buffer =
+            create_storage_buffer::<GpuShadowView>(&device, 1, "scene_shadow_views")
+                .expect("Failed to create shadow view buffer");
+        let empty_shadow_atlas = device
+            .create_texture(
+                &TextureDescriptor::new_2d(
+                    1,
+                    1,
+                    SHADOW_DEPTH_FORMAT,
+                    TextureUsage::RENDER_ATTACHMENT | TextureUsage::TEXTURE_BINDING,
+                )
+                .with_label("empty_shadow_atlas"),
+            )
+            .expect("Failed to create empty shadow atlas");
+        let shadow_sampler = device
+            .create_sampler(
+                &SamplerDescriptor {
+                    mag_filter: FilterMode::Linear,
+                    min_filter: FilterMode::Linear,
+                    ..SamplerDescriptor::new()
+                }
+                .with_address_mode(AddressMode::ClampToEdge)
+                .with_compare(CompareFunction::LessEqual)
+                .with_label("scene_shadow_sampler"),
+            )
+            .expect("Failed to create shadow sampler");
+        let environment = EnvironmentLighting::uniform(&device, [1.0, 1.0, 1.0], 0.1)
+            .expect("Failed to create default environment lighting");
+
+        Self {
+            device,
+            light_buffer,
+            light_count: 0,
+            shadow_view_buffer,
+            shadow_atlases: Vec::new(),
+            empty_shadow_atlas,
+            shadow_sampler,
+            environment,
+            pending_environment: None,
+            view_buffers: HashMap::new(),
+            skybox_materials: HashMap::new(),
+            skybox_buffers: HashMap::new(),
+            fullscreen_triangle: None,
+        }
+    }
+
+    /// Get the graphics device.
+    pub fn device(&self) -> &Arc<GraphicsDevice> {
+        &self.device
+    }
+
+    /// Current image-based ambient lighting.
+    pub fn environment(&self) -> &EnvironmentLighting {
+        &self.environment
+    }
+
+    /// Mutable access to the ambient lighting (e.g. to change its intensity).
+    pub fn environment_mut(&mut self) -> &mut EnvironmentLighting {
+        &mut self.environment
+    }
+
+    /// Replace the image-based ambient lighting.
+    pub fn set_environment(&mut self, environment: EnvironmentLighting) {
+        self.environment = environment;
+    }
+
+    /// Convolve the environment cube map `source` on the GPU and use the
+    /// result as the ambient lighting with the given intensity.
+    ///
+    /// The work is submitted with the next frame's lights by
+    /// [`GatherLights`](crate::GatherLights); the current environment stays
+    /// in use until then.
+    pub fn precompute_environment(
+        &mut self,
+        source: &Arc<Texture>,
+        intensity: f32,
+    ) -> Result<(), GraphicsError> {
+        let precompute = IblPrecompute::new(&self.device, source)?;
+        self.pending_environment = Some((precompute, intensity));
+        Ok(())
+    }
+
+    /// Whether an environment precomputation is waiting to be submitted.
+    pub fn has_pending_environment(&self) -> bool {
+        self.pending_environment.is_some()
+    }
+
+    /// Submit a pending environment precomputation to `schedule` and switch
+    /// to its cube maps.
+    ///
+    /// Returns the graph that camera graphs sampling the new environment
+    /// must wait for.
+    pub(crate) fn submit_pending_environment(
+        &mut self,
+        schedule: &mut FrameSchedule,
+    ) -> Option<GraphHandle> {
+        let (precompute, intensity) = self.pending_environment.take()?;
+        let environment = match EnvironmentLighting::new(
+            &self.device,
+            Arc::clone(precompute.irradiance()),
+            Arc::clone(precompute.prefiltered()),
+            intensity,
+        ) {
+            Ok(environment) => environment.with_brdf_lut(Arc::clone(&self.environment.brdf_lut)),
+            Err(err) => {
+                log::warn!("Failed to create precomputed environment: {err}");
+                return None;
+            }
+        };
+
+        let mut graph = schedule.acquire_graph();
+        precompute.record(&mut graph);
+        let handle = schedule.submit("ibl_precompute", graph, &[]);
+        self.environment = environment;
+        Some(handle)
+    }
+
+    /// Number of lights uploaded this frame.
+    pub fn light_count(&self) -> u32 {
+        self.light_count
+    }
+
+    /// Shadow atlases bound this frame, indexed by [`GpuLight::shadow_atlas`].
+    pub fn shadow_atlases(&self) -> &[Arc<Texture>] {
+        &self.shadow_atlases
+    }
+
+    /// Upload this frame's lights, shadow views and shadow atlases.
+    ///
+    /// Storage buffers grow to the next power of two as needed. At most
+    /// [`MAX_SHADOW_ATLASES`] atlases are kept.
+    pub(crate) fn upload(
+        &mut self,
+        lights: &[GpuLight],
+        shadow_views: &[GpuShadowView],
+        mut shadow_atlases: Vec<Arc<Texture>>,
+    ) {
+        if let Some(buffer) = grow_storage_buffer::<GpuLight>(
+            &self.device,
+            &self.light_buffer,
+            lights.len(),
+            "scene_lights",
+        ) {
+            self.light_buffer = buffer;
+        }
+        if let Some(buffer) = grow_storage_buffer::<GpuShadowView>(
+            &self.device,
+            &self.shadow_view_buffer,
+            shadow_views.len(),
+            "scene_shadow_views",
+        ) {
+            self.shadow_view_buffer = buffer;
+        }
+
+        let mut light_count = lights.len();
+        if !lights.is_empty()
+            && let Err(err) =
+                self.device
+                    .write_buffer(&self.light_buffer, 0, bytemuck::cast_slice(lights))
+        {
+            log::warn!("Failed to upload lights: {err}");
+            light_count = 0;
+        }
+        if !shadow_views.is_empty() {
+            let _ = self.device.write_buffer(
+                &self.shadow_view_buffer,
+                0,
+                bytemuck::cast_slice(shadow_views),
+            );
+        }
+
+        shadow_atlases.truncate(MAX_SHADOW_ATLASES);
+        self.light_count = light_count as u32;
+        self.shadow_atlases = shadow_atlases;
+    }
+
+    /// Build the lighting binding group for the camera with entity index
+    /// `camera_entity`, uploading its view uniforms.
+    ///
+    /// Returns `None` if the camera's uniform buffer could not be created.
+    pub fn view_binding(
+        &mut self,
+        camera_entity: u32,
+        camera: &Camera,
+        global: &GlobalTransform,
+    ) -> Option<Arc<BindingGroup>> {
+        let buffer = match self.view_buffers.get(&camera_entity) {
+            Some(buffer) => Arc::clone(buffer),
+            None => {
+                let buffer = self
+                    .device
+                    .create_buffer(
+                        &BufferDescriptor::new(
+                            std::mem::size_of::<LightingViewUniforms>() as u64,
+                            BufferUsage::UNIFORM | BufferUsage::COPY_DST,
+                        )
+                        .with_label(format!("lighting_view_{camera_entity}")),
+                    )
+                    .inspect_err(|err| log::warn!("Failed to create lighting view buffer: {err}"))
+                    .ok()?;
+                self.view_buffers.insert(camera_entity, Arc::clone(&buffer));
+                buffer
+            }
+        };
+
+        let position = global.translation();
+        let env = &self.environment;
+        let uniforms = LightingViewUniforms {
+            view_projection: mat4_to_cols_array_2d(&camera.view_projection()),
+            view: mat4_to_cols_array_2d(&camera.view_matrix),
+            camera_position: [position.x, position.y, position.z, 1.0],
+            environment: [
+                env.color[0] * env.intensity,
+                env.color[1] * env.intensity,
+                env.color[2] * env.intensity,
+                env.max_reflection_lod(),
+            ],
+            light_count: self.light_count,
+            _padding: [0; 3],
+        };
+        let _ = self
+            .device
+            .write_buffer(&buffer, 0, bytemuck::bytes_of(&uniforms));
+
+        let mut group = BindingGroup::new()
+            .with_buffer(0, buffer)
+            .with_buffer(1, Arc::clone(&self.light_buffer))
+            .with_buffer(2, Arc::clone(&self.shadow_view_buffer));
+        for slot in 0..MAX_SHADOW_ATLASES {
+            let atlas = self
+                .shadow_atlases
+                .get(slot)
+                .unwrap_or(&self.empty_shadow_atlas);
+            group = group.with_texture(3 + slot as u32, Arc::clone(atlas));
+        }
+        let group = group
+            .with_sampler(7, Arc::clone(&self.shadow_sampler))
+            .with_texture(8, Arc::clone(&env.irradiance))
+            .with_texture(9, Arc::clone(&env.prefiltered))
+            .with_texture(10, Arc::clone(&env.brdf_lut))
+            .with_sampler(11, Arc::clone(&env.sampler))
+            .with_label(format!("lighting_{camera_entity}"));
+        Some(Arc::new(group))
+    }
+
+    /// Drop the view uniforms of cameras for which `keep` returns `false`.
+    pub fn retain_views(&mut self, mut keep: impl FnMut(u32) -> bool) {
+        self.view_buffers.retain(|camera, _| keep(*camera));
+        self.skybox_buffers.retain(|camera, _| keep(*camera));
+    }
+
+    /// Build the draw of `skybox` for the camera with entity index
+    /// `camera_entity`, rendering to targets of the given formats.
+    ///
+    /// Returns the fullscreen triangle and the skybox material instance, or
+    /// `None` if a GPU resource could not be created.
+    pub fn skybox_draw(
+        &mut self,
+        camera_entity: u32,
+        camera: &Camera,
+        global: &GlobalTransform,
+        skybox: &Skybox,
+        color_format: TextureFormat,
+        depth_format: TextureFormat,
+    ) -> Option<(Arc<Mesh>, Arc<MaterialInstance>)> {
+        let mesh = match &self.fullscreen_triangle {
+            Some(mesh) => Arc::clone(mesh),
+            None => {
+                let mesh = create_fullscreen_triangle(&self.device)
+                    .inspect_err(|err| log::warn!("Failed to create fullscreen triangle: {err}"))
+                    .ok()?;
+                self.fullscreen_triangle = Some(Arc::clone(&mesh));
+                mesh
+            }
+        };
+        let material = match self.skybox_materials.get(&(color_format, depth_format)) {
+            Some(material) => Arc::clone(material),
+            None => {
+                let material = create_skybox_material(&self.device, color_format, depth_format)
+                    .inspect_err(|err| log::warn!("Failed to create skybox material: {err}"))
+                    .ok()?;
+                self.skybox_materials
+                    .insert((color_format, depth_format), Arc::clone(&material));
+                material
+            }
+        };
+        let buffer = match self.skybox_buffers.get(&camera_entity) {
+            Some(buffer) => Arc::clone(buffer),
+            None => {
+                let buffer = self
+                    .device
+                    .create_buffer(
+                        &BufferDescriptor::new(
+                            std::mem::size_of::<SkyboxUniforms>() as u64,
+                            BufferUsage::UNIFORM | BufferUsage::COPY_DST,
+                        )
+                        .with_label(format!("skybox_{camera_entity}")),
+                    )
+                    .inspect_err(|err| log::warn!("Failed to create skybox buffer: {err}"))
+                    .ok()?;
+                self.skybox_buffers
+                    .insert(camera_entity, Arc::clone(&buffer));
+                buffer
+            }
+        };
+
+        let inverse_view_projection = camera.view_projection().try_inverse()?;
+        let position = global.translation();
+        let uniforms = SkyboxUniforms {
+            inverse_view_projection: mat4_to_cols_array_2d(&inverse_view_projection),
+            camera_position: [position.x, position.y, position.z, 1.0],
+            params: [skybox.intensity, skybox.lod, 0.0, 0.0],
+        };
+        let _ = self
+            .device
+            .write_buffer(&buffer, 0, bytemuck::bytes_of(&uniforms));
+
+        let group = BindingGroup::new()
+            .with_buffer(0, buffer)
+            .with_texture(1, Arc::clone(&skybox.cubemap))
+            .with_sampler(2, Arc::clone(&self.environment.sampler));
+        let instance = MaterialInstance::new(material)
+            .with_binding_group(Arc::new(group))
+            .with_label(format!("skybox_{camera_entity}"));
+        Some((mesh, Arc::new(instance)))
+    }
+}
+
+fn create_storage_buffer<T>(
+    device: &Arc<GraphicsDevice>,
+    capacity: usize,
+    label: &str,
+) -> Result<Arc<Buffer>, GraphicsError> {
+    device.create_buffer(
+        &BufferDescriptor::new(
+            (capacity.max(1) * std::mem::size_of::<T>()) as u64,
+            BufferUsage::STORAGE | BufferUsage::COPY_DST,
+        )
+        .with_label(label),
+    )
+}
+
+/// Reallocate `buffer` if it cannot hold `len` elements of `T`.
+fn grow_storage_buffer<T>(
+    device: &Arc<GraphicsDevice>,
+    buffer: &Buffer,
+    len: usize,
+    label: &str,
+) -> Option<Arc<Buffer>> {
+    let capacity = buffer.size() as usize / std::mem::size_of::<T>();
+    if len <= capacity {
+        return None;
+    }
+    create_storage_buffer::<T>(device, len.next_power_of_two(), label)
+        .inspect_err(|err| log::warn!("Failed to grow {label} buffer: {err}"))
+        .ok()
+}
+
+fn create_environment_sampler(device: &Arc<GraphicsDevice>) -> Result<Arc<Sampler>, GraphicsError> {
+    device.create_sampler(
+        &SamplerDescriptor::linear()
+            .with_address_mode(AddressMode::ClampToEdge)
+            .with_label("environment_sampler"),
+    )
+}
+
+fn create_white_cube(device: &Arc<GraphicsDevice>) -> Result<Arc<Texture>, GraphicsError> {
+    let texture = device.create_texture(
+        &TextureDescriptor::new_cube(
+            1,
+            TextureFormat::Rgba8Unorm,
+            TextureUsage::TEXTURE_BINDING | TextureUsage::COPY_DST,
+        )
+        .with_label("environment_white_cube"),
+    )?;
+    device.write_texture(&texture, &[255u8; 4 * 6])?;
+    Ok(texture)
+}
+
+fn create_brdf_lut(device: &Arc<GraphicsDevice>) -> Result<Arc<Texture>, GraphicsError> {
+    let data: Vec<u8> = integrate_brdf_lut(BRDF_LUT_SIZE, BRDF_LUT_SAMPLES)
+        .into_iter()
+        .flat_map(|[scale, bias]| {
+            [
+                (scale.clamp(0.0, 1.0) * 255.0).round() as u8,
+                (bias.clamp(0.0, 1.0) * 255.0).round() as u8,
+                0,
+                255,
+            ]
+        })
+        .collect();
+    let texture = device.create_texture(
+        &TextureDescriptor::new_2d(
+            BRDF_LUT_SIZE,
+            BRDF_LUT_SIZE,
+            TextureFormat::Rgba8Unorm,
+            TextureUsage::TEXTURE_BINDING | TextureUsage::COPY_DST,
+        )
+        .with_label("brdf_lut"),
+    )?;
+    device.write_texture(&texture, &data)?;
+    Ok(texture)
+}
+
+/// Integrate the split-sum GGX BRDF into a `size`×`size` table of
+/// `[scale, bias]` pairs applied to F0.
+///
+/// Columns are N·V and rows are roughness, both sampled at texel centers.
+pub(crate) fn integrate_brdf_lut(size: u32, samples: u32) -> Vec<[f32; 2]> {
+    let mut table = Vec::with_capacity((size * size) as usize);
+    for y in 0..size {
+        let roughness = (y as f32 + 0.5) / size as f32;
+        let alpha = roughness * roughness;
+        let k = alpha / 2.0;
+        for x in 0..size {
+            let n_dot_v = (x as f32 + 0.5) / size as f32;
+            let view = [(1.0 - n_dot_v * n_dot_v).sqrt(), 0.0, n_dot_v];
+
+            let (mut scale, mut bias) = (0.0, 0.0);
+            for i in 0..samples {
+                // Hammersley point, importance sampled GGX half vector.
+                let u = i as f32 / samples as f32;
+                let v = i.reverse_bits() as f32 / 4_294_967_296.0;
+                let phi = 2.0 * std::f32::consts::PI * u;
+                let cos_theta = ((1.0 - v) / (1.0 + (alpha * alpha - 1.0) * v)).sqrt();
+                let sin_theta = (1.0 - cos_theta * cos_theta).sqrt();
+                let half = [sin_theta * phi.cos(), sin_theta * phi.sin(), cos_theta];
+
+                let v_dot_h = view[0] * half[0] + view[1] * half[1] + view[2] * half[2];
+                let n_dot_l = 2.0 * v_dot_h * half[2] - view[2];
+                if n_dot_l <= 0.0 {
+                    continue;
+                }
+                let v_dot_h = v_dot_h.max(0.0);
+                let g =
+                    (n_dot_v / (n_dot_v * (1.0 - k) + k)) * (n_dot_l / (n_dot_l * (1.0 - k) + k));
+                let g_vis = g * v_dot_h / (half[2] * n_dot_v);
+                let fresnel = (1.0 - v_dot_h).powi(5);
+                scale += (1.0 - fresnel) * g_vis;
+                bias += fresnel * g_vis;
+            }
+            table.push([scale / samples as f32, bias / samples as f32]);
+        }
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gpu_structs_match_shader_layout() {
+        assert_eq!(std::mem::size_of::<GpuLight>(), 96);
+        assert_eq!(std::mem::size_of::<GpuShadowView>(), 96);
+        assert_eq!(std::mem::size_of::<LightingViewUniforms>(), 176);
+        assert_eq!(std::mem::size_of::<SkyboxUniforms>(), 96);
+    }
+
+    #[test]
+    fn brdf_lut_is_energy_bounded() {
+        let size = 8;
+        let table = integrate_brdf_lut(size, 64);
+        assert_eq!(table.len(), (size * size) as usize);
+        for [scale, bias] in &table {
+            assert!(*scale >= 0.0 && *bias >= 0.0);
+            assert!(scale + bias <= 1.01, "{scale} + {bias}");
+        }
+        // Smooth surfaces viewed head-on reflect almost exactly F0.
+        let [scale, bias] = table[(size - 1) as usize];
+        assert!(scale > 0.9 && bias < 0.05, "{scale}, {bias}");
+    }
+}
@@ -0,0 +1,300 @@
+//! Shadow map storage resource.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use redlilium_core::math::Mat4;
+use redlilium_graphics::{
+    AddressMode, BindingGroup, Buffer, CompareFunction, FilterMode, GraphHandle, GraphicsDevice,
+    Material, Sampler, SamplerDescriptor, Texture, TextureDescriptor, TextureFormat, TextureUsage,
+    VertexLayout, Viewport,
+};
+
+use crate::std::components::ShadowSettings;
+use crate::std::rendering::shaders::shadow_depth::{
+    ShadowViewUniforms, create_shadow_depth_material, create_shadow_view_binding,
+};
+
+/// Depth format of every shadow map atlas.
+///
+/// Custom [`Shadow`](crate::RenderPassType::Shadow) pass materials must be
+/// created with this depth format and no color targets.
+pub const SHADOW_DEPTH_FORMAT: TextureFormat = TextureFormat::Depth32Float;
+
+/// Which light type a [`ShadowMap`] belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShadowMapKind {
+    /// Cascaded shadow map, one view per cascade, laid out left to right.
+    Directional,
+    /// Cube shadow map, six faces (+X, -X, +Y, -Y, +Z, -Z) in a 3x2 grid.
+    Point,
+    /// Single perspective frustum.
+    Spot,
+}
+
+impl ShadowMapKind {
+    /// Atlas grid `(columns, rows)` for `view_count` views.
+    fn grid(self, view_count: u32) -> (u32, u32) {
+        match self {
+            Self::Directional => (view_count.max(1), 1),
+            Self::Point => (3, 2),
+            Self::Spot => (1, 1),
+        }
+    }
+}
+
+/// One rendered view of a shadow map (a cascade, cube face or spot frustum).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShadowView {
+    /// World to light clip space.
+    pub view_projection: Mat4,
+    /// UV rect of this view in the atlas: `[x, y, width, height]`.
+    pub atlas_rect: [f32; 4],
+    /// World-space size of one texel. For perspective views (point and spot
+    /// lights) this is the size at distance 1 from the light.
+    pub texel_world_size: f32,
+}
+
+/// GPU shadow map of one light: a depth atlas holding all of its views.
+pub struct ShadowMap {
+    kind: ShadowMapKind,
+    settings: ShadowSettings,
+    texture: Arc<Texture>,
+    tile_size: u32,
+    views: Vec<ShadowView>,
+    cascade_splits: Vec<f32>,
+    view_bindings: Vec<(Arc<Buffer>, Arc<BindingGroup>)>,
+    graph: Option<GraphHandle>,
+}
+
+impl ShadowMap {
+    /// Light type this map was rendered for.
+    pub fn kind(&self) -> ShadowMapKind {
+        self.kind
+    }
+
+    /// Settings the map was rendered with.
+    pub fn settings(&self) -> &ShadowSettings {
+        &self.settings
+    }
+
+    /// Depth atlas texture ([`SHADOW_DEPTH_FORMAT`]).
+    pub fn texture(&self) -> &Arc<Texture> {
+        &self.texture
+    }
+
+    /// Size in texels of each view's square tile.
+    pub fn tile_size(&self) -> u32 {
+        self.tile_size
+    }
+
+    /// Views rendered this frame, in atlas order.
+    pub fn views(&self) -> &[ShadowView] {
+        &self.views
+    }
+
+    /// Far view distance of each directional cascade (empty for point and
+    /// spot lights).
+    pub fn cascade_splits(&self) -> &[f32] {
+        &self.cascade_splits
+    }
+
+    /// Graph that rendered the map this frame, if any.
+    pub fn graph(&self) -> Option<GraphHandle> {
+        self.graph
+    }
+
+    /// Pixel viewport of view `index` in the atlas.
+    pub fn viewport(&self, index: usize) -> Viewport {
+        let (columns, _) = self.kind.grid(self.views.len() as u32);
+        let column = index as u32 % columns;
+        let row = index as u32 / columns;
+        let size = self.tile_size as f32;
+        Viewport::new(column as f32 * size, row as f32 * size, size, size)
+    }
+
+    /// Binding group (shadow view uniforms) for view `index`.
+    pub fn view_binding(&self, index: usize) -> Option<&Arc<BindingGroup>> {
+        self.view_bindings.get(index).map(|(_, group)| group)
+    }
+
+    /// Record the graph that rendered this map.
+    pub(crate) fn set_graph(&mut self, graph: GraphHandle) {
+        self.graph = Some(graph);
+    }
+}
+
+/// Resource holding the shadow maps of all shadow casting lights.
+///
+/// Filled each frame by the [`ShadowRenderSystem`](crate::ShadowRenderSystem)
+/// and keyed by light entity index. Lit materials read a light's atlas
+/// through [`get`](Self::get) and sample it with [`sampler`](Self::sampler)
+/// using the `shadows` shader library module. Atlas textures and per-view
+/// uniform buffers are reused across frames while the light's settings stay
+/// the same.
+pub struct ShadowMaps {
+    device: Arc<GraphicsDevice>,
+    sampler: Arc<Sampler>,
+    materials: HashMap<(VertexLayout, bool), Option<Arc<Material>>>,
+    maps: HashMap<u32, ShadowMap>,
+}
+
+impl ShadowMaps {
+    /// Create an empty shadow map resource for the given device.
+    pub fn new(device: Arc<GraphicsDevice>) -> Self {
+        let sampler = device
+            .create_sampler(
+                &SamplerDescriptor {
+                    mag_filter: FilterMode::Linear,
+                    min_filter: FilterMode::Linear,
+                    ..SamplerDescriptor::new()
+                }
+                .with_address_mode(AddressMode::ClampToEdge)
+                .with_compare(CompareFunction::LessEqual)
+                .with_label("shadow_compare_sampler"),
+            )
+            .expect("Failed to create shadow sampler");
+        Self {
+            device,
+            sampler,
+            materials: HashMap::new(),
+            maps: HashMap::new(),
+        }
+    }
+
+    /// Get the graphics device.
+    pub fn device(&self) -> &Arc<GraphicsDevice> {
+        &self.device
+    }
+
+    /// Linear-filtered comparison sampler for PCF lookups.
+    pub fn sampler(&self) -> &Arc<Sampler> {
+        &self.sampler
+    }
+
+    /// Shadow map of the light with entity index `light`.
+    pub fn get(&self, light: u32) -> Option<&ShadowMap> {
+        self.maps.get(&light)
+    }
+
+    /// Iterate over `(light entity index, shadow map)` pairs.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &ShadowMap)> {
+        self.maps.iter().map(|(light, map)| (*light, map))
+    }
+
+    /// Number of lights with a shadow map.
+    pub fn len(&self) -> usize {
+        self.maps.len()
+    }
+
+    /// Returns `true` if no light has a shadow map.
+    pub fn is_empty(&self) -> bool {
+        self.maps.is_empty()
+    }
+
+    /// Get (or lazily create) the standard depth material for casters with
+    /// `layout`. Returns `None` if the material failed to compile; the
+    /// failure is logged once and cached.
+    pub fn caster_material(
+        &mut self,
+        layout: &Arc<VertexLayout>,
+        skinned: bool,
+    ) -> Option<Arc<Material>> {
+        let device = &self.device;
+        self.materials
+            .entry(((**layout).clone(), skinned))
+            .or_insert_with(|| {
+                create_shadow_depth_material(
+                    device,
+                    Arc::clone(layout),
+                    skinned,
+                    SHADOW_DEPTH_FORMAT,
+                )
+                .inspect_err(|err| log::warn!("Failed to create shadow depth material: {err}"))
+                .ok()
+            })
+            .clone()
+    }
+
+    /// Drop the shadow maps of lights for which `keep` returns `false`.
+    pub fn retain(&mut self, mut keep: impl FnMut(u32) -> bool) {
+        self.maps.retain(|light, _| keep(*light));
+    }
+
+    /// Update the shadow map of `light` for this frame.
+    ///
+    /// (Re)allocates the atlas when the view count or resolution changed,
+    /// fills in each view's atlas rect and uploads its view-projection
+    /// matrix. Returns `None` if GPU resources could not be created.
+    pub(crate) fn prepare(
+        &mut self,
+        light: u32,
+        kind: ShadowMapKind,
+        settings: ShadowSettings,
+        mut views: Vec<ShadowView>,
+        cascade_splits: Vec<f32>,
+    ) -> Option<&mut ShadowMap> {
+        let tile_size = settings.resolution.max(1);
+        let (columns, rows) = kind.grid(views.len() as u32);
+
+        let reuse = self.maps.get(&light).is_some_and(|map| {
+            map.kind == kind && map.tile_size == tile_size && map.view_bindings.len() == views.len()
+        });
+        if !reuse {
+            let texture = self
+                .device
+                .create_texture(
+                    &TextureDescriptor::new_2d(
+                        columns * tile_size,
+                        rows * tile_size,
+                        SHADOW_DEPTH_FORMAT,
+                        TextureUsage::RENDER_ATTACHMENT | TextureUsage::TEXTURE_BINDING,
+                    )
+                    .with_label(format!("shadow_map_{light}")),
+                )
+                .inspect_err(|err| log::warn!("Failed to create shadow map texture: {err}"))
+                .ok()?;
+            let view_bindings = (0..views.len())
+                .map(|_| create_shadow_view_binding(&self.device))
+                .collect::<Result<Vec<_>, _>>()
+                .inspect_err(|err| log::warn!("Failed to create shadow view uniforms: {err}"))
+                .ok()?;
+            self.maps.insert(
+                light,
+                ShadowMap {
+                    kind,
+                    settings,
+                    texture,
+                    tile_size,
+                    views: Vec::new(),
+                    cascade_splits: Vec::new(),
+                    view_bindings,
+                    graph: None,
+                },
+            );
+        }
+
+        let map = self.maps.get_mut(&light)?;
+        for (index, view) in views.iter_mut().enumerate() {
+            let column = index as u32 % columns;
+            let row = index as u32 / columns;
+            view.atlas_rect = [
+                column as f32 / columns as f32,
+                row as f32 / rows as f32,
+                1.0 / columns as f32,
+                1.0 / rows as f32,
+            ];
+            let uniforms = ShadowViewUniforms::new(&view.view_projection);
+            let _ = self.device.write_buffer(
+                &map.view_bindings[index].0,
+                0,
+                bytemuck::bytes_of(&uniforms),
+            );
+        }
+        map.settings = settings;
+        map.views = views;
+        map.cascade_splits = cascade_splits;
+        map.graph = None;
+        Some(map)
+    }
+}
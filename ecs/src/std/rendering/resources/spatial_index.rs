@@ -0,0 +1,551 @@
+//! Spatial index resource: a bounding volume hierarchy over rendered entities.
+
+use std::collections::{BTreeSet, HashMap};
+
+use redlilium_core::math::{Aabb, Frustum, Ray};
+
+/// Maximum number of entities stored in a BVH leaf.
+const MAX_LEAF_SIZE: usize = 4;
+
+/// Refitting loosens the tree as entities move; once the summed surface
+/// area of its nodes has grown by this factor since the last build,
+/// rebuild instead.
+const REBUILD_GROWTH: f32 = 2.0;
+
+/// Frustum culling statistics of one camera for one frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CullingStats {
+    /// Entities in the index, bounded or not.
+    pub total: u32,
+    /// Entities that passed the frustum test, including unbounded ones.
+    pub visible: u32,
+    /// Entities rejected by the frustum test.
+    pub culled: u32,
+    /// Entities without bounds, which are never culled.
+    pub unbounded: u32,
+    /// BVH nodes tested against the frustum.
+    pub nodes_tested: u32,
+}
+
+impl CullingStats {
+    /// Sum of two stats, e.g. to total all cameras of a frame.
+    pub fn merge(&self, other: &CullingStats) -> CullingStats {
+        CullingStats {
+            total: self.total + other.total,
+            visible: self.visible + other.visible,
+            culled: self.culled + other.culled,
+            unbounded: self.unbounded + other.unbounded,
+            nodes_tested: self.nodes_tested + other.nodes_tested,
+        }
+    }
+}
+
+/// An entity whose world bounds are hit by a ray.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayHit {
+    /// Entity index.
+    pub entity: u32,
+    /// Distance along the ray to where it enters the entity's bounds.
+    pub distance: f32,
+}
+
+/// BVH node. Its entities are `items[start..start + count]`, for inner
+/// nodes as well as leaves.
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    aabb: Aabb,
+    start: u32,
+    count: u32,
+    /// Index of the right child (the left child is the next node), or zero
+    /// for leaves.
+    right: u32,
+}
+
+impl Node {
+    fn is_leaf(&self) -> bool {
+        self.right == 0
+    }
+}
+
+/// Pending work for [`SpatialIndex::update`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pending {
+    None,
+    Refit,
+    Rebuild,
+}
+
+/// World-space bounds of rendered entities, organized in a bounding volume
+/// hierarchy for visibility and picking queries.
+///
+/// Kept up to date by the [`UpdateSpatialIndex`](crate::UpdateSpatialIndex)
+/// system from [`RenderMesh`](crate::RenderMesh) bounds and
+/// [`GlobalTransform`](crate::GlobalTransform) changes. Moving entities
+/// only refit the tree; it is rebuilt when entities are added or removed,
+/// or once refitting has degraded it too much.
+///
+/// The render systems frustum-cull every camera through
+/// [`cull_camera`](Self::cull_camera), which records per-camera
+/// [`CullingStats`]. Ray and box queries work on bounding boxes, so editor
+/// picking can find candidates without a GPU readback.
+///
+/// Entities without bounds are tracked separately: they are always
+/// visible and never returned by ray or box queries.
+pub struct SpatialIndex {
+    bounds: HashMap<u32, Aabb>,
+    unbounded: BTreeSet<u32>,
+    nodes: Vec<Node>,
+    items: Vec<(u32, Aabb)>,
+    pending: Pending,
+    built_area: f32,
+    stats: HashMap<u32, CullingStats>,
+}
+
+impl Default for SpatialIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SpatialIndex {
+    /// Create an empty index.
+    pub fn new() -> Self {
+        Self {
+            bounds: HashMap::new(),
+            unbounded: BTreeSet::new(),
+            nodes: Vec::new(),
+            items: Vec::new(),
+            pending: Pending::None,
+            built_area: 0.0,
+            stats: HashMap::new(),
+        }
+    }
+
+    /// Number of entities in the index, bounded or not.
+    pub fn len(&self) -> usize {
+        self.bounds.len() + self.unbounded.len()
+    }
+
+    /// Whether the index has no entities.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether `entity` is in the index.
+    pub fn contains(&self, entity: u32) -> bool {
+        self.bounds.contains_key(&entity) || self.unbounded.contains(&entity)
+    }
+
+    /// World bounds of `entity`, or `None` if it is unbounded or not indexed.
+    pub fn bounds(&self, entity: u32) -> Option<Aabb> {
+        self.bounds.get(&entity).copied()
+    }
+
+    /// Insert `entity` with world bounds `aabb`, or update its bounds.
+    ///
+    /// Takes effect on the next [`update`](Self::update).
+    pub fn insert(&mut self, entity: u32, aabb: Aabb) {
+        if self.unbounded.remove(&entity) {
+            self.pending = Pending::Rebuild;
+        }
+        match self.bounds.insert(entity, aabb) {
+            None => self.pending = Pending::Rebuild,
+            Some(old) if old != aabb && self.pending == Pending::None => {
+                self.pending = Pending::Refit;
+            }
+            Some(_) => {}
+        }
+    }
+
+    /// Insert `entity` without bounds, so it is never culled.
+    pub fn insert_unbounded(&mut self, entity: u32) {
+        if self.bounds.remove(&entity).is_some() {
+            self.pending = Pending::Rebuild;
+        }
+        self.unbounded.insert(entity);
+    }
+
+    /// Remove `entity`. Returns `true` if it was indexed.
+    pub fn remove(&mut self, entity: u32) -> bool {
+        if self.bounds.remove(&entity).is_some() {
+            self.pending = Pending::Rebuild;
+            true
+        } else {
+            self.unbounded.remove(&entity)
+        }
+    }
+
+    /// Keep only the entities for which `keep` returns `true`.
+    pub fn retain(&mut self, mut keep: impl FnMut(u32) -> bool) {
+        let before = self.bounds.len();
+        self.bounds.retain(|&entity, _| keep(entity));
+        if self.bounds.len() != before {
+            self.pending = Pending::Rebuild;
+        }
+        self.unbounded.retain(|&entity| keep(entity));
+    }
+
+    /// Apply pending insertions, removals and bound changes to the tree.
+    ///
+    /// Queries see the index as of the last update.
+    pub fn update(&mut self) {
+        if self.pending == Pending::Refit {
+            self.refit();
+            if self.tree_area() > self.built_area * REBUILD_GROWTH {
+                self.pending = Pending::Rebuild;
+            }
+        }
+        if self.pending == Pending::Rebuild {
+            self.rebuild();
+        }
+        self.pending = Pending::None;
+    }
+
+    /// Append every entity that may be visible in `frustum` to `visible`:
+    /// bounded entities intersecting it, then all unbounded entities.
+    pub fn cull(&self, frustum: &Frustum, visible: &mut Vec<u32>) -> CullingStats {
+        let first = visible.len();
+        let mut stats = CullingStats {
+            total: self.len() as u32,
+            unbounded: self.unbounded.len() as u32,
+            ..Default::default()
+        };
+
+        if !self.nodes.is_empty() {
+            let mut stack = vec![0usize];
+            while let Some(index) = stack.pop() {
+                let node = &self.nodes[index];
+                stats.nodes_tested += 1;
+                if !frustum.intersects_aabb(&node.aabb) {
+                    continue;
+                }
+                let items = &self.items[node.start as usize..(node.start + node.count) as usize];
+                if node.is_leaf() {
+                    visible.extend(
+                        items
+                            .iter()
+                            .filter(|(_, aabb)| frustum.intersects_aabb(aabb))
+                            .map(|&(entity, _)| entity),
+                    );
+                } else if frustum.contains_aabb(&node.aabb) {
+                    // Fully inside: no need to test the subtree.
+                    visible.extend(items.iter().map(|&(entity, _)| entity));
+                } else {
+                    stack.push(node.right as usize);
+                    stack.push(index + 1);
+                }
+            }
+        }
+        visible.extend(self.unbounded.iter().copied());
+
+        stats.visible = (visible.len() - first) as u32;
+        stats.culled = stats.total - stats.visible;
+        stats
+    }
+
+    /// Cull for the camera with entity index `camera`, recording its
+    /// [`CullingStats`] for this frame.
+    pub fn cull_camera(&mut self, camera: u32, frustum: &Frustum) -> Vec<u32> {
+        let mut visible = Vec::new();
+        let stats = self.cull(frustum, &mut visible);
+        self.stats.insert(camera, stats);
+        visible
+    }
+
+    /// Culling stats recorded for `camera` this frame.
+    pub fn camera_stats(&self, camera: u32) -> Option<CullingStats> {
+        self.stats.get(&camera).copied()
+    }
+
+    /// Culling stats of all cameras this frame, summed.
+    pub fn frame_stats(&self) -> CullingStats {
+        self.stats
+            .values()
+            .fold(CullingStats::default(), |sum, stats| sum.merge(stats))
+    }
+
+    /// Forget the recorded culling stats; called at the start of each frame.
+    pub fn clear_stats(&mut self) {
+        self.stats.clear();
+    }
+
+    /// Bounded entities whose world bounds intersect `aabb`.
+    pub fn query_aabb(&self, aabb: &Aabb) -> Vec<u32> {
+        let mut found = Vec::new();
+        self.traverse(
+            |node| node.intersects(aabb),
+            |entity, bounds| {
+                if bounds.intersects(aabb) {
+                    found.push(entity);
+                }
+            },
+        );
+        found
+    }
+
+    /// All bounded entities whose world bounds the ray enters within
+    /// `max_distance`, nearest first.
+    pub fn ray_query(&self, ray: &Ray, max_distance: f32) -> Vec<RayHit> {
+        let mut hits = Vec::new();
+        self.traverse(
+            |node| ray.intersect_aabb(node).is_some_and(|t| t <= max_distance),
+            |entity, bounds| {
+                if let Some(distance) = ray.intersect_aabb(bounds).filter(|&t| t <= max_distance) {
+                    hits.push(RayHit { entity, distance });
+                }
+            },
+        );
+        hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        hits
+    }
+
+    /// The bounded entity whose world bounds the ray enters first, within
+    /// `max_distance`.
+    ///
+    /// This is a bounding box test; callers needing exact picking can test
+    /// the mesh of the hit (or of each [`ray_query`](Self::ray_query) hit).
+    pub fn raycast(&self, ray: &Ray, max_distance: f32) -> Option<RayHit> {
+        if self.nodes.is_empty() {
+            return None;
+        }
+        let mut best: Option<RayHit> = None;
+        let mut stack = vec![0usize];
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            let limit = best.map_or(max_distance, |hit| hit.distance);
+            if !ray.intersect_aabb(&node.aabb).is_some_and(|t| t <= limit) {
+                continue;
+            }
+            if node.is_leaf() {
+                let items = &self.items[node.start as usize..(node.start + node.count) as usize];
+                for &(entity, aabb) in items {
+                    let limit = best.map_or(max_distance, |hit| hit.distance);
+                    if let Some(distance) = ray.intersect_aabb(&aabb).filter(|&t| t <= limit) {
+                        best = Some(RayHit { entity, distance });
+                    }
+                }
+            } else {
+                stack.push(node.right as usize);
+                stack.push(index + 1);
+            }
+        }
+        best
+    }
+
+    /// Visit the bounded entities of every leaf whose bounds pass `enter`.
+    fn traverse(&self, enter: impl Fn(&Aabb) -> bool, mut visit: impl FnMut(u32, &Aabb)) {
+        if self.nodes.is_empty() {
+            return;
+        }
+        let mut stack = vec![0usize];
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !enter(&node.aabb) {
+                continue;
+            }
+            if node.is_leaf() {
+                let items = &self.items[node.start as usize..(node.start + node.count) as usize];
+                for (entity, aabb) in items {
+                    visit(*entity, aabb);
+                }
+            } else {
+                stack.push(node.right as usize);
+                stack.push(index + 1);
+            }
+        }
+    }
+
+    /// Summed surface area of all nodes, a measure of traversal cost.
+    fn tree_area(&self) -> f32 {
+        self.nodes.iter().map(|node| node.aabb.surface_area()).sum()
+    }
+
+    /// Rebuild the tree with median splits along the widest axis.
+    fn rebuild(&mut self) {
+        self.items.clear();
+        self.items
+            .extend(self.bounds.iter().map(|(&entity, &aabb)| (entity, aabb)));
+        // Sorted input keeps the tree (and draw order) deterministic.
+        self.items.sort_unstable_by_key(|&(entity, _)| entity);
+        self.nodes.clear();
+        if !self.items.is_empty() {
+            build_node(&mut self.nodes, &mut self.items, 0);
+        }
+        self.built_area = self.tree_area();
+    }
+
+    /// Recompute node bounds from the entities' current bounds.
+    fn refit(&mut self) {
+        for (entity, aabb) in &mut self.items {
+            if let Some(bounds) = self.bounds.get(entity) {
+                *aabb = *bounds;
+            }
+        }
+        // Children always follow their parent, so a reverse pass is bottom-up.
+        for index in (0..self.nodes.len()).rev() {
+            let node = self.nodes[index];
+            self.nodes[index].aabb = if node.is_leaf() {
+                union_all(&self.items[node.start as usize..(node.start + node.count) as usize])
+            } else {
+                self.nodes[index + 1]
+                    .aabb
+                    .union(&self.nodes[node.right as usize].aabb)
+            };
+        }
+    }
+}
+
+/// Build the subtree over `items` (which start at `offset` in the index's
+/// item list), returning its node index.
+fn build_node(nodes: &mut Vec<Node>, items: &mut [(u32, Aabb)], offset: usize) -> usize {
+    let index = nodes.len();
+    nodes.push(Node {
+        aabb: union_all(items),
+        start: offset as u32,
+        count: items.len() as u32,
+        right: 0,
+    });
+    if items.len() <= MAX_LEAF_SIZE {
+        return index;
+    }
+
+    let centers = items
+        .iter()
+        .map(|(_, aabb)| aabb.center())
+        .fold(None::<Aabb>, |bounds, c| {
+            let point = Aabb::new([c.x, c.y, c.z], [c.x, c.y, c.z]);
+            Some(bounds.map_or(point, |b| b.union(&point)))
+        })
+        .expect("items are not empty");
+    let extent = [0, 1, 2].map(|axis| centers.max[axis] - centers.min[axis]);
+    let axis = (0..3)
+        .max_by(|&a, &b| extent[a].total_cmp(&extent[b]))
+        .unwrap_or(0);
+
+    let mid = items.len() / 2;
+    items.select_nth_unstable_by(mid, |(_, a), (_, b)| {
+        a.center()[axis].total_cmp(&b.center()[axis])
+    });
+    let (left, right) = items.split_at_mut(mid);
+    build_node(nodes, left, offset);
+    let right_index = build_node(nodes, right, offset + mid);
+    nodes[index].right = right_index as u32;
+    index
+}
+
+fn union_all(items: &[(u32, Aabb)]) -> Aabb {
+    items
+        .iter()
+        .skip(1)
+        .fold(items[0].1, |bounds, (_, aabb)| bounds.union(aabb))
+}
+
+#[cfg(test)]
+mod tests {
+    use redlilium_core::math::{Mat4, Vec3, look_at_rh, perspective_rh};
+
+    use super::*;
+
+    fn unit_box_at(x: f32, z: f32) -> Aabb {
+        Aabb::new([x - 0.5, -0.5, z - 0.5], [x + 0.5, 0.5, z + 0.5])
+    }
+
+    /// A 10x10 grid of unit boxes spaced 4 apart, entity = row * 10 + column.
+    fn grid_index() -> SpatialIndex {
+        let mut index = SpatialIndex::new();
+        for row in 0..10 {
+            for column in 0..10 {
+                let entity = row * 10 + column;
+                index.insert(entity, unit_box_at(column as f32 * 4.0, row as f32 * -4.0));
+            }
+        }
+        index.update();
+        index
+    }
+
+    /// Camera at the origin looking down -Z with a narrow field of view.
+    fn narrow_frustum() -> Frustum {
+        let proj = perspective_rh(0.1, 1.0, 0.1, 100.0);
+        let view: Mat4 = look_at_rh(
+            &Vec3::new(0.0, 0.0, 2.0),
+            &Vec3::new(0.0, 0.0, -1.0),
+            &Vec3::new(0.0, 1.0, 0.0),
+        );
+        Frustum::from_view_projection(&(proj * view))
+    }
+
+    #[test]
+    fn cull_keeps_only_boxes_in_view() {
+        let mut index = grid_index();
+        index.insert_unbounded(1000);
+        index.update();
+
+        let mut visible = Vec::new();
+        let stats = index.cull(&narrow_frustum(), &mut visible);
+        visible.sort_unstable();
+
+        // Only the first column lies along the camera's view direction.
+        let mut expected: Vec<u32> = (0..10).map(|row| row * 10).collect();
+        expected.push(1000);
+        assert_eq!(visible, expected);
+        assert_eq!(stats.total, 101);
+        assert_eq!(stats.visible, 11);
+        assert_eq!(stats.culled, 90);
+        assert_eq!(stats.unbounded, 1);
+        assert!(stats.nodes_tested < 50);
+    }
+
+    #[test]
+    fn moved_entities_are_refit() {
+        let mut index = grid_index();
+        // Move a box from the far corner into view.
+        index.insert(99, unit_box_at(0.0, -2.0));
+        index.update();
+
+        let visible = index.cull_camera(7, &narrow_frustum());
+        assert!(visible.contains(&99));
+        assert_eq!(index.camera_stats(7).map(|s| s.visible), Some(11));
+        assert_eq!(index.frame_stats().visible, 11);
+        index.clear_stats();
+        assert_eq!(index.camera_stats(7), None);
+    }
+
+    #[test]
+    fn removed_entities_are_not_returned() {
+        let mut index = grid_index();
+        assert!(index.remove(0));
+        index.retain(|entity| entity != 10);
+        index.update();
+        assert_eq!(index.len(), 98);
+
+        let mut visible = Vec::new();
+        index.cull(&narrow_frustum(), &mut visible);
+        assert!(!visible.contains(&0));
+        assert!(!visible.contains(&10));
+        assert!(visible.contains(&20));
+    }
+
+    #[test]
+    fn raycast_finds_nearest() {
+        let index = grid_index();
+        let ray = Ray::new(Vec3::new(8.0, 0.0, 10.0), Vec3::new(0.0, 0.0, -1.0));
+
+        let hit = index.raycast(&ray, f32::INFINITY).unwrap();
+        assert_eq!(hit.entity, 2);
+        assert!((hit.distance - 9.5).abs() < 1e-5);
+
+        let hits = index.ray_query(&ray, 20.0);
+        let entities: Vec<u32> = hits.iter().map(|hit| hit.entity).collect();
+        assert_eq!(entities, vec![2, 12, 22]);
+
+        assert!(index.raycast(&ray, 5.0).is_none());
+    }
+
+    #[test]
+    fn query_aabb_returns_overlapping() {
+        let index = grid_index();
+        let mut found = index.query_aabb(&Aabb::new([-1.0, -1.0, -5.0], [5.0, 1.0, 1.0]));
+        found.sort_unstable();
+        assert_eq!(found, vec![0, 1, 10, 11]);
+    }
+}
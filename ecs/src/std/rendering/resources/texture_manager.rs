@@ -5,6 +5,7 @@ use std::fmt;
 use std::path::Path;
 use std::sync::Arc;
 
+use redlilium_core::texture::TextureContainer;
 use redlilium_graphics::{
     CpuSampler, CpuTexture, GraphicsDevice, GraphicsError, Sampler, Texture, TextureFormat,
 };
@@ -148,6 +149,10 @@ impl TextureManager {
     // --- File loading ---
 
     /// Load a texture from a file path.
+    ///
+    /// Reads synchronously from the local filesystem. Use
+    /// [`AssetServer`](super::AssetServer) to load through the VFS without
+    /// blocking.
     pub fn load_texture(
         &mut self,
         path: impl AsRef<Path>,
@@ -160,15 +165,33 @@ impl TextureManager {
         }
 
         let bytes = std::fs::read(path)?;
-        let img = image::load_from_memory(&bytes)?;
+        let cpu_texture = Self::decode_image(&bytes, path_str)?;
+        let texture = self.create_texture(&cpu_texture)?;
+        Ok(texture)
+    }
+
+    /// Decode an encoded image into a [`CpuTexture`].
+    ///
+    /// KTX2 and DDS files keep their format, mip levels and array layers;
+    /// other images (PNG, JPEG) are decoded to RGBA8.
+    pub fn decode_image(
+        bytes: &[u8],
+        name: impl Into<String>,
+    ) -> Result<CpuTexture, TextureManagerError> {
+        if TextureContainer::detect(bytes).is_some() {
+            return CpuTexture::from_container(bytes)
+                .map(|texture| texture.with_name(name))
+                .map_err(|err| TextureManagerError::ImageDecode(err.to_string()));
+        }
+
+        let img = image::load_from_memory(bytes)?;
         let rgba = img.to_rgba8();
         let (width, height) = (img.width(), img.height());
 
-        let cpu_texture =
+        Ok(
             CpuTexture::new(width, height, TextureFormat::Rgba8Unorm, rgba.into_raw())
-                .with_name(path_str);
-        let texture = self.create_texture(&cpu_texture)?;
-        Ok(texture)
+                .with_name(name),
+        )
     }
 
     // --- Iteration ---
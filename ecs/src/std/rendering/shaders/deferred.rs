@@ -0,0 +1,93 @@
+//! Deferred lighting resolve material.
+//!
+//! Shades the [`GBuffer`] written by the PBR `fs_gbuffer` entry point (see
+//! [`create_pbr_gbuffer_material`](super::create_pbr_gbuffer_material))
+//! with the same lights, shadows and ambient lighting as the forward path,
+//! drawing a fullscreen triangle into the camera's color target.
+//!
+//! # Binding groups
+//!
+//! - Group 0: G-buffer attachments (bindings 0-3, see [`gbuffer_binding`])
+//! - Group 1: scene lighting (see
+//!   [`lighting_binding_layout`](super::lighting_binding_layout))
+
+use std::sync::Arc;
+
+use redlilium_graphics::{
+    BindingGroup, BindingLayout, GraphicsDevice, GraphicsError, Material, MaterialDescriptor,
+    ShaderSource, ShaderStage, TextureFormat,
+};
+
+use super::pbr::lighting_binding_layout;
+use crate::std::rendering::components::GBuffer;
+
+/// Slang shader for the deferred lighting resolve.
+pub(super) const SHADER_SLANG: &str =
+    include_str!("../../../../../shaders/standard/deferred_resolve.slang");
+
+/// Binding layout of the G-buffer group (group 0).
+pub fn gbuffer_binding_layout() -> BindingLayout {
+    BindingLayout::new()
+        .with_texture(0)
+        .with_texture(1)
+        .with_texture(2)
+        .with_texture(3)
+        .with_label("gbuffer")
+}
+
+/// Bind the attachments of `gbuffer` in the order of [`gbuffer_binding_layout`].
+pub fn gbuffer_binding(gbuffer: &GBuffer) -> BindingGroup {
+    gbuffer
+        .attachments()
+        .into_iter()
+        .enumerate()
+        .fold(BindingGroup::new(), |group, (binding, texture)| {
+            group.with_texture(binding as u32, Arc::clone(texture))
+        })
+        .with_label("gbuffer")
+}
+
+/// Create the deferred resolve [`Material`] writing to `color_format`.
+///
+/// The resolve pass has no depth attachment.
+pub fn create_deferred_resolve_material(
+    device: &Arc<GraphicsDevice>,
+    color_format: TextureFormat,
+) -> Result<Arc<Material>, GraphicsError> {
+    device.create_material(
+        &MaterialDescriptor::new()
+            .with_shader(ShaderSource::slang(
+                ShaderStage::Vertex,
+                SHADER_SLANG.as_bytes().to_vec(),
+                "vs_main",
+                vec![],
+            ))
+            .with_shader(ShaderSource::slang(
+                ShaderStage::Fragment,
+                SHADER_SLANG.as_bytes().to_vec(),
+                "fs_main",
+                vec![],
+            ))
+            .with_binding_layout(Arc::new(gbuffer_binding_layout()))
+            .with_binding_layout(Arc::new(lighting_binding_layout()))
+            .with_color_format(color_format)
+            .with_label("std_deferred_resolve"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use redlilium_graphics::BindingType;
+
+    use super::*;
+
+    #[test]
+    fn gbuffer_layout_has_one_texture_per_attachment() {
+        let layout = gbuffer_binding_layout();
+        assert_eq!(layout.entries.len(), GBuffer::FORMATS.len());
+        for (index, entry) in layout.entries.iter().enumerate() {
+            assert_eq!(entry.binding, index as u32);
+            assert_eq!(entry.binding_type, BindingType::Texture);
+        }
+    }
+}
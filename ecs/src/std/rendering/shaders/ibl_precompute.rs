@@ -0,0 +1,362 @@
+//! GPU image-based lighting precomputation.
+//!
+//! Convolves an environment cube map into the diffuse irradiance map and
+//! the roughness pre-filtered specular map used by
+//! [`EnvironmentLighting`](crate::EnvironmentLighting), with compute
+//! shaders instead of the CPU. Every output mip is written into a shared
+//! storage buffer and copied into its cube texture by a transfer pass in
+//! the same render graph.
+//!
+//! Usually driven through
+//! [`SceneLighting::precompute_environment`](crate::SceneLighting::precompute_environment).
+//!
+//! # Binding groups
+//!
+//! - Group 0: [`IblPrecomputeUniforms`] (binding 0), source cube map
+//!   (binding 1), sampler (binding 2) and output texels (binding 3)
+
+use std::sync::Arc;
+
+use redlilium_graphics::{
+    AddressMode, BindingGroup, BindingLayout, BindingLayoutEntry, BindingType, Buffer,
+    BufferDescriptor, BufferTextureCopyRegion, BufferTextureLayout, BufferUsage, ComputePass,
+    Extent3d, GraphicsDevice, GraphicsError, Material, MaterialDescriptor, MaterialInstance,
+    RenderGraph, SamplerDescriptor, ShaderSource, ShaderStage, ShaderStageFlags, Texture,
+    TextureCopyLocation, TextureDescriptor, TextureFormat, TextureOrigin, TextureUsage,
+    TransferConfig, TransferOperation, TransferPass,
+};
+
+/// Slang shader for IBL precomputation.
+pub(super) const SHADER_SLANG: &str =
+    include_str!("../../../../../shaders/standard/ibl_precompute.slang");
+
+/// Face size of the generated irradiance cube map.
+pub const IRRADIANCE_SIZE: u32 = 32;
+
+/// Face size of the generated pre-filtered cube map's first mip.
+pub const PREFILTER_SIZE: u32 = 128;
+
+/// Format of both generated cube maps.
+pub const IBL_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+
+/// Hemisphere integration steps per axis for irradiance texels.
+const IRRADIANCE_STEPS: u32 = 32;
+
+/// GGX importance samples per pre-filtered texel.
+const PREFILTER_SAMPLES: u32 = 256;
+
+/// Threads per workgroup along x and y (`numthreads(8, 8, 1)`).
+const WORKGROUP_SIZE: u32 = 8;
+
+/// Bytes per output texel (four half floats).
+const TEXEL_BYTES: u32 = 8;
+
+/// Parameters of one precompute dispatch (32 bytes).
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct IblPrecomputeUniforms {
+    pub face_size: u32,
+    /// Texels per padded output row.
+    pub row_stride: u32,
+    /// First output texel of this dispatch.
+    pub base_offset: u32,
+    /// Irradiance steps per axis, or pre-filter samples.
+    pub sample_count: u32,
+    pub roughness: f32,
+    pub source_size: f32,
+    pub source_max_mip: f32,
+    pub _padding: f32,
+}
+
+/// Which cube map an output mip belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IblTarget {
+    Irradiance,
+    Prefiltered,
+}
+
+/// One output mip: its dispatch parameters and copy region.
+#[derive(Debug, Clone, Copy)]
+struct IblOutput {
+    target: IblTarget,
+    mip: u32,
+    size: u32,
+    row_stride: u32,
+    /// Offset of the first texel in the output buffer.
+    offset: u32,
+    roughness: f32,
+}
+
+impl IblOutput {
+    /// Texels covered by all six faces, including row padding.
+    fn texel_count(&self) -> u32 {
+        self.row_stride * self.size * 6
+    }
+}
+
+/// Texels per output row of a `size` wide face, padded so rows start at
+/// 256 byte boundaries as buffer-to-texture copies require.
+fn padded_row_stride(size: u32) -> u32 {
+    (size * TEXEL_BYTES).next_multiple_of(256) / TEXEL_BYTES
+}
+
+/// Lay out the irradiance map followed by every pre-filtered mip.
+fn plan_outputs(prefilter_mips: u32) -> Vec<IblOutput> {
+    let mut outputs = Vec::new();
+    let mut offset = 0;
+    let mut push = |target, mip, size, roughness| {
+        let output = IblOutput {
+            target,
+            mip,
+            size,
+            row_stride: padded_row_stride(size),
+            offset,
+            roughness,
+        };
+        offset += output.texel_count();
+        outputs.push(output);
+    };
+    push(IblTarget::Irradiance, 0, IRRADIANCE_SIZE, 0.0);
+    for mip in 0..prefilter_mips {
+        let roughness = mip as f32 / prefilter_mips.saturating_sub(1).max(1) as f32;
+        push(
+            IblTarget::Prefiltered,
+            mip,
+            (PREFILTER_SIZE >> mip).max(1),
+            roughness,
+        );
+    }
+    outputs
+}
+
+/// Binding layout of the precompute group.
+pub fn ibl_precompute_binding_layout() -> BindingLayout {
+    BindingLayout::new()
+        .with_entry(
+            BindingLayoutEntry::new(0, BindingType::UniformBuffer)
+                .with_visibility(ShaderStageFlags::COMPUTE),
+        )
+        .with_entry(
+            BindingLayoutEntry::new(1, BindingType::TextureCube)
+                .with_visibility(ShaderStageFlags::COMPUTE),
+        )
+        .with_entry(
+            BindingLayoutEntry::new(2, BindingType::Sampler)
+                .with_visibility(ShaderStageFlags::COMPUTE),
+        )
+        .with_entry(
+            BindingLayoutEntry::new(3, BindingType::ReadWriteStorageBuffer)
+                .with_visibility(ShaderStageFlags::COMPUTE),
+        )
+        .with_label("ibl_precompute")
+}
+
+fn create_compute_material(
+    device: &Arc<GraphicsDevice>,
+    entry_point: &str,
+) -> Result<Arc<Material>, GraphicsError> {
+    device.create_material(
+        &MaterialDescriptor::new()
+            .with_shader(ShaderSource::slang(
+                ShaderStage::Compute,
+                SHADER_SLANG.as_bytes().to_vec(),
+                entry_point,
+                vec![],
+            ))
+            .with_binding_layout(Arc::new(ibl_precompute_binding_layout()))
+            .with_label(format!("std_ibl_{entry_point}")),
+    )
+}
+
+/// GPU resources convolving one environment cube map.
+///
+/// [`new`](Self::new) creates the output cube maps and per-mip dispatches;
+/// [`record`](Self::record) adds the compute and copy passes to a render
+/// graph. The cube maps hold valid data once that graph has executed.
+pub struct IblPrecompute {
+    irradiance: Arc<Texture>,
+    prefiltered: Arc<Texture>,
+    output: Arc<Buffer>,
+    dispatches: Vec<(Arc<MaterialInstance>, u32)>,
+    outputs: Vec<IblOutput>,
+}
+
+impl IblPrecompute {
+    /// Prepare the convolution of `source`, a cube map created with
+    /// `TextureUsage::TEXTURE_BINDING`. Mips of `source` are used to reduce
+    /// noise when present.
+    pub fn new(device: &Arc<GraphicsDevice>, source: &Arc<Texture>) -> Result<Self, GraphicsError> {
+        let prefilter_mips = PREFILTER_SIZE.ilog2() + 1;
+        let outputs = plan_outputs(prefilter_mips);
+
+        let usage = TextureUsage::TEXTURE_BINDING | TextureUsage::COPY_DST;
+        let irradiance = device.create_texture(
+            &TextureDescriptor::new_cube(IRRADIANCE_SIZE, IBL_FORMAT, usage)
+                .with_label("ibl_irradiance"),
+        )?;
+        let prefiltered = device.create_texture(
+            &TextureDescriptor::new_cube(PREFILTER_SIZE, IBL_FORMAT, usage)
+                .with_mip_levels(prefilter_mips)
+                .with_label("ibl_prefiltered"),
+        )?;
+
+        let texel_count: u32 = outputs.iter().map(IblOutput::texel_count).sum();
+        let output = device.create_buffer(
+            &BufferDescriptor::new(
+                (texel_count * TEXEL_BYTES) as u64,
+                BufferUsage::STORAGE | BufferUsage::COPY_SRC,
+            )
+            .with_label("ibl_precompute_output"),
+        )?;
+        let sampler = device.create_sampler(
+            &SamplerDescriptor::linear()
+                .with_address_mode(AddressMode::ClampToEdge)
+                .with_label("ibl_precompute_sampler"),
+        )?;
+
+        let irradiance_material = create_compute_material(device, "cs_irradiance")?;
+        let prefilter_material = create_compute_material(device, "cs_prefilter")?;
+
+        let mut dispatches = Vec::with_capacity(outputs.len());
+        for output_mip in &outputs {
+            let (material, sample_count) = match output_mip.target {
+                IblTarget::Irradiance => (&irradiance_material, IRRADIANCE_STEPS),
+                IblTarget::Prefiltered => (&prefilter_material, PREFILTER_SAMPLES),
+            };
+            let uniforms = IblPrecomputeUniforms {
+                face_size: output_mip.size,
+                row_stride: output_mip.row_stride,
+                base_offset: output_mip.offset,
+                sample_count,
+                roughness: output_mip.roughness,
+                source_size: source.width() as f32,
+                source_max_mip: source.mip_level_count().saturating_sub(1) as f32,
+                _padding: 0.0,
+            };
+            let uniform_buffer = device.create_buffer(
+                &BufferDescriptor::new(
+                    std::mem::size_of::<IblPrecomputeUniforms>() as u64,
+                    BufferUsage::UNIFORM | BufferUsage::COPY_DST,
+                )
+                .with_label("ibl_precompute_params"),
+            )?;
+            device.write_buffer(&uniform_buffer, 0, bytemuck::bytes_of(&uniforms))?;
+
+            let group = BindingGroup::new()
+                .with_buffer(0, uniform_buffer)
+                .with_texture(1, Arc::clone(source))
+                .with_sampler(2, Arc::clone(&sampler))
+                .with_buffer(3, Arc::clone(&output));
+            let instance =
+                MaterialInstance::new(Arc::clone(material)).with_binding_group(Arc::new(group));
+            dispatches.push((Arc::new(instance), output_mip.size));
+        }
+
+        Ok(Self {
+            irradiance,
+            prefiltered,
+            output,
+            dispatches,
+            outputs,
+        })
+    }
+
+    /// Diffuse irradiance cube map.
+    pub fn irradiance(&self) -> &Arc<Texture> {
+        &self.irradiance
+    }
+
+    /// Roughness pre-filtered specular cube map.
+    pub fn prefiltered(&self) -> &Arc<Texture> {
+        &self.prefiltered
+    }
+
+    /// Add the convolution and copy passes to `graph`.
+    pub fn record(&self, graph: &mut RenderGraph) {
+        let mut compute = ComputePass::new("ibl_precompute".into());
+        for (instance, size) in &self.dispatches {
+            let groups = size.div_ceil(WORKGROUP_SIZE);
+            compute.add_dispatch(Arc::clone(instance), groups, groups, 6);
+        }
+
+        let mut irradiance_regions = Vec::new();
+        let mut prefiltered_regions = Vec::new();
+        for output in &self.outputs {
+            let regions = match output.target {
+                IblTarget::Irradiance => &mut irradiance_regions,
+                IblTarget::Prefiltered => &mut prefiltered_regions,
+            };
+            let face_texels = output.row_stride * output.size;
+            for face in 0..6 {
+                regions.push(BufferTextureCopyRegion::new(
+                    BufferTextureLayout::new(
+                        ((output.offset + face * face_texels) * TEXEL_BYTES) as u64,
+                        Some(output.row_stride * TEXEL_BYTES),
+                        Some(output.size),
+                    ),
+                    TextureCopyLocation::new(output.mip, TextureOrigin::new(0, 0, face)),
+                    Extent3d::new_2d(output.size, output.size),
+                ));
+            }
+        }
+        let mut copy = TransferPass::new("ibl_copy".into());
+        copy.set_transfer_config(
+            TransferConfig::new()
+                .with_operation(TransferOperation::upload_texture(
+                    Arc::clone(&self.output),
+                    Arc::clone(&self.irradiance),
+                    irradiance_regions,
+                ))
+                .with_operation(TransferOperation::upload_texture(
+                    Arc::clone(&self.output),
+                    Arc::clone(&self.prefiltered),
+                    prefiltered_regions,
+                )),
+        );
+
+        let compute = graph.add_compute_pass(compute);
+        let copy = graph.add_transfer_pass(copy);
+        graph.add_dependency(copy, compute);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uniforms_are_32_bytes() {
+        assert_eq!(std::mem::size_of::<IblPrecomputeUniforms>(), 32);
+    }
+
+    #[test]
+    fn output_rows_are_256_byte_aligned() {
+        assert_eq!(padded_row_stride(128), 128);
+        assert_eq!(padded_row_stride(32), 32);
+        assert_eq!(padded_row_stride(16), 32);
+        assert_eq!(padded_row_stride(1), 32);
+
+        let outputs = plan_outputs(PREFILTER_SIZE.ilog2() + 1);
+        for output in &outputs {
+            assert_eq!((output.row_stride * TEXEL_BYTES) % 256, 0);
+            assert!(output.row_stride >= output.size);
+        }
+    }
+
+    #[test]
+    fn outputs_are_packed_back_to_back() {
+        let outputs = plan_outputs(8);
+        assert_eq!(outputs.len(), 9);
+        assert_eq!(outputs[0].target, IblTarget::Irradiance);
+        assert_eq!(outputs[0].offset, 0);
+        for pair in outputs.windows(2) {
+            assert_eq!(pair[1].offset, pair[0].offset + pair[0].texel_count());
+        }
+
+        let prefiltered = &outputs[1..];
+        assert_eq!(prefiltered[0].size, PREFILTER_SIZE);
+        assert_eq!(prefiltered[0].roughness, 0.0);
+        assert_eq!(prefiltered[7].size, 1);
+        assert_eq!(prefiltered[7].roughness, 1.0);
+    }
+}
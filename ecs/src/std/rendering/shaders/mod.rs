@@ -2,16 +2,108 @@
 //!
 //! Each submodule provides a shader, uniform struct, and factory functions
 //! for a common material type.
+//!
+//! The shader sources are compiled in. To hot reload edits to the `.slang`
+//! files, mount the engine `shaders` directory and call
+//! [`watch_standard_shaders`].
 
+pub mod deferred;
 pub mod entity_index;
+pub mod ibl_precompute;
 pub mod opaque_color;
+pub mod pbr;
+pub mod post_process;
+pub mod shadow_depth;
+pub mod skinned_color;
+pub mod skybox;
 
+pub use deferred::{create_deferred_resolve_material, gbuffer_binding, gbuffer_binding_layout};
 pub use entity_index::{
     EntityIndexUniforms, create_entity_index_instance, create_entity_index_material,
     update_entity_index_uniforms,
 };
+pub use ibl_precompute::{
+    IBL_FORMAT, IRRADIANCE_SIZE, IblPrecompute, IblPrecomputeUniforms, PREFILTER_SIZE,
+    ibl_precompute_binding_layout,
+};
 pub use opaque_color::{
     OpaqueColorUniforms, create_opaque_color_cpu_material, create_opaque_color_entity,
     create_opaque_color_entity_full, create_opaque_color_entity_with_picking,
     create_opaque_color_material, update_opaque_color_uniforms,
 };
+pub use pbr::{
+    LIGHTING_BINDING_GROUP, alpha_mode_defines, create_pbr_entity, create_pbr_entity_sharing,
+    create_pbr_entity_with_passes, create_pbr_gbuffer_material, create_pbr_material,
+    lighting_binding_layout, pbr_binding_layouts, pbr_shader_defines,
+};
+pub use post_process::{
+    PostProcessUniforms, create_post_process_material, post_process_binding_layout,
+};
+pub use shadow_depth::{
+    ShadowViewUniforms, create_shadow_depth_material, create_shadow_view_binding,
+    shadow_caster_binding,
+};
+pub use skinned_color::{
+    create_skinned_opaque_color_entity_full, create_skinned_opaque_color_material,
+};
+pub use skybox::{
+    SkyboxUniforms, create_fullscreen_triangle, create_skybox_material, skybox_binding_layout,
+};
+
+use redlilium_graphics::ShaderLibrary;
+
+use super::resources::{AssetServer, MaterialManager, ShaderAsset};
+
+/// Compiled-in standard shaders and their file names in `shaders/standard/`.
+const STANDARD_SHADERS: [(&str, &str); 9] = [
+    ("deferred_resolve", deferred::SHADER_SLANG),
+    ("entity_index", entity_index::SHADER_SLANG),
+    ("ibl_precompute", ibl_precompute::SHADER_SLANG),
+    ("opaque_color", opaque_color::SHADER_SLANG),
+    ("pbr", pbr::SHADER_SLANG),
+    ("post_process", post_process::SHADER_SLANG),
+    ("shadow_depth", shadow_depth::SHADER_SLANG),
+    ("skinned_opaque_color", skinned_color::SHADER_SLANG),
+    ("skybox", skybox::SHADER_SLANG),
+];
+
+/// Load the standard shaders and shader library modules through the
+/// [`AssetServer`] so that editing their files hot reloads the materials
+/// registered in the [`MaterialManager`].
+///
+/// `root` is the VFS directory holding the engine `shaders` directory's
+/// `standard/` and `library/` folders. Each registered material stage compiled
+/// from a built-in standard shader is tied to that shader's file; materials
+/// registered later are not.
+pub fn watch_standard_shaders(
+    assets: &mut AssetServer,
+    materials: &mut MaterialManager,
+    root: &str,
+) {
+    for (file, builtin) in STANDARD_SHADERS {
+        let stages: Vec<_> = materials
+            .materials()
+            .iter()
+            .flat_map(|(name, (_, material))| {
+                material
+                    .descriptor()
+                    .shaders
+                    .iter()
+                    .filter(|source| source.source == builtin.as_bytes())
+                    .map(|source| (name.clone(), source.stage))
+            })
+            .collect();
+        if stages.is_empty() {
+            continue;
+        }
+        let shader = assets.load::<ShaderAsset>(&format!("{root}/standard/{file}.slang"));
+        for (name, stage) in stages {
+            materials.set_stage_shader(name, stage, shader.clone());
+        }
+    }
+
+    for (name, _) in ShaderLibrary::standard_slang().modules() {
+        let shader = assets.load::<ShaderAsset>(&format!("{root}/library/{name}.slang"));
+        materials.set_library_module(name, shader);
+    }
+}
@@ -36,10 +36,11 @@ use crate::std::rendering::components::{
 };
 
 /// Slang shader for opaque color rendering with camera VP + model matrix uniforms.
-const SHADER_SLANG: &str = include_str!("../../../../../shaders/standard/opaque_color.slang");
+pub(super) const SHADER_SLANG: &str =
+    include_str!("../../../../../shaders/standard/opaque_color.slang");
 
 /// Default base color: light gray matching the original hardcoded value.
-const DEFAULT_BASE_COLOR: [f32; 4] = [0.6, 0.6, 0.65, 1.0];
+pub(super) const DEFAULT_BASE_COLOR: [f32; 4] = [0.6, 0.6, 0.65, 1.0];
 
 /// Per-entity uniform data: view-projection matrix + model matrix.
 #[repr(C)]
@@ -212,7 +213,7 @@ pub fn create_opaque_color_entity_full(
 }
 
 /// Create the material properties GPU buffer with default base_color.
-fn create_material_props_buffer(device: &Arc<GraphicsDevice>) -> Arc<Buffer> {
+pub(super) fn create_material_props_buffer(device: &Arc<GraphicsDevice>) -> Arc<Buffer> {
     let buffer = device
         .create_buffer(
             &BufferDescriptor::new(
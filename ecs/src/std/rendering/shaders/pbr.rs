@@ -0,0 +1,439 @@
+//! Standard PBR metallic-roughness material.
+//!
+//! Renders [`CpuMaterial::pbr_metallic_roughness`] materials (e.g. imported
+//! from glTF) with Cook-Torrance direct lighting from every light gathered
+//! into [`SceneLighting`](super::super::SceneLighting), shadow maps and
+//! image-based ambient lighting. One GPU material is created per CPU
+//! material: the present texture slots and vertex attributes become shader
+//! defines.
+//!
+//! # Binding groups
+//!
+//! - Group 0: per-entity transform uniforms ([`OpaqueColorUniforms`]
+//!   layout, only `model` is used)
+//! - Group 1: material properties (binding 0) and textures (binding 1+)
+//! - Group 2: scene lighting, appended per camera by the forward render
+//!   systems (see [`LIGHTING_BINDING_GROUP`])
+//!
+//! The `fs_gbuffer` entry point ([`create_pbr_gbuffer_material`]) writes
+//! the same surface into a [`GBuffer`] for deferred rendering instead.
+//!
+//! # Usage
+//!
+//! ```ignore
+//! // At init time, per CPU material:
+//! let material = create_pbr_material(&device, &cpu_material, color_fmt, depth_fmt)?;
+//! material_manager.register_material("pbr_0", cpu_material, material);
+//!
+//! // Per entity:
+//! let (per_entity, render_mat, _bundle) =
+//!     create_pbr_entity(&mut material_manager, &mut textures, "pbr_0", cpu_instance)?;
+//! world.insert(entity, render_mat);
+//! world.insert(entity, per_entity);
+//! ```
+
+use std::sync::Arc;
+
+use redlilium_core::material::{AlphaMode, CpuMaterial, CpuMaterialInstance};
+use redlilium_core::mesh::VertexAttributeSemantic;
+use redlilium_graphics::{
+    BindingGroup, BindingLayout, BindingLayoutEntry, BindingType, BlendState, BoundResource,
+    BufferDescriptor, BufferUsage, GraphicsDevice, GraphicsError, Material, MaterialDescriptor,
+    MaterialInstance, ShaderSource, ShaderStage, TextureFormat,
+};
+
+use super::opaque_color::OpaqueColorUniforms;
+use crate::std::rendering::components::{
+    GBuffer, MaterialBundle, PerEntityBuffers, RenderMaterial, RenderPassType,
+};
+use crate::std::rendering::resources::{
+    MAX_SHADOW_ATLASES, MaterialManager, MaterialManagerError, TextureManager,
+};
+
+/// Slang shader for PBR metallic-roughness rendering.
+pub(super) const SHADER_SLANG: &str = include_str!("../../../../../shaders/standard/pbr.slang");
+
+/// Index of the scene lighting binding group.
+///
+/// Materials with exactly this many binding groups in their instances and
+/// one more in their layouts get the camera's lighting group appended by
+/// [`ForwardRenderSystem`](super::super::ForwardRenderSystem).
+pub const LIGHTING_BINDING_GROUP: usize = 2;
+
+/// Texture slots of [`CpuMaterial::pbr_metallic_roughness`] and the shader
+/// define carrying each slot's binding.
+const TEXTURE_DEFINES: [(&str, &str); 5] = [
+    ("base_color_texture", "BASE_COLOR_TEXTURE_BINDING"),
+    (
+        "metallic_roughness_texture",
+        "METALLIC_ROUGHNESS_TEXTURE_BINDING",
+    ),
+    ("normal_texture", "NORMAL_TEXTURE_BINDING"),
+    ("occlusion_texture", "OCCLUSION_TEXTURE_BINDING"),
+    ("emissive_texture", "EMISSIVE_TEXTURE_BINDING"),
+];
+
+/// Shader defines selecting the standard shaders' handling of `alpha_mode`:
+/// `ALPHA_CUTOFF` for masked materials and `ALPHA_BLEND` for blended ones.
+pub fn alpha_mode_defines(alpha_mode: AlphaMode) -> Vec<(String, String)> {
+    match alpha_mode {
+        AlphaMode::Opaque => Vec::new(),
+        AlphaMode::Mask { cutoff } => vec![("ALPHA_CUTOFF".into(), format!("{cutoff:?}"))],
+        AlphaMode::Blend => vec![("ALPHA_BLEND".into(), "1".into())],
+    }
+}
+
+/// Shader defines describing `cpu_material`'s alpha mode, textures and
+/// vertex layout.
+pub fn pbr_shader_defines(cpu_material: &CpuMaterial) -> Vec<(String, String)> {
+    let mut defines = alpha_mode_defines(cpu_material.alpha_mode);
+    let layout = &cpu_material.vertex_layout;
+    if layout.has_semantic(VertexAttributeSemantic::TexCoord0) {
+        defines.push(("HAS_TEXCOORD0".into(), "1".into()));
+    }
+    if layout.has_semantic(VertexAttributeSemantic::Tangent) {
+        defines.push(("HAS_TANGENT".into(), "1".into()));
+    }
+    for (name, define) in TEXTURE_DEFINES {
+        if let Some((_, binding)) = cpu_material.find_binding(name) {
+            defines.push((define.into(), binding.binding.to_string()));
+        }
+    }
+    defines
+}
+
+/// Binding layout of the scene lighting group (group 2).
+///
+/// See [`SceneLighting`](super::super::SceneLighting) for the resources
+/// bound at each slot.
+pub fn lighting_binding_layout() -> BindingLayout {
+    let mut layout = BindingLayout::new()
+        .with_uniform_buffer(0)
+        .with_entry(BindingLayoutEntry::new(1, BindingType::StorageBuffer))
+        .with_entry(BindingLayoutEntry::new(2, BindingType::StorageBuffer));
+    for slot in 0..MAX_SHADOW_ATLASES as u32 {
+        layout = layout.with_depth_texture(3 + slot);
+    }
+    layout
+        .with_comparison_sampler(7)
+        .with_texture_cube(8)
+        .with_texture_cube(9)
+        .with_texture(10)
+        .with_sampler(11)
+        .with_label("scene_lighting")
+}
+
+/// Binding layouts of the PBR material for `cpu_material`, one per group.
+///
+/// Set explicitly because reflection cannot tell depth textures and
+/// comparison samplers apart from regular ones.
+pub fn pbr_binding_layouts(cpu_material: &CpuMaterial) -> Vec<Arc<BindingLayout>> {
+    let transform = BindingLayout::new()
+        .with_uniform_buffer(0)
+        .with_label("pbr_transform");
+
+    let mut material = BindingLayout::new().with_uniform_buffer(0);
+    for (name, _) in TEXTURE_DEFINES {
+        if let Some((_, binding)) = cpu_material.find_binding(name) {
+            material = material.with_combined_texture_sampler(binding.binding);
+        }
+    }
+
+    vec![
+        Arc::new(transform),
+        Arc::new(material.with_label("pbr_material")),
+        Arc::new(lighting_binding_layout()),
+    ]
+}
+
+/// Create the GPU [`Material`] rendering `cpu_material`.
+///
+/// [`AlphaMode::Blend`] materials get alpha blending without depth writes
+/// and [`AlphaMode::Mask`] materials discard fragments below their cutoff;
+/// the render systems draw them in the matching
+/// [`RenderQueue`](crate::RenderQueue).
+pub fn create_pbr_material(
+    device: &Arc<GraphicsDevice>,
+    cpu_material: &CpuMaterial,
+    color_format: TextureFormat,
+    depth_format: TextureFormat,
+) -> Result<Arc<Material>, GraphicsError> {
+    let mut descriptor = pbr_descriptor(cpu_material, "fs_main")
+        .with_color_format(color_format)
+        .with_depth_format(depth_format)
+        .with_label("std_pbr");
+    if cpu_material.alpha_mode == AlphaMode::Blend {
+        descriptor = descriptor
+            .with_blend_state(BlendState::alpha_blending())
+            .with_depth_write(false);
+    }
+    device.create_material(&descriptor)
+}
+
+/// Create the GPU [`Material`] writing `cpu_material` into a [`GBuffer`]
+/// for the [`DeferredRenderSystem`](super::super::DeferredRenderSystem).
+///
+/// Register it alongside the forward material and pass both to
+/// [`create_pbr_entity_with_passes`]. Blended materials should stay forward
+/// only, since the G-buffer holds a single surface per pixel.
+pub fn create_pbr_gbuffer_material(
+    device: &Arc<GraphicsDevice>,
+    cpu_material: &CpuMaterial,
+    depth_format: TextureFormat,
+) -> Result<Arc<Material>, GraphicsError> {
+    let mut descriptor = pbr_descriptor(cpu_material, "fs_gbuffer")
+        .with_depth_format(depth_format)
+        .with_label("std_pbr_gbuffer");
+    for format in GBuffer::FORMATS {
+        descriptor = descriptor.with_color_format(format);
+    }
+    device.create_material(&descriptor)
+}
+
+/// Shaders, layouts and vertex input shared by the PBR materials.
+fn pbr_descriptor(cpu_material: &CpuMaterial, fragment_entry: &str) -> MaterialDescriptor {
+    let defines = pbr_shader_defines(cpu_material);
+    let mut descriptor = MaterialDescriptor::new()
+        .with_shader(ShaderSource::slang(
+            ShaderStage::Vertex,
+            SHADER_SLANG.as_bytes().to_vec(),
+            "vs_main",
+            defines.clone(),
+        ))
+        .with_shader(ShaderSource::slang(
+            ShaderStage::Fragment,
+            SHADER_SLANG.as_bytes().to_vec(),
+            fragment_entry,
+            defines,
+        ))
+        .with_vertex_layout(Arc::clone(&cpu_material.vertex_layout))
+        .with_topology(cpu_material.topology)
+        .with_polygon_mode(cpu_material.polygon_mode);
+    for layout in pbr_binding_layouts(cpu_material) {
+        descriptor = descriptor.with_binding_layout(layout);
+    }
+    descriptor
+}
+
+/// Create per-entity GPU resources for a PBR material registered in
+/// `material_manager` as `material_name`.
+///
+/// Textures of `cpu_instance` are resolved through `textures`. Returns
+/// `(per_entity_buffers, render_material, material_bundle)`, ready for ECS
+/// insertion; the bundle is registered with the material manager for
+/// serialization and uniform sync.
+pub fn create_pbr_entity(
+    material_manager: &mut MaterialManager,
+    textures: &mut TextureManager,
+    material_name: &str,
+    cpu_instance: Arc<CpuMaterialInstance>,
+) -> Result<(PerEntityBuffers, RenderMaterial, Arc<MaterialBundle>), MaterialManagerError> {
+    create_pbr_entity_with_passes(
+        material_manager,
+        textures,
+        &[(RenderPassType::Forward, material_name)],
+        cpu_instance,
+    )
+}
+
+/// Like [`create_pbr_entity`], with one registered material per render pass.
+///
+/// Use `(RenderPassType::Deferred, name)` with a material from
+/// [`create_pbr_gbuffer_material`] to draw the entity into G-buffers;
+/// cameras with a forward target keep using the forward material. All
+/// passes share the entity's transform and material bindings.
+pub fn create_pbr_entity_with_passes(
+    material_manager: &mut MaterialManager,
+    textures: &mut TextureManager,
+    pass_materials: &[(RenderPassType, &str)],
+    cpu_instance: Arc<CpuMaterialInstance>,
+) -> Result<(PerEntityBuffers, RenderMaterial, Arc<MaterialBundle>), MaterialManagerError> {
+    let device = Arc::clone(material_manager.device());
+
+    let uniform_buffer = device.create_buffer(
+        &BufferDescriptor::new(
+            std::mem::size_of::<OpaqueColorUniforms>() as u64,
+            BufferUsage::UNIFORM | BufferUsage::COPY_DST,
+        )
+        .with_label("pbr_transform"),
+    )?;
+    let transform_group = Arc::new(BindingGroup::new().with_buffer(0, uniform_buffer.clone()));
+
+    let material_group = Arc::new(material_manager.build_binding_group(&cpu_instance, textures)?);
+    let material_buffer =
+        material_group
+            .entries
+            .iter()
+            .find_map(|entry| match (&entry.resource, entry.binding) {
+                (BoundResource::Buffer(buffer), 0) => Some(Arc::clone(buffer)),
+                _ => None,
+            });
+
+    let mut bundle = MaterialBundle::new().with_shared_bindings(vec![Arc::clone(&transform_group)]);
+    for &(pass, material_name) in pass_materials {
+        let material = material_manager
+            .get_material(material_name)
+            .cloned()
+            .ok_or_else(|| MaterialManagerError::MaterialNotFound(material_name.to_owned()))?;
+        let mut instance = MaterialInstance::new(material)
+            .with_binding_group(Arc::clone(&transform_group)) // group 0
+            .with_binding_group(Arc::clone(&material_group)); // group 1
+        if let Some(name) = &cpu_instance.name {
+            instance =
+                instance.with_label(format!("{name}_{}", pass.as_str().to_ascii_lowercase()));
+        }
+        bundle = bundle.with_pass(pass, Arc::new(instance));
+    }
+    if let Some(name) = &cpu_instance.name {
+        bundle = bundle.with_label(name.clone());
+    }
+    let bundle = Arc::new(bundle);
+
+    let pass_materials: Vec<_> = pass_materials
+        .iter()
+        .map(|&(pass, name)| (pass, name.to_owned()))
+        .collect();
+    material_manager.register_bundle(&bundle, Arc::clone(&cpu_instance), pass_materials.clone());
+
+    let mut render_material =
+        RenderMaterial::with_cpu_data(Arc::clone(&bundle), cpu_instance, pass_materials);
+    if let Some(buffer) = material_buffer {
+        render_material = render_material.with_material_uniform_buffer(buffer);
+    }
+
+    Ok((
+        PerEntityBuffers::new(uniform_buffer),
+        render_material,
+        bundle,
+    ))
+}
+
+/// Create per-entity GPU resources for another entity drawn with the same
+/// material instance as `source` (from [`create_pbr_entity`]).
+///
+/// Only the transform group is new; the material group is shared, so the
+/// render systems can draw all such entities with one instanced draw (see
+/// [`DrawBatcher`](crate::DrawBatcher)). Editing the material values of one
+/// of them edits all of them.
+pub fn create_pbr_entity_sharing(
+    material_manager: &mut MaterialManager,
+    source: &RenderMaterial,
+) -> Result<(PerEntityBuffers, RenderMaterial), MaterialManagerError> {
+    let uniform_buffer = material_manager.device().create_buffer(
+        &BufferDescriptor::new(
+            std::mem::size_of::<OpaqueColorUniforms>() as u64,
+            BufferUsage::UNIFORM | BufferUsage::COPY_DST,
+        )
+        .with_label("pbr_transform"),
+    )?;
+    let transform_group = Arc::new(BindingGroup::new().with_buffer(0, uniform_buffer.clone()));
+    let bundle = Arc::new(
+        source
+            .bundle()
+            .with_shared_bindings_replaced(vec![transform_group]),
+    );
+
+    let mut render_material = source.clone();
+    if let (Some(cpu_instance), Some(pass_materials)) =
+        (source.cpu_instance(), source.pass_materials())
+    {
+        material_manager.register_bundle(
+            &bundle,
+            Arc::clone(cpu_instance),
+            pass_materials.to_vec(),
+        );
+    }
+    render_material.set_bundle(bundle, None, None);
+
+    Ok((PerEntityBuffers::new(uniform_buffer), render_material))
+}
+
+#[cfg(test)]
+mod tests {
+    use redlilium_core::mesh::VertexLayout;
+
+    use super::*;
+
+    fn cpu_material(layout: Arc<VertexLayout>) -> CpuMaterial {
+        CpuMaterial::pbr_metallic_roughness(
+            layout,
+            AlphaMode::Opaque,
+            false,
+            true,
+            false,
+            true,
+            false,
+            true,
+        )
+    }
+
+    #[test]
+    fn defines_follow_texture_bindings() {
+        let defines = pbr_shader_defines(&cpu_material(VertexLayout::pbr()));
+        let get = |name: &str| {
+            defines
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str())
+        };
+        assert_eq!(get("BASE_COLOR_TEXTURE_BINDING"), Some("1"));
+        assert_eq!(get("NORMAL_TEXTURE_BINDING"), Some("2"));
+        assert_eq!(get("EMISSIVE_TEXTURE_BINDING"), Some("3"));
+        assert_eq!(get("METALLIC_ROUGHNESS_TEXTURE_BINDING"), None);
+        assert_eq!(get("HAS_TEXCOORD0"), Some("1"));
+        assert_eq!(get("HAS_TANGENT"), Some("1"));
+
+        let defines = pbr_shader_defines(&cpu_material(VertexLayout::position_normal()));
+        assert!(!defines.iter().any(|(key, _)| key == "HAS_TEXCOORD0"));
+        assert!(!defines.iter().any(|(key, _)| key == "HAS_TANGENT"));
+    }
+
+    #[test]
+    fn defines_follow_alpha_mode() {
+        assert!(alpha_mode_defines(AlphaMode::Opaque).is_empty());
+        assert_eq!(
+            alpha_mode_defines(AlphaMode::Mask { cutoff: 0.5 }),
+            vec![("ALPHA_CUTOFF".to_owned(), "0.5".to_owned())]
+        );
+        assert_eq!(
+            alpha_mode_defines(AlphaMode::Mask { cutoff: 1.0 }),
+            vec![("ALPHA_CUTOFF".to_owned(), "1.0".to_owned())]
+        );
+        assert_eq!(
+            alpha_mode_defines(AlphaMode::Blend),
+            vec![("ALPHA_BLEND".to_owned(), "1".to_owned())]
+        );
+    }
+
+    #[test]
+    fn layouts_cover_transform_material_and_lighting() {
+        let layouts = pbr_binding_layouts(&cpu_material(VertexLayout::pbr()));
+        assert_eq!(layouts.len(), LIGHTING_BINDING_GROUP + 1);
+
+        let types = |index: usize| {
+            layouts[index]
+                .entries
+                .iter()
+                .map(|entry| (entry.binding, entry.binding_type))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(types(0), vec![(0, BindingType::UniformBuffer)]);
+        assert_eq!(
+            types(1),
+            vec![
+                (0, BindingType::UniformBuffer),
+                (1, BindingType::CombinedTextureSampler),
+                (2, BindingType::CombinedTextureSampler),
+                (3, BindingType::CombinedTextureSampler),
+            ]
+        );
+
+        let lighting = types(LIGHTING_BINDING_GROUP);
+        assert_eq!(lighting.len(), 12);
+        assert_eq!(lighting[1], (1, BindingType::StorageBuffer));
+        assert_eq!(lighting[3], (3, BindingType::DepthTexture));
+        assert_eq!(lighting[7], (7, BindingType::ComparisonSampler));
+        assert_eq!(lighting[8], (8, BindingType::TextureCube));
+        assert_eq!(lighting[11], (11, BindingType::Sampler));
+    }
+}
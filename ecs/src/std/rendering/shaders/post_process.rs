@@ -0,0 +1,83 @@
+//! Post-processing effect materials.
+//!
+//! Each effect of a [`PostProcessStack`](crate::PostProcessStack) is a
+//! fragment entry point of one shader, drawn as a fullscreen triangle that
+//! reads the previous effect's output (see
+//! [`PostProcessRenderer`](crate::PostProcessRenderer)).
+//!
+//! # Binding groups
+//!
+//! - Group 0: [`PostProcessUniforms`] (binding 0), input texture
+//!   (binding 1), sampler (binding 2) and auxiliary texture (binding 3,
+//!   the bloom image or color grading LUT)
+
+use std::sync::Arc;
+
+use redlilium_graphics::{
+    BindingLayout, GraphicsDevice, GraphicsError, Material, MaterialDescriptor, ShaderSource,
+    ShaderStage, TextureFormat,
+};
+
+/// Slang shader with every post-processing effect.
+pub(super) const SHADER_SLANG: &str =
+    include_str!("../../../../../shaders/standard/post_process.slang");
+
+/// Post-processing uniform buffer layout (32 bytes).
+#[repr(C)]
+#[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct PostProcessUniforms {
+    /// Effect specific parameters.
+    pub params: [f32; 4],
+    /// `xy` = reciprocal input size, `zw` = input size in texels.
+    pub texel: [f32; 4],
+}
+
+impl PostProcessUniforms {
+    /// Uniforms for an effect reading a `width` x `height` input.
+    pub fn new(params: [f32; 4], width: u32, height: u32) -> Self {
+        let (width, height) = (width.max(1) as f32, height.max(1) as f32);
+        Self {
+            params,
+            texel: [1.0 / width, 1.0 / height, width, height],
+        }
+    }
+}
+
+/// Binding layout of the post-processing group.
+pub fn post_process_binding_layout() -> BindingLayout {
+    BindingLayout::new()
+        .with_uniform_buffer(0)
+        .with_texture(1)
+        .with_sampler(2)
+        .with_texture(3)
+        .with_label("post_process")
+}
+
+/// Create the [`Material`] of the effect `entry_point` (e.g.
+/// `"fs_tonemap_aces"`) writing to `color_format`.
+///
+/// Post-processing passes have no depth attachment.
+pub fn create_post_process_material(
+    device: &Arc<GraphicsDevice>,
+    entry_point: &str,
+    color_format: TextureFormat,
+) -> Result<Arc<Material>, GraphicsError> {
+    device.create_material(
+        &MaterialDescriptor::new()
+            .with_shader(ShaderSource::slang(
+                ShaderStage::Vertex,
+                SHADER_SLANG.as_bytes().to_vec(),
+                "vs_main",
+                vec![],
+            ))
+            .with_shader(ShaderSource::slang(
+                ShaderStage::Fragment,
+                SHADER_SLANG.as_bytes().to_vec(),
+                entry_point,
+                vec![],
+            ))
+            .with_binding_layout(Arc::new(post_process_binding_layout()))
+            .with_color_format(color_format)
+            .with_label(format!("std_post_{entry_point}")),
+    )
+}
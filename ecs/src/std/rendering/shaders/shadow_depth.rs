@@ -0,0 +1,107 @@
+//! Standard shadow depth material.
+//!
+//! Renders shadow casters into a shadow map view. The vertex shader reads
+//! only the position attribute, so one material is created per mesh vertex
+//! layout; skinned casters use the `vs_skinned` entry point with joint
+//! matrices from [`PerEntityBuffers::joint_buffer`].
+//!
+//! The [`ShadowRenderSystem`](super::super::ShadowRenderSystem) creates
+//! and caches these materials in [`ShadowMaps`](super::super::ShadowMaps);
+//! most code never calls this module directly.
+//!
+//! # Binding groups
+//!
+//! - Group 0: per-entity transform uniforms ([`OpaqueColorUniforms`]
+//!   layout, only `model` is used) and, when skinned, joint matrices
+//! - Group 1: [`ShadowViewUniforms`] for the view being rendered
+//!
+//! [`OpaqueColorUniforms`]: super::OpaqueColorUniforms
+
+use std::sync::Arc;
+
+use redlilium_core::math::{Mat4, mat4_to_cols_array_2d};
+use redlilium_graphics::{
+    BindingGroup, Buffer, BufferDescriptor, BufferUsage, GraphicsDevice, GraphicsError, Material,
+    MaterialDescriptor, ShaderSource, ShaderStage, TextureFormat, VertexLayout,
+};
+
+use crate::std::rendering::components::PerEntityBuffers;
+
+/// Slang shader for shadow caster depth rendering.
+pub(super) const SHADER_SLANG: &str =
+    include_str!("../../../../../shaders/standard/shadow_depth.slang");
+
+/// Per-view shadow uniform data: the light's view-projection matrix.
+#[repr(C)]
+#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct ShadowViewUniforms {
+    pub light_view_projection: [[f32; 4]; 4],
+}
+
+impl ShadowViewUniforms {
+    /// Build uniforms from a light view-projection matrix.
+    pub fn new(light_view_projection: &Mat4) -> Self {
+        Self {
+            light_view_projection: mat4_to_cols_array_2d(light_view_projection),
+        }
+    }
+}
+
+/// Create the depth-only shadow [`Material`] for meshes with `vertex_layout`.
+///
+/// `skinned` selects the skinning vertex shader, which requires joint
+/// indices and weights in the layout.
+pub fn create_shadow_depth_material(
+    device: &Arc<GraphicsDevice>,
+    vertex_layout: Arc<VertexLayout>,
+    skinned: bool,
+    depth_format: TextureFormat,
+) -> Result<Arc<Material>, GraphicsError> {
+    let (entry, label) = if skinned {
+        ("vs_skinned", "std_shadow_depth_skinned")
+    } else {
+        ("vs_main", "std_shadow_depth")
+    };
+    device.create_material(
+        &MaterialDescriptor::new()
+            .with_shader(ShaderSource::slang(
+                ShaderStage::Vertex,
+                SHADER_SLANG.as_bytes().to_vec(),
+                entry,
+                vec![],
+            ))
+            .with_shader(ShaderSource::slang(
+                ShaderStage::Fragment,
+                SHADER_SLANG.as_bytes().to_vec(),
+                "fs_main",
+                vec![],
+            ))
+            .with_vertex_layout(vertex_layout)
+            .with_depth_format(depth_format)
+            .with_label(label),
+    )
+}
+
+/// Create the uniform buffer and binding group (group 1) for one shadow view.
+pub fn create_shadow_view_binding(
+    device: &Arc<GraphicsDevice>,
+) -> Result<(Arc<Buffer>, Arc<BindingGroup>), GraphicsError> {
+    let buffer = device.create_buffer(
+        &BufferDescriptor::new(
+            std::mem::size_of::<ShadowViewUniforms>() as u64,
+            BufferUsage::UNIFORM | BufferUsage::COPY_DST,
+        )
+        .with_label("shadow_view_uniforms"),
+    )?;
+    let group = Arc::new(BindingGroup::new().with_buffer(0, Arc::clone(&buffer)));
+    Ok((buffer, group))
+}
+
+/// Build the per-entity binding group (group 0) for a shadow caster.
+pub fn shadow_caster_binding(per_entity: &PerEntityBuffers) -> Arc<BindingGroup> {
+    let mut group = BindingGroup::new().with_buffer(0, Arc::clone(&per_entity.forward_buffer));
+    if let Some(joints) = &per_entity.joint_buffer {
+        group = group.with_buffer(1, Arc::clone(joints));
+    }
+    Arc::new(group)
+}
@@ -0,0 +1,157 @@
+//! Skinned variant of the standard opaque color material.
+//!
+//! Deforms vertices on the GPU with linear blend skinning, using the joint
+//! indices and weights of [`VertexLayout::animated_pbr`]. Joint matrices come
+//! from the entity's [`SkinnedMesh`](crate::SkinnedMesh) and are uploaded by
+//! [`UpdatePerEntityUniforms`](super::super::UpdatePerEntityUniforms) into a
+//! per-entity storage buffer. Lighting and material properties match
+//! [`opaque_color`](super::opaque_color).
+//!
+//! # Usage
+//!
+//! ```ignore
+//! // At init time:
+//! let material = create_skinned_opaque_color_material(&device, color_fmt, depth_fmt);
+//! let cpu_material = create_opaque_color_cpu_material();
+//!
+//! // Per skinned mesh entity (mesh converted with `cpu_mesh.with_layout(VertexLayout::animated_pbr())`):
+//! let (per_entity, render_mat, _bundle) =
+//!     create_skinned_opaque_color_entity_full(&device, &material, &cpu_material, joint_count);
+//! world.insert(entity, render_mat);
+//! world.insert(entity, per_entity);
+//! ```
+
+use std::sync::Arc;
+
+use redlilium_core::material::{CpuMaterial, CpuMaterialInstance};
+use redlilium_graphics::{
+    BindingGroup, Buffer, BufferDescriptor, BufferUsage, GraphicsDevice, Material,
+    MaterialDescriptor, MaterialInstance, ShaderSource, ShaderStage, TextureFormat, VertexLayout,
+};
+
+use super::opaque_color::{DEFAULT_BASE_COLOR, OpaqueColorUniforms, create_material_props_buffer};
+use crate::std::rendering::components::{
+    MaterialBundle, PerEntityBuffers, RenderMaterial, RenderPassType,
+};
+
+/// Slang shader for skinned opaque color rendering.
+pub(super) const SHADER_SLANG: &str =
+    include_str!("../../../../../shaders/standard/skinned_opaque_color.slang");
+
+/// Create the GPU [`Material`] for the skinned opaque color shader.
+///
+/// Uses [`VertexLayout::animated_pbr`]. Binding groups:
+/// - Group 0: per-entity transform uniforms (VP + model) and joint matrices
+/// - Group 1: material property uniforms (base_color)
+pub fn create_skinned_opaque_color_material(
+    device: &Arc<GraphicsDevice>,
+    color_format: TextureFormat,
+    depth_format: TextureFormat,
+) -> Arc<Material> {
+    device
+        .create_material(
+            &MaterialDescriptor::new()
+                .with_shader(ShaderSource::slang(
+                    ShaderStage::Vertex,
+                    SHADER_SLANG.as_bytes().to_vec(),
+                    "vs_main",
+                    vec![],
+                ))
+                .with_shader(ShaderSource::slang(
+                    ShaderStage::Fragment,
+                    SHADER_SLANG.as_bytes().to_vec(),
+                    "fs_main",
+                    vec![],
+                ))
+                .with_vertex_layout(VertexLayout::animated_pbr())
+                .with_color_format(color_format)
+                .with_depth_format(depth_format)
+                .with_label("std_skinned_opaque_color"),
+        )
+        .expect("Failed to create skinned opaque color material")
+}
+
+/// Create per-entity GPU resources for the skinned opaque color material.
+///
+/// `joint_count` sizes the joint matrix buffer; it should match the length
+/// of the entity's [`SkinnedMesh::joints`](crate::SkinnedMesh::joints).
+/// The joint buffer starts as identity matrices (bind pose).
+///
+/// Returns `(per_entity_buffers, render_material, material_bundle)`, ready
+/// for ECS insertion. Skinned entities have no entity-index pass.
+pub fn create_skinned_opaque_color_entity_full(
+    device: &Arc<GraphicsDevice>,
+    material: &Arc<Material>,
+    cpu_material: &Arc<CpuMaterial>,
+    joint_count: usize,
+) -> (PerEntityBuffers, RenderMaterial, Arc<MaterialBundle>) {
+    let uniform_buffer = device
+        .create_buffer(&BufferDescriptor::new(
+            std::mem::size_of::<OpaqueColorUniforms>() as u64,
+            BufferUsage::UNIFORM | BufferUsage::COPY_DST,
+        ))
+        .expect("Failed to create skinned opaque color uniform buffer");
+    let joint_buffer = create_joint_buffer(device, joint_count);
+
+    let transform_group = Arc::new(
+        BindingGroup::new()
+            .with_buffer(0, uniform_buffer.clone())
+            .with_buffer(1, joint_buffer.clone()),
+    );
+
+    let mat_props_buffer = create_material_props_buffer(device);
+    let mat_props_group = Arc::new(BindingGroup::new().with_buffer(0, mat_props_buffer.clone()));
+
+    let instance = Arc::new(
+        MaterialInstance::new(Arc::clone(material))
+            .with_binding_group(Arc::clone(&transform_group)) // group 0
+            .with_binding_group(mat_props_group), // group 1
+    );
+
+    let bundle = Arc::new(
+        MaterialBundle::new()
+            .with_pass(RenderPassType::Forward, instance)
+            .with_shared_bindings(vec![transform_group]),
+    );
+
+    let cpu_instance = Arc::new(
+        CpuMaterialInstance::new(Arc::clone(cpu_material)).with_value(
+            0,
+            redlilium_core::material::MaterialValue::Vec4(DEFAULT_BASE_COLOR),
+        ),
+    );
+    let render_material = RenderMaterial::with_cpu_data(
+        Arc::clone(&bundle),
+        cpu_instance,
+        vec![(RenderPassType::Forward, "skinned_opaque_color".into())],
+    )
+    .with_material_uniform_buffer(mat_props_buffer);
+
+    (
+        PerEntityBuffers::with_joints(uniform_buffer, joint_buffer),
+        render_material,
+        bundle,
+    )
+}
+
+/// Create the joint matrix storage buffer, initialized to identity matrices.
+fn create_joint_buffer(device: &Arc<GraphicsDevice>, joint_count: usize) -> Arc<Buffer> {
+    let identity: [[f32; 4]; 4] = [
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ];
+    let joints = vec![identity; joint_count.max(1)];
+    let buffer = device
+        .create_buffer(
+            &BufferDescriptor::new(
+                std::mem::size_of_val(joints.as_slice()) as u64,
+                BufferUsage::STORAGE | BufferUsage::COPY_DST,
+            )
+            .with_label("skinned_opaque_color_joints"),
+        )
+        .expect("Failed to create joint matrix buffer");
+    let _ = device.write_buffer(&buffer, 0, bytemuck::cast_slice(&joints));
+    buffer
+}
@@ -0,0 +1,89 @@
+//! Skybox material and fullscreen triangle.
+//!
+//! Draws a [`Skybox`](crate::Skybox) cube map as a fullscreen triangle at
+//! the far plane. The vertex shader reconstructs each pixel's view
+//! direction from the inverse view-projection matrix.
+//!
+//! # Binding groups
+//!
+//! - Group 0: [`SkyboxUniforms`] (binding 0), cube map (binding 1) and
+//!   sampler (binding 2)
+
+use std::sync::Arc;
+
+use redlilium_graphics::{
+    BindingLayout, GraphicsDevice, GraphicsError, Material, MaterialDescriptor, Mesh,
+    MeshDescriptor, ShaderSource, ShaderStage, TextureFormat, VertexBufferLayout, VertexLayout,
+};
+
+/// Slang shader for skybox rendering.
+pub(super) const SHADER_SLANG: &str = include_str!("../../../../../shaders/standard/skybox.slang");
+
+/// Skybox uniform buffer layout (96 bytes).
+#[repr(C)]
+#[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct SkyboxUniforms {
+    pub inverse_view_projection: [[f32; 4]; 4],
+    pub camera_position: [f32; 4],
+    /// `x` = intensity, `y` = mip level.
+    pub params: [f32; 4],
+}
+
+/// Binding layout of the skybox group.
+pub fn skybox_binding_layout() -> BindingLayout {
+    BindingLayout::new()
+        .with_uniform_buffer(0)
+        .with_texture_cube(1)
+        .with_sampler(2)
+        .with_label("skybox")
+}
+
+/// Create the skybox [`Material`] for the given target formats.
+pub fn create_skybox_material(
+    device: &Arc<GraphicsDevice>,
+    color_format: TextureFormat,
+    depth_format: TextureFormat,
+) -> Result<Arc<Material>, GraphicsError> {
+    device.create_material(
+        &MaterialDescriptor::new()
+            .with_shader(ShaderSource::slang(
+                ShaderStage::Vertex,
+                SHADER_SLANG.as_bytes().to_vec(),
+                "vs_main",
+                vec![],
+            ))
+            .with_shader(ShaderSource::slang(
+                ShaderStage::Fragment,
+                SHADER_SLANG.as_bytes().to_vec(),
+                "fs_main",
+                vec![],
+            ))
+            .with_binding_layout(Arc::new(skybox_binding_layout()))
+            .with_color_format(color_format)
+            .with_depth_format(depth_format)
+            .with_label("std_skybox"),
+    )
+}
+
+/// Create a mesh drawing one fullscreen triangle.
+///
+/// The mesh has no vertex attributes; shaders derive positions from
+/// `SV_VertexID`.
+pub fn create_fullscreen_triangle(
+    device: &Arc<GraphicsDevice>,
+) -> Result<Arc<Mesh>, GraphicsError> {
+    let layout = Arc::new(
+        VertexLayout::new()
+            .with_buffer(VertexBufferLayout::new(4))
+            .with_label("fullscreen_triangle"),
+    );
+    let mesh = device.create_mesh(
+        &MeshDescriptor::new(layout)
+            .with_vertex_count(3)
+            .with_label("fullscreen_triangle"),
+    )?;
+    if let Some(buffer) = mesh.vertex_buffer(0) {
+        device.write_buffer(buffer, 0, bytemuck::cast_slice(&[0.0f32; 3]))?;
+    }
+    Ok(mesh)
+}
@@ -0,0 +1,273 @@
+//! Deferred rendering systems.
+
+use std::sync::Arc;
+
+use redlilium_graphics::{
+    ColorAttachment, DepthStencilAttachment, GraphicsPass, LoadOp, RenderTarget,
+    RenderTargetConfig, StoreOp,
+};
+
+use super::forward_render::{
+    CameraView, add_post_passes, build_post_passes, collect_camera_views, queue_pass_draws,
+};
+use crate::SystemContext;
+use crate::std::components::{Camera, GlobalTransform, Visibility};
+use crate::std::rendering::components::{
+    CameraTarget, PerEntityBuffers, PostProcessStack, RenderMaterial, RenderMesh, RenderPassType,
+    Skybox,
+};
+use crate::std::rendering::resources::{
+    DeferredRenderer, DrawBatcher, PostProcessRenderer, RenderSchedule, SceneLighting, SpatialIndex,
+};
+
+/// Deferred render system.
+///
+/// Renders every camera whose [`CameraTarget`] has a
+/// [`GBuffer`](crate::GBuffer), in three passes per camera:
+///
+/// 1. Geometry: entities with a [`RenderPassType::Deferred`] material
+///    instance (see
+///    [`create_pbr_gbuffer_material`](crate::shaders::create_pbr_gbuffer_material))
+///    write their surfaces into the G-buffer and the depth target.
+/// 2. Resolve: a fullscreen pass shades the G-buffer with every light,
+///    shadow map and the ambient lighting from [`SceneLighting`] into the
+///    color target.
+/// 3. Forward: entities without a deferred instance (e.g. blended
+///    materials) and the camera's [`Skybox`] are drawn on top, tested
+///    against the geometry depth.
+///
+/// A camera's [`PostProcessStack`] runs after the forward pass.
+///
+/// Both entity passes draw the camera's frustum-culled entities from the
+/// [`SpatialIndex`], batched and ordered by the [`DrawBatcher`].
+///
+/// Requires the [`DeferredRenderer`], [`DrawBatcher`],
+/// [`PostProcessRenderer`], [`SceneLighting`] and [`SpatialIndex`]
+/// resources; run [`GatherLights`](crate::GatherLights) and
+/// [`UpdateSpatialIndex`](crate::UpdateSpatialIndex) first.
+/// [`insert_render_resources`](crate::insert_render_resources) inserts all
+/// but the [`DeferredRenderer`].
+pub struct DeferredRenderSystem;
+
+impl crate::System for DeferredRenderSystem {
+    type Result = ();
+
+    fn run<'a>(&'a self, ctx: &'a SystemContext<'a>) -> Result<(), crate::system::SystemError> {
+        let views = ctx
+            .lock::<(
+                crate::Read<Camera>,
+                crate::Read<GlobalTransform>,
+                crate::Read<CameraTarget>,
+                crate::Read<Skybox>,
+                crate::Read<PostProcessStack>,
+                crate::ResMut<SceneLighting>,
+                crate::ResMut<SpatialIndex>,
+                crate::ResMut<PostProcessRenderer>,
+            )>()
+            .execute(
+                |(
+                    cameras,
+                    globals,
+                    targets,
+                    skyboxes,
+                    stacks,
+                    mut lighting,
+                    mut spatial,
+                    mut post,
+                )| {
+                    let mut views = collect_camera_views(
+                        &cameras,
+                        &globals,
+                        &targets,
+                        &skyboxes,
+                        &mut lighting,
+                        &mut spatial,
+                        true,
+                    );
+                    build_post_passes(&mut views, &stacks, &mut post, "");
+                    views
+                },
+            );
+
+        submit_deferred_passes(ctx, views, "");
+        Ok(())
+    }
+}
+
+/// Editor-aware deferred render system.
+///
+/// Like [`DeferredRenderSystem`], but uses `ReadAll` for camera queries so
+/// it can see editor-flagged entities (e.g. the editor camera).
+pub struct EditorDeferredRenderSystem;
+
+impl crate::System for EditorDeferredRenderSystem {
+    type Result = ();
+
+    fn run<'a>(&'a self, ctx: &'a SystemContext<'a>) -> Result<(), crate::system::SystemError> {
+        let views = ctx
+            .lock::<(
+                crate::ReadAll<Camera>,
+                crate::ReadAll<GlobalTransform>,
+                crate::ReadAll<CameraTarget>,
+                crate::ReadAll<Skybox>,
+                crate::ReadAll<PostProcessStack>,
+                crate::ResMut<SceneLighting>,
+                crate::ResMut<SpatialIndex>,
+                crate::ResMut<PostProcessRenderer>,
+            )>()
+            .execute(
+                |(
+                    cameras,
+                    globals,
+                    targets,
+                    skyboxes,
+                    stacks,
+                    mut lighting,
+                    mut spatial,
+                    mut post,
+                )| {
+                    let mut views = collect_camera_views(
+                        &cameras,
+                        &globals,
+                        &targets,
+                        &skyboxes,
+                        &mut lighting,
+                        &mut spatial,
+                        true,
+                    );
+                    build_post_passes(&mut views, &stacks, &mut post, "editor_");
+                    views
+                },
+            );
+
+        submit_deferred_passes(ctx, views, "editor_");
+        Ok(())
+    }
+}
+
+fn submit_deferred_passes(ctx: &SystemContext<'_>, views: Vec<CameraView>, prefix: &str) {
+    ctx.lock::<(
+        crate::Read<RenderMesh>,
+        crate::Read<RenderMaterial>,
+        crate::Read<PerEntityBuffers>,
+        crate::Read<Visibility>,
+        crate::Read<GlobalTransform>,
+        crate::ResMut<RenderSchedule>,
+        crate::ResMut<DeferredRenderer>,
+        crate::ResMut<DrawBatcher>,
+    )>()
+    .execute(
+        |(
+            meshes,
+            materials,
+            per_entity,
+            visibilities,
+            globals,
+            mut schedule_res,
+            mut renderer,
+            mut batcher,
+        )| {
+            renderer.retain_cameras(|camera| views.iter().any(|view| view.entity == camera));
+            if views.is_empty() {
+                return;
+            }
+            let wait_for = schedule_res.dependencies().to_vec();
+            let Some(schedule) = schedule_res.schedule_mut() else {
+                return;
+            };
+
+            for view in views {
+                let Some(gbuffer) = &view.gbuffer else {
+                    continue;
+                };
+                let cam_idx = view.entity;
+
+                // 1. Geometry pass into the G-buffer.
+                let mut targets = RenderTargetConfig::new();
+                for attachment in gbuffer.attachments() {
+                    targets = targets.with_color(
+                        ColorAttachment::from_texture(Arc::clone(attachment))
+                            .with_load_op(LoadOp::clear_color(0.0, 0.0, 0.0, 0.0))
+                            .with_store_op(StoreOp::Store),
+                    );
+                }
+                let targets = targets.with_depth_stencil(
+                    DepthStencilAttachment::new(RenderTarget::from_texture(Arc::clone(
+                        &view.depth,
+                    )))
+                    .with_clear_depth(1.0)
+                    .with_depth_store_op(StoreOp::Store),
+                );
+                let mut geometry = GraphicsPass::new(format!("{prefix}gbuffer_{cam_idx}"));
+                geometry.set_render_targets(targets);
+                let draws = queue_pass_draws(
+                    &view,
+                    RenderPassType::Deferred,
+                    None,
+                    &meshes,
+                    &materials,
+                    &per_entity,
+                    &visibilities,
+                    &globals,
+                );
+                batcher.add_draws(&mut geometry, draws, schedule);
+
+                // 2. Lighting resolve into the color target.
+                let [r, g, b, a] = view.clear_color;
+                let mut resolve = GraphicsPass::new(format!("{prefix}deferred_resolve_{cam_idx}"));
+                resolve.set_render_targets(
+                    RenderTargetConfig::new().with_color(
+                        ColorAttachment::from_texture(Arc::clone(&view.color))
+                            .with_load_op(LoadOp::clear_color(r, g, b, a))
+                            .with_store_op(StoreOp::Store),
+                    ),
+                );
+                if let Some(lighting) = &view.lighting
+                    && let Some((mesh, instance)) =
+                        renderer.resolve_draw(cam_idx, gbuffer, view.color.format(), lighting)
+                {
+                    resolve.add_draw(mesh, instance);
+                }
+
+                // 3. Forward-only entities and the skybox over the result.
+                let draws = queue_pass_draws(
+                    &view,
+                    RenderPassType::Forward,
+                    Some(RenderPassType::Deferred),
+                    &meshes,
+                    &materials,
+                    &per_entity,
+                    &visibilities,
+                    &globals,
+                );
+                let mut forward = GraphicsPass::new(format!("{prefix}forward_{cam_idx}"));
+                forward.set_render_targets(
+                    RenderTargetConfig::new()
+                        .with_color(
+                            ColorAttachment::from_texture(Arc::clone(&view.color))
+                                .with_load_op(LoadOp::Load)
+                                .with_store_op(StoreOp::Store),
+                        )
+                        .with_depth_stencil(
+                            DepthStencilAttachment::new(RenderTarget::from_texture(view.depth))
+                                .with_depth_load_op(LoadOp::Load)
+                                .with_depth_store_op(StoreOp::DontCare),
+                        ),
+                );
+                batcher.add_draws(&mut forward, draws, schedule);
+                if let Some((mesh, instance)) = view.skybox {
+                    forward.add_draw(mesh, instance);
+                }
+
+                let mut graph = schedule.acquire_graph();
+                let geometry = graph.add_graphics_pass(geometry);
+                let resolve = graph.add_graphics_pass(resolve);
+                let forward = graph.add_graphics_pass(forward);
+                graph.add_dependency(resolve, geometry);
+                graph.add_dependency(forward, resolve);
+                add_post_passes(&mut graph, forward, view.post_passes);
+                schedule.submit(format!("{prefix}camera_{cam_idx}"), graph, &wait_for);
+            }
+        },
+    );
+}
@@ -2,110 +2,96 @@
 
 use std::sync::Arc;
 
+use redlilium_core::math::{Frustum, Mat4};
 use redlilium_graphics::{
-    ColorAttachment, DepthStencilAttachment, GraphicsPass, LoadOp, RenderTarget,
-    RenderTargetConfig, StoreOp,
+    BindingGroup, ColorAttachment, DepthStencilAttachment, GraphicsPass, LoadOp, MaterialInstance,
+    Mesh, PassHandle, RenderGraph, RenderTarget, RenderTargetConfig, StoreOp, Texture,
 };
 
-use crate::SystemContext;
 use crate::std::components::{Camera, GlobalTransform, Visibility};
 use crate::std::rendering::components::{
-    CameraTarget, PerEntityBuffers, RenderMaterial, RenderMesh, RenderPassType,
+    CameraTarget, GBuffer, PerEntityBuffers, PostProcessStack, RenderMaterial, RenderMesh,
+    RenderPassType, Skybox,
 };
-use crate::std::rendering::resources::RenderSchedule;
+use crate::std::rendering::resources::{
+    DrawBatcher, PostProcessRenderer, QueuedDraw, RenderSchedule, SceneLighting, SpatialIndex,
+};
+use crate::std::rendering::shaders::LIGHTING_BINDING_GROUP;
+use crate::{Ref, SystemContext};
 
 /// Simple forward render system.
 ///
 /// Collects all visible entities with [`RenderMesh`] + [`RenderMaterial`] and,
 /// for each camera that has a [`CameraTarget`], builds a render graph with a
 /// single forward graphics pass and submits it to the [`RenderSchedule`].
+/// Camera graphs wait for the schedule's
+/// [`dependencies`](RenderSchedule::dependencies), such as shadow maps.
+///
+/// Entities are frustum-culled per camera with the [`SpatialIndex`]
+/// resource, which must exist; run
+/// [`UpdateSpatialIndex`](crate::UpdateSpatialIndex) first to fill it. The
+/// visible entities are batched and ordered by the [`DrawBatcher`]
+/// resource, which must exist as well: opaque, then alpha-tested, then
+/// blended entities (see [`RenderQueue`](crate::RenderQueue)), the last
+/// sorted back-to-front.
+///
+/// Materials that declare the scene lighting group (see
+/// [`shaders::pbr`](crate::shaders::pbr)) get the camera's
+/// [`SceneLighting`] binding group appended, so the resource must exist;
+/// run [`GatherLights`](crate::GatherLights) first to fill it. A camera's
+/// [`Skybox`] is drawn after all entities.
+///
+/// A camera's [`PostProcessStack`] runs after the forward pass, with passes
+/// built by the [`PostProcessRenderer`] resource, which must exist.
+///
+/// [`insert_render_resources`](crate::insert_render_resources) inserts all
+/// four resources.
+///
+/// Cameras whose target has a [`GBuffer`] are skipped; they are rendered by
+/// the [`DeferredRenderSystem`](crate::DeferredRenderSystem).
 pub struct ForwardRenderSystem;
 
 impl crate::System for ForwardRenderSystem {
     type Result = ();
 
     fn run<'a>(&'a self, ctx: &'a SystemContext<'a>) -> Result<(), crate::system::SystemError> {
-        ctx.lock::<(
-            crate::Read<Camera>,
-            crate::Read<GlobalTransform>,
-            crate::Read<CameraTarget>,
-            crate::Read<RenderMesh>,
-            crate::Read<RenderMaterial>,
-            crate::Read<PerEntityBuffers>,
-            crate::Read<Visibility>,
-            crate::ResMut<RenderSchedule>,
-        )>()
-        .execute(
-            |(
-                cameras,
-                globals,
-                targets,
-                meshes,
-                materials,
-                per_entity,
-                visibilities,
-                mut schedule_res,
-            )| {
-                let Some(schedule) = schedule_res.schedule_mut() else {
-                    return;
-                };
-
-                for (cam_idx, _camera) in cameras.iter() {
-                    let Some(target) = targets.get(cam_idx) else {
-                        continue;
-                    };
-                    let Some(_cam_global) = globals.get(cam_idx) else {
-                        continue;
-                    };
-
-                    let render_target_config = RenderTargetConfig::new()
-                        .with_color(
-                            ColorAttachment::new(RenderTarget::from_texture(Arc::clone(
-                                &target.color,
-                            )))
-                            .with_load_op(LoadOp::clear_color(
-                                target.clear_color[0],
-                                target.clear_color[1],
-                                target.clear_color[2],
-                                target.clear_color[3],
-                            ))
-                            .with_store_op(StoreOp::Store),
-                        )
-                        .with_depth_stencil(
-                            DepthStencilAttachment::new(RenderTarget::from_texture(Arc::clone(
-                                &target.depth,
-                            )))
-                            .with_clear_depth(1.0)
-                            .with_depth_store_op(StoreOp::DontCare),
-                        );
-
-                    let mut pass = GraphicsPass::new(format!("forward_{cam_idx}"));
-                    pass.set_render_targets(render_target_config);
-
-                    for (entity_idx, render_mesh) in meshes.iter() {
-                        if !per_entity.contains(entity_idx) {
-                            continue;
-                        }
-                        let Some(render_material) = materials.get(entity_idx) else {
-                            continue;
-                        };
-                        if let Some(vis) = visibilities.get(entity_idx)
-                            && !vis.is_visible()
-                        {
-                            continue;
-                        }
-
-                        if let Some(instance) = render_material.pass(RenderPassType::Forward) {
-                            pass.add_draw(Arc::clone(&render_mesh.mesh), Arc::clone(instance));
-                        }
-                    }
-
-                    let mut graph = schedule.acquire_graph();
-                    graph.add_graphics_pass(pass);
-                    schedule.submit(format!("camera_{cam_idx}"), graph, &[]);
-                }
-            },
-        );
+        let views = ctx
+            .lock::<(
+                crate::Read<Camera>,
+                crate::Read<GlobalTransform>,
+                crate::Read<CameraTarget>,
+                crate::Read<Skybox>,
+                crate::Read<PostProcessStack>,
+                crate::ResMut<SceneLighting>,
+                crate::ResMut<SpatialIndex>,
+                crate::ResMut<PostProcessRenderer>,
+            )>()
+            .execute(
+                |(
+                    cameras,
+                    globals,
+                    targets,
+                    skyboxes,
+                    stacks,
+                    mut lighting,
+                    mut spatial,
+                    mut post,
+                )| {
+                    let mut views = collect_camera_views(
+                        &cameras,
+                        &globals,
+                        &targets,
+                        &skyboxes,
+                        &mut lighting,
+                        &mut spatial,
+                        false,
+                    );
+                    build_post_passes(&mut views, &stacks, &mut post, "");
+                    views
+                },
+            );
+
+        submit_forward_passes(ctx, views, "");
         Ok(())
     }
 }
@@ -120,87 +106,271 @@ impl crate::System for EditorForwardRenderSystem {
     type Result = ();
 
     fn run<'a>(&'a self, ctx: &'a SystemContext<'a>) -> Result<(), crate::system::SystemError> {
-        ctx.lock::<(
-            crate::ReadAll<Camera>,
-            crate::ReadAll<GlobalTransform>,
-            crate::ReadAll<CameraTarget>,
-            crate::Read<RenderMesh>,
-            crate::Read<RenderMaterial>,
-            crate::Read<PerEntityBuffers>,
-            crate::Read<Visibility>,
-            crate::ResMut<RenderSchedule>,
-        )>()
-        .execute(
-            |(
-                cameras,
-                globals,
-                targets,
-                meshes,
-                materials,
-                per_entity,
-                visibilities,
-                mut schedule_res,
-            )| {
-                let Some(schedule) = schedule_res.schedule_mut() else {
-                    return;
-                };
-
-                for (cam_idx, _camera) in cameras.iter() {
-                    let Some(target) = targets.get(cam_idx) else {
-                        continue;
-                    };
-                    let Some(_cam_global) = globals.get(cam_idx) else {
-                        continue;
-                    };
-
-                    let render_target_config = RenderTargetConfig::new()
-                        .with_color(
-                            ColorAttachment::new(RenderTarget::from_texture(Arc::clone(
-                                &target.color,
-                            )))
-                            .with_load_op(LoadOp::clear_color(
-                                target.clear_color[0],
-                                target.clear_color[1],
-                                target.clear_color[2],
-                                target.clear_color[3],
-                            ))
+        let views = ctx
+            .lock::<(
+                crate::ReadAll<Camera>,
+                crate::ReadAll<GlobalTransform>,
+                crate::ReadAll<CameraTarget>,
+                crate::ReadAll<Skybox>,
+                crate::ReadAll<PostProcessStack>,
+                crate::ResMut<SceneLighting>,
+                crate::ResMut<SpatialIndex>,
+                crate::ResMut<PostProcessRenderer>,
+            )>()
+            .execute(
+                |(
+                    cameras,
+                    globals,
+                    targets,
+                    skyboxes,
+                    stacks,
+                    mut lighting,
+                    mut spatial,
+                    mut post,
+                )| {
+                    let mut views = collect_camera_views(
+                        &cameras,
+                        &globals,
+                        &targets,
+                        &skyboxes,
+                        &mut lighting,
+                        &mut spatial,
+                        false,
+                    );
+                    build_post_passes(&mut views, &stacks, &mut post, "editor_");
+                    views
+                },
+            );
+
+        submit_forward_passes(ctx, views, "editor_");
+        Ok(())
+    }
+}
+
+/// A camera to render this frame.
+pub(super) struct CameraView {
+    pub entity: u32,
+    pub color: Arc<Texture>,
+    pub depth: Arc<Texture>,
+    pub clear_color: [f32; 4],
+    pub view_matrix: Mat4,
+    /// Entities inside the camera's frustum.
+    pub visible: Vec<u32>,
+    pub gbuffer: Option<GBuffer>,
+    pub lighting: Option<Arc<BindingGroup>>,
+    pub skybox: Option<(Arc<Mesh>, Arc<MaterialInstance>)>,
+    /// Passes of the camera's [`PostProcessStack`], in execution order.
+    pub post_passes: Vec<GraphicsPass>,
+}
+
+/// Collect the cameras rendered with the deferred path if `deferred` is
+/// set, or with the forward path otherwise, with their culled entities.
+pub(super) fn collect_camera_views(
+    cameras: &Ref<Camera>,
+    globals: &Ref<GlobalTransform>,
+    targets: &Ref<CameraTarget>,
+    skyboxes: &Ref<Skybox>,
+    lighting: &mut SceneLighting,
+    spatial: &mut SpatialIndex,
+    deferred: bool,
+) -> Vec<CameraView> {
+    let mut views = Vec::new();
+    for (cam_idx, camera) in cameras.iter() {
+        let Some(target) = targets.get(cam_idx).filter(|t| t.is_deferred() == deferred) else {
+            continue;
+        };
+        let Some(cam_global) = globals.get(cam_idx) else {
+            continue;
+        };
+        let skybox = skyboxes.get(cam_idx).and_then(|skybox| {
+            lighting.skybox_draw(
+                cam_idx,
+                camera,
+                cam_global,
+                skybox,
+                target.color.format(),
+                target.depth.format(),
+            )
+        });
+        views.push(CameraView {
+            entity: cam_idx,
+            color: Arc::clone(&target.color),
+            depth: Arc::clone(&target.depth),
+            clear_color: target.clear_color,
+            view_matrix: camera.view_matrix,
+            visible: spatial.cull_camera(
+                cam_idx,
+                &Frustum::from_view_projection(&camera.view_projection()),
+            ),
+            gbuffer: target.gbuffer.clone(),
+            lighting: lighting.view_binding(cam_idx, camera, cam_global),
+            skybox,
+            post_passes: Vec::new(),
+        });
+    }
+    lighting.retain_views(|camera| targets.contains(camera));
+    views
+}
+
+/// Build the post-processing passes of the views whose camera has a
+/// [`PostProcessStack`].
+pub(super) fn build_post_passes(
+    views: &mut [CameraView],
+    stacks: &Ref<PostProcessStack>,
+    renderer: &mut PostProcessRenderer,
+    prefix: &str,
+) {
+    for view in views.iter_mut() {
+        if let Some(stack) = stacks.get(view.entity) {
+            view.post_passes = renderer.build_passes(view.entity, &view.color, stack, prefix);
+        }
+    }
+    renderer.retain_cameras(|camera| stacks.contains(camera));
+}
+
+/// Add `passes` to `graph`, each running after the previous one and the
+/// first after `after`.
+pub(super) fn add_post_passes(
+    graph: &mut RenderGraph,
+    after: PassHandle,
+    passes: Vec<GraphicsPass>,
+) {
+    let mut previous = after;
+    for pass in passes {
+        let handle = graph.add_graphics_pass(pass);
+        graph.add_dependency(handle, previous);
+        previous = handle;
+    }
+}
+
+fn submit_forward_passes(ctx: &SystemContext<'_>, views: Vec<CameraView>, prefix: &str) {
+    if views.is_empty() {
+        return;
+    }
+    ctx.lock::<(
+        crate::Read<RenderMesh>,
+        crate::Read<RenderMaterial>,
+        crate::Read<PerEntityBuffers>,
+        crate::Read<Visibility>,
+        crate::Read<GlobalTransform>,
+        crate::ResMut<RenderSchedule>,
+        crate::ResMut<DrawBatcher>,
+    )>()
+    .execute(
+        |(meshes, materials, per_entity, visibilities, globals, mut schedule_res, mut batcher)| {
+            let wait_for = schedule_res.dependencies().to_vec();
+            let Some(schedule) = schedule_res.schedule_mut() else {
+                return;
+            };
+
+            for view in views {
+                let cam_idx = view.entity;
+                let draws = queue_pass_draws(
+                    &view,
+                    RenderPassType::Forward,
+                    None,
+                    &meshes,
+                    &materials,
+                    &per_entity,
+                    &visibilities,
+                    &globals,
+                );
+                let [r, g, b, a] = view.clear_color;
+                let render_target_config = RenderTargetConfig::new()
+                    .with_color(
+                        ColorAttachment::new(RenderTarget::from_texture(view.color))
+                            .with_load_op(LoadOp::clear_color(r, g, b, a))
                             .with_store_op(StoreOp::Store),
-                        )
-                        .with_depth_stencil(
-                            DepthStencilAttachment::new(RenderTarget::from_texture(Arc::clone(
-                                &target.depth,
-                            )))
+                    )
+                    .with_depth_stencil(
+                        DepthStencilAttachment::new(RenderTarget::from_texture(view.depth))
                             .with_clear_depth(1.0)
                             .with_depth_store_op(StoreOp::DontCare),
-                        );
-
-                    let mut pass = GraphicsPass::new(format!("editor_forward_{cam_idx}"));
-                    pass.set_render_targets(render_target_config);
-
-                    for (entity_idx, render_mesh) in meshes.iter() {
-                        if !per_entity.contains(entity_idx) {
-                            continue;
-                        }
-                        let Some(render_material) = materials.get(entity_idx) else {
-                            continue;
-                        };
-                        if let Some(vis) = visibilities.get(entity_idx)
-                            && !vis.is_visible()
-                        {
-                            continue;
-                        }
-
-                        if let Some(instance) = render_material.pass(RenderPassType::Forward) {
-                            pass.add_draw(Arc::clone(&render_mesh.mesh), Arc::clone(instance));
-                        }
-                    }
-
-                    let mut graph = schedule.acquire_graph();
-                    graph.add_graphics_pass(pass);
-                    schedule.submit(format!("editor_camera_{cam_idx}"), graph, &[]);
+                    );
+
+                let mut pass = GraphicsPass::new(format!("{prefix}forward_{cam_idx}"));
+                pass.set_render_targets(render_target_config);
+                batcher.add_draws(&mut pass, draws, schedule);
+                if let Some((mesh, instance)) = view.skybox {
+                    pass.add_draw(mesh, instance);
                 }
-            },
-        );
-        Ok(())
+
+                let mut graph = schedule.acquire_graph();
+                let forward = graph.add_graphics_pass(pass);
+                add_post_passes(&mut graph, forward, view.post_passes);
+                schedule.submit(format!("{prefix}camera_{cam_idx}"), graph, &wait_for);
+            }
+        },
+    );
+}
+
+/// Queue a draw for every visible entity culled for `view` with a
+/// `pass_type` material instance, skipping entities that also have an
+/// `except` instance, for the [`DrawBatcher`].
+#[allow(clippy::too_many_arguments)]
+pub(super) fn queue_pass_draws(
+    view: &CameraView,
+    pass_type: RenderPassType,
+    except: Option<RenderPassType>,
+    meshes: &Ref<RenderMesh>,
+    materials: &Ref<RenderMaterial>,
+    per_entity: &Ref<PerEntityBuffers>,
+    visibilities: &Ref<Visibility>,
+    globals: &Ref<GlobalTransform>,
+) -> Vec<QueuedDraw> {
+    let mut draws = Vec::with_capacity(view.visible.len());
+    for &entity_idx in &view.visible {
+        let Some(render_mesh) = meshes.get(entity_idx) else {
+            continue;
+        };
+        if !per_entity.contains(entity_idx) {
+            continue;
+        }
+        let Some(render_material) = materials.get(entity_idx) else {
+            continue;
+        };
+        if let Some(vis) = visibilities.get(entity_idx)
+            && !vis.is_visible()
+        {
+            continue;
+        }
+        if except.is_some_and(|except| render_material.pass(except).is_some()) {
+            continue;
+        }
+
+        if let Some(instance) = render_material.pass(pass_type) {
+            let instance = match &view.lighting {
+                Some(lighting) => lit_instance(instance, lighting),
+                None => Arc::clone(instance),
+            };
+            let model = globals.get(entity_idx).map_or_else(Mat4::identity, |g| g.0);
+            let depth = -(view.view_matrix * model.column(3)).z;
+            draws.push(QueuedDraw {
+                mesh: Arc::clone(&render_mesh.mesh),
+                instance,
+                model,
+                depth,
+                queue: render_material.queue(pass_type),
+            });
+        }
+    }
+    draws
+}
+
+/// Append the camera's lighting group to instances whose material expects it.
+fn lit_instance(
+    instance: &Arc<MaterialInstance>,
+    lighting: &Arc<BindingGroup>,
+) -> Arc<MaterialInstance> {
+    let layouts = instance.material().binding_layouts().len();
+    if layouts != LIGHTING_BINDING_GROUP + 1
+        || instance.binding_groups().len() != LIGHTING_BINDING_GROUP
+    {
+        return Arc::clone(instance);
     }
+    let mut groups = instance.binding_groups().to_vec();
+    groups.push(Arc::clone(lighting));
+    let mut lit = MaterialInstance::new(Arc::clone(instance.material()));
+    lit.set_binding_groups(groups);
+    Arc::new(lit)
 }
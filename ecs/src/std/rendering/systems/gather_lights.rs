@@ -0,0 +1,312 @@
+//! Light gathering system.
+
+use std::sync::Arc;
+
+use crate::std::components::{
+    DirectionalLight, GlobalTransform, PointLight, ShadowSettings, SpotLight, Visibility,
+};
+use crate::std::rendering::resources::{
+    GpuLight, GpuShadowView, MAX_SHADOW_ATLASES, RenderSchedule, SceneLighting, ShadowMaps,
+    ShadowView,
+};
+use crate::{Ref, SystemContext};
+
+/// Packs every visible light into the [`SceneLighting`] resource.
+///
+/// Lights whose shadow map was rendered this frame by the
+/// [`ShadowRenderSystem`](crate::ShadowRenderSystem) reference its atlas and
+/// views; up to [`MAX_SHADOW_ATLASES`] shadowed lights are supported and
+/// the rest are lit without shadows. Must run after the shadow render
+/// system and before the forward and deferred render systems.
+///
+/// Also submits environment precomputation queued with
+/// [`SceneLighting::precompute_environment`] and adds it to the
+/// [`RenderSchedule`] dependencies, so camera graphs sample finished maps.
+///
+/// # Access
+///
+/// - Reads: `GlobalTransform`, `Visibility`, lights
+/// - Resources: `Res<ShadowMaps>`, `ResMut<SceneLighting>`,
+///   `ResMut<RenderSchedule>`
+pub struct GatherLights;
+
+impl crate::System for GatherLights {
+    type Result = ();
+
+    fn run<'a>(&'a self, ctx: &'a SystemContext<'a>) -> Result<(), crate::system::SystemError> {
+        ctx.lock::<(
+            crate::Read<GlobalTransform>,
+            crate::Read<Visibility>,
+            crate::Read<DirectionalLight>,
+            crate::Read<PointLight>,
+            crate::Read<SpotLight>,
+            crate::Res<ShadowMaps>,
+            crate::ResMut<SceneLighting>,
+            crate::ResMut<RenderSchedule>,
+        )>()
+        .execute(
+            |(
+                globals,
+                visibilities,
+                directional,
+                point,
+                spot,
+                shadow_maps,
+                mut lighting,
+                mut schedule,
+            )| {
+                redlilium_core::profile_scope!("gather_lights");
+
+                if lighting.has_pending_environment()
+                    && let Some(frame) = schedule.schedule_mut()
+                    && let Some(handle) = lighting.submit_pending_environment(frame)
+                {
+                    schedule.add_dependency(handle);
+                }
+
+                let (mut lights, entities) =
+                    pack_lights(&globals, &visibilities, &directional, &point, &spot);
+
+                let mut shadow_views = Vec::new();
+                let mut atlases = Vec::new();
+                for (light, &entity) in lights.iter_mut().zip(&entities) {
+                    if atlases.len() >= MAX_SHADOW_ATLASES {
+                        break;
+                    }
+                    let Some(map) = shadow_maps.get(entity) else {
+                        continue;
+                    };
+                    if map.graph().is_none() || map.views().is_empty() {
+                        continue;
+                    }
+                    attach_shadow(
+                        light,
+                        atlases.len() as i32,
+                        shadow_views.len() as u32,
+                        map.views(),
+                        map.cascade_splits(),
+                        map.settings(),
+                    );
+                    shadow_views.extend(map.views().iter().map(GpuShadowView::from));
+                    atlases.push(Arc::clone(map.texture()));
+                }
+
+                lighting.upload(&lights, &shadow_views, atlases);
+            },
+        );
+        Ok(())
+    }
+}
+
+/// Pack visible lights into GPU form.
+///
+/// Returns the lights and, in the same order, their entity indices.
+pub(crate) fn pack_lights(
+    globals: &Ref<GlobalTransform>,
+    visibilities: &Ref<Visibility>,
+    directional: &Ref<DirectionalLight>,
+    point: &Ref<PointLight>,
+    spot: &Ref<SpotLight>,
+) -> (Vec<GpuLight>, Vec<u32>) {
+    let mut lights = Vec::new();
+    let mut entities = Vec::new();
+    let visible = |entity: u32| visibilities.get(entity).is_none_or(|v| v.is_visible());
+
+    let base = |global: &GlobalTransform, kind: u32| {
+        let position = global.translation();
+        let direction = global.forward().normalize();
+        GpuLight {
+            position: [position.x, position.y, position.z],
+            range: 0.0,
+            direction: [direction.x, direction.y, direction.z],
+            kind,
+            color: [1.0; 3],
+            intensity: 0.0,
+            spot_scale: 0.0,
+            spot_offset: 0.0,
+            shadow_atlas: -1,
+            first_shadow_view: 0,
+            shadow_view_count: 0,
+            depth_bias: 0.0,
+            normal_bias: 0.0,
+            pcf_radius: 0.0,
+            cascade_splits: [0.0; 4],
+        }
+    };
+
+    for (entity, light) in directional.iter() {
+        let Some(global) = globals.get(entity).filter(|_| visible(entity)) else {
+            continue;
+        };
+        lights.push(GpuLight {
+            color: light.color.into(),
+            intensity: light.intensity,
+            ..base(global, GpuLight::DIRECTIONAL)
+        });
+        entities.push(entity);
+    }
+    for (entity, light) in point.iter() {
+        let Some(global) = globals.get(entity).filter(|_| visible(entity)) else {
+            continue;
+        };
+        lights.push(GpuLight {
+            color: light.color.into(),
+            intensity: light.intensity,
+            range: light.range,
+            ..base(global, GpuLight::POINT)
+        });
+        entities.push(entity);
+    }
+    for (entity, light) in spot.iter() {
+        let Some(global) = globals.get(entity).filter(|_| visible(entity)) else {
+            continue;
+        };
+        let cos_inner = light.inner_cone_angle.cos();
+        let cos_outer = light.outer_cone_angle.cos();
+        let spot_scale = 1.0 / (cos_inner - cos_outer).max(0.001);
+        lights.push(GpuLight {
+            color: light.color.into(),
+            intensity: light.intensity,
+            range: light.range,
+            spot_scale,
+            spot_offset: -cos_outer * spot_scale,
+            ..base(global, GpuLight::SPOT)
+        });
+        entities.push(entity);
+    }
+    (lights, entities)
+}
+
+/// Point `light` at its shadow map in atlas slot `atlas`, whose views start
+/// at `first_view` in the shadow view buffer.
+pub(crate) fn attach_shadow(
+    light: &mut GpuLight,
+    atlas: i32,
+    first_view: u32,
+    views: &[ShadowView],
+    cascade_splits: &[f32],
+    settings: &ShadowSettings,
+) {
+    light.shadow_atlas = atlas;
+    light.first_shadow_view = first_view;
+    light.shadow_view_count = views.len() as u32;
+    light.depth_bias = settings.depth_bias;
+    light.normal_bias = settings.normal_bias;
+    light.pcf_radius = settings.pcf_radius;
+    for (split, &distance) in light.cascade_splits.iter_mut().zip(cascade_splits) {
+        *split = distance;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::World;
+    use redlilium_core::math::{Mat4, Vec3, mat4_from_translation};
+
+    #[test]
+    fn packs_only_visible_lights() {
+        let mut world = World::new();
+        world.register_component::<GlobalTransform>();
+        world.register_component::<Visibility>();
+        world.register_component::<DirectionalLight>();
+        world.register_component::<PointLight>();
+        world.register_component::<SpotLight>();
+
+        let sun = world.spawn();
+        world.insert(sun, GlobalTransform::IDENTITY).unwrap();
+        world
+            .insert(sun, DirectionalLight::new(Vec3::new(1.0, 0.9, 0.8), 3.0))
+            .unwrap();
+
+        let lamp = world.spawn();
+        world
+            .insert(
+                lamp,
+                GlobalTransform(mat4_from_translation(Vec3::new(0.0, 3.0, 0.0))),
+            )
+            .unwrap();
+        world
+            .insert(lamp, PointLight::default().with_range(8.0))
+            .unwrap();
+
+        let hidden = world.spawn();
+        world.insert(hidden, GlobalTransform::IDENTITY).unwrap();
+        world.insert(hidden, Visibility::HIDDEN).unwrap();
+        world.insert(hidden, SpotLight::default()).unwrap();
+
+        let (lights, entities) = pack_lights(
+            &world.read::<GlobalTransform>().unwrap(),
+            &world.read::<Visibility>().unwrap(),
+            &world.read::<DirectionalLight>().unwrap(),
+            &world.read::<PointLight>().unwrap(),
+            &world.read::<SpotLight>().unwrap(),
+        );
+
+        assert_eq!(entities, vec![sun.index(), lamp.index()]);
+        assert_eq!(lights[0].kind, GpuLight::DIRECTIONAL);
+        assert_eq!(lights[0].color, [1.0, 0.9, 0.8]);
+        assert_eq!(lights[0].direction, [0.0, 0.0, -1.0]);
+        assert_eq!(lights[0].shadow_atlas, -1);
+        assert_eq!(lights[1].kind, GpuLight::POINT);
+        assert_eq!(lights[1].position, [0.0, 3.0, 0.0]);
+        assert_eq!(lights[1].range, 8.0);
+    }
+
+    #[test]
+    fn spot_cone_fades_between_inner_and_outer() {
+        let mut world = World::new();
+        world.register_component::<GlobalTransform>();
+        world.register_component::<Visibility>();
+        world.register_component::<DirectionalLight>();
+        world.register_component::<PointLight>();
+        world.register_component::<SpotLight>();
+
+        let spot = world.spawn();
+        world.insert(spot, GlobalTransform::IDENTITY).unwrap();
+        world
+            .insert(
+                spot,
+                SpotLight::new(Vec3::new(1.0, 1.0, 1.0), 10.0, 0.2, 0.5),
+            )
+            .unwrap();
+
+        let (lights, _) = pack_lights(
+            &world.read::<GlobalTransform>().unwrap(),
+            &world.read::<Visibility>().unwrap(),
+            &world.read::<DirectionalLight>().unwrap(),
+            &world.read::<PointLight>().unwrap(),
+            &world.read::<SpotLight>().unwrap(),
+        );
+        let light = lights[0];
+        assert_eq!(light.kind, GpuLight::SPOT);
+        let cone =
+            |angle: f32| (angle.cos() * light.spot_scale + light.spot_offset).clamp(0.0, 1.0);
+        assert!((cone(0.2) - 1.0).abs() < 1e-4);
+        assert!(cone(0.5).abs() < 1e-4);
+        assert!(cone(0.35) > 0.0 && cone(0.35) < 1.0);
+    }
+
+    #[test]
+    fn attach_shadow_references_atlas_and_views() {
+        let mut light = GpuLight {
+            kind: GpuLight::DIRECTIONAL,
+            shadow_atlas: -1,
+            ..bytemuck::Zeroable::zeroed()
+        };
+        let view = ShadowView {
+            view_projection: Mat4::identity(),
+            atlas_rect: [0.0, 0.0, 0.5, 0.5],
+            texel_world_size: 0.1,
+        };
+        let settings = ShadowSettings::default().with_pcf_radius(2.0);
+        attach_shadow(&mut light, 1, 6, &[view, view], &[10.0, 40.0], &settings);
+
+        assert_eq!(light.shadow_atlas, 1);
+        assert_eq!(light.first_shadow_view, 6);
+        assert_eq!(light.shadow_view_count, 2);
+        assert_eq!(light.cascade_splits, [10.0, 40.0, 0.0, 0.0]);
+        assert_eq!(light.pcf_radius, 2.0);
+        assert_eq!(light.depth_bias, settings.depth_bias);
+    }
+}
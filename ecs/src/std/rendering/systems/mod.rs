@@ -1,11 +1,25 @@
 //! Rendering ECS systems.
 
+mod deferred_render;
 mod forward_render;
+mod gather_lights;
 mod initialize_entities;
+mod reload_shaders;
+mod shadow_render;
 mod sync_materials;
+mod sync_prefabs;
+mod update_assets;
+mod update_spatial_index;
 mod update_uniforms;
 
+pub use deferred_render::{DeferredRenderSystem, EditorDeferredRenderSystem};
 pub use forward_render::{EditorForwardRenderSystem, ForwardRenderSystem};
+pub use gather_lights::GatherLights;
 pub use initialize_entities::InitializeRenderEntities;
+pub use reload_shaders::ReloadMaterialShaders;
+pub use shadow_render::ShadowRenderSystem;
 pub use sync_materials::SyncMaterialUniforms;
+pub use sync_prefabs::SyncPrefabInstances;
+pub use update_assets::UpdateAssetServer;
+pub use update_spatial_index::UpdateSpatialIndex;
 pub use update_uniforms::UpdatePerEntityUniforms;
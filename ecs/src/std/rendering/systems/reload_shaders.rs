@@ -0,0 +1,69 @@
+//! Material shader hot reload system.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::std::rendering::components::{MaterialBundle, RenderMaterial};
+use crate::std::rendering::resources::MaterialManager;
+
+/// Recompiles materials whose shader asset was hot reloaded and switches
+/// [`RenderMaterial`] components over to the new pipelines.
+///
+/// Materials opt in through [`MaterialManager::set_material_shader`], or for
+/// the standard shaders through
+/// [`watch_standard_shaders`](crate::std::rendering::shaders::watch_standard_shaders). A shader
+/// that fails to compile leaves the previous pipeline in use. Schedule this
+/// after [`UpdateAssetServer`](super::UpdateAssetServer) so that reloads are
+/// picked up in the same frame.
+pub struct ReloadMaterialShaders;
+
+impl crate::System for ReloadMaterialShaders {
+    type Result = ();
+
+    fn run<'a>(
+        &'a self,
+        ctx: &'a crate::SystemContext<'a>,
+    ) -> Result<(), crate::system::SystemError> {
+        ctx.lock::<(
+            crate::ResMut<MaterialManager>,
+            crate::WriteAll<RenderMaterial>,
+        )>()
+        .execute(|(mut manager, mut materials)| {
+            let replaced = manager.reload_shaders();
+            if replaced.is_empty() {
+                return;
+            }
+
+            // Bundles are often shared between entities; rebuild each once
+            let mut rebuilt: HashMap<usize, Option<Arc<MaterialBundle>>> = HashMap::new();
+            let indices: Vec<u32> = materials.iter().map(|(idx, _)| idx).collect();
+            for idx in indices {
+                let Some(bundle) = materials.get(idx).map(|m| Arc::clone(m.bundle())) else {
+                    continue;
+                };
+                let new_bundle = rebuilt
+                    .entry(Arc::as_ptr(&bundle) as usize)
+                    .or_insert_with(|| {
+                        let mut current: Option<MaterialBundle> = None;
+                        for (old, new) in &replaced {
+                            let source = current.as_ref().unwrap_or(&bundle);
+                            if let Some(next) = source.with_material_replaced(old, new) {
+                                current = Some(next);
+                            }
+                        }
+                        let new_bundle = Arc::new(current?);
+                        manager.replace_bundle(&bundle, &new_bundle);
+                        Some(new_bundle)
+                    })
+                    .clone();
+
+                if let Some(new_bundle) = new_bundle
+                    && let Some(mut material) = materials.get_mut(idx)
+                {
+                    material.set_bundle(new_bundle, None, None);
+                }
+            }
+        });
+        Ok(())
+    }
+}
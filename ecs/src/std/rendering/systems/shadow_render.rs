@@ -0,0 +1,614 @@
+//! Shadow map rendering system.
+
+use std::sync::Arc;
+
+use redlilium_core::math::{Mat4, Vec3, Vec4, look_at_rh, orthographic_rh, perspective_rh};
+use redlilium_graphics::{
+    BindingGroup, DepthStencilAttachment, GraphicsPass, LoadOp, Material, MaterialInstance, Mesh,
+    RenderTarget, RenderTargetConfig, StoreOp,
+};
+
+use crate::std::components::{
+    Camera, DirectionalLight, GlobalTransform, PointLight, ShadowSettings, SpotLight, Visibility,
+};
+use crate::std::rendering::components::{
+    PerEntityBuffers, RenderMaterial, RenderMesh, RenderPassType,
+};
+use crate::std::rendering::resources::{RenderSchedule, ShadowMapKind, ShadowMaps, ShadowView};
+use crate::std::rendering::shaders::shadow_caster_binding;
+use crate::{Ref, SystemContext};
+
+/// Renders shadow maps for lights with [`ShadowSettings`].
+///
+/// - [`DirectionalLight`]: cascaded shadow map fitted to the first camera's
+///   view frustum (split with the practical split scheme, stabilized by
+///   bounding spheres and texel snapping)
+/// - [`PointLight`]: cube shadow map, six 90° faces
+/// - [`SpotLight`]: a single frustum covering the outer cone
+///
+/// Each light's views are rendered into one depth atlas held by the
+/// [`ShadowMaps`] resource, and each map is built as its own graph. The
+/// graphs are recorded as [`RenderSchedule`] dependencies, so this system
+/// must run before the forward render systems.
+///
+/// Every visible entity with [`RenderMesh`] and [`PerEntityBuffers`] casts
+/// shadows. Entities whose material bundle has a
+/// [`Shadow`](RenderPassType::Shadow) pass draw with that instance, with the
+/// shadow view uniforms appended as the last binding group; all others use
+/// the standard [`shadow_depth`](crate::shaders::shadow_depth) material.
+///
+/// # Access
+///
+/// - Reads: `Camera` (all), `GlobalTransform`, lights, `ShadowSettings`,
+///   `RenderMesh`, `RenderMaterial`, `PerEntityBuffers`, `Visibility`
+/// - Resources: `ResMut<ShadowMaps>`, `ResMut<RenderSchedule>`
+pub struct ShadowRenderSystem;
+
+impl crate::System for ShadowRenderSystem {
+    type Result = ();
+
+    fn run<'a>(&'a self, ctx: &'a SystemContext<'a>) -> Result<(), crate::system::SystemError> {
+        let lights = ctx
+            .lock::<(
+                crate::ReadAll<Camera>,
+                crate::Read<GlobalTransform>,
+                crate::Read<DirectionalLight>,
+                crate::Read<PointLight>,
+                crate::Read<SpotLight>,
+                crate::Read<ShadowSettings>,
+            )>()
+            .execute(|(cameras, globals, directional, point, spot, settings)| {
+                let camera = cameras.iter().next().map(|(_, camera)| *camera);
+                collect_shadow_lights(
+                    camera.as_ref(),
+                    &globals,
+                    &directional,
+                    &point,
+                    &spot,
+                    &settings,
+                )
+            });
+
+        ctx.lock::<(
+            crate::Read<RenderMesh>,
+            crate::Read<RenderMaterial>,
+            crate::Read<PerEntityBuffers>,
+            crate::Read<Visibility>,
+            crate::ResMut<ShadowMaps>,
+            crate::ResMut<RenderSchedule>,
+        )>()
+        .execute(
+            |(meshes, materials, per_entity, visibilities, mut shadow_maps, mut schedule_res)| {
+                shadow_maps.retain(|light| lights.iter().any(|l| l.entity == light));
+                if lights.is_empty() || !schedule_res.is_active() {
+                    return;
+                }
+
+                let casters = collect_casters(
+                    &meshes,
+                    &materials,
+                    &per_entity,
+                    &visibilities,
+                    &mut shadow_maps,
+                );
+
+                for light in lights {
+                    let entity = light.entity;
+                    let Some(map) = shadow_maps.prepare(
+                        entity,
+                        light.kind,
+                        light.settings,
+                        light.views,
+                        light.cascade_splits,
+                    ) else {
+                        continue;
+                    };
+                    let Some(schedule) = schedule_res.schedule_mut() else {
+                        return;
+                    };
+
+                    let mut graph = schedule.acquire_graph();
+                    let mut previous = None;
+                    for view_index in 0..map.views().len() {
+                        let Some(view_binding) = map.view_binding(view_index) else {
+                            continue;
+                        };
+                        // The first pass clears the whole atlas; later passes
+                        // keep the tiles already rendered.
+                        let depth = DepthStencilAttachment::new(RenderTarget::from_texture(
+                            Arc::clone(map.texture()),
+                        ))
+                        .with_depth_store_op(StoreOp::Store);
+                        let depth = if previous.is_none() {
+                            depth.with_clear_depth(1.0)
+                        } else {
+                            depth.with_depth_load_op(LoadOp::Load)
+                        };
+
+                        let mut pass = GraphicsPass::new(format!("shadow_{entity}_{view_index}"));
+                        pass.set_render_targets(
+                            RenderTargetConfig::new().with_depth_stencil(depth),
+                        );
+                        pass.set_viewport(map.viewport(view_index));
+                        for caster in &casters {
+                            let mut groups = caster.groups.clone();
+                            groups.push(Arc::clone(view_binding));
+                            let mut instance = MaterialInstance::new(Arc::clone(&caster.material));
+                            instance.set_binding_groups(groups);
+                            pass.add_draw(Arc::clone(&caster.mesh), Arc::new(instance));
+                        }
+
+                        let handle = graph.add_graphics_pass(pass);
+                        if let Some(previous) = previous {
+                            graph.add_dependency(handle, previous);
+                        }
+                        previous = Some(handle);
+                    }
+
+                    let handle = schedule.submit(format!("shadow_{entity}"), graph, &[]);
+                    map.set_graph(handle);
+                    schedule_res.add_dependency(handle);
+                }
+            },
+        );
+        Ok(())
+    }
+}
+
+/// Shadow views of one light, computed on the CPU.
+struct ShadowLight {
+    entity: u32,
+    kind: ShadowMapKind,
+    settings: ShadowSettings,
+    views: Vec<ShadowView>,
+    cascade_splits: Vec<f32>,
+}
+
+/// A shadow caster draw shared by every view.
+struct ShadowCaster {
+    mesh: Arc<Mesh>,
+    material: Arc<Material>,
+    /// Caster binding groups; the view binding group is appended per view.
+    groups: Vec<Arc<BindingGroup>>,
+}
+
+fn collect_shadow_lights(
+    camera: Option<&Camera>,
+    globals: &Ref<GlobalTransform>,
+    directional: &Ref<DirectionalLight>,
+    point: &Ref<PointLight>,
+    spot: &Ref<SpotLight>,
+    settings: &Ref<ShadowSettings>,
+) -> Vec<ShadowLight> {
+    redlilium_core::profile_scope!("collect_shadow_lights");
+
+    let mut lights = Vec::new();
+    for (entity, settings) in settings.iter() {
+        let Some(global) = globals.get(entity) else {
+            continue;
+        };
+        let settings = *settings;
+        let position = global.translation();
+        let range = |range: f32| {
+            if range > 0.0 {
+                range
+            } else {
+                settings.max_distance
+            }
+        };
+
+        let (kind, views, cascade_splits) = if directional.get(entity).is_some() {
+            let Some(camera) = camera else {
+                continue;
+            };
+            let (views, splits) = directional_cascades(camera, global.forward(), &settings);
+            (ShadowMapKind::Directional, views, splits)
+        } else if let Some(light) = spot.get(entity) {
+            let view = spot_light_view(
+                position,
+                global.forward(),
+                light.outer_cone_angle,
+                settings.near,
+                range(light.range),
+                settings.resolution,
+            );
+            (ShadowMapKind::Spot, vec![view], Vec::new())
+        } else if let Some(light) = point.get(entity) {
+            let views = point_light_views(
+                position,
+                settings.near,
+                range(light.range),
+                settings.resolution,
+            );
+            (ShadowMapKind::Point, views, Vec::new())
+        } else {
+            continue;
+        };
+
+        if views.is_empty() {
+            continue;
+        }
+        lights.push(ShadowLight {
+            entity,
+            kind,
+            settings,
+            views,
+            cascade_splits,
+        });
+    }
+    lights
+}
+
+fn collect_casters(
+    meshes: &Ref<RenderMesh>,
+    materials: &Ref<RenderMaterial>,
+    per_entity: &Ref<PerEntityBuffers>,
+    visibilities: &Ref<Visibility>,
+    shadow_maps: &mut ShadowMaps,
+) -> Vec<ShadowCaster> {
+    let mut casters = Vec::new();
+    for (entity_idx, render_mesh) in meshes.iter() {
+        let Some(buffers) = per_entity.get(entity_idx) else {
+            continue;
+        };
+        if let Some(vis) = visibilities.get(entity_idx)
+            && !vis.is_visible()
+        {
+            continue;
+        }
+
+        let custom = materials
+            .get(entity_idx)
+            .and_then(|m| m.pass(RenderPassType::Shadow));
+        let (material, groups) = match custom {
+            Some(instance) => (
+                Arc::clone(instance.material()),
+                instance.binding_groups().to_vec(),
+            ),
+            None => {
+                let skinned = buffers.joint_buffer.is_some();
+                let Some(material) =
+                    shadow_maps.caster_material(render_mesh.mesh.layout(), skinned)
+                else {
+                    continue;
+                };
+                (material, vec![shadow_caster_binding(buffers)])
+            }
+        };
+        casters.push(ShadowCaster {
+            mesh: Arc::clone(&render_mesh.mesh),
+            material,
+            groups,
+        });
+    }
+    casters
+}
+
+/// Far distance of each of `count` cascades between `near` and `far`.
+///
+/// `lambda` blends uniform (0) and logarithmic (1) split positions. The last
+/// split is always `far`.
+pub(crate) fn cascade_splits(near: f32, far: f32, count: u32, lambda: f32) -> Vec<f32> {
+    let near = near.max(f32::EPSILON);
+    (1..=count)
+        .map(|i| {
+            let t = i as f32 / count as f32;
+            let log = near * (far / near).powf(t);
+            let uniform = near + (far - near) * t;
+            lambda * log + (1.0 - lambda) * uniform
+        })
+        .collect()
+}
+
+/// Fit one orthographic view per cascade to slices of `camera`'s frustum.
+///
+/// Returns the cascade views and their far view distances. Each cascade is
+/// fitted to the bounding sphere of its slice and snapped to whole texels, so
+/// shadows do not shimmer as the camera moves or rotates. Casters up to
+/// `max_distance` behind a slice (towards the light) are included.
+pub(crate) fn directional_cascades(
+    camera: &Camera,
+    direction: Vec3,
+    settings: &ShadowSettings,
+) -> (Vec<ShadowView>, Vec<f32>) {
+    let Some(inverse_vp) = camera.view_projection().try_inverse() else {
+        return (Vec::new(), Vec::new());
+    };
+    let direction = direction.normalize();
+    let corners = frustum_corners(&inverse_vp);
+    let view_depth = |p: &Vec3| -transform_point(&camera.view_matrix, p).z;
+    let near = view_depth(&corners[0]);
+    let far = view_depth(&corners[4]);
+    let shadow_far = far.min(settings.max_distance);
+    if shadow_far <= near {
+        return (Vec::new(), Vec::new());
+    }
+
+    let splits = cascade_splits(
+        near,
+        shadow_far,
+        settings.clamped_cascade_count(),
+        settings.cascade_split_lambda,
+    );
+    let resolution = settings.resolution.max(1) as f32;
+    let up = stable_up(direction);
+    let light_rotation = look_at_rh(&Vec3::zeros(), &direction, &up);
+    let inverse_rotation = light_rotation.transpose();
+    let extension = settings.max_distance;
+
+    let mut views = Vec::with_capacity(splits.len());
+    let mut start = near;
+    for &end in &splits {
+        let t0 = (start - near) / (far - near);
+        let t1 = (end - near) / (far - near);
+        let slice: Vec<Vec3> = (0..4)
+            .flat_map(|k| {
+                let ray = corners[k + 4] - corners[k];
+                [corners[k] + ray * t0, corners[k] + ray * t1]
+            })
+            .collect();
+        start = end;
+
+        let center = slice.iter().sum::<Vec3>() / slice.len() as f32;
+        let radius = slice
+            .iter()
+            .map(|p| (p - center).norm())
+            .fold(0.0, f32::max);
+        // Quantize the radius so the projection size only changes in steps
+        let radius = (radius * 16.0).ceil() / 16.0;
+        let texel = 2.0 * radius / resolution;
+
+        let mut light_center = transform_point(&light_rotation, &center);
+        light_center.x = (light_center.x / texel).floor() * texel;
+        light_center.y = (light_center.y / texel).floor() * texel;
+        let center = transform_point(&inverse_rotation, &light_center);
+
+        let eye = center - direction * (radius + extension);
+        let view = look_at_rh(&eye, &center, &up);
+        let projection = orthographic_rh(
+            -radius,
+            radius,
+            -radius,
+            radius,
+            0.0,
+            2.0 * radius + extension,
+        );
+        views.push(ShadowView {
+            view_projection: projection * view,
+            atlas_rect: [0.0; 4],
+            texel_world_size: texel,
+        });
+    }
+    (views, splits)
+}
+
+/// Six 90° views around a point light, in +X, -X, +Y, -Y, +Z, -Z order.
+pub(crate) fn point_light_views(
+    position: Vec3,
+    near: f32,
+    far: f32,
+    resolution: u32,
+) -> Vec<ShadowView> {
+    let faces = [
+        (Vec3::x(), -Vec3::y()),
+        (-Vec3::x(), -Vec3::y()),
+        (Vec3::y(), Vec3::z()),
+        (-Vec3::y(), -Vec3::z()),
+        (Vec3::z(), -Vec3::y()),
+        (-Vec3::z(), -Vec3::y()),
+    ];
+    let projection = perspective_rh(std::f32::consts::FRAC_PI_2, 1.0, near, far);
+    let texel_world_size = 2.0 / resolution.max(1) as f32;
+    faces
+        .iter()
+        .map(|(forward, up)| ShadowView {
+            view_projection: projection * look_at_rh(&position, &(position + forward), up),
+            atlas_rect: [0.0; 4],
+            texel_world_size,
+        })
+        .collect()
+}
+
+/// Perspective view covering a spot light's outer cone.
+pub(crate) fn spot_light_view(
+    position: Vec3,
+    direction: Vec3,
+    outer_cone_angle: f32,
+    near: f32,
+    far: f32,
+    resolution: u32,
+) -> ShadowView {
+    let direction = direction.normalize();
+    let fov = (2.0 * outer_cone_angle).clamp(0.01, std::f32::consts::PI - 0.01);
+    let projection = perspective_rh(fov, 1.0, near, far);
+    let view = look_at_rh(&position, &(position + direction), &stable_up(direction));
+    ShadowView {
+        view_projection: projection * view,
+        atlas_rect: [0.0; 4],
+        texel_world_size: 2.0 * (fov * 0.5).tan() / resolution.max(1) as f32,
+    }
+}
+
+/// World-space frustum corners: near plane (0..4) then far plane (4..8).
+fn frustum_corners(inverse_view_projection: &Mat4) -> [Vec3; 8] {
+    let mut corners = [Vec3::zeros(); 8];
+    for (i, corner) in corners.iter_mut().enumerate() {
+        let x = if i & 1 == 0 { -1.0 } else { 1.0 };
+        let y = if i & 2 == 0 { -1.0 } else { 1.0 };
+        let z = if i < 4 { 0.0 } else { 1.0 };
+        *corner = transform_point(inverse_view_projection, &Vec3::new(x, y, z));
+    }
+    corners
+}
+
+fn transform_point(m: &Mat4, p: &Vec3) -> Vec3 {
+    let h = m * Vec4::new(p.x, p.y, p.z, 1.0);
+    h.xyz() / h.w
+}
+
+/// An up vector that is never parallel to `direction`.
+fn stable_up(direction: Vec3) -> Vec3 {
+    if direction.y.abs() > 0.99 {
+        Vec3::z()
+    } else {
+        Vec3::y()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::World;
+    use redlilium_core::math::mat4_from_translation;
+
+    fn assert_in_clip_volume(vp: &Mat4, p: &Vec3) {
+        let ndc = transform_point(vp, p);
+        assert!(
+            ndc.x.abs() <= 1.0 + 1e-4 && ndc.y.abs() <= 1.0 + 1e-4,
+            "{p:?} -> {ndc:?}"
+        );
+        assert!(ndc.z >= -1e-4 && ndc.z <= 1.0 + 1e-4, "{p:?} -> {ndc:?}");
+    }
+
+    fn test_camera() -> Camera {
+        let mut camera = Camera::perspective(1.0, 16.0 / 9.0, 0.1, 500.0);
+        camera.view_matrix = look_at_rh(
+            &Vec3::new(0.0, 5.0, 10.0),
+            &Vec3::new(0.0, 0.0, 0.0),
+            &Vec3::y(),
+        );
+        camera
+    }
+
+    #[test]
+    fn splits_are_monotonic_and_end_at_far() {
+        let splits = cascade_splits(0.1, 100.0, 4, 0.75);
+        assert_eq!(splits.len(), 4);
+        assert!(splits.windows(2).all(|w| w[0] < w[1]));
+        assert!((splits[3] - 100.0).abs() < 1e-3);
+
+        let uniform = cascade_splits(0.0, 100.0, 4, 0.0);
+        assert!((uniform[0] - 25.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn cascades_contain_their_frustum_slice() {
+        let camera = test_camera();
+        let settings = ShadowSettings::default().with_max_distance(60.0);
+        let direction = Vec3::new(-0.3, -1.0, -0.2).normalize();
+        let (views, splits) = directional_cascades(&camera, direction, &settings);
+        assert_eq!(views.len(), 4);
+        assert!((splits[3] - 60.0).abs() < 1e-2);
+
+        let corners = frustum_corners(&camera.view_projection().try_inverse().unwrap());
+        let near = -transform_point(&camera.view_matrix, &corners[0]).z;
+        let far = -transform_point(&camera.view_matrix, &corners[4]).z;
+        let mut start = near;
+        for (view, &end) in views.iter().zip(&splits) {
+            for k in 0..4 {
+                let ray = corners[k + 4] - corners[k];
+                for d in [start, end] {
+                    let p = corners[k] + ray * ((d - near) / (far - near));
+                    assert_in_clip_volume(&view.view_projection, &p);
+                }
+            }
+            start = end;
+        }
+    }
+
+    #[test]
+    fn point_light_faces_cover_axes() {
+        let position = Vec3::new(1.0, 2.0, 3.0);
+        let views = point_light_views(position, 0.1, 20.0, 512);
+        let axes = [
+            Vec3::x(),
+            -Vec3::x(),
+            Vec3::y(),
+            -Vec3::y(),
+            Vec3::z(),
+            -Vec3::z(),
+        ];
+        for (view, axis) in views.iter().zip(axes) {
+            let ndc = transform_point(&view.view_projection, &(position + axis * 5.0));
+            assert!(
+                ndc.x.abs() < 1e-4 && ndc.y.abs() < 1e-4,
+                "{axis:?} -> {ndc:?}"
+            );
+            assert!(ndc.z > 0.0 && ndc.z < 1.0);
+        }
+    }
+
+    #[test]
+    fn spot_light_covers_outer_cone() {
+        let position = Vec3::new(0.0, 4.0, 0.0);
+        let direction = -Vec3::y();
+        let view = spot_light_view(position, direction, 0.5, 0.1, 10.0, 1024);
+        assert_in_clip_volume(&view.view_projection, &Vec3::new(0.0, 0.0, 0.0));
+        // A point just inside the outer cone, 3 units down
+        let edge = 3.0 * (0.5f32 - 0.01).tan();
+        assert_in_clip_volume(&view.view_projection, &Vec3::new(edge, 1.0, 0.0));
+    }
+
+    #[test]
+    fn collects_only_lights_with_shadow_settings() {
+        let mut world = World::new();
+        world.register_component::<GlobalTransform>();
+        world.register_component::<DirectionalLight>();
+        world.register_component::<PointLight>();
+        world.register_component::<SpotLight>();
+        world.register_component::<ShadowSettings>();
+
+        let sun = world.spawn();
+        world.insert(sun, GlobalTransform::IDENTITY).unwrap();
+        world.insert(sun, DirectionalLight::default()).unwrap();
+        world
+            .insert(sun, ShadowSettings::default().with_cascades(2))
+            .unwrap();
+
+        let lamp = world.spawn();
+        world
+            .insert(
+                lamp,
+                GlobalTransform(mat4_from_translation(Vec3::new(0.0, 3.0, 0.0))),
+            )
+            .unwrap();
+        world
+            .insert(lamp, PointLight::default().with_range(8.0))
+            .unwrap();
+        world.insert(lamp, ShadowSettings::default()).unwrap();
+
+        let no_shadows = world.spawn();
+        world.insert(no_shadows, GlobalTransform::IDENTITY).unwrap();
+        world.insert(no_shadows, SpotLight::default()).unwrap();
+
+        let camera = test_camera();
+        let lights = collect_shadow_lights(
+            Some(&camera),
+            &world.read::<GlobalTransform>().unwrap(),
+            &world.read::<DirectionalLight>().unwrap(),
+            &world.read::<PointLight>().unwrap(),
+            &world.read::<SpotLight>().unwrap(),
+            &world.read::<ShadowSettings>().unwrap(),
+        );
+
+        assert_eq!(lights.len(), 2);
+        let sun_light = lights.iter().find(|l| l.entity == sun.index()).unwrap();
+        assert_eq!(sun_light.kind, ShadowMapKind::Directional);
+        assert_eq!(sun_light.views.len(), 2);
+        assert_eq!(sun_light.cascade_splits.len(), 2);
+        let lamp_light = lights.iter().find(|l| l.entity == lamp.index()).unwrap();
+        assert_eq!(lamp_light.kind, ShadowMapKind::Point);
+        assert_eq!(lamp_light.views.len(), 6);
+
+        // Without a camera, directional lights have nothing to fit cascades to
+        let lights = collect_shadow_lights(
+            None,
+            &world.read::<GlobalTransform>().unwrap(),
+            &world.read::<DirectionalLight>().unwrap(),
+            &world.read::<PointLight>().unwrap(),
+            &world.read::<SpotLight>().unwrap(),
+            &world.read::<ShadowSettings>().unwrap(),
+        );
+        assert_eq!(lights.len(), 1);
+    }
+}
@@ -0,0 +1,309 @@
+//! Prefab instance hot reload system.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::serialize::SerializedPrefab;
+use crate::std::components::{Children, Parent, PrefabInstance, PrefabInstanceIndex, Transform};
+use crate::std::hierarchy::{despawn_recursive, set_parent};
+use crate::std::rendering::resources::{AssetServer, Handle};
+use crate::{Component, Entity, World};
+
+/// A prefab file referenced by at least one live [`PrefabInstance`].
+struct TrackedPrefab {
+    handle: Handle<SerializedPrefab>,
+    /// Asset version the instances were last synced to. `None` until the
+    /// first load finishes; instances spawned before that are assumed current.
+    synced_version: Option<u64>,
+}
+
+/// Rebuilds [`PrefabInstance`] trees whose prefab file was hot reloaded.
+///
+/// Each prefab referenced by a live instance is loaded through the
+/// [`AssetServer`]. When a new version arrives, the instances of that prefab,
+/// looked up in the [`PrefabInstanceIndex`], are rebuilt from it in place:
+/// the root entity keeps its handle, [`Transform`] and parent, while its
+/// other components and all descendants are replaced. If the new prefab
+/// cannot be deserialized, the old tree is kept.
+///
+/// Does nothing unless the [`AssetServer`] and [`PrefabInstanceIndex`]
+/// resources exist. Schedule after
+/// [`UpdateAssetServer`](super::UpdateAssetServer).
+#[derive(Default)]
+pub struct SyncPrefabInstances {
+    prefabs: HashMap<String, TrackedPrefab>,
+}
+
+impl SyncPrefabInstances {
+    /// Create the system with no tracked prefabs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl crate::ExclusiveSystem for SyncPrefabInstances {
+    type Result = ();
+
+    fn run(&mut self, world: &mut World) -> Result<(), crate::system::SystemError> {
+        if !world.has_resource::<AssetServer>() || !world.has_resource::<PrefabInstanceIndex>() {
+            return Ok(());
+        }
+
+        {
+            // Forget prefabs without instances so their handles can unload
+            let index = world.resource::<PrefabInstanceIndex>();
+            let sources: HashSet<&str> = index.sources().collect();
+            self.prefabs
+                .retain(|source, _| sources.contains(source.as_str()));
+            let mut server = world.resource_mut::<AssetServer>();
+            for source in sources {
+                if !self.prefabs.contains_key(source) {
+                    self.prefabs.insert(
+                        source.to_owned(),
+                        TrackedPrefab {
+                            handle: server.load(source),
+                            synced_version: None,
+                        },
+                    );
+                }
+            }
+        }
+
+        let mut changed: Vec<(&str, Arc<SerializedPrefab>)> = Vec::new();
+        for (source, tracked) in &mut self.prefabs {
+            let Some(prefab) = tracked.handle.get() else {
+                continue;
+            };
+            let version = tracked.handle.version();
+            match tracked.synced_version {
+                Some(synced) if synced != version => changed.push((source.as_str(), prefab)),
+                _ => {}
+            }
+            tracked.synced_version = Some(version);
+        }
+
+        for (source, prefab) in changed {
+            let roots: Vec<Entity> = world
+                .resource::<PrefabInstanceIndex>()
+                .instances(source)
+                .collect();
+            for root in roots {
+                if world.is_alive(root) {
+                    resync_instance(world, root, source, &prefab);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Rebuild the instance tree under `root` from `prefab`, keeping `root`.
+///
+/// The new tree is spawned first, so a prefab that fails to deserialize
+/// leaves the instance untouched. Its descendants are then moved under
+/// `root` and its root components copied over, after which the spawned
+/// root is despawned.
+fn resync_instance(world: &mut World, root: Entity, source: &str, prefab: &SerializedPrefab) {
+    let entities = match world.deserialize_prefab(prefab) {
+        Ok(entities) => entities,
+        Err(err) => {
+            log::error!("Failed to rebuild prefab '{source}', keeping previous instance: {err}");
+            return;
+        }
+    };
+    let Some(&new_root) = entities.first() else {
+        return;
+    };
+
+    // Replace the descendants
+    for child in children_of(world, root) {
+        despawn_recursive(world, child);
+    }
+    for child in children_of(world, new_root) {
+        set_parent(world, child, root);
+    }
+
+    // Replace the root components, except placement and instance bookkeeping
+    let kept = [
+        Transform::NAME,
+        Parent::NAME,
+        Children::NAME,
+        PrefabInstance::NAME,
+    ];
+    let new_components = world.inspectable_components_of(new_root);
+    for name in world.inspectable_components_of(root) {
+        if !kept.contains(&name) && !new_components.contains(&name) {
+            world.remove_by_name(root, name);
+        }
+    }
+    for name in new_components {
+        if !kept.contains(&name)
+            && let Some(component) = world.extract_by_name(new_root, name)
+        {
+            world.insert_bag(root, component);
+        }
+    }
+
+    // References to the spawned root now mean the kept one
+    for entity in std::iter::once(root).chain(entities.iter().skip(1).copied()) {
+        for name in world.inspectable_components_of(entity) {
+            world.remap_entities_by_name(entity, name, &mut |e| {
+                if e == new_root { root } else { e }
+            });
+        }
+    }
+    world.despawn(new_root);
+    log::info!("Rebuilt prefab instance of '{source}'");
+}
+
+fn children_of(world: &World, entity: Entity) -> Vec<Entity> {
+    world
+        .get::<Children>(entity)
+        .map(|children| children.0.clone())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+#[cfg(all(feature = "serialize-ron", not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use crate::ExclusiveSystem;
+    use crate::serialize::{Format, encode};
+    use crate::std::components::Name;
+    use crate::std::rendering::resources::{AssetManagers, MeshManager, TextureManager};
+    use redlilium_core::math::Vec3;
+    use redlilium_graphics::GraphicsInstance;
+    use redlilium_vfs::{MemoryProvider, Vfs};
+
+    /// Encode a prefab with the given root name and one child per entry of
+    /// `children`.
+    fn prefab_bytes(name: &str, children: &[&str]) -> Vec<u8> {
+        let mut world = World::new();
+        crate::register_std_components(&mut world);
+        let root = world.spawn();
+        world.insert(root, Name::new(name)).unwrap();
+        world.insert(root, Transform::IDENTITY).unwrap();
+        for child_name in children {
+            let child = world.spawn();
+            world.insert(child, Name::new(*child_name)).unwrap();
+            set_parent(&mut world, child, root);
+        }
+        encode(&world.serialize_prefab(root).unwrap(), Format::Ron).unwrap()
+    }
+
+    /// Run the asset server and the system until no load is pending.
+    fn step(world: &mut World, system: &mut SyncPrefabInstances) {
+        let device = GraphicsInstance::new().unwrap().create_device().unwrap();
+        let mut textures = TextureManager::new(device.clone());
+        let mut meshes = MeshManager::new(device);
+        for _ in 0..1000 {
+            system.run(world).unwrap();
+            let mut server = world.resource_mut::<AssetServer>();
+            server.update(&mut AssetManagers {
+                textures: &mut textures,
+                meshes: &mut meshes,
+            });
+            if server.pending_count() == 0 {
+                drop(server);
+                system.run(world).unwrap();
+                return;
+            }
+            drop(server);
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        panic!("prefab did not load");
+    }
+
+    fn names(world: &World) -> Vec<String> {
+        world
+            .iter_entities()
+            .filter_map(|e| world.get::<Name>(e).map(|n| n.as_str().to_owned()))
+            .collect()
+    }
+
+    #[test]
+    fn rebuilds_instances_of_changed_prefab_in_place() {
+        let memory = MemoryProvider::new();
+        memory.insert("crate.prefab", prefab_bytes("v1", &["lid"]));
+        let vfs = Vfs::new();
+        vfs.mount("assets", memory.clone());
+        let mut server = AssetServer::new(vfs, crate::IoRuntime::new());
+        server.watch_for_changes();
+
+        let mut world = World::new();
+        crate::register_std_components(&mut world);
+        world.insert_resource(server);
+
+        let parent = world.spawn();
+        let root = world.spawn();
+        world.insert(root, Name::new("v1")).unwrap();
+        let placed = Transform::from_translation(Vec3::new(1.0, 2.0, 3.0));
+        world.insert(root, placed).unwrap();
+        world
+            .insert(root, PrefabInstance::new("assets/crate.prefab"))
+            .unwrap();
+        set_parent(&mut world, root, parent);
+        let lid = world.spawn();
+        world.insert(lid, Name::new("lid")).unwrap();
+        set_parent(&mut world, lid, root);
+
+        let mut system = SyncPrefabInstances::new();
+        step(&mut world, &mut system);
+        assert!(world.is_alive(lid), "first load must not rebuild");
+
+        memory.insert("crate.prefab", prefab_bytes("v2", &["handle", "hinge"]));
+        step(&mut world, &mut system);
+
+        assert!(world.is_alive(root));
+        assert!(!world.is_alive(lid));
+        let mut names = names(&world);
+        names.sort();
+        assert_eq!(names, ["handle", "hinge", "v2"]);
+        assert_eq!(world.get::<Transform>(root), Some(&placed));
+        assert_eq!(world.get::<Parent>(root).map(|p| p.0), Some(parent));
+        let children = children_of(&world, root);
+        assert_eq!(children.len(), 2);
+        assert!(
+            children
+                .iter()
+                .all(|&child| world.get::<Parent>(child).map(|p| p.0) == Some(root))
+        );
+        assert_eq!(
+            world
+                .resource::<PrefabInstanceIndex>()
+                .instances("assets/crate.prefab")
+                .collect::<Vec<_>>(),
+            [root]
+        );
+    }
+
+    #[test]
+    fn index_follows_instance_changes() {
+        let mut world = World::new();
+        crate::register_std_components(&mut world);
+        let a = world.spawn();
+        let b = world.spawn();
+        world
+            .insert(a, PrefabInstance::new("crate.prefab"))
+            .unwrap();
+        world
+            .insert(b, PrefabInstance::new("crate.prefab"))
+            .unwrap();
+        world
+            .insert(b, PrefabInstance::new("barrel.prefab"))
+            .unwrap();
+
+        let instances = |world: &World, source: &str| {
+            world
+                .resource::<PrefabInstanceIndex>()
+                .instances(source)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(instances(&world, "crate.prefab"), [a]);
+        assert_eq!(instances(&world, "barrel.prefab"), [b]);
+
+        world.despawn(a);
+        assert!(instances(&world, "crate.prefab").is_empty());
+        assert_eq!(world.resource::<PrefabInstanceIndex>().sources().count(), 1);
+    }
+}
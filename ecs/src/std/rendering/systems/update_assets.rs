@@ -0,0 +1,33 @@
+//! Asset server update system.
+
+use crate::std::rendering::resources::{AssetManagers, AssetServer, MeshManager, TextureManager};
+
+/// Finishes completed [`AssetServer`] loads and unloads assets whose last
+/// handle has been dropped.
+///
+/// GPU uploads for newly loaded assets go through the [`TextureManager`] and
+/// [`MeshManager`] resources. Schedule this early in the frame so that
+/// handles resolved here are visible to the systems that follow.
+pub struct UpdateAssetServer;
+
+impl crate::System for UpdateAssetServer {
+    type Result = ();
+
+    fn run<'a>(
+        &'a self,
+        ctx: &'a crate::SystemContext<'a>,
+    ) -> Result<(), crate::system::SystemError> {
+        ctx.lock::<(
+            crate::ResMut<AssetServer>,
+            crate::ResMut<TextureManager>,
+            crate::ResMut<MeshManager>,
+        )>()
+        .execute(|(mut server, mut textures, mut meshes)| {
+            server.update(&mut AssetManagers {
+                textures: &mut textures,
+                meshes: &mut meshes,
+            });
+        });
+        Ok(())
+    }
+}
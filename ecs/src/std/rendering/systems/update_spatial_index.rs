@@ -0,0 +1,59 @@
+//! Spatial index update system.
+
+use crate::std::components::GlobalTransform;
+use crate::std::rendering::components::RenderMesh;
+use crate::std::rendering::resources::SpatialIndex;
+
+/// Keeps the [`SpatialIndex`] in sync with rendered entities.
+///
+/// Entities with a [`RenderMesh`] are indexed by their mesh bounds
+/// transformed by their [`GlobalTransform`]; meshes without bounds are
+/// indexed as unbounded (never culled). Only entities that are new to the
+/// index or whose transform or mesh changed this frame are re-transformed,
+/// and entities that lost their mesh are removed.
+///
+/// Run after [`UpdateGlobalTransforms`](crate::UpdateGlobalTransforms) and
+/// before the render systems. Also clears last frame's culling stats.
+///
+/// # Access
+///
+/// - Reads: `RenderMesh`, `GlobalTransform`
+/// - Writes: `SpatialIndex` (resource)
+pub struct UpdateSpatialIndex;
+
+impl crate::System for UpdateSpatialIndex {
+    type Result = ();
+
+    fn run<'a>(
+        &'a self,
+        ctx: &'a crate::SystemContext<'a>,
+    ) -> Result<(), crate::system::SystemError> {
+        ctx.lock::<(
+            crate::Read<RenderMesh>,
+            crate::Read<GlobalTransform>,
+            crate::MaybeChanged<RenderMesh>,
+            crate::MaybeChanged<GlobalTransform>,
+            crate::ResMut<SpatialIndex>,
+        )>()
+        .execute(|(meshes, globals, edited, moved, mut index)| {
+            redlilium_core::profile_scope!("update_spatial_index");
+
+            index.clear_stats();
+            index.retain(|entity| meshes.contains(entity) && globals.contains(entity));
+            for (entity, mesh) in meshes.iter() {
+                if index.contains(entity) && !edited.matches(entity) && !moved.matches(entity) {
+                    continue;
+                }
+                let Some(global) = globals.get(entity) else {
+                    continue;
+                };
+                match mesh.aabb {
+                    Some(aabb) => index.insert(entity, aabb.transform(&global.0)),
+                    None => index.insert_unbounded(entity),
+                }
+            }
+            index.update();
+        });
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 //! Per-entity uniform update system.
 
-use crate::std::components::{Camera, GlobalTransform};
+use crate::std::components::{Camera, GlobalTransform, SkinnedMesh};
 use crate::std::rendering::components::PerEntityBuffers;
 use crate::std::rendering::resources::MaterialManager;
 
@@ -8,7 +8,9 @@ use crate::std::rendering::resources::MaterialManager;
 ///
 /// Reads the first camera's view-projection matrix and writes it together
 /// with each entity's model matrix (from [`GlobalTransform`]) into the
-/// entity's [`PerEntityBuffers`].
+/// entity's [`PerEntityBuffers`]. Entities with a joint buffer also get
+/// their [`SkinnedMesh`] joint matrices uploaded; joints beyond the buffer's
+/// capacity are dropped.
 pub struct UpdatePerEntityUniforms;
 
 impl crate::System for UpdatePerEntityUniforms {
@@ -22,9 +24,10 @@ impl crate::System for UpdatePerEntityUniforms {
             crate::ReadAll<Camera>,
             crate::Read<GlobalTransform>,
             crate::Read<PerEntityBuffers>,
+            crate::Read<SkinnedMesh>,
             crate::Res<MaterialManager>,
         )>()
-        .execute(|(cameras, globals, buffers, mat_manager)| {
+        .execute(|(cameras, globals, buffers, skins, mat_manager)| {
             let Some((_, camera)) = cameras.iter().next() else {
                 return;
             };
@@ -56,6 +59,19 @@ impl crate::System for UpdatePerEntityUniforms {
                     };
                     let _ = device.write_buffer(ei_buffer, 0, bytemuck::bytes_of(&ei_uniforms));
                 }
+
+                if let Some(joint_buffer) = &per_entity.joint_buffer
+                    && let Some(skin) = skins.get(entity_idx)
+                {
+                    let capacity = joint_buffer.size() as usize / std::mem::size_of::<[f32; 16]>();
+                    let joints: Vec<[[f32; 4]; 4]> = skin
+                        .joint_matrices
+                        .iter()
+                        .take(capacity)
+                        .map(redlilium_core::math::mat4_to_cols_array_2d)
+                        .collect();
+                    let _ = device.write_buffer(joint_buffer, 0, bytemuck::cast_slice(&joints));
+                }
             }
         });
         Ok(())
@@ -1,7 +1,12 @@
+use std::sync::Arc;
+
 use crate::{Entity, World};
+use redlilium_core::math::Mat4;
 use redlilium_core::scene::{CameraProjection, Scene, SceneNode};
 
-use super::components::{Camera, GlobalTransform, Name, Transform, Visibility};
+use super::components::{
+    AnimationPlayer, Camera, GlobalTransform, Name, SkinnedMesh, Transform, Visibility,
+};
 use super::hierarchy::set_parent;
 
 /// Spawns all entities from a loaded [`Scene`] into the ECS [`World`].
@@ -20,12 +25,65 @@ use super::hierarchy::set_parent;
 /// - **Visibility** — always (default: visible)
 /// - **Camera** — if the node has a camera reference
 /// - **Parent** / **Children** — for nested nodes
+/// - **SkinnedMesh** — if the node has a skin (joints resolved to entities)
+///
+/// If the scene has animations, the first root entity also gets an
+/// [`AnimationPlayer`] holding all of them, with every spawned node as a
+/// target. No clip is started.
 pub fn spawn_scene(world: &mut World, scene: &Scene) -> Vec<Entity> {
-    scene
+    // Every spawned entity in depth-first pre-order, matching the node
+    // indices used by skins and animation channels.
+    let mut nodes = Vec::new();
+    let mut skinned = Vec::new();
+    let roots: Vec<Entity> = scene
         .nodes
         .iter()
-        .map(|node| spawn_node(world, node, scene, None))
-        .collect()
+        .map(|node| spawn_node(world, node, scene, None, &mut nodes, &mut skinned))
+        .collect();
+
+    for (entity, skin_idx) in skinned {
+        let Some(skin) = scene.skins.get(skin_idx) else {
+            continue;
+        };
+        let mut joints = Vec::with_capacity(skin.joints.len());
+        let mut inverse_bind_matrices = Vec::with_capacity(skin.joints.len());
+        for (i, &joint) in skin.joints.iter().enumerate() {
+            let inverse_bind = skin
+                .inverse_bind_matrices
+                .get(i)
+                .map(|m| Mat4::from_column_slice(m))
+                .unwrap_or(Mat4::identity());
+            match nodes.get(joint) {
+                Some(&joint_entity) => {
+                    joints.push(joint_entity);
+                    inverse_bind_matrices.push(inverse_bind);
+                }
+                // Joint outside the scene: bind it to the mesh itself so its
+                // skinning matrix stays identity
+                None => {
+                    joints.push(entity);
+                    inverse_bind_matrices.push(Mat4::identity());
+                }
+            }
+        }
+        world
+            .insert(
+                entity,
+                SkinnedMesh::new(joints, Arc::new(inverse_bind_matrices)),
+            )
+            .expect("SkinnedMesh not registered");
+    }
+
+    if let Some(&root) = roots.first()
+        && !scene.animations.is_empty()
+    {
+        let clips = scene.animations.iter().cloned().map(Arc::new).collect();
+        world
+            .insert(root, AnimationPlayer::new(clips, nodes))
+            .expect("AnimationPlayer not registered");
+    }
+
+    roots
 }
 
 fn spawn_node(
@@ -33,8 +91,14 @@ fn spawn_node(
     node: &SceneNode,
     scene: &Scene,
     parent_entity: Option<Entity>,
+    nodes: &mut Vec<Entity>,
+    skinned: &mut Vec<(Entity, usize)>,
 ) -> Entity {
     let entity = world.spawn();
+    nodes.push(entity);
+    if let Some(skin_idx) = node.skin {
+        skinned.push((entity, skin_idx));
+    }
 
     let transform = Transform::from(node.transform);
     world
@@ -82,7 +146,7 @@ fn spawn_node(
     }
 
     for child_node in &node.children {
-        spawn_node(world, child_node, scene, Some(entity));
+        spawn_node(world, child_node, scene, Some(entity), nodes, skinned);
     }
 
     entity
@@ -222,4 +286,51 @@ mod tests {
         let leaf = mid_children.0[0];
         assert_eq!(world.get::<crate::Parent>(leaf).unwrap().0, mid);
     }
+
+    #[test]
+    fn spawn_skin_and_animation_player() {
+        use redlilium_core::scene::{
+            Animation, AnimationChannel, AnimationProperty, Interpolation, SceneSkin,
+        };
+
+        let mut world = World::new();
+        crate::register_std_components(&mut world);
+        let mut mesh_node = SceneNode::new().with_name("mesh");
+        mesh_node.skin = Some(0);
+        let mut scene = Scene::new().with_nodes(vec![
+            SceneNode::new()
+                .with_name("armature")
+                .with_children(vec![SceneNode::new().with_name("bone")]),
+            mesh_node,
+        ]);
+        scene.skins = vec![SceneSkin {
+            name: None,
+            joints: vec![1, usize::MAX],
+            inverse_bind_matrices: vec![[2.0; 16]],
+            skeleton: Some(0),
+        }];
+        scene.animations = vec![Animation::new().with_name("wave").with_channels(vec![
+            AnimationChannel {
+                target_node: 1,
+                property: AnimationProperty::Translation,
+                interpolation: Interpolation::Linear,
+                timestamps: vec![0.0],
+                values: vec![0.0; 3],
+            },
+        ])];
+
+        let roots = spawn_scene(&mut world, &scene);
+        let bone = world.get::<crate::Children>(roots[0]).unwrap().0[0];
+
+        let skin = world.get::<SkinnedMesh>(roots[1]).unwrap();
+        assert_eq!(skin.joints, vec![bone, roots[1]]);
+        assert_eq!(skin.inverse_bind_matrices[0], Mat4::from_element(2.0));
+        assert_eq!(skin.inverse_bind_matrices[1], Mat4::identity());
+
+        let player = world.get::<AnimationPlayer>(roots[0]).unwrap();
+        assert_eq!(player.targets, vec![roots[0], bone, roots[1]]);
+        assert_eq!(player.clip_by_name("wave"), Some(0));
+        assert!(player.active_clips().is_empty());
+        assert!(world.get::<AnimationPlayer>(roots[1]).is_none());
+    }
 }
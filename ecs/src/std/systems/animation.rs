@@ -0,0 +1,337 @@
+use std::collections::HashMap;
+
+use crate::{Ref, RefMut, SystemContext, Time};
+use redlilium_core::math::{Mat4, Quat, Vec3};
+use redlilium_core::scene::AnimationProperty;
+
+use crate::std::components::{AnimationPlayer, GlobalTransform, SkinnedMesh, Transform};
+
+/// System that advances every [`AnimationPlayer`] and writes the sampled,
+/// blended poses into the target entities' [`Transform`]s.
+///
+/// Clip weights are normalized per animated property, so crossfades and
+/// blends never scale a pose down towards zero. Rotations are blended along
+/// the shortest arc. Morph target weight channels are ignored.
+/// Must run before [`UpdateGlobalTransforms`](super::UpdateGlobalTransforms).
+///
+/// # Access
+///
+/// - Resource: `Res<Time>`
+/// - Writes: `AnimationPlayer`, `Transform`
+pub struct AnimateTransforms;
+
+impl crate::System for AnimateTransforms {
+    type Result = ();
+    fn run<'a>(&'a self, ctx: &'a SystemContext<'a>) -> Result<(), crate::system::SystemError> {
+        ctx.lock::<(
+            crate::Res<Time>,
+            crate::WriteAll<AnimationPlayer>,
+            crate::WriteAll<Transform>,
+        )>()
+        .execute(|(time, mut players, mut transforms)| {
+            animate_transforms(time.delta_f32(), &mut players, &mut transforms);
+        });
+        Ok(())
+    }
+}
+
+/// System that computes [`SkinnedMesh::joint_matrices`] from the joints'
+/// [`GlobalTransform`]s.
+///
+/// Each matrix maps a bind-pose vertex into the skinned mesh entity's local
+/// space: `inverse(mesh) * joint * inverse_bind`. Joints without a
+/// `GlobalTransform` get the identity matrix.
+/// Must run after [`UpdateGlobalTransforms`](super::UpdateGlobalTransforms).
+///
+/// # Access
+///
+/// - Reads: `GlobalTransform`
+/// - Writes: `SkinnedMesh`
+pub struct UpdateSkinJoints;
+
+impl crate::System for UpdateSkinJoints {
+    type Result = ();
+    fn run<'a>(&'a self, ctx: &'a SystemContext<'a>) -> Result<(), crate::system::SystemError> {
+        ctx.lock::<(
+            crate::ReadAll<GlobalTransform>,
+            crate::WriteAll<SkinnedMesh>,
+        )>()
+        .execute(|(globals, mut skins)| {
+            update_skin_joints(&globals, &mut skins);
+        });
+        Ok(())
+    }
+}
+
+/// Weighted sum of one animated property of one entity.
+struct Accumulator {
+    value: [f32; 4],
+    weight: f32,
+}
+
+fn animate_transforms(
+    dt: f32,
+    players: &mut RefMut<AnimationPlayer>,
+    transforms: &mut RefMut<Transform>,
+) {
+    redlilium_core::profile_scope!("animate_transforms");
+
+    let mut poses: HashMap<(u32, u8), Accumulator> = HashMap::new();
+    for (_, mut player) in players.iter_mut() {
+        player.advance(dt);
+        accumulate_poses(&player, &mut poses);
+    }
+
+    for ((entity_index, property), pose) in poses {
+        let Some(mut transform) = transforms.get_mut(entity_index) else {
+            continue;
+        };
+        if pose.weight <= 0.0 {
+            continue;
+        }
+        let v = pose.value.map(|x| x / pose.weight);
+        match property {
+            0 => transform.translation = Vec3::new(v[0], v[1], v[2]),
+            1 => transform.rotation = Quat::new(v[3], v[0], v[1], v[2]).normalize(),
+            _ => transform.scale = Vec3::new(v[0], v[1], v[2]),
+        }
+    }
+}
+
+/// Sample every playing clip of `player` and add the weighted results to
+/// `poses`, keyed by (entity index, property).
+fn accumulate_poses(player: &AnimationPlayer, poses: &mut HashMap<(u32, u8), Accumulator>) {
+    let mut sample = [0.0f32; 4];
+    for active in player.active_clips() {
+        if active.weight <= 0.0 {
+            continue;
+        }
+        let Some(clip) = player.clips.get(active.clip) else {
+            continue;
+        };
+        for channel in &clip.channels {
+            let property = match channel.property {
+                AnimationProperty::Translation => 0,
+                AnimationProperty::Rotation => 1,
+                AnimationProperty::Scale => 2,
+                AnimationProperty::MorphTargetWeights => continue,
+            };
+            let Some(target) = player.targets.get(channel.target_node) else {
+                continue;
+            };
+            if channel.stride() > sample.len() {
+                continue;
+            }
+            channel.sample(active.time, &mut sample);
+
+            let pose = poses
+                .entry((target.index(), property))
+                .or_insert(Accumulator {
+                    value: [0.0; 4],
+                    weight: 0.0,
+                });
+            // Keep quaternions in the same hemisphere before summing
+            let sign = if property == 1 && dot4(&pose.value, &sample) < 0.0 {
+                -1.0
+            } else {
+                1.0
+            };
+            for (acc, x) in pose.value.iter_mut().zip(sample) {
+                *acc += x * sign * active.weight;
+            }
+            pose.weight += active.weight;
+        }
+    }
+}
+
+fn dot4(a: &[f32; 4], b: &[f32; 4]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn update_skin_joints(globals: &Ref<GlobalTransform>, skins: &mut RefMut<SkinnedMesh>) {
+    redlilium_core::profile_scope!("update_skin_joints");
+
+    for (idx, mut skin) in skins.iter_mut() {
+        let inverse_mesh = globals
+            .get(idx)
+            .and_then(|g| g.0.try_inverse())
+            .unwrap_or(Mat4::identity());
+        let skin = &mut *skin;
+        skin.joint_matrices
+            .resize(skin.joints.len(), Mat4::identity());
+        for (i, joint) in skin.joints.iter().enumerate() {
+            let inverse_bind = skin
+                .inverse_bind_matrices
+                .get(i)
+                .copied()
+                .unwrap_or(Mat4::identity());
+            skin.joint_matrices[i] = match globals.get(joint.index()) {
+                Some(global) => inverse_mesh * global.0 * inverse_bind,
+                None => Mat4::identity(),
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::World;
+    use redlilium_core::math::{mat4_from_translation, quat_from_rotation_y};
+    use redlilium_core::scene::{Animation, AnimationChannel, Interpolation};
+
+    fn translation_clip(name: &str, to: f32) -> Arc<Animation> {
+        Arc::new(
+            Animation::new()
+                .with_name(name)
+                .with_channels(vec![AnimationChannel {
+                    target_node: 0,
+                    property: AnimationProperty::Translation,
+                    interpolation: Interpolation::Linear,
+                    timestamps: vec![0.0, 1.0],
+                    values: vec![0.0, 0.0, 0.0, to, 0.0, 0.0],
+                }]),
+        )
+    }
+
+    fn setup(clips: Vec<Arc<Animation>>) -> (World, crate::Entity, crate::Entity) {
+        let mut world = World::new();
+        world.register_component::<Transform>();
+        world.register_component::<AnimationPlayer>();
+        let target = world.spawn();
+        world.insert(target, Transform::IDENTITY).unwrap();
+        let player = world.spawn();
+        world
+            .insert(player, AnimationPlayer::new(clips, vec![target]))
+            .unwrap();
+        (world, player, target)
+    }
+
+    fn step(world: &World, dt: f32) {
+        let mut players = world.write::<AnimationPlayer>().unwrap();
+        let mut transforms = world.write::<Transform>().unwrap();
+        animate_transforms(dt, &mut players, &mut transforms);
+    }
+
+    fn translation_x(world: &World, entity: crate::Entity) -> f32 {
+        world
+            .read::<Transform>()
+            .unwrap()
+            .get(entity.index())
+            .unwrap()
+            .translation
+            .x
+    }
+
+    #[test]
+    fn plays_and_loops_clip() {
+        let (world, player, target) = setup(vec![translation_clip("walk", 4.0)]);
+        world
+            .write::<AnimationPlayer>()
+            .unwrap()
+            .get_mut(player.index())
+            .unwrap()
+            .play(0);
+
+        step(&world, 0.25);
+        assert!((translation_x(&world, target) - 1.0).abs() < 1e-5);
+
+        // 1.5s wraps to 0.5s
+        step(&world, 1.25);
+        assert!((translation_x(&world, target) - 2.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn crossfade_blends_then_finishes() {
+        let (world, player, target) =
+            setup(vec![translation_clip("a", 0.0), translation_clip("b", 4.0)]);
+        {
+            let mut players = world.write::<AnimationPlayer>().unwrap();
+            let mut p = players.get_mut(player.index()).unwrap();
+            p.play(0);
+            p.crossfade(1, 1.0).repeat = crate::std::components::RepeatMode::Once;
+        }
+
+        // Halfway through the fade: clip b at t=0.5 → 2.0, weighted 50/50 with 0.0
+        step(&world, 0.5);
+        assert!((translation_x(&world, target) - 1.0).abs() < 1e-5);
+
+        step(&world, 0.5);
+        assert!((translation_x(&world, target) - 4.0).abs() < 1e-5);
+        let players = world.read::<AnimationPlayer>().unwrap();
+        let p = players.get(player.index()).unwrap();
+        assert!(!p.is_playing(0));
+        assert!(p.is_playing(1));
+    }
+
+    #[test]
+    fn rotations_blend_along_shortest_arc() {
+        let turn = quat_from_rotation_y(std::f32::consts::FRAC_PI_2);
+        // Same rotation, opposite hemispheres
+        let clip = |q: Quat| {
+            Arc::new(Animation::new().with_channels(vec![AnimationChannel {
+                target_node: 0,
+                property: AnimationProperty::Rotation,
+                interpolation: Interpolation::Step,
+                timestamps: vec![0.0],
+                values: vec![q.i, q.j, q.k, q.w],
+            }]))
+        };
+        let (world, player, target) = setup(vec![clip(turn), clip(-turn)]);
+        {
+            let mut players = world.write::<AnimationPlayer>().unwrap();
+            let mut p = players.get_mut(player.index()).unwrap();
+            p.blend(0, 0.5);
+            p.blend(1, 0.5);
+        }
+        step(&world, 0.0);
+
+        let transforms = world.read::<Transform>().unwrap();
+        let rotation = transforms.get(target.index()).unwrap().rotation;
+        assert!((rotation.dot(&turn).abs() - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn skin_joint_matrices_are_mesh_relative() {
+        let mut world = World::new();
+        world.register_component::<GlobalTransform>();
+        world.register_component::<SkinnedMesh>();
+
+        let mesh = world.spawn();
+        let joint = world.spawn();
+        let missing = world.spawn();
+        world
+            .insert(
+                mesh,
+                GlobalTransform(mat4_from_translation(Vec3::new(10.0, 0.0, 0.0))),
+            )
+            .unwrap();
+        world
+            .insert(
+                joint,
+                GlobalTransform(mat4_from_translation(Vec3::new(10.0, 2.0, 0.0))),
+            )
+            .unwrap();
+        let inverse_bind = mat4_from_translation(Vec3::new(0.0, -1.0, 0.0));
+        world
+            .insert(
+                mesh,
+                SkinnedMesh::new(vec![joint, missing], Arc::new(vec![inverse_bind])),
+            )
+            .unwrap();
+
+        {
+            let globals = world.read::<GlobalTransform>().unwrap();
+            let mut skins = world.write::<SkinnedMesh>().unwrap();
+            update_skin_joints(&globals, &mut skins);
+        }
+
+        let skins = world.read::<SkinnedMesh>().unwrap();
+        let skin = skins.get(mesh.index()).unwrap();
+        let expected = mat4_from_translation(Vec3::new(0.0, 1.0, 0.0));
+        assert!((skin.joint_matrices[0] - expected).norm() < 1e-5);
+        assert_eq!(skin.joint_matrices[1], Mat4::identity());
+    }
+}
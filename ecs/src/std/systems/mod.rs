@@ -1,3 +1,4 @@
+mod animation;
 mod camera;
 mod free_fly_camera;
 #[cfg(feature = "rendering")]
@@ -6,6 +7,7 @@ mod grid;
 mod selection_aabb;
 mod transform;
 
+pub use animation::{AnimateTransforms, UpdateSkinJoints};
 pub use camera::UpdateCameraMatrices;
 pub use free_fly_camera::UpdateFreeFlyCamera;
 #[cfg(feature = "rendering")]
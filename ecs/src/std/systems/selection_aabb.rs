@@ -68,7 +68,7 @@ impl crate::System for DrawSelectionAabb {
                             let Some(gt) = world.get::<crate::GlobalTransform>(entity) else {
                                 continue;
                             };
-                            let world_aabb = transform_aabb(&aabb, &gt.0);
+                            let world_aabb = aabb.transform(&gt.0);
                             combined = Some(match combined {
                                 Some(c) => c.union(&world_aabb),
                                 None => world_aabb,
@@ -84,7 +84,7 @@ impl crate::System for DrawSelectionAabb {
                                 continue;
                             };
                             for aabb in world.entity_aabbs(entity) {
-                                let world_aabb = transform_aabb(&aabb, &gt.0);
+                                let world_aabb = aabb.transform(&gt.0);
                                 draw_ctx.draw_aabb(world_aabb.min, world_aabb.max, SELECTION_COLOR);
                             }
                         }
@@ -94,33 +94,3 @@ impl crate::System for DrawSelectionAabb {
         Ok(())
     }
 }
-
-/// Transform a local-space AABB by a 4x4 world matrix, returning a new world-space AABB.
-fn transform_aabb(aabb: &Aabb, matrix: &redlilium_core::math::Mat4) -> Aabb {
-    let corners = [
-        [aabb.min[0], aabb.min[1], aabb.min[2]],
-        [aabb.max[0], aabb.min[1], aabb.min[2]],
-        [aabb.max[0], aabb.max[1], aabb.min[2]],
-        [aabb.min[0], aabb.max[1], aabb.min[2]],
-        [aabb.min[0], aabb.min[1], aabb.max[2]],
-        [aabb.max[0], aabb.min[1], aabb.max[2]],
-        [aabb.max[0], aabb.max[1], aabb.max[2]],
-        [aabb.min[0], aabb.max[1], aabb.max[2]],
-    ];
-
-    let mut world_min = [f32::MAX; 3];
-    let mut world_max = [f32::MIN; 3];
-
-    for corner in &corners {
-        let v = redlilium_core::math::Vec4::new(corner[0], corner[1], corner[2], 1.0);
-        let transformed = matrix * v;
-        world_min[0] = world_min[0].min(transformed.x);
-        world_min[1] = world_min[1].min(transformed.y);
-        world_min[2] = world_min[2].min(transformed.z);
-        world_max[0] = world_max[0].max(transformed.x);
-        world_max[1] = world_max[1].max(transformed.y);
-        world_max[2] = world_max[2].max(transformed.z);
-    }
-
-    Aabb::new(world_min, world_max)
-}
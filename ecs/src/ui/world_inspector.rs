@@ -509,11 +509,13 @@ fn apply_parent(world: &mut World, entity: Entity, parent: Option<Entity>) -> Ed
 /// Reversible action that spawns entities from a serialized prefab.
 ///
 /// - `apply()`: deserializes the prefab into new entities, optionally parenting
-///   the root under `parent`.
+///   the root under `parent` and marking it as a
+///   [`PrefabInstance`](crate::PrefabInstance) of `source`.
 /// - `undo()`: despawns the root (and all children recursively).
 pub struct SpawnPrefabAction {
     serialized: crate::serialize::SerializedPrefab,
     parent: Option<Entity>,
+    source: Option<String>,
     spawned_entities: Vec<Entity>,
 }
 
@@ -522,9 +524,17 @@ impl SpawnPrefabAction {
         Self {
             serialized,
             parent,
+            source: None,
             spawned_entities: Vec::new(),
         }
     }
+
+    /// Record the VFS path the prefab was read from, so that the spawned
+    /// instance follows hot reloads of the file.
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
 }
 
 impl std::fmt::Debug for SpawnPrefabAction {
@@ -547,6 +557,11 @@ impl EditAction<World> for SpawnPrefabAction {
         {
             crate::std::hierarchy::set_parent(world, entities[0], parent);
         }
+        if let Some(source) = &self.source
+            && let Some(&root) = entities.first()
+        {
+            let _ = world.insert(root, crate::PrefabInstance::new(source.clone()));
+        }
         self.spawned_entities = entities;
         Ok(())
     }
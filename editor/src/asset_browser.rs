@@ -1,19 +1,13 @@
 use std::collections::{HashMap, HashSet};
+use std::time::{Duration, SystemTime};
 
 use redlilium_ecs::Entity;
 use redlilium_ecs::ui::{ComponentDragPayload, ComponentFileDragPayload, PrefabFileDragPayload};
-use redlilium_vfs::Vfs;
+use redlilium_vfs::{Vfs, VfsChange, VfsDirEntry, VfsWatcher};
 
 use crate::background_vfs::{BackgroundVfs, VfsRequestId, VfsResult};
-use crate::fs_watcher::FsWatcher;
 use crate::project::ProjectConfig;
 
-/// A directory entry in the asset browser.
-struct DirEntry {
-    name: String,
-    is_dir: bool,
-}
-
 /// Asset browser panel showing VFS contents as a directory tree + file list.
 pub struct AssetBrowser {
     /// Mount names from the project config (used as tree roots).
@@ -23,14 +17,14 @@ pub struct AssetBrowser {
     /// Tree nodes that are currently expanded (keys: "source/dir/subdir").
     expanded: HashSet<String>,
     /// Cached file listing for the right panel.
-    cached_entries: Vec<DirEntry>,
+    cached_entries: Vec<VfsDirEntry>,
     /// The (source, dir) that `cached_entries` corresponds to.
     cached_key: Option<(String, String)>,
 
     // Async VFS support
     bg_vfs: BackgroundVfs,
     /// Cached directory listings by VFS path.
-    dir_cache: HashMap<String, Vec<String>>,
+    dir_cache: HashMap<String, Vec<VfsDirEntry>>,
     /// In-flight listing requests: vfs_path -> request_id.
     pending_requests: HashMap<String, VfsRequestId>,
     /// In-flight write requests: vfs_path -> request_id.
@@ -39,8 +33,18 @@ pub struct AssetBrowser {
     pending_reads: HashMap<VfsRequestId, String>,
     /// Completed reads waiting to be consumed by the editor.
     pub completed_reads: Vec<(String, Vec<u8>)>,
-    /// Watches local filesystem mounts for external changes.
-    fs_watcher: Option<FsWatcher>,
+    /// Finished writes since the last poll: (vfs_path, succeeded).
+    /// Cleared at the start of every poll.
+    pub completed_writes: Vec<(String, bool)>,
+    /// Paths whose reads failed since the last poll.
+    /// Cleared at the start of every poll.
+    pub failed_reads: Vec<String>,
+    /// Watches all mounts for external changes (OS notifications for local
+    /// directories, polling for remote ones). The editor's only watcher.
+    watcher: VfsWatcher,
+    /// External file changes since the last poll, for other consumers such
+    /// as the worlds' asset servers. Cleared at the start of every poll.
+    pub changes: Vec<VfsChange>,
     /// Pending component export: (entity, comp_name, target_vfs_dir).
     /// Set when a component is dropped from inspector onto the file list.
     pub pending_component_export: Option<(Entity, &'static str, String)>,
@@ -51,9 +55,16 @@ pub struct AssetBrowser {
 
 impl AssetBrowser {
     /// Create a new asset browser from the project config.
-    pub fn new(config: &ProjectConfig) -> Self {
+    pub fn new(config: &ProjectConfig, vfs: &Vfs) -> Self {
+        // Layered mounts share a name and show up as one tree root.
+        let mut mount_names: Vec<String> = Vec::new();
+        for mount in &config.mount {
+            if !mount_names.contains(&mount.name) {
+                mount_names.push(mount.name.clone());
+            }
+        }
         Self {
-            mount_names: config.mount.iter().map(|m| m.name.clone()).collect(),
+            mount_names,
             selected: None,
             expanded: HashSet::new(),
             cached_entries: Vec::new(),
@@ -64,21 +75,30 @@ impl AssetBrowser {
             pending_writes: HashMap::new(),
             pending_reads: HashMap::new(),
             completed_reads: Vec::new(),
-            fs_watcher: FsWatcher::new(config),
+            completed_writes: Vec::new(),
+            failed_reads: Vec::new(),
+            watcher: vfs.watch(),
+            changes: Vec::new(),
             pending_component_export: None,
             pending_prefab_export: None,
         }
     }
 
-    /// Poll completed background VFS results and filesystem changes. Call once per frame.
+    /// Poll completed background VFS results and file changes. Call once per frame.
     pub fn poll(&mut self) {
-        // Check for external filesystem changes
-        if let Some(watcher) = &self.fs_watcher {
-            for vfs_dir in watcher.poll_changes() {
-                log::debug!("Filesystem change detected: {vfs_dir}");
-                self.dir_cache.remove(&vfs_dir);
-                self.cached_key = None;
+        self.completed_writes.clear();
+        self.failed_reads.clear();
+
+        // Check for external changes. The path may be a file or a directory,
+        // so drop the listings of both the path and its parent.
+        self.changes = self.watcher.poll();
+        for change in &self.changes {
+            log::debug!("VFS change detected: {} ({:?})", change.path, change.kind);
+            if let Some((parent, _)) = change.path.rsplit_once('/') {
+                self.dir_cache.remove(parent);
             }
+            self.dir_cache.remove(&change.path);
+            self.cached_key = None;
         }
 
         for (id, result) in self.bg_vfs.poll_results() {
@@ -107,11 +127,17 @@ impl AssetBrowser {
                         }
                         self.pending_writes.remove(&path);
                         self.cached_key = None;
+                        self.completed_writes.push((path, true));
                     }
                 }
                 VfsResult::Write(Err(e)) => {
                     log::error!("VFS write failed: {e}");
-                    self.pending_writes.retain(|_, rid| *rid != id);
+                    if let Some((path, _)) = self.pending_writes.iter().find(|(_, rid)| **rid == id)
+                    {
+                        let path = path.clone();
+                        self.pending_writes.remove(&path);
+                        self.completed_writes.push((path, false));
+                    }
                 }
                 VfsResult::Read(Ok(data)) => {
                     if let Some(path) = self.pending_reads.remove(&id) {
@@ -120,7 +146,9 @@ impl AssetBrowser {
                 }
                 VfsResult::Read(Err(e)) => {
                     log::error!("VFS read failed: {e}");
-                    self.pending_reads.remove(&id);
+                    if let Some(path) = self.pending_reads.remove(&id) {
+                        self.failed_reads.push(path);
+                    }
                 }
             }
         }
@@ -128,7 +156,7 @@ impl AssetBrowser {
 
     /// Request a directory listing. Returns cached result if available,
     /// otherwise dispatches a background request and returns `None`.
-    fn request_list_dir(&mut self, vfs: &Vfs, vfs_path: &str) -> Option<Vec<String>> {
+    fn request_list_dir(&mut self, vfs: &Vfs, vfs_path: &str) -> Option<Vec<VfsDirEntry>> {
         if let Some(entries) = self.dir_cache.get(vfs_path) {
             return Some(entries.clone());
         }
@@ -294,7 +322,13 @@ impl AssetBrowser {
         };
 
         let entries = self.request_list_dir(vfs, &vfs_path)?;
-        Some(entries.into_iter().filter(|e| !e.contains('.')).collect())
+        Some(
+            entries
+                .into_iter()
+                .filter(|e| e.is_dir())
+                .map(|e| e.name)
+                .collect(),
+        )
     }
 
     /// Draw the file listing (right panel).
@@ -315,17 +349,10 @@ impl AssetBrowser {
             };
 
             match self.request_list_dir(vfs, &vfs_path) {
-                Some(names) => {
-                    self.cached_entries = names
-                        .into_iter()
-                        .map(|name| {
-                            let is_dir = !name.contains('.');
-                            DirEntry { name, is_dir }
-                        })
-                        .collect();
-
+                Some(entries) => {
+                    self.cached_entries = entries;
                     self.cached_entries
-                        .sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then(a.name.cmp(&b.name)));
+                        .sort_by(|a, b| b.is_dir().cmp(&a.is_dir()).then(a.name.cmp(&b.name)));
 
                     self.cached_key = Some((source.clone(), dir_path.clone()));
                 }
@@ -356,23 +383,23 @@ impl AssetBrowser {
 
         // File listing
         for entry in &self.cached_entries {
-            let icon = if entry.is_dir {
-                "\u{1F4C1}"
-            } else {
-                "\u{1F4C4}"
-            };
+            let is_dir = entry.is_dir();
+            let icon = if is_dir { "\u{1F4C1}" } else { "\u{1F4C4}" };
             let label = format!("{icon} {}", entry.name);
 
             // Use Button with click_and_drag sense so file entries can
             // initiate drag-and-drop (selectable_label only has click sense).
-            let response = ui.add(
+            let mut response = ui.add(
                 egui::Button::new(&label)
                     .frame(false)
                     .sense(egui::Sense::click_and_drag()),
             );
+            if let Some(details) = entry_details(entry) {
+                response = response.on_hover_text(details);
+            }
 
             // Make .component files draggable for import into inspector
-            if !entry.is_dir && entry.name.ends_with(".component") {
+            if !is_dir && entry.name.ends_with(".component") {
                 let vfs_path = if dir_path.is_empty() {
                     format!("{source}/{}", entry.name)
                 } else {
@@ -382,7 +409,7 @@ impl AssetBrowser {
             }
 
             // Make .prefab files draggable for import into world inspector
-            if !entry.is_dir && entry.name.ends_with(".prefab") {
+            if !is_dir && entry.name.ends_with(".prefab") {
                 let vfs_path = if dir_path.is_empty() {
                     format!("{source}/{}", entry.name)
                 } else {
@@ -391,7 +418,7 @@ impl AssetBrowser {
                 response.dnd_set_drag_payload(PrefabFileDragPayload { vfs_path });
             }
 
-            if response.double_clicked() && entry.is_dir {
+            if response.double_clicked() && is_dir {
                 let new_dir = if dir_path.is_empty() {
                     entry.name.clone()
                 } else {
@@ -450,3 +477,42 @@ impl AssetBrowser {
         }
     }
 }
+
+/// Hover text for a file list entry: size for files and age of the last
+/// modification, when the provider reports it.
+fn entry_details(entry: &VfsDirEntry) -> Option<String> {
+    let size = (!entry.is_dir()).then(|| format_size(entry.metadata.size));
+    let age = entry
+        .metadata
+        .modified
+        .and_then(|t| SystemTime::now().duration_since(t).ok())
+        .map(|age| format!("modified {}", format_age(age)));
+    match (size, age) {
+        (Some(size), Some(age)) => Some(format!("{size}, {age}")),
+        (size, age) => size.or(age),
+    }
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+fn format_age(age: Duration) -> String {
+    let secs = age.as_secs();
+    match secs {
+        0..60 => "just now".to_owned(),
+        60..3600 => format!("{} min ago", secs / 60),
+        3600..86400 => format!("{} h ago", secs / 3600),
+        _ => format!("{} days ago", secs / 86400),
+    }
+}
@@ -14,11 +14,13 @@ use redlilium_ecs::ui::{
     PrefabFileDragPayload, SelectAction, SpawnPrefabAction,
 };
 use redlilium_ecs::{
-    Camera, DrawGrid, DrawSelectionAabb, EcsRunner, Entity, FreeFlyCamera, GlobalTransform,
-    GridConfig, InitializeRenderEntities, MaterialManager, MeshManager, Name, PerEntityBuffers,
-    PostUpdate, RenderMesh, Schedules, SyncMaterialUniforms, TextureManager, Transform, Update,
-    UpdateCameraMatrices, UpdateFreeFlyCamera, UpdateGlobalTransforms, UpdatePerEntityUniforms,
-    Visibility, WindowInput, World, register_std_components,
+    AssetServer, Camera, DrawGrid, DrawSelectionAabb, EcsRunner, Entity, FreeFlyCamera,
+    GlobalTransform, GridConfig, InitializeRenderEntities, MaterialManager, MeshManager, Name,
+    PerEntityBuffers, PostUpdate, ReloadMaterialShaders, RenderMesh, ScheduleLabel, Schedules,
+    SyncMaterialUniforms, SyncPrefabInstances, TextureManager, Time, Transform, Update,
+    UpdateAssetServer, UpdateCameraMatrices, UpdateFreeFlyCamera, UpdateGlobalTransforms,
+    UpdatePerEntityUniforms, UpdateSkinJoints, Visibility, WindowInput, World,
+    register_std_components,
 };
 use redlilium_graphics::egui::{EguiApp, EguiController};
 use redlilium_graphics::{FrameSchedule, RenderTarget, TextureFormat};
@@ -31,11 +33,16 @@ use crate::console::ConsolePanel;
 use crate::dock::{self, EditorTabViewer, Tab};
 #[cfg(not(target_os = "macos"))]
 use crate::menu;
+use crate::menu::MenuAction;
 #[cfg(target_os = "macos")]
 use crate::menu::NativeMenu;
+use crate::play_mode::{self, PlaySession};
+use crate::scene_file::{
+    self, RecentFiles, ScenePathDialog, ScenePathDialogKind, ScenePathDialogResult,
+};
 use crate::scene_view::SceneViewState;
 use crate::status_bar;
-use crate::toolbar::PlayState;
+use crate::toolbar::{PlayCommand, PlayState};
 
 /// A minimal EguiApp that does nothing.
 ///
@@ -51,6 +58,13 @@ impl EguiApp for NullEguiApp {
     }
 }
 
+/// Read-only editor overlay systems (debug grid, selection bounds).
+///
+/// Kept out of [`Update`] so they keep drawing while a play world is paused
+/// and its game schedules are skipped. Runs after every frame.
+struct EditorUpdate;
+impl ScheduleLabel for EditorUpdate {}
+
 /// An independent ECS world managed by the editor.
 pub struct EditorWorld {
     pub world: World,
@@ -63,6 +77,8 @@ pub struct EditorWorld {
     pub window_input: Arc<RwLock<WindowInput>>,
     /// Handle to the DebugDrawer resource for advance_tick / take_render_data.
     pub debug_drawer: Arc<RwLock<DebugDrawer>>,
+    /// VFS path of the scene document, or `None` for an unsaved scene.
+    pub scene_path: Option<String>,
 }
 
 pub struct Editor {
@@ -81,6 +97,8 @@ pub struct Editor {
     dock_state: DockState<Tab>,
     inspector_state: InspectorState,
     play_state: PlayState,
+    /// Edited world set aside while the game runs on a copy, if playing.
+    play_session: Option<PlaySession>,
     #[cfg(target_os = "macos")]
     native_menu: Option<NativeMenu>,
 
@@ -112,12 +130,40 @@ pub struct Editor {
     /// Pending prefab import from asset browser (VFS read in progress).
     pending_prefab_import: Option<PendingPrefabImport>,
 
-    /// Whether the "unsaved changes" dialog is currently shown.
-    show_close_dialog: bool,
+    /// Scene documents opened or saved recently.
+    recent_files: RecentFiles,
+    /// Open/Save As path prompt, if shown.
+    scene_dialog: Option<ScenePathDialog>,
+    /// VFS path of a scene being read for opening.
+    pending_scene_open: Option<String>,
+    /// VFS path of a scene being written.
+    pending_scene_save: Option<String>,
+
+    /// Action waiting on the "unsaved changes" dialog, if it is shown.
+    unsaved_dialog: Option<DeferredAction>,
+    /// Action to perform once the pending save finishes.
+    after_save: Option<DeferredAction>,
     /// Set to `true` when the user confirms closing (with or without saving).
     should_close: bool,
 }
 
+/// An action that discards the current scene and therefore asks about
+/// unsaved changes first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DeferredAction {
+    Close,
+    NewScene,
+    OpenScene(String),
+}
+
+/// Button pressed in the "unsaved changes" dialog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UnsavedChoice {
+    Save,
+    DontSave,
+    Cancel,
+}
+
 /// Tracks an in-flight VFS read for component import.
 struct PendingImport {
     vfs_path: String,
@@ -132,10 +178,14 @@ struct PendingPrefabImport {
 
 impl Editor {
     pub fn new() -> Self {
-        let project_path = std::path::Path::new("project.toml");
-        let (config, vfs) = crate::project::load_or_default(project_path);
-        let asset_browser = AssetBrowser::new(&config);
+        // Resolved up front so files kept next to the project don't follow
+        // later changes of the working directory.
+        let project_path = std::path::absolute("project.toml")
+            .unwrap_or_else(|_| std::path::PathBuf::from("project.toml"));
+        let (config, vfs) = crate::project::load_or_default(&project_path);
+        let asset_browser = AssetBrowser::new(&config, &vfs);
         let console = ConsolePanel::new(crate::log_capture::log_buffer());
+        let recent_files = RecentFiles::load(&project_path.with_file_name("recent_scenes.toml"));
 
         Self {
             worlds: Vec::new(),
@@ -148,6 +198,7 @@ impl Editor {
             dock_state: dock::create_default_layout(),
             inspector_state: InspectorState::new(),
             play_state: PlayState::Editing,
+            play_session: None,
             #[cfg(target_os = "macos")]
             native_menu: None,
             scene_view: None,
@@ -161,12 +212,20 @@ impl Editor {
             fps: 0.0,
             pending_import: None,
             pending_prefab_import: None,
-            show_close_dialog: false,
+            recent_files,
+            scene_dialog: None,
+            pending_scene_open: None,
+            pending_scene_save: None,
+            unsaved_dialog: None,
+            after_save: None,
             should_close: false,
         }
     }
 
-    /// Create a new editor world with a simple demo scene.
+    /// Create a new editor world with an empty scene.
+    ///
+    /// The world contains the editor camera, the rendering managers with the
+    /// built-in materials and meshes, and the editor schedules.
     fn create_editor_world(&self, scene_view: &SceneViewState, aspect: f32) -> EditorWorld {
         let mut world = World::new();
         register_std_components(&mut world);
@@ -192,6 +251,17 @@ impl Editor {
             );
         }
 
+        // Asset server with hot reload of files changed on any mount,
+        // including the standard shaders the materials above compile from.
+        // Changes come from the asset browser's watcher, see `update`.
+        let mut asset_server = AssetServer::new(self.vfs.clone(), self.runner.io().clone());
+        redlilium_ecs::shaders::watch_standard_shaders(
+            &mut asset_server,
+            &mut world.resource_mut::<MaterialManager>(),
+            crate::project::ENGINE_SHADERS_MOUNT,
+        );
+        world.insert_resource(asset_server);
+
         // Insert WindowInput resource
         let window_input_handle = world.insert_resource(WindowInput::default());
 
@@ -220,67 +290,13 @@ impl Editor {
         // from game queries and the world inspector by default.
         redlilium_ecs::mark_editor(&mut world, editor_camera);
 
-        // --- Demo scene entities ---
-        let cpu_cube = generators::generate_cube(0.5);
-        let cube_aabb = cpu_cube.compute_aabb();
-
-        // Register the cube mesh in MeshManager so prefab deserialization can find it
+        // Built-in cube mesh, so that scenes and prefabs referencing it by
+        // name can be deserialized
         world
             .resource_mut::<MeshManager>()
-            .create_mesh(&cpu_cube)
+            .create_mesh(&generators::generate_cube(0.5))
             .expect("Failed to register cube mesh");
 
-        // Ground plane (scaled flat cube)
-        {
-            let entity = world.spawn();
-            let transform = Transform::new(
-                Vec3::new(0.0, -0.05, 0.0),
-                redlilium_core::math::Quat::identity(),
-                Vec3::new(10.0, 0.1, 10.0),
-            );
-            world.insert(entity, transform).unwrap();
-            world
-                .insert(entity, GlobalTransform(transform.to_matrix()))
-                .unwrap();
-            world.insert(entity, Visibility::VISIBLE).unwrap();
-
-            let (per_entity, render_mat, mesh) = scene_view.create_entity_resources(&cpu_cube);
-            let render_mesh = match cube_aabb {
-                Some(aabb) => RenderMesh::with_aabb(mesh, aabb),
-                None => RenderMesh::new(mesh),
-            };
-            register_render_material(&world, &render_mat);
-            world.insert(entity, render_mesh).unwrap();
-            world.insert(entity, render_mat).unwrap();
-            world.insert(entity, per_entity).unwrap();
-        }
-
-        // 3 cubes at different positions
-        let cube_positions = [
-            Vec3::new(0.0, 0.5, 0.0),
-            Vec3::new(-2.0, 0.5, 1.0),
-            Vec3::new(1.5, 0.5, -1.0),
-        ];
-        for pos in &cube_positions {
-            let entity = world.spawn();
-            let transform = Transform::from_translation(*pos);
-            world.insert(entity, transform).unwrap();
-            world
-                .insert(entity, GlobalTransform(transform.to_matrix()))
-                .unwrap();
-            world.insert(entity, Visibility::VISIBLE).unwrap();
-
-            let (per_entity, render_mat, mesh) = scene_view.create_entity_resources(&cpu_cube);
-            let render_mesh = match cube_aabb {
-                Some(aabb) => RenderMesh::with_aabb(mesh, aabb),
-                None => RenderMesh::new(mesh),
-            };
-            register_render_material(&world, &render_mat);
-            world.insert(entity, render_mesh).unwrap();
-            world.insert(entity, render_mat).unwrap();
-            world.insert(entity, per_entity).unwrap();
-        }
-
         // Insert ActionQueue for editor action dispatch
         world.insert_resource(ActionQueue::<World>::new());
 
@@ -290,14 +306,17 @@ impl Editor {
         // --- Setup schedules ---
         let mut schedules = Schedules::new();
 
-        // Update: read-only editor systems (debug grid, future interaction systems).
-        // Systems here cannot mutate the world directly — they must push actions
-        // through the ActionQueue resource.
-        schedules.get_mut::<Update>().add(DrawGrid);
+        // Update: read-only until play mode adds game systems. Systems here
+        // cannot mutate the world directly — they must push actions through
+        // the ActionQueue resource.
+        schedules.get_mut::<Update>().set_read_only(true);
+
+        // EditorUpdate: read-only editor overlays, also drawn while paused.
+        schedules.get_mut::<EditorUpdate>().add(DrawGrid);
         schedules
-            .get_mut::<Update>()
+            .get_mut::<EditorUpdate>()
             .add(DrawSelectionAabb::default());
-        schedules.get_mut::<Update>().set_read_only(true);
+        schedules.get_mut::<EditorUpdate>().set_read_only(true);
 
         // PostUpdate: camera input -> transform propagation -> camera matrices.
         // Camera movement is viewport navigation, not a scene mutation, so it
@@ -307,6 +326,7 @@ impl Editor {
             .get_mut::<PostUpdate>()
             .add(UpdateGlobalTransforms);
         schedules.get_mut::<PostUpdate>().add(UpdateCameraMatrices);
+        schedules.get_mut::<PostUpdate>().add(UpdateSkinJoints);
         schedules
             .get_mut::<PostUpdate>()
             .add_edge::<UpdateFreeFlyCamera, UpdateGlobalTransforms>()
@@ -315,11 +335,35 @@ impl Editor {
             .get_mut::<PostUpdate>()
             .add_edge::<UpdateGlobalTransforms, UpdateCameraMatrices>()
             .expect("No cycle");
+        schedules
+            .get_mut::<PostUpdate>()
+            .add_edge::<UpdateGlobalTransforms, UpdateSkinJoints>()
+            .expect("No cycle");
+
+        // Hot reload: finish asset loads, then apply changed shaders and
+        // re-spawn prefab instances before their render entities are initialized.
+        schedules.get_mut::<PostUpdate>().add(UpdateAssetServer);
+        schedules.get_mut::<PostUpdate>().add(ReloadMaterialShaders);
+        schedules
+            .get_mut::<PostUpdate>()
+            .add_exclusive(SyncPrefabInstances::new());
+        schedules
+            .get_mut::<PostUpdate>()
+            .add_edge::<UpdateAssetServer, ReloadMaterialShaders>()
+            .expect("No cycle");
+        schedules
+            .get_mut::<PostUpdate>()
+            .add_edge::<UpdateAssetServer, SyncPrefabInstances>()
+            .expect("No cycle");
 
         // Initialize GPU resources for newly deserialized render entities.
         schedules
             .get_mut::<PostUpdate>()
             .add_exclusive(InitializeRenderEntities);
+        schedules
+            .get_mut::<PostUpdate>()
+            .add_edge::<SyncPrefabInstances, InitializeRenderEntities>()
+            .expect("No cycle");
 
         // Automatic GPU sync systems — run after camera matrices are computed.
         schedules
@@ -330,6 +374,10 @@ impl Editor {
             .get_mut::<PostUpdate>()
             .add_edge::<UpdateCameraMatrices, UpdatePerEntityUniforms>()
             .expect("No cycle");
+        schedules
+            .get_mut::<PostUpdate>()
+            .add_edge::<UpdateSkinJoints, UpdatePerEntityUniforms>()
+            .expect("No cycle");
         schedules
             .get_mut::<PostUpdate>()
             .add_edge::<UpdateCameraMatrices, SyncMaterialUniforms>()
@@ -342,6 +390,7 @@ impl Editor {
             editor_camera,
             window_input: window_input_handle,
             debug_drawer: debug_drawer_handle,
+            scene_path: None,
         }
     }
 
@@ -350,10 +399,24 @@ impl Editor {
         &self.worlds[self.active_world]
     }
 
+    /// The world being edited: the one set aside while playing, otherwise
+    /// the active world.
+    fn edit_world(&self) -> &EditorWorld {
+        match &self.play_session {
+            Some(session) => &session.edit_world,
+            None => self.active_world(),
+        }
+    }
+
     /// Returns `true` if any editor world has unsaved changes.
     fn has_unsaved_changes(&self) -> bool {
         self.worlds
             .iter()
+            .chain(
+                self.play_session
+                    .as_ref()
+                    .map(|session| &session.edit_world),
+            )
             .any(|ew| ew.history.has_unsaved_changes())
     }
 
@@ -405,6 +468,329 @@ impl Editor {
                 redlilium_core::math::perspective_rh(FRAC_PI_4, aspect, 0.1, 500.0);
         }
     }
+
+    // --- Scene documents ---
+
+    /// Dispatch a menu action.
+    fn handle_menu_action(&mut self, action: MenuAction) {
+        match action {
+            MenuAction::New => self.request_action(DeferredAction::NewScene),
+            MenuAction::Open => {
+                let path = self.default_scene_path();
+                self.scene_dialog = Some(ScenePathDialog::new(ScenePathDialogKind::Open, path));
+            }
+            MenuAction::OpenRecent(path) => self.request_action(DeferredAction::OpenScene(path)),
+            MenuAction::Save => self.save_scene(None),
+            MenuAction::SaveAs => self.show_save_as_dialog(),
+            MenuAction::CloseWindow => self.request_action(DeferredAction::Close),
+            MenuAction::Undo if !self.worlds.is_empty() => {
+                let ew = &mut self.worlds[self.active_world];
+                if let Err(e) = ew.history.undo(&mut ew.world) {
+                    log::warn!("Undo failed: {e}");
+                }
+            }
+            MenuAction::Redo if !self.worlds.is_empty() => {
+                let ew = &mut self.worlds[self.active_world];
+                if let Err(e) = ew.history.redo(&mut ew.world) {
+                    log::warn!("Redo failed: {e}");
+                }
+            }
+            _ => log::info!("Menu action: {action:?}"),
+        }
+    }
+
+    /// Perform an action that discards the current scene, asking about
+    /// unsaved changes first.
+    fn request_action(&mut self, action: DeferredAction) {
+        if self.has_unsaved_changes() {
+            self.unsaved_dialog = Some(action);
+        } else {
+            self.perform_action(action);
+        }
+    }
+
+    /// Perform an action without checking for unsaved changes.
+    fn perform_action(&mut self, action: DeferredAction) {
+        match action {
+            DeferredAction::Close => self.should_close = true,
+            DeferredAction::NewScene => self.new_scene(),
+            DeferredAction::OpenScene(path) => {
+                log::info!("Opening scene: {path}");
+                self.asset_browser.dispatch_read(&self.vfs, &path);
+                self.pending_scene_open = Some(path);
+            }
+        }
+    }
+
+    /// Handle the user's choice in the "unsaved changes" dialog.
+    fn resolve_unsaved_dialog(&mut self, choice: UnsavedChoice) {
+        let Some(action) = self.unsaved_dialog.take() else {
+            return;
+        };
+        match choice {
+            UnsavedChoice::Save => {
+                self.after_save = Some(action);
+                self.save_scene(None);
+            }
+            UnsavedChoice::DontSave => self.perform_action(action),
+            UnsavedChoice::Cancel => {}
+        }
+    }
+
+    /// VFS path pre-filled in the Open / Save As dialogs.
+    fn default_scene_path(&self) -> String {
+        self.worlds
+            .get(self.active_world)
+            .and_then(|ew| ew.scene_path.clone())
+            .unwrap_or_else(|| format!("scenes/untitled.{}", scene_file::SCENE_EXTENSION))
+    }
+
+    fn show_save_as_dialog(&mut self) {
+        let path = self.default_scene_path();
+        self.scene_dialog = Some(ScenePathDialog::new(ScenePathDialogKind::SaveAs, path));
+    }
+
+    /// Handle a path confirmed in the Open / Save As dialog.
+    fn resolve_scene_dialog(&mut self, kind: ScenePathDialogKind, path: &str) {
+        let path = match self.vfs.canonical_path(path) {
+            Ok(path) => path,
+            Err(e) => {
+                log::error!("Invalid scene path '{path}': {e}");
+                self.after_save = None;
+                return;
+            }
+        };
+        match kind {
+            ScenePathDialogKind::Open => self.request_action(DeferredAction::OpenScene(path)),
+            ScenePathDialogKind::SaveAs => self.save_scene(Some(path)),
+        }
+    }
+
+    /// Write the edited scene to `path`, or to its current path if `None`.
+    ///
+    /// Untitled scenes prompt for a path instead. The history is marked as
+    /// saved once the write has completed. While playing, the edited world
+    /// kept by the play session is saved and play continues.
+    fn save_scene(&mut self, path: Option<String>) {
+        if self.worlds.is_empty() {
+            return;
+        }
+        let Some(path) = path.or_else(|| self.edit_world().scene_path.clone()) else {
+            self.show_save_as_dialog();
+            return;
+        };
+        match scene_file::encode_scene(&self.edit_world().world) {
+            Ok(data) => {
+                log::info!("Saving scene to: {path}");
+                self.asset_browser.dispatch_write(&self.vfs, &path, data);
+                self.pending_scene_save = Some(path);
+            }
+            Err(e) => {
+                log::error!("{e}");
+                self.after_save = None;
+            }
+        }
+    }
+
+    /// Replace the active world with a fresh, empty scene.
+    fn new_scene(&mut self) {
+        let Some(scene_view) = &self.scene_view else {
+            return;
+        };
+        let mut ew = self.create_editor_world(scene_view, scene_view.aspect_ratio());
+        // An untouched empty scene has nothing worth saving
+        ew.history.mark_saved();
+        self.replace_active_world(ew);
+        log::info!("New scene");
+    }
+
+    /// Restore a scene document into a fresh editor world.
+    fn finish_open_scene(&mut self, path: String, data: &[u8]) {
+        let scene = match scene_file::decode_scene(data) {
+            Ok(scene) => scene,
+            Err(e) => {
+                log::error!("Failed to open {path}: {e}");
+                return;
+            }
+        };
+        let Some(scene_view) = &self.scene_view else {
+            return;
+        };
+        let mut ew = self.create_editor_world(scene_view, scene_view.aspect_ratio());
+        if let Err(e) = ew.world.deserialize_scene(&scene) {
+            log::error!("Failed to load scene {path}: {e}");
+            return;
+        }
+        ew.scene_path = Some(path.clone());
+        ew.history.mark_saved();
+        self.replace_active_world(ew);
+        self.recent_files.push(&path);
+        log::info!("Opened scene: {path}");
+    }
+
+    /// Make `ew` the active world, dropping the previous one.
+    fn replace_active_world(&mut self, mut ew: EditorWorld) {
+        self.stop_play();
+        ew.schedules.run_startup(&mut ew.world, &self.runner);
+        if self.worlds.is_empty() {
+            self.active_world = 0;
+            self.worlds.push(ew);
+        } else {
+            self.worlds[self.active_world] = ew;
+        }
+
+        self.reset_world_ui_state();
+    }
+
+    /// Drop UI state referring to entities of the previous active world.
+    ///
+    /// Returns the previous inspector state. The inspector filter settings
+    /// are kept.
+    fn reset_world_ui_state(&mut self) -> InspectorState {
+        let mut inspector_state = InspectorState::new();
+        inspector_state.filter = self.inspector_state.filter.clone();
+        inspector_state.show_editor_entities = self.inspector_state.show_editor_entities;
+        self.pending_import = None;
+        self.pending_prefab_import = None;
+        std::mem::replace(&mut self.inspector_state, inspector_state)
+    }
+
+    // --- Play mode ---
+
+    /// Apply a play control button.
+    fn handle_play_command(&mut self, command: PlayCommand) {
+        match command {
+            PlayCommand::Play => self.start_play(),
+            PlayCommand::Pause => self.play_state = PlayState::Paused,
+            PlayCommand::Resume => self.play_state = PlayState::Playing,
+            PlayCommand::Step => {
+                if let Some(session) = &mut self.play_session {
+                    session.step_pending = true;
+                    self.play_state = PlayState::Paused;
+                }
+            }
+            PlayCommand::Stop => self.stop_play(),
+        }
+    }
+
+    /// Copy the active world and start running the game schedules on the copy.
+    ///
+    /// The edited world and its undo history are set aside untouched until
+    /// [`stop_play`](Self::stop_play).
+    fn start_play(&mut self) {
+        if self.play_session.is_some() || self.worlds.is_empty() {
+            return;
+        }
+        let Some(scene_view) = &self.scene_view else {
+            return;
+        };
+        let mut play = self.create_editor_world(scene_view, scene_view.aspect_ratio());
+        let edit = &self.worlds[self.active_world];
+        play_mode::copy_scene(&edit.world, &mut play.world);
+
+        // Keep the viewport where it is
+        if let Some(free_fly) = edit.world.get::<FreeFlyCamera>(edit.editor_camera).copied() {
+            play.world.insert(play.editor_camera, free_fly).unwrap();
+            play.world
+                .insert(play.editor_camera, free_fly.to_transform())
+                .unwrap();
+        }
+        play.scene_path = edit.scene_path.clone();
+        play.history.mark_saved();
+
+        play_mode::add_game_systems(&mut play.world, &mut play.schedules);
+        play.schedules.run_startup(&mut play.world, &self.runner);
+
+        let edit_world = std::mem::replace(&mut self.worlds[self.active_world], play);
+        let inspector_state = self.reset_world_ui_state();
+        self.play_session = Some(PlaySession {
+            edit_world,
+            inspector_state,
+            step_pending: false,
+        });
+        self.play_state = PlayState::Playing;
+        log::info!("Entered play mode");
+    }
+
+    /// Discard the play copy and restore the edited world with its undo history.
+    fn stop_play(&mut self) {
+        let Some(session) = self.play_session.take() else {
+            return;
+        };
+        self.worlds[self.active_world] = session.edit_world;
+        self.reset_world_ui_state();
+        self.inspector_state = session.inspector_state;
+        self.play_state = PlayState::Editing;
+        // The viewport may have been resized while playing
+        if let Some(scene_view) = &self.scene_view {
+            self.update_camera_projection(scene_view.aspect_ratio());
+        }
+        log::info!("Exited play mode");
+    }
+
+    /// Time to advance the active world by this frame, or `None` to skip
+    /// the game schedules.
+    ///
+    /// A paused play world skips them entirely, so that viewport navigation
+    /// and render sync keep working while the game stands still. A pending
+    /// step advances it by exactly one fixed timestep.
+    fn frame_delta(&mut self, delta_time: f64) -> Option<f64> {
+        if self.play_state != PlayState::Paused {
+            return Some(delta_time);
+        }
+        let step = self
+            .play_session
+            .as_mut()
+            .is_some_and(|session| std::mem::take(&mut session.step_pending));
+        step.then(|| self.active_world().world.resource::<Time>().fixed_delta())
+    }
+
+    /// Apply finished scene reads and writes. Call after polling the asset browser.
+    fn poll_scene_io(&mut self) {
+        if let Some(path) = &self.pending_scene_save
+            && let Some(&(_, succeeded)) = self
+                .asset_browser
+                .completed_writes
+                .iter()
+                .find(|(written, _)| written == path)
+        {
+            let path = self.pending_scene_save.take().unwrap_or_default();
+            if succeeded && !self.worlds.is_empty() {
+                // The play copy shows the same document as the edited world.
+                self.worlds[self.active_world].scene_path = Some(path.clone());
+                let ew = match &mut self.play_session {
+                    Some(session) => &mut session.edit_world,
+                    None => &mut self.worlds[self.active_world],
+                };
+                ew.history.mark_saved();
+                ew.scene_path = Some(path.clone());
+                self.recent_files.push(&path);
+                log::info!("Saved scene: {path}");
+                if let Some(action) = self.after_save.take() {
+                    self.perform_action(action);
+                }
+            } else {
+                self.after_save = None;
+            }
+        }
+
+        if let Some(path) = &self.pending_scene_open {
+            if let Some(idx) = self
+                .asset_browser
+                .completed_reads
+                .iter()
+                .position(|(read, _)| read == path)
+            {
+                let (path, data) = self.asset_browser.completed_reads.remove(idx);
+                self.pending_scene_open = None;
+                self.finish_open_scene(path, &data);
+            } else if self.asset_browser.failed_reads.contains(path) {
+                log::error!("Failed to open {path}");
+                self.recent_files.remove(path);
+                self.pending_scene_open = None;
+            }
+        }
+    }
 }
 
 impl AppHandler for Editor {
@@ -427,7 +813,8 @@ impl AppHandler for Editor {
 
         // Create the first editor world with a demo scene
         let aspect = ctx.aspect_ratio();
-        let editor_world = self.create_editor_world(&scene_view, aspect);
+        let mut editor_world = self.create_editor_world(&scene_view, aspect);
+        spawn_demo_scene(&mut editor_world.world, &scene_view);
         self.worlds.push(editor_world);
 
         self.scene_view = Some(scene_view);
@@ -468,7 +855,7 @@ impl AppHandler for Editor {
 
     fn on_close_requested(&mut self, _ctx: &mut AppContext) -> bool {
         if self.has_unsaved_changes() {
-            self.show_close_dialog = true;
+            self.unsaved_dialog = Some(DeferredAction::Close);
             return false; // don't close yet — show dialog first
         }
         true
@@ -550,35 +937,32 @@ impl AppHandler for Editor {
 
         // Poll native menu events (macOS only)
         #[cfg(target_os = "macos")]
-        if let Some(menu) = &self.native_menu
-            && let Some(action) = menu.poll_event()
         {
-            use crate::menu::MenuAction;
-            let ew = &mut self.worlds[self.active_world];
-            match action {
-                MenuAction::Save => {
-                    ew.history.mark_saved();
-                    log::info!("Saved");
-                }
-                MenuAction::Undo => {
-                    if let Err(e) = ew.history.undo(&mut ew.world) {
-                        log::warn!("Undo failed: {e}");
-                    }
-                }
-                MenuAction::Redo => {
-                    if let Err(e) = ew.history.redo(&mut ew.world) {
-                        log::warn!("Redo failed: {e}");
-                    }
-                }
-                _ => log::info!("Menu action: {action:?}"),
+            if let Some(menu) = &mut self.native_menu {
+                menu.set_recent_files(self.recent_files.paths());
+            }
+            if let Some(action) = self.native_menu.as_ref().and_then(|m| m.poll_event()) {
+                self.handle_menu_action(action);
             }
         }
 
         // Sync ui_wants_input flag from previous frame's egui state
         self.sync_input_flags();
 
-        // Poll completed background VFS results for the asset browser
+        // Poll completed background VFS results and file changes for the
+        // asset browser, and hand the changes on to every world's assets
         self.asset_browser.poll();
+        if !self.asset_browser.changes.is_empty() {
+            let edit_world = self.play_session.as_mut().map(|s| &mut s.edit_world);
+            for ew in self.worlds.iter_mut().chain(edit_world) {
+                if ew.world.has_resource::<AssetServer>() {
+                    ew.world
+                        .resource_mut::<AssetServer>()
+                        .apply_changes(&self.asset_browser.changes);
+                }
+            }
+        }
+        self.poll_scene_io();
 
         // Advance debug drawer tick (systems will write to the new tick)
         {
@@ -601,8 +985,11 @@ impl AppHandler for Editor {
             }
         }
 
-        // Run ECS schedules (always run in editing mode for camera/transforms)
+        // Run ECS schedules (always run in editing mode for camera/transforms;
+        // in play mode the active world is the play copy with game systems,
+        // which are skipped while paused)
         {
+            let delta_time = self.frame_delta(ctx.delta_time() as f64);
             let Editor {
                 worlds,
                 active_world,
@@ -610,8 +997,12 @@ impl AppHandler for Editor {
                 ..
             } = self;
             let ew = &mut worlds[*active_world];
+            match delta_time {
+                Some(delta_time) => ew.schedules.run_frame(&mut ew.world, runner, delta_time),
+                None => ew.schedules.run_paused_frame(&mut ew.world, runner),
+            }
             ew.schedules
-                .run_frame(&mut ew.world, runner, ctx.delta_time() as f64);
+                .run_schedule::<EditorUpdate>(&mut ew.world, runner);
         }
 
         // Process component export (inspector → asset browser)
@@ -726,7 +1117,7 @@ impl AppHandler for Editor {
                 redlilium_ecs::serialize::Format::Ron,
             ) {
                 Ok(serialized) => {
-                    let action = SpawnPrefabAction::new(serialized, parent);
+                    let action = SpawnPrefabAction::new(serialized, parent).with_source(path);
                     let ew = &mut self.worlds[self.active_world];
                     if let Err(e) = ew.history.execute(Box::new(action), &mut ew.world) {
                         log::warn!("Prefab spawn action failed: {e}");
@@ -758,6 +1149,12 @@ impl AppHandler for Editor {
         let mut ui_graph = ctx.acquire_graph();
         let mut scene_view_rect = None;
         let mut pixels_per_point = 1.0;
+        // UI results that need `&mut self`, applied after the egui frame
+        #[allow(unused_mut)]
+        let mut menu_action = None;
+        let mut play_command = None;
+        let mut unsaved_choice = None;
+        let mut scene_dialog_result = ScenePathDialogResult::Pending;
 
         if let Some(egui) = &mut self.egui_controller {
             let width = ctx.width();
@@ -782,8 +1179,7 @@ impl AppHandler for Editor {
                             // Traffic lights occupy ~70px on the left
                             let available = ui.available_width();
                             ui.add_space((available / 2.0 - 40.0).max(0.0));
-                            self.play_state =
-                                crate::toolbar::draw_play_controls(ui, self.play_state);
+                            play_command = crate::toolbar::draw_play_controls(ui, self.play_state);
                         });
 
                         // Double-click on background toggles maximize
@@ -806,46 +1202,15 @@ impl AppHandler for Editor {
             // Menu bar with play controls (egui fallback for non-macOS platforms)
             #[cfg(not(target_os = "macos"))]
             {
-                let result =
-                    menu::draw_menu_bar(&egui_ctx, &window, custom_titlebar, self.play_state);
-                self.play_state = result.play_state;
-                if let Some(action) = result.action {
-                    use crate::menu::MenuAction;
-                    match action {
-                        MenuAction::CloseWindow => {
-                            if self
-                                .worlds
-                                .iter()
-                                .any(|ew| ew.history.has_unsaved_changes())
-                            {
-                                self.show_close_dialog = true;
-                            } else {
-                                self.should_close = true;
-                            }
-                        }
-                        _ if !self.worlds.is_empty() => {
-                            let ew = &mut self.worlds[self.active_world];
-                            match action {
-                                MenuAction::Save => {
-                                    ew.history.mark_saved();
-                                    log::info!("Saved");
-                                }
-                                MenuAction::Undo => {
-                                    if let Err(e) = ew.history.undo(&mut ew.world) {
-                                        log::warn!("Undo failed: {e}");
-                                    }
-                                }
-                                MenuAction::Redo => {
-                                    if let Err(e) = ew.history.redo(&mut ew.world) {
-                                        log::warn!("Redo failed: {e}");
-                                    }
-                                }
-                                _ => log::info!("Menu action: {action:?}"),
-                            }
-                        }
-                        _ => {}
-                    }
-                }
+                let result = menu::draw_menu_bar(
+                    &egui_ctx,
+                    &window,
+                    custom_titlebar,
+                    self.play_state,
+                    self.recent_files.paths(),
+                );
+                play_command = result.play_command;
+                menu_action = result.action;
             }
 
             // Update smoothed FPS from frame delta
@@ -938,21 +1303,8 @@ impl AppHandler for Editor {
                 });
 
             // Modal "Unsaved Changes" dialog
-            if self.show_close_dialog {
-                // Full-screen dimming overlay that captures all interaction.
-                egui::Area::new("close_dialog_overlay".into())
-                    .fixed_pos(egui::pos2(0.0, 0.0))
-                    .order(egui::Order::Foreground)
-                    .interactable(true)
-                    .show(&egui_ctx, |ui| {
-                        let screen = ui.ctx().input(|i| i.viewport_rect());
-                        ui.allocate_rect(screen, egui::Sense::click());
-                        ui.painter().rect_filled(
-                            screen,
-                            egui::CornerRadius::ZERO,
-                            egui::Color32::from_black_alpha(128),
-                        );
-                    });
+            if self.unsaved_dialog.is_some() {
+                show_modal_overlay(&egui_ctx);
 
                 // Dialog window, centered, above the overlay.
                 egui::Window::new("Unsaved Changes")
@@ -965,21 +1317,20 @@ impl AppHandler for Editor {
                         ui.add_space(8.0);
                         ui.horizontal(|ui| {
                             if ui.button("Save").clicked() {
-                                for ew in &mut self.worlds {
-                                    ew.history.mark_saved();
-                                }
-                                self.show_close_dialog = false;
-                                self.should_close = true;
+                                unsaved_choice = Some(UnsavedChoice::Save);
                             }
                             if ui.button("Don't Save").clicked() {
-                                self.show_close_dialog = false;
-                                self.should_close = true;
+                                unsaved_choice = Some(UnsavedChoice::DontSave);
                             }
                             if ui.button("Cancel").clicked() {
-                                self.show_close_dialog = false;
+                                unsaved_choice = Some(UnsavedChoice::Cancel);
                             }
                         });
                     });
+            } else if let Some(dialog) = &mut self.scene_dialog {
+                // Modal Open / Save As path prompt
+                show_modal_overlay(&egui_ctx);
+                scene_dialog_result = dialog.show(&egui_ctx);
             }
 
             // Store egui input state for next frame.
@@ -1002,6 +1353,28 @@ impl AppHandler for Editor {
             }
         }
 
+        if let Some(command) = play_command {
+            self.handle_play_command(command);
+        }
+        if let Some(action) = menu_action {
+            self.handle_menu_action(action);
+        }
+        if let Some(choice) = unsaved_choice {
+            self.resolve_unsaved_dialog(choice);
+        }
+        match scene_dialog_result {
+            ScenePathDialogResult::Pending => {}
+            ScenePathDialogResult::Cancelled => {
+                self.scene_dialog = None;
+                self.after_save = None;
+            }
+            ScenePathDialogResult::Confirmed(path) => {
+                if let Some(dialog) = self.scene_dialog.take() {
+                    self.resolve_scene_dialog(dialog.kind(), &path);
+                }
+            }
+        }
+
         // Update viewport/scissor from SceneView panel rect (outside egui block)
         if let Some(rect) = scene_view_rect {
             // Store physical-pixel rect for input hit-testing
@@ -1262,6 +1635,23 @@ impl AppHandler for Editor {
     }
 }
 
+/// Full-screen dimming overlay that captures all interaction below modal dialogs.
+fn show_modal_overlay(ctx: &egui::Context) {
+    egui::Area::new("modal_dialog_overlay".into())
+        .fixed_pos(egui::pos2(0.0, 0.0))
+        .order(egui::Order::Foreground)
+        .interactable(true)
+        .show(ctx, |ui| {
+            let screen = ui.ctx().input(|i| i.viewport_rect());
+            ui.allocate_rect(screen, egui::Sense::click());
+            ui.painter().rect_filled(
+                screen,
+                egui::CornerRadius::ZERO,
+                egui::Color32::from_black_alpha(128),
+            );
+        });
+}
+
 /// Show a floating label near the cursor for any active drag payload.
 fn show_drag_overlay(ctx: &egui::Context, world: &World) {
     let label = if let Some(entity) = egui::DragAndDrop::payload::<Entity>(ctx) {
@@ -1320,3 +1710,61 @@ fn register_render_material(world: &World, render_mat: &redlilium_ecs::RenderMat
         );
     }
 }
+
+/// Spawn the demo scene shown when the editor starts: a ground plane and
+/// three cubes.
+fn spawn_demo_scene(world: &mut World, scene_view: &SceneViewState) {
+    let cpu_cube = generators::generate_cube(0.5);
+    let cube_aabb = cpu_cube.compute_aabb();
+
+    // Ground plane (scaled flat cube)
+    {
+        let entity = world.spawn();
+        let transform = Transform::new(
+            Vec3::new(0.0, -0.05, 0.0),
+            redlilium_core::math::Quat::identity(),
+            Vec3::new(10.0, 0.1, 10.0),
+        );
+        world.insert(entity, transform).unwrap();
+        world
+            .insert(entity, GlobalTransform(transform.to_matrix()))
+            .unwrap();
+        world.insert(entity, Visibility::VISIBLE).unwrap();
+
+        let (per_entity, render_mat, mesh) = scene_view.create_entity_resources(&cpu_cube);
+        let render_mesh = match cube_aabb {
+            Some(aabb) => RenderMesh::with_aabb(mesh, aabb),
+            None => RenderMesh::new(mesh),
+        };
+        register_render_material(world, &render_mat);
+        world.insert(entity, render_mesh).unwrap();
+        world.insert(entity, render_mat).unwrap();
+        world.insert(entity, per_entity).unwrap();
+    }
+
+    // 3 cubes at different positions
+    let cube_positions = [
+        Vec3::new(0.0, 0.5, 0.0),
+        Vec3::new(-2.0, 0.5, 1.0),
+        Vec3::new(1.5, 0.5, -1.0),
+    ];
+    for pos in &cube_positions {
+        let entity = world.spawn();
+        let transform = Transform::from_translation(*pos);
+        world.insert(entity, transform).unwrap();
+        world
+            .insert(entity, GlobalTransform(transform.to_matrix()))
+            .unwrap();
+        world.insert(entity, Visibility::VISIBLE).unwrap();
+
+        let (per_entity, render_mat, mesh) = scene_view.create_entity_resources(&cpu_cube);
+        let render_mesh = match cube_aabb {
+            Some(aabb) => RenderMesh::with_aabb(mesh, aabb),
+            None => RenderMesh::new(mesh),
+        };
+        register_render_material(world, &render_mat);
+        world.insert(entity, render_mesh).unwrap();
+        world.insert(entity, render_mat).unwrap();
+        world.insert(entity, per_entity).unwrap();
+    }
+}
@@ -3,11 +3,12 @@ mod background_vfs;
 mod console;
 mod dock;
 mod editor;
-mod fs_watcher;
 mod history_panel;
 mod log_capture;
 mod menu;
+mod play_mode;
 mod project;
+mod scene_file;
 mod scene_view;
 mod status_bar;
 mod theme;
@@ -8,7 +8,11 @@ use winit::window::Window;
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum MenuAction {
     About,
+    New,
+    Open,
+    OpenRecent(String),
     Save,
+    SaveAs,
     Undo,
     Redo,
     #[allow(dead_code)]
@@ -28,9 +32,15 @@ mod native {
         #[allow(dead_code)]
         menu: muda::Menu,
         about_id: MenuId,
+        new_id: MenuId,
+        open_id: MenuId,
         save_id: MenuId,
+        save_as_id: MenuId,
         undo_id: MenuId,
         redo_id: MenuId,
+        recent_submenu: muda::Submenu,
+        /// Items currently in the "Open Recent" submenu with their VFS paths.
+        recent_items: Vec<(muda::MenuItem, String)>,
     }
 
     impl NativeMenu {
@@ -56,6 +66,23 @@ mod native {
             .expect("failed to create app submenu");
 
             // File submenu
+            let new_item = MenuItem::new(
+                "New Scene",
+                true,
+                Some(Accelerator::new(
+                    Some(muda::accelerator::Modifiers::META),
+                    muda::accelerator::Code::KeyN,
+                )),
+            );
+            let open_item = MenuItem::new(
+                "Open Scene...",
+                true,
+                Some(Accelerator::new(
+                    Some(muda::accelerator::Modifiers::META),
+                    muda::accelerator::Code::KeyO,
+                )),
+            );
+            let recent_submenu = Submenu::new("Open Recent", true);
             let save_item = MenuItem::new(
                 "Save",
                 true,
@@ -64,10 +91,32 @@ mod native {
                     muda::accelerator::Code::KeyS,
                 )),
             );
+            let save_as_item = MenuItem::new(
+                "Save As...",
+                true,
+                Some(Accelerator::new(
+                    Some(muda::accelerator::Modifiers::META | muda::accelerator::Modifiers::SHIFT),
+                    muda::accelerator::Code::KeyS,
+                )),
+            );
+            let new_id = new_item.id().clone();
+            let open_id = open_item.id().clone();
             let save_id = save_item.id().clone();
+            let save_as_id = save_as_item.id().clone();
 
-            let file_submenu = Submenu::with_items("File", true, &[&save_item])
-                .expect("failed to create file submenu");
+            let file_submenu = Submenu::with_items(
+                "File",
+                true,
+                &[
+                    &new_item,
+                    &open_item,
+                    &recent_submenu,
+                    &PredefinedMenuItem::separator(),
+                    &save_item,
+                    &save_as_item,
+                ],
+            )
+            .expect("failed to create file submenu");
 
             // Edit submenu
             let undo_item = MenuItem::new(
@@ -104,9 +153,35 @@ mod native {
             NativeMenu {
                 menu,
                 about_id,
+                new_id,
+                open_id,
                 save_id,
+                save_as_id,
                 undo_id,
                 redo_id,
+                recent_submenu,
+                recent_items: Vec::new(),
+            }
+        }
+
+        /// Rebuild the "Open Recent" submenu from a list of VFS paths.
+        pub fn set_recent_files(&mut self, paths: &[String]) {
+            if self
+                .recent_items
+                .iter()
+                .map(|(_, path)| path)
+                .eq(paths.iter())
+            {
+                return;
+            }
+            for (item, _) in self.recent_items.drain(..) {
+                let _ = self.recent_submenu.remove(&item);
+            }
+            for path in paths {
+                let item = muda::MenuItem::new(path, true, None);
+                if self.recent_submenu.append(&item).is_ok() {
+                    self.recent_items.push((item, path.clone()));
+                }
             }
         }
 
@@ -115,8 +190,20 @@ mod native {
             if let Ok(event) = MenuEvent::receiver().try_recv() {
                 if event.id == self.about_id {
                     return Some(MenuAction::About);
+                } else if event.id == self.new_id {
+                    return Some(MenuAction::New);
+                } else if event.id == self.open_id {
+                    return Some(MenuAction::Open);
                 } else if event.id == self.save_id {
                     return Some(MenuAction::Save);
+                } else if event.id == self.save_as_id {
+                    return Some(MenuAction::SaveAs);
+                } else if let Some((_, path)) = self
+                    .recent_items
+                    .iter()
+                    .find(|(item, _)| event.id == *item.id())
+                {
+                    return Some(MenuAction::OpenRecent(path.clone()));
                 } else if event.id == self.undo_id {
                     return Some(MenuAction::Undo);
                 } else if event.id == self.redo_id {
@@ -136,7 +223,7 @@ pub use native::NativeMenu;
 #[cfg(not(target_os = "macos"))]
 pub struct MenuBarResult {
     pub action: Option<MenuAction>,
-    pub play_state: crate::toolbar::PlayState,
+    pub play_command: Option<crate::toolbar::PlayCommand>,
 }
 
 #[cfg(not(target_os = "macos"))]
@@ -145,9 +232,10 @@ pub fn draw_menu_bar(
     window: &Arc<Window>,
     custom_titlebar: bool,
     play_state: crate::toolbar::PlayState,
+    recent_files: &[String],
 ) -> MenuBarResult {
     let mut action = None;
-    let mut new_play_state = play_state;
+    let mut play_command = None;
     let window_for_drag = window.clone();
 
     egui::TopBottomPanel::top("menu_bar").show(ctx, |ui| {
@@ -160,6 +248,31 @@ pub fn draw_menu_bar(
                     }
                 });
                 ui.menu_button("File", |ui| {
+                    if ui
+                        .add(egui::Button::new("New Scene").shortcut_text("Ctrl+N"))
+                        .clicked()
+                    {
+                        action = Some(MenuAction::New);
+                        ui.close();
+                    }
+                    if ui
+                        .add(egui::Button::new("Open Scene...").shortcut_text("Ctrl+O"))
+                        .clicked()
+                    {
+                        action = Some(MenuAction::Open);
+                        ui.close();
+                    }
+                    ui.add_enabled_ui(!recent_files.is_empty(), |ui| {
+                        ui.menu_button("Open Recent", |ui| {
+                            for path in recent_files {
+                                if ui.button(path).clicked() {
+                                    action = Some(MenuAction::OpenRecent(path.clone()));
+                                    ui.close();
+                                }
+                            }
+                        });
+                    });
+                    ui.separator();
                     if ui
                         .add(egui::Button::new("Save").shortcut_text("Ctrl+S"))
                         .clicked()
@@ -167,6 +280,13 @@ pub fn draw_menu_bar(
                         action = Some(MenuAction::Save);
                         ui.close();
                     }
+                    if ui
+                        .add(egui::Button::new("Save As...").shortcut_text("Ctrl+Shift+S"))
+                        .clicked()
+                    {
+                        action = Some(MenuAction::SaveAs);
+                        ui.close();
+                    }
                 });
                 ui.menu_button("Edit", |ui| {
                     if ui
@@ -198,14 +318,14 @@ pub fn draw_menu_bar(
             // the window controls.
             let play_rect = egui::Rect::from_min_size(
                 egui::pos2(usable_center - 40.0, full_rect.top()),
-                egui::vec2(120.0, full_rect.height()),
+                egui::vec2(180.0, full_rect.height()),
             );
             let mut play_ui = ui.new_child(
                 egui::UiBuilder::new()
                     .max_rect(play_rect)
                     .layout(egui::Layout::left_to_right(egui::Align::Center)),
             );
-            new_play_state = crate::toolbar::draw_play_controls(&mut play_ui, play_state);
+            play_command = crate::toolbar::draw_play_controls(&mut play_ui, play_state);
 
             // Right: window control buttons (custom titlebar only)
             if custom_titlebar {
@@ -238,7 +358,7 @@ pub fn draw_menu_bar(
 
     MenuBarResult {
         action,
-        play_state: new_play_state,
+        play_command,
     }
 }
 
@@ -0,0 +1,66 @@
+use redlilium_ecs::physics::physics2d::PhysicsWorld2D;
+use redlilium_ecs::physics::physics3d::PhysicsWorld3D;
+use redlilium_ecs::physics::systems2d::{StepPhysics2D, SyncPhysicsBodies2D, SyncPhysicsJoints2D};
+use redlilium_ecs::physics::systems3d::{StepPhysics3D, SyncPhysicsBodies3D, SyncPhysicsJoints3D};
+use redlilium_ecs::ui::InspectorState;
+use redlilium_ecs::{AnimateTransforms, FixedUpdate, Schedules, Update, World};
+
+use crate::editor::EditorWorld;
+
+/// Edit-time state set aside while the game runs on a copy of the world.
+pub struct PlaySession {
+    /// The edited world, restored with its undo history when play stops.
+    pub edit_world: EditorWorld,
+    /// Inspector state (selection, filter) of the edited world.
+    pub inspector_state: InspectorState,
+    /// Set by the Step button; the next frame advances one fixed timestep.
+    pub step_pending: bool,
+}
+
+/// Copy every scene tree of `src` into `dst`.
+///
+/// Uses in-memory prefab extraction, so components that are not
+/// serializable (e.g. physics descriptors) are copied too. Editor entities
+/// are skipped; `dst` keeps its own editor camera and resources.
+pub fn copy_scene(src: &World, dst: &mut World) {
+    src.extract_scene_prefab().instantiate(dst);
+}
+
+/// Add the game systems to the schedules of a play world.
+///
+/// Physics bodies and joints are synced and stepped in [`FixedUpdate`];
+/// animation players advance in [`Update`]. [`Update`] is made writable so
+/// that game systems can mutate the world directly instead of going through
+/// the editor action queue.
+pub fn add_game_systems(world: &mut World, schedules: &mut Schedules) {
+    if !world.has_resource::<PhysicsWorld3D>() {
+        world.insert_resource(PhysicsWorld3D::default());
+    }
+    if !world.has_resource::<PhysicsWorld2D>() {
+        world.insert_resource(PhysicsWorld2D::default());
+    }
+
+    schedules.get_mut::<Update>().set_read_only(false);
+    schedules.get_mut::<Update>().add(AnimateTransforms);
+
+    let fixed = schedules.get_mut::<FixedUpdate>();
+    fixed.add_exclusive(SyncPhysicsBodies3D);
+    fixed.add_exclusive(SyncPhysicsJoints3D);
+    fixed.add(StepPhysics3D);
+    fixed
+        .add_edge::<SyncPhysicsBodies3D, SyncPhysicsJoints3D>()
+        .expect("No cycle");
+    fixed
+        .add_edge::<SyncPhysicsJoints3D, StepPhysics3D>()
+        .expect("No cycle");
+
+    fixed.add_exclusive(SyncPhysicsBodies2D);
+    fixed.add_exclusive(SyncPhysicsJoints2D);
+    fixed.add(StepPhysics2D);
+    fixed
+        .add_edge::<SyncPhysicsBodies2D, SyncPhysicsJoints2D>()
+        .expect("No cycle");
+    fixed
+        .add_edge::<SyncPhysicsJoints2D, StepPhysics2D>()
+        .expect("No cycle");
+}
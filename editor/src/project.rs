@@ -1,6 +1,9 @@
 use std::path::Path;
 
-use redlilium_vfs::{FileSystemProvider, SftpConfig, SftpProvider, Vfs};
+use redlilium_vfs::{
+    CacheConfig, CachingProvider, FileSystemProvider, HttpConfig, HttpProvider, MemoryProvider,
+    PackProvider, SftpConfig, SftpProvider, Vfs, VfsProvider,
+};
 use serde::Deserialize;
 use serde::de;
 
@@ -20,17 +23,32 @@ pub struct ProjectInfo {
 
 /// A single VFS mount point definition.
 ///
-/// The `type` field selects the provider: `"filesystem"` (default) or `"sftp"`.
+/// The `type` field selects the provider: `"filesystem"` (default), `"pack"`,
+/// `"http"` or `"sftp"`. Pack mounts read a `.pak` or zip file at `path`.
+/// HTTP mounts use `path` as the base URL and the optional `manifest` field.
 /// SFTP mounts use the `host`, `port`, `username`, and `key` fields.
+///
+/// Setting `cache` to a directory keeps an on-disk copy of everything read
+/// from the mount, so remote mounts stay fast and keep working offline.
+///
+/// Mounts sharing a `name` are stacked as layers, highest `priority` on top,
+/// so a patch pack or a mod directory can shadow files of the base mount.
 #[derive(Debug, Clone, Deserialize)]
 pub struct MountConfig {
     pub name: String,
     pub path: String,
     #[serde(default)]
     pub default: bool,
-    /// `"filesystem"` (default) or `"sftp"`.
+    /// Layer priority among mounts with the same name.
+    #[serde(default)]
+    pub priority: i32,
+    /// `"filesystem"` (default), `"pack"`, `"http"` or `"sftp"`.
     #[serde(default = "default_mount_type")]
     pub r#type: String,
+    /// HTTP manifest location, relative to the base URL.
+    pub manifest: Option<String>,
+    /// Directory of an on-disk cache in front of the mount.
+    pub cache: Option<String>,
     // SFTP-specific fields (ignored for filesystem mounts).
     pub host: Option<String>,
     pub port: Option<u16>,
@@ -86,12 +104,18 @@ pub fn load_project(path: &Path) -> Result<ProjectConfig, String> {
     toml::from_str(&content).map_err(|e| format!("failed to parse {}: {e}", path.display()))
 }
 
+/// Mount name of the engine `shaders` directory, whose standard shaders and
+/// library modules are hot reloaded when edited.
+pub const ENGINE_SHADERS_MOUNT: &str = "engine_shaders";
+
 /// Build a [`Vfs`] from a project config.
 ///
 /// Creates the appropriate provider for each mount and sets the default source
-/// if one is marked with `default = true`.
+/// if one is marked with `default = true`. The engine `shaders` directory is
+/// mounted as [`ENGINE_SHADERS_MOUNT`] unless the project mounts that name
+/// itself.
 pub fn build_vfs(config: &ProjectConfig) -> Vfs {
-    let mut vfs = Vfs::new();
+    let vfs = Vfs::new();
 
     for mount in &config.mount {
         match mount.r#type.as_str() {
@@ -101,7 +125,29 @@ pub fn build_vfs(config: &ProjectConfig) -> Vfs {
                     mount.name,
                     mount.path
                 );
-                vfs.mount(&mount.name, FileSystemProvider::new(&mount.path));
+                mount_provider(&vfs, mount, FileSystemProvider::new(&mount.path));
+            }
+            "pack" => match PackProvider::open(&mount.path) {
+                Ok(provider) => {
+                    log::info!(
+                        "VFS mount: \"{}\" -> pack {:?} ({} files)",
+                        mount.name,
+                        mount.path,
+                        provider.file_count()
+                    );
+                    mount_provider(&vfs, mount, provider);
+                }
+                Err(e) => {
+                    log::error!("Failed to open pack mount \"{}\": {e}", mount.name);
+                }
+            },
+            "http" => {
+                log::info!("VFS mount: \"{}\" -> http {}", mount.name, mount.path);
+                let http_config = HttpConfig {
+                    manifest: mount.manifest.clone(),
+                    ..HttpConfig::new(&mount.path)
+                };
+                mount_provider(&vfs, mount, HttpProvider::new(http_config));
             }
             "sftp" => {
                 let key_paths = if mount.key.is_empty() {
@@ -125,11 +171,10 @@ pub fn build_vfs(config: &ProjectConfig) -> Vfs {
                     sftp_config.remote_root,
                 );
                 match SftpProvider::connect(sftp_config) {
-                    Ok(provider) => {
-                        vfs.mount(&mount.name, provider);
-                    }
+                    Ok(provider) => mount_provider(&vfs, mount, provider),
                     Err(e) => {
                         log::error!("Failed to connect SFTP mount \"{}\": {e}", mount.name);
+                        mount_offline_cache(&vfs, mount);
                     }
                 }
             }
@@ -139,6 +184,12 @@ pub fn build_vfs(config: &ProjectConfig) -> Vfs {
         }
     }
 
+    if !config.mount.iter().any(|m| m.name == ENGINE_SHADERS_MOUNT) {
+        let shaders = concat!(env!("CARGO_MANIFEST_DIR"), "/../shaders");
+        log::info!("VFS mount: \"{ENGINE_SHADERS_MOUNT}\" -> filesystem {shaders:?}");
+        vfs.mount(ENGINE_SHADERS_MOUNT, FileSystemProvider::new(shaders));
+    }
+
     if let Some(default_mount) = config.mount.iter().find(|m| m.default) {
         vfs.set_default(&default_mount.name);
     }
@@ -146,6 +197,50 @@ pub fn build_vfs(config: &ProjectConfig) -> Vfs {
     vfs
 }
 
+/// Mount `provider` as a layer, behind a [`CachingProvider`] if the mount
+/// sets `cache`.
+fn mount_provider(vfs: &Vfs, mount: &MountConfig, provider: impl VfsProvider) {
+    let Some(dir) = &mount.cache else {
+        vfs.mount_layer(&mount.name, provider, mount.priority);
+        return;
+    };
+    match CachingProvider::new(provider, CacheConfig::new(dir)) {
+        Ok(cached) => {
+            log::info!("VFS mount: \"{}\" cached in {:?}", mount.name, dir);
+            vfs.mount_layer(&mount.name, cached, mount.priority);
+        }
+        Err(e) => {
+            log::error!("Failed to open cache for mount \"{}\": {e}", mount.name);
+        }
+    }
+}
+
+/// Serve a mount whose remote is unreachable from its cache alone, if the
+/// mount sets `cache`.
+fn mount_offline_cache(vfs: &Vfs, mount: &MountConfig) {
+    let Some(dir) = &mount.cache else {
+        return;
+    };
+    let config = CacheConfig {
+        offline: true,
+        ..CacheConfig::new(dir)
+    };
+    // The empty stand-in remote is never contacted while offline.
+    match CachingProvider::new(MemoryProvider::new(), config) {
+        Ok(cached) => {
+            log::warn!(
+                "VFS mount: \"{}\" is offline, serving cached files from {:?}",
+                mount.name,
+                dir
+            );
+            vfs.mount_layer(&mount.name, cached, mount.priority);
+        }
+        Err(e) => {
+            log::error!("Failed to open cache for mount \"{}\": {e}", mount.name);
+        }
+    }
+}
+
 /// Load project config, falling back to a default if the file doesn't exist.
 pub fn load_or_default(path: &Path) -> (ProjectConfig, Vfs) {
     let config = match load_project(path) {
@@ -167,7 +262,10 @@ pub fn load_or_default(path: &Path) -> (ProjectConfig, Vfs) {
                     name: "assets".into(),
                     path: "./assets".into(),
                     default: true,
+                    priority: 0,
                     r#type: "filesystem".into(),
+                    manifest: None,
+                    cache: None,
                     host: None,
                     port: None,
                     username: None,
@@ -0,0 +1,184 @@
+use std::path::{Path, PathBuf};
+
+use redlilium_ecs::World;
+use redlilium_ecs::serialize::{Format, SerializedScene, decode, encode};
+use serde::{Deserialize, Serialize};
+
+/// File extension used for scene documents.
+pub const SCENE_EXTENSION: &str = "scene";
+
+/// Maximum number of entries kept in the recent files list.
+const MAX_RECENT_FILES: usize = 10;
+
+/// Serialize every root entity tree and serializable resource of a world
+/// into a RON scene document.
+pub fn encode_scene(world: &World) -> Result<Vec<u8>, String> {
+    let scene = world
+        .serialize_scene()
+        .map_err(|e| format!("failed to serialize scene: {e}"))?;
+    encode(&scene, Format::Ron).map_err(|e| format!("failed to encode scene: {e}"))
+}
+
+/// Parse a RON scene document.
+pub fn decode_scene(data: &[u8]) -> Result<SerializedScene, String> {
+    decode(data, Format::Ron).map_err(|e| format!("failed to decode scene: {e}"))
+}
+
+/// Append the scene extension to a VFS path if it has none.
+pub fn with_scene_extension(path: &str) -> String {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    if file_name.contains('.') {
+        path.to_owned()
+    } else {
+        format!("{path}.{SCENE_EXTENSION}")
+    }
+}
+
+/// On-disk layout of the recent files list.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RecentFilesFile {
+    #[serde(default)]
+    recent: Vec<String>,
+}
+
+/// Most-recently-used list of scene VFS paths, persisted to a TOML file
+/// next to the project file.
+pub struct RecentFiles {
+    /// File the list is saved to.
+    file: PathBuf,
+    /// VFS paths, most recent first.
+    paths: Vec<String>,
+}
+
+impl RecentFiles {
+    /// Load the recent files list, starting empty if the file is missing or invalid.
+    pub fn load(file: &Path) -> Self {
+        let paths = match std::fs::read_to_string(file) {
+            Ok(content) => match toml::from_str::<RecentFilesFile>(&content) {
+                Ok(parsed) => parsed.recent,
+                Err(e) => {
+                    log::warn!("Ignoring invalid {}: {e}", file.display());
+                    Vec::new()
+                }
+            },
+            Err(_) => Vec::new(),
+        };
+        Self {
+            file: file.to_owned(),
+            paths,
+        }
+    }
+
+    /// VFS paths, most recent first.
+    pub fn paths(&self) -> &[String] {
+        &self.paths
+    }
+
+    /// Move a path to the front of the list and persist it.
+    pub fn push(&mut self, path: &str) {
+        self.paths.retain(|p| p != path);
+        self.paths.insert(0, path.to_owned());
+        self.paths.truncate(MAX_RECENT_FILES);
+        self.save();
+    }
+
+    /// Remove a path (e.g. one that failed to open) and persist the list.
+    pub fn remove(&mut self, path: &str) {
+        let len = self.paths.len();
+        self.paths.retain(|p| p != path);
+        if self.paths.len() != len {
+            self.save();
+        }
+    }
+
+    fn save(&self) {
+        let file = RecentFilesFile {
+            recent: self.paths.clone(),
+        };
+        let result = toml::to_string(&file)
+            .map_err(|e| e.to_string())
+            .and_then(|content| std::fs::write(&self.file, content).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            log::warn!("Failed to save {}: {e}", self.file.display());
+        }
+    }
+}
+
+/// Purpose of a [`ScenePathDialog`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScenePathDialogKind {
+    Open,
+    SaveAs,
+}
+
+/// Outcome of showing a [`ScenePathDialog`] for one frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScenePathDialogResult {
+    /// The dialog is still open.
+    Pending,
+    /// The user confirmed a VFS path (with the scene extension applied).
+    Confirmed(String),
+    /// The user dismissed the dialog.
+    Cancelled,
+}
+
+/// Modal prompt for the VFS path of a scene to open or save.
+pub struct ScenePathDialog {
+    kind: ScenePathDialogKind,
+    path: String,
+}
+
+impl ScenePathDialog {
+    /// Create a dialog pre-filled with `path`.
+    pub fn new(kind: ScenePathDialogKind, path: impl Into<String>) -> Self {
+        Self {
+            kind,
+            path: path.into(),
+        }
+    }
+
+    /// What the dialog was opened for.
+    pub fn kind(&self) -> ScenePathDialogKind {
+        self.kind
+    }
+
+    /// Draw the dialog. Call once per frame while it is open.
+    pub fn show(&mut self, ctx: &egui::Context) -> ScenePathDialogResult {
+        let (title, confirm) = match self.kind {
+            ScenePathDialogKind::Open => ("Open Scene", "Open"),
+            ScenePathDialogKind::SaveAs => ("Save Scene As", "Save"),
+        };
+
+        let mut result = ScenePathDialogResult::Pending;
+        egui::Window::new(title)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                ui.label("VFS path (e.g. assets/scenes/main.scene):");
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut self.path)
+                        .desired_width(320.0)
+                        .font(egui::TextStyle::Monospace),
+                );
+                let submitted =
+                    response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    let valid = !self.path.trim().is_empty();
+                    if (ui.add_enabled(valid, egui::Button::new(confirm)).clicked() || submitted)
+                        && valid
+                    {
+                        result = ScenePathDialogResult::Confirmed(with_scene_extension(
+                            self.path.trim(),
+                        ));
+                    }
+                    if ui.button("Cancel").clicked() {
+                        result = ScenePathDialogResult::Cancelled;
+                    }
+                });
+            });
+        result
+    }
+}
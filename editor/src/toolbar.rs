@@ -6,35 +6,56 @@ pub enum PlayState {
     Paused,
 }
 
-/// Draw the play/pause/stop controls inline in a horizontal UI region.
+/// A play control button pressed by the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayCommand {
+    /// Copy the edited world and start running the game schedules on it.
+    Play,
+    /// Freeze the game simulation.
+    Pause,
+    /// Continue a paused simulation.
+    Resume,
+    /// Advance a paused simulation by one fixed timestep.
+    Step,
+    /// Discard the play copy and return to the edited world.
+    Stop,
+}
+
+/// Draw the play/pause/step/stop controls inline in a horizontal UI region.
 ///
-/// Used inside the titlebar / menu bar. Returns the updated play state.
-pub fn draw_play_controls(ui: &mut egui::Ui, play_state: PlayState) -> PlayState {
-    let mut new_state = play_state;
+/// Used inside the titlebar / menu bar. Returns the button pressed, if any.
+pub fn draw_play_controls(ui: &mut egui::Ui, play_state: PlayState) -> Option<PlayCommand> {
+    let mut command = None;
 
     match play_state {
         PlayState::Editing => {
             if ui.button("\u{25B6} Play").clicked() {
-                new_state = PlayState::Playing;
+                command = Some(PlayCommand::Play);
             }
         }
         PlayState::Playing => {
             if ui.button("\u{23F8} Pause").clicked() {
-                new_state = PlayState::Paused;
+                command = Some(PlayCommand::Pause);
+            }
+            if ui.button("\u{23ED} Step").clicked() {
+                command = Some(PlayCommand::Step);
             }
             if ui.button("\u{23F9} Stop").clicked() {
-                new_state = PlayState::Editing;
+                command = Some(PlayCommand::Stop);
             }
         }
         PlayState::Paused => {
             if ui.button("\u{25B6} Resume").clicked() {
-                new_state = PlayState::Playing;
+                command = Some(PlayCommand::Resume);
+            }
+            if ui.button("\u{23ED} Step").clicked() {
+                command = Some(PlayCommand::Step);
             }
             if ui.button("\u{23F9} Stop").clicked() {
-                new_state = PlayState::Editing;
+                command = Some(PlayCommand::Stop);
             }
         }
     }
 
-    new_state
+    command
 }
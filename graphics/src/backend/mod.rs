@@ -7,6 +7,7 @@
 //! # Available Backends
 //!
 //! - `Dummy` (default): No-op backend for testing and development
+//! - `Software`: CPU rasterizer for headless rendering tests (requires `software-backend` feature)
 //! - `Wgpu`: Cross-platform backend using wgpu (requires `wgpu-backend` feature)
 //! - `Vulkan`: Native Vulkan backend using ash (requires `vulkan-backend` feature)
 //!
@@ -24,6 +25,9 @@ pub mod wgpu_impl;
 #[cfg(feature = "vulkan-backend")]
 pub mod vulkan;
 
+#[cfg(feature = "software-backend")]
+pub mod software;
+
 pub mod dummy;
 
 use std::sync::Arc;
@@ -48,6 +52,9 @@ use crate::types::{BufferDescriptor, SamplerDescriptor, TextureDescriptor};
 pub enum GpuBuffer {
     /// Dummy backend (no GPU allocation)
     Dummy,
+    /// Software backend buffer (host memory)
+    #[cfg(feature = "software-backend")]
+    Software(software::SoftwareBuffer),
     /// wgpu backend buffer
     #[cfg(feature = "wgpu-backend")]
     Wgpu(wgpu::Buffer),
@@ -67,6 +74,8 @@ impl std::fmt::Debug for GpuBuffer {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Dummy => write!(f, "GpuBuffer::Dummy"),
+            #[cfg(feature = "software-backend")]
+            Self::Software(buffer) => f.debug_tuple("GpuBuffer::Software").field(buffer).finish(),
             #[cfg(feature = "wgpu-backend")]
             Self::Wgpu(buffer) => f.debug_tuple("GpuBuffer::Wgpu").field(buffer).finish(),
             #[cfg(feature = "vulkan-backend")]
@@ -84,6 +93,9 @@ impl std::fmt::Debug for GpuBuffer {
 pub enum GpuTexture {
     /// Dummy backend (no GPU allocation)
     Dummy,
+    /// Software backend texture (host memory)
+    #[cfg(feature = "software-backend")]
+    Software(software::SoftwareTexture),
     /// wgpu backend texture
     #[cfg(feature = "wgpu-backend")]
     Wgpu {
@@ -108,6 +120,11 @@ impl std::fmt::Debug for GpuTexture {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Dummy => write!(f, "GpuTexture::Dummy"),
+            #[cfg(feature = "software-backend")]
+            Self::Software(texture) => f
+                .debug_tuple("GpuTexture::Software")
+                .field(texture)
+                .finish(),
             #[cfg(feature = "wgpu-backend")]
             Self::Wgpu { texture, view } => f
                 .debug_struct("GpuTexture::Wgpu")
@@ -137,6 +154,9 @@ impl std::fmt::Debug for GpuTexture {
 pub enum GpuSampler {
     /// Dummy backend (no GPU allocation)
     Dummy,
+    /// Software backend sampler (sampling state only)
+    #[cfg(feature = "software-backend")]
+    Software(SamplerDescriptor),
     /// wgpu backend sampler
     #[cfg(feature = "wgpu-backend")]
     Wgpu(wgpu::Sampler),
@@ -152,6 +172,11 @@ impl std::fmt::Debug for GpuSampler {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Dummy => write!(f, "GpuSampler::Dummy"),
+            #[cfg(feature = "software-backend")]
+            Self::Software(descriptor) => f
+                .debug_tuple("GpuSampler::Software")
+                .field(descriptor)
+                .finish(),
             #[cfg(feature = "wgpu-backend")]
             Self::Wgpu(sampler) => f.debug_tuple("GpuSampler::Wgpu").field(sampler).finish(),
             #[cfg(feature = "vulkan-backend")]
@@ -171,6 +196,9 @@ impl std::fmt::Debug for GpuSampler {
 pub enum GpuPipeline {
     /// Dummy backend (no GPU pipeline)
     Dummy,
+    /// Software backend pipeline (CPU shader)
+    #[cfg(feature = "software-backend")]
+    Software(software::SoftwarePipeline),
     /// wgpu backend graphics pipeline
     #[cfg(feature = "wgpu-backend")]
     WgpuGraphics {
@@ -197,6 +225,11 @@ impl std::fmt::Debug for GpuPipeline {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Dummy => write!(f, "GpuPipeline::Dummy"),
+            #[cfg(feature = "software-backend")]
+            Self::Software(pipeline) => f
+                .debug_tuple("GpuPipeline::Software")
+                .field(pipeline)
+                .finish(),
             #[cfg(feature = "wgpu-backend")]
             Self::WgpuGraphics { .. } => f
                 .debug_struct("GpuPipeline::WgpuGraphics")
@@ -239,7 +272,8 @@ impl std::fmt::Debug for GpuPipeline {
 /// across all backends at the cost of less precise control.
 ///
 /// ## Dummy (`AtomicBool`)
-/// Simple CPU-side flag for testing without GPU hardware.
+/// Simple CPU-side flag for testing without GPU hardware. Also used by the
+/// software backend, which executes graphs synchronously.
 #[allow(clippy::large_enum_variant)]
 pub enum GpuFence {
     /// Dummy backend - CPU-side atomic boolean for testing.
@@ -316,6 +350,91 @@ impl std::fmt::Debug for GpuSemaphore {
     }
 }
 
+/// Handle to a pool of GPU timestamp queries.
+///
+/// Created by [`GpuBackend::create_timestamp_pool`] when the device supports
+/// timestamp queries. Passes write a timestamp at their start and end (see
+/// [`GpuTimestampWrites`]); [`GpuBackend::map_timestamps`] starts reading
+/// the results back after the frame's last submission and
+/// [`GpuBackend::read_timestamps`] returns them once the GPU has finished.
+#[allow(clippy::large_enum_variant)]
+pub enum GpuTimestampPool {
+    /// Dummy backend pool, whose queries are never written
+    Dummy { capacity: u32 },
+    /// wgpu backend query set, with a buffer the queries are resolved into
+    /// and a mappable copy of it
+    #[cfg(feature = "wgpu-backend")]
+    Wgpu {
+        query_set: wgpu::QuerySet,
+        resolve_buffer: wgpu::Buffer,
+        readback_buffer: wgpu::Buffer,
+        capacity: u32,
+        /// Last submission that wrote timestamps
+        submission: std::sync::Mutex<Option<wgpu::SubmissionIndex>>,
+        /// Result of the readback buffer's pending `map_async`, if one was started
+        mapping:
+            std::sync::Mutex<Option<std::sync::mpsc::Receiver<Result<(), wgpu::BufferAsyncError>>>>,
+    },
+    /// Vulkan backend timestamp query pool
+    #[cfg(feature = "vulkan-backend")]
+    Vulkan {
+        device: ash::Device,
+        pool: vk::QueryPool,
+        capacity: u32,
+    },
+}
+
+impl GpuTimestampPool {
+    /// Number of timestamps the pool holds.
+    pub fn capacity(&self) -> u32 {
+        match self {
+            Self::Dummy { capacity } => *capacity,
+            #[cfg(feature = "wgpu-backend")]
+            Self::Wgpu { capacity, .. } => *capacity,
+            #[cfg(feature = "vulkan-backend")]
+            Self::Vulkan { capacity, .. } => *capacity,
+        }
+    }
+}
+
+impl std::fmt::Debug for GpuTimestampPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Dummy { capacity } => f
+                .debug_struct("GpuTimestampPool::Dummy")
+                .field("capacity", capacity)
+                .finish(),
+            #[cfg(feature = "wgpu-backend")]
+            Self::Wgpu { capacity, .. } => f
+                .debug_struct("GpuTimestampPool::Wgpu")
+                .field("capacity", capacity)
+                .finish_non_exhaustive(),
+            #[cfg(feature = "vulkan-backend")]
+            Self::Vulkan { pool, capacity, .. } => f
+                .debug_struct("GpuTimestampPool::Vulkan")
+                .field("pool", pool)
+                .field("capacity", capacity)
+                .finish_non_exhaustive(),
+        }
+    }
+}
+
+/// Timestamp queries reserved for one graph execution.
+///
+/// The backend writes two timestamps around every timed pass (see
+/// [`Pass::is_timed`](crate::graph::Pass::is_timed)) in compiled order: the
+/// `n`-th timed pass writes queries `first_query + 2n` (start) and
+/// `first_query + 2n + 1` (end).
+#[derive(Debug, Clone, Copy)]
+pub struct GpuTimestampWrites<'a> {
+    /// Pool the timestamps are written to.
+    pub pool: &'a GpuTimestampPool,
+    /// First query of the reserved range.
+    pub first_query: u32,
+    /// Number of queries reserved (two per timed pass).
+    pub query_count: u32,
+}
+
 /// Handle to an acquired surface texture for presentation.
 ///
 /// This encapsulates all backend-specific state needed to render to a surface
@@ -718,6 +837,15 @@ impl Drop for GpuSemaphore {
     }
 }
 
+#[cfg(feature = "vulkan-backend")]
+impl Drop for GpuTimestampPool {
+    fn drop(&mut self) {
+        if let GpuTimestampPool::Vulkan { device, pool, .. } = self {
+            unsafe { device.destroy_query_pool(*pool, None) };
+        }
+    }
+}
+
 #[cfg(feature = "vulkan-backend")]
 impl Drop for GpuPipeline {
     fn drop(&mut self) {
@@ -751,6 +879,9 @@ impl Drop for GpuPipeline {
 pub enum GpuBackend {
     /// Dummy backend for testing and development.
     Dummy(dummy::DummyBackend),
+    /// CPU rasterizer backend for headless rendering.
+    #[cfg(feature = "software-backend")]
+    Software(software::SoftwareBackend),
     /// wgpu backend for cross-platform GPU access.
     #[cfg(feature = "wgpu-backend")]
     Wgpu(wgpu_impl::WgpuBackend),
@@ -763,6 +894,11 @@ impl std::fmt::Debug for GpuBackend {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Dummy(backend) => f.debug_tuple("GpuBackend::Dummy").field(backend).finish(),
+            #[cfg(feature = "software-backend")]
+            Self::Software(backend) => f
+                .debug_tuple("GpuBackend::Software")
+                .field(backend)
+                .finish(),
             #[cfg(feature = "wgpu-backend")]
             Self::Wgpu(backend) => f.debug_tuple("GpuBackend::Wgpu").field(backend).finish(),
             #[cfg(feature = "vulkan-backend")]
@@ -780,6 +916,8 @@ impl GpuBackend {
     pub fn name(&self) -> &'static str {
         match self {
             Self::Dummy(backend) => backend.name(),
+            #[cfg(feature = "software-backend")]
+            Self::Software(backend) => backend.name(),
             #[cfg(feature = "wgpu-backend")]
             Self::Wgpu(backend) => backend.name(),
             #[cfg(feature = "vulkan-backend")]
@@ -791,6 +929,8 @@ impl GpuBackend {
     pub fn create_buffer(&self, descriptor: &BufferDescriptor) -> Result<GpuBuffer, GraphicsError> {
         match self {
             Self::Dummy(backend) => backend.create_buffer(descriptor),
+            #[cfg(feature = "software-backend")]
+            Self::Software(backend) => backend.create_buffer(descriptor),
             #[cfg(feature = "wgpu-backend")]
             Self::Wgpu(backend) => backend.create_buffer(descriptor),
             #[cfg(feature = "vulkan-backend")]
@@ -805,6 +945,8 @@ impl GpuBackend {
     ) -> Result<GpuTexture, GraphicsError> {
         match self {
             Self::Dummy(backend) => backend.create_texture(descriptor),
+            #[cfg(feature = "software-backend")]
+            Self::Software(backend) => backend.create_texture(descriptor),
             #[cfg(feature = "wgpu-backend")]
             Self::Wgpu(backend) => backend.create_texture(descriptor),
             #[cfg(feature = "vulkan-backend")]
@@ -819,6 +961,8 @@ impl GpuBackend {
     ) -> Result<GpuSampler, GraphicsError> {
         match self {
             Self::Dummy(backend) => backend.create_sampler(descriptor),
+            #[cfg(feature = "software-backend")]
+            Self::Software(backend) => backend.create_sampler(descriptor),
             #[cfg(feature = "wgpu-backend")]
             Self::Wgpu(backend) => backend.create_sampler(descriptor),
             #[cfg(feature = "vulkan-backend")]
@@ -833,6 +977,8 @@ impl GpuBackend {
     ) -> Result<GpuPipeline, GraphicsError> {
         match self {
             Self::Dummy(_) => Ok(GpuPipeline::Dummy),
+            #[cfg(feature = "software-backend")]
+            Self::Software(backend) => backend.create_pipeline(descriptor),
             #[cfg(feature = "wgpu-backend")]
             Self::Wgpu(backend) => backend.create_pipeline(descriptor),
             #[cfg(feature = "vulkan-backend")]
@@ -844,6 +990,8 @@ impl GpuBackend {
     pub fn create_semaphore(&self) -> GpuSemaphore {
         match self {
             Self::Dummy(backend) => backend.create_semaphore(),
+            #[cfg(feature = "software-backend")]
+            Self::Software(backend) => backend.create_semaphore(),
             #[cfg(feature = "wgpu-backend")]
             Self::Wgpu(backend) => backend.create_semaphore(),
             #[cfg(feature = "vulkan-backend")]
@@ -855,6 +1003,8 @@ impl GpuBackend {
     pub fn create_fence(&self, signaled: bool) -> GpuFence {
         match self {
             Self::Dummy(backend) => backend.create_fence(signaled),
+            #[cfg(feature = "software-backend")]
+            Self::Software(backend) => backend.create_fence(signaled),
             #[cfg(feature = "wgpu-backend")]
             Self::Wgpu(backend) => backend.create_fence(signaled),
             #[cfg(feature = "vulkan-backend")]
@@ -866,6 +1016,8 @@ impl GpuBackend {
     pub fn wait_fence(&self, fence: &GpuFence) {
         match self {
             Self::Dummy(backend) => backend.wait_fence(fence),
+            #[cfg(feature = "software-backend")]
+            Self::Software(backend) => backend.wait_fence(fence),
             #[cfg(feature = "wgpu-backend")]
             Self::Wgpu(backend) => backend.wait_fence(fence),
             #[cfg(feature = "vulkan-backend")]
@@ -879,6 +1031,8 @@ impl GpuBackend {
     pub fn wait_fence_timeout(&self, fence: &GpuFence, timeout: std::time::Duration) -> bool {
         match self {
             Self::Dummy(backend) => backend.wait_fence_timeout(fence, timeout),
+            #[cfg(feature = "software-backend")]
+            Self::Software(backend) => backend.wait_fence_timeout(fence, timeout),
             #[cfg(feature = "wgpu-backend")]
             Self::Wgpu(backend) => backend.wait_fence_timeout(fence, timeout),
             #[cfg(feature = "vulkan-backend")]
@@ -890,6 +1044,8 @@ impl GpuBackend {
     pub fn is_fence_signaled(&self, fence: &GpuFence) -> bool {
         match self {
             Self::Dummy(backend) => backend.is_fence_signaled(fence),
+            #[cfg(feature = "software-backend")]
+            Self::Software(backend) => backend.is_fence_signaled(fence),
             #[cfg(feature = "wgpu-backend")]
             Self::Wgpu(backend) => backend.is_fence_signaled(fence),
             #[cfg(feature = "vulkan-backend")]
@@ -901,6 +1057,8 @@ impl GpuBackend {
     pub fn signal_fence(&self, fence: &GpuFence) {
         match self {
             Self::Dummy(backend) => backend.signal_fence(fence),
+            #[cfg(feature = "software-backend")]
+            Self::Software(backend) => backend.signal_fence(fence),
             #[cfg(feature = "wgpu-backend")]
             Self::Wgpu(backend) => backend.signal_fence(fence),
             #[cfg(feature = "vulkan-backend")]
@@ -917,6 +1075,8 @@ impl GpuBackend {
     /// * `wait_semaphores` - GPU semaphores to wait on before execution begins
     /// * `signal_semaphores` - GPU semaphores to signal when execution completes
     /// * `signal_fence` - Optional fence to signal when execution completes (for CPU waiting)
+    /// * `timestamps` - Optional queries to time each pass with (ignored by
+    ///   backends without timestamp support)
     pub fn execute_graph(
         &self,
         graph: &RenderGraph,
@@ -924,6 +1084,7 @@ impl GpuBackend {
         wait_semaphores: &[&GpuSemaphore],
         signal_semaphores: &[&GpuSemaphore],
         signal_fence: Option<&GpuFence>,
+        timestamps: Option<GpuTimestampWrites<'_>>,
     ) -> Result<(), GraphicsError> {
         match self {
             Self::Dummy(backend) => backend.execute_graph(
@@ -933,6 +1094,14 @@ impl GpuBackend {
                 signal_semaphores,
                 signal_fence,
             ),
+            #[cfg(feature = "software-backend")]
+            Self::Software(backend) => backend.execute_graph(
+                graph,
+                compiled,
+                wait_semaphores,
+                signal_semaphores,
+                signal_fence,
+            ),
             #[cfg(feature = "wgpu-backend")]
             Self::Wgpu(backend) => backend.execute_graph(
                 graph,
@@ -940,6 +1109,7 @@ impl GpuBackend {
                 wait_semaphores,
                 signal_semaphores,
                 signal_fence,
+                timestamps,
             ),
             #[cfg(feature = "vulkan-backend")]
             Self::Vulkan(backend) => backend.execute_graph(
@@ -948,10 +1118,142 @@ impl GpuBackend {
                 wait_semaphores,
                 signal_semaphores,
                 signal_fence,
+                timestamps,
             ),
         }
     }
 
+    /// Create a pool of `capacity` timestamp queries.
+    ///
+    /// Returns `None` if the backend or device does not support timestamp
+    /// queries.
+    pub fn create_timestamp_pool(&self, capacity: u32) -> Option<GpuTimestampPool> {
+        match self {
+            Self::Dummy(_) => Some(GpuTimestampPool::Dummy { capacity }),
+            #[cfg(feature = "software-backend")]
+            Self::Software(_) => None,
+            #[cfg(feature = "wgpu-backend")]
+            Self::Wgpu(backend) => backend.create_timestamp_pool(capacity),
+            #[cfg(feature = "vulkan-backend")]
+            Self::Vulkan(backend) => backend.create_timestamp_pool(capacity),
+        }
+    }
+
+    /// Nanoseconds per timestamp tick.
+    pub fn timestamp_period(&self) -> f32 {
+        match self {
+            Self::Dummy(_) => 1.0,
+            #[cfg(feature = "software-backend")]
+            Self::Software(_) => 1.0,
+            #[cfg(feature = "wgpu-backend")]
+            Self::Wgpu(backend) => backend.timestamp_period(),
+            #[cfg(feature = "vulkan-backend")]
+            Self::Vulkan(backend) => backend.timestamp_period(),
+        }
+    }
+
+    /// Start reading back the first `count` timestamps of `pool`.
+    ///
+    /// Call right after the last submission writing them, so that
+    /// [`read_timestamps`](Self::read_timestamps) does not have to wait for
+    /// later work. A no-op for backends that read queries directly.
+    pub fn map_timestamps(&self, pool: &GpuTimestampPool, count: u32) {
+        let count = count.min(pool.capacity());
+        match self {
+            #[cfg(feature = "wgpu-backend")]
+            Self::Wgpu(backend) => backend.map_timestamps(pool, count),
+            _ => {}
+        }
+    }
+
+    /// Read the first `count` timestamps of `pool`, in ticks.
+    ///
+    /// Must only be called once the GPU has finished the work that wrote
+    /// them (e.g. after waiting on the frame's fence), and after
+    /// [`map_timestamps`](Self::map_timestamps). Queries that were never
+    /// written read as `0`.
+    pub fn read_timestamps(&self, pool: &GpuTimestampPool, count: u32) -> Vec<u64> {
+        let count = count.min(pool.capacity());
+        match self {
+            Self::Dummy(_) => vec![0; count as usize],
+            #[cfg(feature = "software-backend")]
+            Self::Software(_) => vec![0; count as usize],
+            #[cfg(feature = "wgpu-backend")]
+            Self::Wgpu(backend) => backend.read_timestamps(pool, count),
+            #[cfg(feature = "vulkan-backend")]
+            Self::Vulkan(backend) => backend.read_timestamps(pool, count),
+        }
+    }
+
+    /// Get the cache of compiled shaders.
+    ///
+    /// Returns `None` for backends that do not compile shaders.
+    pub fn shader_cache(&self) -> Option<&crate::shader::ShaderCache> {
+        match self {
+            Self::Dummy(_) => None,
+            #[cfg(feature = "software-backend")]
+            Self::Software(_) => None,
+            #[cfg(feature = "wgpu-backend")]
+            Self::Wgpu(backend) => Some(backend.shader_cache()),
+            #[cfg(feature = "vulkan-backend")]
+            Self::Vulkan(backend) => Some(backend.shader_cache()),
+        }
+    }
+
+    /// Get the modules Slang sources can import.
+    ///
+    /// Returns `None` for backends that do not compile shaders.
+    pub fn shader_library(&self) -> Option<&std::sync::RwLock<crate::shader::ShaderLibrary>> {
+        match self {
+            Self::Dummy(_) => None,
+            #[cfg(feature = "software-backend")]
+            Self::Software(_) => None,
+            #[cfg(feature = "wgpu-backend")]
+            Self::Wgpu(backend) => Some(backend.shader_library()),
+            #[cfg(feature = "vulkan-backend")]
+            Self::Vulkan(backend) => Some(backend.shader_library()),
+        }
+    }
+
+    /// Identity of the device and driver that persisted pipeline cache data
+    /// must match.
+    pub fn device_uuid(&self) -> [u8; 16] {
+        match self {
+            Self::Dummy(_) => [0; 16],
+            #[cfg(feature = "software-backend")]
+            Self::Software(_) => [0; 16],
+            #[cfg(feature = "wgpu-backend")]
+            Self::Wgpu(backend) => backend.device_uuid(),
+            #[cfg(feature = "vulkan-backend")]
+            Self::Vulkan(backend) => backend.device_uuid(),
+        }
+    }
+
+    /// Get the driver pipeline cache contents for persisting.
+    ///
+    /// Empty for backends without a pipeline cache.
+    pub fn pipeline_cache_data(&self) -> Vec<u8> {
+        match self {
+            #[cfg(feature = "vulkan-backend")]
+            Self::Vulkan(backend) => backend.pipeline_cache_data(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Merge persisted pipeline cache contents into the driver pipeline cache.
+    ///
+    /// Ignored by backends without a pipeline cache.
+    pub fn merge_pipeline_cache_data(&self, data: &[u8]) -> Result<(), GraphicsError> {
+        match self {
+            #[cfg(feature = "vulkan-backend")]
+            Self::Vulkan(backend) => backend.merge_pipeline_cache_data(data),
+            _ => {
+                let _ = data;
+                Ok(())
+            }
+        }
+    }
+
     /// Write data to a buffer.
     pub fn write_buffer(
         &self,
@@ -961,6 +1263,8 @@ impl GpuBackend {
     ) -> Result<(), GraphicsError> {
         match self {
             Self::Dummy(backend) => backend.write_buffer(buffer, offset, data),
+            #[cfg(feature = "software-backend")]
+            Self::Software(backend) => backend.write_buffer(buffer, offset, data),
             #[cfg(feature = "wgpu-backend")]
             Self::Wgpu(backend) => backend.write_buffer(buffer, offset, data),
             #[cfg(feature = "vulkan-backend")]
@@ -974,6 +1278,8 @@ impl GpuBackend {
     pub fn read_buffer(&self, buffer: &GpuBuffer, offset: u64, size: u64) -> Vec<u8> {
         match self {
             Self::Dummy(backend) => backend.read_buffer(buffer, offset, size),
+            #[cfg(feature = "software-backend")]
+            Self::Software(backend) => backend.read_buffer(buffer, offset, size),
             #[cfg(feature = "wgpu-backend")]
             Self::Wgpu(backend) => backend.read_buffer(buffer, offset, size),
             #[cfg(feature = "vulkan-backend")]
@@ -990,6 +1296,8 @@ impl GpuBackend {
     ) -> Result<(), GraphicsError> {
         match self {
             Self::Dummy(backend) => backend.write_texture(texture, data, descriptor),
+            #[cfg(feature = "software-backend")]
+            Self::Software(backend) => backend.write_texture(texture, data, descriptor),
             #[cfg(feature = "wgpu-backend")]
             Self::Wgpu(backend) => backend.write_texture(texture, data, descriptor),
             #[cfg(feature = "vulkan-backend")]
@@ -997,6 +1305,24 @@ impl GpuBackend {
         }
     }
 
+    /// Fill mip levels `1..` of a texture by downsampling each level from
+    /// the previous one, waiting for the GPU to finish.
+    pub fn generate_mipmaps(
+        &self,
+        texture: &GpuTexture,
+        descriptor: &TextureDescriptor,
+    ) -> Result<(), GraphicsError> {
+        match self {
+            Self::Dummy(_) => Ok(()),
+            #[cfg(feature = "software-backend")]
+            Self::Software(backend) => backend.generate_mipmaps(texture, descriptor),
+            #[cfg(feature = "wgpu-backend")]
+            Self::Wgpu(backend) => backend.generate_mipmaps(texture, descriptor),
+            #[cfg(feature = "vulkan-backend")]
+            Self::Vulkan(backend) => backend.generate_mipmaps(texture, descriptor),
+        }
+    }
+
     /// Create a surface from a window.
     ///
     /// # Safety
@@ -1012,6 +1338,13 @@ impl GpuBackend {
                 Ok(GpuSurface::Dummy)
             }
 
+            // The software backend renders offscreen only; surface targets are skipped.
+            #[cfg(feature = "software-backend")]
+            Self::Software(_) => {
+                log::info!("Created dummy surface for software backend");
+                Ok(GpuSurface::Dummy)
+            }
+
             #[cfg(feature = "wgpu-backend")]
             Self::Wgpu(wgpu_backend) => {
                 // Create wgpu surface from window
@@ -1086,6 +1419,9 @@ impl GpuBackend {
                 Ok(true)
             }
 
+            #[cfg(feature = "software-backend")]
+            (Self::Software(_), GpuSurface::Dummy) => Ok(true),
+
             #[cfg(feature = "wgpu-backend")]
             (Self::Wgpu(wgpu_backend), GpuSurface::Wgpu { surface }) => {
                 wgpu_backend.ensure_compatible_with_surface(surface)
@@ -1115,6 +1451,9 @@ impl GpuBackend {
         match (self, surface) {
             (Self::Dummy(_), _) => true,
 
+            #[cfg(feature = "software-backend")]
+            (Self::Software(_), GpuSurface::Dummy) => true,
+
             #[cfg(feature = "wgpu-backend")]
             (Self::Wgpu(wgpu_backend), GpuSurface::Wgpu { surface }) => {
                 wgpu_backend.is_adapter_compatible_with_surface(surface)
@@ -1150,6 +1489,20 @@ pub fn create_backend_with_params(
             log::info!("Using dummy backend (requested)");
             Ok(GpuBackend::Dummy(dummy::DummyBackend::new()))
         }
+        BackendType::Software => {
+            #[cfg(feature = "software-backend")]
+            {
+                log::info!("Using software backend (requested)");
+                Ok(GpuBackend::Software(software::SoftwareBackend::new()))
+            }
+            #[cfg(not(feature = "software-backend"))]
+            {
+                Err(GraphicsError::ResourceCreationFailed(
+                    "Software backend requested but software-backend feature is not enabled"
+                        .to_string(),
+                ))
+            }
+        }
         BackendType::Wgpu => {
             #[cfg(feature = "wgpu-backend")]
             {
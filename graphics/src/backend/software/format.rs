@@ -0,0 +1,346 @@
+//! Texel encoding and decoding for the software backend.
+//!
+//! Converts between the in-memory byte representation of a texel and a
+//! normalized `[f32; 4]` RGBA value. sRGB formats are decoded to linear on
+//! read and encoded back on write, matching GPU behavior.
+//!
+//! Depth formats use an internal representation that is only guaranteed to
+//! round-trip through this module:
+//! - `Depth16Unorm`: 16-bit unorm
+//! - `Depth24Plus`, `Depth32Float`: 32-bit float
+//! - `Depth24PlusStencil8`: 24-bit unorm depth + 8-bit stencil
+//! - `Depth32FloatStencil8`: 32-bit float depth + 8-bit stencil + padding
+
+use crate::types::TextureFormat;
+
+/// Decode a single texel into linear RGBA.
+///
+/// Returns `None` for block-compressed formats, which cannot be addressed
+/// per texel.
+pub fn decode_texel(format: TextureFormat, bytes: &[u8]) -> Option<[f32; 4]> {
+    use TextureFormat as F;
+
+    let unorm8 = |b: u8| b as f32 / 255.0;
+    let snorm8 = |b: u8| (b as i8 as f32 / 127.0).max(-1.0);
+    let u16_at = |i: usize| u16::from_le_bytes([bytes[i], bytes[i + 1]]);
+    let u32_at =
+        |i: usize| u32::from_le_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]]);
+    let f32_at = |i: usize| f32::from_bits(u32_at(i));
+
+    let texel = match format {
+        F::R8Unorm => [unorm8(bytes[0]), 0.0, 0.0, 1.0],
+        F::R8Snorm => [snorm8(bytes[0]), 0.0, 0.0, 1.0],
+        F::R8Uint => [bytes[0] as f32, 0.0, 0.0, 1.0],
+        F::R8Sint => [bytes[0] as i8 as f32, 0.0, 0.0, 1.0],
+        F::R16Unorm => [u16_at(0) as f32 / 65535.0, 0.0, 0.0, 1.0],
+        F::R16Float => [f16_to_f32(u16_at(0)), 0.0, 0.0, 1.0],
+        F::Rg8Unorm => [unorm8(bytes[0]), unorm8(bytes[1]), 0.0, 1.0],
+        F::R32Float => [f32_at(0), 0.0, 0.0, 1.0],
+        F::R32Uint => [u32_at(0) as f32, 0.0, 0.0, 1.0],
+        F::Rg16Float => [f16_to_f32(u16_at(0)), f16_to_f32(u16_at(2)), 0.0, 1.0],
+        F::Rgba8Unorm => [
+            unorm8(bytes[0]),
+            unorm8(bytes[1]),
+            unorm8(bytes[2]),
+            unorm8(bytes[3]),
+        ],
+        F::Rgba8UnormSrgb => [
+            srgb_to_linear(unorm8(bytes[0])),
+            srgb_to_linear(unorm8(bytes[1])),
+            srgb_to_linear(unorm8(bytes[2])),
+            unorm8(bytes[3]),
+        ],
+        F::Bgra8Unorm => [
+            unorm8(bytes[2]),
+            unorm8(bytes[1]),
+            unorm8(bytes[0]),
+            unorm8(bytes[3]),
+        ],
+        F::Bgra8UnormSrgb => [
+            srgb_to_linear(unorm8(bytes[2])),
+            srgb_to_linear(unorm8(bytes[1])),
+            srgb_to_linear(unorm8(bytes[0])),
+            unorm8(bytes[3]),
+        ],
+        F::Rgba10a2Unorm | F::Bgra10a2Unorm => {
+            let packed = u32_at(0);
+            let c0 = (packed & 0x3ff) as f32 / 1023.0;
+            let c1 = ((packed >> 10) & 0x3ff) as f32 / 1023.0;
+            let c2 = ((packed >> 20) & 0x3ff) as f32 / 1023.0;
+            let a = (packed >> 30) as f32 / 3.0;
+            if format == F::Rgba10a2Unorm {
+                [c0, c1, c2, a]
+            } else {
+                [c2, c1, c0, a]
+            }
+        }
+        F::Rgba16Float => [
+            f16_to_f32(u16_at(0)),
+            f16_to_f32(u16_at(2)),
+            f16_to_f32(u16_at(4)),
+            f16_to_f32(u16_at(6)),
+        ],
+        F::Rg32Float => [f32_at(0), f32_at(4), 0.0, 1.0],
+        F::Rgba32Float => [f32_at(0), f32_at(4), f32_at(8), f32_at(12)],
+        F::Depth16Unorm => [u16_at(0) as f32 / 65535.0, 0.0, 0.0, 1.0],
+        F::Depth24Plus | F::Depth32Float | F::Depth32FloatStencil8 => [f32_at(0), 0.0, 0.0, 1.0],
+        F::Depth24PlusStencil8 => [
+            (u32_at(0) & 0x00ff_ffff) as f32 / 16_777_215.0,
+            0.0,
+            0.0,
+            1.0,
+        ],
+        _ => return None,
+    };
+    Some(texel)
+}
+
+/// Encode linear RGBA into a single texel.
+///
+/// Values are clamped to the representable range of normalized formats.
+/// Block-compressed formats are left untouched.
+pub fn encode_texel(format: TextureFormat, color: [f32; 4], out: &mut [u8]) {
+    use TextureFormat as F;
+
+    let unorm8 = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+    let snorm8 = |v: f32| (v.clamp(-1.0, 1.0) * 127.0).round() as i8 as u8;
+    let unorm10 = |v: f32| (v.clamp(0.0, 1.0) * 1023.0).round() as u32;
+    let [r, g, b, a] = color;
+
+    match format {
+        F::R8Unorm => out[0] = unorm8(r),
+        F::R8Snorm => out[0] = snorm8(r),
+        F::R8Uint => out[0] = r.clamp(0.0, 255.0) as u8,
+        F::R8Sint => out[0] = r.clamp(-128.0, 127.0) as i8 as u8,
+        F::R16Unorm => {
+            let v = (r.clamp(0.0, 1.0) * 65535.0).round() as u16;
+            out[..2].copy_from_slice(&v.to_le_bytes());
+        }
+        F::R16Float => out[..2].copy_from_slice(&f32_to_f16(r).to_le_bytes()),
+        F::Rg8Unorm => {
+            out[0] = unorm8(r);
+            out[1] = unorm8(g);
+        }
+        F::R32Float => out[..4].copy_from_slice(&r.to_le_bytes()),
+        F::R32Uint => out[..4].copy_from_slice(&(r.max(0.0) as u32).to_le_bytes()),
+        F::Rg16Float => {
+            out[..2].copy_from_slice(&f32_to_f16(r).to_le_bytes());
+            out[2..4].copy_from_slice(&f32_to_f16(g).to_le_bytes());
+        }
+        F::Rgba8Unorm => {
+            out[..4].copy_from_slice(&[unorm8(r), unorm8(g), unorm8(b), unorm8(a)]);
+        }
+        F::Rgba8UnormSrgb => out[..4].copy_from_slice(&[
+            unorm8(linear_to_srgb(r)),
+            unorm8(linear_to_srgb(g)),
+            unorm8(linear_to_srgb(b)),
+            unorm8(a),
+        ]),
+        F::Bgra8Unorm => {
+            out[..4].copy_from_slice(&[unorm8(b), unorm8(g), unorm8(r), unorm8(a)]);
+        }
+        F::Bgra8UnormSrgb => out[..4].copy_from_slice(&[
+            unorm8(linear_to_srgb(b)),
+            unorm8(linear_to_srgb(g)),
+            unorm8(linear_to_srgb(r)),
+            unorm8(a),
+        ]),
+        F::Rgba10a2Unorm | F::Bgra10a2Unorm => {
+            let (c0, c2) = if format == F::Rgba10a2Unorm {
+                (r, b)
+            } else {
+                (b, r)
+            };
+            let alpha = (a.clamp(0.0, 1.0) * 3.0).round() as u32;
+            let packed = unorm10(c0) | (unorm10(g) << 10) | (unorm10(c2) << 20) | (alpha << 30);
+            out[..4].copy_from_slice(&packed.to_le_bytes());
+        }
+        F::Rgba16Float => {
+            for (i, v) in color.iter().enumerate() {
+                out[i * 2..i * 2 + 2].copy_from_slice(&f32_to_f16(*v).to_le_bytes());
+            }
+        }
+        F::Rg32Float => {
+            out[..4].copy_from_slice(&r.to_le_bytes());
+            out[4..8].copy_from_slice(&g.to_le_bytes());
+        }
+        F::Rgba32Float => {
+            for (i, v) in color.iter().enumerate() {
+                out[i * 4..i * 4 + 4].copy_from_slice(&v.to_le_bytes());
+            }
+        }
+        F::Depth16Unorm
+        | F::Depth24Plus
+        | F::Depth24PlusStencil8
+        | F::Depth32Float
+        | F::Depth32FloatStencil8 => encode_depth(format, r, out),
+        _ => {}
+    }
+}
+
+/// Read the depth component of a depth/stencil texel.
+pub fn decode_depth(format: TextureFormat, bytes: &[u8]) -> f32 {
+    decode_texel(format, bytes).map_or(1.0, |texel| texel[0])
+}
+
+/// Write the depth component of a depth/stencil texel, preserving stencil.
+pub fn encode_depth(format: TextureFormat, depth: f32, out: &mut [u8]) {
+    let depth = depth.clamp(0.0, 1.0);
+    match format {
+        TextureFormat::Depth16Unorm => {
+            let v = (depth * 65535.0).round() as u16;
+            out[..2].copy_from_slice(&v.to_le_bytes());
+        }
+        TextureFormat::Depth24Plus
+        | TextureFormat::Depth32Float
+        | TextureFormat::Depth32FloatStencil8 => out[..4].copy_from_slice(&depth.to_le_bytes()),
+        TextureFormat::Depth24PlusStencil8 => {
+            let v = (depth * 16_777_215.0).round() as u32;
+            out[0] = v as u8;
+            out[1] = (v >> 8) as u8;
+            out[2] = (v >> 16) as u8;
+        }
+        _ => {}
+    }
+}
+
+/// Write the stencil component of a depth/stencil texel, preserving depth.
+pub fn encode_stencil(format: TextureFormat, stencil: u8, out: &mut [u8]) {
+    match format {
+        TextureFormat::Depth24PlusStencil8 => out[3] = stencil,
+        TextureFormat::Depth32FloatStencil8 => out[4] = stencil,
+        _ => {}
+    }
+}
+
+/// Convert an sRGB-encoded channel to linear.
+pub fn srgb_to_linear(v: f32) -> f32 {
+    if v <= 0.04045 {
+        v / 12.92
+    } else {
+        ((v + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Convert a linear channel to sRGB encoding.
+pub fn linear_to_srgb(v: f32) -> f32 {
+    let v = v.clamp(0.0, 1.0);
+    if v <= 0.003_130_8 {
+        v * 12.92
+    } else {
+        1.055 * v.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Convert an IEEE 754 half-precision float to `f32`.
+pub fn f16_to_f32(bits: u16) -> f32 {
+    let sign = ((bits >> 15) as u32) << 31;
+    let exponent = ((bits >> 10) & 0x1f) as u32;
+    let mantissa = (bits & 0x3ff) as u32;
+
+    let value = match (exponent, mantissa) {
+        (0, 0) => sign,
+        (0, _) => {
+            // Subnormal: normalize the mantissa.
+            let shift = mantissa.leading_zeros() - 21;
+            let mantissa = (mantissa << shift) & 0x3ff;
+            sign | ((113 - shift) << 23) | (mantissa << 13)
+        }
+        (0x1f, _) => sign | 0x7f80_0000 | (mantissa << 13),
+        _ => sign | ((exponent + 112) << 23) | (mantissa << 13),
+    };
+    f32::from_bits(value)
+}
+
+/// Convert an `f32` to IEEE 754 half-precision, rounding to nearest.
+pub fn f32_to_f16(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x007f_ffff;
+
+    if exponent == 0xff {
+        let nan = if mantissa != 0 { 0x200 } else { 0 };
+        return sign | 0x7c00 | nan;
+    }
+
+    let half_exponent = exponent - 112;
+    if half_exponent >= 0x1f {
+        return sign | 0x7c00;
+    }
+    if half_exponent <= 0 {
+        if half_exponent < -10 {
+            return sign;
+        }
+        let mantissa = mantissa | 0x0080_0000;
+        let shift = (14 - half_exponent) as u32;
+        let rounded = (mantissa + (1 << (shift - 1))) >> shift;
+        return sign | rounded as u16;
+    }
+
+    let rounded = ((half_exponent as u32) << 10) + ((mantissa + 0x1000) >> 13);
+    // Mantissa rounding may carry into the exponent, which is the correct result.
+    if rounded >= 0x7c00 {
+        sign | 0x7c00
+    } else {
+        sign | rounded as u16
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rgba8_round_trip() {
+        let mut bytes = [0u8; 4];
+        encode_texel(TextureFormat::Rgba8Unorm, [1.0, 0.5, 0.0, 1.0], &mut bytes);
+        assert_eq!(bytes, [255, 128, 0, 255]);
+        let decoded = decode_texel(TextureFormat::Rgba8Unorm, &bytes).unwrap();
+        assert!((decoded[1] - 128.0 / 255.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_bgra8_swizzle() {
+        let mut bytes = [0u8; 4];
+        encode_texel(TextureFormat::Bgra8Unorm, [1.0, 0.0, 0.0, 1.0], &mut bytes);
+        assert_eq!(bytes, [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn test_srgb_round_trip() {
+        for i in 0..=255u8 {
+            let linear = srgb_to_linear(i as f32 / 255.0);
+            let back = (linear_to_srgb(linear) * 255.0).round() as u8;
+            assert_eq!(back, i);
+        }
+    }
+
+    #[test]
+    fn test_f16_round_trip() {
+        for value in [0.0f32, 1.0, -2.5, 0.333, 65504.0, 6.1e-5, 1.0e-7] {
+            let back = f16_to_f32(f32_to_f16(value));
+            assert!(
+                (back - value).abs() <= value.abs() * 1e-3 + 1e-7,
+                "{value} -> {back}"
+            );
+        }
+        assert_eq!(f32_to_f16(f32::INFINITY), 0x7c00);
+        assert!(f16_to_f32(f32_to_f16(f32::NAN)).is_nan());
+    }
+
+    #[test]
+    fn test_depth24_stencil8_preserves_stencil() {
+        let mut bytes = [0u8; 4];
+        encode_stencil(TextureFormat::Depth24PlusStencil8, 7, &mut bytes);
+        encode_depth(TextureFormat::Depth24PlusStencil8, 0.5, &mut bytes);
+        assert_eq!(bytes[3], 7);
+        let depth = decode_depth(TextureFormat::Depth24PlusStencil8, &bytes);
+        assert!((depth - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_compressed_formats_are_not_decoded() {
+        assert!(decode_texel(TextureFormat::Bc1RgbaUnorm, &[0u8; 8]).is_none());
+    }
+}
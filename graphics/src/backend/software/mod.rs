@@ -0,0 +1,500 @@
+//! Software (CPU) rasterizer backend.
+//!
+//! This backend keeps all buffers and textures in host memory and executes
+//! render graphs on the CPU:
+//!
+//! - Graphics passes are rasterized with depth testing, blending, viewport
+//!   and scissor support (see [`raster`])
+//! - Transfer passes copy between buffers and textures using the same
+//!   row-padding rules as the GPU backends (see [`transfer`])
+//! - Compute passes are not executed
+//!
+//! Shaders cannot be compiled for the CPU, so materials provide a
+//! [`SoftwareShader`] implementation instead. This makes it possible to run
+//! pixel-verifying tests without GPU hardware.
+//!
+//! Execution is synchronous: fences are signaled before `execute_graph` returns.
+
+mod format;
+mod raster;
+mod shader;
+mod transfer;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use parking_lot::RwLock;
+use redlilium_core::profile_scope;
+
+use crate::error::GraphicsError;
+use crate::graph::{CompiledGraph, Pass, RenderGraph};
+use crate::materials::MaterialDescriptor;
+use crate::types::{
+    BufferDescriptor, SamplerDescriptor, TextureDescriptor, TextureDimension, TextureFormat,
+};
+
+use super::{GpuBuffer, GpuFence, GpuPipeline, GpuSampler, GpuSemaphore, GpuTexture};
+
+pub use format::{decode_texel, encode_texel};
+pub use shader::{
+    FragmentInput, FragmentOutput, MAX_COLOR_ATTACHMENTS, MAX_VARYING_SLOTS, PassthroughShader,
+    ShaderResources, SoftwareShader, Varyings, VertexInput,
+};
+
+// ============================================================================
+// Resources
+// ============================================================================
+
+/// Host-memory storage for a buffer.
+pub struct SoftwareBuffer {
+    pub(super) data: RwLock<Vec<u8>>,
+}
+
+impl SoftwareBuffer {
+    fn new(size: u64) -> Self {
+        Self {
+            data: RwLock::new(vec![0u8; size as usize]),
+        }
+    }
+
+    /// Get the buffer size in bytes.
+    pub fn size(&self) -> u64 {
+        self.data.read().len() as u64
+    }
+}
+
+impl std::fmt::Debug for SoftwareBuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SoftwareBuffer")
+            .field("size", &self.size())
+            .finish()
+    }
+}
+
+/// Host-memory storage for a texture.
+///
+/// Each subresource (mip level × array layer) is stored as tightly packed
+/// rows. Depth slices of 3D textures are stored as array layers.
+pub struct SoftwareTexture {
+    format: TextureFormat,
+    width: u32,
+    height: u32,
+    mip_level_count: u32,
+    layer_count: u32,
+    pub(super) subresources: RwLock<Vec<Vec<u8>>>,
+}
+
+impl SoftwareTexture {
+    fn new(descriptor: &TextureDescriptor) -> Self {
+        let layer_count = layer_count(descriptor);
+        let mip_level_count = descriptor.mip_level_count.max(1);
+        let mut texture = Self {
+            format: descriptor.format,
+            width: descriptor.size.width.max(1),
+            height: descriptor.size.height.max(1),
+            mip_level_count,
+            layer_count,
+            subresources: RwLock::new(Vec::new()),
+        };
+        let subresources = (0..mip_level_count)
+            .flat_map(|mip| {
+                let size = texture.row_pitch(mip) * texture.row_count(mip);
+                (0..layer_count).map(move |_| vec![0u8; size])
+            })
+            .collect();
+        texture.subresources = RwLock::new(subresources);
+        texture
+    }
+
+    /// Get the texture format.
+    pub fn format(&self) -> TextureFormat {
+        self.format
+    }
+
+    /// Get the number of array layers (6 per cube, depth slices for 3D).
+    pub fn layer_count(&self) -> u32 {
+        self.layer_count
+    }
+
+    /// Get the number of mip levels.
+    pub fn mip_level_count(&self) -> u32 {
+        self.mip_level_count
+    }
+
+    /// Get the size in texels of a mip level.
+    pub fn mip_extent(&self, mip_level: u32) -> (u32, u32) {
+        (
+            (self.width >> mip_level).max(1),
+            (self.height >> mip_level).max(1),
+        )
+    }
+
+    /// Bytes per tightly packed row of blocks in a mip level.
+    pub(super) fn row_pitch(&self, mip_level: u32) -> usize {
+        let (block_width, _) = self.format.block_dimensions();
+        let (width, _) = self.mip_extent(mip_level);
+        width.div_ceil(block_width) as usize * self.format.block_size() as usize
+    }
+
+    /// Number of block rows in a mip level.
+    pub(super) fn row_count(&self, mip_level: u32) -> usize {
+        let (_, block_height) = self.format.block_dimensions();
+        let (_, height) = self.mip_extent(mip_level);
+        height.div_ceil(block_height) as usize
+    }
+
+    /// Index of a subresource in the storage vector.
+    pub(super) fn subresource_index(&self, mip_level: u32, layer: u32) -> Option<usize> {
+        (mip_level < self.mip_level_count && layer < self.layer_count)
+            .then(|| (mip_level * self.layer_count + layer) as usize)
+    }
+
+    /// Read a single texel as linear RGBA.
+    ///
+    /// Returns `None` if the coordinates are out of range, the format is
+    /// block-compressed, or the texture is currently being rendered to.
+    pub fn load(&self, mip_level: u32, layer: u32, x: u32, y: u32) -> Option<[f32; 4]> {
+        let (width, height) = self.mip_extent(mip_level);
+        if x >= width || y >= height {
+            return None;
+        }
+        let index = self.subresource_index(mip_level, layer)?;
+        let subresources = self.subresources.try_read()?;
+        let texel_size = self.format.block_size() as usize;
+        let offset = y as usize * self.row_pitch(mip_level) + x as usize * texel_size;
+        decode_texel(
+            self.format,
+            &subresources[index][offset..offset + texel_size],
+        )
+    }
+}
+
+impl std::fmt::Debug for SoftwareTexture {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SoftwareTexture")
+            .field("format", &self.format)
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("mip_level_count", &self.mip_level_count)
+            .field("layer_count", &self.layer_count)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Number of array layers stored for a texture descriptor.
+fn layer_count(descriptor: &TextureDescriptor) -> u32 {
+    match descriptor.dimension {
+        TextureDimension::Cube => 6,
+        TextureDimension::CubeArray => descriptor.size.depth.max(1) * 6,
+        TextureDimension::D1
+        | TextureDimension::D1Array
+        | TextureDimension::D2
+        | TextureDimension::D2Array
+        | TextureDimension::D3 => descriptor.size.depth.max(1),
+    }
+}
+
+/// CPU pipeline: the shader that replaces the compiled GPU shader stages.
+#[derive(Debug)]
+pub struct SoftwarePipeline {
+    pub(super) shader: Arc<dyn SoftwareShader>,
+}
+
+// ============================================================================
+// Backend
+// ============================================================================
+
+/// Software rasterizer backend.
+#[derive(Debug)]
+pub struct SoftwareBackend;
+
+impl SoftwareBackend {
+    /// Create a new software backend.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Get the backend name.
+    pub fn name(&self) -> &'static str {
+        "Software Backend"
+    }
+
+    /// Create a buffer resource.
+    pub fn create_buffer(&self, descriptor: &BufferDescriptor) -> Result<GpuBuffer, GraphicsError> {
+        Ok(GpuBuffer::Software(SoftwareBuffer::new(descriptor.size)))
+    }
+
+    /// Create a texture resource.
+    pub fn create_texture(
+        &self,
+        descriptor: &TextureDescriptor,
+    ) -> Result<GpuTexture, GraphicsError> {
+        if descriptor.sample_count > 1 {
+            log::debug!(
+                "Software backend ignores multisampling for texture {:?}",
+                descriptor.label
+            );
+        }
+        Ok(GpuTexture::Software(SoftwareTexture::new(descriptor)))
+    }
+
+    /// Create a sampler resource.
+    pub fn create_sampler(
+        &self,
+        descriptor: &SamplerDescriptor,
+    ) -> Result<GpuSampler, GraphicsError> {
+        Ok(GpuSampler::Software(descriptor.clone()))
+    }
+
+    /// Create a pipeline from a material descriptor.
+    ///
+    /// Uses the descriptor's software shader, or [`PassthroughShader`] if none is set.
+    pub fn create_pipeline(
+        &self,
+        descriptor: &MaterialDescriptor,
+    ) -> Result<GpuPipeline, GraphicsError> {
+        let shader = descriptor
+            .software_shader
+            .clone()
+            .unwrap_or_else(|| Arc::new(PassthroughShader));
+        Ok(GpuPipeline::Software(SoftwarePipeline { shader }))
+    }
+
+    /// Create a GPU semaphore (no-op, execution is synchronous).
+    pub fn create_semaphore(&self) -> GpuSemaphore {
+        GpuSemaphore::Dummy
+    }
+
+    /// Create a fence for CPU-GPU synchronization.
+    ///
+    /// Uses the CPU-side fence shared with the dummy backend.
+    pub fn create_fence(&self, signaled: bool) -> GpuFence {
+        GpuFence::Dummy {
+            signaled: AtomicBool::new(signaled),
+        }
+    }
+
+    /// Wait for a fence to be signaled.
+    pub fn wait_fence(&self, fence: &GpuFence) {
+        if let GpuFence::Dummy { signaled } = fence {
+            while !signaled.load(Ordering::Acquire) {
+                std::thread::yield_now();
+            }
+        }
+    }
+
+    /// Wait for a fence to be signaled with a timeout.
+    ///
+    /// Returns `true` if the fence was signaled, `false` if the timeout elapsed.
+    pub fn wait_fence_timeout(&self, fence: &GpuFence, timeout: std::time::Duration) -> bool {
+        let GpuFence::Dummy { signaled } = fence else {
+            return false;
+        };
+        let start = std::time::Instant::now();
+        while !signaled.load(Ordering::Acquire) {
+            if start.elapsed() >= timeout {
+                return false;
+            }
+            std::thread::yield_now();
+        }
+        true
+    }
+
+    /// Check if a fence is signaled (non-blocking).
+    pub fn is_fence_signaled(&self, fence: &GpuFence) -> bool {
+        match fence {
+            GpuFence::Dummy { signaled } => signaled.load(Ordering::Acquire),
+            #[allow(unreachable_patterns)]
+            _ => false,
+        }
+    }
+
+    /// Signal a fence.
+    pub fn signal_fence(&self, fence: &GpuFence) {
+        if let GpuFence::Dummy { signaled } = fence {
+            signaled.store(true, Ordering::Release);
+        }
+    }
+
+    /// Execute a compiled render graph on the CPU.
+    pub fn execute_graph(
+        &self,
+        graph: &RenderGraph,
+        compiled: &CompiledGraph,
+        _wait_semaphores: &[&GpuSemaphore],
+        _signal_semaphores: &[&GpuSemaphore],
+        signal_fence: Option<&GpuFence>,
+    ) -> Result<(), GraphicsError> {
+        profile_scope!("software_execute_graph");
+
+        let passes = graph.passes();
+        for handle in compiled.pass_order() {
+            match &passes[handle.index()] {
+                Pass::Graphics(pass) => raster::execute_graphics_pass(pass)?,
+                Pass::Transfer(pass) => transfer::execute_transfer_pass(pass)?,
+                Pass::Compute(pass) => {
+                    log::debug!(
+                        "Software backend skips compute pass '{}' (not supported)",
+                        pass.name()
+                    );
+                }
+            }
+        }
+
+        if let Some(fence) = signal_fence {
+            self.signal_fence(fence);
+        }
+        Ok(())
+    }
+
+    /// Write data to a buffer.
+    pub fn write_buffer(
+        &self,
+        buffer: &GpuBuffer,
+        offset: u64,
+        data: &[u8],
+    ) -> Result<(), GraphicsError> {
+        let GpuBuffer::Software(buffer) = buffer else {
+            return Err(GraphicsError::Internal(
+                "write_buffer called with non-Software buffer".to_string(),
+            ));
+        };
+        let mut storage = buffer.data.write();
+        let start = offset as usize;
+        let end = start + data.len();
+        if end > storage.len() {
+            return Err(GraphicsError::InvalidParameter(format!(
+                "write of {} bytes at offset {offset} exceeds buffer size {}",
+                data.len(),
+                storage.len()
+            )));
+        }
+        storage[start..end].copy_from_slice(data);
+        Ok(())
+    }
+
+    /// Read data from a buffer.
+    pub fn read_buffer(&self, buffer: &GpuBuffer, offset: u64, size: u64) -> Vec<u8> {
+        let GpuBuffer::Software(buffer) = buffer else {
+            return vec![0u8; size as usize];
+        };
+        let storage = buffer.data.read();
+        let start = (offset as usize).min(storage.len());
+        let end = (start + size as usize).min(storage.len());
+        let mut data = storage[start..end].to_vec();
+        data.resize(size as usize, 0);
+        data
+    }
+
+    /// Write tightly packed data to a texture's subresources.
+    ///
+    /// Data fills every layer of mip 0, then every layer of mip 1 and so
+    /// on, stopping when it runs out; shorter data leaves the remaining
+    /// subresources untouched.
+    pub fn write_texture(
+        &self,
+        texture: &GpuTexture,
+        data: &[u8],
+        _descriptor: &TextureDescriptor,
+    ) -> Result<(), GraphicsError> {
+        let GpuTexture::Software(texture) = texture else {
+            return Err(GraphicsError::Internal(
+                "write_texture called with non-Software texture".to_string(),
+            ));
+        };
+        let mut subresources = texture.subresources.write();
+        let mut remaining = data;
+        for subresource in subresources.iter_mut() {
+            if remaining.is_empty() {
+                break;
+            }
+            let len = subresource.len().min(remaining.len());
+            subresource[..len].copy_from_slice(&remaining[..len]);
+            remaining = &remaining[len..];
+        }
+        Ok(())
+    }
+
+    /// Fill mip levels `1..` of a texture by downsampling each level from
+    /// the previous one.
+    pub fn generate_mipmaps(
+        &self,
+        texture: &GpuTexture,
+        descriptor: &TextureDescriptor,
+    ) -> Result<(), GraphicsError> {
+        let GpuTexture::Software(texture) = texture else {
+            return Err(GraphicsError::Internal(
+                "generate_mipmaps called with non-Software texture".to_string(),
+            ));
+        };
+        for region in crate::graph::TextureBlitRegion::mip_chain(descriptor) {
+            transfer::blit_region(texture, texture, &region, crate::types::FilterMode::Linear)?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for SoftwareBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{BufferUsage, TextureUsage};
+
+    #[test]
+    fn test_buffer_write_read() {
+        let backend = SoftwareBackend::new();
+        let buffer = backend
+            .create_buffer(&BufferDescriptor::new(16, BufferUsage::COPY_DST))
+            .unwrap();
+        backend.write_buffer(&buffer, 4, &[1, 2, 3, 4]).unwrap();
+        assert_eq!(
+            backend.read_buffer(&buffer, 0, 10),
+            vec![0, 0, 0, 0, 1, 2, 3, 4, 0, 0]
+        );
+        assert!(backend.write_buffer(&buffer, 14, &[0; 4]).is_err());
+    }
+
+    #[test]
+    fn test_texture_storage_layout() {
+        let descriptor = TextureDescriptor::new_2d(
+            5,
+            3,
+            TextureFormat::Rgba8Unorm,
+            TextureUsage::TEXTURE_BINDING,
+        )
+        .with_mip_levels(3);
+        let texture = SoftwareTexture::new(&descriptor);
+        assert_eq!(texture.mip_extent(1), (2, 1));
+        assert_eq!(texture.mip_extent(2), (1, 1));
+        assert_eq!(texture.row_pitch(0), 20);
+        assert_eq!(texture.subresources.read().len(), 3);
+    }
+
+    #[test]
+    fn test_write_texture_and_load() {
+        let backend = SoftwareBackend::new();
+        let descriptor = TextureDescriptor::new_2d(
+            2,
+            2,
+            TextureFormat::Rgba8Unorm,
+            TextureUsage::TEXTURE_BINDING,
+        );
+        let texture = backend.create_texture(&descriptor).unwrap();
+        let data = [
+            255, 0, 0, 255, 0, 255, 0, 255, //
+            0, 0, 255, 255, 255, 255, 255, 255,
+        ];
+        backend.write_texture(&texture, &data, &descriptor).unwrap();
+        let GpuTexture::Software(texture) = &texture else {
+            unreachable!()
+        };
+        assert_eq!(texture.load(0, 0, 1, 0), Some([0.0, 1.0, 0.0, 1.0]));
+        assert_eq!(texture.load(0, 0, 0, 1), Some([0.0, 0.0, 1.0, 1.0]));
+        assert_eq!(texture.load(0, 0, 2, 0), None);
+    }
+}
@@ -0,0 +1,862 @@
+//! Graphics pass execution for the software backend.
+//!
+//! Follows the conventions of the GPU backends:
+//! - Clip space is wgpu-style: +Y up, depth in `[0, w]`
+//! - The framebuffer origin is the top-left corner
+//! - Pixel coverage is sampled at pixel centers with a top-left fill rule
+//! - Depth testing uses `LessEqual`; depth is written when the material has a
+//!   depth format and the attachment is not read-only
+//! - No face culling
+
+use std::collections::HashMap;
+
+use parking_lot::{MappedRwLockWriteGuard, RwLockReadGuard, RwLockWriteGuard};
+use redlilium_core::profile_scope;
+
+use crate::error::GraphicsError;
+use crate::graph::{GraphicsPass, LoadOp, RenderTarget};
+use crate::materials::{
+    BlendComponent, BlendFactor, BlendOperation, BlendState, MaterialInstance, PolygonMode,
+};
+use crate::mesh::{IndexFormat, Mesh, PrimitiveTopology};
+use crate::types::{ClearValue, ScissorRect, TextureFormat};
+
+use super::super::{GpuBuffer, GpuPipeline, GpuTexture};
+use super::SoftwareTexture;
+use super::format::{decode_depth, decode_texel, encode_depth, encode_stencil, encode_texel};
+use super::shader::{
+    FragmentInput, FragmentOutput, MAX_COLOR_ATTACHMENTS, ShaderResources, SoftwareShader,
+    Varyings, VertexInput,
+};
+
+/// Smallest `w` accepted after clipping, avoids division by zero.
+const W_EPSILON: f32 = 1e-6;
+
+// ============================================================================
+// Framebuffer
+// ============================================================================
+
+/// A locked texture subresource used as a render target.
+struct TargetView<'a> {
+    format: TextureFormat,
+    width: u32,
+    height: u32,
+    row_pitch: usize,
+    texel_size: usize,
+    data: MappedRwLockWriteGuard<'a, Vec<u8>>,
+}
+
+impl TargetView<'_> {
+    fn texel_mut(&mut self, x: u32, y: u32) -> &mut [u8] {
+        let offset = y as usize * self.row_pitch + x as usize * self.texel_size;
+        &mut self.data[offset..offset + self.texel_size]
+    }
+
+    fn fill(&mut self, texel: &[u8]) {
+        for chunk in self.data.chunks_exact_mut(self.texel_size) {
+            chunk.copy_from_slice(texel);
+        }
+    }
+}
+
+/// Lock the subresource referenced by a render target.
+///
+/// Returns `Ok(None)` for surface targets, which the software backend cannot present to.
+fn lock_target<'a>(
+    target: &'a RenderTarget,
+    pass_name: &str,
+) -> Result<Option<TargetView<'a>>, GraphicsError> {
+    let RenderTarget::Texture {
+        texture,
+        mip_level,
+        array_layer,
+    } = target
+    else {
+        log::debug!("Software backend skips surface attachment in pass '{pass_name}'");
+        return Ok(None);
+    };
+    let GpuTexture::Software(software) = texture.gpu_handle() else {
+        return Err(GraphicsError::Internal(format!(
+            "Pass '{pass_name}' renders to a non-Software texture"
+        )));
+    };
+    lock_subresource(software, *mip_level, *array_layer, pass_name).map(Some)
+}
+
+fn lock_subresource<'a>(
+    texture: &'a SoftwareTexture,
+    mip_level: u32,
+    layer: u32,
+    pass_name: &str,
+) -> Result<TargetView<'a>, GraphicsError> {
+    let index = texture.subresource_index(mip_level, layer).ok_or_else(|| {
+        GraphicsError::InvalidParameter(format!(
+            "Pass '{pass_name}' targets mip {mip_level} layer {layer} which does not exist"
+        ))
+    })?;
+    let guard = texture.subresources.try_write().ok_or_else(|| {
+        GraphicsError::InvalidParameter(format!(
+            "Pass '{pass_name}' uses the same texture for several attachments"
+        ))
+    })?;
+    let (width, height) = texture.mip_extent(mip_level);
+    Ok(TargetView {
+        format: texture.format(),
+        width,
+        height,
+        row_pitch: texture.row_pitch(mip_level),
+        texel_size: texture.format().block_size() as usize,
+        data: RwLockWriteGuard::map(guard, |subresources| &mut subresources[index]),
+    })
+}
+
+/// Locked attachments of a graphics pass.
+struct Framebuffer<'a> {
+    colors: Vec<Option<TargetView<'a>>>,
+    depth: Option<TargetView<'a>>,
+    depth_read_only: bool,
+    width: u32,
+    height: u32,
+}
+
+/// Pixel rectangle `[x0, x1) × [y0, y1)` that fragments may be written to.
+#[derive(Debug, Clone, Copy)]
+struct ClipRect {
+    x0: u32,
+    y0: u32,
+    x1: u32,
+    y1: u32,
+}
+
+impl ClipRect {
+    fn intersect_scissor(self, scissor: &ScissorRect) -> Self {
+        let sx0 = scissor.x.max(0) as u32;
+        let sy0 = scissor.y.max(0) as u32;
+        let sx1 = (scissor.x as i64 + scissor.width as i64).max(0) as u32;
+        let sy1 = (scissor.y as i64 + scissor.height as i64).max(0) as u32;
+        Self {
+            x0: self.x0.max(sx0),
+            y0: self.y0.max(sy0),
+            x1: self.x1.min(sx1),
+            y1: self.y1.min(sy1),
+        }
+    }
+
+    fn contains(&self, x: u32, y: u32) -> bool {
+        x >= self.x0 && x < self.x1 && y >= self.y0 && y < self.y1
+    }
+}
+
+// ============================================================================
+// Pass Execution
+// ============================================================================
+
+/// Execute a graphics pass: apply load operations, rasterize draws and resolve.
+pub(super) fn execute_graphics_pass(pass: &GraphicsPass) -> Result<(), GraphicsError> {
+    profile_scope!("software_graphics_pass");
+
+    let Some(render_targets) = pass.render_targets() else {
+        return Ok(());
+    };
+
+    let mut colors = Vec::with_capacity(render_targets.color_attachments.len());
+    for attachment in &render_targets.color_attachments {
+        let mut view = lock_target(&attachment.target, pass.name())?;
+        if let (Some(view), LoadOp::Clear(ClearValue::Color { r, g, b, a })) =
+            (view.as_mut(), attachment.load_op())
+        {
+            let mut texel = vec![0u8; view.texel_size];
+            encode_texel(view.format, [r, g, b, a], &mut texel);
+            view.fill(&texel);
+        }
+        colors.push(view);
+    }
+
+    let mut depth = None;
+    let mut depth_read_only = true;
+    if let Some(attachment) = &render_targets.depth_stencil_attachment {
+        depth = lock_target(&attachment.target, pass.name())?;
+        depth_read_only = attachment.depth_read_only;
+        if let Some(view) = depth.as_mut() {
+            let clear_depth = match attachment.depth_load_op() {
+                LoadOp::Clear(ClearValue::Depth(d))
+                | LoadOp::Clear(ClearValue::DepthStencil { depth: d, .. }) => Some(d),
+                _ => None,
+            };
+            let clear_stencil = match attachment.stencil_load_op() {
+                LoadOp::Clear(ClearValue::Stencil(s))
+                | LoadOp::Clear(ClearValue::DepthStencil { stencil: s, .. }) => Some(s as u8),
+                _ => None,
+            };
+            // Depth and stencil share texels, so clear each aspect in place.
+            let format = view.format;
+            let texel_size = view.texel_size;
+            for texel in view.data.chunks_exact_mut(texel_size) {
+                if let Some(d) = clear_depth {
+                    encode_depth(format, d, texel);
+                }
+                if let Some(s) = clear_stencil {
+                    encode_stencil(format, s, texel);
+                }
+            }
+        }
+    }
+
+    let Some((width, height)) = colors
+        .iter()
+        .flatten()
+        .chain(depth.iter())
+        .map(|view| (view.width, view.height))
+        .next()
+    else {
+        return Ok(());
+    };
+
+    let mut framebuffer = Framebuffer {
+        colors,
+        depth,
+        depth_read_only,
+        width,
+        height,
+    };
+
+    let viewport = pass
+        .viewport()
+        .map(|vp| [vp.x, vp.y, vp.width, vp.height, vp.min_depth, vp.max_depth])
+        .unwrap_or([0.0, 0.0, width as f32, height as f32, 0.0, 1.0]);
+
+    let mut pass_clip = ClipRect {
+        x0: viewport[0].max(0.0).floor() as u32,
+        y0: viewport[1].max(0.0).floor() as u32,
+        x1: ((viewport[0] + viewport[2]).ceil().max(0.0) as u32).min(width),
+        y1: ((viewport[1] + viewport[3]).ceil().max(0.0) as u32).min(height),
+    };
+    let viewport_clip = pass_clip;
+    if let Some(scissor) = pass.scissor_rect() {
+        pass_clip = pass_clip.intersect_scissor(scissor);
+    }
+
+    for draw in pass.draw_commands() {
+        let clip = match &draw.scissor_rect {
+            Some(scissor) => viewport_clip.intersect_scissor(scissor),
+            None => pass_clip,
+        };
+        let range = if draw.mesh.is_indexed() {
+            DrawRange::Indexed {
+                index_count: draw.mesh.index_count(),
+                first_index: 0,
+                base_vertex: 0,
+            }
+        } else {
+            DrawRange::Vertices {
+                vertex_count: draw.mesh.vertex_count(),
+                first_vertex: 0,
+            }
+        };
+        let instances = draw.first_instance..draw.first_instance + draw.instance_count;
+        draw_mesh(
+            &mut framebuffer,
+            &draw.mesh,
+            &draw.material,
+            range,
+            instances,
+            clip,
+            viewport,
+        );
+    }
+
+    for draw in pass.indirect_draw_commands() {
+        let GpuBuffer::Software(buffer) = draw.indirect_buffer.gpu_handle() else {
+            continue;
+        };
+        let args: Vec<[u32; 5]> = {
+            let data = buffer.data.read();
+            (0..draw.draw_count)
+                .filter_map(|i| {
+                    let offset = draw.indirect_offset as usize + (i * draw.stride) as usize;
+                    let bytes = data.get(offset..offset + 20)?;
+                    let word =
+                        |w: usize| u32::from_le_bytes(bytes[w * 4..w * 4 + 4].try_into().unwrap());
+                    Some([word(0), word(1), word(2), word(3), word(4)])
+                })
+                .collect()
+        };
+        for [a, b, c, d, e] in args {
+            let (range, instances) = if draw.indexed {
+                (
+                    DrawRange::Indexed {
+                        index_count: a,
+                        first_index: c,
+                        base_vertex: d as i32,
+                    },
+                    e..e + b,
+                )
+            } else {
+                (
+                    DrawRange::Vertices {
+                        vertex_count: a,
+                        first_vertex: c,
+                    },
+                    d..d + b,
+                )
+            };
+            draw_mesh(
+                &mut framebuffer,
+                &draw.mesh,
+                &draw.material,
+                range,
+                instances,
+                pass_clip,
+                viewport,
+            );
+        }
+    }
+
+    // Resolve: copy the rendered subresource into the resolve target.
+    for (attachment, view) in render_targets
+        .color_attachments
+        .iter()
+        .zip(&framebuffer.colors)
+    {
+        let (Some(resolve), Some(view)) = (&attachment.resolve_target, view) else {
+            continue;
+        };
+        if let Some(mut resolve_view) = lock_target(resolve, pass.name())?
+            && resolve_view.data.len() == view.data.len()
+        {
+            resolve_view.data.copy_from_slice(&view.data);
+        }
+    }
+
+    Ok(())
+}
+
+/// Vertex range of a single draw.
+enum DrawRange {
+    Vertices {
+        vertex_count: u32,
+        first_vertex: u32,
+    },
+    Indexed {
+        index_count: u32,
+        first_index: u32,
+        base_vertex: i32,
+    },
+}
+
+/// Per-draw state shared by all primitives.
+struct DrawState<'a> {
+    shader: &'a dyn SoftwareShader,
+    resources: ShaderResources<'a>,
+    blend: Option<BlendState>,
+    depth_write: bool,
+    wireframe: bool,
+    clip: ClipRect,
+    /// Viewport as `[x, y, width, height, min_depth, max_depth]`.
+    viewport: [f32; 6],
+}
+
+/// A vertex after the vertex stage, in clip space.
+#[derive(Clone, Copy)]
+struct ClipVertex {
+    position: [f32; 4],
+    varyings: Varyings,
+}
+
+/// A vertex after perspective division and viewport transform.
+#[derive(Clone, Copy)]
+struct ScreenVertex {
+    x: f32,
+    y: f32,
+    z: f32,
+    inv_w: f32,
+    varyings: Varyings,
+}
+
+fn draw_mesh(
+    framebuffer: &mut Framebuffer<'_>,
+    mesh: &Mesh,
+    material: &MaterialInstance,
+    range: DrawRange,
+    instances: std::ops::Range<u32>,
+    clip: ClipRect,
+    viewport: [f32; 6],
+) {
+    let material_arc = material.material();
+    let GpuPipeline::Software(pipeline) = material_arc.gpu_handle() else {
+        log::warn!("Material has no software pipeline");
+        return;
+    };
+
+    let vertex_guards: Vec<Option<RwLockReadGuard<'_, Vec<u8>>>> = mesh
+        .vertex_buffers()
+        .iter()
+        .map(|buffer| match buffer.gpu_handle() {
+            GpuBuffer::Software(buffer) => buffer.data.try_read(),
+            _ => None,
+        })
+        .collect();
+    let vertex_data: Vec<&[u8]> = vertex_guards
+        .iter()
+        .map(|guard| guard.as_deref().map_or(&[][..], |data| data.as_slice()))
+        .collect();
+
+    let indices: Vec<u32> = match range {
+        DrawRange::Vertices {
+            vertex_count,
+            first_vertex,
+        } => (first_vertex..first_vertex + vertex_count).collect(),
+        DrawRange::Indexed {
+            index_count,
+            first_index,
+            base_vertex,
+        } => {
+            let Some(GpuBuffer::Software(buffer)) =
+                mesh.index_buffer().map(|buffer| buffer.gpu_handle())
+            else {
+                return;
+            };
+            let data = buffer.data.read();
+            let format = mesh.index_format().unwrap_or(IndexFormat::Uint16);
+            (first_index..first_index + index_count)
+                .filter_map(|i| {
+                    let index = match format {
+                        IndexFormat::Uint16 => {
+                            let at = i as usize * 2;
+                            u16::from_le_bytes(data.get(at..at + 2)?.try_into().ok()?) as u32
+                        }
+                        IndexFormat::Uint32 => {
+                            let at = i as usize * 4;
+                            u32::from_le_bytes(data.get(at..at + 4)?.try_into().ok()?)
+                        }
+                    };
+                    Some((index as i64 + base_vertex as i64) as u32)
+                })
+                .collect()
+        }
+    };
+
+    let descriptor = material_arc.descriptor();
+    let state = DrawState {
+        shader: pipeline.shader.as_ref(),
+        resources: ShaderResources {
+            groups: material.binding_groups(),
+        },
+        blend: descriptor.blend_state,
+        depth_write: descriptor.depth_format.is_some()
+            && descriptor.depth_write
+            && !framebuffer.depth_read_only,
+        wireframe: descriptor.polygon_mode == PolygonMode::Line,
+        clip,
+        viewport,
+    };
+    let topology = descriptor.topology;
+
+    let mut cache: HashMap<u32, ClipVertex> = HashMap::new();
+    for instance_index in instances {
+        cache.clear();
+        let mut shade = |vertex_index: u32| -> ClipVertex {
+            *cache.entry(vertex_index).or_insert_with(|| {
+                let input = VertexInput {
+                    layout: mesh.layout(),
+                    buffers: &vertex_data,
+                    vertex_index,
+                    instance_index,
+                };
+                let mut varyings = Varyings::default();
+                let position = state.shader.vertex(&input, &state.resources, &mut varyings);
+                ClipVertex { position, varyings }
+            })
+        };
+
+        match topology {
+            PrimitiveTopology::TriangleList => {
+                for tri in indices.chunks_exact(3) {
+                    let vertices = [shade(tri[0]), shade(tri[1]), shade(tri[2])];
+                    draw_triangle(framebuffer, &state, vertices);
+                }
+            }
+            PrimitiveTopology::TriangleStrip => {
+                for (i, tri) in indices.windows(3).enumerate() {
+                    // Keep a consistent winding for odd triangles.
+                    let (a, b) = if i % 2 == 0 {
+                        (tri[0], tri[1])
+                    } else {
+                        (tri[1], tri[0])
+                    };
+                    let vertices = [shade(a), shade(b), shade(tri[2])];
+                    draw_triangle(framebuffer, &state, vertices);
+                }
+            }
+            PrimitiveTopology::LineList => {
+                for line in indices.chunks_exact(2) {
+                    draw_line(framebuffer, &state, [shade(line[0]), shade(line[1])]);
+                }
+            }
+            PrimitiveTopology::LineStrip => {
+                for line in indices.windows(2) {
+                    draw_line(framebuffer, &state, [shade(line[0]), shade(line[1])]);
+                }
+            }
+            PrimitiveTopology::PointList => {
+                for index in &indices {
+                    draw_point(framebuffer, &state, shade(*index));
+                }
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Clipping
+// ============================================================================
+
+/// Signed distances to the clip planes that bound depth: near, far and `w > 0`.
+fn plane_distances(p: &[f32; 4]) -> [f32; 3] {
+    [p[2], p[3] - p[2], p[3] - W_EPSILON]
+}
+
+fn lerp_vertex(a: &ClipVertex, b: &ClipVertex, t: f32) -> ClipVertex {
+    let mut position = [0.0; 4];
+    for (i, value) in position.iter_mut().enumerate() {
+        *value = a.position[i] + (b.position[i] - a.position[i]) * t;
+    }
+    ClipVertex {
+        position,
+        varyings: Varyings::lerp(&a.varyings, &b.varyings, t),
+    }
+}
+
+/// Clip a polygon against the depth planes (Sutherland–Hodgman).
+fn clip_polygon(mut polygon: Vec<ClipVertex>) -> Vec<ClipVertex> {
+    for plane in 0..3 {
+        if polygon.is_empty() {
+            break;
+        }
+        let mut output = Vec::with_capacity(polygon.len() + 2);
+        for i in 0..polygon.len() {
+            let current = &polygon[i];
+            let next = &polygon[(i + 1) % polygon.len()];
+            let dc = plane_distances(&current.position)[plane];
+            let dn = plane_distances(&next.position)[plane];
+            if dc >= 0.0 {
+                output.push(*current);
+            }
+            if (dc >= 0.0) != (dn >= 0.0) {
+                output.push(lerp_vertex(current, next, dc / (dc - dn)));
+            }
+        }
+        polygon = output;
+    }
+    polygon
+}
+
+fn to_screen(vertex: &ClipVertex, viewport: &[f32; 6]) -> ScreenVertex {
+    let [x, y, z, w] = vertex.position;
+    let inv_w = 1.0 / w;
+    let [vx, vy, vw, vh, min_depth, max_depth] = *viewport;
+    ScreenVertex {
+        x: vx + (x * inv_w + 1.0) * 0.5 * vw,
+        y: vy + (1.0 - y * inv_w) * 0.5 * vh,
+        z: min_depth + z * inv_w * (max_depth - min_depth),
+        inv_w,
+        varyings: vertex.varyings,
+    }
+}
+
+// ============================================================================
+// Rasterization
+// ============================================================================
+
+fn edge(a: &ScreenVertex, b: &ScreenVertex, px: f32, py: f32) -> f32 {
+    (px - a.x) * (b.y - a.y) - (py - a.y) * (b.x - a.x)
+}
+
+/// Top-left fill rule for an edge of a triangle with positive area.
+fn is_top_left(a: &ScreenVertex, b: &ScreenVertex) -> bool {
+    let dx = b.x - a.x;
+    let dy = b.y - a.y;
+    dy > 0.0 || (dy == 0.0 && dx < 0.0)
+}
+
+fn covers(weight: f32, top_left: bool) -> bool {
+    weight > 0.0 || (weight == 0.0 && top_left)
+}
+
+fn draw_triangle(
+    framebuffer: &mut Framebuffer<'_>,
+    state: &DrawState<'_>,
+    vertices: [ClipVertex; 3],
+) {
+    if state.wireframe {
+        for i in 0..3 {
+            draw_line(framebuffer, state, [vertices[i], vertices[(i + 1) % 3]]);
+        }
+        return;
+    }
+    let polygon = clip_polygon(vertices.to_vec());
+    if polygon.len() < 3 {
+        return;
+    }
+    let screen: Vec<ScreenVertex> = polygon
+        .iter()
+        .map(|vertex| to_screen(vertex, &state.viewport))
+        .collect();
+    for i in 1..screen.len() - 1 {
+        rasterize_triangle(framebuffer, state, [screen[0], screen[i], screen[i + 1]]);
+    }
+}
+
+fn rasterize_triangle(
+    framebuffer: &mut Framebuffer<'_>,
+    state: &DrawState<'_>,
+    [v0, mut v1, mut v2]: [ScreenVertex; 3],
+) {
+    let mut area = edge(&v0, &v1, v2.x, v2.y);
+    if area == 0.0 || !area.is_finite() {
+        return;
+    }
+    // Counter-clockwise in clip space (+Y up) has negative area in screen space.
+    let front_facing = area < 0.0;
+    if area < 0.0 {
+        std::mem::swap(&mut v1, &mut v2);
+        area = -area;
+    }
+
+    let clip = state.clip;
+    let min_x = v0.x.min(v1.x).min(v2.x).floor().max(clip.x0 as f32) as u32;
+    let min_y = v0.y.min(v1.y).min(v2.y).floor().max(clip.y0 as f32) as u32;
+    let max_x = (v0.x.max(v1.x).max(v2.x).ceil().max(0.0) as u32).min(clip.x1);
+    let max_y = (v0.y.max(v1.y).max(v2.y).ceil().max(0.0) as u32).min(clip.y1);
+
+    let top_left = [
+        is_top_left(&v1, &v2),
+        is_top_left(&v2, &v0),
+        is_top_left(&v0, &v1),
+    ];
+
+    for y in min_y..max_y {
+        let py = y as f32 + 0.5;
+        for x in min_x..max_x {
+            let px = x as f32 + 0.5;
+            let w0 = edge(&v1, &v2, px, py);
+            let w1 = edge(&v2, &v0, px, py);
+            let w2 = edge(&v0, &v1, px, py);
+            if !(covers(w0, top_left[0]) && covers(w1, top_left[1]) && covers(w2, top_left[2])) {
+                continue;
+            }
+            let (l0, l1, l2) = (w0 / area, w1 / area, w2 / area);
+            let z = l0 * v0.z + l1 * v1.z + l2 * v2.z;
+            let inv_w = l0 * v0.inv_w + l1 * v1.inv_w + l2 * v2.inv_w;
+            let weights = [
+                l0 * v0.inv_w / inv_w,
+                l1 * v1.inv_w / inv_w,
+                l2 * v2.inv_w / inv_w,
+            ];
+            let varyings = Varyings::combine([&v0.varyings, &v1.varyings, &v2.varyings], weights);
+            shade_fragment(framebuffer, state, x, y, z, inv_w, varyings, front_facing);
+        }
+    }
+}
+
+fn draw_line(framebuffer: &mut Framebuffer<'_>, state: &DrawState<'_>, [a, b]: [ClipVertex; 2]) {
+    // Parametric clipping against the depth planes.
+    let (da, db) = (plane_distances(&a.position), plane_distances(&b.position));
+    let (mut t0, mut t1) = (0.0f32, 1.0f32);
+    for plane in 0..3 {
+        let (pa, pb) = (da[plane], db[plane]);
+        if pa < 0.0 && pb < 0.0 {
+            return;
+        }
+        let t = pa / (pa - pb);
+        if pa < 0.0 {
+            t0 = t0.max(t);
+        } else if pb < 0.0 {
+            t1 = t1.min(t);
+        }
+    }
+    if t0 >= t1 {
+        return;
+    }
+    let start = to_screen(&lerp_vertex(&a, &b, t0), &state.viewport);
+    let end = to_screen(&lerp_vertex(&a, &b, t1), &state.viewport);
+
+    // Step one pixel along the major axis; the end pixel is left to the next segment.
+    let (dx, dy) = (end.x - start.x, end.y - start.y);
+    let steps = dx.abs().max(dy.abs()).ceil() as u32;
+    for i in 0..steps {
+        let t = i as f32 / steps as f32;
+        let (px, py) = (start.x + dx * t, start.y + dy * t);
+        if px < 0.0 || py < 0.0 {
+            continue;
+        }
+        let (x, y) = (px as u32, py as u32);
+        if !state.clip.contains(x, y) {
+            continue;
+        }
+        let inv_w = start.inv_w + (end.inv_w - start.inv_w) * t;
+        let weights = [(1.0 - t) * start.inv_w / inv_w, t * end.inv_w / inv_w, 0.0];
+        let varyings =
+            Varyings::combine([&start.varyings, &end.varyings, &start.varyings], weights);
+        let z = start.z + (end.z - start.z) * t;
+        shade_fragment(framebuffer, state, x, y, z, inv_w, varyings, true);
+    }
+}
+
+fn draw_point(framebuffer: &mut Framebuffer<'_>, state: &DrawState<'_>, vertex: ClipVertex) {
+    if plane_distances(&vertex.position).iter().any(|d| *d < 0.0) {
+        return;
+    }
+    let v = to_screen(&vertex, &state.viewport);
+    if v.x < 0.0 || v.y < 0.0 {
+        return;
+    }
+    let (x, y) = (v.x.floor() as u32, v.y.floor() as u32);
+    if state.clip.contains(x, y) {
+        shade_fragment(framebuffer, state, x, y, v.z, v.inv_w, v.varyings, true);
+    }
+}
+
+// ============================================================================
+// Fragment Processing
+// ============================================================================
+
+#[allow(clippy::too_many_arguments)]
+fn shade_fragment(
+    framebuffer: &mut Framebuffer<'_>,
+    state: &DrawState<'_>,
+    x: u32,
+    y: u32,
+    z: f32,
+    inv_w: f32,
+    varyings: Varyings,
+    front_facing: bool,
+) {
+    if x >= framebuffer.width || y >= framebuffer.height {
+        return;
+    }
+    let (depth_lo, depth_hi) = (
+        state.viewport[4].min(state.viewport[5]),
+        state.viewport[4].max(state.viewport[5]),
+    );
+    let z = z.clamp(depth_lo, depth_hi);
+
+    if let Some(depth) = framebuffer.depth.as_mut() {
+        let format = depth.format;
+        let stored = decode_depth(format, depth.texel_mut(x, y));
+        if z > stored {
+            return;
+        }
+    }
+
+    let input = FragmentInput {
+        position: [x as f32 + 0.5, y as f32 + 0.5, z, inv_w],
+        varyings,
+        front_facing,
+    };
+    let mut output = FragmentOutput::new();
+    state.shader.fragment(&input, &state.resources, &mut output);
+    if output.discarded {
+        return;
+    }
+
+    for (attachment, target) in framebuffer
+        .colors
+        .iter_mut()
+        .enumerate()
+        .take(MAX_COLOR_ATTACHMENTS)
+    {
+        let Some(target) = target else {
+            continue;
+        };
+        let format = target.format;
+        let texel = target.texel_mut(x, y);
+        let src = output.colors[attachment];
+        let color = match &state.blend {
+            Some(blend) => {
+                let dst = decode_texel(format, texel).unwrap_or([0.0; 4]);
+                if is_float_format(format) {
+                    blend_color(blend, src, dst)
+                } else {
+                    blend_color(blend, saturate(src), saturate(dst))
+                }
+            }
+            None => src,
+        };
+        encode_texel(format, color, texel);
+    }
+
+    if state.depth_write
+        && let Some(depth) = framebuffer.depth.as_mut()
+    {
+        let format = depth.format;
+        encode_depth(format, z, depth.texel_mut(x, y));
+    }
+}
+
+fn is_float_format(format: TextureFormat) -> bool {
+    matches!(
+        format,
+        TextureFormat::R16Float
+            | TextureFormat::Rg16Float
+            | TextureFormat::Rgba16Float
+            | TextureFormat::R32Float
+            | TextureFormat::Rg32Float
+            | TextureFormat::Rgba32Float
+    )
+}
+
+fn saturate(color: [f32; 4]) -> [f32; 4] {
+    color.map(|c| c.clamp(0.0, 1.0))
+}
+
+/// Blend a source fragment onto a destination color.
+///
+/// The blend constant is always zero, matching the GPU backends which never set it.
+fn blend_color(state: &BlendState, src: [f32; 4], dst: [f32; 4]) -> [f32; 4] {
+    let mut out = [0.0; 4];
+    for (channel, value) in out.iter_mut().enumerate() {
+        let component = if channel < 3 {
+            &state.color
+        } else {
+            &state.alpha
+        };
+        *value = blend_channel(component, src, dst, channel);
+    }
+    out
+}
+
+fn blend_channel(component: &BlendComponent, src: [f32; 4], dst: [f32; 4], channel: usize) -> f32 {
+    let factor = |factor: BlendFactor| -> f32 {
+        match factor {
+            BlendFactor::Zero => 0.0,
+            BlendFactor::One => 1.0,
+            BlendFactor::Src => src[channel],
+            BlendFactor::OneMinusSrc => 1.0 - src[channel],
+            BlendFactor::SrcAlpha => src[3],
+            BlendFactor::OneMinusSrcAlpha => 1.0 - src[3],
+            BlendFactor::Dst => dst[channel],
+            BlendFactor::OneMinusDst => 1.0 - dst[channel],
+            BlendFactor::DstAlpha => dst[3],
+            BlendFactor::OneMinusDstAlpha => 1.0 - dst[3],
+            BlendFactor::SrcAlphaSaturated => {
+                if channel < 3 {
+                    src[3].min(1.0 - dst[3])
+                } else {
+                    1.0
+                }
+            }
+            BlendFactor::Constant => 0.0,
+            BlendFactor::OneMinusConstant => 1.0,
+        }
+    };
+    let s = src[channel] * factor(component.src_factor);
+    let d = dst[channel] * factor(component.dst_factor);
+    match component.operation {
+        BlendOperation::Add => s + d,
+        BlendOperation::Subtract => s - d,
+        BlendOperation::ReverseSubtract => d - s,
+        BlendOperation::Min => src[channel].min(dst[channel]),
+        BlendOperation::Max => src[channel].max(dst[channel]),
+    }
+}
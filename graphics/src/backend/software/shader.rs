@@ -0,0 +1,400 @@
+//! CPU shader interface for the software backend.
+//!
+//! The software backend cannot execute WGSL/GLSL/Slang, so materials that
+//! should render on it attach a [`SoftwareShader`] via
+//! [`MaterialDescriptor::with_software_shader`](crate::materials::MaterialDescriptor::with_software_shader).
+//! Materials without one fall back to [`PassthroughShader`].
+//!
+//! # Example
+//!
+//! ```ignore
+//! #[derive(Debug)]
+//! struct SolidRed;
+//!
+//! impl SoftwareShader for SolidRed {
+//!     fn vertex(&self, input: &VertexInput, _: &ShaderResources, _: &mut Varyings) -> [f32; 4] {
+//!         let p = input.attribute(VertexAttributeSemantic::Position).unwrap_or_default();
+//!         [p[0], p[1], p[2], 1.0]
+//!     }
+//!
+//!     fn fragment(&self, _: &FragmentInput, _: &ShaderResources, output: &mut FragmentOutput) {
+//!         output.set_color(0, [1.0, 0.0, 0.0, 1.0]);
+//!     }
+//! }
+//! ```
+
+use std::sync::Arc;
+
+use crate::mesh::{VertexAttributeFormat, VertexAttributeSemantic, VertexLayout, VertexStepMode};
+
+use crate::backend::{GpuBuffer, GpuSampler, GpuTexture};
+use crate::materials::{BindingGroup, BoundResource};
+use crate::types::{AddressMode, FilterMode, SamplerDescriptor};
+
+/// Number of `vec4` slots available for passing data from vertex to fragment stage.
+pub const MAX_VARYING_SLOTS: usize = 4;
+
+/// Maximum number of color attachments a fragment shader can write.
+pub const MAX_COLOR_ATTACHMENTS: usize = 8;
+
+/// A CPU implementation of a vertex + fragment shader pair.
+///
+/// Both stages run on the calling thread during graph execution. Clip-space
+/// output follows wgpu conventions: +Y up, depth range `[0, w]`.
+pub trait SoftwareShader: std::fmt::Debug + Send + Sync {
+    /// Run the vertex stage.
+    ///
+    /// Writes interpolated data into `varyings` and returns the clip-space position.
+    fn vertex(
+        &self,
+        input: &VertexInput<'_>,
+        resources: &ShaderResources<'_>,
+        varyings: &mut Varyings,
+    ) -> [f32; 4];
+
+    /// Run the fragment stage.
+    ///
+    /// Leaving `output` untouched writes zeros; call [`FragmentOutput::discard`]
+    /// to reject the fragment.
+    fn fragment(
+        &self,
+        input: &FragmentInput,
+        resources: &ShaderResources<'_>,
+        output: &mut FragmentOutput,
+    );
+}
+
+/// Perspective-interpolated values passed from the vertex to the fragment stage.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Varyings {
+    slots: [[f32; 4]; MAX_VARYING_SLOTS],
+}
+
+impl Varyings {
+    /// Get the value of a varying slot.
+    pub fn get(&self, slot: usize) -> [f32; 4] {
+        self.slots[slot]
+    }
+
+    /// Set the value of a varying slot.
+    pub fn set(&mut self, slot: usize, value: [f32; 4]) {
+        self.slots[slot] = value;
+    }
+
+    /// Weighted sum of three varyings (barycentric interpolation).
+    pub(super) fn combine(v: [&Varyings; 3], w: [f32; 3]) -> Self {
+        let mut out = Self::default();
+        for slot in 0..MAX_VARYING_SLOTS {
+            for c in 0..4 {
+                out.slots[slot][c] = v[0].slots[slot][c] * w[0]
+                    + v[1].slots[slot][c] * w[1]
+                    + v[2].slots[slot][c] * w[2];
+            }
+        }
+        out
+    }
+
+    /// Linear interpolation between two varyings.
+    pub(super) fn lerp(a: &Varyings, b: &Varyings, t: f32) -> Self {
+        Self::combine([a, b, a], [1.0 - t, t, 0.0])
+    }
+}
+
+/// Input to the vertex stage: one vertex of the current draw.
+pub struct VertexInput<'a> {
+    pub(super) layout: &'a VertexLayout,
+    pub(super) buffers: &'a [&'a [u8]],
+    pub(super) vertex_index: u32,
+    pub(super) instance_index: u32,
+}
+
+impl VertexInput<'_> {
+    /// Index of the vertex being processed.
+    pub fn vertex_index(&self) -> u32 {
+        self.vertex_index
+    }
+
+    /// Index of the instance being processed.
+    pub fn instance_index(&self) -> u32 {
+        self.instance_index
+    }
+
+    /// Fetch a vertex attribute by semantic, widened to four components.
+    ///
+    /// Missing components default to `0.0`, except `w` which defaults to `1.0`.
+    /// Returns `None` if the mesh layout has no such attribute.
+    pub fn attribute(&self, semantic: VertexAttributeSemantic) -> Option<[f32; 4]> {
+        let attribute = self.layout.get_attribute(semantic)?;
+        let buffer_layout = self.layout.buffer(attribute.buffer_index as usize)?;
+        let data = self.buffers.get(attribute.buffer_index as usize)?;
+        let element = match buffer_layout.step_mode {
+            VertexStepMode::Vertex => self.vertex_index,
+            VertexStepMode::Instance => self.instance_index,
+        };
+        let start = element as usize * buffer_layout.stride as usize + attribute.offset as usize;
+        let bytes = data.get(start..start + attribute.format.size())?;
+        Some(read_attribute(attribute.format, bytes))
+    }
+}
+
+/// Input to the fragment stage.
+#[derive(Debug, Clone, Copy)]
+pub struct FragmentInput {
+    /// Framebuffer position: pixel center `x`, `y`, depth `z` and `1/w`.
+    pub position: [f32; 4],
+    /// Interpolated varyings.
+    pub varyings: Varyings,
+    /// Whether the primitive faces the viewer (counter-clockwise winding).
+    pub front_facing: bool,
+}
+
+/// Output of the fragment stage.
+#[derive(Debug, Clone, Copy)]
+pub struct FragmentOutput {
+    pub(super) colors: [[f32; 4]; MAX_COLOR_ATTACHMENTS],
+    pub(super) discarded: bool,
+}
+
+impl FragmentOutput {
+    pub(super) fn new() -> Self {
+        Self {
+            colors: [[0.0; 4]; MAX_COLOR_ATTACHMENTS],
+            discarded: false,
+        }
+    }
+
+    /// Set the color written to a color attachment.
+    pub fn set_color(&mut self, attachment: usize, color: [f32; 4]) {
+        self.colors[attachment] = color;
+    }
+
+    /// Discard the fragment: nothing is written to color or depth.
+    pub fn discard(&mut self) {
+        self.discarded = true;
+    }
+}
+
+/// Read access to the binding groups of the material instance being drawn.
+///
+/// Groups and bindings use the same indices as the GPU shaders.
+/// Resources that are currently bound as render targets of the pass are not
+/// accessible and read as missing.
+pub struct ShaderResources<'a> {
+    pub(super) groups: &'a [Arc<BindingGroup>],
+}
+
+impl ShaderResources<'_> {
+    fn resource(&self, group: usize, binding: u32) -> Option<&BoundResource> {
+        self.groups
+            .get(group)?
+            .entries
+            .iter()
+            .find(|entry| entry.binding == binding)
+            .map(|entry| &entry.resource)
+    }
+
+    /// Read a plain-old-data value from a bound buffer at a byte offset.
+    pub fn read_buffer<T: bytemuck::Pod>(
+        &self,
+        group: usize,
+        binding: u32,
+        offset: usize,
+    ) -> Option<T> {
+        let BoundResource::Buffer(buffer) = self.resource(group, binding)? else {
+            return None;
+        };
+        let GpuBuffer::Software(buffer) = buffer.gpu_handle() else {
+            return None;
+        };
+        let data = buffer.data.try_read()?;
+        let bytes = data.get(offset..offset + std::mem::size_of::<T>())?;
+        Some(bytemuck::pod_read_unaligned(bytes))
+    }
+
+    /// Fetch a single texel of a bound texture (like WGSL `textureLoad`).
+    pub fn load(&self, group: usize, binding: u32, x: u32, y: u32, mip_level: u32) -> [f32; 4] {
+        let texture = match self.resource(group, binding) {
+            Some(BoundResource::Texture(texture))
+            | Some(BoundResource::CombinedTextureSampler { texture, .. }) => texture,
+            _ => return [0.0; 4],
+        };
+        let GpuTexture::Software(texture) = texture.gpu_handle() else {
+            return [0.0; 4];
+        };
+        texture.load(mip_level, 0, x, y).unwrap_or([0.0; 4])
+    }
+
+    /// Sample a bound texture at normalized coordinates (like WGSL `textureSample`).
+    ///
+    /// If `texture_binding` holds a combined texture-sampler, its own sampler is
+    /// used and `sampler_binding` is ignored. Sampling always reads mip level 0,
+    /// since the rasterizer does not compute derivatives.
+    pub fn sample(
+        &self,
+        group: usize,
+        texture_binding: u32,
+        sampler_binding: u32,
+        uv: [f32; 2],
+    ) -> [f32; 4] {
+        let (texture, sampler) = match self.resource(group, texture_binding) {
+            Some(BoundResource::CombinedTextureSampler { texture, sampler }) => (texture, sampler),
+            Some(BoundResource::Texture(texture)) => match self.resource(group, sampler_binding) {
+                Some(BoundResource::Sampler(sampler)) => (texture, sampler),
+                _ => return [0.0; 4],
+            },
+            _ => return [0.0; 4],
+        };
+        let (GpuTexture::Software(texture), GpuSampler::Software(sampler)) =
+            (texture.gpu_handle(), sampler.gpu_handle())
+        else {
+            return [0.0; 4];
+        };
+        sample_texture(texture, sampler, uv)
+    }
+}
+
+/// Default shader used when a material has no software shader attached.
+///
+/// Treats the `Position` attribute as clip-space coordinates and outputs the
+/// `Color` attribute (or opaque white) to every color attachment.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PassthroughShader;
+
+impl SoftwareShader for PassthroughShader {
+    fn vertex(
+        &self,
+        input: &VertexInput<'_>,
+        _resources: &ShaderResources<'_>,
+        varyings: &mut Varyings,
+    ) -> [f32; 4] {
+        let position = input
+            .attribute(VertexAttributeSemantic::Position)
+            .unwrap_or([0.0, 0.0, 0.0, 1.0]);
+        let color = input
+            .attribute(VertexAttributeSemantic::Color)
+            .unwrap_or([1.0; 4]);
+        varyings.set(0, color);
+        position
+    }
+
+    fn fragment(
+        &self,
+        input: &FragmentInput,
+        _resources: &ShaderResources<'_>,
+        output: &mut FragmentOutput,
+    ) {
+        for attachment in 0..MAX_COLOR_ATTACHMENTS {
+            output.set_color(attachment, input.varyings.get(0));
+        }
+    }
+}
+
+/// Decode a vertex attribute into four components.
+fn read_attribute(format: VertexAttributeFormat, bytes: &[u8]) -> [f32; 4] {
+    use VertexAttributeFormat as F;
+
+    let word = |i: usize| {
+        u32::from_le_bytes([
+            bytes[i * 4],
+            bytes[i * 4 + 1],
+            bytes[i * 4 + 2],
+            bytes[i * 4 + 3],
+        ])
+    };
+    let (count, component): (usize, &dyn Fn(usize) -> f32) = match format {
+        F::Float => (1, &|i| f32::from_bits(word(i))),
+        F::Float2 => (2, &|i| f32::from_bits(word(i))),
+        F::Float3 => (3, &|i| f32::from_bits(word(i))),
+        F::Float4 => (4, &|i| f32::from_bits(word(i))),
+        F::Int => (1, &|i| word(i) as i32 as f32),
+        F::Int2 => (2, &|i| word(i) as i32 as f32),
+        F::Int3 => (3, &|i| word(i) as i32 as f32),
+        F::Int4 => (4, &|i| word(i) as i32 as f32),
+        F::Uint => (1, &|i| word(i) as f32),
+        F::Uint2 => (2, &|i| word(i) as f32),
+        F::Uint3 => (3, &|i| word(i) as f32),
+        F::Uint4 => (4, &|i| word(i) as f32),
+        F::Unorm8x4 => (4, &|i| bytes[i] as f32 / 255.0),
+        F::Snorm8x4 => (4, &|i| (bytes[i] as i8 as f32 / 127.0).max(-1.0)),
+    };
+
+    let mut values = [0.0, 0.0, 0.0, 1.0];
+    for (i, value) in values.iter_mut().enumerate().take(count) {
+        *value = component(i);
+    }
+    values
+}
+
+/// Sample mip level 0 of a texture with the given sampler state.
+fn sample_texture(
+    texture: &super::SoftwareTexture,
+    sampler: &SamplerDescriptor,
+    uv: [f32; 2],
+) -> [f32; 4] {
+    let (width, height) = texture.mip_extent(0);
+    // Magnification and minification are indistinguishable without derivatives.
+    let filter = sampler.mag_filter;
+
+    let fetch = |x: i64, y: i64| -> [f32; 4] {
+        let (Some(x), Some(y)) = (
+            wrap_coord(x, width, sampler.address_mode_u),
+            wrap_coord(y, height, sampler.address_mode_v),
+        ) else {
+            return [0.0; 4];
+        };
+        texture.load(0, 0, x, y).unwrap_or([0.0; 4])
+    };
+
+    let u = uv[0] * width as f32;
+    let v = uv[1] * height as f32;
+    match filter {
+        FilterMode::Nearest => fetch(u.floor() as i64, v.floor() as i64),
+        FilterMode::Linear => {
+            let u = u - 0.5;
+            let v = v - 0.5;
+            let x0 = u.floor();
+            let y0 = v.floor();
+            let fx = u - x0;
+            let fy = v - y0;
+            let (x0, y0) = (x0 as i64, y0 as i64);
+            let c00 = fetch(x0, y0);
+            let c10 = fetch(x0 + 1, y0);
+            let c01 = fetch(x0, y0 + 1);
+            let c11 = fetch(x0 + 1, y0 + 1);
+            let mut out = [0.0; 4];
+            for c in 0..4 {
+                let top = c00[c] + (c10[c] - c00[c]) * fx;
+                let bottom = c01[c] + (c11[c] - c01[c]) * fx;
+                out[c] = top + (bottom - top) * fy;
+            }
+            out
+        }
+    }
+}
+
+/// Apply an address mode to an integer texel coordinate.
+///
+/// Returns `None` for out-of-range coordinates with `ClampToBorder`
+/// (the border color is transparent black).
+fn wrap_coord(coord: i64, size: u32, mode: AddressMode) -> Option<u32> {
+    let size = size.max(1) as i64;
+    let wrapped = match mode {
+        AddressMode::ClampToEdge => coord.clamp(0, size - 1),
+        AddressMode::Repeat => coord.rem_euclid(size),
+        AddressMode::MirrorRepeat => {
+            let period = coord.rem_euclid(size * 2);
+            if period < size {
+                period
+            } else {
+                size * 2 - 1 - period
+            }
+        }
+        AddressMode::ClampToBorder => {
+            if coord < 0 || coord >= size {
+                return None;
+            }
+            coord
+        }
+    };
+    Some(wrapped as u32)
+}
@@ -0,0 +1,363 @@
+//! Transfer pass execution for the software backend.
+//!
+//! Buffer↔texture copies follow the GPU backends' layout rules: when
+//! `bytes_per_row` is not specified and the copy spans several rows, rows are
+//! padded to 256 bytes; `rows_per_image` defaults to the copy height.
+//! Texture coordinates and extents are given in texels and copied in whole
+//! blocks for compressed formats. Blits filter in two dimensions; depth
+//! slices and array layers are picked with nearest filtering.
+
+use redlilium_core::profile_scope;
+
+use crate::error::GraphicsError;
+use crate::graph::{TextureBlitRegion, TextureCopyLocation, TransferOperation, TransferPass};
+use crate::types::{Extent3d, FilterMode};
+
+use super::super::{GpuBuffer, GpuTexture};
+use super::{SoftwareBuffer, SoftwareTexture, encode_texel};
+
+/// Row alignment used when `bytes_per_row` is not given.
+const COPY_BYTES_PER_ROW_ALIGNMENT: u32 = 256;
+
+/// Execute all operations of a transfer pass in order.
+pub(super) fn execute_transfer_pass(pass: &TransferPass) -> Result<(), GraphicsError> {
+    profile_scope!("software_transfer_pass");
+
+    let Some(config) = pass.transfer_config() else {
+        return Ok(());
+    };
+    for operation in &config.operations {
+        execute_operation(operation)?;
+    }
+    Ok(())
+}
+
+fn software_buffer(buffer: &GpuBuffer) -> Result<&SoftwareBuffer, GraphicsError> {
+    match buffer {
+        GpuBuffer::Software(buffer) => Ok(buffer),
+        _ => Err(GraphicsError::Internal(
+            "Software transfer with non-Software buffer".to_string(),
+        )),
+    }
+}
+
+fn software_texture(texture: &GpuTexture) -> Result<&SoftwareTexture, GraphicsError> {
+    match texture {
+        GpuTexture::Software(texture) => Ok(texture),
+        _ => Err(GraphicsError::Internal(
+            "Software transfer with non-Software texture".to_string(),
+        )),
+    }
+}
+
+fn execute_operation(operation: &TransferOperation) -> Result<(), GraphicsError> {
+    match operation {
+        TransferOperation::BufferToBuffer { src, dst, regions } => {
+            let src = software_buffer(src.gpu_handle())?;
+            let dst = software_buffer(dst.gpu_handle())?;
+            for region in regions {
+                let (src_start, dst_start, size) = (
+                    region.src_offset as usize,
+                    region.dst_offset as usize,
+                    region.size as usize,
+                );
+                // Stage through a copy so that src and dst may be the same buffer.
+                let staged = src
+                    .data
+                    .read()
+                    .get(src_start..src_start + size)
+                    .map(<[u8]>::to_vec)
+                    .ok_or_else(|| out_of_bounds("buffer copy source"))?;
+                dst.data
+                    .write()
+                    .get_mut(dst_start..dst_start + size)
+                    .ok_or_else(|| out_of_bounds("buffer copy destination"))?
+                    .copy_from_slice(&staged);
+            }
+        }
+        TransferOperation::TextureToTexture { src, dst, regions } => {
+            let src = software_texture(src.gpu_handle())?;
+            let dst = software_texture(dst.gpu_handle())?;
+            for region in regions {
+                let rows = read_texture_rows(src, &region.src, region.extent)?;
+                write_texture_rows(dst, &region.dst, region.extent, &rows)?;
+            }
+        }
+        TransferOperation::BufferToTexture { src, dst, regions } => {
+            let buffer = software_buffer(src.gpu_handle())?;
+            let texture = software_texture(dst.gpu_handle())?;
+            for region in regions {
+                let layout = BufferLayout::new(texture, &region.buffer_layout, region.extent);
+                let rows = {
+                    let data = buffer.data.read();
+                    let mut rows = Vec::with_capacity(layout.total_rows());
+                    for (offset, len) in layout.row_ranges() {
+                        rows.push(
+                            data.get(offset..offset + len)
+                                .ok_or_else(|| out_of_bounds("buffer to texture source"))?
+                                .to_vec(),
+                        );
+                    }
+                    rows
+                };
+                write_texture_rows(texture, &region.texture_location, region.extent, &rows)?;
+            }
+        }
+        TransferOperation::TextureToBuffer { src, dst, regions } => {
+            let texture = software_texture(src.gpu_handle())?;
+            let buffer = software_buffer(dst.gpu_handle())?;
+            for region in regions {
+                let layout = BufferLayout::new(texture, &region.buffer_layout, region.extent);
+                let rows = read_texture_rows(texture, &region.texture_location, region.extent)?;
+                let mut data = buffer.data.write();
+                for ((offset, len), row) in layout.row_ranges().zip(&rows) {
+                    data.get_mut(offset..offset + len)
+                        .ok_or_else(|| out_of_bounds("texture to buffer destination"))?
+                        .copy_from_slice(row);
+                }
+            }
+        }
+        TransferOperation::Blit {
+            src,
+            dst,
+            regions,
+            filter,
+        } => {
+            let src = software_texture(src.gpu_handle())?;
+            let dst = software_texture(dst.gpu_handle())?;
+            for region in regions {
+                blit_region(src, dst, region, *filter)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Scale one region of `src` into `dst`.
+pub(super) fn blit_region(
+    src: &SoftwareTexture,
+    dst: &SoftwareTexture,
+    region: &TextureBlitRegion,
+    filter: FilterMode,
+) -> Result<(), GraphicsError> {
+    if src.format().is_compressed() || dst.format().is_compressed() {
+        return Err(GraphicsError::InvalidParameter(
+            "blits between compressed textures are not supported".to_string(),
+        ));
+    }
+    let (src_extent, dst_extent) = (region.src_extent, region.dst_extent);
+    if [src_extent, dst_extent]
+        .iter()
+        .any(|e| e.width == 0 || e.height == 0 || e.depth == 0)
+    {
+        return Ok(());
+    }
+
+    let sample = |layer: u32, x: u32, y: u32| {
+        src.load(
+            region.src.mip_level,
+            layer,
+            region.src.origin.x + x.min(src_extent.width - 1),
+            region.src.origin.y + y.min(src_extent.height - 1),
+        )
+        .ok_or_else(|| out_of_bounds("blit source"))
+    };
+
+    // Resolve all destination texels before writing, as the source may be
+    // another mip of the same texture.
+    let mut texels =
+        Vec::with_capacity((dst_extent.width * dst_extent.height * dst_extent.depth) as usize);
+    let scale_x = src_extent.width as f32 / dst_extent.width as f32;
+    let scale_y = src_extent.height as f32 / dst_extent.height as f32;
+    for z in 0..dst_extent.depth {
+        let layer = region.src.origin.z + z * src_extent.depth / dst_extent.depth;
+        for y in 0..dst_extent.height {
+            for x in 0..dst_extent.width {
+                let u = (x as f32 + 0.5) * scale_x;
+                let v = (y as f32 + 0.5) * scale_y;
+                let color = match filter {
+                    FilterMode::Nearest => sample(layer, u as u32, v as u32)?,
+                    FilterMode::Linear => {
+                        let (u, v) = ((u - 0.5).max(0.0), (v - 0.5).max(0.0));
+                        let (x0, y0) = (u as u32, v as u32);
+                        let (fx, fy) = (u.fract(), v.fract());
+                        let texels = [
+                            sample(layer, x0, y0)?,
+                            sample(layer, x0 + 1, y0)?,
+                            sample(layer, x0, y0 + 1)?,
+                            sample(layer, x0 + 1, y0 + 1)?,
+                        ];
+                        std::array::from_fn(|c| {
+                            let top = texels[0][c] + (texels[1][c] - texels[0][c]) * fx;
+                            let bottom = texels[2][c] + (texels[3][c] - texels[2][c]) * fx;
+                            top + (bottom - top) * fy
+                        })
+                    }
+                };
+                texels.push(color);
+            }
+        }
+    }
+
+    let texel_size = dst.format().block_size() as usize;
+    let pitch = dst.row_pitch(region.dst.mip_level);
+    let mut subresources = dst.subresources.write();
+    let mut colors = texels.into_iter();
+    for z in 0..dst_extent.depth {
+        let index = dst
+            .subresource_index(region.dst.mip_level, region.dst.origin.z + z)
+            .ok_or_else(|| out_of_bounds("blit destination"))?;
+        let data = &mut subresources[index];
+        for y in region.dst.origin.y..region.dst.origin.y + dst_extent.height {
+            for x in region.dst.origin.x..region.dst.origin.x + dst_extent.width {
+                let start = y as usize * pitch + x as usize * texel_size;
+                let out = data
+                    .get_mut(start..start + texel_size)
+                    .ok_or_else(|| out_of_bounds("blit destination"))?;
+                encode_texel(dst.format(), colors.next().unwrap_or_default(), out);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn out_of_bounds(what: &str) -> GraphicsError {
+    GraphicsError::InvalidParameter(format!("{what} is out of bounds"))
+}
+
+/// Block-row geometry of a copy region within a texture.
+struct BlockRegion {
+    /// Byte offset of the first block in each row.
+    x_offset: usize,
+    /// Bytes copied per row.
+    row_bytes: usize,
+    /// First block row.
+    first_row: usize,
+    /// Number of block rows per layer.
+    row_count: usize,
+}
+
+impl BlockRegion {
+    fn new(texture: &SoftwareTexture, origin_x: u32, origin_y: u32, extent: Extent3d) -> Self {
+        let format = texture.format();
+        let (block_width, block_height) = format.block_dimensions();
+        let block_size = format.block_size() as usize;
+        Self {
+            x_offset: (origin_x / block_width) as usize * block_size,
+            row_bytes: extent.width.div_ceil(block_width) as usize * block_size,
+            first_row: (origin_y / block_height) as usize,
+            row_count: extent.height.div_ceil(block_height) as usize,
+        }
+    }
+}
+
+/// Read block rows of a texture region, layer by layer.
+fn read_texture_rows(
+    texture: &SoftwareTexture,
+    location: &TextureCopyLocation,
+    extent: Extent3d,
+) -> Result<Vec<Vec<u8>>, GraphicsError> {
+    let region = BlockRegion::new(texture, location.origin.x, location.origin.y, extent);
+    let pitch = texture.row_pitch(location.mip_level);
+    let subresources = texture.subresources.read();
+    let mut rows = Vec::with_capacity(region.row_count * extent.depth as usize);
+    for layer in location.origin.z..location.origin.z + extent.depth {
+        let index = texture
+            .subresource_index(location.mip_level, layer)
+            .ok_or_else(|| out_of_bounds("texture copy source"))?;
+        let data = &subresources[index];
+        for row in region.first_row..region.first_row + region.row_count {
+            let start = row * pitch + region.x_offset;
+            rows.push(
+                data.get(start..start + region.row_bytes)
+                    .ok_or_else(|| out_of_bounds("texture copy source"))?
+                    .to_vec(),
+            );
+        }
+    }
+    Ok(rows)
+}
+
+/// Write block rows produced by [`read_texture_rows`] or a buffer layout.
+fn write_texture_rows(
+    texture: &SoftwareTexture,
+    location: &TextureCopyLocation,
+    extent: Extent3d,
+    rows: &[Vec<u8>],
+) -> Result<(), GraphicsError> {
+    let region = BlockRegion::new(texture, location.origin.x, location.origin.y, extent);
+    let pitch = texture.row_pitch(location.mip_level);
+    let mut subresources = texture.subresources.write();
+    let mut source = rows.iter();
+    for layer in location.origin.z..location.origin.z + extent.depth {
+        let index = texture
+            .subresource_index(location.mip_level, layer)
+            .ok_or_else(|| out_of_bounds("texture copy destination"))?;
+        let data = &mut subresources[index];
+        for row in region.first_row..region.first_row + region.row_count {
+            let Some(src) = source.next() else {
+                return Ok(());
+            };
+            let start = row * pitch + region.x_offset;
+            let len = region.row_bytes.min(src.len());
+            data.get_mut(start..start + len)
+                .ok_or_else(|| out_of_bounds("texture copy destination"))?
+                .copy_from_slice(&src[..len]);
+        }
+    }
+    Ok(())
+}
+
+/// Resolved layout of texture data in a buffer.
+struct BufferLayout {
+    offset: usize,
+    bytes_per_row: usize,
+    rows_per_image: usize,
+    row_bytes: usize,
+    row_count: usize,
+    depth: usize,
+}
+
+impl BufferLayout {
+    fn new(
+        texture: &SoftwareTexture,
+        layout: &crate::graph::BufferTextureLayout,
+        extent: Extent3d,
+    ) -> Self {
+        let region = BlockRegion::new(texture, 0, 0, extent);
+        let bytes_per_row = layout.bytes_per_row.unwrap_or(if extent.height > 1 {
+            (region.row_bytes as u32).next_multiple_of(COPY_BYTES_PER_ROW_ALIGNMENT)
+        } else {
+            region.row_bytes as u32
+        });
+        let (_, block_height) = texture.format().block_dimensions();
+        let rows_per_image = layout
+            .rows_per_image
+            .map(|rows| rows.div_ceil(block_height) as usize)
+            .unwrap_or(region.row_count);
+        Self {
+            offset: layout.offset as usize,
+            bytes_per_row: bytes_per_row as usize,
+            rows_per_image,
+            row_bytes: region.row_bytes,
+            row_count: region.row_count,
+            depth: extent.depth as usize,
+        }
+    }
+
+    fn total_rows(&self) -> usize {
+        self.row_count * self.depth
+    }
+
+    /// `(offset, length)` of every block row, layer by layer.
+    fn row_ranges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.depth).flat_map(move |layer| {
+            (0..self.row_count).map(move |row| {
+                let offset = self.offset
+                    + layer * self.rows_per_image * self.bytes_per_row
+                    + row * self.bytes_per_row;
+                (offset, self.row_bytes)
+            })
+        })
+    }
+}
@@ -26,7 +26,9 @@ use crate::graph::{CompiledGraph, Pass, RenderGraph, RenderTarget};
 use crate::types::{BufferDescriptor, SamplerDescriptor, TextureDescriptor};
 use redlilium_core::profiling::profile_scope;
 
-use super::{GpuBuffer, GpuFence, GpuSampler, GpuSemaphore, GpuTexture};
+use super::{
+    GpuBuffer, GpuFence, GpuSampler, GpuSemaphore, GpuTexture, GpuTimestampPool, GpuTimestampWrites,
+};
 
 /// Maximum number of frames in flight for per-slot resource tracking.
 pub const MAX_FRAMES_IN_FLIGHT: usize = 3;
@@ -857,6 +859,7 @@ impl VulkanBackend {
             pipeline_layout,
             &descriptor.color_formats,
             descriptor.depth_format,
+            descriptor.depth_write,
             descriptor.blend_state.as_ref(),
             descriptor.polygon_mode,
             &self.dynamic_rendering,
@@ -1027,6 +1030,110 @@ impl VulkanBackend {
         // This is a no-op for the Vulkan backend
     }
 
+    /// Get the cache of compiled shaders.
+    pub fn shader_cache(&self) -> &crate::shader::ShaderCache {
+        self.pipeline_manager.shader_cache()
+    }
+
+    /// Get the modules Slang sources can import.
+    pub fn shader_library(&self) -> &std::sync::RwLock<crate::shader::ShaderLibrary> {
+        self.pipeline_manager.shader_library()
+    }
+
+    /// Pipeline cache UUID of the physical device, identifying the driver
+    /// build that persisted pipeline cache data is valid for.
+    pub fn device_uuid(&self) -> [u8; 16] {
+        let properties = unsafe {
+            self.instance
+                .get_physical_device_properties(self.physical_device)
+        };
+        properties.pipeline_cache_uuid
+    }
+
+    /// Get the driver pipeline cache contents for persisting.
+    pub fn pipeline_cache_data(&self) -> Vec<u8> {
+        self.pipeline_manager.pipeline_cache_data()
+    }
+
+    /// Merge persisted pipeline cache contents into the driver pipeline cache.
+    pub fn merge_pipeline_cache_data(&self, data: &[u8]) -> Result<(), GraphicsError> {
+        self.pipeline_manager.merge_pipeline_cache_data(data)
+    }
+
+    /// Create a timestamp query pool with `capacity` queries.
+    ///
+    /// Returns `None` if the device cannot write timestamps on the graphics
+    /// queue.
+    pub fn create_timestamp_pool(&self, capacity: u32) -> Option<GpuTimestampPool> {
+        let properties = unsafe {
+            self.instance
+                .get_physical_device_properties(self.physical_device)
+        };
+        let queue_families = unsafe {
+            self.instance
+                .get_physical_device_queue_family_properties(self.physical_device)
+        };
+        let valid_bits = queue_families
+            .get(self.graphics_queue_family as usize)
+            .map_or(0, |family| family.timestamp_valid_bits);
+        if capacity == 0
+            || properties.limits.timestamp_compute_and_graphics == vk::FALSE
+            || valid_bits == 0
+        {
+            return None;
+        }
+
+        let create_info = vk::QueryPoolCreateInfo::default()
+            .query_type(vk::QueryType::TIMESTAMP)
+            .query_count(capacity);
+        let pool = unsafe { self.device.create_query_pool(&create_info, None) }
+            .inspect_err(|e| log::warn!("Failed to create timestamp query pool: {:?}", e))
+            .ok()?;
+        Some(GpuTimestampPool::Vulkan {
+            device: self.device.clone(),
+            pool,
+            capacity,
+        })
+    }
+
+    /// Nanoseconds per timestamp tick.
+    pub fn timestamp_period(&self) -> f32 {
+        let properties = unsafe {
+            self.instance
+                .get_physical_device_properties(self.physical_device)
+        };
+        properties.limits.timestamp_period
+    }
+
+    /// Read the first `count` timestamps of `pool` without waiting.
+    ///
+    /// Queries whose results are not available (never written) read as `0`.
+    pub fn read_timestamps(&self, pool: &GpuTimestampPool, count: u32) -> Vec<u64> {
+        let GpuTimestampPool::Vulkan { pool, .. } = pool else {
+            return vec![0; count as usize];
+        };
+        if count == 0 {
+            return Vec::new();
+        }
+
+        // Each result is followed by its availability (non-zero if written)
+        let mut results = vec![[0u64; 2]; count as usize];
+        // NOT_READY is expected when some queries were not written; the
+        // availability flags tell which results are valid.
+        let _ = unsafe {
+            self.device.get_query_pool_results(
+                *pool,
+                0,
+                &mut results,
+                vk::QueryResultFlags::TYPE_64 | vk::QueryResultFlags::WITH_AVAILABILITY,
+            )
+        };
+        results
+            .into_iter()
+            .map(|[value, available]| if available != 0 { value } else { 0 })
+            .collect()
+    }
+
     /// Execute a compiled render graph.
     ///
     /// # Async Behavior
@@ -1037,6 +1144,10 @@ impl VulkanBackend {
     /// - If `signal_fence` is `None`: Blocks until GPU completes (sync, for backwards compatibility).
     ///
     /// For true async rendering with multiple frames in flight, always provide a fence.
+    ///
+    /// With `timestamps`, the reserved queries are reset at the start of the
+    /// command buffer and graphics and compute passes are wrapped in
+    /// timestamp writes.
     pub fn execute_graph(
         &self,
         graph: &RenderGraph,
@@ -1044,6 +1155,7 @@ impl VulkanBackend {
         wait_semaphores: &[&GpuSemaphore],
         signal_semaphores: &[&GpuSemaphore],
         signal_fence: Option<&GpuFence>,
+        timestamps: Option<GpuTimestampWrites<'_>>,
     ) -> Result<(), GraphicsError> {
         profile_scope!("vulkan_execute_graph");
 
@@ -1071,6 +1183,23 @@ impl VulkanBackend {
         // Get all passes from the graph
         let passes = graph.passes();
 
+        // Reset the reserved timestamp queries before any pass writes them
+        let query_pool = timestamps.and_then(|writes| match writes.pool {
+            GpuTimestampPool::Vulkan { pool, .. } => {
+                unsafe {
+                    self.device.cmd_reset_query_pool(
+                        cmd,
+                        *pool,
+                        writes.first_query,
+                        writes.query_count,
+                    );
+                }
+                Some(*pool)
+            }
+            _ => None,
+        });
+        let mut next_query = timestamps.map_or(0, |writes| writes.first_query);
+
         // Process each pass in compiled order using pre-computed resource usages
         {
             profile_scope!("record_passes");
@@ -1082,8 +1211,30 @@ impl VulkanBackend {
                 let barriers = self.generate_barriers_for_pass(&pass_usages[i]);
                 barriers.submit(&self.device, cmd);
 
-                // Encode the pass
+                // Encode the pass, between timestamps if it is timed
+                let timed_pool = query_pool.filter(|_| pass.is_timed());
+                if let Some(pool) = timed_pool {
+                    unsafe {
+                        self.device.cmd_write_timestamp(
+                            cmd,
+                            vk::PipelineStageFlags::TOP_OF_PIPE,
+                            pool,
+                            next_query,
+                        );
+                    }
+                }
                 self.encode_pass(cmd, pass)?;
+                if let Some(pool) = timed_pool {
+                    unsafe {
+                        self.device.cmd_write_timestamp(
+                            cmd,
+                            vk::PipelineStageFlags::BOTTOM_OF_PIPE,
+                            pool,
+                            next_query + 1,
+                        );
+                    }
+                    next_query += 2;
+                }
             }
         }
 
@@ -1340,6 +1491,45 @@ impl VulkanBackend {
             return Ok(());
         }
 
+        // Data holds the mip levels largest first, each with all layers.
+        // Copy every level it fully covers.
+        let is_3d = descriptor.dimension == crate::types::TextureDimension::D3;
+        let mut copy_regions = Vec::new();
+        let mut buffer_offset = 0;
+        for level in 0..descriptor.mip_level_count {
+            let level_size = descriptor.mip_level_size(level);
+            if buffer_offset + level_size > data.len() {
+                break;
+            }
+            let extent = descriptor.mip_level_extent(level);
+            copy_regions.push(
+                vk::BufferImageCopy::default()
+                    .buffer_offset(buffer_offset as u64)
+                    .buffer_row_length(0) // 0 means tightly packed
+                    .buffer_image_height(0)
+                    .image_subresource(vk::ImageSubresourceLayers {
+                        aspect_mask: vk::ImageAspectFlags::COLOR,
+                        mip_level: level,
+                        base_array_layer: 0,
+                        layer_count: if is_3d { 1 } else { extent.depth },
+                    })
+                    .image_offset(vk::Offset3D { x: 0, y: 0, z: 0 })
+                    .image_extent(vk::Extent3D {
+                        width: extent.width,
+                        height: extent.height,
+                        depth: if is_3d { extent.depth } else { 1 },
+                    }),
+            );
+            buffer_offset += level_size;
+        }
+        if copy_regions.is_empty() {
+            return Err(GraphicsError::InvalidParameter(format!(
+                "texture data is {} bytes, mip level 0 needs {}",
+                data.len(),
+                descriptor.mip_level_size(0)
+            )));
+        }
+
         // Create staging buffer
         let staging_buffer_info = vk::BufferCreateInfo::default()
             .size(data.len() as u64)
@@ -1478,9 +1668,9 @@ impl VulkanBackend {
             .subresource_range(vk::ImageSubresourceRange {
                 aspect_mask: vk::ImageAspectFlags::COLOR,
                 base_mip_level: 0,
-                level_count: 1,
+                level_count: vk::REMAINING_MIP_LEVELS,
                 base_array_layer: 0,
-                layer_count: 1,
+                layer_count: vk::REMAINING_ARRAY_LAYERS,
             })
             .src_access_mask(vk::AccessFlags::empty())
             .dst_access_mask(vk::AccessFlags::TRANSFER_WRITE);
@@ -1498,30 +1688,13 @@ impl VulkanBackend {
         }
 
         // Copy buffer to image
-        let region = vk::BufferImageCopy::default()
-            .buffer_offset(0)
-            .buffer_row_length(0) // 0 means tightly packed
-            .buffer_image_height(0)
-            .image_subresource(vk::ImageSubresourceLayers {
-                aspect_mask: vk::ImageAspectFlags::COLOR,
-                mip_level: 0,
-                base_array_layer: 0,
-                layer_count: 1,
-            })
-            .image_offset(vk::Offset3D { x: 0, y: 0, z: 0 })
-            .image_extent(vk::Extent3D {
-                width: descriptor.size.width,
-                height: descriptor.size.height,
-                depth: 1,
-            });
-
         unsafe {
             self.device.cmd_copy_buffer_to_image(
                 cmd,
                 staging_buffer,
                 *image,
                 vk::ImageLayout::TRANSFER_DST_OPTIMAL,
-                &[region],
+                &copy_regions,
             );
         }
 
@@ -1535,9 +1708,9 @@ impl VulkanBackend {
             .subresource_range(vk::ImageSubresourceRange {
                 aspect_mask: vk::ImageAspectFlags::COLOR,
                 base_mip_level: 0,
-                level_count: 1,
+                level_count: vk::REMAINING_MIP_LEVELS,
                 base_array_layer: 0,
-                layer_count: 1,
+                layer_count: vk::REMAINING_ARRAY_LAYERS,
             })
             .src_access_mask(vk::AccessFlags::TRANSFER_WRITE)
             .dst_access_mask(vk::AccessFlags::SHADER_READ);
@@ -1627,6 +1800,110 @@ impl VulkanBackend {
         Ok(())
     }
 
+    /// Fill mip levels `1..` of a texture by downsampling each level from
+    /// the previous one with `vkCmdBlitImage`, waiting for the GPU to finish.
+    ///
+    /// The texture is expected in `SHADER_READ_ONLY_OPTIMAL` layout, as left
+    /// by [`write_texture`](Self::write_texture), and is returned to it.
+    pub fn generate_mipmaps(
+        &self,
+        texture: &GpuTexture,
+        descriptor: &TextureDescriptor,
+    ) -> Result<(), GraphicsError> {
+        let GpuTexture::Vulkan { image, .. } = texture else {
+            return Err(GraphicsError::Internal(
+                "generate_mipmaps called with non-Vulkan texture".to_string(),
+            ));
+        };
+        let regions = crate::graph::TextureBlitRegion::mip_chain(descriptor);
+        if regions.is_empty() {
+            return Ok(());
+        }
+
+        let alloc_info = vk::CommandBufferAllocateInfo::default()
+            .command_pool(self.command_pool)
+            .level(vk::CommandBufferLevel::PRIMARY)
+            .command_buffer_count(1);
+        let cmd_buffers =
+            unsafe { self.device.allocate_command_buffers(&alloc_info) }.map_err(|e| {
+                GraphicsError::Internal(format!("Failed to allocate command buffer: {:?}", e))
+            })?;
+        let cmd = cmd_buffers[0];
+        let free_cmd = || unsafe {
+            self.device
+                .free_command_buffers(self.command_pool, &cmd_buffers);
+        };
+
+        let begin_info = vk::CommandBufferBeginInfo::default()
+            .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+        if let Err(e) = unsafe { self.device.begin_command_buffer(cmd, &begin_info) } {
+            free_cmd();
+            return Err(GraphicsError::Internal(format!(
+                "Failed to begin command buffer: {:?}",
+                e
+            )));
+        }
+
+        let is_3d = descriptor.dimension == crate::types::TextureDimension::D3;
+        self.transition_image_layout(
+            cmd,
+            *image,
+            vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
+            vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+            vk::ImageAspectFlags::COLOR,
+        );
+        self.record_blit(
+            cmd,
+            *image,
+            *image,
+            (is_3d, is_3d),
+            &regions,
+            crate::types::FilterMode::Linear,
+        );
+        self.transition_image_layout(
+            cmd,
+            *image,
+            vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+            vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
+            vk::ImageAspectFlags::COLOR,
+        );
+
+        if let Err(e) = unsafe { self.device.end_command_buffer(cmd) } {
+            free_cmd();
+            return Err(GraphicsError::Internal(format!(
+                "Failed to end command buffer: {:?}",
+                e
+            )));
+        }
+
+        let fence = match unsafe {
+            self.device
+                .create_fence(&vk::FenceCreateInfo::default(), None)
+        } {
+            Ok(fence) => fence,
+            Err(e) => {
+                free_cmd();
+                return Err(GraphicsError::Internal(format!(
+                    "Failed to create fence: {:?}",
+                    e
+                )));
+            }
+        };
+        let submit_info = vk::SubmitInfo::default().command_buffers(&cmd_buffers);
+        let result = unsafe {
+            self.device
+                .queue_submit(self.graphics_queue, &[submit_info], fence)
+                .and_then(|()| self.device.wait_for_fences(&[fence], true, u64::MAX))
+        };
+        unsafe {
+            self.device.destroy_fence(fence, None);
+        }
+        free_cmd();
+        result.map_err(|e| {
+            GraphicsError::Internal(format!("Failed to submit mipmap generation: {:?}", e))
+        })
+    }
+
     fn encode_pass(&self, cmd: vk::CommandBuffer, pass: &Pass) -> Result<(), GraphicsError> {
         profile_scope!("encode_pass");
         match pass {
@@ -2045,12 +2322,17 @@ impl VulkanBackend {
                         let info = &scratch_buffer_infos[buffer_idx..buffer_idx + 1];
                         buffer_idx += 1;
                         // Use the binding type from layout, defaulting to UNIFORM_BUFFER
-                        let descriptor_type =
-                            if binding_type == Some(crate::materials::BindingType::StorageBuffer) {
-                                vk::DescriptorType::STORAGE_BUFFER
-                            } else {
-                                vk::DescriptorType::UNIFORM_BUFFER
-                            };
+                        let descriptor_type = if matches!(
+                            binding_type,
+                            Some(
+                                crate::materials::BindingType::StorageBuffer
+                                    | crate::materials::BindingType::ReadWriteStorageBuffer
+                            )
+                        ) {
+                            vk::DescriptorType::STORAGE_BUFFER
+                        } else {
+                            vk::DescriptorType::UNIFORM_BUFFER
+                        };
                         vk::WriteDescriptorSet::default()
                             .dst_set(descriptor_set)
                             .dst_binding(entry.binding)
@@ -2504,10 +2786,149 @@ impl VulkanBackend {
                     );
                 }
             }
+            TransferOperation::Blit {
+                src,
+                dst,
+                regions,
+                filter,
+            } => {
+                let GpuTexture::Vulkan {
+                    image: src_image, ..
+                } = src.gpu_handle()
+                else {
+                    return Ok(());
+                };
+                let GpuTexture::Vulkan {
+                    image: dst_image, ..
+                } = dst.gpu_handle()
+                else {
+                    return Ok(());
+                };
+
+                let is_3d = |texture: &crate::resources::Texture| {
+                    texture.descriptor().dimension == crate::types::TextureDimension::D3
+                };
+                self.record_blit(
+                    cmd,
+                    *src_image,
+                    *dst_image,
+                    (is_3d(src), is_3d(dst)),
+                    regions,
+                    *filter,
+                );
+            }
         }
         Ok(())
     }
 
+    /// Record blits of `regions` with `src` in `TRANSFER_SRC_OPTIMAL` and
+    /// `dst` in `TRANSFER_DST_OPTIMAL` layout.
+    ///
+    /// When `src` and `dst` are the same image, the whole image is expected
+    /// in `TRANSFER_DST_OPTIMAL`: each source mip level is moved to
+    /// `TRANSFER_SRC_OPTIMAL` right before its blit, so it sees the writes
+    /// of earlier regions, and moved back once all regions are recorded.
+    fn record_blit(
+        &self,
+        cmd: vk::CommandBuffer,
+        src: vk::Image,
+        dst: vk::Image,
+        (src_is_3d, dst_is_3d): (bool, bool),
+        regions: &[crate::graph::TextureBlitRegion],
+        filter: crate::types::FilterMode,
+    ) {
+        let same_image = src == dst;
+        let filter = match filter {
+            crate::types::FilterMode::Nearest => vk::Filter::NEAREST,
+            crate::types::FilterMode::Linear => vk::Filter::LINEAR,
+        };
+        let mip_barrier = |mip_level: u32, old_layout, new_layout, src_access, dst_access| {
+            vk::ImageMemoryBarrier::default()
+                .old_layout(old_layout)
+                .new_layout(new_layout)
+                .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+                .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+                .image(src)
+                .subresource_range(vk::ImageSubresourceRange {
+                    aspect_mask: vk::ImageAspectFlags::COLOR,
+                    base_mip_level: mip_level,
+                    level_count: 1,
+                    base_array_layer: 0,
+                    layer_count: vk::REMAINING_ARRAY_LAYERS,
+                })
+                .src_access_mask(src_access)
+                .dst_access_mask(dst_access)
+        };
+
+        let mut source_mips = Vec::new();
+        for region in regions {
+            if same_image && !source_mips.contains(&region.src.mip_level) {
+                source_mips.push(region.src.mip_level);
+                let barrier = mip_barrier(
+                    region.src.mip_level,
+                    vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                    vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                    vk::AccessFlags::TRANSFER_WRITE,
+                    vk::AccessFlags::TRANSFER_READ,
+                );
+                unsafe {
+                    self.device.cmd_pipeline_barrier(
+                        cmd,
+                        vk::PipelineStageFlags::TRANSFER,
+                        vk::PipelineStageFlags::TRANSFER,
+                        vk::DependencyFlags::empty(),
+                        &[],
+                        &[],
+                        &[barrier],
+                    );
+                }
+            }
+
+            let blit = vk::ImageBlit::default()
+                .src_subresource(blit_subresource(&region.src, region.src_extent, src_is_3d))
+                .src_offsets(blit_offsets(&region.src, region.src_extent, src_is_3d))
+                .dst_subresource(blit_subresource(&region.dst, region.dst_extent, dst_is_3d))
+                .dst_offsets(blit_offsets(&region.dst, region.dst_extent, dst_is_3d));
+            unsafe {
+                self.device.cmd_blit_image(
+                    cmd,
+                    src,
+                    vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                    dst,
+                    vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                    &[blit],
+                    filter,
+                );
+            }
+        }
+
+        if !source_mips.is_empty() {
+            let barriers: Vec<_> = source_mips
+                .into_iter()
+                .map(|mip_level| {
+                    mip_barrier(
+                        mip_level,
+                        vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                        vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                        vk::AccessFlags::TRANSFER_READ,
+                        vk::AccessFlags::TRANSFER_WRITE,
+                    )
+                })
+                .collect();
+            unsafe {
+                self.device.cmd_pipeline_barrier(
+                    cmd,
+                    vk::PipelineStageFlags::TRANSFER,
+                    vk::PipelineStageFlags::TRANSFER,
+                    vk::DependencyFlags::empty(),
+                    &[],
+                    &[],
+                    &barriers,
+                );
+            }
+        }
+    }
+
     fn encode_compute_pass(
         &self,
         cmd: vk::CommandBuffer,
@@ -2643,9 +3064,13 @@ impl VulkanBackend {
                         BoundResource::Buffer(_) => {
                             let info = &scratch_buffer_infos[buffer_idx..buffer_idx + 1];
                             buffer_idx += 1;
-                            let descriptor_type = if binding_type
-                                == Some(crate::materials::BindingType::StorageBuffer)
-                            {
+                            let descriptor_type = if matches!(
+                                binding_type,
+                                Some(
+                                    crate::materials::BindingType::StorageBuffer
+                                        | crate::materials::BindingType::ReadWriteStorageBuffer
+                                )
+                            ) {
                                 vk::DescriptorType::STORAGE_BUFFER
                             } else {
                                 vk::DescriptorType::UNIFORM_BUFFER
@@ -2730,7 +3155,6 @@ impl VulkanBackend {
         Ok(())
     }
 
-    #[allow(dead_code)]
     fn transition_image_layout(
         &self,
         cmd: vk::CommandBuffer,
@@ -2834,3 +3258,43 @@ impl VulkanBackend {
         }
     }
 }
+
+/// Subresource of a blit region: `origin.z` and `depth` select array layers
+/// unless the texture is 3D.
+fn blit_subresource(
+    location: &crate::graph::TextureCopyLocation,
+    extent: crate::types::Extent3d,
+    is_3d: bool,
+) -> vk::ImageSubresourceLayers {
+    vk::ImageSubresourceLayers {
+        aspect_mask: vk::ImageAspectFlags::COLOR,
+        mip_level: location.mip_level,
+        base_array_layer: if is_3d { 0 } else { location.origin.z },
+        layer_count: if is_3d { 1 } else { extent.depth.max(1) },
+    }
+}
+
+/// Corner offsets of a blit region.
+fn blit_offsets(
+    location: &crate::graph::TextureCopyLocation,
+    extent: crate::types::Extent3d,
+    is_3d: bool,
+) -> [vk::Offset3D; 2] {
+    let (z, depth) = if is_3d {
+        (location.origin.z, extent.depth.max(1))
+    } else {
+        (0, 1)
+    };
+    [
+        vk::Offset3D {
+            x: location.origin.x as i32,
+            y: location.origin.y as i32,
+            z: z as i32,
+        },
+        vk::Offset3D {
+            x: (location.origin.x + extent.width) as i32,
+            y: (location.origin.y + extent.height) as i32,
+            z: (z + depth) as i32,
+        },
+    ]
+}
@@ -3,10 +3,12 @@
 use std::ffi::CString;
 
 use ash::vk;
+use parking_lot::RwLock;
 
 use crate::error::GraphicsError;
 use crate::materials::{BindingLayout, BindingType, ShaderSourceLanguage, ShaderStage};
 use crate::mesh::VertexAttributeFormat;
+use crate::shader::{ShaderCache, ShaderCacheKey, ShaderLibrary, ShaderTarget};
 use crate::types::TextureFormat;
 use redlilium_core::mesh::{PrimitiveTopology, VertexLayout};
 
@@ -19,6 +21,13 @@ pub struct PipelineManager {
     /// Each slot's pool is only reset after its fence signals,
     /// preventing resets while another slot's descriptors are in use.
     descriptor_pools: [vk::DescriptorPool; super::MAX_FRAMES_IN_FLIGHT],
+    /// Driver pipeline cache used for every pipeline. Locked for writing
+    /// only while merging imported data, which needs external sync.
+    pipeline_cache: RwLock<vk::PipelineCache>,
+    /// Compiled SPIR-V keyed by shader inputs.
+    shader_cache: ShaderCache,
+    /// Modules Slang sources can import.
+    shader_library: std::sync::RwLock<ShaderLibrary>,
     /// Whether resources have been explicitly destroyed.
     destroyed: bool,
 }
@@ -69,13 +78,78 @@ impl PipelineManager {
             })?;
         }
 
+        let pipeline_cache =
+            unsafe { device.create_pipeline_cache(&vk::PipelineCacheCreateInfo::default(), None) }
+                .map_err(|e| {
+                    GraphicsError::ResourceCreationFailed(format!(
+                        "Failed to create pipeline cache: {:?}",
+                        e
+                    ))
+                })?;
+
         Ok(Self {
             device,
             descriptor_pools,
+            pipeline_cache: RwLock::new(pipeline_cache),
+            shader_cache: ShaderCache::new(),
+            shader_library: std::sync::RwLock::new(ShaderLibrary::standard_slang()),
             destroyed: false,
         })
     }
 
+    /// Get the cache of compiled shaders.
+    pub fn shader_cache(&self) -> &ShaderCache {
+        &self.shader_cache
+    }
+
+    /// Get the modules Slang sources can import.
+    pub fn shader_library(&self) -> &std::sync::RwLock<ShaderLibrary> {
+        &self.shader_library
+    }
+
+    /// Get the driver pipeline cache contents for persisting.
+    pub fn pipeline_cache_data(&self) -> Vec<u8> {
+        let cache = self.pipeline_cache.read();
+        unsafe { self.device.get_pipeline_cache_data(*cache) }.unwrap_or_else(|e| {
+            log::warn!("Failed to read pipeline cache data: {:?}", e);
+            Vec::new()
+        })
+    }
+
+    /// Merge previously persisted pipeline cache contents into the driver
+    /// pipeline cache.
+    ///
+    /// The driver validates the data header itself and ignores data from an
+    /// incompatible device or driver version.
+    pub fn merge_pipeline_cache_data(&self, data: &[u8]) -> Result<(), GraphicsError> {
+        if data.is_empty() {
+            return Ok(());
+        }
+
+        let imported = unsafe {
+            self.device.create_pipeline_cache(
+                &vk::PipelineCacheCreateInfo::default().initial_data(data),
+                None,
+            )
+        }
+        .map_err(|e| {
+            GraphicsError::ResourceCreationFailed(format!(
+                "Failed to create pipeline cache: {:?}",
+                e
+            ))
+        })?;
+
+        let cache = self.pipeline_cache.write();
+        let result = unsafe { self.device.merge_pipeline_caches(*cache, &[imported]) };
+        unsafe { self.device.destroy_pipeline_cache(imported, None) };
+        result.map_err(|e| {
+            GraphicsError::ResourceCreationFailed(format!(
+                "Failed to merge pipeline cache: {:?}",
+                e
+            ))
+        })
+    }
+
     /// Compile shader source to SPIR-V and create a Vulkan shader module.
     ///
     /// Returns `(shader_module, actual_entry_point)` where `actual_entry_point` is the
@@ -84,6 +158,8 @@ impl PipelineManager {
     ///
     /// For WGSL sources: uses naga (WGSL → naga IR → SPIR-V); preserves the original name.
     /// For Slang sources: uses the Slang compiler (Slang → SPIR-V); reads actual name from SPIR-V.
+    ///
+    /// Compilation is skipped if the shader cache already holds the SPIR-V.
     pub fn compile_shader(
         &self,
         source: &[u8],
@@ -92,23 +168,46 @@ impl PipelineManager {
         language: ShaderSourceLanguage,
         defines: &[(String, String)],
     ) -> Result<(vk::ShaderModule, String), GraphicsError> {
-        let (spv, actual_entry) = match language {
-            ShaderSourceLanguage::Wgsl => {
-                let spv = self.compile_wgsl_to_spirv(source, stage, entry_point)?;
-                (spv, entry_point.to_string())
-            }
-            #[cfg(feature = "slang-shaders")]
-            ShaderSourceLanguage::Slang => {
-                let spv = self.compile_slang_to_spirv(source, entry_point, defines)?;
-                let actual =
-                    spirv_entry_point_name(&spv).unwrap_or_else(|| entry_point.to_string());
-                (spv, actual)
-            }
-            #[cfg(not(feature = "slang-shaders"))]
+        let library = match language {
+            ShaderSourceLanguage::Wgsl => ShaderLibrary::empty(),
+            ShaderSourceLanguage::Slang => self.shader_library.read().unwrap().clone(),
+        };
+        let key = ShaderCacheKey::new(
+            source,
+            entry_point,
+            stage,
+            language,
+            ShaderTarget::Spirv,
+            defines,
+            &library,
+        );
+        let code = self.shader_cache.get_or_compile(key, || {
+            let spv = match language {
+                ShaderSourceLanguage::Wgsl => {
+                    self.compile_wgsl_to_spirv(source, stage, entry_point)?
+                }
+                #[cfg(feature = "slang-shaders")]
+                ShaderSourceLanguage::Slang => {
+                    self.compile_slang_to_spirv(source, entry_point, defines, &library)?
+                }
+                #[cfg(not(feature = "slang-shaders"))]
+                ShaderSourceLanguage::Slang => {
+                    return Err(GraphicsError::FeatureNotSupported(
+                        "Slang shaders require the 'slang-shaders' feature".into(),
+                    ));
+                }
+            };
+            Ok(spv.iter().flat_map(|word| word.to_le_bytes()).collect())
+        })?;
+
+        let spv: Vec<u32> = code
+            .chunks_exact(4)
+            .map(|chunk| u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+            .collect();
+        let actual_entry = match language {
+            ShaderSourceLanguage::Wgsl => entry_point.to_string(),
             ShaderSourceLanguage::Slang => {
-                return Err(GraphicsError::FeatureNotSupported(
-                    "Slang shaders require the 'slang-shaders' feature".into(),
-                ));
+                spirv_entry_point_name(&spv).unwrap_or_else(|| entry_point.to_string())
             }
         };
 
@@ -196,13 +295,14 @@ impl PipelineManager {
         source: &[u8],
         entry_point: &str,
         defines: &[(String, String)],
+        library: &ShaderLibrary,
     ) -> Result<Vec<u32>, GraphicsError> {
         let source_str = std::str::from_utf8(source)
             .map_err(|e| GraphicsError::ShaderCompilationFailed(format!("Invalid UTF-8: {e}")))?;
 
         let compiler = crate::shader::SlangCompiler::new()?;
-        // Write standard library modules so `import math;` etc. resolve
-        compiler.write_library_modules(&crate::shader::ShaderLibrary::standard_slang())?;
+        // Write library modules so `import math;` etc. resolve
+        compiler.write_library_modules(library)?;
         let defines: Vec<(&str, &str)> = defines
             .iter()
             .map(|(k, v)| (k.as_str(), v.as_str()))
@@ -234,9 +334,13 @@ impl PipelineManager {
             .map(|entry| {
                 let descriptor_type = match entry.binding_type {
                     BindingType::UniformBuffer => vk::DescriptorType::UNIFORM_BUFFER,
-                    BindingType::StorageBuffer => vk::DescriptorType::STORAGE_BUFFER,
+                    BindingType::StorageBuffer | BindingType::ReadWriteStorageBuffer => {
+                        vk::DescriptorType::STORAGE_BUFFER
+                    }
                     BindingType::Sampler => vk::DescriptorType::SAMPLER,
+                    BindingType::ComparisonSampler => vk::DescriptorType::SAMPLER,
                     BindingType::Texture => vk::DescriptorType::SAMPLED_IMAGE,
+                    BindingType::DepthTexture => vk::DescriptorType::SAMPLED_IMAGE,
                     BindingType::TextureCube => vk::DescriptorType::SAMPLED_IMAGE,
                     BindingType::Texture2DArray => vk::DescriptorType::SAMPLED_IMAGE,
                     BindingType::CombinedTextureSampler => {
@@ -320,6 +424,7 @@ impl PipelineManager {
         pipeline_layout: vk::PipelineLayout,
         color_formats: &[TextureFormat],
         depth_format: Option<TextureFormat>,
+        depth_write: bool,
         blend_state: Option<&crate::materials::BlendState>,
         polygon_mode: crate::materials::PolygonMode,
         _dynamic_rendering: &ash::khr::dynamic_rendering::Device,
@@ -421,7 +526,7 @@ impl PipelineManager {
 
         let depth_stencil_state = vk::PipelineDepthStencilStateCreateInfo::default()
             .depth_test_enable(depth_format.is_some())
-            .depth_write_enable(depth_format.is_some())
+            .depth_write_enable(depth_format.is_some() && depth_write)
             .depth_compare_op(vk::CompareOp::LESS_OR_EQUAL)
             .depth_bounds_test_enable(false)
             .stencil_test_enable(false);
@@ -476,8 +581,11 @@ impl PipelineManager {
             .push_next(&mut rendering_info);
 
         let pipelines = unsafe {
-            self.device
-                .create_graphics_pipelines(vk::PipelineCache::null(), &[pipeline_info], None)
+            self.device.create_graphics_pipelines(
+                *self.pipeline_cache.read(),
+                &[pipeline_info],
+                None,
+            )
         }
         .map_err(|(_, e)| {
             GraphicsError::ResourceCreationFailed(format!(
@@ -513,8 +621,11 @@ impl PipelineManager {
             .layout(pipeline_layout);
 
         let pipelines = unsafe {
-            self.device
-                .create_compute_pipelines(vk::PipelineCache::null(), &[pipeline_info], None)
+            self.device.create_compute_pipelines(
+                *self.pipeline_cache.read(),
+                &[pipeline_info],
+                None,
+            )
         }
         .map_err(|(_, e)| {
             GraphicsError::ResourceCreationFailed(format!(
@@ -567,7 +678,12 @@ impl PipelineManager {
         }
 
         // Pipelines are owned by Materials and destroyed when their last Arc is dropped.
-        // We only need to destroy the descriptor pools here.
+        // We only need to destroy the pipeline cache and descriptor pools here.
+        // SAFETY: Caller guarantees GPU is idle and device is valid
+        unsafe {
+            self.device
+                .destroy_pipeline_cache(*self.pipeline_cache.get_mut(), None);
+        }
 
         // Destroy all per-slot descriptor pools
         // SAFETY: Caller guarantees GPU is idle and device is valid
@@ -0,0 +1,312 @@
+//! Texture blits for the wgpu backend.
+//!
+//! wgpu has no blit command, so each region and array layer is drawn as a
+//! fullscreen triangle into a viewport covering the destination rectangle,
+//! sampling the source mip level with the requested filter.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use wgpu::util::DeviceExt;
+
+use crate::error::GraphicsError;
+use crate::graph::TextureBlitRegion;
+use crate::types::FilterMode;
+
+const BLIT_SHADER: &str = r#"
+struct BlitRect {
+    offset: vec2<f32>,
+    scale: vec2<f32>,
+}
+
+@group(0) @binding(0) var source: texture_2d<f32>;
+@group(0) @binding(1) var source_sampler: sampler;
+@group(0) @binding(2) var<uniform> rect: BlitRect;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    var out: VertexOutput;
+    out.position = vec4<f32>(uv * vec2<f32>(2.0, -2.0) + vec2<f32>(-1.0, 1.0), 0.0, 1.0);
+    out.uv = rect.offset + uv * rect.scale;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return textureSampleLevel(source, source_sampler, in.uv, 0.0);
+}
+"#;
+
+/// Render pipelines and samplers used to encode blits.
+///
+/// Pipelines are created on first use per destination format.
+pub(super) struct WgpuBlitter {
+    shader: wgpu::ShaderModule,
+    /// Bind group and pipeline layouts for filterable and unfilterable
+    /// sources.
+    filterable: (wgpu::BindGroupLayout, wgpu::PipelineLayout),
+    unfilterable: (wgpu::BindGroupLayout, wgpu::PipelineLayout),
+    linear_sampler: wgpu::Sampler,
+    nearest_sampler: wgpu::Sampler,
+    pipelines: Mutex<HashMap<(wgpu::TextureFormat, bool), wgpu::RenderPipeline>>,
+}
+
+impl WgpuBlitter {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Blit Shader"),
+            source: wgpu::ShaderSource::Wgsl(BLIT_SHADER.into()),
+        });
+
+        let layouts = |filterable: bool| {
+            let sampler_binding = if filterable {
+                wgpu::SamplerBindingType::Filtering
+            } else {
+                wgpu::SamplerBindingType::NonFiltering
+            };
+            let bind_group_layout =
+                device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                    label: Some("Blit Bind Group Layout"),
+                    entries: &[
+                        wgpu::BindGroupLayoutEntry {
+                            binding: 0,
+                            visibility: wgpu::ShaderStages::FRAGMENT,
+                            ty: wgpu::BindingType::Texture {
+                                sample_type: wgpu::TextureSampleType::Float { filterable },
+                                view_dimension: wgpu::TextureViewDimension::D2,
+                                multisampled: false,
+                            },
+                            count: None,
+                        },
+                        wgpu::BindGroupLayoutEntry {
+                            binding: 1,
+                            visibility: wgpu::ShaderStages::FRAGMENT,
+                            ty: wgpu::BindingType::Sampler(sampler_binding),
+                            count: None,
+                        },
+                        wgpu::BindGroupLayoutEntry {
+                            binding: 2,
+                            visibility: wgpu::ShaderStages::VERTEX,
+                            ty: wgpu::BindingType::Buffer {
+                                ty: wgpu::BufferBindingType::Uniform,
+                                has_dynamic_offset: false,
+                                min_binding_size: None,
+                            },
+                            count: None,
+                        },
+                    ],
+                });
+            let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Blit Pipeline Layout"),
+                bind_group_layouts: &[&bind_group_layout],
+                immediate_size: 0,
+            });
+            (bind_group_layout, pipeline_layout)
+        };
+
+        let sampler = |filter: wgpu::FilterMode| {
+            device.create_sampler(&wgpu::SamplerDescriptor {
+                label: Some("Blit Sampler"),
+                mag_filter: filter,
+                min_filter: filter,
+                ..Default::default()
+            })
+        };
+
+        Self {
+            shader,
+            filterable: layouts(true),
+            unfilterable: layouts(false),
+            linear_sampler: sampler(wgpu::FilterMode::Linear),
+            nearest_sampler: sampler(wgpu::FilterMode::Nearest),
+            pipelines: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Encode blits of `regions` from `src` into `dst`.
+    ///
+    /// Unfilterable sources fall back to nearest filtering.
+    pub fn encode(
+        &self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        src: &wgpu::Texture,
+        dst: &wgpu::Texture,
+        regions: &[TextureBlitRegion],
+        filter: FilterMode,
+    ) -> Result<(), GraphicsError> {
+        if src.dimension() != wgpu::TextureDimension::D2
+            || dst.dimension() != wgpu::TextureDimension::D2
+        {
+            return Err(GraphicsError::FeatureNotSupported(
+                "wgpu blits are limited to 2D, array and cube textures".to_string(),
+            ));
+        }
+        let filterable = match src.format().sample_type(None, Some(device.features())) {
+            Some(wgpu::TextureSampleType::Float { filterable }) => filterable,
+            _ => {
+                return Err(GraphicsError::FeatureNotSupported(format!(
+                    "wgpu blits can't sample from {:?} textures",
+                    src.format()
+                )));
+            }
+        };
+        if dst.format().is_compressed()
+            || !matches!(
+                dst.format().sample_type(None, None),
+                Some(wgpu::TextureSampleType::Float { .. })
+            )
+        {
+            return Err(GraphicsError::FeatureNotSupported(format!(
+                "wgpu blits can't render to {:?} textures",
+                dst.format()
+            )));
+        }
+
+        let pipeline = self.pipeline(device, dst.format(), filterable);
+        let (bind_group_layout, _) = if filterable {
+            &self.filterable
+        } else {
+            &self.unfilterable
+        };
+        let sampler = match filter {
+            FilterMode::Linear if filterable => &self.linear_sampler,
+            _ => &self.nearest_sampler,
+        };
+
+        for region in regions {
+            let src_size = src
+                .size()
+                .mip_level_size(region.src.mip_level, src.dimension());
+            let rect: [f32; 4] = [
+                region.src.origin.x as f32 / src_size.width as f32,
+                region.src.origin.y as f32 / src_size.height as f32,
+                region.src_extent.width as f32 / src_size.width as f32,
+                region.src_extent.height as f32 / src_size.height as f32,
+            ];
+            let uniform = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Blit Rect"),
+                contents: bytemuck::cast_slice(&rect),
+                usage: wgpu::BufferUsages::UNIFORM,
+            });
+
+            let layers = region.src_extent.depth.min(region.dst_extent.depth);
+            for layer in 0..layers {
+                let src_view =
+                    single_subresource_view(src, region.src.mip_level, region.src.origin.z + layer);
+                let dst_view =
+                    single_subresource_view(dst, region.dst.mip_level, region.dst.origin.z + layer);
+                let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: Some("Blit Bind Group"),
+                    layout: bind_group_layout,
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: wgpu::BindingResource::TextureView(&src_view),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: wgpu::BindingResource::Sampler(sampler),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 2,
+                            resource: uniform.as_entire_binding(),
+                        },
+                    ],
+                });
+
+                let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("Blit"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: &dst_view,
+                        depth_slice: None,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Load,
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                    multiview_mask: None,
+                });
+                pass.set_pipeline(&pipeline);
+                pass.set_bind_group(0, &bind_group, &[]);
+                pass.set_viewport(
+                    region.dst.origin.x as f32,
+                    region.dst.origin.y as f32,
+                    region.dst_extent.width as f32,
+                    region.dst_extent.height as f32,
+                    0.0,
+                    1.0,
+                );
+                pass.draw(0..3, 0..1);
+            }
+        }
+        Ok(())
+    }
+
+    fn pipeline(
+        &self,
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        filterable: bool,
+    ) -> wgpu::RenderPipeline {
+        let mut pipelines = self.pipelines.lock().unwrap();
+        pipelines
+            .entry((format, filterable))
+            .or_insert_with(|| {
+                let (_, layout) = if filterable {
+                    &self.filterable
+                } else {
+                    &self.unfilterable
+                };
+                device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                    label: Some("Blit Pipeline"),
+                    layout: Some(layout),
+                    vertex: wgpu::VertexState {
+                        module: &self.shader,
+                        entry_point: Some("vs_main"),
+                        buffers: &[],
+                        compilation_options: wgpu::PipelineCompilationOptions::default(),
+                    },
+                    fragment: Some(wgpu::FragmentState {
+                        module: &self.shader,
+                        entry_point: Some("fs_main"),
+                        targets: &[Some(wgpu::ColorTargetState {
+                            format,
+                            blend: None,
+                            write_mask: wgpu::ColorWrites::ALL,
+                        })],
+                        compilation_options: wgpu::PipelineCompilationOptions::default(),
+                    }),
+                    primitive: wgpu::PrimitiveState::default(),
+                    depth_stencil: None,
+                    multisample: wgpu::MultisampleState::default(),
+                    multiview_mask: None,
+                    cache: None,
+                })
+            })
+            .clone()
+    }
+}
+
+/// Create a 2D view of one mip level and array layer of `texture`.
+fn single_subresource_view(texture: &wgpu::Texture, mip: u32, layer: u32) -> wgpu::TextureView {
+    texture.create_view(&wgpu::TextureViewDescriptor {
+        label: Some("Blit View"),
+        dimension: Some(wgpu::TextureViewDimension::D2),
+        base_mip_level: mip,
+        mip_level_count: Some(1),
+        base_array_layer: layer,
+        array_layer_count: Some(1),
+        ..Default::default()
+    })
+}
@@ -498,6 +498,11 @@ pub fn convert_binding_type(binding_type: crate::materials::BindingType) -> wgpu
             has_dynamic_offset: false,
             min_binding_size: None,
         },
+        crate::materials::BindingType::ReadWriteStorageBuffer => wgpu::BindingType::Buffer {
+            ty: wgpu::BufferBindingType::Storage { read_only: false },
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
         crate::materials::BindingType::Texture => wgpu::BindingType::Texture {
             sample_type: wgpu::TextureSampleType::Float { filterable: true },
             view_dimension: wgpu::TextureViewDimension::D2,
@@ -513,9 +518,17 @@ pub fn convert_binding_type(binding_type: crate::materials::BindingType) -> wgpu
             view_dimension: wgpu::TextureViewDimension::D2Array,
             multisampled: false,
         },
+        crate::materials::BindingType::DepthTexture => wgpu::BindingType::Texture {
+            sample_type: wgpu::TextureSampleType::Depth,
+            view_dimension: wgpu::TextureViewDimension::D2,
+            multisampled: false,
+        },
         crate::materials::BindingType::Sampler => {
             wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering)
         }
+        crate::materials::BindingType::ComparisonSampler => {
+            wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Comparison)
+        }
         crate::materials::BindingType::CombinedTextureSampler => {
             // wgpu doesn't have combined texture/sampler, use texture binding
             wgpu::BindingType::Texture {
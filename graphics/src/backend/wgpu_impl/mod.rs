@@ -3,6 +3,7 @@
 //! This backend uses wgpu for cross-platform GPU access, supporting
 //! Vulkan, Metal, DX12, and WebGPU.
 
+mod blit;
 pub(crate) mod conversion;
 mod pass_encoding;
 mod resources;
@@ -46,9 +47,16 @@ impl SurfaceTextureView {
 
 use crate::error::GraphicsError;
 use crate::graph::{CompiledGraph, RenderGraph};
+use crate::shader::{ShaderCache, ShaderLibrary};
 use redlilium_core::profiling::profile_scope;
 
-use super::GpuFence;
+use super::{GpuFence, GpuTimestampPool, GpuTimestampWrites};
+
+/// Device features enabled when the adapter supports them.
+const OPTIONAL_FEATURES: wgpu::Features = wgpu::Features::TIMESTAMP_QUERY
+    .union(wgpu::Features::TEXTURE_COMPRESSION_BC)
+    .union(wgpu::Features::TEXTURE_COMPRESSION_ETC2)
+    .union(wgpu::Features::TEXTURE_COMPRESSION_ASTC);
 
 /// wgpu-based GPU backend.
 pub struct WgpuBackend {
@@ -59,6 +67,12 @@ pub struct WgpuBackend {
     device: Arc<wgpu::Device>,
     queue: Arc<wgpu::Queue>,
     encoder_scratch: std::sync::Mutex<WgpuEncoderScratch>,
+    /// Slang → WGSL compilation results.
+    shader_cache: ShaderCache,
+    /// Modules Slang sources can import.
+    shader_library: std::sync::RwLock<ShaderLibrary>,
+    /// Pipelines for texture blits, created on first use.
+    blitter: std::sync::OnceLock<blit::WgpuBlitter>,
 }
 
 impl std::fmt::Debug for WgpuBackend {
@@ -115,7 +129,8 @@ impl WgpuBackend {
         // Request device
         let (device, queue) = pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor {
             label: Some("RedLilium Device"),
-            required_features: wgpu::Features::POLYGON_MODE_LINE,
+            required_features: wgpu::Features::POLYGON_MODE_LINE
+                | (adapter.features() & OPTIONAL_FEATURES),
             required_limits: wgpu::Limits::default(),
             memory_hints: wgpu::MemoryHints::default(),
             experimental_features: wgpu::ExperimentalFeatures::default(),
@@ -131,6 +146,9 @@ impl WgpuBackend {
             device: Arc::new(device),
             queue: Arc::new(queue),
             encoder_scratch: std::sync::Mutex::new(WgpuEncoderScratch::default()),
+            shader_cache: ShaderCache::new(),
+            shader_library: std::sync::RwLock::new(ShaderLibrary::standard_slang()),
+            blitter: std::sync::OnceLock::new(),
         })
     }
 
@@ -154,6 +172,39 @@ impl WgpuBackend {
         &self.queue
     }
 
+    /// Get the texture blitter, creating it on first use.
+    fn blitter(&self) -> &blit::WgpuBlitter {
+        self.blitter
+            .get_or_init(|| blit::WgpuBlitter::new(&self.device))
+    }
+
+    /// Get the cache of compiled shaders.
+    pub fn shader_cache(&self) -> &ShaderCache {
+        &self.shader_cache
+    }
+
+    /// Get the modules Slang sources can import.
+    pub fn shader_library(&self) -> &std::sync::RwLock<ShaderLibrary> {
+        &self.shader_library
+    }
+
+    /// Identity of the adapter and driver, for validating persisted caches.
+    ///
+    /// wgpu exposes no pipeline cache UUID, so this hashes the adapter info.
+    pub fn device_uuid(&self) -> [u8; 16] {
+        let info = self.adapter.get_info();
+        let mut uuid = [0u8; 16];
+        uuid[..4].copy_from_slice(&info.vendor.to_le_bytes());
+        uuid[4..8].copy_from_slice(&info.device.to_le_bytes());
+        let driver = format!("{:?}/{}/{}", info.backend, info.driver, info.driver_info);
+        // FNV-1a over the backend and driver version strings
+        let hash = driver.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+        uuid[8..].copy_from_slice(&hash.to_le_bytes());
+        uuid
+    }
+
     /// Check if the current adapter is compatible with a surface.
     pub fn is_adapter_compatible_with_surface(&self, surface: &wgpu::Surface<'_>) -> bool {
         self.adapter.is_surface_supported(surface)
@@ -199,7 +250,8 @@ impl WgpuBackend {
         let (new_device, new_queue) =
             pollster::block_on(new_adapter.request_device(&wgpu::DeviceDescriptor {
                 label: Some("RedLilium Device"),
-                required_features: wgpu::Features::POLYGON_MODE_LINE,
+                required_features: wgpu::Features::POLYGON_MODE_LINE
+                    | (new_adapter.features() & OPTIONAL_FEATURES),
                 required_limits: wgpu::Limits::default(),
                 memory_hints: wgpu::MemoryHints::default(),
                 experimental_features: wgpu::ExperimentalFeatures::default(),
@@ -216,6 +268,7 @@ impl WgpuBackend {
         self.adapter = new_adapter;
         self.device = Arc::new(new_device);
         self.queue = Arc::new(new_queue);
+        self.blitter = std::sync::OnceLock::new();
 
         Ok(true)
     }
@@ -234,6 +287,10 @@ impl WgpuBackend {
     /// - If `signal_fence` is `None`: Blocks until GPU completes (sync, for backwards compatibility).
     ///
     /// For true async rendering with multiple frames in flight, always provide a fence.
+    ///
+    /// With `timestamps`, graphics and compute passes write their start and
+    /// end timestamps, which are resolved and copied into the pool's
+    /// readback buffer at the end of the same command encoder.
     pub fn execute_graph(
         &self,
         graph: &RenderGraph,
@@ -241,6 +298,7 @@ impl WgpuBackend {
         _wait_semaphores: &[&super::GpuSemaphore],
         _signal_semaphores: &[&super::GpuSemaphore],
         signal_fence: Option<&GpuFence>,
+        timestamps: Option<GpuTimestampWrites<'_>>,
     ) -> Result<(), GraphicsError> {
         profile_scope!("wgpu_execute_graph");
 
@@ -256,12 +314,51 @@ impl WgpuBackend {
         // Process each pass in compiled order
         {
             profile_scope!("record_passes");
+            let query_set = timestamps.and_then(|writes| match writes.pool {
+                GpuTimestampPool::Wgpu { query_set, .. } => Some(query_set),
+                _ => None,
+            });
+            let mut next_query = timestamps.map_or(0, |writes| writes.first_query);
             for handle in compiled.pass_order() {
                 let pass = &passes[handle.index()];
-                self.encode_pass(&mut encoder, pass)?;
+                let pass_timestamps = match query_set {
+                    Some(query_set) if pass.is_timed() => {
+                        next_query += 2;
+                        Some((query_set, next_query - 2))
+                    }
+                    _ => None,
+                };
+                self.encode_pass(&mut encoder, pass, pass_timestamps)?;
             }
         }
 
+        if let Some(writes) = timestamps
+            && writes.query_count > 0
+            && let GpuTimestampPool::Wgpu {
+                query_set,
+                resolve_buffer,
+                readback_buffer,
+                ..
+            } = writes.pool
+        {
+            // Resolve offsets must be 256-byte aligned, so resolve to the
+            // start of the scratch buffer and copy into place.
+            let size = u64::from(writes.query_count) * wgpu::QUERY_SIZE as u64;
+            encoder.resolve_query_set(
+                query_set,
+                writes.first_query..writes.first_query + writes.query_count,
+                resolve_buffer,
+                0,
+            );
+            encoder.copy_buffer_to_buffer(
+                resolve_buffer,
+                0,
+                readback_buffer,
+                u64::from(writes.first_query) * wgpu::QUERY_SIZE as u64,
+                size,
+            );
+        }
+
         // Submit commands
         let command_buffer = encoder.finish();
         let submission_index = {
@@ -269,6 +366,14 @@ impl WgpuBackend {
             self.queue.submit(std::iter::once(command_buffer))
         };
 
+        // Remember the submission for the timestamp readback to wait on
+        if let Some(writes) = timestamps
+            && writes.query_count > 0
+            && let GpuTimestampPool::Wgpu { submission, .. } = writes.pool
+        {
+            *submission.lock().unwrap() = Some(submission_index.clone());
+        }
+
         // Store submission index in fence for async polling
         if let Some(GpuFence::Wgpu {
             submission_index: fence_idx,
@@ -13,21 +13,24 @@ use super::conversion::{
 };
 
 impl WgpuBackend {
+    /// Encode one pass. With `timestamps`, the pass writes its start and
+    /// end timestamps to the given query and the one after it.
     pub(super) fn encode_pass(
         &self,
         encoder: &mut wgpu::CommandEncoder,
         pass: &Pass,
+        timestamps: Option<(&wgpu::QuerySet, u32)>,
     ) -> Result<(), GraphicsError> {
         profile_scope!("encode_pass");
         match pass {
             Pass::Graphics(graphics_pass) => {
-                self.encode_graphics_pass(encoder, graphics_pass)?;
+                self.encode_graphics_pass(encoder, graphics_pass, timestamps)?;
             }
             Pass::Transfer(transfer_pass) => {
                 self.encode_transfer_pass(encoder, transfer_pass)?;
             }
             Pass::Compute(compute_pass) => {
-                self.encode_compute_pass(encoder, compute_pass)?;
+                self.encode_compute_pass(encoder, compute_pass, timestamps)?;
             }
         }
         Ok(())
@@ -37,6 +40,7 @@ impl WgpuBackend {
         &self,
         encoder: &mut wgpu::CommandEncoder,
         pass: &crate::graph::GraphicsPass,
+        timestamps: Option<(&wgpu::QuerySet, u32)>,
     ) -> Result<(), GraphicsError> {
         use crate::graph::RenderTarget;
 
@@ -130,7 +134,13 @@ impl WgpuBackend {
             label: Some(pass.name()),
             color_attachments,
             depth_stencil_attachment,
-            timestamp_writes: None,
+            timestamp_writes: timestamps.map(|(query_set, first)| {
+                wgpu::RenderPassTimestampWrites {
+                    query_set,
+                    beginning_of_pass_write_index: Some(first),
+                    end_of_pass_write_index: Some(first + 1),
+                }
+            }),
             occlusion_query_set: None,
             multiview_mask: None,
         });
@@ -522,6 +532,36 @@ impl WgpuBackend {
                     );
                 }
             }
+            TransferOperation::Blit {
+                src,
+                dst,
+                regions,
+                filter,
+            } => {
+                let GpuTexture::Wgpu {
+                    texture: src_texture,
+                    ..
+                } = src.gpu_handle()
+                else {
+                    return Ok(());
+                };
+                let GpuTexture::Wgpu {
+                    texture: dst_texture,
+                    ..
+                } = dst.gpu_handle()
+                else {
+                    return Ok(());
+                };
+
+                self.blitter().encode(
+                    &self.device,
+                    encoder,
+                    src_texture,
+                    dst_texture,
+                    regions,
+                    *filter,
+                )?;
+            }
         }
         Ok(())
     }
@@ -530,6 +570,7 @@ impl WgpuBackend {
         &self,
         encoder: &mut wgpu::CommandEncoder,
         pass: &crate::graph::ComputePass,
+        timestamps: Option<(&wgpu::QuerySet, u32)>,
     ) -> Result<(), GraphicsError> {
         if !pass.has_dispatches() {
             return Ok(());
@@ -537,7 +578,13 @@ impl WgpuBackend {
 
         let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
             label: Some(pass.name()),
-            timestamp_writes: None,
+            timestamp_writes: timestamps.map(|(query_set, first)| {
+                wgpu::ComputePassTimestampWrites {
+                    query_set,
+                    beginning_of_pass_write_index: Some(first),
+                    end_of_pass_write_index: Some(first + 1),
+                }
+            }),
         });
 
         let scratch = &mut *self.encoder_scratch.lock().unwrap();
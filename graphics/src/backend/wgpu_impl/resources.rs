@@ -5,7 +5,7 @@ use std::sync::Mutex;
 use crate::error::GraphicsError;
 use crate::types::{BufferDescriptor, SamplerDescriptor, TextureDescriptor};
 
-use super::super::{GpuBuffer, GpuFence, GpuSampler, GpuSemaphore, GpuTexture};
+use super::super::{GpuBuffer, GpuFence, GpuSampler, GpuSemaphore, GpuTexture, GpuTimestampPool};
 use super::WgpuBackend;
 use super::conversion::{
     convert_address_mode, convert_buffer_usage, convert_compare_function, convert_filter_mode,
@@ -280,7 +280,7 @@ impl WgpuBackend {
                 },
                 depth_stencil: depth_format.map(|format| wgpu::DepthStencilState {
                     format,
-                    depth_write_enabled: true,
+                    depth_write_enabled: descriptor.depth_write,
                     depth_compare: wgpu::CompareFunction::LessEqual,
                     stencil: wgpu::StencilState::default(),
                     bias: wgpu::DepthBiasState::default(),
@@ -393,15 +393,34 @@ impl WgpuBackend {
             ShaderSourceLanguage::Wgsl => Ok(source_str.to_string()),
             #[cfg(feature = "slang-shaders")]
             ShaderSourceLanguage::Slang => {
-                let compiler = crate::shader::SlangCompiler::new()?;
-                // Write standard library modules so `import math;` etc. resolve
-                compiler.write_library_modules(&crate::shader::ShaderLibrary::standard_slang())?;
-                let defines: Vec<(&str, &str)> = shader
-                    .defines
-                    .iter()
-                    .map(|(k, v)| (k.as_str(), v.as_str()))
-                    .collect();
-                compiler.compile_to_wgsl(source_str, &shader.entry_point, &[], &defines)
+                use crate::shader::{ShaderCacheKey, ShaderTarget};
+
+                let library = self.shader_library.read().unwrap().clone();
+                let key = ShaderCacheKey::new(
+                    &shader.source,
+                    &shader.entry_point,
+                    shader.stage,
+                    shader.language,
+                    ShaderTarget::Wgsl,
+                    &shader.defines,
+                    &library,
+                );
+                let wgsl = self.shader_cache.get_or_compile(key, || {
+                    let compiler = crate::shader::SlangCompiler::new()?;
+                    // Write standard library modules so `import math;` etc. resolve
+                    compiler.write_library_modules(&library)?;
+                    let defines: Vec<(&str, &str)> = shader
+                        .defines
+                        .iter()
+                        .map(|(k, v)| (k.as_str(), v.as_str()))
+                        .collect();
+                    compiler
+                        .compile_to_wgsl(source_str, &shader.entry_point, &[], &defines)
+                        .map(String::into_bytes)
+                })?;
+                String::from_utf8(wgsl.to_vec()).map_err(|e| {
+                    GraphicsError::ShaderCompilationFailed(format!("Cached WGSL is not UTF-8: {e}"))
+                })
             }
             #[cfg(not(feature = "slang-shaders"))]
             ShaderSourceLanguage::Slang => Err(GraphicsError::FeatureNotSupported(
@@ -543,8 +562,6 @@ impl WgpuBackend {
         data: &[u8],
         descriptor: &TextureDescriptor,
     ) -> Result<(), crate::error::GraphicsError> {
-        use crate::types::TextureDimension;
-
         let GpuTexture::Wgpu {
             texture: wgpu_texture,
             ..
@@ -555,39 +572,76 @@ impl WgpuBackend {
             ));
         };
 
-        let format = convert_texture_format(descriptor.format);
-        let block_size = format.block_copy_size(None).unwrap_or(4);
-        let bytes_per_row = descriptor.size.width * block_size;
-
-        let depth_or_array_layers = match descriptor.dimension {
-            TextureDimension::Cube => 6,
-            TextureDimension::CubeArray => descriptor.size.depth * 6,
-            TextureDimension::D1
-            | TextureDimension::D1Array
-            | TextureDimension::D2
-            | TextureDimension::D2Array
-            | TextureDimension::D3 => descriptor.size.depth,
+        // Data holds the mip levels largest first, each with all layers.
+        let mut offset = 0;
+        for level in 0..descriptor.mip_level_count {
+            let level_size = descriptor.mip_level_size(level);
+            let Some(level_data) = data.get(offset..offset + level_size) else {
+                break;
+            };
+            offset += level_size;
+
+            let extent = descriptor.mip_level_extent(level);
+            self.queue.write_texture(
+                wgpu::TexelCopyTextureInfo {
+                    texture: wgpu_texture,
+                    mip_level: level,
+                    origin: wgpu::Origin3d::ZERO,
+                    aspect: wgpu::TextureAspect::All,
+                },
+                level_data,
+                wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(descriptor.format.bytes_per_row(extent.width)),
+                    rows_per_image: Some(descriptor.format.rows_per_image(extent.height)),
+                },
+                wgpu::Extent3d {
+                    width: extent.width,
+                    height: extent.height,
+                    depth_or_array_layers: extent.depth,
+                },
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Fill mip levels `1..` of a texture by downsampling each level from
+    /// the previous one, waiting for the GPU to finish.
+    pub fn generate_mipmaps(
+        &self,
+        texture: &GpuTexture,
+        descriptor: &TextureDescriptor,
+    ) -> Result<(), crate::error::GraphicsError> {
+        let GpuTexture::Wgpu {
+            texture: wgpu_texture,
+            ..
+        } = texture
+        else {
+            return Err(crate::error::GraphicsError::Internal(
+                "generate_mipmaps called with non-Wgpu texture".to_string(),
+            ));
         };
+        let regions = crate::graph::TextureBlitRegion::mip_chain(descriptor);
+        if regions.is_empty() {
+            return Ok(());
+        }
 
-        self.queue.write_texture(
-            wgpu::TexelCopyTextureInfo {
-                texture: wgpu_texture,
-                mip_level: 0,
-                origin: wgpu::Origin3d::ZERO,
-                aspect: wgpu::TextureAspect::All,
-            },
-            data,
-            wgpu::TexelCopyBufferLayout {
-                offset: 0,
-                bytes_per_row: Some(bytes_per_row),
-                rows_per_image: Some(descriptor.size.height),
-            },
-            wgpu::Extent3d {
-                width: descriptor.size.width,
-                height: descriptor.size.height,
-                depth_or_array_layers,
-            },
-        );
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Generate Mipmaps"),
+            });
+        self.blitter().encode(
+            &self.device,
+            &mut encoder,
+            wgpu_texture,
+            wgpu_texture,
+            &regions,
+            crate::types::FilterMode::Linear,
+        )?;
+        self.queue.submit(Some(encoder.finish()));
+        let _ = self.device.poll(wgpu::PollType::wait_indefinitely());
 
         Ok(())
     }
@@ -656,4 +710,116 @@ impl WgpuBackend {
             vec![0u8; size as usize]
         }
     }
+
+    /// Create a timestamp query set with `capacity` queries.
+    ///
+    /// Returns `None` if the device was created without
+    /// [`wgpu::Features::TIMESTAMP_QUERY`].
+    pub fn create_timestamp_pool(&self, capacity: u32) -> Option<GpuTimestampPool> {
+        if capacity == 0
+            || !self
+                .device
+                .features()
+                .contains(wgpu::Features::TIMESTAMP_QUERY)
+        {
+            return None;
+        }
+        let capacity = capacity.min(wgpu::QUERY_SET_MAX_QUERIES);
+        let size = u64::from(capacity) * wgpu::QUERY_SIZE as u64;
+        let query_set = self.device.create_query_set(&wgpu::QuerySetDescriptor {
+            label: Some("Timestamp Queries"),
+            ty: wgpu::QueryType::Timestamp,
+            count: capacity,
+        });
+        let resolve_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Timestamp Resolve Buffer"),
+            size,
+            usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Timestamp Readback Buffer"),
+            size,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        Some(GpuTimestampPool::Wgpu {
+            query_set,
+            resolve_buffer,
+            readback_buffer,
+            capacity,
+            submission: std::sync::Mutex::new(None),
+            mapping: std::sync::Mutex::new(None),
+        })
+    }
+
+    /// Nanoseconds per timestamp tick.
+    pub fn timestamp_period(&self) -> f32 {
+        self.queue.get_timestamp_period()
+    }
+
+    /// Map the first `count` timestamps of the pool's readback buffer once
+    /// the submissions so far have finished.
+    pub fn map_timestamps(&self, pool: &GpuTimestampPool, count: u32) {
+        let GpuTimestampPool::Wgpu {
+            readback_buffer,
+            mapping,
+            ..
+        } = pool
+        else {
+            return;
+        };
+        if count == 0 {
+            return;
+        }
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        readback_buffer
+            .slice(..u64::from(count) * wgpu::QUERY_SIZE as u64)
+            .map_async(wgpu::MapMode::Read, move |result| {
+                let _ = tx.send(result);
+            });
+        *mapping.lock().unwrap() = Some(rx);
+    }
+
+    /// Read the first `count` timestamps mapped by
+    /// [`map_timestamps`](Self::map_timestamps).
+    ///
+    /// Waits only for the last submission that wrote timestamps, which has
+    /// normally finished already, not for work queued after it.
+    pub fn read_timestamps(&self, pool: &GpuTimestampPool, count: u32) -> Vec<u64> {
+        let GpuTimestampPool::Wgpu {
+            readback_buffer,
+            submission,
+            mapping,
+            ..
+        } = pool
+        else {
+            return vec![0; count as usize];
+        };
+        let Some(mapping) = mapping.lock().unwrap().take() else {
+            return vec![0; count as usize];
+        };
+
+        let poll = match submission.lock().unwrap().take() {
+            Some(submission_index) => wgpu::PollType::Wait {
+                submission_index: Some(submission_index),
+                timeout: Some(std::time::Duration::from_secs(10)),
+            },
+            None => wgpu::PollType::Poll,
+        };
+        let _ = self.device.poll(poll);
+        if !matches!(mapping.try_recv(), Ok(Ok(()))) {
+            return vec![0; count as usize];
+        }
+
+        let timestamps = readback_buffer
+            .slice(..u64::from(count) * wgpu::QUERY_SIZE as u64)
+            .get_mapped_range()
+            .chunks_exact(8)
+            .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+            .collect();
+        readback_buffer.unmap();
+        timestamps
+    }
 }
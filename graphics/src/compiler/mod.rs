@@ -11,6 +11,8 @@
 //! 2. **Auto-dependency generation** - Detect resource conflicts between passes
 //! 3. **Topological sort** - Order passes respecting all dependencies
 //! 4. **Cycle detection** - Validate the graph is a DAG
+//! 5. **Transient planning** - Compute transient resource lifetimes and assign
+//!    non-overlapping transients to shared physical slots (memory aliasing)
 //!
 //! The compiler automatically infers dependencies from resource access patterns.
 //! When pass A writes a texture that pass B reads, B is automatically ordered
@@ -34,14 +36,16 @@
 //! let compiled = graph.compile(RenderGraphCompilationMode::Automatic)?;
 //! ```
 
-use std::collections::{HashSet, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 
 use redlilium_core::pool::Poolable;
 use redlilium_core::profiling::{profile_function, profile_scope};
 
 use crate::graph::resource_usage::{PassResourceUsage, SurfaceAccess};
+use crate::graph::transient::{TransientResources, textures_compatible};
 use crate::graph::{Pass, PassHandle, RenderGraph};
+use crate::types::{BufferDescriptor, TextureDescriptor};
 
 /// Controls how the compiler handles ambiguous pass ordering.
 ///
@@ -59,17 +63,40 @@ pub enum RenderGraphCompilationMode {
     Strict,
 }
 
+/// Physical allocation assigned to a transient resource.
+///
+/// Lifetimes are given as positions in [`CompiledGraph::pass_order`] and are
+/// inclusive on both ends. Transients sharing a `slot` share memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TransientAllocation {
+    /// Index of the physical slot backing this transient.
+    pub slot: usize,
+    /// Position of the first pass that uses the transient.
+    pub first_use: usize,
+    /// Position of the last pass that uses the transient.
+    pub last_use: usize,
+}
+
 /// A compiled render graph ready for execution.
 ///
 /// Contains a topologically sorted pass order that respects all dependencies,
 /// along with pre-computed resource usage for each pass (used for barrier
-/// generation by the backend).
+/// generation by the backend) and the allocation plan for transient resources.
 #[derive(Debug, Default)]
 pub struct CompiledGraph {
     /// Optimized pass execution order as handles.
     pass_order: Vec<PassHandle>,
     /// Pre-computed resource usage per pass, parallel to `pass_order`.
     pass_usages: Vec<PassResourceUsage>,
+    /// Allocation per transient texture, parallel to the graph's transient textures.
+    /// `None` for transients not used by any pass.
+    transient_textures: Vec<Option<TransientAllocation>>,
+    /// Allocation per transient buffer, parallel to the graph's transient buffers.
+    transient_buffers: Vec<Option<TransientAllocation>>,
+    /// Descriptor of each physical texture slot.
+    texture_slots: Vec<TextureDescriptor>,
+    /// Descriptor of each physical buffer slot (size is the largest member).
+    buffer_slots: Vec<BufferDescriptor>,
 }
 
 impl PartialEq for CompiledGraph {
@@ -88,6 +115,7 @@ impl CompiledGraph {
         Self {
             pass_order,
             pass_usages: (0..len).map(|_| PassResourceUsage::new()).collect(),
+            ..Default::default()
         }
     }
 
@@ -113,6 +141,31 @@ impl CompiledGraph {
     pub fn is_empty(&self) -> bool {
         self.pass_order.is_empty()
     }
+
+    /// Get the allocation of each transient texture.
+    ///
+    /// Indexed parallel to [`RenderGraph::transient_textures`]. Transients not
+    /// used by any pass have no allocation.
+    pub fn transient_textures(&self) -> &[Option<TransientAllocation>] {
+        &self.transient_textures
+    }
+
+    /// Get the allocation of each transient buffer.
+    ///
+    /// Indexed parallel to [`RenderGraph::transient_buffers`].
+    pub fn transient_buffers(&self) -> &[Option<TransientAllocation>] {
+        &self.transient_buffers
+    }
+
+    /// Get the descriptors of the physical texture slots backing transients.
+    pub fn transient_texture_slots(&self) -> &[TextureDescriptor] {
+        &self.texture_slots
+    }
+
+    /// Get the descriptors of the physical buffer slots backing transients.
+    pub fn transient_buffer_slots(&self) -> &[BufferDescriptor] {
+        &self.buffer_slots
+    }
 }
 
 impl Poolable for CompiledGraph {
@@ -122,6 +175,10 @@ impl Poolable for CompiledGraph {
     fn reset(&mut self) {
         self.pass_order.clear();
         self.pass_usages.clear();
+        self.transient_textures.clear();
+        self.transient_buffers.clear();
+        self.texture_slots.clear();
+        self.buffer_slots.clear();
     }
 }
 
@@ -181,25 +238,32 @@ pub fn compile(
     mode: RenderGraphCompilationMode,
 ) -> Result<CompiledGraph, GraphError> {
     let mut result = CompiledGraph::default();
-    compile_into(graph.passes(), graph.edges(), mode, &mut result)?;
+    compile_into(
+        graph.passes(),
+        graph.edges(),
+        &graph.transients,
+        mode,
+        &mut result,
+    )?;
     Ok(result)
 }
 
 /// Compile a render graph into an existing [`CompiledGraph`], reusing its allocation.
 ///
 /// This is the in-place variant of [`compile`]. It clears the target and fills
-/// it with the topologically sorted pass order and pre-computed resource usages.
+/// it with the topologically sorted pass order, pre-computed resource usages
+/// and the transient allocation plan.
 pub(crate) fn compile_into(
     passes: &[Pass],
     edges: &[(PassHandle, PassHandle)],
+    transients: &TransientResources,
     mode: RenderGraphCompilationMode,
     target: &mut CompiledGraph,
 ) -> Result<(), GraphError> {
     profile_function!();
 
     let n = passes.len();
-    target.pass_order.clear();
-    target.pass_usages.clear();
+    target.reset();
 
     if n == 0 {
         return Ok(());
@@ -273,9 +337,150 @@ pub(crate) fn compile_into(
         }
     }
 
+    // Step 6: Plan transient lifetimes and memory aliasing
+    {
+        profile_scope!("plan_transients");
+        plan_transients(transients, target);
+    }
+
     Ok(())
 }
 
+/// Compute the `(first_use, last_use)` pass positions of each transient.
+///
+/// `uses` yields `(pass position, resource pointer)` pairs for every resource
+/// access; `index` maps a transient's pointer to its position in the transient list.
+fn transient_lifetimes<T>(
+    count: usize,
+    index: &HashMap<*const T, usize>,
+    uses: impl Iterator<Item = (usize, *const T)>,
+) -> Vec<Option<(usize, usize)>> {
+    let mut lifetimes = vec![None; count];
+    for (position, ptr) in uses {
+        if let Some(&i) = index.get(&ptr) {
+            lifetimes[i] = Some(match lifetimes[i] {
+                Some((first, _)) => (first, position),
+                None => (position, position),
+            });
+        }
+    }
+    lifetimes
+}
+
+/// Greedily assign lifetimes to physical slots.
+///
+/// Transients are visited in order of first use; each takes the first slot
+/// whose previous occupant is dead and whose descriptor is compatible, or
+/// opens a new slot. Returns the allocation per transient and the index of
+/// the transient that opened each slot.
+fn assign_slots(
+    lifetimes: &[Option<(usize, usize)>],
+    compatible: impl Fn(usize, usize) -> bool,
+) -> (Vec<Option<TransientAllocation>>, Vec<usize>) {
+    let mut order: Vec<usize> = (0..lifetimes.len())
+        .filter(|&i| lifetimes[i].is_some())
+        .collect();
+    order.sort_by_key(|&i| lifetimes[i].map(|(first, _)| first));
+
+    let mut allocations = vec![None; lifetimes.len()];
+    // (founding transient, last use of the current occupant)
+    let mut slots: Vec<(usize, usize)> = Vec::new();
+    for i in order {
+        let (first_use, last_use) = lifetimes[i].unwrap();
+        let slot = match slots
+            .iter()
+            .position(|&(founder, busy_until)| busy_until < first_use && compatible(founder, i))
+        {
+            Some(slot) => {
+                slots[slot].1 = last_use;
+                slot
+            }
+            None => {
+                slots.push((i, last_use));
+                slots.len() - 1
+            }
+        };
+        allocations[i] = Some(TransientAllocation {
+            slot,
+            first_use,
+            last_use,
+        });
+    }
+    (
+        allocations,
+        slots.into_iter().map(|(founder, _)| founder).collect(),
+    )
+}
+
+/// Compute lifetimes of transient resources and assign them to physical slots.
+fn plan_transients(transients: &TransientResources, target: &mut CompiledGraph) {
+    if !transients.textures.is_empty() {
+        let index: HashMap<_, _> = transients
+            .textures
+            .iter()
+            .enumerate()
+            .map(|(i, t)| (Arc::as_ptr(t), i))
+            .collect();
+        let uses = target
+            .pass_usages
+            .iter()
+            .enumerate()
+            .flat_map(|(position, usage)| {
+                usage
+                    .texture_usages
+                    .iter()
+                    .map(move |u| (position, Arc::as_ptr(&u.texture)))
+            });
+        let lifetimes = transient_lifetimes(transients.textures.len(), &index, uses);
+        let textures = &transients.textures;
+        let (allocations, founders) = assign_slots(&lifetimes, |a, b| {
+            textures_compatible(textures[a].descriptor(), textures[b].descriptor())
+        });
+        target.transient_textures = allocations;
+        target.texture_slots = founders
+            .into_iter()
+            .map(|i| textures[i].descriptor().clone())
+            .collect();
+    }
+
+    if !transients.buffers.is_empty() {
+        let index: HashMap<_, _> = transients
+            .buffers
+            .iter()
+            .enumerate()
+            .map(|(i, b)| (Arc::as_ptr(b), i))
+            .collect();
+        let uses = target
+            .pass_usages
+            .iter()
+            .enumerate()
+            .flat_map(|(position, usage)| {
+                usage
+                    .buffer_usages
+                    .iter()
+                    .map(move |u| (position, Arc::as_ptr(&u.buffer)))
+            });
+        let lifetimes = transient_lifetimes(transients.buffers.len(), &index, uses);
+        let buffers = &transients.buffers;
+        let (allocations, founders) = assign_slots(&lifetimes, |a, b| {
+            buffers[a].descriptor().usage == buffers[b].descriptor().usage
+        });
+        // Each buffer slot must fit its largest member
+        let mut slots: Vec<BufferDescriptor> = founders
+            .into_iter()
+            .map(|i| buffers[i].descriptor().clone())
+            .collect();
+        for (buffer, allocation) in buffers.iter().zip(&allocations) {
+            if let Some(allocation) = allocation {
+                let slot = &mut slots[allocation.slot];
+                slot.size = slot.size.max(buffer.size());
+            }
+        }
+        target.transient_buffers = allocations;
+        target.buffer_slots = slots;
+    }
+}
+
 /// Analyze resource conflicts between two passes.
 ///
 /// Returns `(a_before_b, b_before_a, has_waw)`:
@@ -459,9 +664,14 @@ fn compute_reachability(n: usize, edges: &[(PassHandle, PassHandle)]) -> Vec<Vec
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::graph::{ComputePass, GraphicsPass, TransferPass};
+    use crate::graph::{
+        ColorAttachment, ComputePass, GraphicsPass, RenderTargetConfig, TransferConfig,
+        TransferOperation, TransferPass,
+    };
+    use crate::resources::Texture;
+    use crate::types::{TextureFormat, TextureUsage};
 
-    use RenderGraphCompilationMode::Automatic;
+    use RenderGraphCompilationMode::{Automatic, Strict};
 
     #[test]
     fn test_compile_empty_graph() {
@@ -831,4 +1041,116 @@ mod tests {
         let auto = infer_resource_edges(&usages, &[], Automatic).unwrap();
         assert!(auto.is_empty());
     }
+
+    fn transient_target(graph: &mut RenderGraph, format: TextureFormat) -> Arc<Texture> {
+        graph.create_transient_texture(TextureDescriptor::new_2d(
+            64,
+            64,
+            format,
+            TextureUsage::RENDER_ATTACHMENT | TextureUsage::TEXTURE_BINDING,
+        ))
+    }
+
+    fn write_pass(name: &str, target: &Arc<Texture>) -> GraphicsPass {
+        let mut pass = GraphicsPass::new(name.into());
+        pass.set_render_targets(
+            RenderTargetConfig::new().with_color(ColorAttachment::from_texture(target.clone())),
+        );
+        pass
+    }
+
+    fn read_pass(name: &str, target: &Arc<Texture>, source: &Arc<Texture>) -> TransferPass {
+        let mut pass = TransferPass::new(name.into());
+        pass.set_transfer_config(TransferConfig::new().with_operation(
+            TransferOperation::copy_texture_whole(source.clone(), target.clone()),
+        ));
+        pass
+    }
+
+    #[test]
+    fn test_transient_chain_aliases_memory() {
+        // A → B → C → D, each pass reads the previous transient and writes the next.
+        // Only adjacent transients are alive at once, so two slots suffice.
+        let mut graph = RenderGraph::new();
+        let targets: Vec<_> = (0..4)
+            .map(|_| transient_target(&mut graph, TextureFormat::Rgba8Unorm))
+            .collect();
+        graph.add_graphics_pass(write_pass("first", &targets[0]));
+        for i in 1..targets.len() {
+            graph.add_transfer_pass(read_pass("copy", &targets[i], &targets[i - 1]));
+        }
+
+        let compiled = compile(&graph, Strict).unwrap();
+        assert_eq!(compiled.transient_texture_slots().len(), 2);
+
+        let allocations: Vec<_> = compiled
+            .transient_textures()
+            .iter()
+            .map(|a| a.unwrap())
+            .collect();
+        assert_eq!((allocations[0].first_use, allocations[0].last_use), (0, 1));
+        assert_eq!((allocations[3].first_use, allocations[3].last_use), (3, 3));
+        assert_eq!(allocations[0].slot, allocations[2].slot);
+        assert_eq!(allocations[1].slot, allocations[3].slot);
+        assert_ne!(allocations[0].slot, allocations[1].slot);
+    }
+
+    #[test]
+    fn test_transient_incompatible_descriptors_not_aliased() {
+        let mut graph = RenderGraph::new();
+        let color = transient_target(&mut graph, TextureFormat::Rgba8Unorm);
+        let hdr = transient_target(&mut graph, TextureFormat::Rgba16Float);
+        let a = graph.add_graphics_pass(write_pass("a", &color));
+        let b = graph.add_graphics_pass(write_pass("b", &hdr));
+        graph.add_dependency(b, a);
+
+        let compiled = compile(&graph, Strict).unwrap();
+        assert_eq!(compiled.transient_texture_slots().len(), 2);
+    }
+
+    #[test]
+    fn test_transient_unused_has_no_allocation() {
+        let mut graph = RenderGraph::new();
+        let used = transient_target(&mut graph, TextureFormat::Rgba8Unorm);
+        let _unused = transient_target(&mut graph, TextureFormat::Rgba8Unorm);
+        graph.add_graphics_pass(write_pass("main", &used));
+
+        let compiled = compile(&graph, Strict).unwrap();
+        assert!(compiled.transient_textures()[0].is_some());
+        assert!(compiled.transient_textures()[1].is_none());
+        assert_eq!(compiled.transient_texture_slots().len(), 1);
+    }
+
+    #[test]
+    fn test_transient_buffer_slot_fits_largest() {
+        use crate::types::{BufferDescriptor, BufferUsage};
+
+        let usage = BufferUsage::COPY_SRC | BufferUsage::COPY_DST;
+        let mut graph = RenderGraph::new();
+        let small = graph.create_transient_buffer(BufferDescriptor::new(256, usage));
+        let large = graph.create_transient_buffer(BufferDescriptor::new(1024, usage));
+        let other = graph.create_transient_buffer(BufferDescriptor::new(64, usage));
+
+        let mut first = TransferPass::new("first".into());
+        first.set_transfer_config(TransferConfig::new().with_operation(
+            TransferOperation::copy_buffer_whole(small.clone(), other.clone()),
+        ));
+        let mut second = TransferPass::new("second".into());
+        second.set_transfer_config(
+            TransferConfig::new()
+                .with_operation(TransferOperation::copy_buffer_whole(other, large)),
+        );
+        graph.add_transfer_pass(first);
+        graph.add_transfer_pass(second);
+
+        let compiled = compile(&graph, Strict).unwrap();
+        // `small` dies after the first pass, so `large` takes over its slot
+        let allocations = compiled.transient_buffers();
+        assert_eq!(allocations[0].unwrap().slot, allocations[1].unwrap().slot);
+        assert_eq!(compiled.transient_buffer_slots().len(), 2);
+        assert_eq!(
+            compiled.transient_buffer_slots()[allocations[1].unwrap().slot].size,
+            1024
+        );
+    }
 }
@@ -11,11 +11,13 @@ use crate::materials::{Material, MaterialDescriptor};
 use crate::mesh::{CpuMesh, Mesh, MeshDescriptor};
 use crate::pipeline::FramePipeline;
 use crate::resources::{Buffer, Sampler, Texture};
+use crate::shader::ShaderCache;
 use crate::types::{
     BufferDescriptor, BufferUsage, CpuSampler, CpuTexture, Extent3d, SamplerDescriptor,
     TextureDescriptor, TextureUsage,
 };
 use redlilium_core::profiling::profile_scope;
+use redlilium_vfs::{Vfs, VfsError};
 
 /// Capabilities of a graphics device.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -202,9 +204,10 @@ impl GraphicsDevice {
     ///
     /// This is a convenience method that:
     /// 1. Creates a GPU texture with `TEXTURE_BINDING | COPY_DST` usage
-    /// 2. Writes the pixel data from the `CpuTexture` into it
+    /// 2. Writes the pixel data from the `CpuTexture` into it, including all
+    ///    mip levels and array layers it holds
     ///
-    /// For textures that need custom usage flags, mip levels, or multisampling,
+    /// For textures that need custom usage flags, generated mip levels, or multisampling,
     /// use [`create_texture`] with a [`TextureDescriptor`] instead.
     ///
     /// # Example
@@ -223,8 +226,8 @@ impl GraphicsDevice {
 
         let descriptor = TextureDescriptor {
             label: cpu_texture.name.clone(),
-            size: Extent3d::new_2d(cpu_texture.width, cpu_texture.height),
-            mip_level_count: 1,
+            size: Extent3d::new_3d(cpu_texture.width, cpu_texture.height, cpu_texture.depth),
+            mip_level_count: cpu_texture.mip_level_count.max(1),
             sample_count: 1,
             dimension: cpu_texture.dimension,
             format: cpu_texture.format,
@@ -327,7 +330,7 @@ impl GraphicsDevice {
                     .all(|s| s.language == crate::materials::ShaderSourceLanguage::Slang)
             {
                 let compiler = crate::shader::SlangCompiler::new()?;
-                compiler.write_library_modules(&crate::shader::ShaderLibrary::standard_slang())?;
+                compiler.write_library_modules(&self.shader_library())?;
 
                 // Pre-compute owned data (source strings and define refs)
                 let sources: Vec<String> = desc
@@ -667,7 +670,10 @@ impl GraphicsDevice {
     /// Write data to a texture.
     ///
     /// Uploads pixel data to the texture. The data should be in the format
-    /// matching the texture's format.
+    /// matching the texture's format. It holds the mip levels largest first,
+    /// each with all array layers (or depth slices) in order; trailing mip
+    /// levels may be left out, e.g. to fill them with
+    /// [`generate_mipmaps`](Self::generate_mipmaps) afterwards.
     ///
     /// # Arguments
     ///
@@ -690,6 +696,111 @@ impl GraphicsDevice {
             .write_texture(texture.gpu_handle(), data, texture.descriptor())
     }
 
+    /// Fill mip levels `1..` of a texture by downsampling each level from
+    /// the previous one with linear filtering.
+    ///
+    /// Blocks until the GPU is done. Call this after writing mip level 0
+    /// with [`write_texture`](Self::write_texture). The texture needs
+    /// `COPY_SRC | COPY_DST` usage, plus `TEXTURE_BINDING |
+    /// RENDER_ATTACHMENT` on the wgpu backend, and a format that is neither
+    /// compressed nor integer. To generate mips inside a frame, record
+    /// [`TransferOperation::generate_mipmaps`](crate::graph::TransferOperation::generate_mipmaps)
+    /// instead.
+    pub fn generate_mipmaps(&self, texture: &Texture) -> Result<(), GraphicsError> {
+        profile_scope!("generate_mipmaps");
+        self.instance
+            .backend()
+            .generate_mipmaps(texture.gpu_handle(), texture.descriptor())
+    }
+
+    /// Replace the source of a Slang shader library module, e.g. after its
+    /// file was edited.
+    ///
+    /// Shaders compiled afterwards import the new source; existing materials
+    /// keep their pipelines until they are recreated. A no-op for backends
+    /// that do not compile shaders.
+    pub fn set_shader_library_module(&self, name: &str, source: impl Into<String>) {
+        let backend = self.instance.backend();
+        if let Some(library) = backend.shader_library() {
+            library.write().unwrap().set_module(name, source.into());
+        }
+    }
+
+    /// Current modules Slang sources can import.
+    pub fn shader_library(&self) -> crate::shader::ShaderLibrary {
+        let backend = self.instance.backend();
+        match backend.shader_library() {
+            Some(library) => library.read().unwrap().clone(),
+            None => crate::shader::ShaderLibrary::standard_slang(),
+        }
+    }
+
+    /// Serialize the compiled shader cache and the driver pipeline cache.
+    ///
+    /// The result can be restored with
+    /// [`import_shader_cache`](Self::import_shader_cache).
+    pub fn export_shader_cache(&self) -> Vec<u8> {
+        let backend = self.instance.backend();
+        let empty;
+        let cache = match backend.shader_cache() {
+            Some(cache) => cache,
+            None => {
+                empty = ShaderCache::new();
+                &empty
+            }
+        };
+        cache.serialize(backend.device_uuid(), &backend.pipeline_cache_data())
+    }
+
+    /// Restore caches serialized by [`export_shader_cache`](Self::export_shader_cache).
+    ///
+    /// Compiled shaders are reused on any device. The pipeline cache is only
+    /// restored if it was created on a device with the same UUID. Call this
+    /// before creating materials.
+    pub fn import_shader_cache(&self, bytes: &[u8]) -> Result<(), GraphicsError> {
+        let backend = self.instance.backend();
+        let empty;
+        let cache = match backend.shader_cache() {
+            Some(cache) => cache,
+            None => {
+                empty = ShaderCache::new();
+                &empty
+            }
+        };
+        let pipeline_cache = cache.deserialize(bytes, backend.device_uuid())?;
+        backend.merge_pipeline_cache_data(&pipeline_cache)
+    }
+
+    /// Load the shader and pipeline caches from `path` in `vfs`.
+    ///
+    /// Returns `false` if the file does not exist yet (e.g. on first launch).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// device.load_shader_cache(&vfs, "cache/shaders.bin").await?;
+    /// // ... create materials ...
+    /// device.save_shader_cache(&vfs, "cache/shaders.bin").await?;
+    /// ```
+    pub async fn load_shader_cache(&self, vfs: &Vfs, path: &str) -> Result<bool, GraphicsError> {
+        match vfs.read(path).await {
+            Ok(bytes) => {
+                self.import_shader_cache(&bytes)?;
+                Ok(true)
+            }
+            Err(VfsError::NotFound(_)) => Ok(false),
+            Err(e) => Err(GraphicsError::Io(e.to_string())),
+        }
+    }
+
+    /// Save the shader and pipeline caches to `path` in `vfs`.
+    pub async fn save_shader_cache(&self, vfs: &Vfs, path: &str) -> Result<(), GraphicsError> {
+        let bytes = self.export_shader_cache();
+        vfs.write(path, bytes)
+            .await
+            .map_err(|e| GraphicsError::Io(e.to_string()))
+    }
+
     /// Clean up dead weak references to released resources.
     pub fn cleanup_dead_resources(&self) {
         if let Ok(mut buffers) = self.buffers.write() {
@@ -754,6 +865,22 @@ mod tests {
         instance.create_device().unwrap()
     }
 
+    #[test]
+    fn test_shader_cache_save_load() {
+        let device = create_test_device();
+        let vfs = Vfs::new();
+        vfs.mount("cache", redlilium_vfs::MemoryProvider::new());
+
+        let loaded = pollster::block_on(device.load_shader_cache(&vfs, "cache/shaders.bin"));
+        assert_eq!(loaded, Ok(false));
+
+        pollster::block_on(device.save_shader_cache(&vfs, "cache/shaders.bin")).unwrap();
+        let loaded = pollster::block_on(device.load_shader_cache(&vfs, "cache/shaders.bin"));
+        assert_eq!(loaded, Ok(true));
+
+        assert!(device.import_shader_cache(b"garbage").is_err());
+    }
+
     #[test]
     fn test_device_name() {
         let device = create_test_device();
@@ -27,6 +27,8 @@ pub enum GraphicsError {
     SurfaceLost,
     /// Shader compilation failed.
     ShaderCompilationFailed(String),
+    /// Reading or writing a file failed.
+    Io(String),
 }
 
 impl fmt::Display for GraphicsError {
@@ -42,6 +44,7 @@ impl fmt::Display for GraphicsError {
             Self::SurfaceOutdated => write!(f, "surface outdated, needs reconfiguration"),
             Self::SurfaceLost => write!(f, "surface lost, needs recreation"),
             Self::ShaderCompilationFailed(msg) => write!(f, "shader compilation failed: {msg}"),
+            Self::Io(msg) => write!(f, "I/O error: {msg}"),
         }
     }
 }
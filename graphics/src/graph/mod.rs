@@ -6,7 +6,7 @@
 //! - Optimal pass ordering via topological sort
 //! - Resource lifetime analysis
 //! - Synchronization and barrier insertion
-//! - Memory aliasing opportunities
+//! - Memory aliasing of transient resources
 //!
 //! # Architecture
 //!
@@ -38,11 +38,22 @@
 //! );
 //! let handle = graph.add_graphics_pass(pass);
 //! ```
+//!
+//! # Transient Resources
+//!
+//! Intermediate targets that only live within one frame (e.g. post-processing
+//! chains) should be created with [`RenderGraph::create_transient_texture`] and
+//! [`RenderGraph::create_transient_buffer`] instead of the device. The compiler
+//! assigns transients with non-overlapping lifetimes to shared memory, and the
+//! [`FramePipeline`](crate::pipeline::FramePipeline) pools that memory across
+//! frames. Because memory is shared, the first pass using a transient should
+//! clear or fully overwrite it.
 
 mod pass;
 pub mod resource_usage;
 mod target;
 mod transfer;
+pub(crate) mod transient;
 
 pub use pass::{
     ComputePass, DispatchCommand, DrawCommand, GraphicsPass, IndirectDrawCommand, Pass,
@@ -50,10 +61,18 @@ pub use pass::{
 };
 
 // Re-export compiler types for convenience
-pub use crate::compiler::{CompiledGraph, GraphError, RenderGraphCompilationMode, compile};
+pub use crate::compiler::{
+    CompiledGraph, GraphError, RenderGraphCompilationMode, TransientAllocation, compile,
+};
+
+use std::sync::Arc;
 
 use redlilium_core::pool::{Poolable, Pooled};
 
+use crate::resources::{Buffer, Texture};
+use crate::types::{BufferDescriptor, TextureDescriptor};
+use transient::TransientResources;
+
 /// Handle to a pass in the render graph.
 ///
 /// `PassHandle` is `Copy` and cheap to pass around. It is only valid within
@@ -74,8 +93,8 @@ pub use target::{
     ColorAttachment, DepthStencilAttachment, LoadOp, RenderTarget, RenderTargetConfig, StoreOp,
 };
 pub use transfer::{
-    BufferCopyRegion, BufferTextureCopyRegion, BufferTextureLayout, TextureCopyLocation,
-    TextureCopyRegion, TextureOrigin, TransferConfig, TransferOperation,
+    BufferCopyRegion, BufferTextureCopyRegion, BufferTextureLayout, TextureBlitRegion,
+    TextureCopyLocation, TextureCopyRegion, TextureOrigin, TransferConfig, TransferOperation,
 };
 
 /// The render graph describes a frame's rendering operations.
@@ -112,6 +131,8 @@ pub struct RenderGraph {
     /// Dependency edges stored as (dependent, dependency) pairs.
     /// Using edge list avoids per-pass Vec allocations.
     edges: Vec<(PassHandle, PassHandle)>,
+    /// Transient resources declared on this graph.
+    pub(crate) transients: TransientResources,
     /// Cached compiled result. Uses [`Pooled`] to preserve allocation
     /// across invalidations instead of deallocating with `Option<T>`.
     compiled: Pooled<CompiledGraph>,
@@ -127,10 +148,60 @@ impl RenderGraph {
         Self {
             passes: Vec::new(),
             edges: Vec::new(),
+            transients: TransientResources::default(),
             compiled: Pooled::default(),
         }
     }
 
+    /// Create a transient texture owned by this graph.
+    ///
+    /// The returned texture can be used like any other texture in this graph's
+    /// passes (attachments, bindings, transfers), but it has no memory until
+    /// the graph is submitted. Its memory may be shared with other transients
+    /// whose lifetimes don't overlap, so its contents are undefined at first
+    /// use. The texture must not be used after the frame it was submitted in.
+    ///
+    /// Note: Creating a transient invalidates any cached compiled graph.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let bloom = graph.create_transient_texture(TextureDescriptor::new_2d(
+    ///     width, height,
+    ///     TextureFormat::Rgba16Float,
+    ///     TextureUsage::RENDER_ATTACHMENT | TextureUsage::TEXTURE_BINDING,
+    /// ));
+    /// ```
+    pub fn create_transient_texture(&mut self, descriptor: TextureDescriptor) -> Arc<Texture> {
+        self.compiled.release(); // Invalidate cache
+        let texture = Arc::new(Texture::new_transient(descriptor));
+        self.transients.textures.push(Arc::clone(&texture));
+        texture
+    }
+
+    /// Create a transient buffer owned by this graph.
+    ///
+    /// See [`create_transient_texture`](Self::create_transient_texture) for the
+    /// lifetime and content rules of transient resources.
+    ///
+    /// Note: Creating a transient invalidates any cached compiled graph.
+    pub fn create_transient_buffer(&mut self, descriptor: BufferDescriptor) -> Arc<Buffer> {
+        self.compiled.release(); // Invalidate cache
+        let buffer = Arc::new(Buffer::new_transient(descriptor));
+        self.transients.buffers.push(Arc::clone(&buffer));
+        buffer
+    }
+
+    /// Get the transient textures declared on this graph, in creation order.
+    pub fn transient_textures(&self) -> &[Arc<Texture>] {
+        &self.transients.textures
+    }
+
+    /// Get the transient buffers declared on this graph, in creation order.
+    pub fn transient_buffers(&self) -> &[Arc<Buffer>] {
+        &self.transients.buffers
+    }
+
     /// Add a graphics pass to the graph.
     ///
     /// The pass should be fully configured before adding.
@@ -239,6 +310,7 @@ impl RenderGraph {
     /// - Resource usage inference for each pass
     /// - Auto-dependency generation from resource access patterns
     /// - Topological sorting of passes
+    /// - Transient resource lifetime analysis and aliasing
     ///
     /// The result is cached; subsequent calls return the cached result
     /// until the graph is modified.
@@ -250,7 +322,13 @@ impl RenderGraph {
     ) -> Result<&CompiledGraph, GraphError> {
         if !self.compiled.is_active() {
             let target = self.compiled.activate();
-            if let Err(e) = crate::compiler::compile_into(&self.passes, &self.edges, mode, target) {
+            if let Err(e) = crate::compiler::compile_into(
+                &self.passes,
+                &self.edges,
+                &self.transients,
+                mode,
+                target,
+            ) {
                 self.compiled.release();
                 return Err(e);
             }
@@ -281,6 +359,7 @@ impl Poolable for RenderGraph {
     fn reset(&mut self) {
         self.passes.clear();
         self.edges.clear();
+        self.transients.clear();
         self.compiled.release();
     }
 }
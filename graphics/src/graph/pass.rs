@@ -2,8 +2,8 @@
 
 use std::sync::Arc;
 
-use crate::materials::BoundResource;
 use crate::materials::MaterialInstance;
+use crate::materials::{BindingType, BoundResource};
 use crate::mesh::Mesh;
 use crate::resources::Buffer;
 use crate::types::{ScissorRect, Viewport};
@@ -38,6 +38,13 @@ impl Pass {
         }
     }
 
+    /// Whether GPU timing wraps this pass in timestamp queries.
+    ///
+    /// Graphics and compute passes are timed; transfer passes are not.
+    pub fn is_timed(&self) -> bool {
+        matches!(self, Pass::Graphics(_) | Pass::Compute(_))
+    }
+
     /// Get this pass as a graphics pass, if it is one.
     pub fn as_graphics(&self) -> Option<&GraphicsPass> {
         if let Pass::Graphics(p) = self {
@@ -776,11 +783,24 @@ impl GraphicsPass {
         usage
     }
 
-    /// Extract textures from a material instance's bindings.
+    /// Extract textures and writable buffers from a material instance's bindings.
     fn extract_material_textures(material: &MaterialInstance, usage: &mut PassResourceUsage) {
-        for group in material.binding_groups() {
+        let layouts = material.material().binding_layouts();
+        for (group_index, group) in material.binding_groups().iter().enumerate() {
             for entry in &group.entries {
                 match &entry.resource {
+                    BoundResource::Buffer(buffer) => {
+                        let read_write = layouts.get(group_index).is_some_and(|layout| {
+                            layout.entries.iter().any(|e| {
+                                e.binding == entry.binding
+                                    && e.binding_type == BindingType::ReadWriteStorageBuffer
+                            })
+                        });
+                        if read_write {
+                            usage
+                                .add_buffer(Arc::clone(buffer), BufferAccessMode::StorageReadWrite);
+                        }
+                    }
                     BoundResource::Texture(tex) => {
                         usage.add_texture(Arc::clone(tex), TextureAccessMode::ShaderRead);
                     }
@@ -865,6 +885,15 @@ impl TransferPass {
                         usage.add_buffer(Arc::clone(src), BufferAccessMode::TransferRead);
                         usage.add_buffer(Arc::clone(dst), BufferAccessMode::TransferWrite);
                     }
+                    // A blit within one texture (e.g. mipmap generation) moves
+                    // the source mips to the read layout per region itself.
+                    TransferOperation::Blit { src, dst, .. } if Arc::ptr_eq(src, dst) => {
+                        usage.add_texture(Arc::clone(dst), TextureAccessMode::TransferWrite);
+                    }
+                    TransferOperation::Blit { src, dst, .. } => {
+                        usage.add_texture(Arc::clone(src), TextureAccessMode::TransferRead);
+                        usage.add_texture(Arc::clone(dst), TextureAccessMode::TransferWrite);
+                    }
                 }
             }
         }
@@ -990,7 +1019,8 @@ impl ComputePass {
     /// Infer resource usage from the compute pass.
     ///
     /// Examines material bindings in dispatch commands to determine
-    /// which textures and buffers are used.
+    /// which textures are read and which buffers are written (bindings
+    /// declared as [`BindingType::ReadWriteStorageBuffer`]).
     pub fn infer_resource_usage(&self) -> PassResourceUsage {
         let mut usage = PassResourceUsage::new();
 
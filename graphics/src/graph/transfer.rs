@@ -7,11 +7,12 @@
 //! - Texture to texture copies
 //! - Buffer to texture uploads
 //! - Texture to buffer readbacks
+//! - Filtered, scaling texture blits (e.g. for mipmap generation)
 
 use std::sync::Arc;
 
 use crate::resources::{Buffer, Texture};
-use crate::types::Extent3d;
+use crate::types::{Extent3d, FilterMode, TextureDescriptor};
 
 /// A region within a buffer for copy operations.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -123,6 +124,65 @@ impl TextureCopyRegion {
     }
 }
 
+/// A region of a texture to blit.
+///
+/// Unlike a copy, the source and destination regions may differ in size;
+/// the source is scaled to fit using the blit's filter. For array and cube
+/// textures `origin.z` is the first array layer and `depth` the number of
+/// layers, which must match between source and destination.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextureBlitRegion {
+    /// Source mip level and origin.
+    pub src: TextureCopyLocation,
+    /// Size of the source region.
+    pub src_extent: Extent3d,
+    /// Destination mip level and origin.
+    pub dst: TextureCopyLocation,
+    /// Size of the destination region.
+    pub dst_extent: Extent3d,
+}
+
+impl TextureBlitRegion {
+    /// Create a new texture blit region.
+    pub fn new(
+        src: TextureCopyLocation,
+        src_extent: Extent3d,
+        dst: TextureCopyLocation,
+        dst_extent: Extent3d,
+    ) -> Self {
+        Self {
+            src,
+            src_extent,
+            dst,
+            dst_extent,
+        }
+    }
+
+    /// Create a region that scales mip `src_mip` of `src` onto mip `dst_mip`
+    /// of `dst`, covering all array layers.
+    pub fn mips(src: &Texture, src_mip: u32, dst: &Texture, dst_mip: u32) -> Self {
+        Self {
+            src: TextureCopyLocation::mip(src_mip),
+            src_extent: src.descriptor().mip_level_extent(src_mip),
+            dst: TextureCopyLocation::mip(dst_mip),
+            dst_extent: dst.descriptor().mip_level_extent(dst_mip),
+        }
+    }
+
+    /// Create the regions that fill mip levels `1..` of a texture, each
+    /// downsampled from the previous level.
+    pub fn mip_chain(descriptor: &TextureDescriptor) -> Vec<Self> {
+        (1..descriptor.mip_level_count)
+            .map(|level| Self {
+                src: TextureCopyLocation::mip(level - 1),
+                src_extent: descriptor.mip_level_extent(level - 1),
+                dst: TextureCopyLocation::mip(level),
+                dst_extent: descriptor.mip_level_extent(level),
+            })
+            .collect()
+    }
+}
+
 /// Layout of buffer data when copying to/from textures.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub struct BufferTextureLayout {
@@ -238,6 +298,27 @@ pub enum TransferOperation {
         /// Regions to copy.
         regions: Vec<BufferTextureCopyRegion>,
     },
+
+    /// Scale regions of a texture into another, or between mip levels of
+    /// the same texture.
+    ///
+    /// The Vulkan backend records `vkCmdBlitImage`, so both textures need
+    /// `COPY_SRC`/`COPY_DST` usage. The wgpu backend renders a textured quad
+    /// instead, which also needs `TEXTURE_BINDING` on the source and
+    /// `RENDER_ATTACHMENT` on the destination. Compressed formats can't be
+    /// blit destinations. Within one texture, source and destination of a
+    /// region must be different mip levels, and regions run in order so
+    /// later regions may read what earlier ones wrote.
+    Blit {
+        /// Source texture.
+        src: Arc<Texture>,
+        /// Destination texture.
+        dst: Arc<Texture>,
+        /// Regions to blit.
+        regions: Vec<TextureBlitRegion>,
+        /// Filter used when scaling.
+        filter: FilterMode,
+    },
 }
 
 impl TransferOperation {
@@ -314,6 +395,46 @@ impl TransferOperation {
     }
 }
 
+impl TransferOperation {
+    /// Create a texture blit operation.
+    pub fn blit(
+        src: Arc<Texture>,
+        dst: Arc<Texture>,
+        regions: Vec<TextureBlitRegion>,
+        filter: FilterMode,
+    ) -> Self {
+        Self::Blit {
+            src,
+            dst,
+            regions,
+            filter,
+        }
+    }
+
+    /// Create a blit scaling mip 0 of `src` onto mip 0 of `dst`.
+    pub fn blit_whole(src: Arc<Texture>, dst: Arc<Texture>, filter: FilterMode) -> Self {
+        let region = TextureBlitRegion::mips(&src, 0, &dst, 0);
+        Self::Blit {
+            src,
+            dst,
+            regions: vec![region],
+            filter,
+        }
+    }
+
+    /// Create a blit filling mip levels `1..` of `texture` by successively
+    /// downsampling each level from the previous one with linear filtering.
+    pub fn generate_mipmaps(texture: Arc<Texture>) -> Self {
+        let regions = TextureBlitRegion::mip_chain(texture.descriptor());
+        Self::Blit {
+            src: Arc::clone(&texture),
+            dst: texture,
+            regions,
+            filter: FilterMode::Linear,
+        }
+    }
+}
+
 /// Configuration for a transfer pass.
 #[derive(Debug, Clone, Default)]
 pub struct TransferConfig {
@@ -474,6 +595,72 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_blit_whole_scales_mip_zero() {
+        let instance = GraphicsInstance::new().unwrap();
+        let device = instance.create_device().unwrap();
+        let usage = TextureUsage::COPY_SRC | TextureUsage::COPY_DST;
+        let src = device
+            .create_texture(&TextureDescriptor::new_2d(
+                256,
+                128,
+                TextureFormat::Rgba8Unorm,
+                usage,
+            ))
+            .unwrap();
+        let dst = device
+            .create_texture(&TextureDescriptor::new_2d(
+                64,
+                64,
+                TextureFormat::Rgba8Unorm,
+                usage,
+            ))
+            .unwrap();
+
+        match TransferOperation::blit_whole(src, dst, FilterMode::Nearest) {
+            TransferOperation::Blit {
+                regions, filter, ..
+            } => {
+                assert_eq!(filter, FilterMode::Nearest);
+                assert_eq!(regions.len(), 1);
+                assert_eq!(regions[0].src_extent, Extent3d::new_2d(256, 128));
+                assert_eq!(regions[0].dst_extent, Extent3d::new_2d(64, 64));
+            }
+            _ => panic!("Expected Blit"),
+        }
+    }
+
+    #[test]
+    fn test_generate_mipmaps_regions() {
+        let instance = GraphicsInstance::new().unwrap();
+        let device = instance.create_device().unwrap();
+        let texture = device
+            .create_texture(
+                &TextureDescriptor::new_cube(
+                    16,
+                    TextureFormat::Rgba8Unorm,
+                    TextureUsage::COPY_SRC | TextureUsage::COPY_DST,
+                )
+                .with_full_mip_chain(),
+            )
+            .unwrap();
+        assert_eq!(texture.mip_level_count(), 5);
+
+        match TransferOperation::generate_mipmaps(texture) {
+            TransferOperation::Blit {
+                src, dst, regions, ..
+            } => {
+                assert!(Arc::ptr_eq(&src, &dst));
+                assert_eq!(regions.len(), 4);
+                let last = regions[3];
+                assert_eq!((last.src.mip_level, last.dst.mip_level), (3, 4));
+                assert_eq!(last.src_extent, Extent3d::new_3d(2, 2, 6));
+                assert_eq!(last.dst_extent, Extent3d::new_3d(1, 1, 6));
+            }
+            _ => panic!("Expected Blit"),
+        }
+    }
+
     #[test]
     fn test_texture_to_buffer_readback() {
         let (_, dst_buf, src_tex, _) = create_test_resources();
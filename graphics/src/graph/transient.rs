@@ -0,0 +1,346 @@
+//! Transient render graph resources.
+//!
+//! Transient textures and buffers are declared on a [`RenderGraph`](super::RenderGraph)
+//! with only a descriptor. They exist for a single frame and have no memory of
+//! their own until the graph is submitted:
+//!
+//! 1. The [compiler](crate::compiler) computes each transient's lifetime (first
+//!    and last pass in execution order) and assigns transients with compatible
+//!    descriptors and non-overlapping lifetimes to the same physical slot.
+//! 2. On submission, the [`FrameSchedule`](crate::scheduler::FrameSchedule)
+//!    binds every slot to a physical resource from the frame slot's
+//!    [`TransientResourcePool`], creating one only if the pool has no match.
+//!
+//! Pools live in [`FramePipeline`](crate::pipeline::FramePipeline), one per
+//! frame slot, so physical resources are reused across frames without ever
+//! being shared with a frame the GPU may still be executing.
+//!
+//! # Contents
+//!
+//! Aliased transients share memory, so the contents of a transient are
+//! undefined at its first use. The first pass that touches a transient should
+//! clear it or fully overwrite it.
+
+use std::sync::Arc;
+
+use crate::compiler::CompiledGraph;
+use crate::device::GraphicsDevice;
+use crate::error::GraphicsError;
+use crate::resources::{Buffer, Texture};
+use crate::types::{BufferDescriptor, TextureDescriptor};
+
+/// Number of frames a pooled resource may stay unused before it is released.
+const MAX_IDLE_FRAMES: u32 = 8;
+
+/// Transient resources declared on a render graph.
+///
+/// Indices into these lists match the allocation plan in
+/// [`CompiledGraph::transient_textures`] and [`CompiledGraph::transient_buffers`].
+#[derive(Debug, Default)]
+pub(crate) struct TransientResources {
+    /// Unbound transient textures, in creation order.
+    pub textures: Vec<Arc<Texture>>,
+    /// Unbound transient buffers, in creation order.
+    pub buffers: Vec<Arc<Buffer>>,
+}
+
+impl TransientResources {
+    /// Remove all declared transients.
+    pub fn clear(&mut self) {
+        self.textures.clear();
+        self.buffers.clear();
+    }
+}
+
+/// Check whether two texture descriptors describe interchangeable memory.
+///
+/// Labels are ignored; everything else must match.
+pub(crate) fn textures_compatible(a: &TextureDescriptor, b: &TextureDescriptor) -> bool {
+    a.size == b.size
+        && a.mip_level_count == b.mip_level_count
+        && a.sample_count == b.sample_count
+        && a.dimension == b.dimension
+        && a.format == b.format
+        && a.usage == b.usage
+}
+
+/// A physical texture owned by a [`TransientResourcePool`].
+#[derive(Debug)]
+struct PooledTexture {
+    texture: Arc<Texture>,
+    in_use: bool,
+    idle_frames: u32,
+}
+
+/// A physical buffer owned by a [`TransientResourcePool`].
+#[derive(Debug)]
+struct PooledBuffer {
+    buffer: Arc<Buffer>,
+    in_use: bool,
+    idle_frames: u32,
+}
+
+/// Pool of physical resources backing transient render graph resources.
+///
+/// One pool exists per frame slot of a [`FramePipeline`](crate::pipeline::FramePipeline).
+/// Resources handed out during a frame stay reserved until the slot comes
+/// around again, after its fence has been waited on.
+#[derive(Debug, Default)]
+pub(crate) struct TransientResourcePool {
+    textures: Vec<PooledTexture>,
+    buffers: Vec<PooledBuffer>,
+}
+
+impl TransientResourcePool {
+    /// Release all reservations at the start of a frame.
+    ///
+    /// Must only be called after the frame slot's fence has been waited on.
+    /// Resources unused for more than [`MAX_IDLE_FRAMES`] frames are dropped.
+    pub fn reset(&mut self) {
+        for entry in &mut self.textures {
+            entry.idle_frames = if entry.in_use {
+                0
+            } else {
+                entry.idle_frames + 1
+            };
+            entry.in_use = false;
+        }
+        for entry in &mut self.buffers {
+            entry.idle_frames = if entry.in_use {
+                0
+            } else {
+                entry.idle_frames + 1
+            };
+            entry.in_use = false;
+        }
+        self.textures.retain(|e| e.idle_frames <= MAX_IDLE_FRAMES);
+        self.buffers.retain(|e| e.idle_frames <= MAX_IDLE_FRAMES);
+    }
+
+    /// Number of physical textures owned by the pool.
+    pub fn texture_count(&self) -> usize {
+        self.textures.len()
+    }
+
+    /// Number of physical buffers owned by the pool.
+    pub fn buffer_count(&self) -> usize {
+        self.buffers.len()
+    }
+
+    /// Bind the transients of a compiled graph to physical resources.
+    ///
+    /// Each physical slot of the allocation plan is backed by a pooled resource
+    /// reserved for the rest of the frame. Transients that are already bound
+    /// (e.g. a graph submitted twice) are left untouched.
+    pub fn bind(
+        &mut self,
+        device: &Arc<GraphicsDevice>,
+        transients: &TransientResources,
+        compiled: &CompiledGraph,
+    ) -> Result<(), GraphicsError> {
+        let texture_slots = compiled
+            .transient_texture_slots()
+            .iter()
+            .map(|descriptor| self.acquire_texture(device, descriptor))
+            .collect::<Result<Vec<_>, _>>()?;
+        for (texture, allocation) in transients
+            .textures
+            .iter()
+            .zip(compiled.transient_textures())
+        {
+            if let Some(allocation) = allocation {
+                texture.bind_transient(Arc::clone(&texture_slots[allocation.slot]));
+            }
+        }
+
+        let buffer_slots = compiled
+            .transient_buffer_slots()
+            .iter()
+            .map(|descriptor| self.acquire_buffer(device, descriptor))
+            .collect::<Result<Vec<_>, _>>()?;
+        for (buffer, allocation) in transients.buffers.iter().zip(compiled.transient_buffers()) {
+            if let Some(allocation) = allocation {
+                buffer.bind_transient(Arc::clone(&buffer_slots[allocation.slot]));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn acquire_texture(
+        &mut self,
+        device: &Arc<GraphicsDevice>,
+        descriptor: &TextureDescriptor,
+    ) -> Result<Arc<Texture>, GraphicsError> {
+        if let Some(entry) = self
+            .textures
+            .iter_mut()
+            .find(|e| !e.in_use && textures_compatible(e.texture.descriptor(), descriptor))
+        {
+            entry.in_use = true;
+            return Ok(Arc::clone(&entry.texture));
+        }
+
+        let texture = device.create_texture(descriptor)?;
+        self.textures.push(PooledTexture {
+            texture: Arc::clone(&texture),
+            in_use: true,
+            idle_frames: 0,
+        });
+        Ok(texture)
+    }
+
+    fn acquire_buffer(
+        &mut self,
+        device: &Arc<GraphicsDevice>,
+        descriptor: &BufferDescriptor,
+    ) -> Result<Arc<Buffer>, GraphicsError> {
+        // Best fit: the smallest free buffer with matching usage that is large enough
+        if let Some(entry) = self
+            .buffers
+            .iter_mut()
+            .filter(|e| {
+                !e.in_use
+                    && e.buffer.descriptor().usage == descriptor.usage
+                    && e.buffer.size() >= descriptor.size
+            })
+            .min_by_key(|e| e.buffer.size())
+        {
+            entry.in_use = true;
+            return Ok(Arc::clone(&entry.buffer));
+        }
+
+        let buffer = device.create_buffer(descriptor)?;
+        self.buffers.push(PooledBuffer {
+            buffer: Arc::clone(&buffer),
+            in_use: true,
+            idle_frames: 0,
+        });
+        Ok(buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ColorAttachment, GraphicsPass, RenderGraph, RenderTargetConfig};
+    use crate::instance::GraphicsInstance;
+    use crate::types::{BufferUsage, TextureFormat, TextureUsage};
+
+    use crate::compiler::RenderGraphCompilationMode::Automatic;
+
+    fn create_test_device() -> Arc<GraphicsDevice> {
+        let instance = GraphicsInstance::new().unwrap();
+        instance.create_device().unwrap()
+    }
+
+    fn target_descriptor() -> TextureDescriptor {
+        TextureDescriptor::new_2d(
+            64,
+            64,
+            TextureFormat::Rgba8Unorm,
+            TextureUsage::RENDER_ATTACHMENT | TextureUsage::TEXTURE_BINDING,
+        )
+    }
+
+    fn render_to(texture: &Arc<Texture>, name: &str) -> GraphicsPass {
+        let mut pass = GraphicsPass::new(name.into());
+        pass.set_render_targets(
+            RenderTargetConfig::new().with_color(ColorAttachment::from_texture(texture.clone())),
+        );
+        pass
+    }
+
+    #[test]
+    fn test_bind_transients() {
+        let device = create_test_device();
+        let mut graph = RenderGraph::new();
+        let a = graph.create_transient_texture(target_descriptor());
+        let b = graph.create_transient_texture(target_descriptor());
+        let first = graph.add_graphics_pass(render_to(&a, "a"));
+        let second = graph.add_graphics_pass(render_to(&b, "b"));
+        graph.add_dependency(second, first);
+        graph.compile(Automatic).unwrap();
+
+        let mut pool = TransientResourcePool::default();
+        pool.bind(&device, &graph.transients, graph.compiled().unwrap())
+            .unwrap();
+
+        // Lifetimes don't overlap, so both share one physical texture
+        assert_eq!(pool.texture_count(), 1);
+        assert!(Arc::ptr_eq(
+            a.transient_backing().unwrap(),
+            b.transient_backing().unwrap()
+        ));
+        assert_eq!(a.width(), 64);
+    }
+
+    #[test]
+    fn test_pool_reuse_across_frames() {
+        let device = create_test_device();
+        let mut pool = TransientResourcePool::default();
+
+        for _ in 0..3 {
+            pool.reset();
+            let mut graph = RenderGraph::new();
+            let texture = graph.create_transient_texture(target_descriptor());
+            graph.add_graphics_pass(render_to(&texture, "main"));
+            graph.compile(Automatic).unwrap();
+            pool.bind(&device, &graph.transients, graph.compiled().unwrap())
+                .unwrap();
+        }
+
+        assert_eq!(pool.texture_count(), 1);
+    }
+
+    #[test]
+    fn test_pool_reserves_within_frame() {
+        let device = create_test_device();
+        let mut pool = TransientResourcePool::default();
+
+        // Two graphs in the same frame must not share memory
+        for name in ["first", "second"] {
+            let mut graph = RenderGraph::new();
+            let texture = graph.create_transient_texture(target_descriptor());
+            graph.add_graphics_pass(render_to(&texture, name));
+            graph.compile(Automatic).unwrap();
+            pool.bind(&device, &graph.transients, graph.compiled().unwrap())
+                .unwrap();
+        }
+
+        assert_eq!(pool.texture_count(), 2);
+    }
+
+    #[test]
+    fn test_pool_releases_idle_resources() {
+        let device = create_test_device();
+        let mut pool = TransientResourcePool::default();
+        pool.acquire_buffer(&device, &BufferDescriptor::new(256, BufferUsage::STORAGE))
+            .unwrap();
+        assert_eq!(pool.buffer_count(), 1);
+
+        for _ in 0..=MAX_IDLE_FRAMES + 1 {
+            pool.reset();
+        }
+        assert_eq!(pool.buffer_count(), 0);
+    }
+
+    #[test]
+    fn test_buffer_best_fit() {
+        let device = create_test_device();
+        let mut pool = TransientResourcePool::default();
+        let large = pool
+            .acquire_buffer(&device, &BufferDescriptor::new(4096, BufferUsage::STORAGE))
+            .unwrap();
+        let small = pool
+            .acquire_buffer(&device, &BufferDescriptor::new(512, BufferUsage::STORAGE))
+            .unwrap();
+        pool.reset();
+
+        let reused = pool
+            .acquire_buffer(&device, &BufferDescriptor::new(256, BufferUsage::STORAGE))
+            .unwrap();
+        assert!(Arc::ptr_eq(&reused, &small));
+        assert!(!Arc::ptr_eq(&reused, &large));
+    }
+}
@@ -24,6 +24,8 @@ pub enum BackendType {
     Auto,
     /// Use the dummy backend (no actual GPU operations).
     Dummy,
+    /// Use the software rasterizer backend (CPU rendering, no GPU required).
+    Software,
     /// Use the wgpu backend (cross-platform via wgpu).
     Wgpu,
     /// Use the native Vulkan backend (via ash).
@@ -85,8 +85,8 @@ pub use graph::{
     BufferCopyRegion, BufferTextureCopyRegion, BufferTextureLayout, ColorAttachment, CompiledGraph,
     ComputePass, DepthStencilAttachment, DrawCommand, GraphError, GraphicsPass,
     IndirectDrawCommand, LoadOp, Pass, PassHandle, RenderGraph, RenderGraphCompilationMode,
-    RenderTarget, RenderTargetConfig, StoreOp, TextureCopyLocation, TextureCopyRegion,
-    TextureOrigin, TransferConfig, TransferOperation, TransferPass,
+    RenderTarget, RenderTargetConfig, StoreOp, TextureBlitRegion, TextureCopyLocation,
+    TextureCopyRegion, TextureOrigin, TransferConfig, TransferOperation, TransferPass,
     resource_usage::{PassResourceUsage, TextureAccessMode, TextureUsageDecl},
 };
 pub use instance::{
@@ -102,11 +102,11 @@ pub use mesh::{
     VertexAttributeFormat, VertexAttributeSemantic, VertexBufferLayout, VertexLayout,
     VertexStepMode,
 };
-pub use pipeline::FramePipeline;
+pub use pipeline::{FramePipeline, GpuPassTiming, GpuTimingReport};
 pub use resize::{ResizeEvent, ResizeManager, ResizeStrategy};
 pub use resources::{Buffer, RingAllocation, RingBuffer, Sampler, Texture};
 pub use scheduler::{Fence, FenceStatus, FrameSchedule, GraphHandle, Semaphore};
-pub use shader::ShaderLibrary;
+pub use shader::{ShaderCache, ShaderCacheKey, ShaderLibrary, ShaderTarget};
 pub use swapchain::{PresentMode, Surface, SurfaceConfiguration, SurfaceTexture};
 pub use types::{
     AddressMode, BufferDescriptor, BufferUsage, ClearValue, CompareFunction, CpuSampler,
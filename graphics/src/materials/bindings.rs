@@ -13,6 +13,9 @@ pub enum BindingType {
     /// Storage buffer (read-write, larger data).
     StorageBuffer,
 
+    /// Read-write storage buffer (e.g. compute shader output).
+    ReadWriteStorageBuffer,
+
     /// Sampled texture (for reading in shaders).
     Texture,
 
@@ -22,9 +25,15 @@ pub enum BindingType {
     /// 2D array texture (for sampling texture arrays in shaders).
     Texture2DArray,
 
+    /// Depth texture (for comparison sampling, e.g. shadow maps).
+    DepthTexture,
+
     /// Texture sampler.
     Sampler,
 
+    /// Comparison sampler (for depth comparison lookups).
+    ComparisonSampler,
+
     /// Combined texture and sampler.
     CombinedTextureSampler,
 }
@@ -115,6 +124,19 @@ impl BindingLayout {
         self.with_entry(BindingLayoutEntry::new(binding, BindingType::UniformBuffer))
     }
 
+    /// Add a read-only storage buffer binding.
+    pub fn with_storage_buffer(self, binding: u32) -> Self {
+        self.with_entry(BindingLayoutEntry::new(binding, BindingType::StorageBuffer))
+    }
+
+    /// Add a read-write storage buffer binding.
+    pub fn with_read_write_storage_buffer(self, binding: u32) -> Self {
+        self.with_entry(BindingLayoutEntry::new(
+            binding,
+            BindingType::ReadWriteStorageBuffer,
+        ))
+    }
+
     /// Add a texture binding.
     pub fn with_texture(self, binding: u32) -> Self {
         self.with_entry(BindingLayoutEntry::new(binding, BindingType::Texture))
@@ -133,11 +155,24 @@ impl BindingLayout {
         ))
     }
 
+    /// Add a depth texture binding.
+    pub fn with_depth_texture(self, binding: u32) -> Self {
+        self.with_entry(BindingLayoutEntry::new(binding, BindingType::DepthTexture))
+    }
+
     /// Add a sampler binding.
     pub fn with_sampler(self, binding: u32) -> Self {
         self.with_entry(BindingLayoutEntry::new(binding, BindingType::Sampler))
     }
 
+    /// Add a comparison sampler binding.
+    pub fn with_comparison_sampler(self, binding: u32) -> Self {
+        self.with_entry(BindingLayoutEntry::new(
+            binding,
+            BindingType::ComparisonSampler,
+        ))
+    }
+
     /// Add a combined texture+sampler binding.
     pub fn with_combined_texture_sampler(self, binding: u32) -> Self {
         self.with_entry(BindingLayoutEntry::new(
@@ -183,4 +218,17 @@ mod tests {
         assert_eq!(entry.visibility, ShaderStageFlags::VERTEX);
         assert!(!entry.visibility.contains(ShaderStageFlags::FRAGMENT));
     }
+
+    #[test]
+    fn test_storage_buffer_builders() {
+        let layout = BindingLayout::new()
+            .with_storage_buffer(0)
+            .with_read_write_storage_buffer(1);
+
+        assert_eq!(layout.entries[0].binding_type, BindingType::StorageBuffer);
+        assert_eq!(
+            layout.entries[1].binding_type,
+            BindingType::ReadWriteStorageBuffer
+        );
+    }
 }
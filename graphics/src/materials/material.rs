@@ -9,6 +9,8 @@
 use std::sync::Arc;
 
 use crate::backend::GpuPipeline;
+#[cfg(feature = "software-backend")]
+use crate::backend::software::SoftwareShader;
 use crate::device::GraphicsDevice;
 use crate::mesh::VertexLayout;
 use crate::types::TextureFormat;
@@ -268,8 +270,20 @@ pub struct MaterialDescriptor {
     /// Depth attachment format, if any.
     pub depth_format: Option<TextureFormat>,
 
+    /// Whether draws write depth (they are depth tested either way).
+    /// Blended materials usually disable it so surfaces behind them still
+    /// draw. Defaults to `true`.
+    pub depth_write: bool,
+
     /// Optional label for debugging.
     pub label: Option<String>,
+
+    /// CPU shader used by the software backend in place of `shaders`.
+    ///
+    /// If `None`, the software backend uses
+    /// [`PassthroughShader`](crate::backend::software::PassthroughShader).
+    #[cfg(feature = "software-backend")]
+    pub software_shader: Option<Arc<dyn SoftwareShader>>,
 }
 
 impl Default for MaterialDescriptor {
@@ -283,7 +297,10 @@ impl Default for MaterialDescriptor {
             polygon_mode: PolygonMode::Fill,
             color_formats: Vec::new(),
             depth_format: None,
+            depth_write: true,
             label: None,
+            #[cfg(feature = "software-backend")]
+            software_shader: None,
         }
     }
 }
@@ -342,11 +359,24 @@ impl MaterialDescriptor {
         self
     }
 
+    /// Enable or disable depth writes.
+    pub fn with_depth_write(mut self, depth_write: bool) -> Self {
+        self.depth_write = depth_write;
+        self
+    }
+
     /// Set a debug label.
     pub fn with_label(mut self, label: impl Into<String>) -> Self {
         self.label = Some(label.into());
         self
     }
+
+    /// Set the CPU shader used by the software backend.
+    #[cfg(feature = "software-backend")]
+    pub fn with_software_shader(mut self, shader: Arc<dyn SoftwareShader>) -> Self {
+        self.software_shader = Some(shader);
+        self
+    }
 }
 
 /// A material defines the shader and binding layout for rendering.
@@ -427,6 +457,11 @@ impl Material {
         self.descriptor.blend_state.as_ref()
     }
 
+    /// Whether draws with this material write depth.
+    pub fn depth_write(&self) -> bool {
+        self.descriptor.depth_write
+    }
+
     /// Get the primitive topology.
     pub fn topology(&self) -> PrimitiveTopology {
         self.descriptor.topology
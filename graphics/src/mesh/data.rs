@@ -179,6 +179,31 @@ impl Mesh {
             PrimitiveTopology::TriangleStrip => count.saturating_sub(2),
         }
     }
+
+    /// Create a mesh sharing this mesh's buffers, with `instance_buffer`
+    /// bound as an extra per-instance buffer holding a model matrix per
+    /// instance (see [`VertexLayout::instanced`]).
+    ///
+    /// Draws pick their instances with
+    /// [`DrawCommand::with_first_instance`](crate::graph::DrawCommand::with_first_instance),
+    /// so one instance buffer (e.g. a ring buffer) can serve many draws.
+    pub fn with_instance_buffer(&self, instance_buffer: Arc<Buffer>) -> Mesh {
+        let mut vertex_buffers = self.vertex_buffers.clone();
+        vertex_buffers.push(instance_buffer);
+        Mesh::new(
+            Arc::clone(&self.device),
+            Arc::new(self.layout.instanced()),
+            self.topology,
+            vertex_buffers,
+            self.vertex_count,
+            self.index_buffer.clone(),
+            self.index_format,
+            self.index_count,
+            self.label
+                .as_ref()
+                .map(|label| format!("{label}_instanced")),
+        )
+    }
 }
 
 impl std::fmt::Debug for Mesh {
@@ -0,0 +1,345 @@
+//! GPU pass timing with timestamp queries.
+//!
+//! When enabled with [`FramePipeline::enable_gpu_timing`](super::FramePipeline::enable_gpu_timing),
+//! every graphics and compute pass is wrapped in two timestamp queries. Each
+//! frame slot owns a query pool; its readback starts when the frame ends and
+//! is collected when the slot is reused, after its fence was waited on, so a
+//! [`GpuTimingReport`] describes the frame submitted `frames_in_flight`
+//! frames earlier.
+
+use std::fmt;
+use std::time::Duration;
+
+use crate::backend::{GpuBackend, GpuTimestampPool, GpuTimestampWrites};
+use crate::graph::{CompiledGraph, RenderGraph};
+
+/// GPU timing of all timed passes of one frame.
+#[derive(Debug, Clone, Default)]
+pub struct GpuTimingReport {
+    /// Frame number (see [`FramePipeline::frame_count`](super::FramePipeline::frame_count)).
+    pub frame: u64,
+    /// Time from the start of the first timed pass to the end of the last.
+    pub total: Duration,
+    /// Per-pass timing in submission order.
+    pub passes: Vec<GpuPassTiming>,
+}
+
+/// GPU timing of a single pass.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GpuPassTiming {
+    /// Name the pass's graph was submitted with.
+    pub graph: String,
+    /// Pass name.
+    pub pass: String,
+    /// Start of the pass, relative to the start of the frame's first pass.
+    pub start: Duration,
+    /// Execution duration on the GPU.
+    pub duration: Duration,
+}
+
+impl GpuTimingReport {
+    /// Build a report from the raw timestamps (in ticks of `period`
+    /// nanoseconds) written for `passes`.
+    ///
+    /// Passes whose timestamps were not written (e.g. passes the backend
+    /// skipped) are left out.
+    pub(crate) fn from_timestamps(
+        frame: u64,
+        passes: &[TimedPass],
+        timestamps: &[u64],
+        period: f32,
+    ) -> Self {
+        let ticks = |index: u32| timestamps.get(index as usize).copied().unwrap_or(0);
+        let written: Vec<(&TimedPass, u64, u64)> = passes
+            .iter()
+            .map(|pass| (pass, ticks(pass.query), ticks(pass.query + 1)))
+            .filter(|&(_, start, end)| start != 0 && end >= start)
+            .collect();
+
+        let first = written
+            .iter()
+            .map(|&(_, start, _)| start)
+            .min()
+            .unwrap_or(0);
+        let last = written.iter().map(|&(_, _, end)| end).max().unwrap_or(0);
+        let to_duration =
+            |ticks: u64| Duration::from_nanos((ticks as f64 * f64::from(period)) as u64);
+
+        Self {
+            frame,
+            total: to_duration(last - first),
+            passes: written
+                .into_iter()
+                .map(|(pass, start, end)| GpuPassTiming {
+                    graph: pass.graph.clone(),
+                    pass: pass.pass.clone(),
+                    start: to_duration(start - first),
+                    duration: to_duration(end - start),
+                })
+                .collect(),
+        }
+    }
+
+    /// Timing of the pass `pass` of the graph submitted as `graph`.
+    pub fn pass(&self, graph: &str, pass: &str) -> Option<&GpuPassTiming> {
+        self.passes
+            .iter()
+            .find(|timing| timing.graph == graph && timing.pass == pass)
+    }
+
+    /// Sum of the durations of the passes of the graph submitted as `graph`.
+    pub fn graph_duration(&self, graph: &str) -> Duration {
+        self.passes
+            .iter()
+            .filter(|timing| timing.graph == graph)
+            .map(|timing| timing.duration)
+            .sum()
+    }
+}
+
+impl fmt::Display for GpuTimingReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "GPU frame {}: {:.2?}", self.frame, self.total)?;
+        for timing in &self.passes {
+            writeln!(
+                f,
+                "  {}/{}: {:.2?}",
+                timing.graph, timing.pass, timing.duration
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// A pass whose start and end timestamps are written to `query` and
+/// `query + 1`.
+#[derive(Debug, Clone)]
+pub(crate) struct TimedPass {
+    pub graph: String,
+    pub pass: String,
+    pub query: u32,
+}
+
+/// Timestamp queries of one frame slot.
+///
+/// Moved into the [`FrameSchedule`](crate::scheduler::FrameSchedule) for the
+/// frame, which reserves queries for each graph it executes.
+pub(crate) struct FrameTimestamps {
+    pool: GpuTimestampPool,
+    frame: u64,
+    next_query: u32,
+    passes: Vec<TimedPass>,
+}
+
+impl FrameTimestamps {
+    pub fn new(pool: GpuTimestampPool) -> Self {
+        Self {
+            pool,
+            frame: 0,
+            next_query: 0,
+            passes: Vec::new(),
+        }
+    }
+
+    /// Reserve queries for the timed passes of `graph`, submitted as
+    /// `graph_name`.
+    ///
+    /// Returns `None` if the graph has no timed passes or the pool is full;
+    /// the graph then runs untimed.
+    pub fn reserve(
+        &mut self,
+        graph_name: &str,
+        graph: &RenderGraph,
+        compiled: &CompiledGraph,
+    ) -> Option<GpuTimestampWrites<'_>> {
+        let passes = graph.passes();
+        let timed = compiled
+            .pass_order()
+            .iter()
+            .map(|handle| &passes[handle.index()])
+            .filter(|pass| pass.is_timed());
+        let query_count = 2 * timed.clone().count() as u32;
+        if query_count == 0 || self.next_query + query_count > self.pool.capacity() {
+            return None;
+        }
+
+        let first_query = self.next_query;
+        for (index, pass) in timed.enumerate() {
+            self.passes.push(TimedPass {
+                graph: graph_name.to_owned(),
+                pass: pass.name().to_owned(),
+                query: first_query + 2 * index as u32,
+            });
+        }
+        self.next_query += query_count;
+        Some(GpuTimestampWrites {
+            pool: &self.pool,
+            first_query,
+            query_count,
+        })
+    }
+
+    /// Start reading back the queries written this frame.
+    ///
+    /// Called once the frame's graphs have all been submitted.
+    pub fn map(&self, backend: &GpuBackend) {
+        if !self.passes.is_empty() {
+            backend.map_timestamps(&self.pool, self.next_query);
+        }
+    }
+
+    /// Read back the queries written during the slot's last frame and start
+    /// recording `frame`.
+    ///
+    /// Must only be called after the slot's fence was waited on. Returns
+    /// `None` if no pass was timed. With the `profiling` feature the passes
+    /// are also emitted as zones of the Tracy GPU context `tracy`, created on
+    /// first use.
+    pub fn resolve(
+        &mut self,
+        backend: &GpuBackend,
+        frame: u64,
+        #[cfg(feature = "profiling")] tracy: &mut Option<tracy_client::GpuContext>,
+    ) -> Option<GpuTimingReport> {
+        let passes = std::mem::take(&mut self.passes);
+        let count = std::mem::take(&mut self.next_query);
+        let previous_frame = std::mem::replace(&mut self.frame, frame);
+        if passes.is_empty() {
+            return None;
+        }
+
+        let timestamps = backend.read_timestamps(&self.pool, count);
+        let period = backend.timestamp_period();
+        #[cfg(feature = "profiling")]
+        send_to_tracy(tracy, &passes, &timestamps, period);
+        Some(GpuTimingReport::from_timestamps(
+            previous_frame,
+            &passes,
+            &timestamps,
+            period,
+        ))
+    }
+}
+
+/// Emit the passes as Tracy GPU zones.
+#[cfg(feature = "profiling")]
+fn send_to_tracy(
+    tracy: &mut Option<tracy_client::GpuContext>,
+    passes: &[TimedPass],
+    timestamps: &[u64],
+    period: f32,
+) {
+    let Some(first) = timestamps.iter().copied().find(|&ticks| ticks != 0) else {
+        return;
+    };
+    if tracy.is_none() {
+        *tracy = tracy_client::Client::running().and_then(|client| {
+            client
+                .new_gpu_context(
+                    Some("GPU"),
+                    tracy_client::GpuContextType::Invalid,
+                    first as i64,
+                    period,
+                )
+                .ok()
+        });
+    }
+    let Some(context) = tracy.as_ref() else {
+        return;
+    };
+
+    for pass in passes {
+        let start = timestamps.get(pass.query as usize).copied().unwrap_or(0);
+        let end = timestamps
+            .get(pass.query as usize + 1)
+            .copied()
+            .unwrap_or(0);
+        if start == 0 || end < start {
+            continue;
+        }
+        let name = format!("{}/{}", pass.graph, pass.pass);
+        if let Ok(mut span) = context.span_alloc(&name, &pass.graph, file!(), line!()) {
+            span.end_zone();
+            span.upload_timestamp_start(start as i64);
+            span.upload_timestamp_end(end as i64);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::GraphicsPass;
+    use crate::instance::{BackendType, GraphicsInstance, InstanceParameters};
+    use crate::pipeline::FramePipeline;
+
+    fn timed(graph: &str, pass: &str, query: u32) -> TimedPass {
+        TimedPass {
+            graph: graph.to_owned(),
+            pass: pass.to_owned(),
+            query,
+        }
+    }
+
+    #[test]
+    fn report_converts_ticks_to_durations() {
+        let passes = [
+            timed("shadows", "cascade", 0),
+            timed("camera", "forward", 2),
+        ];
+        let timestamps = [100, 150, 200, 500];
+        let report = GpuTimingReport::from_timestamps(7, &passes, &timestamps, 2.0);
+
+        assert_eq!(report.frame, 7);
+        assert_eq!(report.total, Duration::from_nanos(800));
+        let forward = report.pass("camera", "forward").unwrap();
+        assert_eq!(forward.start, Duration::from_nanos(200));
+        assert_eq!(forward.duration, Duration::from_nanos(600));
+        assert_eq!(report.graph_duration("shadows"), Duration::from_nanos(100));
+    }
+
+    #[test]
+    fn report_skips_unwritten_passes() {
+        let passes = [timed("camera", "empty", 0), timed("camera", "forward", 2)];
+        let timestamps = [0, 0, 40, 90];
+        let report = GpuTimingReport::from_timestamps(1, &passes, &timestamps, 1.0);
+
+        assert_eq!(report.passes.len(), 1);
+        assert!(report.pass("camera", "empty").is_none());
+        assert_eq!(report.total, Duration::from_nanos(50));
+    }
+
+    #[test]
+    fn empty_report_has_zero_total() {
+        let report = GpuTimingReport::from_timestamps(3, &[], &[], 1.0);
+        assert!(report.passes.is_empty());
+        assert_eq!(report.total, Duration::ZERO);
+    }
+
+    #[test]
+    fn report_appears_when_slot_is_reused() {
+        let params = InstanceParameters::new().with_backend(BackendType::Dummy);
+        let device = GraphicsInstance::with_parameters(params)
+            .unwrap()
+            .create_device()
+            .unwrap();
+        let mut pipeline = FramePipeline::new(device, 2);
+        assert!(pipeline.enable_gpu_timing(4));
+
+        let mut frames = Vec::new();
+        let mut reports = Vec::new();
+        for _ in 0..3 {
+            let mut schedule = pipeline.begin_frame();
+            frames.push(pipeline.frame_count());
+            reports.push(pipeline.gpu_timing_report().map(|report| report.frame));
+            let mut graph = RenderGraph::new();
+            graph.add_graphics_pass(GraphicsPass::new("forward".into()));
+            let main = schedule.submit("main", graph, &[]);
+            schedule.finish(&[main]);
+            pipeline.end_frame(schedule);
+        }
+
+        // Frame 0's slot is first reused by frame 2.
+        assert_eq!(reports, vec![None, None, Some(frames[0])]);
+    }
+}
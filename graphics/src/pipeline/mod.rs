@@ -59,6 +59,8 @@
 //!
 //! More frames = higher throughput but more input latency and memory usage.
 
+mod gpu_timing;
+
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -67,11 +69,15 @@ use redlilium_core::pool::Poolable;
 use crate::device::GraphicsDevice;
 use crate::error::GraphicsError;
 use crate::graph::RenderGraph;
+use crate::graph::transient::TransientResourcePool;
 use crate::resources::RingBuffer;
 use crate::scheduler::{Fence, FrameSchedule, SubmittedGraph};
 use crate::types::BufferUsage;
 use redlilium_core::profiling::{frame_mark, profile_scope};
 
+pub(crate) use gpu_timing::FrameTimestamps;
+pub use gpu_timing::{GpuPassTiming, GpuTimingReport};
+
 /// Manages multiple frames in flight for CPU-GPU parallelism.
 ///
 /// `FramePipeline` coordinates the overlap between CPU frame preparation and
@@ -133,6 +139,21 @@ pub struct FramePipeline {
 
     /// Per-slot submitted metadata (keeps semaphores alive until fence wait).
     slot_submitted: Vec<Vec<SubmittedGraph>>,
+
+    /// Per-slot pools of physical memory for transient graph resources.
+    /// Temporarily moved to FrameSchedule during a frame.
+    transient_pools: Vec<TransientResourcePool>,
+
+    /// Per-slot timestamp queries for GPU pass timing. Empty if disabled,
+    /// `None` while moved to FrameSchedule during a frame.
+    slot_timestamps: Vec<Option<FrameTimestamps>>,
+
+    /// Most recently resolved GPU timing.
+    gpu_timing_report: Option<GpuTimingReport>,
+
+    /// Tracy GPU context receiving the pass timings.
+    #[cfg(feature = "profiling")]
+    tracy_gpu_context: Option<tracy_client::GpuContext>,
 }
 
 impl std::fmt::Debug for FramePipeline {
@@ -144,6 +165,7 @@ impl std::fmt::Debug for FramePipeline {
             .field("frames_in_flight", &self.frames_in_flight)
             .field("frame_count", &self.frame_count)
             .field("has_ring_buffers", &self.has_ring_buffers())
+            .field("gpu_timing", &self.is_gpu_timing_enabled())
             .finish()
     }
 }
@@ -175,6 +197,13 @@ impl FramePipeline {
             graph_pool: Vec::new(),
             slot_graphs: (0..frames_in_flight).map(|_| Vec::new()).collect(),
             slot_submitted: (0..frames_in_flight).map(|_| Vec::new()).collect(),
+            transient_pools: (0..frames_in_flight)
+                .map(|_| TransientResourcePool::default())
+                .collect(),
+            slot_timestamps: Vec::new(),
+            gpu_timing_report: None,
+            #[cfg(feature = "profiling")]
+            tracy_gpu_context: None,
         }
     }
 
@@ -242,11 +271,20 @@ impl FramePipeline {
         // Take graph pool for this frame
         let graph_pool = std::mem::take(&mut self.graph_pool);
 
+        // Take this slot's transient pool. The old graphs holding transients
+        // were reset above, so its resources can be handed out again.
+        let mut transient_pool = std::mem::take(&mut self.transient_pools[self.current_slot]);
+        transient_pool.reset();
+
+        let timestamps = self.take_slot_timestamps();
+
         FrameSchedule::new(
             self.device.clone(),
             self.current_slot,
             ring_buffer,
             graph_pool,
+            transient_pool,
+            timestamps,
         )
     }
 
@@ -323,11 +361,20 @@ impl FramePipeline {
         // Take graph pool for this frame
         let graph_pool = std::mem::take(&mut self.graph_pool);
 
+        // Take this slot's transient pool. The old graphs holding transients
+        // were reset above, so its resources can be handed out again.
+        let mut transient_pool = std::mem::take(&mut self.transient_pools[self.current_slot]);
+        transient_pool.reset();
+
+        let timestamps = self.take_slot_timestamps();
+
         Some(FrameSchedule::new(
             self.device.clone(),
             self.current_slot,
             ring_buffer,
             graph_pool,
+            transient_pool,
+            timestamps,
         ))
     }
 
@@ -376,6 +423,20 @@ impl FramePipeline {
         // Return unused graphs to the pool directly
         self.graph_pool.extend(schedule.take_graph_pool());
 
+        // Return the transient pool; its resources stay reserved until this
+        // slot's fence has been waited on
+        self.transient_pools[self.current_slot] = schedule.take_transient_pool();
+
+        // Return the timestamp queries; their readback starts now and is
+        // collected once this slot's fence has been waited on
+        if !self.slot_timestamps.is_empty() {
+            let timestamps = schedule.take_timestamps();
+            if let Some(timestamps) = &timestamps {
+                timestamps.map(&self.device.instance().backend());
+            }
+            self.slot_timestamps[self.current_slot] = timestamps;
+        }
+
         // Store fence for this slot
         self.frame_fences[self.current_slot] = Some(fence);
 
@@ -626,6 +687,94 @@ impl FramePipeline {
             .and_then(|opt| opt.as_ref().map(|rb| rb.capacity()))
     }
 
+    /// Get the number of physical textures pooled for transient graph resources.
+    ///
+    /// Counts all frame slots. With aliasing, this is usually much lower than
+    /// the number of transient textures created per frame.
+    pub fn transient_texture_count(&self) -> usize {
+        self.transient_pools.iter().map(|p| p.texture_count()).sum()
+    }
+
+    /// Get the number of physical buffers pooled for transient graph resources.
+    ///
+    /// Counts all frame slots.
+    pub fn transient_buffer_count(&self) -> usize {
+        self.transient_pools.iter().map(|p| p.buffer_count()).sum()
+    }
+
+    /// Enable GPU timing of every graphics and compute pass.
+    ///
+    /// Each pass is wrapped in timestamp queries; the results of a frame are
+    /// read back when its slot is reused, `frames_in_flight` frames later,
+    /// and exposed through [`gpu_timing_report`](Self::gpu_timing_report).
+    /// With the `profiling` feature they are also sent to Tracy as GPU zones.
+    ///
+    /// Graphs submitted after `max_passes_per_frame` timed passes in a frame
+    /// run untimed. Call between frames; waits for all in-flight frames.
+    ///
+    /// Returns `false` if the backend does not support timestamp queries.
+    pub fn enable_gpu_timing(&mut self, max_passes_per_frame: u32) -> bool {
+        self.wait_idle();
+        let backend = self.device.instance().backend();
+        let pools: Option<Vec<_>> = (0..self.frames_in_flight)
+            .map(|_| backend.create_timestamp_pool(max_passes_per_frame.saturating_mul(2)))
+            .collect();
+        drop(backend);
+
+        match pools {
+            Some(pools) => {
+                self.slot_timestamps = pools
+                    .into_iter()
+                    .map(|pool| Some(FrameTimestamps::new(pool)))
+                    .collect();
+                true
+            }
+            None => {
+                self.disable_gpu_timing();
+                false
+            }
+        }
+    }
+
+    /// Disable GPU timing and release the query pools.
+    ///
+    /// Waits for all in-flight frames.
+    pub fn disable_gpu_timing(&mut self) {
+        self.wait_idle();
+        self.slot_timestamps.clear();
+        self.gpu_timing_report = None;
+    }
+
+    /// Check if GPU timing is enabled.
+    pub fn is_gpu_timing_enabled(&self) -> bool {
+        !self.slot_timestamps.is_empty()
+    }
+
+    /// Get the most recently resolved GPU timing.
+    ///
+    /// Describes the frame [`GpuTimingReport::frame`], which lags the
+    /// current frame by `frames_in_flight`. `None` until a timed frame has
+    /// completed.
+    pub fn gpu_timing_report(&self) -> Option<&GpuTimingReport> {
+        self.gpu_timing_report.as_ref()
+    }
+
+    /// Take the current slot's timestamp queries, resolving the ones written
+    /// by the slot's previous frame. Must be called after the fence wait.
+    fn take_slot_timestamps(&mut self) -> Option<FrameTimestamps> {
+        let mut timestamps = self.slot_timestamps.get_mut(self.current_slot)?.take()?;
+        let report = timestamps.resolve(
+            &self.device.instance().backend(),
+            self.frame_count,
+            #[cfg(feature = "profiling")]
+            &mut self.tracy_gpu_context,
+        );
+        if report.is_some() {
+            self.gpu_timing_report = report;
+        }
+        Some(timestamps)
+    }
+
     /// Check if a specific frame slot is ready (non-blocking).
     ///
     /// Returns `true` if the slot's fence is signaled or if the slot
@@ -843,4 +992,43 @@ mod tests {
         pipeline.end_frame(schedule);
         assert_eq!(pipeline.current_slot(), 1);
     }
+
+    #[test]
+    fn test_transients_pooled_across_frames() {
+        use crate::graph::{ColorAttachment, GraphicsPass, RenderTargetConfig};
+        use crate::types::{TextureDescriptor, TextureFormat, TextureUsage};
+
+        let mut pipeline = make_test_pipeline(1);
+
+        for _ in 0..3 {
+            let mut schedule = pipeline.begin_frame();
+            let mut graph = schedule.acquire_graph();
+            // A chain of full-screen targets, each rendered after the previous
+            let mut previous = None;
+            for i in 0..6 {
+                let target = graph.create_transient_texture(TextureDescriptor::new_2d(
+                    128,
+                    128,
+                    TextureFormat::Rgba8Unorm,
+                    TextureUsage::RENDER_ATTACHMENT,
+                ));
+                let mut pass = GraphicsPass::new(format!("post_{i}"));
+                pass.set_render_targets(
+                    RenderTargetConfig::new().with_color(ColorAttachment::from_texture(target)),
+                );
+                let handle = graph.add_graphics_pass(pass);
+                if let Some(previous) = previous {
+                    graph.add_dependency(handle, previous);
+                }
+                previous = Some(handle);
+            }
+            let main = schedule.submit("post", graph, &[]);
+            schedule.finish(&[main]);
+            pipeline.end_frame(schedule);
+        }
+
+        // Non-overlapping transients share one texture, reused every frame
+        assert_eq!(pipeline.transient_texture_count(), 1);
+        assert_eq!(pipeline.transient_buffer_count(), 0);
+    }
 }
@@ -1,6 +1,6 @@
 //! GPU buffer resource.
 
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
 use crate::backend::GpuBuffer;
 use crate::device::GraphicsDevice;
@@ -17,10 +17,27 @@ use crate::types::BufferDescriptor;
 /// let buffer = device.create_buffer(&BufferDescriptor::new(1024, BufferUsage::VERTEX))?;
 /// println!("Buffer size: {}", buffer.size());
 /// ```
+///
+/// # Transient Buffers
+///
+/// Buffers created by [`RenderGraph::create_transient_buffer`](crate::graph::RenderGraph::create_transient_buffer)
+/// are bound to a pooled physical buffer when their graph is submitted. Accessing
+/// the GPU handle or device of an unbound transient panics.
 pub struct Buffer {
-    device: Arc<GraphicsDevice>,
     descriptor: BufferDescriptor,
-    gpu_handle: GpuBuffer,
+    storage: BufferStorage,
+}
+
+/// Backing storage of a [`Buffer`].
+#[allow(clippy::large_enum_variant)]
+enum BufferStorage {
+    /// GPU memory owned by this buffer.
+    Owned {
+        device: Arc<GraphicsDevice>,
+        gpu_handle: GpuBuffer,
+    },
+    /// Physical buffer assigned by the render graph's transient pool.
+    Transient(OnceLock<Arc<Buffer>>),
 }
 
 impl Buffer {
@@ -31,20 +48,76 @@ impl Buffer {
         gpu_handle: GpuBuffer,
     ) -> Self {
         Self {
-            device,
             descriptor,
-            gpu_handle,
+            storage: BufferStorage::Owned { device, gpu_handle },
+        }
+    }
+
+    /// Create an unbound transient buffer (called by RenderGraph).
+    pub(crate) fn new_transient(descriptor: BufferDescriptor) -> Self {
+        Self {
+            descriptor,
+            storage: BufferStorage::Transient(OnceLock::new()),
         }
     }
 
     /// Get the GPU handle for this buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this is a transient buffer that hasn't been bound yet.
     pub fn gpu_handle(&self) -> &GpuBuffer {
-        &self.gpu_handle
+        match &self.storage {
+            BufferStorage::Owned { gpu_handle, .. } => gpu_handle,
+            BufferStorage::Transient(_) => self.expect_backing().gpu_handle(),
+        }
     }
 
     /// Get the parent device.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this is a transient buffer that hasn't been bound yet.
     pub fn device(&self) -> &Arc<GraphicsDevice> {
-        &self.device
+        match &self.storage {
+            BufferStorage::Owned { device, .. } => device,
+            BufferStorage::Transient(_) => self.expect_backing().device(),
+        }
+    }
+
+    /// Check if this buffer was created as a transient render graph resource.
+    pub fn is_transient(&self) -> bool {
+        matches!(self.storage, BufferStorage::Transient(_))
+    }
+
+    /// Get the physical buffer backing a transient buffer.
+    ///
+    /// Returns `None` for regular buffers and for transients that haven't
+    /// been bound yet.
+    pub fn transient_backing(&self) -> Option<&Arc<Buffer>> {
+        match &self.storage {
+            BufferStorage::Owned { .. } => None,
+            BufferStorage::Transient(backing) => backing.get(),
+        }
+    }
+
+    /// Bind a transient buffer to its physical buffer.
+    ///
+    /// Returns `false` if the buffer is not transient or is already bound.
+    pub(crate) fn bind_transient(&self, backing: Arc<Buffer>) -> bool {
+        match &self.storage {
+            BufferStorage::Owned { .. } => false,
+            BufferStorage::Transient(slot) => slot.set(backing).is_ok(),
+        }
+    }
+
+    fn expect_backing(&self) -> &Arc<Buffer> {
+        self.transient_backing().unwrap_or_else(|| {
+            panic!(
+                "transient buffer '{}' used before its render graph was submitted",
+                self.label().unwrap_or("unnamed")
+            )
+        })
     }
 
     /// Get the buffer descriptor.
@@ -69,6 +142,7 @@ impl std::fmt::Debug for Buffer {
             .field("size", &self.descriptor.size)
             .field("usage", &self.descriptor.usage)
             .field("label", &self.descriptor.label)
+            .field("transient", &self.is_transient())
             .finish()
     }
 }
@@ -1,6 +1,6 @@
 //! GPU texture resource.
 
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
 use crate::backend::GpuTexture;
 use crate::device::GraphicsDevice;
@@ -21,10 +21,29 @@ use crate::types::{Extent3d, TextureDescriptor, TextureDimension, TextureFormat}
 /// ))?;
 /// println!("Texture size: {}x{}", texture.width(), texture.height());
 /// ```
+///
+/// # Transient Textures
+///
+/// Textures created by [`RenderGraph::create_transient_texture`](crate::graph::RenderGraph::create_transient_texture)
+/// have no memory of their own. They are bound to a pooled physical texture when
+/// their graph is submitted, and may share that texture with other transients
+/// whose lifetimes don't overlap. Accessing the GPU handle or device of an
+/// unbound transient panics.
 pub struct Texture {
-    device: Arc<GraphicsDevice>,
     descriptor: TextureDescriptor,
-    gpu_handle: GpuTexture,
+    storage: TextureStorage,
+}
+
+/// Backing storage of a [`Texture`].
+#[allow(clippy::large_enum_variant)]
+enum TextureStorage {
+    /// GPU memory owned by this texture.
+    Owned {
+        device: Arc<GraphicsDevice>,
+        gpu_handle: GpuTexture,
+    },
+    /// Physical texture assigned by the render graph's transient pool.
+    Transient(OnceLock<Arc<Texture>>),
 }
 
 impl Texture {
@@ -35,20 +54,76 @@ impl Texture {
         gpu_handle: GpuTexture,
     ) -> Self {
         Self {
-            device,
             descriptor,
-            gpu_handle,
+            storage: TextureStorage::Owned { device, gpu_handle },
+        }
+    }
+
+    /// Create an unbound transient texture (called by RenderGraph).
+    pub(crate) fn new_transient(descriptor: TextureDescriptor) -> Self {
+        Self {
+            descriptor,
+            storage: TextureStorage::Transient(OnceLock::new()),
         }
     }
 
     /// Get the GPU handle for this texture.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this is a transient texture that hasn't been bound yet.
     pub fn gpu_handle(&self) -> &GpuTexture {
-        &self.gpu_handle
+        match &self.storage {
+            TextureStorage::Owned { gpu_handle, .. } => gpu_handle,
+            TextureStorage::Transient(_) => self.expect_backing().gpu_handle(),
+        }
     }
 
     /// Get the parent device.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this is a transient texture that hasn't been bound yet.
     pub fn device(&self) -> &Arc<GraphicsDevice> {
-        &self.device
+        match &self.storage {
+            TextureStorage::Owned { device, .. } => device,
+            TextureStorage::Transient(_) => self.expect_backing().device(),
+        }
+    }
+
+    /// Check if this texture was created as a transient render graph resource.
+    pub fn is_transient(&self) -> bool {
+        matches!(self.storage, TextureStorage::Transient(_))
+    }
+
+    /// Get the physical texture backing a transient texture.
+    ///
+    /// Returns `None` for regular textures and for transients that haven't
+    /// been bound yet.
+    pub fn transient_backing(&self) -> Option<&Arc<Texture>> {
+        match &self.storage {
+            TextureStorage::Owned { .. } => None,
+            TextureStorage::Transient(backing) => backing.get(),
+        }
+    }
+
+    /// Bind a transient texture to its physical texture.
+    ///
+    /// Returns `false` if the texture is not transient or is already bound.
+    pub(crate) fn bind_transient(&self, backing: Arc<Texture>) -> bool {
+        match &self.storage {
+            TextureStorage::Owned { .. } => false,
+            TextureStorage::Transient(slot) => slot.set(backing).is_ok(),
+        }
+    }
+
+    fn expect_backing(&self) -> &Arc<Texture> {
+        self.transient_backing().unwrap_or_else(|| {
+            panic!(
+                "transient texture '{}' used before its render graph was submitted",
+                self.label().unwrap_or("unnamed")
+            )
+        })
     }
 
     /// Get the texture descriptor.
@@ -100,6 +175,13 @@ impl Texture {
     pub fn label(&self) -> Option<&str> {
         self.descriptor.label.as_deref()
     }
+
+    /// Fill mip levels `1..` by downsampling mip level 0.
+    ///
+    /// See [`GraphicsDevice::generate_mipmaps`].
+    pub fn generate_mipmaps(&self) -> Result<(), crate::error::GraphicsError> {
+        self.device().generate_mipmaps(self)
+    }
 }
 
 impl std::fmt::Debug for Texture {
@@ -109,6 +191,7 @@ impl std::fmt::Debug for Texture {
             .field("format", &self.descriptor.format)
             .field("usage", &self.descriptor.usage)
             .field("label", &self.descriptor.label)
+            .field("transient", &self.is_transient())
             .finish()
     }
 }
@@ -53,7 +53,9 @@ pub use sync::{Fence, FenceStatus, Semaphore};
 use std::sync::Arc;
 
 use crate::device::GraphicsDevice;
+use crate::graph::transient::TransientResourcePool;
 use crate::graph::{RenderGraph, RenderGraphCompilationMode};
+use crate::pipeline::FrameTimestamps;
 use crate::resources::{RingAllocation, RingBuffer};
 use redlilium_core::profiling::profile_scope;
 
@@ -146,6 +148,10 @@ pub struct FrameSchedule {
     graph_pool: Vec<RenderGraph>,
     /// Graphs submitted this frame (for recycling in end_frame).
     submitted_graphs: Vec<RenderGraph>,
+    /// Physical resources for transients (moved from FramePipeline each frame).
+    transient_pool: TransientResourcePool,
+    /// Timestamp queries for GPU pass timing (if enabled in FramePipeline).
+    timestamps: Option<FrameTimestamps>,
 }
 
 impl std::fmt::Debug for FrameSchedule {
@@ -169,6 +175,8 @@ impl FrameSchedule {
         frame_slot: usize,
         ring_buffer: Option<RingBuffer>,
         graph_pool: Vec<RenderGraph>,
+        transient_pool: TransientResourcePool,
+        timestamps: Option<FrameTimestamps>,
     ) -> Self {
         Self {
             device,
@@ -179,6 +187,8 @@ impl FrameSchedule {
             ring_buffer,
             graph_pool,
             submitted_graphs: Vec::new(),
+            transient_pool,
+            timestamps,
         }
     }
 
@@ -231,6 +241,11 @@ impl FrameSchedule {
         self.ring_buffer.take()
     }
 
+    /// Take ownership of the timestamp queries (called by FramePipeline::end_frame).
+    pub(crate) fn take_timestamps(&mut self) -> Option<FrameTimestamps> {
+        self.timestamps.take()
+    }
+
     /// Acquire a render graph from the pool.
     ///
     /// Returns a graph from the pool if available, or creates a new one.
@@ -262,6 +277,11 @@ impl FrameSchedule {
         std::mem::take(&mut self.submitted)
     }
 
+    /// Take ownership of the transient resource pool (called by FramePipeline::end_frame).
+    pub(crate) fn take_transient_pool(&mut self) -> TransientResourcePool {
+        std::mem::take(&mut self.transient_pool)
+    }
+
     /// Submit a graph for immediate execution.
     ///
     /// The graph is submitted to the GPU immediately. If `wait_for` is non-empty,
@@ -331,12 +351,20 @@ impl FrameSchedule {
             Ok(_) => {
                 profile_scope!("execute_graph");
                 let compiled = graph.compiled().unwrap();
-                if let Err(e) = backend.execute_graph(
+                if let Err(e) = self
+                    .transient_pool
+                    .bind(&self.device, &graph.transients, compiled)
+                {
+                    log::error!("Failed to allocate transients for graph '{}': {}", name, e);
+                } else if let Err(e) = backend.execute_graph(
                     &graph,
                     compiled,
                     &wait_gpu_semaphores,
                     &signal_gpu_semaphores,
                     None,
+                    self.timestamps
+                        .as_mut()
+                        .and_then(|timestamps| timestamps.reserve(&name, &graph, compiled)),
                 ) {
                     log::error!("Failed to execute graph '{}': {}", name, e);
                 }
@@ -443,12 +471,24 @@ impl FrameSchedule {
             Ok(_) => {
                 profile_scope!("execute_present");
                 let compiled = graph.compiled().unwrap();
-                if let Err(e) = backend.execute_graph(
+                if let Err(e) = self
+                    .transient_pool
+                    .bind(&self.device, &graph.transients, compiled)
+                {
+                    log::error!(
+                        "Failed to allocate transients for present graph '{}': {}",
+                        name,
+                        e
+                    );
+                } else if let Err(e) = backend.execute_graph(
                     &graph,
                     compiled,
                     &wait_gpu_semaphores,
                     &signal_gpu_semaphores,
                     fence.gpu_fence(),
+                    self.timestamps
+                        .as_mut()
+                        .and_then(|timestamps| timestamps.reserve(&name, &graph, compiled)),
                 ) {
                     log::error!("Failed to execute present graph '{}': {}", name, e);
                 }
@@ -573,7 +613,14 @@ mod tests {
     fn make_test_schedule() -> FrameSchedule {
         let instance = GraphicsInstance::new().unwrap();
         let device = instance.create_device().unwrap();
-        FrameSchedule::new(device, 0, None, Vec::new())
+        FrameSchedule::new(
+            device,
+            0,
+            None,
+            Vec::new(),
+            TransientResourcePool::default(),
+            None,
+        )
     }
 
     #[test]
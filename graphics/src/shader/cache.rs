@@ -0,0 +1,388 @@
+//! Compiled shader cache.
+//!
+//! Compiling Slang (or translating WGSL to SPIR-V) is the slowest part of
+//! material creation. The backends look up every compilation in a
+//! [`ShaderCache`] first, keyed by a hash of everything that affects the
+//! output (see [`ShaderCacheKey`]).
+//!
+//! The cache can be persisted together with the Vulkan pipeline cache through
+//! [`GraphicsDevice::save_shader_cache`](crate::GraphicsDevice::save_shader_cache)
+//! and [`GraphicsDevice::load_shader_cache`](crate::GraphicsDevice::load_shader_cache).
+//!
+//! # File Format
+//!
+//! All integers are little-endian:
+//!
+//! | Field | Size |
+//! |-------|------|
+//! | Magic `RLSC` | 4 |
+//! | Format version | 4 |
+//! | Device UUID | 16 |
+//! | Pipeline cache size `n` | 8 |
+//! | Pipeline cache data | `n` |
+//! | Shader count | 4 |
+//! | Per shader: key (16), size `m` (4), code (`m`) | |
+//!
+//! The pipeline cache data is only valid for the device it was created on and
+//! is dropped on load if the device UUID differs. Compiled shaders are device
+//! independent and are always kept.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+
+use crate::error::GraphicsError;
+use crate::materials::{ShaderSourceLanguage, ShaderStage};
+use crate::shader::ShaderLibrary;
+
+/// Magic bytes at the start of a serialized cache.
+const MAGIC: &[u8; 4] = b"RLSC";
+
+/// Serialized cache format version. Bump when the layout or the key
+/// derivation changes.
+const FORMAT_VERSION: u32 = 1;
+
+/// Output format of a cached shader.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShaderTarget {
+    /// SPIR-V words (stored as little-endian bytes).
+    Spirv,
+    /// WGSL text.
+    Wgsl,
+}
+
+/// Hash of a shader compilation's inputs.
+///
+/// Covers the source, entry point, stage, source language, target, defines
+/// and every module of the [`ShaderLibrary`] the source may import.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ShaderCacheKey(u128);
+
+impl ShaderCacheKey {
+    /// Compute the key of a compilation.
+    pub fn new(
+        source: &[u8],
+        entry_point: &str,
+        stage: ShaderStage,
+        language: ShaderSourceLanguage,
+        target: ShaderTarget,
+        defines: &[(String, String)],
+        library: &ShaderLibrary,
+    ) -> Self {
+        let mut hasher = Fnv128::new();
+        hasher.write_u32(FORMAT_VERSION);
+        hasher.write_bytes(source);
+        hasher.write_bytes(entry_point.as_bytes());
+        hasher.write_u32(stage as u32);
+        hasher.write_u32(language as u32);
+        hasher.write_u32(target as u32);
+        hasher.write_u32(defines.len() as u32);
+        for (name, value) in defines {
+            hasher.write_bytes(name.as_bytes());
+            hasher.write_bytes(value.as_bytes());
+        }
+        for (name, module) in library.modules() {
+            hasher.write_bytes(name.as_bytes());
+            hasher.write_bytes(module.as_bytes());
+        }
+        Self(hasher.finish())
+    }
+}
+
+/// Thread-safe map of compiled shaders.
+#[derive(Default)]
+pub struct ShaderCache {
+    shaders: RwLock<HashMap<ShaderCacheKey, Arc<[u8]>>>,
+}
+
+impl std::fmt::Debug for ShaderCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShaderCache")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl ShaderCache {
+    /// Create an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the compiled code for `key`.
+    pub fn get(&self, key: ShaderCacheKey) -> Option<Arc<[u8]>> {
+        self.shaders.read().get(&key).cloned()
+    }
+
+    /// Store the compiled code for `key`.
+    pub fn insert(&self, key: ShaderCacheKey, code: impl Into<Arc<[u8]>>) {
+        self.shaders.write().insert(key, code.into());
+    }
+
+    /// Get the compiled code for `key`, running `compile` on a miss.
+    ///
+    /// Failed compilations are not cached.
+    pub fn get_or_compile(
+        &self,
+        key: ShaderCacheKey,
+        compile: impl FnOnce() -> Result<Vec<u8>, GraphicsError>,
+    ) -> Result<Arc<[u8]>, GraphicsError> {
+        if let Some(code) = self.get(key) {
+            return Ok(code);
+        }
+        let code: Arc<[u8]> = compile()?.into();
+        self.insert(key, code.clone());
+        Ok(code)
+    }
+
+    /// Number of cached shaders.
+    pub fn len(&self) -> usize {
+        self.shaders.read().len()
+    }
+
+    /// Check if the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.shaders.read().is_empty()
+    }
+
+    /// Remove all cached shaders.
+    pub fn clear(&self) {
+        self.shaders.write().clear();
+    }
+
+    /// Serialize the cached shaders together with a backend pipeline cache
+    /// created on the device identified by `device_uuid`.
+    pub fn serialize(&self, device_uuid: [u8; 16], pipeline_cache: &[u8]) -> Vec<u8> {
+        let shaders = self.shaders.read();
+        let mut out = Vec::with_capacity(
+            36 + pipeline_cache.len() + shaders.values().map(|code| 20 + code.len()).sum::<usize>(),
+        );
+        out.extend_from_slice(MAGIC);
+        out.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        out.extend_from_slice(&device_uuid);
+        out.extend_from_slice(&(pipeline_cache.len() as u64).to_le_bytes());
+        out.extend_from_slice(pipeline_cache);
+        out.extend_from_slice(&(shaders.len() as u32).to_le_bytes());
+        for (key, code) in shaders.iter() {
+            out.extend_from_slice(&key.0.to_le_bytes());
+            out.extend_from_slice(&(code.len() as u32).to_le_bytes());
+            out.extend_from_slice(code);
+        }
+        out
+    }
+
+    /// Add the shaders of a cache produced by [`serialize`](Self::serialize).
+    ///
+    /// Returns the stored pipeline cache data if it was created on the device
+    /// identified by `device_uuid`, or an empty vector otherwise. Files
+    /// written by another format version are ignored.
+    pub fn deserialize(
+        &self,
+        bytes: &[u8],
+        device_uuid: [u8; 16],
+    ) -> Result<Vec<u8>, GraphicsError> {
+        let mut reader = Reader { bytes };
+        if reader.take(4)? != MAGIC {
+            return Err(GraphicsError::InvalidParameter(
+                "not a shader cache file".into(),
+            ));
+        }
+        if reader.u32()? != FORMAT_VERSION {
+            log::info!("Ignoring shader cache with a different format version");
+            return Ok(Vec::new());
+        }
+        let same_device = reader.take(16)? == device_uuid;
+        let pipeline_size = reader.u64()?;
+        let pipeline_cache =
+            reader.take(usize::try_from(pipeline_size).map_err(|_| truncated())?)?;
+
+        let count = reader.u32()?;
+        let mut entries = Vec::with_capacity(count.min(4096) as usize);
+        for _ in 0..count {
+            let key = ShaderCacheKey(u128::from_le_bytes(reader.take(16)?.try_into().unwrap()));
+            let size = reader.u32()? as usize;
+            entries.push((key, Arc::<[u8]>::from(reader.take(size)?)));
+        }
+        self.shaders.write().extend(entries);
+
+        if same_device {
+            Ok(pipeline_cache.to_vec())
+        } else {
+            log::info!("Ignoring pipeline cache created on a different device");
+            Ok(Vec::new())
+        }
+    }
+}
+
+fn truncated() -> GraphicsError {
+    GraphicsError::InvalidParameter("truncated shader cache file".into())
+}
+
+/// Cursor over serialized cache bytes.
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], GraphicsError> {
+        if self.bytes.len() < len {
+            return Err(truncated());
+        }
+        let (head, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(head)
+    }
+
+    fn u32(&mut self) -> Result<u32, GraphicsError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, GraphicsError> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+}
+
+/// 128-bit FNV-1a hasher.
+///
+/// Used instead of `std::hash` because keys are persisted and must be stable
+/// across Rust versions.
+struct Fnv128(u128);
+
+impl Fnv128 {
+    const OFFSET: u128 = 0x6c62_272e_07bb_0142_62b8_2175_6295_c58d;
+    const PRIME: u128 = 0x0000_0000_0100_0000_0000_0000_0000_013b;
+
+    fn new() -> Self {
+        Self(Self::OFFSET)
+    }
+
+    fn write_raw(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u128::from(byte);
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    fn write_u32(&mut self, value: u32) {
+        self.write_raw(&value.to_le_bytes());
+    }
+
+    /// Write a length-prefixed byte string, so adjacent fields cannot
+    /// collide by shifting bytes between them.
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        self.write_raw(&(bytes.len() as u64).to_le_bytes());
+        self.write_raw(bytes);
+    }
+
+    fn finish(&self) -> u128 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(source: &str, defines: &[(String, String)], library: &ShaderLibrary) -> ShaderCacheKey {
+        ShaderCacheKey::new(
+            source.as_bytes(),
+            "main",
+            ShaderStage::Fragment,
+            ShaderSourceLanguage::Slang,
+            ShaderTarget::Spirv,
+            defines,
+            library,
+        )
+    }
+
+    #[test]
+    fn test_key_covers_inputs() {
+        let library = ShaderLibrary::standard_slang();
+        let base = key("float4 main() {}", &[], &library);
+
+        assert_eq!(base, key("float4 main() {}", &[], &library));
+        assert_ne!(base, key("float4 main() { }", &[], &library));
+        assert_ne!(
+            base,
+            key(
+                "float4 main() {}",
+                &[("SHADOWS".into(), "1".into())],
+                &library
+            )
+        );
+        assert_ne!(base, key("float4 main() {}", &[], &ShaderLibrary::empty()));
+        assert_ne!(
+            base,
+            ShaderCacheKey::new(
+                b"float4 main() {}",
+                "main",
+                ShaderStage::Fragment,
+                ShaderSourceLanguage::Slang,
+                ShaderTarget::Wgsl,
+                &[],
+                &library,
+            )
+        );
+    }
+
+    #[test]
+    fn test_get_or_compile_caches_success_only() {
+        let cache = ShaderCache::new();
+        let k = key("a", &[], &ShaderLibrary::empty());
+
+        assert!(
+            cache
+                .get_or_compile(k, || Err(GraphicsError::ShaderCompilationFailed(
+                    "x".into()
+                )))
+                .is_err()
+        );
+        assert!(cache.is_empty());
+
+        let code = cache.get_or_compile(k, || Ok(vec![1, 2, 3])).unwrap();
+        assert_eq!(&*code, &[1, 2, 3]);
+        let code = cache
+            .get_or_compile(k, || panic!("should be cached"))
+            .unwrap();
+        assert_eq!(&*code, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn test_serialize_roundtrip() {
+        let cache = ShaderCache::new();
+        let library = ShaderLibrary::empty();
+        cache.insert(key("a", &[], &library), vec![1, 2, 3, 4]);
+        cache.insert(key("b", &[], &library), vec![5]);
+        let uuid = [7; 16];
+        let bytes = cache.serialize(uuid, &[9, 9]);
+
+        let loaded = ShaderCache::new();
+        let pipeline_cache = loaded.deserialize(&bytes, uuid).unwrap();
+        assert_eq!(pipeline_cache, vec![9, 9]);
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(&*loaded.get(key("b", &[], &library)).unwrap(), &[5]);
+    }
+
+    #[test]
+    fn test_other_device_drops_pipeline_cache() {
+        let cache = ShaderCache::new();
+        cache.insert(key("a", &[], &ShaderLibrary::empty()), vec![1]);
+        let bytes = cache.serialize([1; 16], &[9, 9]);
+
+        let loaded = ShaderCache::new();
+        let pipeline_cache = loaded.deserialize(&bytes, [2; 16]).unwrap();
+        assert!(pipeline_cache.is_empty());
+        assert_eq!(loaded.len(), 1);
+    }
+
+    #[test]
+    fn test_deserialize_rejects_invalid_data() {
+        let cache = ShaderCache::new();
+        assert!(cache.deserialize(b"nope", [0; 16]).is_err());
+
+        let mut bytes = ShaderCache::new().serialize([0; 16], &[1, 2, 3]);
+        bytes.truncate(bytes.len() - 6);
+        assert!(cache.deserialize(&bytes, [0; 16]).is_err());
+    }
+}
@@ -10,6 +10,8 @@
 //! - `color.slang` - Color space conversions and tone mapping
 //! - `brdf.slang` - PBR BRDF functions (Cook-Torrance)
 //! - `ibl.slang` - Image-based lighting utilities
+//! - `shadows.slang` - Shadow map projection and PCF filtering
+//! - `lighting.slang` - Direct lighting from the scene's gathered lights
 //! - `egui.slang` - Complete egui shader with types, utilities, and entry points
 //!
 //! # Available Modules
@@ -20,8 +22,17 @@
 //! | `color` | Color space conversions and tone mapping |
 //! | `brdf` | PBR BRDF functions (Cook-Torrance) |
 //! | `ibl` | Image-based lighting utilities |
+//! | `shadows` | Shadow map projection and PCF filtering |
+//! | `lighting` | Direct lighting from the scene's gathered lights |
 //!
 //! Slang shaders use `import math;` to include library modules.
+//!
+//! Each backend keeps its own copy of the standard library, whose modules
+//! can be replaced at runtime with
+//! [`GraphicsDevice::set_shader_library_module`](crate::GraphicsDevice::set_shader_library_module).
+
+use std::borrow::Cow;
+use std::collections::HashSet;
 
 // =============================================================================
 // Shader Module Sources (loaded from files at compile time)
@@ -39,6 +50,12 @@ const BRDF_MODULE: &str = include_str!("../../../shaders/library/brdf.slang");
 /// Image-based lighting utilities (Slang).
 const IBL_MODULE: &str = include_str!("../../../shaders/library/ibl.slang");
 
+/// Shadow map projection, cascade selection and PCF filtering (Slang).
+const SHADOWS_MODULE: &str = include_str!("../../../shaders/library/shadows.slang");
+
+/// Light loop over the scene's gathered lights, with shadows (Slang).
+const LIGHTING_MODULE: &str = include_str!("../../../shaders/library/lighting.slang");
+
 /// Complete egui shader with vertex and fragment entry points (Slang).
 /// Entry points: `vs_main` (vertex) and `fs_main` (fragment).
 /// Use `EGUI_SHADER_SOURCE` to access the full shader for rendering.
@@ -53,8 +70,9 @@ pub const EGUI_SHADER_SOURCE: &str = EGUI_MODULE;
 // =============================================================================
 
 /// Collection of shader modules that can be included via `import` (Slang).
+#[derive(Clone)]
 pub struct ShaderLibrary {
-    modules: Vec<(&'static str, &'static str)>,
+    modules: Vec<(Cow<'static, str>, Cow<'static, str>)>,
 }
 
 impl ShaderLibrary {
@@ -65,14 +83,21 @@ impl ShaderLibrary {
     /// - `color` - Color processing
     /// - `brdf` - PBR BRDF functions (includes math)
     /// - `ibl` - Image-based lighting (includes brdf)
+    /// - `shadows` - Shadow map sampling with PCF
+    /// - `lighting` - Scene light loop (includes brdf and shadows)
     pub fn standard_slang() -> Self {
         Self {
-            modules: vec![
+            modules: [
                 ("math", MATH_MODULE),
                 ("color", COLOR_MODULE),
                 ("brdf", BRDF_MODULE),
                 ("ibl", IBL_MODULE),
-            ],
+                ("shadows", SHADOWS_MODULE),
+                ("lighting", LIGHTING_MODULE),
+            ]
+            .into_iter()
+            .map(|(name, source)| (Cow::Borrowed(name), Cow::Borrowed(source)))
+            .collect(),
         }
     }
 
@@ -84,15 +109,64 @@ impl ShaderLibrary {
     }
 
     /// Get an iterator over all modules (module_name, source).
-    pub fn modules(&self) -> impl Iterator<Item = (&'static str, &'static str)> + '_ {
-        self.modules.iter().copied()
+    pub fn modules(&self) -> impl Iterator<Item = (&str, &str)> + '_ {
+        self.modules
+            .iter()
+            .map(|(name, source)| (name.as_ref(), source.as_ref()))
     }
 
     /// Add a custom module to the library.
     pub fn with_module(mut self, path: &'static str, source: &'static str) -> Self {
-        self.modules.push((path, source));
+        self.modules
+            .push((Cow::Borrowed(path), Cow::Borrowed(source)));
         self
     }
+
+    /// Replace the source of the module `name`, adding it if missing.
+    pub fn set_module(&mut self, name: &str, source: String) {
+        match self.modules.iter_mut().find(|(module, _)| module == name) {
+            Some((_, module_source)) => *module_source = Cow::Owned(source),
+            None => self
+                .modules
+                .push((Cow::Owned(name.to_owned()), Cow::Owned(source))),
+        }
+    }
+
+    /// Get the source of the module `name`.
+    pub fn module(&self, name: &str) -> Option<&str> {
+        self.modules
+            .iter()
+            .find(|(module, _)| module == name)
+            .map(|(_, source)| source.as_ref())
+    }
+
+    /// Names of the modules a Slang source imports with `import name;`.
+    pub fn imports(source: &str) -> impl Iterator<Item = &str> {
+        source.lines().filter_map(|line| {
+            let name = line.trim().strip_prefix("import ")?.strip_suffix(';')?;
+            Some(name.trim())
+        })
+    }
+
+    /// The `changed` modules plus every module importing one of them,
+    /// directly or through other modules.
+    pub fn dependents<'a>(&self, changed: impl IntoIterator<Item = &'a str>) -> HashSet<String> {
+        let mut affected: HashSet<String> = changed.into_iter().map(str::to_owned).collect();
+        loop {
+            let added: Vec<String> = self
+                .modules()
+                .filter(|(name, source)| {
+                    !affected.contains(*name)
+                        && Self::imports(source).any(|import| affected.contains(import))
+                })
+                .map(|(name, _)| name.to_owned())
+                .collect();
+            if added.is_empty() {
+                return affected;
+            }
+            affected.extend(added);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -104,11 +178,13 @@ mod tests {
         let library = ShaderLibrary::standard_slang();
         let modules: Vec<_> = library.modules().collect();
 
-        assert_eq!(modules.len(), 4);
+        assert_eq!(modules.len(), 6);
         assert!(modules.iter().any(|(name, _)| *name == "math"));
         assert!(modules.iter().any(|(name, _)| *name == "color"));
         assert!(modules.iter().any(|(name, _)| *name == "brdf"));
         assert!(modules.iter().any(|(name, _)| *name == "ibl"));
+        assert!(modules.iter().any(|(name, _)| *name == "shadows"));
+        assert!(modules.iter().any(|(name, _)| *name == "lighting"));
     }
 
     #[test]
@@ -124,6 +200,32 @@ mod tests {
         assert_eq!(library.modules().count(), 1);
     }
 
+    #[test]
+    fn test_set_module_replaces_source() {
+        let mut library = ShaderLibrary::standard_slang();
+        library.set_module("math", "static const float PI = 3.0;".to_owned());
+        library.set_module("extra", String::new());
+
+        let modules: Vec<_> = library.modules().collect();
+        assert_eq!(modules.len(), 7);
+        assert!(modules.contains(&("math", "static const float PI = 3.0;")));
+        assert!(modules.contains(&("extra", "")));
+    }
+
+    #[test]
+    fn test_dependents_follow_imports() {
+        let library = ShaderLibrary::standard_slang();
+        assert_eq!(
+            ShaderLibrary::imports(library.module("lighting").unwrap()).collect::<Vec<_>>(),
+            ["brdf", "shadows"]
+        );
+
+        let affected = library.dependents(["brdf"]);
+        let mut affected: Vec<_> = affected.iter().map(String::as_str).collect();
+        affected.sort_unstable();
+        assert_eq!(affected, ["brdf", "ibl", "lighting"]);
+    }
+
     #[test]
     fn test_module_contents_slang() {
         // Verify that included Slang files contain expected content
@@ -137,6 +239,13 @@ mod tests {
         assert!(BRDF_MODULE.contains("float3 fresnel_schlick"));
 
         assert!(IBL_MODULE.contains("float3 ibl_ambient"));
+        assert!(IBL_MODULE.contains("float3 ibl_ambient_lod"));
+
+        assert!(SHADOWS_MODULE.contains("float shadow_pcf"));
+        assert!(SHADOWS_MODULE.contains("uint shadow_select_cascade"));
+
+        assert!(LIGHTING_MODULE.contains("struct Light"));
+        assert!(LIGHTING_MODULE.contains("float3 scene_direct_lighting"));
 
         assert!(EGUI_MODULE.contains("vs_main"));
         assert!(EGUI_MODULE.contains("fs_main"));
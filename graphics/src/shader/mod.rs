@@ -1,9 +1,11 @@
-//! Shader library and Slang compiler support.
+//! Shader library, compiled shader cache and Slang compiler support.
 
+pub mod cache;
 pub mod library;
 #[cfg(feature = "slang-shaders")]
 pub mod slang_compiler;
 
+pub use cache::{ShaderCache, ShaderCacheKey, ShaderTarget};
 pub use library::{EGUI_SHADER_SOURCE, ShaderLibrary};
 #[cfg(feature = "slang-shaders")]
 pub use slang_compiler::{ShaderReflectInput, SlangCompiler};
@@ -275,6 +275,8 @@ impl SlangCompiler {
                     use slang::ResourceShape;
                     match shape {
                         ResourceShape::SlangTextureCube => BindingType::TextureCube,
+                        ResourceShape::SlangStructuredBuffer
+                        | ResourceShape::SlangByteAddressBuffer => BindingType::StorageBuffer,
                         _ => BindingType::Texture,
                     }
                 } else {
@@ -131,6 +131,50 @@ impl TextureDescriptor {
         self
     }
 
+    /// Set the mip level count to the full chain down to 1x1.
+    pub fn with_full_mip_chain(mut self) -> Self {
+        let largest = match self.dimension {
+            TextureDimension::D3 => self.size.width.max(self.size.height).max(self.size.depth),
+            _ => self.size.width.max(self.size.height),
+        };
+        self.mip_level_count = u32::BITS - largest.max(1).leading_zeros();
+        self
+    }
+
+    /// Returns the number of array layers, counting each cube face (1 for
+    /// 3D textures).
+    pub fn layer_count(&self) -> u32 {
+        match self.dimension {
+            TextureDimension::D3 => 1,
+            dimension => dimension.layer_count(self.size.depth),
+        }
+    }
+
+    /// Returns the size of mip level `level`.
+    ///
+    /// `depth` is the depth of the level for 3D textures and the number of
+    /// array layers otherwise, as used by copy and blit regions.
+    pub fn mip_level_extent(&self, level: u32) -> Extent3d {
+        let depth = match self.dimension {
+            TextureDimension::D3 => (self.size.depth >> level).max(1),
+            _ => self.layer_count(),
+        };
+        Extent3d::new_3d(
+            (self.size.width >> level).max(1),
+            (self.size.height >> level).max(1),
+            depth,
+        )
+    }
+
+    /// Returns the size in bytes of tightly packed data for mip level
+    /// `level`, all layers included.
+    pub fn mip_level_size(&self, level: u32) -> usize {
+        let extent = self.mip_level_extent(level);
+        self.format.bytes_per_row(extent.width) as usize
+            * self.format.rows_per_image(extent.height) as usize
+            * extent.depth as usize
+    }
+
     /// Set the sample count for multisampling.
     pub fn with_sample_count(mut self, count: u32) -> Self {
         self.sample_count = count;
@@ -36,6 +36,8 @@ pub fn readback_buffer_size(width: u32, height: u32, bytes_per_pixel: u32) -> u6
 pub enum Backend {
     /// Dummy backend (no actual GPU operations).
     Dummy,
+    /// Software backend (CPU rasterizer).
+    Software,
     /// Vulkan backend (native via ash).
     Vulkan,
     /// WebGPU backend (via wgpu with Vulkan).
@@ -48,6 +50,11 @@ impl Backend {
         match self {
             // Dummy backend is always available
             Backend::Dummy => true,
+            // Software backend runs on the CPU when the feature is enabled
+            #[cfg(feature = "software-backend")]
+            Backend::Software => true,
+            #[cfg(not(feature = "software-backend"))]
+            Backend::Software => false,
             // Vulkan backend via ash is available when the feature is enabled
             #[cfg(feature = "vulkan-backend")]
             Backend::Vulkan => true,
@@ -66,6 +73,7 @@ impl Backend {
     pub fn name(&self) -> &'static str {
         match self {
             Backend::Dummy => "dummy",
+            Backend::Software => "software",
             Backend::Vulkan => "vulkan",
             Backend::WebGpu => "webgpu",
         }
@@ -75,6 +83,7 @@ impl Backend {
     pub fn to_instance_parameters(self) -> InstanceParameters {
         match self {
             Backend::Dummy => InstanceParameters::new().with_backend(BackendType::Dummy),
+            Backend::Software => InstanceParameters::new().with_backend(BackendType::Software),
             Backend::Vulkan => InstanceParameters::new()
                 .with_backend(BackendType::Wgpu)
                 .with_wgpu_backend(WgpuBackendType::Auto),
@@ -656,24 +665,95 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
 }
 "#;
 
+/// CPU equivalents of the WGSL test shaders, used by the software backend.
+#[cfg(feature = "software-backend")]
+pub mod software_shaders {
+    use redlilium_graphics::VertexAttributeSemantic;
+    use redlilium_graphics::backend::software::{
+        FragmentInput, FragmentOutput, ShaderResources, SoftwareShader, Varyings, VertexInput,
+    };
+
+    fn position(input: &VertexInput<'_>) -> [f32; 4] {
+        let [x, y, z, _] = input
+            .attribute(VertexAttributeSemantic::Position)
+            .unwrap_or_default();
+        [x, y, z, 1.0]
+    }
+
+    /// CPU version of [`super::SOLID_RED_SHADER`].
+    #[derive(Debug)]
+    pub struct SolidRed;
+
+    impl SoftwareShader for SolidRed {
+        fn vertex(
+            &self,
+            input: &VertexInput<'_>,
+            _resources: &ShaderResources<'_>,
+            _varyings: &mut Varyings,
+        ) -> [f32; 4] {
+            position(input)
+        }
+
+        fn fragment(
+            &self,
+            _input: &FragmentInput,
+            _resources: &ShaderResources<'_>,
+            output: &mut FragmentOutput,
+        ) {
+            output.set_color(0, [1.0, 0.0, 0.0, 1.0]);
+        }
+    }
+
+    /// CPU version of [`super::TEXTURE_SAMPLE_SHADER`].
+    #[derive(Debug)]
+    pub struct TextureSample;
+
+    impl SoftwareShader for TextureSample {
+        fn vertex(
+            &self,
+            input: &VertexInput<'_>,
+            _resources: &ShaderResources<'_>,
+            varyings: &mut Varyings,
+        ) -> [f32; 4] {
+            let uv = input
+                .attribute(VertexAttributeSemantic::TexCoord0)
+                .unwrap_or_default();
+            varyings.set(0, uv);
+            position(input)
+        }
+
+        fn fragment(
+            &self,
+            input: &FragmentInput,
+            resources: &ShaderResources<'_>,
+            output: &mut FragmentOutput,
+        ) {
+            let [u, v, ..] = input.varyings.get(0);
+            output.set_color(0, resources.sample(0, 0, 1, [u, v]));
+        }
+    }
+}
+
 /// Create a material with the given WGSL shader source.
 #[allow(dead_code)]
 pub fn create_solid_color_material(ctx: &TestContext) -> Arc<Material> {
+    let descriptor = MaterialDescriptor::new()
+        .with_shader(ShaderSource::vertex(
+            SOLID_RED_SHADER.as_bytes().to_vec(),
+            "vs_main",
+        ))
+        .with_shader(ShaderSource::fragment(
+            SOLID_RED_SHADER.as_bytes().to_vec(),
+            "fs_main",
+        ))
+        .with_vertex_layout(quad_vertex_layout())
+        .with_color_format(TextureFormat::Rgba8Unorm)
+        .with_label("solid_red_material");
+    #[cfg(feature = "software-backend")]
+    let descriptor = descriptor.with_software_shader(Arc::new(software_shaders::SolidRed));
+
     ctx.device
-        .create_material(
-            &MaterialDescriptor::new()
-                .with_shader(ShaderSource::vertex(
-                    SOLID_RED_SHADER.as_bytes().to_vec(),
-                    "vs_main",
-                ))
-                .with_shader(ShaderSource::fragment(
-                    SOLID_RED_SHADER.as_bytes().to_vec(),
-                    "fs_main",
-                ))
-                .with_vertex_layout(quad_vertex_layout())
-                .with_color_format(TextureFormat::Rgba8Unorm)
-                .with_label("solid_red_material"),
-        )
+        .create_material(&descriptor)
         .expect("Failed to create material")
 }
 
@@ -759,22 +839,24 @@ pub fn create_texture_sample_material(ctx: &TestContext) -> Arc<Material> {
             .with_label("texture_sample_bindings"),
     );
 
+    let descriptor = MaterialDescriptor::new()
+        .with_shader(ShaderSource::vertex(
+            TEXTURE_SAMPLE_SHADER.as_bytes().to_vec(),
+            "vs_main",
+        ))
+        .with_shader(ShaderSource::fragment(
+            TEXTURE_SAMPLE_SHADER.as_bytes().to_vec(),
+            "fs_main",
+        ))
+        .with_vertex_layout(quad_vertex_layout())
+        .with_binding_layout(binding_layout)
+        .with_color_format(TextureFormat::Rgba8Unorm)
+        .with_label("texture_sample_material");
+    #[cfg(feature = "software-backend")]
+    let descriptor = descriptor.with_software_shader(Arc::new(software_shaders::TextureSample));
+
     ctx.device
-        .create_material(
-            &MaterialDescriptor::new()
-                .with_shader(ShaderSource::vertex(
-                    TEXTURE_SAMPLE_SHADER.as_bytes().to_vec(),
-                    "vs_main",
-                ))
-                .with_shader(ShaderSource::fragment(
-                    TEXTURE_SAMPLE_SHADER.as_bytes().to_vec(),
-                    "fs_main",
-                ))
-                .with_vertex_layout(quad_vertex_layout())
-                .with_binding_layout(binding_layout)
-                .with_color_format(TextureFormat::Rgba8Unorm)
-                .with_label("texture_sample_material"),
-        )
+        .create_material(&descriptor)
         .expect("Failed to create texture sample material")
 }
 
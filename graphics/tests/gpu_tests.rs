@@ -25,6 +25,8 @@
 
 mod common;
 
+use std::sync::Arc;
+
 use rstest::rstest;
 
 use common::{
@@ -34,8 +36,9 @@ use common::{
     generate_test_pattern, get_pixel, readback_buffer_size, verify_pixel, write_quad_vertices,
 };
 use redlilium_graphics::{
-    BufferUsage, RenderGraph, RenderGraphCompilationMode, TextureFormat, TextureUsage,
-    TransferConfig, TransferOperation, TransferPass,
+    BufferTextureCopyRegion, BufferTextureLayout, BufferUsage, Extent3d, FilterMode, RenderGraph,
+    RenderGraphCompilationMode, Texture, TextureCopyLocation, TextureDescriptor, TextureFormat,
+    TextureUsage, TransferConfig, TransferOperation, TransferPass,
 };
 
 // ============================================================================
@@ -51,6 +54,7 @@ use redlilium_graphics::{
 /// 4. The readback data matches the original
 #[rstest]
 #[case::dummy(Backend::Dummy)]
+#[case::software(Backend::Software)]
 #[case::vulkan(Backend::Vulkan)]
 #[case::webgpu(Backend::WebGpu)]
 fn test_buffer_copy_roundtrip(#[case] backend: Backend) {
@@ -116,6 +120,7 @@ fn test_buffer_copy_roundtrip(#[case] backend: Backend) {
 /// Test buffer copy with partial regions.
 #[rstest]
 #[case::dummy(Backend::Dummy)]
+#[case::software(Backend::Software)]
 #[case::vulkan(Backend::Vulkan)]
 #[case::webgpu(Backend::WebGpu)]
 fn test_buffer_copy_partial(#[case] backend: Backend) {
@@ -172,6 +177,7 @@ fn test_buffer_copy_partial(#[case] backend: Backend) {
 /// 4. The readback data shows the expected rendered output
 #[rstest]
 #[case::dummy(Backend::Dummy)]
+#[case::software(Backend::Software)]
 #[case::vulkan(Backend::Vulkan)]
 #[case::webgpu(Backend::WebGpu)]
 fn test_render_single_quad(#[case] backend: Backend) {
@@ -234,6 +240,7 @@ fn test_render_single_quad(#[case] backend: Backend) {
 /// Test clearing a render target to a specific color.
 #[rstest]
 #[case::dummy(Backend::Dummy)]
+#[case::software(Backend::Software)]
 #[case::vulkan(Backend::Vulkan)]
 #[case::webgpu(Backend::WebGpu)]
 fn test_render_clear_color(#[case] backend: Backend) {
@@ -285,6 +292,84 @@ fn test_render_clear_color(#[case] backend: Backend) {
     );
 }
 
+/// Test transient render targets that alias the same memory.
+///
+/// Two transient targets are cleared and read back one after the other. Their
+/// lifetimes don't overlap, so the compiler assigns them the same physical
+/// texture; each readback must still see its own clear color.
+#[rstest]
+#[case::dummy(Backend::Dummy)]
+#[case::software(Backend::Software)]
+#[case::vulkan(Backend::Vulkan)]
+#[case::webgpu(Backend::WebGpu)]
+fn test_render_transient_targets_aliased(#[case] backend: Backend) {
+    let Some(ctx) = TestContext::new(backend) else {
+        eprintln!("Backend {:?} not available, skipping", backend);
+        return;
+    };
+
+    const WIDTH: u32 = 32;
+    const HEIGHT: u32 = 32;
+    const COLORS: [[f32; 4]; 2] = [[1.0, 0.0, 0.0, 1.0], [0.0, 0.0, 1.0, 1.0]];
+
+    let readback_size = readback_buffer_size(WIDTH, HEIGHT, 4);
+    let readbacks = [
+        ctx.create_readback_buffer(readback_size),
+        ctx.create_readback_buffer(readback_size),
+    ];
+
+    let mut graph = RenderGraph::new();
+    let mut previous = None;
+    for (color, readback) in COLORS.iter().zip(&readbacks) {
+        let target = graph.create_transient_texture(TextureDescriptor::new_2d(
+            WIDTH,
+            HEIGHT,
+            TextureFormat::Rgba8Unorm,
+            TextureUsage::RENDER_ATTACHMENT | TextureUsage::COPY_SRC,
+        ));
+
+        let render_pass = create_simple_render_pass("clear_transient", target.clone(), *color);
+        let render_handle = graph.add_graphics_pass(render_pass);
+
+        let mut copy_pass = TransferPass::new("copy_to_readback".into());
+        copy_pass.set_transfer_config(TransferConfig::new().with_operation(
+            TransferOperation::readback_texture_whole(target, readback.clone()),
+        ));
+        let copy_handle = graph.add_transfer_pass(copy_pass);
+        graph.add_dependency(copy_handle, render_handle);
+
+        if let Some(previous) = previous {
+            graph.add_dependency(render_handle, previous);
+        }
+        previous = Some(copy_handle);
+    }
+
+    let slot_count = graph
+        .compile(RenderGraphCompilationMode::Automatic)
+        .expect("Graph should compile")
+        .transient_texture_slots()
+        .len();
+    assert_eq!(slot_count, 1, "Both transients should share one texture");
+
+    ctx.execute_graph(graph);
+
+    // Skip pixel verification on dummy backend since it doesn't actually render
+    if backend == Backend::Dummy {
+        return;
+    }
+
+    for (color, readback) in COLORS.iter().zip(&readbacks) {
+        let data = ctx.device.read_buffer(readback, 0, readback_size);
+        let expected = ExpectedPixel::from_float(color[0], color[1], color[2], color[3]);
+        assert!(
+            verify_pixel(&data, WIDTH, WIDTH / 2, HEIGHT / 2, expected, 2),
+            "Transient pixel should be {:?}, but got {:?}",
+            expected,
+            get_pixel(&data, WIDTH, WIDTH / 2, HEIGHT / 2)
+        );
+    }
+}
+
 // ============================================================================
 // Depth Buffer Tests
 // ============================================================================
@@ -297,6 +382,7 @@ fn test_render_clear_color(#[case] backend: Backend) {
 /// 3. Multiple draw calls in a single pass work correctly
 #[rstest]
 #[case::dummy(Backend::Dummy)]
+#[case::software(Backend::Software)]
 #[case::vulkan(Backend::Vulkan)]
 #[case::webgpu(Backend::WebGpu)]
 fn test_render_depth_buffer_two_quads(#[case] backend: Backend) {
@@ -369,6 +455,7 @@ fn test_render_depth_buffer_two_quads(#[case] backend: Backend) {
 /// because it has a smaller depth value.
 #[rstest]
 #[case::dummy(Backend::Dummy)]
+#[case::software(Backend::Software)]
 #[case::vulkan(Backend::Vulkan)]
 #[case::webgpu(Backend::WebGpu)]
 fn test_render_depth_buffer_reverse_order(#[case] backend: Backend) {
@@ -426,6 +513,7 @@ fn test_render_depth_buffer_reverse_order(#[case] backend: Backend) {
 /// 3. Each target receives the correct output
 #[rstest]
 #[case::dummy(Backend::Dummy)]
+#[case::software(Backend::Software)]
 #[case::vulkan(Backend::Vulkan)]
 #[case::webgpu(Backend::WebGpu)]
 fn test_render_multiple_targets(#[case] backend: Backend) {
@@ -508,6 +596,7 @@ fn test_render_multiple_targets(#[case] backend: Backend) {
 /// Test MRT with different texture formats.
 #[rstest]
 #[case::dummy(Backend::Dummy)]
+#[case::software(Backend::Software)]
 #[case::vulkan(Backend::Vulkan)]
 #[case::webgpu(Backend::WebGpu)]
 fn test_render_mrt_different_formats(#[case] backend: Backend) {
@@ -564,6 +653,125 @@ fn test_render_mrt_different_formats(#[case] backend: Backend) {
     ctx.execute_graph(graph);
 }
 
+// ============================================================================
+// Texture Blit Tests
+// ============================================================================
+
+/// Usage needed for blits on every backend.
+const BLIT_USAGE: TextureUsage = TextureUsage::TEXTURE_BINDING
+    .union(TextureUsage::RENDER_ATTACHMENT)
+    .union(TextureUsage::COPY_SRC)
+    .union(TextureUsage::COPY_DST);
+
+/// Pixels of a `width`x`height` image whose left half is red and right half
+/// blue.
+fn red_blue_halves(width: u32, height: u32) -> Vec<u8> {
+    (0..width * height)
+        .flat_map(|i| {
+            if i % width < width / 2 {
+                [255, 0, 0, 255]
+            } else {
+                [0, 0, 255, 255]
+            }
+        })
+        .collect()
+}
+
+/// Read back one row of mip level `mip` of `texture`.
+fn read_mip_row(ctx: &TestContext, texture: &Arc<Texture>, mip: u32) -> Vec<u8> {
+    let width = texture.descriptor().mip_level_extent(mip).width;
+    let readback = ctx.create_readback_buffer(readback_buffer_size(width, 1, 4));
+
+    let mut graph = RenderGraph::new();
+    let mut copy_pass = TransferPass::new("readback_mip".into());
+    copy_pass.set_transfer_config(TransferConfig::new().with_operation(
+        TransferOperation::readback_texture(
+            texture.clone(),
+            readback.clone(),
+            vec![BufferTextureCopyRegion::new(
+                BufferTextureLayout::packed(),
+                TextureCopyLocation::mip(mip),
+                Extent3d::new_2d(width, 1),
+            )],
+        ),
+    ));
+    graph.add_transfer_pass(copy_pass);
+    ctx.execute_graph(graph);
+
+    ctx.device.read_buffer(&readback, 0, u64::from(width) * 4)
+}
+
+/// Test filling a mip chain with `GraphicsDevice::generate_mipmaps`.
+#[rstest]
+#[case::software(Backend::Software)]
+#[case::vulkan(Backend::Vulkan)]
+#[case::webgpu(Backend::WebGpu)]
+fn test_generate_mipmaps(#[case] backend: Backend) {
+    let Some(ctx) = TestContext::new(backend) else {
+        eprintln!("Backend {:?} not available, skipping", backend);
+        return;
+    };
+
+    let texture = ctx
+        .device
+        .create_texture(
+            &TextureDescriptor::new_2d(4, 4, TextureFormat::Rgba8Unorm, BLIT_USAGE)
+                .with_full_mip_chain(),
+        )
+        .expect("Failed to create texture");
+    assert_eq!(texture.mip_level_count(), 3);
+
+    ctx.device
+        .write_texture(&texture, &red_blue_halves(4, 4))
+        .expect("Failed to write texture");
+    texture
+        .generate_mipmaps()
+        .expect("Failed to generate mipmaps");
+
+    // Mip 1 keeps the halves, the 1x1 mip averages them
+    let mip1 = read_mip_row(&ctx, &texture, 1);
+    assert!(verify_pixel(&mip1, 2, 0, 0, ExpectedPixel::RED, 2));
+    assert!(verify_pixel(&mip1, 2, 1, 0, ExpectedPixel::BLUE, 2));
+    let mip2 = read_mip_row(&ctx, &texture, 2);
+    let purple = ExpectedPixel::new(128, 0, 128, 255);
+    assert!(
+        verify_pixel(&mip2, 1, 0, 0, purple, 2),
+        "1x1 mip should be {:?}, but got {:?}",
+        purple,
+        get_pixel(&mip2, 1, 0, 0)
+    );
+}
+
+/// Test upscaling a texture with a nearest-filtered blit in a transfer pass.
+#[rstest]
+#[case::software(Backend::Software)]
+#[case::vulkan(Backend::Vulkan)]
+#[case::webgpu(Backend::WebGpu)]
+fn test_blit_nearest_upscale(#[case] backend: Backend) {
+    let Some(ctx) = TestContext::new(backend) else {
+        eprintln!("Backend {:?} not available, skipping", backend);
+        return;
+    };
+
+    let src = ctx.create_texture_2d(2, 2, TextureFormat::Rgba8Unorm, BLIT_USAGE);
+    let dst = ctx.create_texture_2d(8, 8, TextureFormat::Rgba8Unorm, BLIT_USAGE);
+    ctx.device
+        .write_texture(&src, &red_blue_halves(2, 2))
+        .expect("Failed to write texture");
+
+    let mut graph = RenderGraph::new();
+    let mut blit_pass = TransferPass::new("blit".into());
+    blit_pass.set_transfer_config(TransferConfig::new().with_operation(
+        TransferOperation::blit_whole(src, dst.clone(), FilterMode::Nearest),
+    ));
+    graph.add_transfer_pass(blit_pass);
+    ctx.execute_graph(graph);
+
+    let row = read_mip_row(&ctx, &dst, 0);
+    assert!(verify_pixel(&row, 8, 3, 0, ExpectedPixel::RED, 0));
+    assert!(verify_pixel(&row, 8, 4, 0, ExpectedPixel::BLUE, 0));
+}
+
 // ============================================================================
 // Additional Tests
 // ============================================================================
@@ -571,6 +779,7 @@ fn test_render_mrt_different_formats(#[case] backend: Backend) {
 /// Test that an empty render graph executes without errors.
 #[rstest]
 #[case::dummy(Backend::Dummy)]
+#[case::software(Backend::Software)]
 #[case::vulkan(Backend::Vulkan)]
 #[case::webgpu(Backend::WebGpu)]
 fn test_empty_graph(#[case] _backend: Backend) {
@@ -586,6 +795,7 @@ fn test_empty_graph(#[case] _backend: Backend) {
 /// Test complex dependency graph with diamond pattern.
 #[rstest]
 #[case::dummy(Backend::Dummy)]
+#[case::software(Backend::Software)]
 #[case::vulkan(Backend::Vulkan)]
 #[case::webgpu(Backend::WebGpu)]
 fn test_diamond_dependency_graph(#[case] backend: Backend) {
@@ -670,6 +880,7 @@ fn test_diamond_dependency_graph(#[case] backend: Backend) {
 /// 4. Texture readback returns expected pixel values
 #[rstest]
 #[case::dummy(Backend::Dummy)]
+#[case::software(Backend::Software)]
 #[case::vulkan(Backend::Vulkan)]
 #[case::webgpu(Backend::WebGpu)]
 fn test_shader_render_half_quad(#[case] backend: Backend) {
@@ -787,6 +998,7 @@ fn test_shader_render_half_quad(#[case] backend: Backend) {
 /// material binding layouts in pipeline creation.
 #[rstest]
 #[case::dummy(Backend::Dummy)]
+#[case::software(Backend::Software)]
 #[case::vulkan(Backend::Vulkan)]
 #[case::webgpu(Backend::WebGpu)]
 fn test_layout_tracking_multi_pass(#[case] backend: Backend) {
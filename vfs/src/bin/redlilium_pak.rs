@@ -0,0 +1,133 @@
+//! Command-line tool for building and inspecting `.pak` files.
+//!
+//! ```text
+//! # Pack a mount's directory; mount the result under the same name
+//! redlilium-pak pack ./assets build/assets.pak --compression zstd
+//!
+//! # List the contents of a pack or zip archive
+//! redlilium-pak list build/assets.pak
+//! ```
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use redlilium_vfs::{
+    FileSystemProvider, PackProvider, PakCompression, PakWriter, Vfs, VfsError, VfsProvider,
+    poll_now,
+};
+
+/// Build and inspect RedLilium `.pak` files.
+#[derive(Parser, Debug)]
+#[command(name = "redlilium-pak", version)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Pack every file under a directory into a `.pak`.
+    Pack {
+        /// Directory to pack, usually the `path` of a mount in `project.toml`.
+        source: PathBuf,
+        /// Output `.pak` file.
+        output: PathBuf,
+        /// Compression applied to each entry.
+        #[arg(long, default_value = "zstd", value_enum)]
+        compression: CliCompression,
+    },
+    /// List the files in a `.pak` or zip archive.
+    List {
+        /// Pack file to inspect.
+        pack: PathBuf,
+    },
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum CliCompression {
+    None,
+    Lz4,
+    Zstd,
+}
+
+impl From<CliCompression> for PakCompression {
+    fn from(value: CliCompression) -> Self {
+        match value {
+            CliCompression::None => PakCompression::None,
+            CliCompression::Lz4 => PakCompression::Lz4,
+            CliCompression::Zstd => PakCompression::Zstd,
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let result = match args.command {
+        Command::Pack {
+            source,
+            output,
+            compression,
+        } => pack(source, &output, compression.into()),
+        Command::List { pack } => list(&pack),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn pack(
+    source: PathBuf,
+    output: &std::path::Path,
+    compression: PakCompression,
+) -> Result<(), VfsError> {
+    if !source.is_dir() {
+        return Err(VfsError::NotFound(source.display().to_string()));
+    }
+    let vfs = Vfs::new();
+    vfs.mount("source", FileSystemProvider::new(source));
+
+    let file = std::io::BufWriter::new(std::fs::File::create(output)?);
+    let writer = PakWriter::new(file)?.with_compression(compression);
+    let writer = poll_now(writer.add_vfs_dir(&vfs, "source"))?;
+    let count = writer.len();
+    writer.finish()?;
+
+    let size = std::fs::metadata(output)?.len();
+    println!(
+        "Packed {count} files into {} ({size} bytes)",
+        output.display()
+    );
+    Ok(())
+}
+
+fn list(path: &std::path::Path) -> Result<(), VfsError> {
+    let pack = PackProvider::open(path)?;
+    let mut pending = vec![String::new()];
+    let mut files = Vec::new();
+    while let Some(dir) = pending.pop() {
+        for entry in poll_now(pack.list_dir_detailed(&dir))? {
+            let path = if dir.is_empty() {
+                entry.name
+            } else {
+                format!("{dir}/{}", entry.name)
+            };
+            if entry.metadata.is_dir() {
+                pending.push(path);
+            } else {
+                files.push((path, entry.metadata.size));
+            }
+        }
+    }
+    files.sort();
+    for (path, size) in &files {
+        println!("{size:>12}  {path}");
+    }
+    let total: u64 = files.iter().map(|(_, size)| size).sum();
+    println!("{} files, {total} bytes", pack.file_count());
+    Ok(())
+}
@@ -0,0 +1,924 @@
+use std::collections::{BTreeSet, HashMap};
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::VfsError;
+use crate::metadata::{self, VfsDirEntry, VfsMetadata};
+use crate::provider::{VfsFuture, VfsProvider};
+use crate::watch::VfsWatcher;
+
+const INDEX_FILE: &str = "index.json";
+const OBJECTS_DIR: &str = "objects";
+/// Minimum time between index saves triggered by stores and removals.
+const SAVE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Configuration for [`CachingProvider`].
+#[derive(Debug, Clone)]
+pub struct CacheConfig {
+    /// Directory holding the cached contents and their index. Created on open.
+    pub dir: PathBuf,
+    /// Byte budget of cached contents. Least recently used files are evicted
+    /// beyond it.
+    pub max_size: u64,
+    /// Start in offline mode (see [`CachingProvider::set_offline()`]).
+    pub offline: bool,
+}
+
+impl CacheConfig {
+    /// A 1 GiB cache in `dir`.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            max_size: 1024 * 1024 * 1024,
+            offline: false,
+        }
+    }
+}
+
+/// Provider keeping an on-disk copy of everything read from a slower one.
+///
+/// Wraps a remote provider such as [`SftpProvider`](crate::SftpProvider) or
+/// [`HttpProvider`](crate::HttpProvider). Each read first asks the remote
+/// for the file's metadata; when size and modification time match the cached
+/// copy, the contents come from disk instead of the network. Remotes that
+/// don't report modification times are always re-read.
+///
+/// - **Content addressing** — contents are stored by SHA-256, so identical
+///   files share one copy. The index survives restarts.
+/// - **Size limit** — beyond [`CacheConfig::max_size`] the least recently
+///   used files are evicted.
+/// - **Offline mode** — when the remote fails with an I/O error, reads,
+///   metadata and listings are answered from the cache. [`set_offline()`]
+///   skips the remote entirely.
+/// - **Write-through** — `write` and `delete` go to the remote first and
+///   update the cache once it succeeds.
+///
+/// [`set_offline()`]: Self::set_offline
+///
+/// # Example
+///
+/// ```ignore
+/// let remote = SftpProvider::connect(config)?;
+/// let cached = CachingProvider::new(remote, CacheConfig::new(".cache/vfs/remote"))?;
+/// vfs.mount("remote", cached);
+/// ```
+pub struct CachingProvider<P: VfsProvider> {
+    inner: Arc<P>,
+    cache: Arc<Mutex<DiskCache>>,
+    offline: Arc<AtomicBool>,
+}
+
+impl<P: VfsProvider> Clone for CachingProvider<P> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+            cache: Arc::clone(&self.cache),
+            offline: Arc::clone(&self.offline),
+        }
+    }
+}
+
+impl<P: VfsProvider> CachingProvider<P> {
+    /// Wrap `inner`, opening or creating the cache in [`CacheConfig::dir`].
+    pub fn new(inner: P, config: CacheConfig) -> Result<Self, VfsError> {
+        Ok(Self {
+            inner: Arc::new(inner),
+            cache: Arc::new(Mutex::new(DiskCache::open(&config.dir, config.max_size)?)),
+            offline: Arc::new(AtomicBool::new(config.offline)),
+        })
+    }
+
+    /// The wrapped provider.
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    /// Serve everything from the cache without contacting the remote.
+    ///
+    /// Uncached files are reported as not found, and writes fail.
+    pub fn set_offline(&self, offline: bool) {
+        self.offline.store(offline, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if offline mode is on.
+    pub fn is_offline(&self) -> bool {
+        self.offline.load(Ordering::Relaxed)
+    }
+
+    /// Total size of the cached contents in bytes.
+    pub fn cached_size(&self) -> u64 {
+        self.cache.lock().unwrap().size
+    }
+
+    /// Remove every cached file.
+    pub fn clear(&self) -> Result<(), VfsError> {
+        Ok(self.cache.lock().unwrap().clear()?)
+    }
+
+    fn check_online(&self) -> Result<(), VfsError> {
+        if self.is_offline() {
+            Err(VfsError::Io(io::Error::new(
+                io::ErrorKind::NotConnected,
+                "caching provider is offline",
+            )))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<P: VfsProvider> VfsProvider for CachingProvider<P> {
+    fn read(&self, path: &str) -> VfsFuture<Vec<u8>> {
+        let inner = Arc::clone(&self.inner);
+        let cache = Arc::clone(&self.cache);
+        let offline = self.is_offline();
+        let path = path.to_owned();
+        Box::pin(async move {
+            if offline {
+                return read_cached(&cache, &path, None);
+            }
+            let metadata = match inner.metadata(&path).await {
+                Ok(metadata) => metadata,
+                Err(err) => return fallback(&cache, &path, err, |blob| Ok(std::fs::read(blob)?)),
+            };
+            let fresh = cache.lock().unwrap().lookup(&path, &metadata);
+            if let Some(data) = fresh.and_then(|blob| std::fs::read(blob).ok()) {
+                return Ok(data);
+            }
+            let data = match inner.read(&path).await {
+                Ok(data) => data,
+                Err(err) => return fallback(&cache, &path, err, |blob| Ok(std::fs::read(blob)?)),
+            };
+            if let Err(e) = store(&cache, &path, &metadata, &data) {
+                log::warn!("Failed to cache \"{path}\": {e}");
+            }
+            Ok(data)
+        })
+    }
+
+    fn metadata(&self, path: &str) -> VfsFuture<VfsMetadata> {
+        let inner = Arc::clone(&self.inner);
+        let cache = Arc::clone(&self.cache);
+        let offline = self.is_offline();
+        let path = path.to_owned();
+        Box::pin(async move {
+            let err = if offline {
+                VfsError::NotFound(path.clone())
+            } else {
+                match inner.metadata(&path).await {
+                    Ok(metadata) => return Ok(metadata),
+                    Err(VfsError::NotFound(_)) => {
+                        cache.lock().unwrap().remove(&path)?;
+                        return Err(VfsError::NotFound(path));
+                    }
+                    Err(err @ VfsError::Io(_)) => err,
+                    Err(err) => return Err(err),
+                }
+            };
+            let cache = cache.lock().unwrap();
+            if let Some(entry) = cache.index.entries.get(&path) {
+                return Ok(entry.metadata());
+            }
+            let prefix = format!("{path}/");
+            if path.is_empty() || cache.index.entries.keys().any(|p| p.starts_with(&prefix)) {
+                return Ok(VfsMetadata::directory(None));
+            }
+            Err(err)
+        })
+    }
+
+    fn list_dir_detailed(&self, path: &str) -> VfsFuture<Vec<VfsDirEntry>> {
+        let inner = Arc::clone(&self.inner);
+        let cache = Arc::clone(&self.cache);
+        let offline = self.is_offline();
+        let path = path.to_owned();
+        Box::pin(async move {
+            if !offline {
+                match inner.list_dir_detailed(&path).await {
+                    Err(VfsError::Io(e)) => log::debug!("Listing \"{path}\" from cache: {e}"),
+                    result => return result,
+                }
+            }
+            let cache = cache.lock().unwrap();
+            let mut dirs = metadata::build_dirs(
+                cache
+                    .index
+                    .entries
+                    .iter()
+                    .map(|(path, entry)| (path.as_str(), entry.metadata())),
+            );
+            Ok(dirs.remove(&path).unwrap_or_default())
+        })
+    }
+
+    fn read_range(&self, path: &str, offset: u64, len: u64) -> VfsFuture<Vec<u8>> {
+        let inner = Arc::clone(&self.inner);
+        let cache = Arc::clone(&self.cache);
+        let offline = self.is_offline();
+        let path = path.to_owned();
+        Box::pin(async move {
+            let read_blob = |blob: &Path| Ok(read_blob_range(blob, offset, len)?);
+            if offline {
+                return read_cached(&cache, &path, Some((offset, len)));
+            }
+            let metadata = match inner.metadata(&path).await {
+                Ok(metadata) => metadata,
+                Err(err) => return fallback(&cache, &path, err, read_blob),
+            };
+            let fresh = cache.lock().unwrap().lookup(&path, &metadata);
+            if let Some(data) = fresh.and_then(|blob| read_blob_range(&blob, offset, len).ok()) {
+                return Ok(data);
+            }
+            match inner.read_range(&path, offset, len).await {
+                Ok(data) => Ok(data),
+                Err(err) => fallback(&cache, &path, err, read_blob),
+            }
+        })
+    }
+
+    fn is_read_only(&self) -> bool {
+        self.inner.is_read_only()
+    }
+
+    fn write(&self, path: &str, data: Vec<u8>) -> VfsFuture<()> {
+        if let Err(e) = self.check_online() {
+            return Box::pin(async move { Err(e) });
+        }
+        let inner = Arc::clone(&self.inner);
+        let cache = Arc::clone(&self.cache);
+        let path = path.to_owned();
+        Box::pin(async move {
+            inner.write(&path, data.clone()).await?;
+            // Key the cached copy by the remote's metadata after the write.
+            let stored = match inner.metadata(&path).await {
+                Ok(metadata) => store(&cache, &path, &metadata, &data),
+                Err(_) => cache.lock().unwrap().remove(&path),
+            };
+            if let Err(e) = stored {
+                log::warn!("Failed to cache \"{path}\": {e}");
+            }
+            Ok(())
+        })
+    }
+
+    fn delete(&self, path: &str) -> VfsFuture<()> {
+        if let Err(e) = self.check_online() {
+            return Box::pin(async move { Err(e) });
+        }
+        let cache = Arc::clone(&self.cache);
+        let delete = self.inner.delete(path);
+        let path = path.to_owned();
+        Box::pin(async move {
+            delete.await?;
+            Ok(cache.lock().unwrap().remove(&path)?)
+        })
+    }
+
+    fn create_dir(&self, path: &str) -> VfsFuture<()> {
+        if let Err(e) = self.check_online() {
+            return Box::pin(async move { Err(e) });
+        }
+        self.inner.create_dir(path)
+    }
+
+    fn watch(&self) -> Option<VfsWatcher> {
+        self.inner.watch()
+    }
+}
+
+/// Cache `data` as the contents of `path`.
+///
+/// Hashing and writing the blob happen outside the lock so lookups aren't
+/// held up by large files; the lock is only taken to commit it.
+fn store(
+    cache: &Mutex<DiskCache>,
+    path: &str,
+    metadata: &VfsMetadata,
+    data: &[u8],
+) -> io::Result<()> {
+    let (objects, max_size) = {
+        let cache = cache.lock().unwrap();
+        (cache.dir.join(OBJECTS_DIR), cache.max_size)
+    };
+    if data.len() as u64 > max_size {
+        return cache.lock().unwrap().remove(path);
+    }
+    let blob = PendingBlob::write(&objects, data)?;
+    cache.lock().unwrap().store(path, metadata, blob)
+}
+
+/// Contents hashed and written to a temporary file, waiting for
+/// [`DiskCache::store`] to move them into place.
+struct PendingBlob {
+    hash: String,
+    size: u64,
+    tmp: PathBuf,
+}
+
+impl PendingBlob {
+    fn write(objects: &Path, data: &[u8]) -> io::Result<Self> {
+        static NEXT_TMP: AtomicU64 = AtomicU64::new(0);
+        let hash: String = Sha256::digest(data)
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        let dir = objects.join(&hash[..2]);
+        std::fs::create_dir_all(&dir)?;
+        // Unique per write so concurrent stores of equal contents don't collide.
+        let tmp = dir.join(format!(
+            "{hash}.{}.tmp",
+            NEXT_TMP.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::write(&tmp, data)?;
+        Ok(Self {
+            hash,
+            size: data.len() as u64,
+            tmp,
+        })
+    }
+}
+
+/// Serve `path` from the cache regardless of freshness, optionally a range.
+fn read_cached(
+    cache: &Mutex<DiskCache>,
+    path: &str,
+    range: Option<(u64, u64)>,
+) -> Result<Vec<u8>, VfsError> {
+    let blob = cache
+        .lock()
+        .unwrap()
+        .cached(path)
+        .ok_or_else(|| VfsError::NotFound(path.to_owned()))?;
+    Ok(match range {
+        Some((offset, len)) => read_blob_range(&blob, offset, len)?,
+        None => std::fs::read(blob)?,
+    })
+}
+
+/// Answer from the cache when the remote is unreachable, otherwise pass the
+/// error on. A remote `NotFound` drops the stale cache entry.
+fn fallback<T>(
+    cache: &Mutex<DiskCache>,
+    path: &str,
+    err: VfsError,
+    read: impl FnOnce(&Path) -> Result<T, VfsError>,
+) -> Result<T, VfsError> {
+    match err {
+        VfsError::Io(e) => {
+            let Some(blob) = cache.lock().unwrap().cached(path) else {
+                return Err(VfsError::Io(e));
+            };
+            log::debug!("Serving \"{path}\" from cache: {e}");
+            read(&blob)
+        }
+        VfsError::NotFound(_) => {
+            cache.lock().unwrap().remove(path)?;
+            Err(err)
+        }
+        err => Err(err),
+    }
+}
+
+/// Whether `hash` is a lowercase hex SHA-256, as blob names must be.
+fn is_valid_hash(hash: &str) -> bool {
+    hash.len() == 64 && hash.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+fn read_blob_range(blob: &Path, offset: u64, len: u64) -> io::Result<Vec<u8>> {
+    let mut file = std::fs::File::open(blob)?;
+    file.seek(SeekFrom::Start(offset))?;
+    let mut data = Vec::new();
+    file.take(len).read_to_end(&mut data)?;
+    Ok(data)
+}
+
+/// Persistent part of the cache, stored as `index.json`.
+#[derive(Default, Serialize, Deserialize)]
+struct CacheIndex {
+    /// Monotonic counter driving LRU order.
+    tick: u64,
+    entries: HashMap<String, CacheEntry>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    size: u64,
+    /// Modification time as reported by the remote, since the Unix epoch.
+    modified: Option<Duration>,
+    /// Hex SHA-256 of the contents, naming the blob.
+    hash: String,
+    last_used: u64,
+}
+
+impl CacheEntry {
+    fn metadata(&self) -> VfsMetadata {
+        VfsMetadata::file(
+            self.size,
+            self.modified.and_then(|d| UNIX_EPOCH.checked_add(d)),
+        )
+    }
+}
+
+/// Blobs under `objects/` plus the index mapping paths to them.
+struct DiskCache {
+    dir: PathBuf,
+    max_size: u64,
+    index: CacheIndex,
+    /// Number of index entries referencing each blob.
+    refs: HashMap<String, usize>,
+    /// Entries ordered by `(last_used, path)`, oldest first.
+    lru: BTreeSet<(u64, String)>,
+    /// Total size of all blobs.
+    size: u64,
+    /// The index changed since it was last saved.
+    dirty: bool,
+    last_save: Instant,
+}
+
+impl DiskCache {
+    fn open(dir: &Path, max_size: u64) -> io::Result<Self> {
+        std::fs::create_dir_all(dir.join(OBJECTS_DIR))?;
+        let index = match std::fs::read(dir.join(INDEX_FILE)) {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+                log::warn!("Discarding unreadable VFS cache index in {dir:?}: {e}");
+                CacheIndex::default()
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => CacheIndex::default(),
+            Err(e) => return Err(e),
+        };
+        let mut cache = Self {
+            dir: dir.to_owned(),
+            max_size,
+            index: CacheIndex {
+                tick: index.tick,
+                entries: HashMap::new(),
+            },
+            refs: HashMap::new(),
+            lru: BTreeSet::new(),
+            size: 0,
+            dirty: false,
+            last_save: Instant::now(),
+        };
+        for (path, entry) in index.entries {
+            // Skip corrupt hashes, which must not name paths, and entries
+            // whose blob went missing.
+            if is_valid_hash(&entry.hash) && cache.blob_path(&entry.hash).is_file() {
+                cache.add_entry(path, entry);
+            }
+        }
+        cache.remove_orphans()?;
+        cache.evict()?;
+        Ok(cache)
+    }
+
+    /// Delete blobs no entry references, left behind when the process
+    /// exited before the index was saved.
+    fn remove_orphans(&self) -> io::Result<()> {
+        for shard in std::fs::read_dir(self.dir.join(OBJECTS_DIR))? {
+            let shard = shard?;
+            if !shard.file_type()?.is_dir() {
+                continue;
+            }
+            for blob in std::fs::read_dir(shard.path())? {
+                let blob = blob?;
+                let referenced = blob
+                    .file_name()
+                    .to_str()
+                    .is_some_and(|hash| self.refs.contains_key(hash));
+                if !referenced {
+                    std::fs::remove_file(blob.path())?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn blob_path(&self, hash: &str) -> PathBuf {
+        self.dir.join(OBJECTS_DIR).join(&hash[..2]).join(hash)
+    }
+
+    fn touch(&mut self, path: &str) -> Option<PathBuf> {
+        self.index.tick += 1;
+        let tick = self.index.tick;
+        let entry = self.index.entries.get_mut(path)?;
+        let previous = std::mem::replace(&mut entry.last_used, tick);
+        let hash = entry.hash.clone();
+        self.lru.remove(&(previous, path.to_owned()));
+        self.lru.insert((tick, path.to_owned()));
+        self.dirty = true;
+        Some(self.blob_path(&hash))
+    }
+
+    /// Blob of `path` if it was cached with the same size and modification time.
+    fn lookup(&mut self, path: &str, metadata: &VfsMetadata) -> Option<PathBuf> {
+        let modified = metadata.modified?.duration_since(UNIX_EPOCH).ok();
+        let entry = self.index.entries.get(path)?;
+        if entry.size != metadata.size || entry.modified != modified {
+            return None;
+        }
+        self.touch(path)
+    }
+
+    /// Blob of `path`, however old.
+    fn cached(&mut self, path: &str) -> Option<PathBuf> {
+        self.touch(path)
+    }
+
+    /// Commit `blob` as the contents of `path`.
+    fn store(&mut self, path: &str, metadata: &VfsMetadata, blob: PendingBlob) -> io::Result<()> {
+        self.remove_entry(path)?;
+        if self.refs.contains_key(&blob.hash) {
+            // Another entry already holds these contents.
+            let _ = std::fs::remove_file(&blob.tmp);
+        } else {
+            std::fs::rename(&blob.tmp, self.blob_path(&blob.hash))?;
+        }
+        self.index.tick += 1;
+        let entry = CacheEntry {
+            size: blob.size,
+            modified: metadata
+                .modified
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok()),
+            hash: blob.hash,
+            last_used: self.index.tick,
+        };
+        self.add_entry(path.to_owned(), entry);
+        self.evict()?;
+        self.mark_dirty()
+    }
+
+    fn remove(&mut self, path: &str) -> io::Result<()> {
+        if self.index.entries.contains_key(path) {
+            self.remove_entry(path)?;
+            self.mark_dirty()?;
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.index.entries.clear();
+        self.refs.clear();
+        self.lru.clear();
+        self.size = 0;
+        match std::fs::remove_dir_all(self.dir.join(OBJECTS_DIR)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+            _ => {}
+        }
+        std::fs::create_dir_all(self.dir.join(OBJECTS_DIR))?;
+        self.save()
+    }
+
+    fn add_entry(&mut self, path: String, entry: CacheEntry) {
+        let refs = self.refs.entry(entry.hash.clone()).or_insert(0);
+        if *refs == 0 {
+            self.size += entry.size;
+        }
+        *refs += 1;
+        self.lru.insert((entry.last_used, path.clone()));
+        self.index.entries.insert(path, entry);
+    }
+
+    /// Drop the entry of `path`, deleting its blob once unreferenced.
+    fn remove_entry(&mut self, path: &str) -> io::Result<()> {
+        let Some(entry) = self.index.entries.remove(path) else {
+            return Ok(());
+        };
+        self.lru.remove(&(entry.last_used, path.to_owned()));
+        let refs = self
+            .refs
+            .get_mut(&entry.hash)
+            .expect("cache entry without refs");
+        *refs -= 1;
+        if *refs == 0 {
+            self.refs.remove(&entry.hash);
+            self.size -= entry.size;
+            match std::fs::remove_file(self.blob_path(&entry.hash)) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Evict least recently used entries until the cache fits its budget.
+    fn evict(&mut self) -> io::Result<()> {
+        while self.size > self.max_size {
+            let Some((_, oldest)) = self.lru.first().cloned() else {
+                break;
+            };
+            self.remove_entry(&oldest)?;
+        }
+        Ok(())
+    }
+
+    /// Record an index change, saving at most once per [`SAVE_INTERVAL`].
+    /// Anything still unsaved is written on drop.
+    fn mark_dirty(&mut self) -> io::Result<()> {
+        self.dirty = true;
+        if self.last_save.elapsed() >= SAVE_INTERVAL {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    fn save(&mut self) -> io::Result<()> {
+        let json = serde_json::to_vec(&self.index).map_err(io::Error::other)?;
+        let tmp = self.dir.join(format!("{INDEX_FILE}.tmp"));
+        std::fs::write(&tmp, json)?;
+        std::fs::rename(&tmp, self.dir.join(INDEX_FILE))?;
+        self.dirty = false;
+        self.last_save = Instant::now();
+        Ok(())
+    }
+}
+
+impl Drop for DiskCache {
+    fn drop(&mut self) {
+        // Persist changes the save throttle held back.
+        if self.dirty
+            && let Err(e) = self.save()
+        {
+            log::warn!("Failed to save VFS cache index in {:?}: {e}", self.dir);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MemoryProvider;
+    use std::sync::atomic::AtomicUsize;
+    use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+    fn poll_ready<T>(mut fut: VfsFuture<T>) -> Result<T, VfsError> {
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        match std::pin::Pin::new(&mut fut).poll(&mut cx) {
+            Poll::Ready(val) => val,
+            Poll::Pending => panic!("expected future to be immediately ready"),
+        }
+    }
+
+    fn noop_waker() -> Waker {
+        fn noop(_: *const ()) {}
+        fn clone(p: *const ()) -> RawWaker {
+            RawWaker::new(p, &VTABLE)
+        }
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("redlilium_vfs_cache_test_{name}"));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    /// Memory provider standing in for a remote that can go down.
+    #[derive(Clone, Default)]
+    struct Remote {
+        files: MemoryProvider,
+        down: Arc<AtomicBool>,
+        reads: Arc<AtomicUsize>,
+    }
+
+    impl Remote {
+        fn reads(&self) -> usize {
+            self.reads.load(Ordering::SeqCst)
+        }
+
+        fn set_down(&self, down: bool) {
+            self.down.store(down, Ordering::SeqCst);
+        }
+
+        fn check(&self) -> Result<(), VfsError> {
+            if self.down.load(Ordering::SeqCst) {
+                Err(VfsError::Io(io::Error::from(
+                    io::ErrorKind::ConnectionRefused,
+                )))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    impl VfsProvider for Remote {
+        fn read(&self, path: &str) -> VfsFuture<Vec<u8>> {
+            if let Err(e) = self.check() {
+                return Box::pin(async move { Err(e) });
+            }
+            self.reads.fetch_add(1, Ordering::SeqCst);
+            self.files.read(path)
+        }
+
+        fn metadata(&self, path: &str) -> VfsFuture<VfsMetadata> {
+            if let Err(e) = self.check() {
+                return Box::pin(async move { Err(e) });
+            }
+            self.files.metadata(path)
+        }
+
+        fn list_dir_detailed(&self, path: &str) -> VfsFuture<Vec<VfsDirEntry>> {
+            if let Err(e) = self.check() {
+                return Box::pin(async move { Err(e) });
+            }
+            self.files.list_dir_detailed(path)
+        }
+
+        fn is_read_only(&self) -> bool {
+            false
+        }
+
+        fn write(&self, path: &str, data: Vec<u8>) -> VfsFuture<()> {
+            self.files.write(path, data)
+        }
+
+        fn delete(&self, path: &str) -> VfsFuture<()> {
+            self.files.delete(path)
+        }
+    }
+
+    #[test]
+    fn unchanged_files_are_read_from_disk() {
+        let dir = temp_dir("hits");
+        let remote = Remote::default();
+        remote.files.insert("textures/brick.png", vec![1; 100]);
+        let cached = CachingProvider::new(remote.clone(), CacheConfig::new(&dir)).unwrap();
+
+        assert_eq!(
+            poll_ready(cached.read("textures/brick.png")).unwrap(),
+            vec![1; 100]
+        );
+        assert_eq!(
+            poll_ready(cached.read("textures/brick.png")).unwrap(),
+            vec![1; 100]
+        );
+        assert_eq!(
+            poll_ready(cached.read_range("textures/brick.png", 90, 20)).unwrap(),
+            vec![1; 10]
+        );
+        assert_eq!(remote.reads(), 1);
+        assert_eq!(cached.cached_size(), 100);
+
+        // A changed file is fetched again.
+        remote.files.insert("textures/brick.png", vec![2; 50]);
+        assert_eq!(
+            poll_ready(cached.read("textures/brick.png")).unwrap(),
+            vec![2; 50]
+        );
+        assert_eq!(remote.reads(), 2);
+        assert_eq!(cached.cached_size(), 50);
+
+        // The index survives a restart.
+        drop(cached);
+        let cached = CachingProvider::new(remote.clone(), CacheConfig::new(&dir)).unwrap();
+        assert_eq!(
+            poll_ready(cached.read("textures/brick.png")).unwrap(),
+            vec![2; 50]
+        );
+        assert_eq!(remote.reads(), 2);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn identical_contents_share_storage() {
+        let dir = temp_dir("dedup");
+        let remote = Remote::default();
+        remote.files.insert("a.bin", vec![7; 64]);
+        remote.files.insert("copy/a.bin", vec![7; 64]);
+        let cached = CachingProvider::new(remote, CacheConfig::new(&dir)).unwrap();
+
+        poll_ready(cached.read("a.bin")).unwrap();
+        poll_ready(cached.read("copy/a.bin")).unwrap();
+        assert_eq!(cached.cached_size(), 64);
+
+        cached.clear().unwrap();
+        assert_eq!(cached.cached_size(), 0);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn least_recently_used_files_are_evicted() {
+        let dir = temp_dir("lru");
+        let remote = Remote::default();
+        for name in ["a", "b", "c"] {
+            remote.files.insert(name, name.repeat(100).into_bytes());
+        }
+        let config = CacheConfig {
+            max_size: 250,
+            ..CacheConfig::new(&dir)
+        };
+        let cached = CachingProvider::new(remote.clone(), config).unwrap();
+
+        poll_ready(cached.read("a")).unwrap();
+        poll_ready(cached.read("b")).unwrap();
+        poll_ready(cached.read("a")).unwrap();
+        poll_ready(cached.read("c")).unwrap();
+        assert_eq!(remote.reads(), 3);
+        assert_eq!(cached.cached_size(), 200);
+
+        // "b" was evicted, "a" and "c" are still cached.
+        poll_ready(cached.read("a")).unwrap();
+        poll_ready(cached.read("c")).unwrap();
+        assert_eq!(remote.reads(), 3);
+        poll_ready(cached.read("b")).unwrap();
+        assert_eq!(remote.reads(), 4);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn corrupt_index_entries_and_orphan_blobs_are_dropped() {
+        let dir = temp_dir("corrupt");
+        let orphan = dir.join(OBJECTS_DIR).join("ff").join("f".repeat(64));
+        std::fs::create_dir_all(orphan.parent().unwrap()).unwrap();
+        std::fs::write(&orphan, b"stale").unwrap();
+        std::fs::write(
+            dir.join(INDEX_FILE),
+            r#"{"tick": 2, "entries": {
+                "a": {"size": 1, "modified": null, "hash": "a", "last_used": 1},
+                "b": {"size": 1, "modified": null, "hash": "../../../../etc/passwd", "last_used": 2}
+            }}"#,
+        )
+        .unwrap();
+
+        let remote = Remote::default();
+        remote.files.insert("a", vec![1]);
+        let cached = CachingProvider::new(remote.clone(), CacheConfig::new(&dir)).unwrap();
+        assert_eq!(cached.cached_size(), 0);
+        assert!(!orphan.exists());
+        assert_eq!(poll_ready(cached.read("a")).unwrap(), vec![1]);
+        assert_eq!(remote.reads(), 1);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn serves_cache_while_remote_is_unreachable() {
+        let dir = temp_dir("offline");
+        let remote = Remote::default();
+        remote.files.insert("levels/one.map", b"one".to_vec());
+        remote.files.insert("levels/two.map", b"two".to_vec());
+        let cached = CachingProvider::new(remote.clone(), CacheConfig::new(&dir)).unwrap();
+        poll_ready(cached.read("levels/one.map")).unwrap();
+
+        remote.set_down(true);
+        assert_eq!(poll_ready(cached.read("levels/one.map")).unwrap(), b"one");
+        assert_eq!(
+            poll_ready(cached.read_range("levels/one.map", 1, 5)).unwrap(),
+            b"ne"
+        );
+        assert!(poll_ready(cached.metadata("levels")).unwrap().is_dir());
+        assert_eq!(
+            poll_ready(cached.list_dir("levels")).unwrap(),
+            vec!["one.map"]
+        );
+        assert!(matches!(
+            poll_ready(cached.read("levels/two.map")),
+            Err(VfsError::Io(_))
+        ));
+
+        // Explicit offline mode never touches the remote.
+        remote.set_down(false);
+        cached.set_offline(true);
+        assert_eq!(poll_ready(cached.read("levels/one.map")).unwrap(), b"one");
+        assert!(!poll_ready(cached.exists("levels/two.map")).unwrap());
+        assert!(poll_ready(cached.write("levels/three.map", Vec::new())).is_err());
+        assert_eq!(remote.reads(), 1);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn writes_and_deletes_go_through() {
+        let dir = temp_dir("write_through");
+        let remote = Remote::default();
+        let cached = CachingProvider::new(remote.clone(), CacheConfig::new(&dir)).unwrap();
+        assert!(!cached.is_read_only());
+
+        poll_ready(cached.write("save.dat", b"progress".to_vec())).unwrap();
+        assert_eq!(
+            poll_ready(remote.files.read("save.dat")).unwrap(),
+            b"progress"
+        );
+        assert_eq!(poll_ready(cached.read("save.dat")).unwrap(), b"progress");
+        assert_eq!(remote.reads(), 0);
+        assert_eq!(cached.cached_size(), 8);
+
+        poll_ready(cached.delete("save.dat")).unwrap();
+        assert!(!poll_ready(remote.files.exists("save.dat")).unwrap());
+        assert_eq!(cached.cached_size(), 0);
+        remote.set_down(true);
+        assert!(poll_ready(cached.read("save.dat")).is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
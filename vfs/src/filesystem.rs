@@ -1,6 +1,13 @@
-use std::path::PathBuf;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
 
+use notify::event::{EventKind, ModifyKind, RenameMode};
+use notify::{RecursiveMode, Watcher};
+
+use crate::metadata::{VfsDirEntry, VfsMetadata};
 use crate::provider::{VfsFuture, VfsProvider};
+use crate::watch::{VfsChange, VfsChangeKind, VfsWatcher};
 
 /// File system VFS provider for reading and writing assets on disk.
 ///
@@ -11,10 +18,13 @@ use crate::provider::{VfsFuture, VfsProvider};
 /// Path traversal is prevented by the VFS path normalization which rejects
 /// `..` segments before they reach the provider.
 ///
+/// [`watch()`](VfsProvider::watch) uses the platform's native file watcher
+/// (via `notify`) to report changes anywhere under the root directory.
+///
 /// # Example
 ///
 /// ```ignore
-/// let mut vfs = Vfs::new();
+/// let vfs = Vfs::new();
 /// vfs.mount("assets", FileSystemProvider::new("./assets"));
 ///
 /// // Reads ./assets/textures/brick.png
@@ -50,6 +60,43 @@ impl VfsProvider for FileSystemProvider {
         Box::pin(async move { Ok(full_path.exists()) })
     }
 
+    fn metadata(&self, path: &str) -> VfsFuture<VfsMetadata> {
+        let full_path = self.resolve(path);
+        Box::pin(async move { Ok(convert_metadata(&std::fs::metadata(full_path)?)) })
+    }
+
+    fn list_dir_detailed(&self, path: &str) -> VfsFuture<Vec<VfsDirEntry>> {
+        let full_path = self.resolve(path);
+        Box::pin(async move {
+            if !full_path.is_dir() {
+                return Ok(Vec::new());
+            }
+            let mut entries = Vec::new();
+            for entry in std::fs::read_dir(full_path)? {
+                let entry = entry?;
+                let Some(name) = entry.file_name().to_str().map(str::to_owned) else {
+                    continue;
+                };
+                // Follow symlinks like `metadata()` does
+                let metadata = std::fs::metadata(entry.path())?;
+                entries.push(VfsDirEntry::new(name, convert_metadata(&metadata)));
+            }
+            entries.sort_by(|a, b| a.name.cmp(&b.name));
+            Ok(entries)
+        })
+    }
+
+    fn read_range(&self, path: &str, offset: u64, len: u64) -> VfsFuture<Vec<u8>> {
+        let full_path = self.resolve(path);
+        Box::pin(async move {
+            let mut file = std::fs::File::open(full_path)?;
+            file.seek(SeekFrom::Start(offset))?;
+            let mut data = Vec::new();
+            file.take(len).read_to_end(&mut data)?;
+            Ok(data)
+        })
+    }
+
     fn list_dir(&self, path: &str) -> VfsFuture<Vec<String>> {
         let full_path = self.resolve(path);
         Box::pin(async move {
@@ -98,6 +145,105 @@ impl VfsProvider for FileSystemProvider {
             Ok(())
         })
     }
+
+    fn watch(&self) -> Option<VfsWatcher> {
+        // Events carry absolute paths, so the root must be absolute as well
+        let root = match self.root.canonicalize() {
+            Ok(root) => root,
+            Err(e) => {
+                log::warn!("Cannot watch {:?}: {e}", self.root);
+                return None;
+            }
+        };
+
+        let (sender, receiver) = mpsc::channel();
+        let event_root = root.clone();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            let Ok(event) = res else {
+                return;
+            };
+            for (path, kind) in classify_event(&event) {
+                if let Some(vfs_path) = to_vfs_path(&event_root, path) {
+                    let _ = sender.send(VfsChange::new(vfs_path, kind));
+                }
+            }
+        })
+        .map_err(|e| log::warn!("Failed to create file watcher: {e}"))
+        .ok()?;
+
+        if let Err(e) = watcher.watch(&root, RecursiveMode::Recursive) {
+            log::warn!("Failed to watch {:?}: {e}", root);
+            return None;
+        }
+
+        Some(VfsWatcher::new(receiver, watcher))
+    }
+}
+
+/// Convert filesystem metadata, treating anything but a directory as a file.
+fn convert_metadata(metadata: &std::fs::Metadata) -> VfsMetadata {
+    let modified = metadata.modified().ok();
+    if metadata.is_dir() {
+        VfsMetadata::directory(modified)
+    } else {
+        VfsMetadata::file(metadata.len(), modified)
+    }
+}
+
+/// Map a notify event to per-path change kinds.
+///
+/// Renames are reported as a removal of the old path and a creation of the
+/// new one. Access and unknown events are ignored.
+fn classify_event(event: &notify::Event) -> Vec<(&Path, VfsChangeKind)> {
+    let kind = match event.kind {
+        EventKind::Create(_) => VfsChangeKind::Created,
+        EventKind::Remove(_) => VfsChangeKind::Removed,
+        EventKind::Modify(ModifyKind::Name(RenameMode::From)) => VfsChangeKind::Removed,
+        EventKind::Modify(ModifyKind::Name(RenameMode::To)) => VfsChangeKind::Created,
+        EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => {
+            let mut changes = Vec::new();
+            if let Some(from) = event.paths.first() {
+                changes.push((from.as_path(), VfsChangeKind::Removed));
+            }
+            if let Some(to) = event.paths.get(1) {
+                changes.push((to.as_path(), VfsChangeKind::Created));
+            }
+            return changes;
+        }
+        EventKind::Modify(ModifyKind::Name(_)) => {
+            // The platform did not say which side of the rename this is
+            return event
+                .paths
+                .iter()
+                .map(|path| {
+                    let kind = if path.exists() {
+                        VfsChangeKind::Created
+                    } else {
+                        VfsChangeKind::Removed
+                    };
+                    (path.as_path(), kind)
+                })
+                .collect();
+        }
+        EventKind::Modify(_) => VfsChangeKind::Modified,
+        EventKind::Access(_) | EventKind::Any | EventKind::Other => return Vec::new(),
+    };
+    event
+        .paths
+        .iter()
+        .map(|path| (path.as_path(), kind))
+        .collect()
+}
+
+/// Convert an absolute path under `root` to a VFS path relative to the root.
+fn to_vfs_path(root: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(root).ok()?;
+    let vfs_path = relative.to_string_lossy().replace('\\', "/");
+    if vfs_path.is_empty() {
+        None
+    } else {
+        Some(vfs_path)
+    }
 }
 
 #[cfg(test)]
@@ -182,6 +328,55 @@ mod tests {
         let _ = std::fs::remove_dir_all(&dir);
     }
 
+    #[test]
+    fn metadata_and_detailed_listing() {
+        let dir = temp_dir("metadata");
+        std::fs::write(dir.join("a.txt"), b"hello").unwrap();
+        std::fs::create_dir_all(dir.join("sub")).unwrap();
+
+        let provider = FileSystemProvider::new(&dir);
+        let file = poll_ready(provider.metadata("a.txt")).unwrap();
+        assert!(file.is_file());
+        assert_eq!(file.size, 5);
+        assert!(file.modified.is_some());
+        assert!(poll_ready(provider.metadata("sub")).unwrap().is_dir());
+        assert!(matches!(
+            poll_ready(provider.metadata("nope.txt")),
+            Err(VfsError::NotFound(_))
+        ));
+
+        let entries = poll_ready(provider.list_dir_detailed("")).unwrap();
+        let names: Vec<_> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["a.txt", "sub"]);
+        assert_eq!(entries[0].metadata.size, 5);
+        assert!(entries[1].is_dir());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn read_range_of_file() {
+        let dir = temp_dir("read_range");
+        std::fs::write(dir.join("data.bin"), b"0123456789").unwrap();
+
+        let provider = FileSystemProvider::new(&dir);
+        assert_eq!(
+            poll_ready(provider.read_range("data.bin", 3, 4)).unwrap(),
+            b"3456"
+        );
+        assert_eq!(
+            poll_ready(provider.read_range("data.bin", 7, 100)).unwrap(),
+            b"789"
+        );
+        assert!(
+            poll_ready(provider.read_range("data.bin", 50, 4))
+                .unwrap()
+                .is_empty()
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn write_creates_file_and_parents() {
         let dir = temp_dir("write");
@@ -224,4 +419,35 @@ mod tests {
         let provider = FileSystemProvider::new("/tmp");
         assert!(!provider.is_read_only());
     }
+
+    #[test]
+    fn vfs_path_relative_to_root() {
+        let root = Path::new("/data/assets");
+        assert_eq!(
+            to_vfs_path(root, Path::new("/data/assets/textures/brick.png")),
+            Some("textures/brick.png".to_owned())
+        );
+        assert_eq!(to_vfs_path(root, root), None);
+        assert_eq!(to_vfs_path(root, Path::new("/elsewhere/file.txt")), None);
+    }
+
+    #[test]
+    fn watch_reports_written_file() {
+        let dir = temp_dir("watch");
+        let provider = FileSystemProvider::new(&dir);
+        let watcher = provider.watch().unwrap();
+
+        std::fs::write(dir.join("new.txt"), b"data").unwrap();
+
+        // Native notifications arrive asynchronously
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        let mut changes = Vec::new();
+        while changes.is_empty() && std::time::Instant::now() < deadline {
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            changes = watcher.poll();
+        }
+        assert!(changes.iter().any(|c| c.path == "new.txt"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
@@ -0,0 +1,65 @@
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::task::{Poll, Waker};
+
+/// Caps the number of HTTP requests in flight.
+///
+/// A runtime-agnostic async semaphore: callers await a [`RequestPermit`]
+/// and hold it for the duration of the request. Releasing a permit wakes
+/// every waiter and the first one to be polled takes it, so dropped waiters
+/// never strand a permit.
+pub(super) struct RequestLimiter {
+    state: Mutex<LimiterState>,
+}
+
+struct LimiterState {
+    available: usize,
+    waiters: Vec<Waker>,
+}
+
+/// A slot in the [`RequestLimiter`], released on drop.
+pub(super) struct RequestPermit {
+    limiter: Arc<RequestLimiter>,
+}
+
+impl RequestLimiter {
+    /// Allow up to `limit` concurrent requests (at least one).
+    pub fn new(limit: usize) -> Arc<Self> {
+        Arc::new(Self {
+            state: Mutex::new(LimiterState {
+                available: limit.max(1),
+                waiters: Vec::new(),
+            }),
+        })
+    }
+
+    /// Wait for a free slot.
+    pub fn acquire(self: &Arc<Self>) -> impl Future<Output = RequestPermit> + Send + 'static {
+        let limiter = Arc::clone(self);
+        std::future::poll_fn(move |cx| {
+            let mut state = limiter.state.lock().unwrap();
+            if state.available > 0 {
+                state.available -= 1;
+                Poll::Ready(RequestPermit {
+                    limiter: Arc::clone(&limiter),
+                })
+            } else {
+                state.waiters.push(cx.waker().clone());
+                Poll::Pending
+            }
+        })
+    }
+}
+
+impl Drop for RequestPermit {
+    fn drop(&mut self) {
+        let waiters = {
+            let mut state = self.limiter.state.lock().unwrap();
+            state.available += 1;
+            std::mem::take(&mut state.waiters)
+        };
+        for waker in waiters {
+            waker.wake();
+        }
+    }
+}
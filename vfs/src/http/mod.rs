@@ -0,0 +1,953 @@
+mod limit;
+#[cfg(not(target_arch = "wasm32"))]
+mod native;
+#[cfg(target_arch = "wasm32")]
+mod wasm;
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex};
+use std::task::{Poll, Waker};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::VfsError;
+use crate::metadata::{self, VfsDirEntry, VfsMetadata, clamp_range};
+use crate::path;
+use crate::provider::{VfsFuture, VfsProvider};
+use crate::vfs::Vfs;
+
+#[cfg(not(target_arch = "wasm32"))]
+use native::Client;
+#[cfg(target_arch = "wasm32")]
+use wasm::Client;
+
+use limit::RequestLimiter;
+
+/// Configuration for [`HttpProvider`].
+#[derive(Debug, Clone)]
+pub struct HttpConfig {
+    /// URL the VFS paths are resolved against, e.g. `"https://cdn.example.com/assets"`.
+    pub base_url: String,
+    /// Location of an [`HttpManifest`], relative to `base_url` or absolute.
+    ///
+    /// Without a manifest, directory listings are empty and metadata comes
+    /// from `HEAD` requests.
+    pub manifest: Option<String>,
+    /// Maximum number of requests in flight at once.
+    pub max_concurrent_requests: usize,
+    /// Byte budget of the ETag cache. `0` disables caching.
+    pub cache_size: usize,
+}
+
+impl HttpConfig {
+    /// Default limits for the given base URL.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            manifest: None,
+            max_concurrent_requests: 6,
+            cache_size: 64 * 1024 * 1024,
+        }
+    }
+
+    /// Use the manifest at `manifest` for listings and metadata.
+    pub fn with_manifest(mut self, manifest: impl Into<String>) -> Self {
+        self.manifest = Some(manifest.into());
+        self
+    }
+}
+
+/// File listing served next to the assets so an [`HttpProvider`] can list
+/// directories and answer metadata queries without a request per file.
+///
+/// ```json
+/// {
+///   "files": {
+///     "textures/brick.png": { "size": 52311, "modified": 1709294400 },
+///     "models/crate.glb": { "size": 18734 }
+///   }
+/// }
+/// ```
+///
+/// `modified` is in seconds since the Unix epoch and optional.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HttpManifest {
+    /// Files keyed by VFS path relative to the base URL.
+    pub files: BTreeMap<String, HttpManifestEntry>,
+}
+
+/// Size and modification time of one file in an [`HttpManifest`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HttpManifestEntry {
+    /// Size in bytes.
+    pub size: u64,
+    /// Seconds since the Unix epoch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified: Option<u64>,
+}
+
+impl HttpManifest {
+    /// Create an empty manifest.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add or replace a file.
+    pub fn insert(&mut self, path: &str, size: u64, modified: Option<SystemTime>) {
+        let modified = modified
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs());
+        self.files
+            .insert(path.to_owned(), HttpManifestEntry { size, modified });
+    }
+
+    /// Parse a manifest from JSON.
+    pub fn from_json(bytes: &[u8]) -> Result<Self, VfsError> {
+        serde_json::from_slice(bytes).map_err(|e| {
+            VfsError::Io(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("invalid HTTP manifest: {e}"),
+            ))
+        })
+    }
+
+    /// Serialize the manifest as pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("manifest serialization cannot fail")
+    }
+
+    /// Build a manifest of every file under `dir` in `vfs`, recursively.
+    ///
+    /// Paths are relative to `dir`, matching an upload of that directory
+    /// to the base URL.
+    pub fn from_vfs_dir(vfs: &Vfs, dir: &str) -> VfsFuture<Self> {
+        let vfs = vfs.clone();
+        let root = dir.trim_matches('/').to_owned();
+        Box::pin(async move {
+            let mut manifest = Self::new();
+            let mut pending = vec![String::new()];
+            while let Some(relative_dir) = pending.pop() {
+                for entry in vfs.list_dir_detailed(&join(&root, &relative_dir)).await? {
+                    let relative = join(&relative_dir, &entry.name);
+                    if entry.is_dir() {
+                        pending.push(relative);
+                    } else {
+                        manifest.insert(&relative, entry.metadata.size, entry.metadata.modified);
+                    }
+                }
+            }
+            Ok(manifest)
+        })
+    }
+}
+
+/// Read-only provider fetching files from a web server.
+///
+/// Paths are appended to the base URL. Native builds use a blocking
+/// `ureq` client on a thread per request; wasm builds use the browser's
+/// `fetch()`, which makes this the way to stream assets into web builds
+/// instead of embedding them in a [`MemoryProvider`](crate::MemoryProvider).
+///
+/// - **Manifest** — with [`HttpConfig::manifest`] set, the [`HttpManifest`]
+///   is fetched on first use and answers `list_dir`, `exists` and
+///   `metadata` locally. Call [`refresh_manifest()`](Self::refresh_manifest)
+///   after the server content changes. If the manifest can't be fetched,
+///   metadata falls back to `HEAD` requests until the next refresh.
+/// - **ETag caching** — bodies served with an `ETag` are kept in memory up
+///   to [`HttpConfig::cache_size`] bytes and revalidated with
+///   `If-None-Match`, so unchanged files cost a `304` instead of a download.
+/// - **Request limits** — at most [`HttpConfig::max_concurrent_requests`]
+///   requests are in flight; further ones wait for a free slot.
+///
+/// `read_range` sends a `Range` header and falls back to slicing the full
+/// body when the server ignores it.
+///
+/// # Example
+///
+/// ```ignore
+/// let vfs = Vfs::new();
+/// let config = HttpConfig::new("https://cdn.example.com/assets").with_manifest("manifest.json");
+/// vfs.mount("assets", HttpProvider::new(config));
+///
+/// // GET https://cdn.example.com/assets/textures/brick.png
+/// let bytes = io.run(vfs.read("assets/textures/brick.png")).await;
+/// ```
+#[derive(Clone)]
+pub struct HttpProvider {
+    inner: Arc<HttpInner>,
+}
+
+struct HttpInner {
+    base_url: String,
+    manifest_url: Option<String>,
+    client: Client,
+    limiter: Arc<RequestLimiter>,
+    cache: Mutex<EtagCache>,
+    manifest: Mutex<ManifestState>,
+}
+
+/// Load state of the manifest. Concurrent callers wait on a single fetch.
+enum ManifestState {
+    Unloaded,
+    Loading(Vec<Waker>),
+    Loaded(Arc<ManifestIndex>),
+    /// The fetch failed; requests go to the server until the next refresh.
+    Unavailable,
+}
+
+/// Claim on the in-flight manifest fetch. Dropping it without a result
+/// (on cancellation) hands the fetch to the next waiter.
+struct ManifestFetch<'a>(&'a Mutex<ManifestState>);
+
+impl ManifestFetch<'_> {
+    fn finish(self, state: ManifestState) {
+        let previous = std::mem::replace(&mut *self.0.lock().unwrap(), state);
+        wake_all(previous);
+    }
+}
+
+impl Drop for ManifestFetch<'_> {
+    fn drop(&mut self) {
+        let mut state = self.0.lock().unwrap();
+        if matches!(*state, ManifestState::Loading(_)) {
+            let previous = std::mem::replace(&mut *state, ManifestState::Unloaded);
+            drop(state);
+            wake_all(previous);
+        }
+    }
+}
+
+fn wake_all(state: ManifestState) {
+    if let ManifestState::Loading(waiters) = state {
+        for waker in waiters {
+            waker.wake();
+        }
+    }
+}
+
+struct HttpRequest {
+    method: &'static str,
+    url: String,
+    headers: Vec<(&'static str, String)>,
+}
+
+struct HttpResponse {
+    status: u16,
+    etag: Option<String>,
+    content_length: Option<u64>,
+    last_modified: Option<String>,
+    body: Vec<u8>,
+}
+
+impl HttpProvider {
+    /// Create a provider. No request is made until the first operation.
+    pub fn new(config: HttpConfig) -> Self {
+        let base_url = config.base_url.trim_end_matches('/').to_owned();
+        let manifest_url = config.manifest.map(|manifest| {
+            if manifest.contains("://") {
+                manifest
+            } else {
+                format!("{base_url}/{}", manifest.trim_start_matches('/'))
+            }
+        });
+        Self {
+            inner: Arc::new(HttpInner {
+                base_url,
+                manifest_url,
+                client: Client::new(),
+                limiter: RequestLimiter::new(config.max_concurrent_requests),
+                cache: Mutex::new(EtagCache::new(config.cache_size)),
+                manifest: Mutex::new(ManifestState::Unloaded),
+            }),
+        }
+    }
+
+    /// Base URL paths are resolved against.
+    pub fn base_url(&self) -> &str {
+        &self.inner.base_url
+    }
+
+    /// Drop the loaded manifest so the next listing fetches it again. Also
+    /// retries a manifest that failed to load.
+    ///
+    /// A fetch already in flight is kept.
+    pub fn refresh_manifest(&self) {
+        let mut state = self.inner.manifest.lock().unwrap();
+        if matches!(
+            *state,
+            ManifestState::Loaded(_) | ManifestState::Unavailable
+        ) {
+            *state = ManifestState::Unloaded;
+        }
+    }
+
+    /// Drop every cached body.
+    pub fn clear_cache(&self) {
+        self.inner.cache.lock().unwrap().clear();
+    }
+}
+
+impl HttpInner {
+    fn url(&self, path: &str) -> String {
+        let mut url = self.base_url.clone();
+        url.push('/');
+        for byte in path.bytes() {
+            match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                    url.push(byte as char)
+                }
+                _ => {
+                    let _ = write!(url, "%{byte:02X}");
+                }
+            }
+        }
+        url
+    }
+
+    async fn send(
+        &self,
+        method: &'static str,
+        url: String,
+        headers: Vec<(&'static str, String)>,
+    ) -> Result<HttpResponse, VfsError> {
+        let _permit = self.limiter.acquire().await;
+        self.client
+            .send(HttpRequest {
+                method,
+                url,
+                headers,
+            })
+            .await
+    }
+
+    /// Fetch a file, revalidating a cached body with its ETag.
+    async fn get(&self, path: &str) -> Result<Vec<u8>, VfsError> {
+        let cached = self.cache.lock().unwrap().get(path);
+        let headers = match &cached {
+            Some((etag, _)) => vec![("If-None-Match", etag.clone())],
+            None => Vec::new(),
+        };
+        let response = self.send("GET", self.url(path), headers).await?;
+        match (response.status, cached) {
+            (304, Some((_, body))) => Ok(body.as_ref().clone()),
+            (200, _) => {
+                if let Some(etag) = response.etag {
+                    self.cache
+                        .lock()
+                        .unwrap()
+                        .insert(path, etag, &response.body);
+                }
+                Ok(response.body)
+            }
+            (404 | 410, _) => Err(VfsError::NotFound(path.to_owned())),
+            (status, _) => Err(status_err(&self.url(path), status)),
+        }
+    }
+
+    /// The manifest index, fetching it on first use. `None` without a manifest.
+    ///
+    /// A manifest that fails to load is logged and also yields `None`, so
+    /// callers fall back to per-file requests.
+    async fn manifest(&self) -> Option<Arc<ManifestIndex>> {
+        let url = self.manifest_url.as_ref()?;
+        // Either the loaded index, or `None` once this caller has claimed
+        // the fetch; anyone else waits for the claimant to finish.
+        let loaded = std::future::poll_fn(|cx| {
+            let mut state = self.manifest.lock().unwrap();
+            match &mut *state {
+                ManifestState::Loaded(index) => Poll::Ready(Some(Some(Arc::clone(index)))),
+                ManifestState::Unavailable => Poll::Ready(Some(None)),
+                ManifestState::Loading(waiters) => {
+                    waiters.push(cx.waker().clone());
+                    Poll::Pending
+                }
+                ManifestState::Unloaded => {
+                    *state = ManifestState::Loading(Vec::new());
+                    Poll::Ready(None)
+                }
+            }
+        })
+        .await;
+        if let Some(index) = loaded {
+            return index;
+        }
+
+        let fetch = ManifestFetch(&self.manifest);
+        match self.fetch_manifest(url).await {
+            Ok(index) => {
+                let index = Arc::new(index);
+                fetch.finish(ManifestState::Loaded(Arc::clone(&index)));
+                Some(index)
+            }
+            Err(e) => {
+                log::warn!("HTTP manifest {url} unavailable, using per-file requests: {e}");
+                fetch.finish(ManifestState::Unavailable);
+                None
+            }
+        }
+    }
+
+    async fn fetch_manifest(&self, url: &str) -> Result<ManifestIndex, VfsError> {
+        let response = self.send("GET", url.to_owned(), Vec::new()).await?;
+        if response.status != 200 {
+            return Err(status_err(url, response.status));
+        }
+        ManifestIndex::new(HttpManifest::from_json(&response.body)?)
+    }
+}
+
+impl VfsProvider for HttpProvider {
+    fn read(&self, path: &str) -> VfsFuture<Vec<u8>> {
+        let inner = Arc::clone(&self.inner);
+        let path = path.to_owned();
+        Box::pin(async move { inner.get(&path).await })
+    }
+
+    fn metadata(&self, path: &str) -> VfsFuture<VfsMetadata> {
+        let inner = Arc::clone(&self.inner);
+        let path = path.to_owned();
+        Box::pin(async move {
+            if let Some(manifest) = inner.manifest().await {
+                return manifest.metadata(&path);
+            }
+            if path.is_empty() {
+                return Ok(VfsMetadata::directory(None));
+            }
+            let response = inner.send("HEAD", inner.url(&path), Vec::new()).await?;
+            match response.status {
+                200..=299 => {
+                    // Chunked responses carry no length; fetch the body to
+                    // learn it rather than report an empty file.
+                    let size = match response.content_length {
+                        Some(size) => size,
+                        None => inner.get(&path).await?.len() as u64,
+                    };
+                    Ok(VfsMetadata::file(
+                        size,
+                        response.last_modified.as_deref().and_then(parse_http_date),
+                    ))
+                }
+                404 | 410 => Err(VfsError::NotFound(path)),
+                status => Err(status_err(&inner.url(&path), status)),
+            }
+        })
+    }
+
+    fn list_dir_detailed(&self, path: &str) -> VfsFuture<Vec<VfsDirEntry>> {
+        let inner = Arc::clone(&self.inner);
+        let path = path.to_owned();
+        Box::pin(async move {
+            Ok(match inner.manifest().await {
+                Some(manifest) => manifest.dirs.get(&path).cloned().unwrap_or_default(),
+                None => Vec::new(),
+            })
+        })
+    }
+
+    fn read_range(&self, path: &str, offset: u64, len: u64) -> VfsFuture<Vec<u8>> {
+        let inner = Arc::clone(&self.inner);
+        let path = path.to_owned();
+        Box::pin(async move {
+            if len == 0 {
+                return Ok(Vec::new());
+            }
+            let last = offset.saturating_add(len - 1);
+            let headers = vec![("Range", format!("bytes={offset}-{last}"))];
+            let response = inner.send("GET", inner.url(&path), headers).await?;
+            match response.status {
+                206 => {
+                    let mut body = response.body;
+                    body.truncate(usize::try_from(len).unwrap_or(usize::MAX));
+                    Ok(body)
+                }
+                // The server ignored the range and sent the whole file.
+                200 => Ok(response.body[clamp_range(response.body.len(), offset, len)].to_vec()),
+                416 => Ok(Vec::new()),
+                404 | 410 => Err(VfsError::NotFound(path)),
+                status => Err(status_err(&inner.url(&path), status)),
+            }
+        })
+    }
+}
+
+/// Files and directory listings of a loaded [`HttpManifest`].
+struct ManifestIndex {
+    files: HashMap<String, VfsMetadata>,
+    dirs: HashMap<String, Vec<VfsDirEntry>>,
+}
+
+impl ManifestIndex {
+    fn new(manifest: HttpManifest) -> Result<Self, VfsError> {
+        let mut files = HashMap::with_capacity(manifest.files.len());
+        for (path, entry) in manifest.files {
+            let modified = entry
+                .modified
+                .and_then(|secs| UNIX_EPOCH.checked_add(Duration::from_secs(secs)));
+            files.insert(
+                path::normalize(&path)?,
+                VfsMetadata::file(entry.size, modified),
+            );
+        }
+        let dirs = metadata::build_dirs(files.iter().map(|(path, meta)| (path.as_str(), *meta)));
+        Ok(Self { files, dirs })
+    }
+
+    fn metadata(&self, path: &str) -> Result<VfsMetadata, VfsError> {
+        if let Some(metadata) = self.files.get(path) {
+            return Ok(*metadata);
+        }
+        if path.is_empty() {
+            let modified = self.dirs[""]
+                .iter()
+                .filter_map(|e| e.metadata.modified)
+                .max();
+            return Ok(VfsMetadata::directory(modified));
+        }
+        let (parent, name) = path.rsplit_once('/').unwrap_or(("", path));
+        self.dirs
+            .get(parent)
+            .and_then(|entries| entries.iter().find(|entry| entry.name == name))
+            .map(|entry| entry.metadata)
+            .ok_or_else(|| VfsError::NotFound(path.to_owned()))
+    }
+}
+
+/// In-memory bodies keyed by path, validated by ETag and evicted least
+/// recently used first.
+struct EtagCache {
+    capacity: usize,
+    size: usize,
+    tick: u64,
+    entries: HashMap<String, CachedBody>,
+}
+
+struct CachedBody {
+    etag: String,
+    body: Arc<Vec<u8>>,
+    last_used: u64,
+}
+
+impl EtagCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            size: 0,
+            tick: 0,
+            entries: HashMap::new(),
+        }
+    }
+
+    fn get(&mut self, path: &str) -> Option<(String, Arc<Vec<u8>>)> {
+        self.tick += 1;
+        let entry = self.entries.get_mut(path)?;
+        entry.last_used = self.tick;
+        Some((entry.etag.clone(), Arc::clone(&entry.body)))
+    }
+
+    fn insert(&mut self, path: &str, etag: String, body: &[u8]) {
+        if let Some(old) = self.entries.remove(path) {
+            self.size -= old.body.len();
+        }
+        if body.len() > self.capacity {
+            return;
+        }
+        while self.size + body.len() > self.capacity {
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(path, _)| path.clone())
+            else {
+                break;
+            };
+            let evicted = self.entries.remove(&oldest).unwrap();
+            self.size -= evicted.body.len();
+        }
+        self.tick += 1;
+        self.size += body.len();
+        self.entries.insert(
+            path.to_owned(),
+            CachedBody {
+                etag,
+                body: Arc::new(body.to_vec()),
+                last_used: self.tick,
+            },
+        );
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.size = 0;
+    }
+}
+
+fn http_err(message: String) -> VfsError {
+    VfsError::Io(std::io::Error::other(message))
+}
+
+fn status_err(url: &str, status: u16) -> VfsError {
+    http_err(format!("{url}: HTTP status {status}"))
+}
+
+/// Parse an IMF-fixdate (`Sun, 06 Nov 1994 08:49:37 GMT`), the format
+/// servers use for `Last-Modified`.
+fn parse_http_date(value: &str) -> Option<SystemTime> {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let parts: Vec<&str> = value.split_whitespace().collect();
+    let [_, day, month, year, time, "GMT"] = parts[..] else {
+        return None;
+    };
+    let month = MONTHS.iter().position(|m| *m == month)? as i64 + 1;
+    // Narrow field types keep hostile values from overflowing the arithmetic.
+    let mut clock = time.split(':').map(|part| part.parse::<u8>().ok());
+    let (hour, minute, second) = (clock.next()??, clock.next()??, clock.next()??);
+    let year: u16 = year.parse().ok()?;
+    let day: u8 = day.parse().ok()?;
+    let days = metadata::days_from_civil(year as i64, month, day as i64);
+    let secs = days * 86400 + hour as i64 * 3600 + minute as i64 * 60 + second as i64;
+    UNIX_EPOCH.checked_add(Duration::from_secs(u64::try_from(secs).ok()?))
+}
+
+fn join(dir: &str, name: &str) -> String {
+    if dir.is_empty() {
+        name.to_owned()
+    } else {
+        format!("{dir}/{name}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::task::{Context, Poll, Wake, Waker};
+    use std::thread;
+
+    const LAST_MODIFIED: &str = "Fri, 01 Mar 2024 12:00:00 GMT";
+
+    /// In-process HTTP server serving a fixed set of files.
+    struct TestServer {
+        server: Arc<tiny_http::Server>,
+        url: String,
+        requests: Arc<AtomicUsize>,
+        not_modified: Arc<AtomicUsize>,
+    }
+
+    impl TestServer {
+        fn start(files: &[(&str, &[u8])]) -> Self {
+            let files: HashMap<String, Vec<u8>> = files
+                .iter()
+                .map(|(path, data)| ((*path).to_owned(), data.to_vec()))
+                .collect();
+            let server = Arc::new(tiny_http::Server::http("127.0.0.1:0").unwrap());
+            let port = server.server_addr().to_ip().unwrap().port();
+            let requests = Arc::new(AtomicUsize::new(0));
+            let not_modified = Arc::new(AtomicUsize::new(0));
+            {
+                let server = Arc::clone(&server);
+                let requests = Arc::clone(&requests);
+                let not_modified = Arc::clone(&not_modified);
+                thread::spawn(move || {
+                    for request in server.incoming_requests() {
+                        requests.fetch_add(1, Ordering::SeqCst);
+                        let response = respond(&files, &request, &not_modified);
+                        let _ = request.respond(response);
+                    }
+                });
+            }
+            Self {
+                server,
+                url: format!("http://127.0.0.1:{port}/assets"),
+                requests,
+                not_modified,
+            }
+        }
+
+        fn provider(&self) -> HttpProvider {
+            HttpProvider::new(HttpConfig::new(&self.url))
+        }
+
+        fn requests(&self) -> usize {
+            self.requests.load(Ordering::SeqCst)
+        }
+    }
+
+    impl Drop for TestServer {
+        fn drop(&mut self) {
+            self.server.unblock();
+        }
+    }
+
+    fn respond(
+        files: &HashMap<String, Vec<u8>>,
+        request: &tiny_http::Request,
+        not_modified: &AtomicUsize,
+    ) -> tiny_http::Response<std::io::Cursor<Vec<u8>>> {
+        let header = |name: &'static str| {
+            request
+                .headers()
+                .iter()
+                .find(|h| h.field.equiv(name))
+                .map(|h| h.value.as_str().to_owned())
+        };
+        let path = request
+            .url()
+            .trim_start_matches("/assets/")
+            .replace("%20", " ");
+        let Some(data) = files.get(&path) else {
+            return tiny_http::Response::from_data(Vec::new()).with_status_code(404);
+        };
+        let etag = format!("\"{}\"", data.len());
+        let with_headers = |response: tiny_http::Response<_>| {
+            response
+                .with_header(tiny_http::Header::from_bytes("ETag", etag.as_bytes()).unwrap())
+                .with_header(tiny_http::Header::from_bytes("Last-Modified", LAST_MODIFIED).unwrap())
+        };
+        if header("If-None-Match").as_deref() == Some(etag.as_str()) {
+            not_modified.fetch_add(1, Ordering::SeqCst);
+            return with_headers(tiny_http::Response::from_data(Vec::new()).with_status_code(304));
+        }
+        if let Some(range) = header("Range") {
+            let (start, end) = range.trim_start_matches("bytes=").split_once('-').unwrap();
+            let start: usize = start.parse().unwrap();
+            let end = (end.parse::<usize>().unwrap() + 1).min(data.len());
+            if start >= data.len() {
+                return tiny_http::Response::from_data(Vec::new()).with_status_code(416);
+            }
+            return with_headers(
+                tiny_http::Response::from_data(data[start..end].to_vec()).with_status_code(206),
+            );
+        }
+        if path.starts_with("chunked/") {
+            // No length given, so the body is sent with chunked encoding.
+            return tiny_http::Response::new(
+                tiny_http::StatusCode(200),
+                Vec::new(),
+                std::io::Cursor::new(data.clone()),
+                None,
+                None,
+            );
+        }
+        with_headers(tiny_http::Response::from_data(data.clone()))
+    }
+
+    struct ThreadWaker(thread::Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    fn block_on<T>(mut fut: impl Future<Output = T> + Unpin) -> T {
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(val) = Pin::new(&mut fut).poll(&mut cx) {
+                return val;
+            }
+            thread::park();
+        }
+    }
+
+    #[test]
+    fn reads_files_relative_to_base_url() {
+        let server = TestServer::start(&[
+            ("config.json", b"{}"),
+            ("textures/my brick.png", b"png data"),
+        ]);
+        let provider = server.provider();
+
+        assert_eq!(block_on(provider.read("config.json")).unwrap(), b"{}");
+        assert_eq!(
+            block_on(provider.read("textures/my brick.png")).unwrap(),
+            b"png data"
+        );
+        assert!(matches!(
+            block_on(provider.read("missing.txt")),
+            Err(VfsError::NotFound(_))
+        ));
+        assert!(provider.is_read_only());
+    }
+
+    #[test]
+    fn exists_and_metadata_use_head_requests() {
+        let server = TestServer::start(&[("models/crate.glb", &[1u8; 300])]);
+        let provider = server.provider();
+
+        let metadata = block_on(provider.metadata("models/crate.glb")).unwrap();
+        assert!(metadata.is_file());
+        assert_eq!(metadata.size, 300);
+        assert_eq!(
+            metadata.modified,
+            Some(UNIX_EPOCH + Duration::from_secs(1_709_294_400))
+        );
+        assert!(block_on(provider.exists("models/crate.glb")).unwrap());
+        assert!(!block_on(provider.exists("models/missing.glb")).unwrap());
+        assert!(block_on(provider.list_dir("models")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn metadata_without_content_length_fetches_the_body() {
+        let server = TestServer::start(&[("chunked/stream.bin", &[4u8; 100])]);
+        let provider = server.provider();
+
+        let metadata = block_on(provider.metadata("chunked/stream.bin")).unwrap();
+        assert_eq!(metadata.size, 100);
+        assert_eq!(server.requests(), 2);
+    }
+
+    #[test]
+    fn unavailable_manifest_falls_back_to_head_requests() {
+        let server = TestServer::start(&[("config.json", b"{}")]);
+        let provider =
+            HttpProvider::new(HttpConfig::new(&server.url).with_manifest("manifest.json"));
+
+        assert_eq!(block_on(provider.metadata("config.json")).unwrap().size, 2);
+        assert!(!block_on(provider.exists("other.txt")).unwrap());
+        assert!(block_on(provider.list_dir("")).unwrap().is_empty());
+        // The manifest is only tried once: one GET plus two HEADs.
+        assert_eq!(server.requests(), 3);
+    }
+
+    #[test]
+    fn manifest_ignores_out_of_range_times() {
+        let json = format!(
+            r#"{{"files": {{"a.bin": {{"size": 1, "modified": {}}}}}}}"#,
+            u64::MAX
+        );
+        let manifest = HttpManifest::from_json(json.as_bytes()).unwrap();
+        let index = ManifestIndex::new(manifest).unwrap();
+        assert_eq!(index.metadata("a.bin").unwrap().modified, None);
+    }
+
+    #[test]
+    fn concurrent_callers_share_one_manifest_fetch() {
+        let mut manifest = HttpManifest::new();
+        manifest.insert("config.json", 2, None);
+        let json = manifest.to_json();
+        let server = TestServer::start(&[("manifest.json", json.as_bytes())]);
+        let provider =
+            HttpProvider::new(HttpConfig::new(&server.url).with_manifest("manifest.json"));
+
+        let mut first = provider.list_dir("");
+        let mut second = provider.list_dir("");
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        let _ = first.as_mut().poll(&mut cx);
+        let _ = second.as_mut().poll(&mut cx);
+
+        assert_eq!(block_on(first).unwrap(), vec!["config.json"]);
+        assert_eq!(block_on(second).unwrap(), vec!["config.json"]);
+        assert_eq!(server.requests(), 1);
+    }
+
+    #[test]
+    fn manifest_answers_listings_and_metadata() {
+        let mut manifest = HttpManifest::new();
+        manifest.insert("textures/brick.png", 4, Some(UNIX_EPOCH));
+        manifest.insert("config.json", 2, None);
+        let json = manifest.to_json();
+        assert_eq!(HttpManifest::from_json(json.as_bytes()).unwrap(), manifest);
+
+        let server = TestServer::start(&[
+            ("manifest.json", json.as_bytes()),
+            ("textures/brick.png", b"data"),
+            ("config.json", b"{}"),
+        ]);
+        let provider =
+            HttpProvider::new(HttpConfig::new(&server.url).with_manifest("manifest.json"));
+
+        assert_eq!(
+            block_on(provider.list_dir("")).unwrap(),
+            vec!["config.json", "textures"]
+        );
+        assert_eq!(
+            block_on(provider.list_dir("textures")).unwrap(),
+            vec!["brick.png"]
+        );
+        assert!(block_on(provider.metadata("textures")).unwrap().is_dir());
+        assert_eq!(block_on(provider.metadata("config.json")).unwrap().size, 2);
+        assert!(!block_on(provider.exists("other.txt")).unwrap());
+        // Only the manifest itself was fetched.
+        assert_eq!(server.requests(), 1);
+
+        provider.refresh_manifest();
+        assert!(block_on(provider.exists("config.json")).unwrap());
+        assert_eq!(server.requests(), 2);
+    }
+
+    #[test]
+    fn unchanged_files_are_revalidated_with_etags() {
+        let server = TestServer::start(&[("level.bin", &[9u8; 1024])]);
+        let provider = server.provider();
+
+        assert_eq!(
+            block_on(provider.read("level.bin")).unwrap(),
+            vec![9u8; 1024]
+        );
+        assert_eq!(
+            block_on(provider.read("level.bin")).unwrap(),
+            vec![9u8; 1024]
+        );
+        assert_eq!(server.requests(), 2);
+        assert_eq!(server.not_modified.load(Ordering::SeqCst), 1);
+
+        provider.clear_cache();
+        block_on(provider.read("level.bin")).unwrap();
+        assert_eq!(server.not_modified.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn read_range_sends_range_requests() {
+        let server = TestServer::start(&[("data.bin", b"0123456789")]);
+        let provider = server.provider();
+
+        assert_eq!(
+            block_on(provider.read_range("data.bin", 2, 3)).unwrap(),
+            b"234"
+        );
+        assert_eq!(
+            block_on(provider.read_range("data.bin", 8, 10)).unwrap(),
+            b"89"
+        );
+        assert!(
+            block_on(provider.read_range("data.bin", 20, 4))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn limiter_queues_requests_over_the_limit() {
+        let limiter = RequestLimiter::new(1);
+        let first = block_on(Box::pin(limiter.acquire()));
+
+        let mut second = Box::pin(limiter.acquire());
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        assert!(second.as_mut().poll(&mut cx).is_pending());
+
+        drop(first);
+        assert!(second.as_mut().poll(&mut cx).is_ready());
+    }
+
+    #[test]
+    fn parses_http_dates() {
+        assert_eq!(
+            parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT"),
+            Some(UNIX_EPOCH + Duration::from_secs(784_111_777))
+        );
+        assert_eq!(parse_http_date("yesterday"), None);
+        assert_eq!(
+            parse_http_date("Sun, 06 Nov 99999999999999999 08:49:37 GMT"),
+            None
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use std::future::Future;
+use std::io::Read;
+use std::sync::{Arc, Mutex};
+use std::task::{Poll, Waker};
+use std::thread;
+
+use super::{HttpRequest, HttpResponse, http_err};
+use crate::error::VfsError;
+
+/// Blocking `ureq` client. Each request runs on its own short-lived thread
+/// so the returned future never blocks the runtime polling it; the
+/// [`RequestLimiter`](super::limit::RequestLimiter) bounds how many run at
+/// once.
+pub(super) struct Client {
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new() -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new().build(),
+        }
+    }
+
+    pub fn send(
+        &self,
+        request: HttpRequest,
+    ) -> impl Future<Output = Result<HttpResponse, VfsError>> + Send + 'static {
+        let agent = self.agent.clone();
+        let slot = Arc::new(Mutex::new(ReplySlot::default()));
+        let sender = ReplySender(Arc::clone(&slot));
+        let spawned = thread::Builder::new()
+            .name("vfs-http".into())
+            .spawn(move || sender.send(execute(&agent, request)))
+            .map_err(VfsError::Io);
+
+        async move {
+            spawned?;
+            std::future::poll_fn(|cx| {
+                let mut slot = slot.lock().unwrap();
+                match slot.result.take() {
+                    Some(result) => Poll::Ready(result),
+                    None => {
+                        slot.waker = Some(cx.waker().clone());
+                        Poll::Pending
+                    }
+                }
+            })
+            .await
+        }
+    }
+}
+
+fn execute(agent: &ureq::Agent, request: HttpRequest) -> Result<HttpResponse, VfsError> {
+    let mut call = agent.request(request.method, &request.url);
+    for (name, value) in &request.headers {
+        call = call.set(name, value);
+    }
+    let response = match call.call() {
+        Ok(response) => response,
+        // Non-2xx statuses are still responses; the provider interprets them.
+        Err(ureq::Error::Status(_, response)) => response,
+        Err(ureq::Error::Transport(err)) => {
+            return Err(http_err(format!("{}: {err}", request.url)));
+        }
+    };
+
+    let status = response.status();
+    let etag = response.header("etag").map(str::to_owned);
+    let last_modified = response.header("last-modified").map(str::to_owned);
+    let content_length = response
+        .header("content-length")
+        .and_then(|len| len.parse().ok());
+    let mut body = Vec::new();
+    if request.method != "HEAD" {
+        response.into_reader().read_to_end(&mut body)?;
+    }
+    Ok(HttpResponse {
+        status,
+        etag,
+        content_length,
+        last_modified,
+        body,
+    })
+}
+
+#[derive(Default)]
+struct ReplySlot {
+    result: Option<Result<HttpResponse, VfsError>>,
+    waker: Option<Waker>,
+}
+
+/// Delivers the request result to the waiting future, reporting an error
+/// if the request thread exits without one.
+struct ReplySender(Arc<Mutex<ReplySlot>>);
+
+impl ReplySender {
+    fn send(self, result: Result<HttpResponse, VfsError>) {
+        self.0.lock().unwrap().result = Some(result);
+    }
+}
+
+impl Drop for ReplySender {
+    fn drop(&mut self) {
+        let mut slot = self.0.lock().unwrap();
+        if slot.result.is_none() {
+            slot.result = Some(Err(http_err("HTTP request thread exited".to_owned())));
+        }
+        if let Some(waker) = slot.waker.take() {
+            waker.wake();
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Headers, Request, RequestInit, Response, Window, WorkerGlobalScope};
+
+use super::{HttpRequest, HttpResponse, http_err};
+use crate::error::VfsError;
+
+/// `fetch()` client, usable from the main thread and from web workers.
+///
+/// Cross-origin servers must list `ETag`, `Last-Modified` and
+/// `Content-Length` in `Access-Control-Expose-Headers` for revalidation and
+/// metadata to work.
+pub(super) struct Client;
+
+impl Client {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn send(
+        &self,
+        request: HttpRequest,
+    ) -> impl Future<Output = Result<HttpResponse, VfsError>> + Send + 'static {
+        AssertSend(Box::pin(fetch(request)))
+    }
+}
+
+async fn fetch(request: HttpRequest) -> Result<HttpResponse, VfsError> {
+    let headers = Headers::new().map_err(js_err)?;
+    for (name, value) in &request.headers {
+        headers.set(name, value).map_err(js_err)?;
+    }
+    let init = RequestInit::new();
+    init.set_method(request.method);
+    init.set_headers(&headers);
+    let js_request = Request::new_with_str_and_init(&request.url, &init).map_err(js_err)?;
+
+    let global = js_sys::global();
+    let promise = if let Some(window) = global.dyn_ref::<Window>() {
+        window.fetch_with_request(&js_request)
+    } else if let Some(worker) = global.dyn_ref::<WorkerGlobalScope>() {
+        worker.fetch_with_request(&js_request)
+    } else {
+        return Err(http_err("fetch() is not available".to_owned()));
+    };
+    let response: Response = JsFuture::from(promise)
+        .await
+        .map_err(|e| http_err(format!("{}: {e:?}", request.url)))?
+        .dyn_into()
+        .map_err(js_err)?;
+
+    let header = |name: &str| response.headers().get(name).ok().flatten();
+    let status = response.status();
+    let etag = header("etag");
+    let last_modified = header("last-modified");
+    let content_length = header("content-length").and_then(|len| len.parse().ok());
+    let body = if request.method == "HEAD" {
+        Vec::new()
+    } else {
+        let buffer = JsFuture::from(response.array_buffer().map_err(js_err)?)
+            .await
+            .map_err(js_err)?;
+        js_sys::Uint8Array::new(&buffer).to_vec()
+    };
+    Ok(HttpResponse {
+        status,
+        etag,
+        content_length,
+        last_modified,
+        body,
+    })
+}
+
+fn js_err(value: JsValue) -> VfsError {
+    http_err(format!("{value:?}"))
+}
+
+/// Wraps the `!Send` fetch future so it fits in a [`VfsFuture`](crate::VfsFuture).
+struct AssertSend<F>(Pin<Box<F>>);
+
+// SAFETY: wasm32 runs the VFS on a single thread; the JS handles inside the
+// future are never moved to another one.
+unsafe impl<F> Send for AssertSend<F> {}
+
+impl<F: Future> Future for AssertSend<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.as_mut().poll(cx)
+    }
+}
@@ -22,33 +22,82 @@
 //! - [`MemoryProvider`] — In-memory storage for tests and embedded assets (read-write)
 //! - [`FileSystemProvider`] — Native filesystem access (read-write, native only)
 //! - [`SftpProvider`] — Remote SSH/SFTP access (read-write, requires `sftp` feature)
+//! - [`PackProvider`] — Zip archives and `.pak` files built with [`PakWriter`]
+//!   or the `redlilium-pak` tool (read-only, requires `pack` feature)
+//! - [`HttpProvider`] — Files served over HTTP(S), using `fetch()` on the web
+//!   (read-only, requires `http` feature)
+//! - [`CachingProvider`] — Wraps a remote provider with an on-disk cache that
+//!   keeps working offline (requires `cache` feature, native only)
 //!
-//! Custom providers can implement the [`VfsProvider`] trait for packed archives,
-//! HTTP fetch, or other storage backends.
+//! Custom providers can implement the [`VfsProvider`] trait for other storage
+//! backends.
 //!
 //! # Read-Only vs Read-Write
 //!
 //! All providers must implement read operations. Write operations are optional
 //! and default to returning [`VfsError::ReadOnly`]. Use
 //! [`VfsProvider::is_read_only()`] to check capability.
+//!
+//! # Layered Mounts
+//!
+//! [`Vfs::mount_layer()`] stacks several providers under one source name for
+//! patch packs, user mods and editor scratch overlays. Reads fall through to
+//! lower layers, writes go to the top writable layer, and deletions of lower
+//! files leave whiteout markers (see [`OverlayProvider`]). Mounting and
+//! unmounting work at any time, through any clone of the [`Vfs`].
+//!
+//! # Metadata and Streaming
+//!
+//! [`VfsProvider::metadata()`] and [`VfsProvider::list_dir_detailed()`]
+//! report whether a path is a file or a directory along with its size and
+//! modification time. [`VfsProvider::read_range()`] reads part of a file, and
+//! [`Vfs::open_reader()`] returns a [`VfsReader`] that streams a file in
+//! fixed-size chunks instead of loading it whole.
+//!
+//! # Change Notifications
+//!
+//! [`Vfs::watch()`] returns a [`VfsWatcher`] that reports created, modified
+//! and removed files across all mounts whose provider supports watching.
+//! [`FileSystemProvider`] uses OS notifications, [`SftpProvider`] polls the
+//! remote tree, and [`MemoryProvider`] reports its own mutations.
 
+#[cfg(all(feature = "cache", not(target_arch = "wasm32")))]
+mod cache;
 mod error;
 #[cfg(all(feature = "filesystem", not(target_arch = "wasm32")))]
 mod filesystem;
+#[cfg(feature = "http")]
+mod http;
 mod memory;
+mod metadata;
+mod overlay;
+#[cfg(feature = "pack")]
+mod pack;
 pub mod path;
 mod poll;
 mod provider;
+mod reader;
 #[cfg(all(feature = "sftp", not(target_arch = "wasm32")))]
 mod sftp;
 mod vfs;
+mod watch;
 
+#[cfg(all(feature = "cache", not(target_arch = "wasm32")))]
+pub use cache::{CacheConfig, CachingProvider};
 pub use error::VfsError;
 #[cfg(all(feature = "filesystem", not(target_arch = "wasm32")))]
 pub use filesystem::FileSystemProvider;
+#[cfg(feature = "http")]
+pub use http::{HttpConfig, HttpManifest, HttpManifestEntry, HttpProvider};
 pub use memory::MemoryProvider;
+pub use metadata::{VfsDirEntry, VfsEntryKind, VfsMetadata};
+pub use overlay::{OverlayProvider, WHITEOUT_PREFIX};
+#[cfg(feature = "pack")]
+pub use pack::{PackProvider, PackSource, PakCompression, PakWriter};
 pub use poll::poll_now;
 pub use provider::{VfsFuture, VfsProvider};
+pub use reader::VfsReader;
 #[cfg(all(feature = "sftp", not(target_arch = "wasm32")))]
 pub use sftp::{SftpConfig, SftpProvider};
-pub use vfs::Vfs;
+pub use vfs::{Vfs, VfsLayerId};
+pub use watch::{VfsChange, VfsChangeKind, VfsWatcher};
@@ -1,8 +1,11 @@
-use std::collections::{HashMap, HashSet};
-use std::sync::{Arc, RwLock};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, RwLock, mpsc};
+use std::time::SystemTime;
 
 use crate::error::VfsError;
+use crate::metadata::{VfsDirEntry, VfsMetadata, clamp_range};
 use crate::provider::{VfsFuture, VfsProvider};
+use crate::watch::{VfsChange, VfsChangeKind, VfsWatcher};
 
 /// In-memory VFS provider for tests and embedded assets.
 ///
@@ -10,7 +13,12 @@ use crate::provider::{VfsFuture, VfsProvider};
 /// Supports both read and write operations.
 ///
 /// Directories are implicit — they exist whenever a file path contains
-/// that directory prefix.
+/// that directory prefix. Files record the time they were last written as
+/// their modification time (except on wasm, where the clock is unavailable).
+///
+/// Watchers created with [`watch()`](VfsProvider::watch) are notified of every
+/// mutation, whether made through the VFS or directly via [`insert`](Self::insert)
+/// and [`remove`](Self::remove).
 ///
 /// # Example
 ///
@@ -19,12 +27,13 @@ use crate::provider::{VfsFuture, VfsProvider};
 /// mem.insert("config/settings.json", b"{}".to_vec());
 /// mem.insert("shaders/basic.wgsl", shader_bytes);
 ///
-/// let mut vfs = Vfs::new();
+/// let vfs = Vfs::new();
 /// vfs.mount("builtin", mem);
 /// ```
 #[derive(Clone)]
 pub struct MemoryProvider {
-    files: Arc<RwLock<HashMap<String, Vec<u8>>>>,
+    files: Arc<RwLock<HashMap<String, MemoryFile>>>,
+    watchers: Arc<Mutex<Vec<mpsc::Sender<VfsChange>>>>,
 }
 
 impl MemoryProvider {
@@ -32,6 +41,7 @@ impl MemoryProvider {
     pub fn new() -> Self {
         Self {
             files: Arc::new(RwLock::new(HashMap::new())),
+            watchers: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
@@ -40,13 +50,108 @@ impl MemoryProvider {
     /// The path should use forward slashes and have no leading slash.
     /// Overwrites any existing file at the same path.
     pub fn insert(&self, path: impl Into<String>, data: Vec<u8>) {
-        self.files.write().unwrap().insert(path.into(), data);
+        insert_file(&self.files, &self.watchers, path.into(), data);
     }
 
     /// Remove a file at the given path, returning its data if it existed.
     pub fn remove(&self, path: &str) -> Option<Vec<u8>> {
-        self.files.write().unwrap().remove(path)
+        remove_file(&self.files, &self.watchers, path)
+    }
+}
+
+/// Contents and modification time of a stored file.
+struct MemoryFile {
+    data: Vec<u8>,
+    modified: Option<SystemTime>,
+}
+
+impl MemoryFile {
+    fn metadata(&self) -> VfsMetadata {
+        VfsMetadata::file(self.data.len() as u64, self.modified)
+    }
+}
+
+/// The current time, where the platform provides a clock.
+fn now() -> Option<SystemTime> {
+    if cfg!(target_arch = "wasm32") {
+        None
+    } else {
+        Some(SystemTime::now())
+    }
+}
+
+/// Insert a file and notify watchers.
+fn insert_file(
+    files: &RwLock<HashMap<String, MemoryFile>>,
+    watchers: &Mutex<Vec<mpsc::Sender<VfsChange>>>,
+    path: String,
+    data: Vec<u8>,
+) {
+    let file = MemoryFile {
+        data,
+        modified: now(),
+    };
+    let previous = files.write().unwrap().insert(path.clone(), file);
+    let kind = if previous.is_some() {
+        VfsChangeKind::Modified
+    } else {
+        VfsChangeKind::Created
+    };
+    notify(watchers, VfsChange::new(path, kind));
+}
+
+/// Remove a file and notify watchers if it existed.
+fn remove_file(
+    files: &RwLock<HashMap<String, MemoryFile>>,
+    watchers: &Mutex<Vec<mpsc::Sender<VfsChange>>>,
+    path: &str,
+) -> Option<Vec<u8>> {
+    let removed = files.write().unwrap().remove(path)?;
+    notify(watchers, VfsChange::new(path, VfsChangeKind::Removed));
+    Some(removed.data)
+}
+
+/// Collect the immediate children of directory `path` with their metadata.
+///
+/// A directory's modification time is the latest one of the files below it.
+fn children(files: &HashMap<String, MemoryFile>, path: &str) -> BTreeMap<String, VfsMetadata> {
+    let prefix = if path.is_empty() {
+        String::new()
+    } else {
+        format!("{path}/")
+    };
+
+    let mut children: BTreeMap<String, VfsMetadata> = BTreeMap::new();
+    for (key, file) in files {
+        let Some(rest) = key.strip_prefix(&prefix) else {
+            continue;
+        };
+        // Extract the immediate child name (first segment)
+        let (child, metadata) = match rest.find('/') {
+            Some(pos) => (&rest[..pos], VfsMetadata::directory(file.modified)),
+            None => (rest, file.metadata()),
+        };
+        if child.is_empty() {
+            continue;
+        }
+        children
+            .entry(child.to_owned())
+            .and_modify(|existing| {
+                if existing.is_dir() {
+                    existing.modified = existing.modified.max(metadata.modified);
+                }
+            })
+            .or_insert(metadata);
     }
+    children
+}
+
+/// Send a change to all live watchers, forgetting dropped ones.
+fn notify(watchers: &Mutex<Vec<mpsc::Sender<VfsChange>>>, change: VfsChange) {
+    watchers
+        .lock()
+        .unwrap()
+        .retain(|sender| sender.send(change.clone()).is_ok());
 }
 
 impl Default for MemoryProvider {
@@ -61,7 +166,48 @@ impl VfsProvider for MemoryProvider {
         let path = path.to_owned();
         Box::pin(async move {
             let map = files.read().unwrap();
-            map.get(&path).cloned().ok_or(VfsError::NotFound(path))
+            map.get(&path)
+                .map(|file| file.data.clone())
+                .ok_or(VfsError::NotFound(path))
+        })
+    }
+
+    fn metadata(&self, path: &str) -> VfsFuture<VfsMetadata> {
+        let files = self.files.clone();
+        let path = path.to_owned();
+        Box::pin(async move {
+            let map = files.read().unwrap();
+            if let Some(file) = map.get(&path) {
+                return Ok(file.metadata());
+            }
+            let (parent, name) = path.rsplit_once('/').unwrap_or(("", &path));
+            match children(&map, parent).remove(name) {
+                Some(metadata) => Ok(metadata),
+                None if path.is_empty() => Ok(VfsMetadata::directory(None)),
+                None => Err(VfsError::NotFound(path)),
+            }
+        })
+    }
+
+    fn list_dir_detailed(&self, path: &str) -> VfsFuture<Vec<VfsDirEntry>> {
+        let files = self.files.clone();
+        let path = path.to_owned();
+        Box::pin(async move {
+            let map = files.read().unwrap();
+            Ok(children(&map, &path)
+                .into_iter()
+                .map(|(name, metadata)| VfsDirEntry::new(name, metadata))
+                .collect())
+        })
+    }
+
+    fn read_range(&self, path: &str, offset: u64, len: u64) -> VfsFuture<Vec<u8>> {
+        let files = self.files.clone();
+        let path = path.to_owned();
+        Box::pin(async move {
+            let map = files.read().unwrap();
+            let file = map.get(&path).ok_or(VfsError::NotFound(path.clone()))?;
+            Ok(file.data[clamp_range(file.data.len(), offset, len)].to_vec())
         })
     }
 
@@ -79,30 +225,7 @@ impl VfsProvider for MemoryProvider {
         let path = path.to_owned();
         Box::pin(async move {
             let map = files.read().unwrap();
-            let mut children = HashSet::new();
-
-            let prefix = if path.is_empty() {
-                String::new()
-            } else {
-                format!("{path}/")
-            };
-
-            for key in map.keys() {
-                if let Some(rest) = key.strip_prefix(&prefix) {
-                    // Extract the immediate child name (first segment)
-                    let child = match rest.find('/') {
-                        Some(pos) => &rest[..pos],
-                        None => rest,
-                    };
-                    if !child.is_empty() {
-                        children.insert(child.to_owned());
-                    }
-                }
-            }
-
-            let mut result: Vec<String> = children.into_iter().collect();
-            result.sort();
-            Ok(result)
+            Ok(children(&map, &path).into_keys().collect())
         })
     }
 
@@ -112,22 +235,20 @@ impl VfsProvider for MemoryProvider {
 
     fn write(&self, path: &str, data: Vec<u8>) -> VfsFuture<()> {
         let files = self.files.clone();
+        let watchers = self.watchers.clone();
         let path = path.to_owned();
         Box::pin(async move {
-            files.write().unwrap().insert(path, data);
+            insert_file(&files, &watchers, path, data);
             Ok(())
         })
     }
 
     fn delete(&self, path: &str) -> VfsFuture<()> {
         let files = self.files.clone();
+        let watchers = self.watchers.clone();
         let path = path.to_owned();
         Box::pin(async move {
-            files
-                .write()
-                .unwrap()
-                .remove(&path)
-                .ok_or(VfsError::NotFound(path))?;
+            remove_file(&files, &watchers, &path).ok_or(VfsError::NotFound(path))?;
             Ok(())
         })
     }
@@ -136,6 +257,12 @@ impl VfsProvider for MemoryProvider {
         // Directories are implicit in MemoryProvider
         Box::pin(async { Ok(()) })
     }
+
+    fn watch(&self) -> Option<VfsWatcher> {
+        let (sender, receiver) = mpsc::channel();
+        self.watchers.lock().unwrap().push(sender);
+        Some(VfsWatcher::new(receiver, ()))
+    }
 }
 
 #[cfg(test)]
@@ -221,6 +348,59 @@ mod tests {
         assert!(entries.is_empty());
     }
 
+    #[test]
+    fn metadata_of_files_and_dirs() {
+        let mem = MemoryProvider::new();
+        mem.insert("dir/a.txt", b"hello".to_vec());
+
+        let file = poll_ready(mem.metadata("dir/a.txt")).unwrap();
+        assert!(file.is_file());
+        assert_eq!(file.size, 5);
+
+        assert!(poll_ready(mem.metadata("dir")).unwrap().is_dir());
+        assert!(poll_ready(mem.metadata("")).unwrap().is_dir());
+        assert!(matches!(
+            poll_ready(mem.metadata("missing")),
+            Err(VfsError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn list_dir_detailed_reports_kinds() {
+        let mem = MemoryProvider::new();
+        mem.insert("a.txt", b"abc".to_vec());
+        mem.insert("b/c.txt", vec![]);
+
+        let entries = poll_ready(mem.list_dir_detailed("")).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].name, "a.txt");
+        assert!(entries[0].metadata.is_file());
+        assert_eq!(entries[0].metadata.size, 3);
+        assert_eq!(entries[1].name, "b");
+        assert!(entries[1].is_dir());
+    }
+
+    #[test]
+    fn read_range_clamps_to_file() {
+        let mem = MemoryProvider::new();
+        mem.insert("file.bin", b"0123456789".to_vec());
+
+        assert_eq!(
+            poll_ready(mem.read_range("file.bin", 2, 3)).unwrap(),
+            b"234"
+        );
+        assert_eq!(
+            poll_ready(mem.read_range("file.bin", 8, 10)).unwrap(),
+            b"89"
+        );
+        assert!(
+            poll_ready(mem.read_range("file.bin", 20, 1))
+                .unwrap()
+                .is_empty()
+        );
+        assert!(poll_ready(mem.read_range("nope.bin", 0, 1)).is_err());
+    }
+
     #[test]
     fn write_and_read() {
         let mem = MemoryProvider::new();
@@ -258,4 +438,37 @@ mod tests {
         assert_eq!(data, Some(b"data".to_vec()));
         assert!(mem.remove("file.txt").is_none());
     }
+
+    #[test]
+    fn watch_reports_mutations() {
+        let mem = MemoryProvider::new();
+        let watcher = mem.watch().unwrap();
+
+        mem.insert("a.txt", b"1".to_vec());
+        assert_eq!(
+            watcher.poll(),
+            vec![VfsChange::new("a.txt", VfsChangeKind::Created)]
+        );
+
+        poll_ready(mem.write("a.txt", b"2".to_vec())).unwrap();
+        assert_eq!(
+            watcher.poll(),
+            vec![VfsChange::new("a.txt", VfsChangeKind::Modified)]
+        );
+
+        poll_ready(mem.delete("a.txt")).unwrap();
+        assert!(mem.remove("missing.txt").is_none());
+        assert_eq!(
+            watcher.poll(),
+            vec![VfsChange::new("a.txt", VfsChangeKind::Removed)]
+        );
+    }
+
+    #[test]
+    fn dropped_watcher_is_forgotten() {
+        let mem = MemoryProvider::new();
+        drop(mem.watch().unwrap());
+        mem.insert("a.txt", vec![]);
+        assert!(mem.watchers.lock().unwrap().is_empty());
+    }
 }
@@ -0,0 +1,137 @@
+#[cfg(any(feature = "pack", feature = "http", feature = "cache"))]
+use std::collections::{BTreeMap, HashMap};
+use std::time::SystemTime;
+
+/// Whether a path refers to a file or a directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VfsEntryKind {
+    /// A regular file.
+    File,
+    /// A directory.
+    Directory,
+}
+
+/// Kind, size and modification time of a file or directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VfsMetadata {
+    /// Whether this is a file or a directory.
+    pub kind: VfsEntryKind,
+    /// Size in bytes. Always 0 for directories.
+    pub size: u64,
+    /// Last modification time, if the provider tracks it.
+    pub modified: Option<SystemTime>,
+}
+
+impl VfsMetadata {
+    /// Metadata of a file of `size` bytes.
+    pub fn file(size: u64, modified: Option<SystemTime>) -> Self {
+        Self {
+            kind: VfsEntryKind::File,
+            size,
+            modified,
+        }
+    }
+
+    /// Metadata of a directory.
+    pub fn directory(modified: Option<SystemTime>) -> Self {
+        Self {
+            kind: VfsEntryKind::Directory,
+            size: 0,
+            modified,
+        }
+    }
+
+    /// Returns `true` if this is a file.
+    pub fn is_file(&self) -> bool {
+        self.kind == VfsEntryKind::File
+    }
+
+    /// Returns `true` if this is a directory.
+    pub fn is_dir(&self) -> bool {
+        self.kind == VfsEntryKind::Directory
+    }
+}
+
+/// A child of a directory, as returned by
+/// [`list_dir_detailed`](crate::VfsProvider::list_dir_detailed).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VfsDirEntry {
+    /// File or directory name (not a full path).
+    pub name: String,
+    /// Metadata of the entry.
+    pub metadata: VfsMetadata,
+}
+
+impl VfsDirEntry {
+    /// Create a directory entry.
+    pub fn new(name: impl Into<String>, metadata: VfsMetadata) -> Self {
+        Self {
+            name: name.into(),
+            metadata,
+        }
+    }
+
+    /// Returns `true` if the entry is a directory.
+    pub fn is_dir(&self) -> bool {
+        self.metadata.is_dir()
+    }
+}
+
+/// Clamp a byte range to a file of `len` bytes.
+///
+/// Ranges starting past the end are empty.
+pub(crate) fn clamp_range(len: usize, offset: u64, range_len: u64) -> std::ops::Range<usize> {
+    let start = usize::try_from(offset).unwrap_or(usize::MAX).min(len);
+    let end = start.saturating_add(usize::try_from(range_len).unwrap_or(usize::MAX));
+    start..end.min(len)
+}
+
+/// Build the directory listings implied by a set of file paths.
+///
+/// Returns the sorted children of every directory, keyed by directory path
+/// (root is `""`). A directory's modification time is the latest of the
+/// files below it.
+#[cfg(any(feature = "pack", feature = "http", feature = "cache"))]
+pub(crate) fn build_dirs<'a>(
+    files: impl IntoIterator<Item = (&'a str, VfsMetadata)>,
+) -> HashMap<String, Vec<VfsDirEntry>> {
+    let mut tree: BTreeMap<&str, BTreeMap<&str, VfsMetadata>> = BTreeMap::new();
+    tree.insert("", BTreeMap::new());
+    for (path, file) in files {
+        let mut parent = "";
+        let mut start = 0;
+        for (i, _) in path.match_indices('/') {
+            let dir = tree
+                .entry(parent)
+                .or_default()
+                .entry(&path[start..i])
+                .or_insert(VfsMetadata::directory(None));
+            dir.modified = dir.modified.max(file.modified);
+            parent = &path[..i];
+            tree.entry(parent).or_default();
+            start = i + 1;
+        }
+        tree.entry(parent).or_default().insert(&path[start..], file);
+    }
+    tree.into_iter()
+        .map(|(dir, children)| {
+            let entries = children
+                .into_iter()
+                .map(|(name, metadata)| VfsDirEntry::new(name, metadata))
+                .collect();
+            (dir.to_owned(), entries)
+        })
+        .collect()
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date.
+#[cfg(any(feature = "pack", feature = "http"))]
+pub(crate) fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = (month + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
@@ -0,0 +1,499 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+use crate::error::VfsError;
+use crate::metadata::{VfsDirEntry, VfsMetadata};
+use crate::provider::{VfsFuture, VfsProvider};
+use crate::watch::VfsWatcher;
+
+/// Name prefix of whiteout markers.
+///
+/// A file `.wh.<name>` in a layer hides `<name>` in every layer below it,
+/// along with everything under `<name>` if it is a directory.
+pub const WHITEOUT_PREFIX: &str = ".wh.";
+
+/// Provider stacking several layers into one merged view.
+///
+/// Layers are ordered from top (highest priority) to bottom. Reads fall
+/// through the stack and return the first layer that has the file. Writes,
+/// deletions and new directories go to the top writable layer. Deleting a
+/// file that still exists in a lower layer leaves a whiteout marker (see
+/// [`WHITEOUT_PREFIX`]) in the writable layer, so the deletion also hides
+/// the lower copy. Packs can ship such markers to remove base files.
+///
+/// Each layer's markers are read once per directory and cached. The
+/// overlay keeps the cache current for its own writes and deletions, and
+/// listing a directory refreshes it; markers added to a layer directly show
+/// up once their directory is next listed through the overlay.
+///
+/// [`Vfs::mount_layer()`](crate::Vfs::mount_layer) builds overlays
+/// automatically when several providers share a source name.
+///
+/// # Example
+///
+/// ```ignore
+/// let overlay = OverlayProvider::new(vec![
+///     Arc::new(FileSystemProvider::new("./mods/hd_textures")),
+///     Arc::new(PackProvider::open("assets.pak")?),
+/// ]);
+/// ```
+#[derive(Clone)]
+pub struct OverlayProvider {
+    layers: Arc<[Arc<dyn VfsProvider>]>,
+    /// Whiteout markers of each layer, parallel to `layers`.
+    whiteouts: Arc<[WhiteoutCache]>,
+}
+
+impl OverlayProvider {
+    /// Create an overlay from layers ordered top to bottom.
+    pub fn new(layers: Vec<Arc<dyn VfsProvider>>) -> Self {
+        Self {
+            whiteouts: layers.iter().map(|_| WhiteoutCache::default()).collect(),
+            layers: layers.into(),
+        }
+    }
+
+    /// Number of layers in the stack.
+    pub fn layer_count(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Index of the top writable layer.
+    fn writable_layer(&self) -> Option<usize> {
+        self.layers.iter().position(|layer| !layer.is_read_only())
+    }
+}
+
+impl VfsProvider for OverlayProvider {
+    fn read(&self, path: &str) -> VfsFuture<Vec<u8>> {
+        let layers = Arc::clone(&self.layers);
+        let whiteouts = Arc::clone(&self.whiteouts);
+        let path = path.to_owned();
+        Box::pin(async move {
+            for (layer, cache) in layers.iter().zip(whiteouts.iter()) {
+                match layer.read(&path).await {
+                    Err(VfsError::NotFound(_)) => {}
+                    result => return result,
+                }
+                if cache.hides(layer.as_ref(), &path).await? {
+                    break;
+                }
+            }
+            Err(VfsError::NotFound(path))
+        })
+    }
+
+    fn metadata(&self, path: &str) -> VfsFuture<VfsMetadata> {
+        let layers = Arc::clone(&self.layers);
+        let whiteouts = Arc::clone(&self.whiteouts);
+        let path = path.to_owned();
+        Box::pin(async move { find_metadata(&layers, &whiteouts, &path).await })
+    }
+
+    fn list_dir_detailed(&self, path: &str) -> VfsFuture<Vec<VfsDirEntry>> {
+        let layers = Arc::clone(&self.layers);
+        let whiteouts = Arc::clone(&self.whiteouts);
+        let path = path.to_owned();
+        Box::pin(async move {
+            let mut merged: BTreeMap<String, VfsMetadata> = BTreeMap::new();
+            let mut hidden = HashSet::new();
+            for (layer, cache) in layers.iter().zip(whiteouts.iter()) {
+                let entries = layer.list_dir_detailed(&path).await?;
+                // Whiteouts only hide entries of lower layers.
+                let mut markers = HashSet::new();
+                for entry in entries {
+                    if let Some(name) = entry.name.strip_prefix(WHITEOUT_PREFIX) {
+                        markers.insert(name.to_owned());
+                    } else if !hidden.contains(&entry.name) {
+                        merged.entry(entry.name).or_insert(entry.metadata);
+                    }
+                }
+                hidden.extend(markers.iter().cloned());
+                cache.store(&path, markers);
+                if cache.hides(layer.as_ref(), &path).await? {
+                    break;
+                }
+            }
+            Ok(merged
+                .into_iter()
+                .map(|(name, metadata)| VfsDirEntry::new(name, metadata))
+                .collect())
+        })
+    }
+
+    fn read_range(&self, path: &str, offset: u64, len: u64) -> VfsFuture<Vec<u8>> {
+        let layers = Arc::clone(&self.layers);
+        let whiteouts = Arc::clone(&self.whiteouts);
+        let path = path.to_owned();
+        Box::pin(async move {
+            for (layer, cache) in layers.iter().zip(whiteouts.iter()) {
+                match layer.read_range(&path, offset, len).await {
+                    Err(VfsError::NotFound(_)) => {}
+                    result => return result,
+                }
+                if cache.hides(layer.as_ref(), &path).await? {
+                    break;
+                }
+            }
+            Err(VfsError::NotFound(path))
+        })
+    }
+
+    fn is_read_only(&self) -> bool {
+        self.writable_layer().is_none()
+    }
+
+    fn write(&self, path: &str, data: Vec<u8>) -> VfsFuture<()> {
+        let Some(top) = self.writable_layer() else {
+            return Box::pin(async { Err(VfsError::ReadOnly) });
+        };
+        let layer = Arc::clone(&self.layers[top]);
+        let whiteouts = Arc::clone(&self.whiteouts);
+        let path = path.to_owned();
+        Box::pin(async move {
+            layer.write(&path, data).await?;
+            // The file is visible again if it had been deleted.
+            let result = match layer.delete(&whiteout_path(&path)).await {
+                Ok(()) | Err(VfsError::NotFound(_)) => Ok(()),
+                Err(e) => Err(e),
+            };
+            whiteouts[top].forget(parent(&path));
+            result
+        })
+    }
+
+    fn delete(&self, path: &str) -> VfsFuture<()> {
+        let Some(top) = self.writable_layer() else {
+            return Box::pin(async { Err(VfsError::ReadOnly) });
+        };
+        let layers = Arc::clone(&self.layers);
+        let whiteouts = Arc::clone(&self.whiteouts);
+        let path = path.to_owned();
+        Box::pin(async move {
+            // A read-only layer above the writable one can't be hidden.
+            if find_metadata(&layers[..top], &whiteouts[..top], &path)
+                .await
+                .is_ok()
+            {
+                return Err(VfsError::ReadOnly);
+            }
+            let layer = &layers[top];
+            let removed = match layer.delete(&path).await {
+                Ok(()) => true,
+                Err(VfsError::NotFound(_)) => false,
+                Err(e) => return Err(e),
+            };
+            if whiteouts[top].hides(layer.as_ref(), &path).await? {
+                return if removed {
+                    Ok(())
+                } else {
+                    Err(VfsError::NotFound(path))
+                };
+            }
+            match find_metadata(&layers[top + 1..], &whiteouts[top + 1..], &path).await {
+                Ok(_) => {
+                    let result = layer.write(&whiteout_path(&path), Vec::new()).await;
+                    whiteouts[top].forget(parent(&path));
+                    result
+                }
+                Err(VfsError::NotFound(_)) if removed => Ok(()),
+                Err(e) => Err(e),
+            }
+        })
+    }
+
+    fn create_dir(&self, path: &str) -> VfsFuture<()> {
+        match self.writable_layer() {
+            Some(top) => self.layers[top].create_dir(path),
+            None => Box::pin(async { Err(VfsError::ReadOnly) }),
+        }
+    }
+
+    fn watch(&self) -> Option<VfsWatcher> {
+        let mut watcher = VfsWatcher::empty();
+        let mut any = false;
+        for layer in self.layers.iter() {
+            if let Some(layer_watcher) = layer.watch() {
+                watcher.merge(layer_watcher);
+                any = true;
+            }
+        }
+        any.then_some(watcher)
+    }
+}
+
+/// Path of the whiteout marker hiding `path`.
+fn whiteout_path(path: &str) -> String {
+    match path.rsplit_once('/') {
+        Some((dir, name)) => format!("{dir}/{WHITEOUT_PREFIX}{name}"),
+        None => format!("{WHITEOUT_PREFIX}{path}"),
+    }
+}
+
+/// Directory containing `path`.
+fn parent(path: &str) -> &str {
+    path.rsplit_once('/').map_or("", |(dir, _)| dir)
+}
+
+/// Whiteout marker names of one layer, by directory.
+#[derive(Default)]
+struct WhiteoutCache(Mutex<HashMap<String, Arc<HashSet<String>>>>);
+
+impl WhiteoutCache {
+    /// Whether `layer` hides `path`, or a directory above it, in the layers
+    /// below it.
+    async fn hides(&self, layer: &dyn VfsProvider, path: &str) -> Result<bool, VfsError> {
+        let mut dir_end = 0usize;
+        for name in path.split('/') {
+            let dir = &path[..dir_end.saturating_sub(1)];
+            dir_end += name.len() + 1;
+            if !name.is_empty() && self.markers(layer, dir).await?.contains(name) {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Markers in `dir`, listing it on first use.
+    async fn markers(
+        &self,
+        layer: &dyn VfsProvider,
+        dir: &str,
+    ) -> Result<Arc<HashSet<String>>, VfsError> {
+        if let Some(markers) = self.0.lock().unwrap().get(dir) {
+            return Ok(Arc::clone(markers));
+        }
+        let markers = match layer.list_dir(dir).await {
+            Ok(names) => names
+                .into_iter()
+                .filter_map(|name| name.strip_prefix(WHITEOUT_PREFIX).map(str::to_owned))
+                .collect(),
+            Err(VfsError::NotFound(_)) => HashSet::new(),
+            Err(e) => return Err(e),
+        };
+        let markers = Arc::new(markers);
+        self.0
+            .lock()
+            .unwrap()
+            .insert(dir.to_owned(), Arc::clone(&markers));
+        Ok(markers)
+    }
+
+    fn store(&self, dir: &str, markers: HashSet<String>) {
+        self.0
+            .lock()
+            .unwrap()
+            .insert(dir.to_owned(), Arc::new(markers));
+    }
+
+    fn forget(&self, dir: &str) {
+        self.0.lock().unwrap().remove(dir);
+    }
+}
+
+/// Metadata of `path` from the first layer that has it.
+async fn find_metadata(
+    layers: &[Arc<dyn VfsProvider>],
+    whiteouts: &[WhiteoutCache],
+    path: &str,
+) -> Result<VfsMetadata, VfsError> {
+    for (layer, cache) in layers.iter().zip(whiteouts) {
+        match layer.metadata(path).await {
+            Err(VfsError::NotFound(_)) => {}
+            result => return result,
+        }
+        if cache.hides(layer.as_ref(), path).await? {
+            break;
+        }
+    }
+    Err(VfsError::NotFound(path.to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MemoryProvider;
+    use std::pin::Pin;
+    use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+    fn poll_ready<T>(mut fut: VfsFuture<T>) -> Result<T, VfsError> {
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        match Pin::new(&mut fut).poll(&mut cx) {
+            Poll::Ready(val) => val,
+            Poll::Pending => panic!("expected future to be immediately ready"),
+        }
+    }
+
+    fn noop_waker() -> Waker {
+        fn noop(_: *const ()) {}
+        fn clone(p: *const ()) -> RawWaker {
+            RawWaker::new(p, &VTABLE)
+        }
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) }
+    }
+
+    /// Read-only wrapper, standing in for a pack.
+    struct ReadOnly(MemoryProvider);
+
+    impl VfsProvider for ReadOnly {
+        fn read(&self, path: &str) -> VfsFuture<Vec<u8>> {
+            self.0.read(path)
+        }
+
+        fn metadata(&self, path: &str) -> VfsFuture<VfsMetadata> {
+            self.0.metadata(path)
+        }
+
+        fn list_dir_detailed(&self, path: &str) -> VfsFuture<Vec<VfsDirEntry>> {
+            self.0.list_dir_detailed(path)
+        }
+    }
+
+    fn base() -> MemoryProvider {
+        let base = MemoryProvider::new();
+        base.insert("config.toml", b"base".to_vec());
+        base.insert("textures/brick.png", b"brick".to_vec());
+        base.insert("textures/stone.png", b"stone".to_vec());
+        base
+    }
+
+    fn overlay(top: &MemoryProvider, base: MemoryProvider) -> OverlayProvider {
+        OverlayProvider::new(vec![Arc::new(top.clone()), Arc::new(ReadOnly(base))])
+    }
+
+    #[test]
+    fn reads_fall_through() {
+        let top = MemoryProvider::new();
+        top.insert("config.toml", b"patched".to_vec());
+        let overlay = overlay(&top, base());
+
+        assert_eq!(poll_ready(overlay.read("config.toml")).unwrap(), b"patched");
+        assert_eq!(
+            poll_ready(overlay.read("textures/brick.png")).unwrap(),
+            b"brick"
+        );
+        assert_eq!(
+            poll_ready(overlay.read_range("textures/stone.png", 1, 2)).unwrap(),
+            b"to"
+        );
+        assert!(matches!(
+            poll_ready(overlay.read("missing")),
+            Err(VfsError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn writes_go_to_top_writable_layer() {
+        let top = MemoryProvider::new();
+        let overlay = overlay(&top, base());
+        assert!(!overlay.is_read_only());
+
+        poll_ready(overlay.write("textures/brick.png", b"new".to_vec())).unwrap();
+        assert_eq!(poll_ready(top.read("textures/brick.png")).unwrap(), b"new");
+        assert_eq!(
+            poll_ready(overlay.read("textures/brick.png")).unwrap(),
+            b"new"
+        );
+    }
+
+    #[test]
+    fn delete_leaves_whiteout() {
+        let top = MemoryProvider::new();
+        top.insert("textures/brick.png", b"override".to_vec());
+        let overlay = overlay(&top, base());
+
+        poll_ready(overlay.delete("textures/brick.png")).unwrap();
+        assert!(poll_ready(top.exists("textures/.wh.brick.png")).unwrap());
+        assert!(!poll_ready(overlay.exists("textures/brick.png")).unwrap());
+        assert!(poll_ready(overlay.read("textures/brick.png")).is_err());
+        assert_eq!(
+            poll_ready(overlay.list_dir("textures")).unwrap(),
+            vec!["stone.png"]
+        );
+
+        // Writing again removes the marker.
+        poll_ready(overlay.write("textures/brick.png", b"back".to_vec())).unwrap();
+        assert!(!poll_ready(top.exists("textures/.wh.brick.png")).unwrap());
+        assert_eq!(
+            poll_ready(overlay.read("textures/brick.png")).unwrap(),
+            b"back"
+        );
+    }
+
+    #[test]
+    fn directory_whiteout_hides_contents() {
+        let top = MemoryProvider::new();
+        top.insert(".wh.textures", vec![]);
+        let overlay = overlay(&top, base());
+
+        assert!(matches!(
+            poll_ready(overlay.read("textures/brick.png")),
+            Err(VfsError::NotFound(_))
+        ));
+        assert!(matches!(
+            poll_ready(overlay.read_range("textures/brick.png", 0, 1)),
+            Err(VfsError::NotFound(_))
+        ));
+        assert!(!poll_ready(overlay.exists("textures/stone.png")).unwrap());
+        assert!(poll_ready(overlay.list_dir("textures")).unwrap().is_empty());
+        assert_eq!(poll_ready(overlay.read("config.toml")).unwrap(), b"base");
+    }
+
+    #[test]
+    fn whiteouts_track_overlay_writes() {
+        let top = MemoryProvider::new();
+        let overlay = overlay(&top, base());
+        assert!(poll_ready(overlay.exists("textures/brick.png")).unwrap());
+
+        poll_ready(overlay.delete("textures/brick.png")).unwrap();
+        assert!(!poll_ready(overlay.exists("textures/brick.png")).unwrap());
+
+        // A marker added behind the overlay's back shows up after a listing.
+        top.insert("textures/.wh.stone.png", vec![]);
+        assert!(poll_ready(overlay.list_dir("textures")).unwrap().is_empty());
+        assert!(!poll_ready(overlay.exists("textures/stone.png")).unwrap());
+    }
+
+    #[test]
+    fn delete_missing_fails() {
+        let top = MemoryProvider::new();
+        let overlay = overlay(&top, base());
+        assert!(matches!(
+            poll_ready(overlay.delete("missing.txt")),
+            Err(VfsError::NotFound(_))
+        ));
+        poll_ready(overlay.delete("config.toml")).unwrap();
+        assert!(matches!(
+            poll_ready(overlay.delete("config.toml")),
+            Err(VfsError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn merged_listing() {
+        let top = MemoryProvider::new();
+        top.insert("mods/readme.txt", vec![]);
+        top.insert("textures/brick.png", b"hd brick".to_vec());
+        let overlay = overlay(&top, base());
+
+        let entries = poll_ready(overlay.list_dir_detailed("")).unwrap();
+        let names: Vec<_> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["config.toml", "mods", "textures"]);
+
+        let textures = poll_ready(overlay.list_dir_detailed("textures")).unwrap();
+        assert_eq!(textures.len(), 2);
+        assert_eq!(textures[0].metadata.size, 8);
+    }
+
+    #[test]
+    fn read_only_overlay() {
+        let overlay = OverlayProvider::new(vec![Arc::new(ReadOnly(base()))]);
+        assert!(overlay.is_read_only());
+        assert!(matches!(
+            poll_ready(overlay.write("a.txt", vec![])),
+            Err(VfsError::ReadOnly)
+        ));
+    }
+}
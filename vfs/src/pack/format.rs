@@ -0,0 +1,238 @@
+//! On-disk layout of the engine's `.pak` format.
+//!
+//! All integers are little-endian.
+//!
+//! ```text
+//! Header (32 bytes)
+//!   magic         [u8; 4]   b"RLPK"
+//!   version       u32       1
+//!   index_offset  u64       byte offset of the index
+//!   index_size    u64       byte size of the index
+//!   entry_count   u32
+//!   reserved      u32
+//! Entry data, one blob per file, stored as compressed by the writer
+//! Index, `entry_count` records of
+//!   path_len      u16
+//!   path          [u8; path_len]   normalized UTF-8 VFS path
+//!   offset        u64              byte offset of the blob
+//!   stored_size   u64              size of the blob
+//!   size          u64              uncompressed size
+//!   compression   u8               0 = none, 1 = lz4, 2 = zstd
+//!   modified      u64              seconds since the Unix epoch, u64::MAX if unknown
+//!   hash          [u8; 32]         SHA-256 of the uncompressed content
+//! ```
+//!
+//! The index lives at the end so the writer can stream entries without
+//! knowing the file list up front; the header is patched on finish.
+
+use std::io::{self, Read};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use sha2::{Digest, Sha256};
+
+pub(super) const MAGIC: [u8; 4] = *b"RLPK";
+pub(super) const VERSION: u32 = 1;
+pub(super) const HEADER_SIZE: u64 = 32;
+/// Size of an index record with an empty path.
+const MIN_RECORD_SIZE: usize = 67;
+/// Largest expansion LZ4 block decompression can produce per input byte.
+const LZ4_MAX_RATIO: u64 = 255;
+
+/// Compression applied to a single `.pak` entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PakCompression {
+    /// Stored as-is. Range reads seek directly into the pack.
+    None,
+    /// LZ4 block compression. Fastest to decompress.
+    Lz4,
+    /// Zstandard. Better ratio at a higher decompression cost.
+    #[default]
+    Zstd,
+}
+
+impl PakCompression {
+    fn to_byte(self) -> u8 {
+        match self {
+            PakCompression::None => 0,
+            PakCompression::Lz4 => 1,
+            PakCompression::Zstd => 2,
+        }
+    }
+
+    fn from_byte(byte: u8) -> io::Result<Self> {
+        match byte {
+            0 => Ok(PakCompression::None),
+            1 => Ok(PakCompression::Lz4),
+            2 => Ok(PakCompression::Zstd),
+            other => Err(invalid_data(format!("unknown pak compression {other}"))),
+        }
+    }
+
+    /// Compress `data`. Returns `None` for [`PakCompression::None`].
+    pub(super) fn compress(self, data: &[u8]) -> Option<Vec<u8>> {
+        match self {
+            PakCompression::None => None,
+            PakCompression::Lz4 => Some(lz4_flex::compress(data)),
+            PakCompression::Zstd => Some(ruzstd::encoding::compress_to_vec(
+                data,
+                ruzstd::encoding::CompressionLevel::Fastest,
+            )),
+        }
+    }
+
+    /// Decompress a blob back to `size` bytes.
+    pub(super) fn decompress(self, stored: Vec<u8>, size: u64) -> io::Result<Vec<u8>> {
+        let data = match self {
+            PakCompression::None => stored,
+            PakCompression::Lz4 => {
+                // `size` comes from the index and lz4_flex allocates it up
+                // front, so reject anything the blob could never expand to.
+                if size > (stored.len() as u64).saturating_mul(LZ4_MAX_RATIO) {
+                    return Err(invalid_data(format!(
+                        "pak entry claims {size} bytes from a {}-byte lz4 blob",
+                        stored.len()
+                    )));
+                }
+                lz4_flex::decompress(&stored, size as usize)
+                    .map_err(|e| invalid_data(format!("lz4: {e}")))?
+            }
+            PakCompression::Zstd => {
+                let decoder = ruzstd::decoding::StreamingDecoder::new(stored.as_slice())
+                    .map_err(|e| invalid_data(format!("zstd: {e}")))?;
+                // One byte past `size` is enough to detect an oversized blob.
+                let mut data = Vec::new();
+                decoder
+                    .take(size.saturating_add(1))
+                    .read_to_end(&mut data)?;
+                data
+            }
+        };
+        if data.len() as u64 != size {
+            return Err(invalid_data(format!(
+                "pak entry decompressed to {} bytes, expected {size}",
+                data.len()
+            )));
+        }
+        Ok(data)
+    }
+}
+
+/// Location and properties of one file inside a `.pak`.
+#[derive(Debug, Clone)]
+pub(super) struct PakEntry {
+    pub offset: u64,
+    pub stored_size: u64,
+    pub size: u64,
+    pub compression: PakCompression,
+    pub modified: Option<SystemTime>,
+    pub hash: [u8; 32],
+}
+
+/// Fixed-size header at the start of a `.pak`.
+pub(super) struct PakHeader {
+    pub index_offset: u64,
+    pub index_size: u64,
+    pub entry_count: u32,
+}
+
+impl PakHeader {
+    pub fn encode(&self) -> [u8; HEADER_SIZE as usize] {
+        let mut bytes = [0u8; HEADER_SIZE as usize];
+        bytes[0..4].copy_from_slice(&MAGIC);
+        bytes[4..8].copy_from_slice(&VERSION.to_le_bytes());
+        bytes[8..16].copy_from_slice(&self.index_offset.to_le_bytes());
+        bytes[16..24].copy_from_slice(&self.index_size.to_le_bytes());
+        bytes[24..28].copy_from_slice(&self.entry_count.to_le_bytes());
+        bytes
+    }
+
+    pub fn decode(bytes: &[u8; HEADER_SIZE as usize]) -> io::Result<Self> {
+        if bytes[0..4] != MAGIC {
+            return Err(invalid_data("not a pak file".to_owned()));
+        }
+        let version = u32::from_le_bytes(bytes[4..8].try_into().unwrap());
+        if version != VERSION {
+            return Err(invalid_data(format!("unsupported pak version {version}")));
+        }
+        Ok(Self {
+            index_offset: u64::from_le_bytes(bytes[8..16].try_into().unwrap()),
+            index_size: u64::from_le_bytes(bytes[16..24].try_into().unwrap()),
+            entry_count: u32::from_le_bytes(bytes[24..28].try_into().unwrap()),
+        })
+    }
+}
+
+/// Append the index record for `entry` to `out`.
+pub(super) fn encode_entry(out: &mut Vec<u8>, path: &str, entry: &PakEntry) {
+    out.extend_from_slice(&(path.len() as u16).to_le_bytes());
+    out.extend_from_slice(path.as_bytes());
+    out.extend_from_slice(&entry.offset.to_le_bytes());
+    out.extend_from_slice(&entry.stored_size.to_le_bytes());
+    out.extend_from_slice(&entry.size.to_le_bytes());
+    out.push(entry.compression.to_byte());
+    let modified = entry
+        .modified
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map_or(u64::MAX, |d| d.as_secs());
+    out.extend_from_slice(&modified.to_le_bytes());
+    out.extend_from_slice(&entry.hash);
+}
+
+/// Parse `count` index records.
+pub(super) fn decode_index(mut bytes: &[u8], count: u32) -> io::Result<Vec<(String, PakEntry)>> {
+    let mut entries = Vec::with_capacity((count as usize).min(bytes.len() / MIN_RECORD_SIZE));
+    for _ in 0..count {
+        let path_len = u16::from_le_bytes(take(&mut bytes)?) as usize;
+        if bytes.len() < path_len {
+            return Err(truncated_index());
+        }
+        let (path, rest) = bytes.split_at(path_len);
+        let path = std::str::from_utf8(path)
+            .map_err(|_| invalid_data("pak entry path is not UTF-8".to_owned()))?
+            .to_owned();
+        bytes = rest;
+        let offset = u64::from_le_bytes(take(&mut bytes)?);
+        let stored_size = u64::from_le_bytes(take(&mut bytes)?);
+        let size = u64::from_le_bytes(take(&mut bytes)?);
+        let [compression] = take::<1>(&mut bytes)?;
+        let modified = u64::from_le_bytes(take(&mut bytes)?);
+        let hash = take::<32>(&mut bytes)?;
+        entries.push((
+            path,
+            PakEntry {
+                offset,
+                stored_size,
+                size,
+                compression: PakCompression::from_byte(compression)?,
+                // Times past what `SystemTime` can hold read as unknown.
+                modified: (modified != u64::MAX)
+                    .then_some(modified)
+                    .and_then(|secs| UNIX_EPOCH.checked_add(Duration::from_secs(secs))),
+                hash,
+            },
+        ));
+    }
+    Ok(entries)
+}
+
+/// SHA-256 of `data`.
+pub(super) fn content_hash(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).into()
+}
+
+pub(super) fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn take<const N: usize>(bytes: &mut &[u8]) -> io::Result<[u8; N]> {
+    if bytes.len() < N {
+        return Err(truncated_index());
+    }
+    let (head, rest) = bytes.split_at(N);
+    *bytes = rest;
+    Ok(head.try_into().unwrap())
+}
+
+fn truncated_index() -> io::Error {
+    invalid_data("truncated pak index".to_owned())
+}
@@ -0,0 +1,541 @@
+mod format;
+mod writer;
+
+use std::collections::HashMap;
+use std::io::{Read, Seek, SeekFrom};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub use format::PakCompression;
+pub use writer::PakWriter;
+
+use format::{HEADER_SIZE, PakEntry, PakHeader};
+
+use crate::error::VfsError;
+use crate::metadata::{self, VfsDirEntry, VfsMetadata};
+use crate::path;
+use crate::provider::{VfsFuture, VfsProvider};
+
+/// Byte source a pack is read from.
+pub trait PackSource: Read + Seek + Send {}
+
+impl<T: Read + Seek + Send> PackSource for T {}
+
+/// Read-only provider serving files from a zip archive or a `.pak`.
+///
+/// The archive index is parsed once on open; reads seek into the shared
+/// source under a lock. `.pak` entries are verified against their SHA-256
+/// content hash on every full read, and uncompressed entries support true
+/// range reads.
+///
+/// # Example
+///
+/// ```ignore
+/// let vfs = Vfs::new();
+/// vfs.mount("assets", PackProvider::open("assets.pak")?);
+///
+/// // Same paths as with the FileSystemProvider the pack was built from
+/// let bytes = io.run(vfs.read("assets/textures/brick.png")).await;
+/// ```
+#[derive(Clone)]
+pub struct PackProvider {
+    inner: Arc<PackInner>,
+}
+
+struct PackInner {
+    archive: Mutex<Archive>,
+    files: HashMap<String, PackFile>,
+    /// Children of every directory, keyed by directory path (root is `""`).
+    dirs: HashMap<String, Vec<VfsDirEntry>>,
+}
+
+enum Archive {
+    Zip(zip::ZipArchive<Box<dyn PackSource>>),
+    Pak(Box<dyn PackSource>),
+}
+
+struct PackFile {
+    size: u64,
+    modified: Option<SystemTime>,
+    location: Location,
+}
+
+enum Location {
+    Zip(usize),
+    Pak(PakEntry),
+}
+
+impl PackProvider {
+    /// Open a pack file from disk.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn open(path: impl AsRef<std::path::Path>) -> Result<Self, VfsError> {
+        let file = std::fs::File::open(path)?;
+        Self::from_reader(std::io::BufReader::new(file))
+    }
+
+    /// Open a pack held in memory, e.g. fetched over HTTP or embedded with
+    /// `include_bytes!`.
+    pub fn from_bytes(bytes: impl Into<Vec<u8>>) -> Result<Self, VfsError> {
+        Self::from_reader(std::io::Cursor::new(bytes.into()))
+    }
+
+    /// Open a pack from any seekable source.
+    ///
+    /// Returns [`VfsError::Io`] with [`InvalidData`](std::io::ErrorKind::InvalidData)
+    /// if the source is neither a zip archive nor a `.pak`.
+    pub fn from_reader(source: impl PackSource + 'static) -> Result<Self, VfsError> {
+        let mut source: Box<dyn PackSource> = Box::new(source);
+        let mut magic = [0u8; 4];
+        source.seek(SeekFrom::Start(0))?;
+        let magic_len = read_up_to(&mut source, &mut magic)?;
+        source.seek(SeekFrom::Start(0))?;
+
+        let (archive, files) = if magic_len == 4 && magic == format::MAGIC {
+            open_pak(source)?
+        } else if magic_len == 4 && magic[..2] == *b"PK" {
+            open_zip(source)?
+        } else {
+            return Err(VfsError::Io(format::invalid_data(
+                "unrecognized pack format".to_owned(),
+            )));
+        };
+
+        let dirs = metadata::build_dirs(
+            files
+                .iter()
+                .map(|(path, file)| (path.as_str(), VfsMetadata::file(file.size, file.modified))),
+        );
+        Ok(Self {
+            inner: Arc::new(PackInner {
+                archive: Mutex::new(archive),
+                files,
+                dirs,
+            }),
+        })
+    }
+
+    /// Number of files in the pack.
+    pub fn file_count(&self) -> usize {
+        self.inner.files.len()
+    }
+
+    /// SHA-256 of a file's content, if the pack records one.
+    ///
+    /// Only `.pak` files store content hashes; zip entries return `None`.
+    pub fn content_hash(&self, path: &str) -> Option<[u8; 32]> {
+        match &self.inner.files.get(path)?.location {
+            Location::Pak(entry) => Some(entry.hash),
+            Location::Zip(_) => None,
+        }
+    }
+}
+
+impl PackInner {
+    fn file(&self, path: &str) -> Result<&PackFile, VfsError> {
+        self.files
+            .get(path)
+            .ok_or_else(|| VfsError::NotFound(path.to_owned()))
+    }
+
+    fn read(&self, path: &str) -> Result<Vec<u8>, VfsError> {
+        let file = self.file(path)?;
+        let mut archive = self.archive.lock().unwrap();
+        match (&mut *archive, &file.location) {
+            (Archive::Zip(zip), Location::Zip(index)) => {
+                let mut entry = zip.by_index(*index).map_err(std::io::Error::from)?;
+                let mut data = Vec::new();
+                entry.read_to_end(&mut data)?;
+                Ok(data)
+            }
+            (Archive::Pak(source), Location::Pak(entry)) => {
+                let stored = read_at(source, entry.offset, entry.stored_size)?;
+                drop(archive);
+                let data = entry.compression.decompress(stored, entry.size)?;
+                if format::content_hash(&data) != entry.hash {
+                    return Err(VfsError::Io(format::invalid_data(format!(
+                        "content hash mismatch: {path}"
+                    ))));
+                }
+                Ok(data)
+            }
+            _ => unreachable!("pack entry location doesn't match the archive kind"),
+        }
+    }
+
+    fn read_range(&self, path: &str, offset: u64, len: u64) -> Result<Vec<u8>, VfsError> {
+        let file = self.file(path)?;
+        if let Location::Pak(entry) = &file.location
+            && entry.compression == PakCompression::None
+        {
+            let range = metadata::clamp_range(entry.size as usize, offset, len);
+            let mut archive = self.archive.lock().unwrap();
+            let Archive::Pak(source) = &mut *archive else {
+                unreachable!("pack entry location doesn't match the archive kind");
+            };
+            return Ok(read_at(
+                source,
+                entry.offset + range.start as u64,
+                range.len() as u64,
+            )?);
+        }
+        let mut data = self.read(path)?;
+        let range = metadata::clamp_range(data.len(), offset, len);
+        data.truncate(range.end);
+        data.drain(..range.start);
+        Ok(data)
+    }
+
+    fn metadata(&self, path: &str) -> Result<VfsMetadata, VfsError> {
+        if let Some(file) = self.files.get(path) {
+            return Ok(VfsMetadata::file(file.size, file.modified));
+        }
+        if path.is_empty() {
+            return Ok(VfsMetadata::directory(None));
+        }
+        let (parent, name) = path.rsplit_once('/').unwrap_or(("", path));
+        self.dirs
+            .get(parent)
+            .and_then(|children| children.iter().find(|e| e.name == name))
+            .map(|entry| entry.metadata)
+            .ok_or_else(|| VfsError::NotFound(path.to_owned()))
+    }
+}
+
+impl VfsProvider for PackProvider {
+    fn read(&self, path: &str) -> VfsFuture<Vec<u8>> {
+        let inner = Arc::clone(&self.inner);
+        let path = path.to_owned();
+        Box::pin(async move { inner.read(&path) })
+    }
+
+    fn metadata(&self, path: &str) -> VfsFuture<VfsMetadata> {
+        let result = self.inner.metadata(path);
+        Box::pin(async move { result })
+    }
+
+    fn list_dir_detailed(&self, path: &str) -> VfsFuture<Vec<VfsDirEntry>> {
+        let entries = self.inner.dirs.get(path).cloned().unwrap_or_default();
+        Box::pin(async move { Ok(entries) })
+    }
+
+    fn read_range(&self, path: &str, offset: u64, len: u64) -> VfsFuture<Vec<u8>> {
+        let inner = Arc::clone(&self.inner);
+        let path = path.to_owned();
+        Box::pin(async move { inner.read_range(&path, offset, len) })
+    }
+}
+
+fn open_pak(
+    mut source: Box<dyn PackSource>,
+) -> Result<(Archive, HashMap<String, PackFile>), VfsError> {
+    let mut header = [0u8; HEADER_SIZE as usize];
+    source.read_exact(&mut header)?;
+    let header = PakHeader::decode(&header)?;
+    let index = read_at(&mut source, header.index_offset, header.index_size)?;
+    if index.len() as u64 != header.index_size {
+        return Err(VfsError::Io(format::invalid_data(
+            "truncated pak index".to_owned(),
+        )));
+    }
+
+    let files = format::decode_index(&index, header.entry_count)?
+        .into_iter()
+        .map(|(path, entry)| {
+            let file = PackFile {
+                size: entry.size,
+                modified: entry.modified,
+                location: Location::Pak(entry),
+            };
+            (path, file)
+        })
+        .collect();
+    Ok((Archive::Pak(source), files))
+}
+
+fn open_zip(source: Box<dyn PackSource>) -> Result<(Archive, HashMap<String, PackFile>), VfsError> {
+    let mut zip = zip::ZipArchive::new(source).map_err(std::io::Error::from)?;
+    let mut files = HashMap::new();
+    for index in 0..zip.len() {
+        let entry = zip.by_index_raw(index).map_err(std::io::Error::from)?;
+        if entry.is_dir() {
+            continue;
+        }
+        // Skip names that would escape the root or can't be addressed.
+        let Ok(path) = path::normalize(entry.name()) else {
+            log::warn!("Skipping zip entry with invalid path: {:?}", entry.name());
+            continue;
+        };
+        let file = PackFile {
+            size: entry.size(),
+            modified: entry.last_modified().and_then(zip_time),
+            location: Location::Zip(index),
+        };
+        drop(entry);
+        files.insert(path, file);
+    }
+    Ok((Archive::Zip(zip), files))
+}
+
+fn read_at(source: &mut Box<dyn PackSource>, offset: u64, len: u64) -> std::io::Result<Vec<u8>> {
+    source.seek(SeekFrom::Start(offset))?;
+    let mut data = Vec::new();
+    source.take(len).read_to_end(&mut data)?;
+    Ok(data)
+}
+
+fn read_up_to(source: &mut Box<dyn PackSource>, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match source.read(&mut buf[filled..])? {
+            0 => break,
+            n => filled += n,
+        }
+    }
+    Ok(filled)
+}
+
+/// Convert a zip (MS-DOS) timestamp to a `SystemTime`, treating it as UTC.
+fn zip_time(time: zip::DateTime) -> Option<SystemTime> {
+    let days =
+        metadata::days_from_civil(time.year() as i64, time.month() as i64, time.day() as i64);
+    let secs =
+        days * 86400 + time.hour() as i64 * 3600 + time.minute() as i64 * 60 + time.second() as i64;
+    u64::try_from(secs)
+        .ok()
+        .map(|secs| UNIX_EPOCH + Duration::from_secs(secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MemoryProvider, Vfs};
+    use std::io::{Cursor, Write};
+    use std::pin::Pin;
+    use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+    fn poll_ready<T>(mut fut: VfsFuture<T>) -> Result<T, VfsError> {
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        match Pin::new(&mut fut).poll(&mut cx) {
+            Poll::Ready(val) => val,
+            Poll::Pending => panic!("expected future to be immediately ready"),
+        }
+    }
+
+    fn noop_waker() -> Waker {
+        fn noop(_: *const ()) {}
+        fn clone(p: *const ()) -> RawWaker {
+            RawWaker::new(p, &VTABLE)
+        }
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) }
+    }
+
+    fn build_pak(compression: PakCompression) -> Vec<u8> {
+        let mut writer = PakWriter::new(Cursor::new(Vec::new()))
+            .unwrap()
+            .with_compression(compression);
+        writer
+            .add_file("config.toml", b"name = \"test\"", None)
+            .unwrap();
+        writer
+            .add_file("textures/brick.png", &[7u8; 4096], Some(UNIX_EPOCH))
+            .unwrap();
+        writer
+            .add_file("textures/ui/icon.png", b"icon", None)
+            .unwrap();
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn pak_round_trip() {
+        for compression in [
+            PakCompression::None,
+            PakCompression::Lz4,
+            PakCompression::Zstd,
+        ] {
+            let pack = PackProvider::from_bytes(build_pak(compression)).unwrap();
+            assert_eq!(pack.file_count(), 3);
+            assert_eq!(
+                poll_ready(pack.read("textures/brick.png")).unwrap(),
+                vec![7u8; 4096]
+            );
+            assert_eq!(
+                poll_ready(pack.read("config.toml")).unwrap(),
+                b"name = \"test\""
+            );
+            assert!(matches!(
+                poll_ready(pack.read("missing.txt")),
+                Err(VfsError::NotFound(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn pak_compresses_only_when_smaller() {
+        let compressed = build_pak(PakCompression::Zstd);
+        let stored = build_pak(PakCompression::None);
+        assert!(compressed.len() < stored.len());
+    }
+
+    #[test]
+    fn pak_listing_and_metadata() {
+        let pack = PackProvider::from_bytes(build_pak(PakCompression::Lz4)).unwrap();
+
+        let root = poll_ready(pack.list_dir_detailed("")).unwrap();
+        let names: Vec<_> = root.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["config.toml", "textures"]);
+        assert!(root[1].is_dir());
+        assert_eq!(root[1].metadata.modified, Some(UNIX_EPOCH));
+
+        let textures = poll_ready(pack.list_dir("textures")).unwrap();
+        assert_eq!(textures, vec!["brick.png", "ui"]);
+
+        let brick = poll_ready(pack.metadata("textures/brick.png")).unwrap();
+        assert!(brick.is_file());
+        assert_eq!(brick.size, 4096);
+        assert!(poll_ready(pack.metadata("textures/ui")).unwrap().is_dir());
+        assert!(poll_ready(pack.metadata("nope")).is_err());
+        assert!(poll_ready(pack.exists("textures/ui")).unwrap());
+        assert!(poll_ready(pack.exists("config.toml")).unwrap());
+        assert!(!poll_ready(pack.exists("nope")).unwrap());
+        assert!(pack.is_read_only());
+    }
+
+    #[test]
+    fn pak_range_reads() {
+        for compression in [PakCompression::None, PakCompression::Zstd] {
+            let pack = PackProvider::from_bytes(build_pak(compression)).unwrap();
+            assert_eq!(
+                poll_ready(pack.read_range("config.toml", 7, 6)).unwrap(),
+                b"\"test\""
+            );
+            assert_eq!(
+                poll_ready(pack.read_range("textures/ui/icon.png", 2, 100)).unwrap(),
+                b"on"
+            );
+        }
+    }
+
+    #[test]
+    fn pak_detects_corruption() {
+        let mut bytes = build_pak(PakCompression::None);
+        let hash = PackProvider::from_bytes(bytes.clone())
+            .unwrap()
+            .content_hash("config.toml")
+            .unwrap();
+        assert_eq!(hash, format::content_hash(b"name = \"test\""));
+
+        // The first entry starts right after the header.
+        bytes[HEADER_SIZE as usize] ^= 0xff;
+        let pack = PackProvider::from_bytes(bytes).unwrap();
+        assert!(matches!(
+            poll_ready(pack.read("config.toml")),
+            Err(VfsError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn pak_rejects_implausible_sizes() {
+        let lz4 = PakCompression::Lz4.compress(b"tiny").unwrap();
+        assert!(PakCompression::Lz4.decompress(lz4, u64::MAX).is_err());
+        let zstd = PakCompression::Zstd.compress(&[7u8; 4096]).unwrap();
+        assert!(PakCompression::Zstd.decompress(zstd, 16).is_err());
+        assert!(format::decode_index(&[], u32::MAX).is_err());
+
+        let entry = format::PakEntry {
+            offset: 0,
+            stored_size: 0,
+            size: 0,
+            compression: PakCompression::None,
+            modified: None,
+            hash: [0; 32],
+        };
+        let mut index = Vec::new();
+        format::encode_entry(&mut index, "a", &entry);
+        index[28..36].copy_from_slice(&(u64::MAX - 1).to_le_bytes());
+        let decoded = format::decode_index(&index, 1).unwrap();
+        assert_eq!(decoded[0].1.modified, None);
+    }
+
+    #[test]
+    fn pak_rejects_duplicates() {
+        let mut writer = PakWriter::new(Cursor::new(Vec::new())).unwrap();
+        writer.add_file("a.txt", b"1", None).unwrap();
+        assert!(matches!(
+            writer.add_file("./a.txt", b"2", None),
+            Err(VfsError::InvalidPath(_))
+        ));
+    }
+
+    #[test]
+    fn pak_from_vfs_dir() {
+        let mem = MemoryProvider::new();
+        mem.insert("models/car.glb", b"glb".to_vec());
+        mem.insert("shaders/pbr.slang", b"slang".to_vec());
+        let vfs = Vfs::new();
+        vfs.mount("assets", mem);
+
+        let writer = PakWriter::new(Cursor::new(Vec::new())).unwrap();
+        let writer = poll_ready(writer.add_vfs_dir(&vfs, "assets")).unwrap();
+        assert_eq!(writer.len(), 2);
+        let bytes = writer.finish().unwrap().into_inner();
+
+        let packed = Vfs::new();
+        packed.mount("assets", PackProvider::from_bytes(bytes).unwrap());
+        assert_eq!(
+            poll_ready(packed.read("assets/models/car.glb")).unwrap(),
+            b"glb"
+        );
+        assert_eq!(
+            poll_ready(packed.read("assets/shaders/pbr.slang")).unwrap(),
+            b"slang"
+        );
+    }
+
+    #[test]
+    fn zip_archive() {
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let deflated = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated)
+            .last_modified_time(zip::DateTime::from_date_and_time(2024, 3, 1, 12, 0, 0).unwrap());
+        zip.add_directory("empty/", deflated).unwrap();
+        zip.start_file("data/level.ron", deflated).unwrap();
+        zip.write_all(b"(entities: [])").unwrap();
+        zip.start_file(
+            "readme.txt",
+            deflated.compression_method(zip::CompressionMethod::Stored),
+        )
+        .unwrap();
+        zip.write_all(b"hello").unwrap();
+        let bytes = zip.finish().unwrap().into_inner();
+
+        let pack = PackProvider::from_bytes(bytes).unwrap();
+        assert_eq!(pack.file_count(), 2);
+        assert_eq!(
+            poll_ready(pack.read("data/level.ron")).unwrap(),
+            b"(entities: [])"
+        );
+        assert_eq!(
+            poll_ready(pack.read_range("readme.txt", 1, 3)).unwrap(),
+            b"ell"
+        );
+        assert!(pack.content_hash("readme.txt").is_none());
+
+        let entries = poll_ready(pack.list_dir_detailed("")).unwrap();
+        let names: Vec<_> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["data", "readme.txt"]);
+
+        // 2024-03-01 12:00:00 UTC
+        let level = poll_ready(pack.metadata("data/level.ron")).unwrap();
+        assert_eq!(
+            level.modified,
+            Some(UNIX_EPOCH + Duration::from_secs(1_709_294_400))
+        );
+    }
+
+    #[test]
+    fn unknown_format_rejected() {
+        assert!(PackProvider::from_bytes(b"not a pack".to_vec()).is_err());
+    }
+}
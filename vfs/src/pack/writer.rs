@@ -0,0 +1,180 @@
+use std::collections::HashSet;
+use std::io::{Seek, SeekFrom, Write};
+use std::time::SystemTime;
+
+use super::format::{self, HEADER_SIZE, PakCompression, PakEntry, PakHeader};
+use crate::error::VfsError;
+use crate::path;
+use crate::provider::VfsFuture;
+use crate::vfs::Vfs;
+
+/// Streaming writer for the engine's `.pak` format.
+///
+/// Entries are compressed and written as they are added; the index is
+/// appended by [`finish()`](Self::finish). Entries that don't shrink under
+/// compression are stored uncompressed, so already-compressed assets (PNG,
+/// Ogg, KTX2 with supercompression) cost nothing to decode.
+///
+/// # Example
+///
+/// ```ignore
+/// let file = std::io::BufWriter::new(std::fs::File::create("assets.pak")?);
+/// let writer = PakWriter::new(file)?.with_compression(PakCompression::Lz4);
+/// let writer = io.run(writer.add_vfs_dir(&vfs, "assets")).await?;
+/// writer.finish()?;
+/// ```
+pub struct PakWriter<W: Write + Seek> {
+    writer: W,
+    compression: PakCompression,
+    entries: Vec<(String, PakEntry)>,
+    paths: HashSet<String>,
+    position: u64,
+}
+
+impl<W: Write + Seek> PakWriter<W> {
+    /// Start a pack at the current position of `writer`.
+    pub fn new(mut writer: W) -> Result<Self, VfsError> {
+        let start = writer.stream_position()?;
+        if start != 0 {
+            return Err(VfsError::Io(format::invalid_data(
+                "pak must start at the beginning of the writer".to_owned(),
+            )));
+        }
+        // Placeholder, patched by `finish()`.
+        writer.write_all(&[0; HEADER_SIZE as usize])?;
+        Ok(Self {
+            writer,
+            compression: PakCompression::default(),
+            entries: Vec::new(),
+            paths: HashSet::new(),
+            position: HEADER_SIZE,
+        })
+    }
+
+    /// Set the compression used by [`add_file()`](Self::add_file).
+    ///
+    /// Defaults to [`PakCompression::Zstd`].
+    pub fn with_compression(mut self, compression: PakCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Number of entries added so far.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no entries have been added.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Add a file using the writer's compression.
+    ///
+    /// The path is normalized like VFS paths. Returns
+    /// [`VfsError::InvalidPath`] for invalid or duplicate paths.
+    pub fn add_file(
+        &mut self,
+        path: &str,
+        data: &[u8],
+        modified: Option<SystemTime>,
+    ) -> Result<(), VfsError> {
+        self.add_file_with(path, data, modified, self.compression)
+    }
+
+    /// Add a file with an explicit compression.
+    pub fn add_file_with(
+        &mut self,
+        path: &str,
+        data: &[u8],
+        modified: Option<SystemTime>,
+        compression: PakCompression,
+    ) -> Result<(), VfsError> {
+        let path = path::normalize(path)?;
+        if path.len() > u16::MAX as usize {
+            return Err(VfsError::InvalidPath(path));
+        }
+        if !self.paths.insert(path.clone()) {
+            return Err(VfsError::InvalidPath(format!(
+                "duplicate pak entry: {path}"
+            )));
+        }
+
+        let (stored, compression) = match compression.compress(data) {
+            Some(compressed) if compressed.len() < data.len() => (compressed, compression),
+            _ => (data.to_vec(), PakCompression::None),
+        };
+        self.writer.write_all(&stored)?;
+
+        self.entries.push((
+            path,
+            PakEntry {
+                offset: self.position,
+                stored_size: stored.len() as u64,
+                size: data.len() as u64,
+                compression,
+                modified,
+                hash: format::content_hash(data),
+            },
+        ));
+        self.position += stored.len() as u64;
+        Ok(())
+    }
+
+    /// Add every file under `dir` in `vfs`, recursively.
+    ///
+    /// Entry paths are relative to `dir`, so a pack built from
+    /// `"assets"` and mounted as `"assets"` serves the same paths as the
+    /// original mount. Modification times are preserved.
+    pub fn add_vfs_dir(mut self, vfs: &Vfs, dir: &str) -> VfsFuture<Self>
+    where
+        W: Send + 'static,
+    {
+        let vfs = vfs.clone();
+        let root = dir.trim_matches('/').to_owned();
+        Box::pin(async move {
+            let mut pending = vec![String::new()];
+            while let Some(relative_dir) = pending.pop() {
+                let full_dir = join(&root, &relative_dir);
+                for entry in vfs.list_dir_detailed(&full_dir).await? {
+                    let relative = join(&relative_dir, &entry.name);
+                    if entry.is_dir() {
+                        pending.push(relative);
+                    } else {
+                        let data = vfs.read(&join(&root, &relative)).await?;
+                        self.add_file(&relative, &data, entry.metadata.modified)?;
+                    }
+                }
+            }
+            Ok(self)
+        })
+    }
+
+    /// Write the index and header, returning the underlying writer.
+    pub fn finish(mut self) -> Result<W, VfsError> {
+        let mut index = Vec::new();
+        for (path, entry) in &self.entries {
+            format::encode_entry(&mut index, path, entry);
+        }
+        self.writer.write_all(&index)?;
+
+        let header = PakHeader {
+            index_offset: self.position,
+            index_size: index.len() as u64,
+            entry_count: self.entries.len() as u32,
+        };
+        self.writer.seek(SeekFrom::Start(0))?;
+        self.writer.write_all(&header.encode())?;
+        self.writer.seek(SeekFrom::End(0))?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+fn join(dir: &str, name: &str) -> String {
+    if dir.is_empty() {
+        name.to_owned()
+    } else {
+        format!("{dir}/{name}")
+    }
+}
@@ -2,6 +2,8 @@ use std::future::Future;
 use std::pin::Pin;
 
 use crate::VfsError;
+use crate::metadata::{VfsDirEntry, VfsMetadata, clamp_range};
+use crate::watch::VfsWatcher;
 
 /// A boxed, `Send` future returning a `Result`.
 ///
@@ -17,8 +19,11 @@ pub type VfsFuture<T> = Pin<Box<dyn Future<Output = Result<T, VfsError>> + Send>
 ///
 /// # Read vs Write
 ///
-/// All providers must implement read operations (`read`, `exists`, `list_dir`).
-/// Write operations (`write`, `delete`, `create_dir`) have default implementations
+/// All providers must implement read operations (`read`, `metadata`,
+/// `list_dir_detailed`). `exists`, `list_dir` and `read_range` have default
+/// implementations built on those; providers override them when they can
+/// answer more cheaply (e.g. `read_range` seeking instead of reading the
+/// whole file). Write operations (`write`, `delete`, `create_dir`) have default implementations
 /// that return [`VfsError::ReadOnly`]. Providers that support writes (e.g.
 /// filesystem, memory) override these methods and return `false` from
 /// [`is_read_only()`](VfsProvider::is_read_only).
@@ -28,20 +33,66 @@ pub type VfsFuture<T> = Pin<Box<dyn Future<Output = Result<T, VfsError>> + Send>
 /// Paths passed to provider methods are already normalized by the [`Vfs`](crate::Vfs)
 /// router: forward slashes, no leading/trailing slashes, no `..` or `.` segments.
 /// The path is relative to the provider's root (the source prefix has been stripped).
+///
+/// # Change Notifications
+///
+/// Providers that can observe external changes (e.g. files edited on disk)
+/// override [`watch()`](VfsProvider::watch). Reported paths follow the same
+/// contract as above: relative to the provider's root.
 pub trait VfsProvider: Send + Sync + 'static {
     // --- Read operations (required) ---
 
     /// Read the entire contents of a file at the given path.
     fn read(&self, path: &str) -> VfsFuture<Vec<u8>>;
 
-    /// Check whether a file exists at the given path.
-    fn exists(&self, path: &str) -> VfsFuture<bool>;
+    /// Get the kind, size and modification time of a file or directory.
+    ///
+    /// Returns [`VfsError::NotFound`] if nothing exists at the path. The
+    /// empty path is the provider's root directory.
+    fn metadata(&self, path: &str) -> VfsFuture<VfsMetadata>;
+
+    /// List the immediate children of a directory with their metadata.
+    ///
+    /// Entries are sorted by name. Returns an empty vec for non-existent
+    /// directories.
+    fn list_dir_detailed(&self, path: &str) -> VfsFuture<Vec<VfsDirEntry>>;
+
+    /// Check whether a file or directory exists at the given path.
+    fn exists(&self, path: &str) -> VfsFuture<bool> {
+        let metadata = self.metadata(path);
+        Box::pin(async move {
+            match metadata.await {
+                Ok(_) => Ok(true),
+                Err(VfsError::NotFound(_)) => Ok(false),
+                Err(e) => Err(e),
+            }
+        })
+    }
 
     /// List the immediate children of a directory.
     ///
     /// Returns file and directory names (not full paths).
     /// Returns an empty vec for non-existent directories.
-    fn list_dir(&self, path: &str) -> VfsFuture<Vec<String>>;
+    fn list_dir(&self, path: &str) -> VfsFuture<Vec<String>> {
+        let entries = self.list_dir_detailed(path);
+        Box::pin(async move { Ok(entries.await?.into_iter().map(|entry| entry.name).collect()) })
+    }
+
+    /// Read up to `len` bytes of a file, starting at byte `offset`.
+    ///
+    /// The result is shorter than `len` when the range extends past the end
+    /// of the file, and empty when `offset` is at or past the end. The
+    /// default implementation reads the whole file.
+    fn read_range(&self, path: &str, offset: u64, len: u64) -> VfsFuture<Vec<u8>> {
+        let data = self.read(path);
+        Box::pin(async move {
+            let mut data = data.await?;
+            let range = clamp_range(data.len(), offset, len);
+            data.truncate(range.end);
+            data.drain(..range.start);
+            Ok(data)
+        })
+    }
 
     // --- Write operations (optional, default returns ReadOnly) ---
 
@@ -67,4 +118,15 @@ pub trait VfsProvider: Send + Sync + 'static {
     fn create_dir(&self, _path: &str) -> VfsFuture<()> {
         Box::pin(async { Err(VfsError::ReadOnly) })
     }
+
+    // --- Change notifications (optional) ---
+
+    /// Start watching the provider for changes.
+    ///
+    /// Returns `None` by default, meaning the provider cannot report changes.
+    /// Each call creates an independent watcher; watching stops when it is
+    /// dropped.
+    fn watch(&self) -> Option<VfsWatcher> {
+        None
+    }
 }
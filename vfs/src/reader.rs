@@ -0,0 +1,111 @@
+use crate::error::VfsError;
+use crate::provider::VfsFuture;
+use crate::vfs::Vfs;
+
+/// Chunked streaming reader over a single file.
+///
+/// Created by [`Vfs::open_reader()`]. Each call to
+/// [`next_chunk()`](Self::next_chunk) issues one
+/// [`read_range`](crate::VfsProvider::read_range), so large files such as
+/// packs or audio streams never have to be held in memory at once.
+///
+/// The returned futures own everything they need, so they can be handed to
+/// an async runtime like any other VFS operation.
+///
+/// ```ignore
+/// let mut reader = io.run(vfs.open_reader("audio/music.ogg")).await?;
+/// while let Some(chunk) = io.run(reader.next_chunk()).await? {
+///     decoder.feed(&chunk);
+/// }
+/// ```
+pub struct VfsReader {
+    vfs: Vfs,
+    path: String,
+    len: u64,
+    position: u64,
+    chunk_size: usize,
+}
+
+impl VfsReader {
+    /// Default number of bytes returned per chunk (64 KiB).
+    pub const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+    pub(crate) fn new(vfs: Vfs, path: String, len: u64) -> Self {
+        Self {
+            vfs,
+            path,
+            len,
+            position: 0,
+            chunk_size: Self::DEFAULT_CHUNK_SIZE,
+        }
+    }
+
+    /// Set the number of bytes requested per chunk.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "chunk size must be non-zero");
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// Path the reader was opened with.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// File size in bytes at the time the reader was opened.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns `true` if the file is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Offset of the next chunk.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Number of bytes left to read.
+    pub fn remaining(&self) -> u64 {
+        self.len.saturating_sub(self.position)
+    }
+
+    /// Move the read position. Positions past the end are clamped.
+    pub fn seek(&mut self, position: u64) {
+        self.position = position.min(self.len);
+    }
+
+    /// Read the next chunk, or `None` once the end of the file is reached.
+    ///
+    /// The position advances when the future is created. If the read fails,
+    /// [`seek()`](Self::seek) back to retry the chunk.
+    pub fn next_chunk(&mut self) -> VfsFuture<Option<Vec<u8>>> {
+        let remaining = self.remaining();
+        if remaining == 0 {
+            return Box::pin(async { Ok(None) });
+        }
+        let offset = self.position;
+        let len = remaining.min(self.chunk_size as u64);
+        self.position += len;
+
+        let read = self.vfs.read_range(&self.path, offset, len);
+        let path = self.path.clone();
+        Box::pin(async move {
+            let data = read.await?;
+            if data.is_empty() {
+                // The file shrank since it was opened.
+                return Err(VfsError::Io(std::io::Error::new(
+                    std::io::ErrorKind::UnexpectedEof,
+                    format!("{path} is shorter than when it was opened"),
+                )));
+            }
+            Ok(Some(data))
+        })
+    }
+}
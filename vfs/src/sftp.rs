@@ -1,12 +1,20 @@
+use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc;
 use std::thread;
+use std::time::Duration;
 
-use tokio::io::AsyncWriteExt;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
 
 use crate::error::VfsError;
+use crate::metadata::{VfsDirEntry, VfsMetadata};
 use crate::provider::{VfsFuture, VfsProvider};
+use crate::watch::{FileStamp, VfsWatcher, diff_snapshots};
+
+/// How often a watcher re-lists the remote tree to detect changes.
+const WATCH_POLL_INTERVAL: Duration = Duration::from_secs(2);
 
 /// Configuration for connecting to an SFTP server.
 pub struct SftpConfig {
@@ -26,6 +34,12 @@ pub struct SftpConfig {
 /// via [`std::sync::mpsc`] and results arrive through per-operation
 /// [`tokio::sync::oneshot`] channels.
 ///
+/// SFTP has no change notifications, so [`watch()`](VfsProvider::watch)
+/// spawns a thread that re-lists the remote tree every couple of seconds
+/// and reports the difference in file sizes and modification times. The
+/// tree is listed one directory at a time, so regular I/O is not held up
+/// behind a full walk of a large remote tree.
+///
 /// # Example `project.toml`
 ///
 /// ```toml
@@ -56,6 +70,20 @@ enum SftpCommand {
         path: String,
         reply: tokio::sync::oneshot::Sender<Result<Vec<String>, VfsError>>,
     },
+    Metadata {
+        path: String,
+        reply: tokio::sync::oneshot::Sender<Result<VfsMetadata, VfsError>>,
+    },
+    ListDirDetailed {
+        path: String,
+        reply: tokio::sync::oneshot::Sender<Result<Vec<VfsDirEntry>, VfsError>>,
+    },
+    ReadRange {
+        path: String,
+        offset: u64,
+        len: u64,
+        reply: tokio::sync::oneshot::Sender<Result<Vec<u8>, VfsError>>,
+    },
     Write {
         path: String,
         data: Vec<u8>,
@@ -76,6 +104,18 @@ fn sftp_err(msg: impl std::fmt::Display) -> VfsError {
     VfsError::Io(std::io::Error::other(msg.to_string()))
 }
 
+/// Map an SFTP error for `path`, reporting missing files as `NotFound`.
+fn sftp_path_err(path: &str, err: russh_sftp::client::error::Error) -> VfsError {
+    match err {
+        russh_sftp::client::error::Error::Status(status)
+            if status.status_code == russh_sftp::protocol::StatusCode::NoSuchFile =>
+        {
+            VfsError::NotFound(path.to_owned())
+        }
+        err => sftp_err(err),
+    }
+}
+
 /// Expand leading `~` to the user's home directory.
 /// Checks `$HOME` (Unix) then `%USERPROFILE%` (Windows).
 fn expand_tilde(path: &str) -> String {
@@ -163,6 +203,35 @@ impl VfsProvider for SftpProvider {
         Box::pin(async move { rx.await.map_err(|_| sftp_err("SFTP connection closed"))? })
     }
 
+    fn metadata(&self, path: &str) -> VfsFuture<VfsMetadata> {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let _ = self.sender.send(SftpCommand::Metadata {
+            path: path.to_owned(),
+            reply: tx,
+        });
+        Box::pin(async move { rx.await.map_err(|_| sftp_err("SFTP connection closed"))? })
+    }
+
+    fn list_dir_detailed(&self, path: &str) -> VfsFuture<Vec<VfsDirEntry>> {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let _ = self.sender.send(SftpCommand::ListDirDetailed {
+            path: path.to_owned(),
+            reply: tx,
+        });
+        Box::pin(async move { rx.await.map_err(|_| sftp_err("SFTP connection closed"))? })
+    }
+
+    fn read_range(&self, path: &str, offset: u64, len: u64) -> VfsFuture<Vec<u8>> {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let _ = self.sender.send(SftpCommand::ReadRange {
+            path: path.to_owned(),
+            offset,
+            len,
+            reply: tx,
+        });
+        Box::pin(async move { rx.await.map_err(|_| sftp_err("SFTP connection closed"))? })
+    }
+
     fn is_read_only(&self) -> bool {
         false
     }
@@ -194,6 +263,103 @@ impl VfsProvider for SftpProvider {
         });
         Box::pin(async move { rx.await.map_err(|_| sftp_err("SFTP connection closed"))? })
     }
+
+    fn watch(&self) -> Option<VfsWatcher> {
+        let (change_tx, change_rx) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let commands = self.sender.clone();
+        let thread_stop = stop.clone();
+
+        thread::Builder::new()
+            .name("sftp-watcher".into())
+            .spawn(move || {
+                let mut previous: Option<HashMap<String, FileStamp>> = None;
+                while !thread_stop.load(Ordering::Relaxed) {
+                    let snapshot = match snapshot_tree(&commands) {
+                        Ok(snapshot) => snapshot,
+                        Err(Some(e)) => {
+                            log::warn!("SFTP watch poll failed: {e}");
+                            thread::sleep(WATCH_POLL_INTERVAL);
+                            continue;
+                        }
+                        // Worker shut down
+                        Err(None) => break,
+                    };
+                    if let Some(previous) = &previous {
+                        for change in diff_snapshots(previous, &snapshot) {
+                            if change_tx.send(change).is_err() {
+                                return;
+                            }
+                        }
+                    }
+                    previous = Some(snapshot);
+                    thread::sleep(WATCH_POLL_INTERVAL);
+                }
+            })
+            .map_err(|e| log::warn!("Failed to spawn SFTP watcher: {e}"))
+            .ok()?;
+
+        Some(VfsWatcher::new(change_rx, StopOnDrop(stop)))
+    }
+}
+
+/// Recursively collect the size and mtime of every file on the remote.
+///
+/// Lists one directory per command, so reads and writes queued on the
+/// worker run between directories instead of waiting for the whole walk.
+/// Keys are paths relative to the root, matching the provider's path
+/// contract. Returns `Err(None)` once the worker has shut down.
+fn snapshot_tree(
+    commands: &mpsc::Sender<SftpCommand>,
+) -> Result<HashMap<String, FileStamp>, Option<VfsError>> {
+    let mut files = HashMap::new();
+    let mut pending = vec![String::new()];
+
+    while let Some(dir) = pending.pop() {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let command = SftpCommand::ListDirDetailed {
+            path: dir.clone(),
+            reply: tx,
+        };
+        if commands.send(command).is_err() {
+            return Err(None);
+        }
+        let entries = rx.blocking_recv().map_err(|_| None)?.map_err(Some)?;
+        for entry in entries {
+            let path = if dir.is_empty() {
+                entry.name
+            } else {
+                format!("{dir}/{}", entry.name)
+            };
+            if entry.metadata.is_dir() {
+                pending.push(path);
+            } else {
+                let mtime = entry
+                    .metadata
+                    .modified
+                    .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map_or(0, |d| d.as_secs());
+                files.insert(
+                    path,
+                    FileStamp {
+                        size: entry.metadata.size,
+                        mtime,
+                    },
+                );
+            }
+        }
+    }
+
+    Ok(files)
+}
+
+/// Signals the polling thread of an SFTP watcher to exit when dropped.
+struct StopOnDrop(Arc<AtomicBool>);
+
+impl Drop for StopOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -313,7 +479,7 @@ async fn run_command_loop(
         match cmd {
             SftpCommand::Read { path, reply } => {
                 let full = format!("{root}/{path}");
-                let result = sftp.read(&full).await.map_err(sftp_err);
+                let result = sftp.read(&full).await.map_err(|e| sftp_path_err(&path, e));
                 let _ = reply.send(result);
             }
             SftpCommand::Exists { path, reply } => {
@@ -346,6 +512,77 @@ async fn run_command_loop(
                 .await;
                 let _ = reply.send(result);
             }
+            SftpCommand::Metadata { path, reply } => {
+                let full = if path.is_empty() {
+                    root.to_owned()
+                } else {
+                    format!("{root}/{path}")
+                };
+                let result = sftp
+                    .metadata(&full)
+                    .await
+                    .map(|metadata| convert_metadata(&metadata))
+                    .map_err(|e| sftp_path_err(&path, e));
+                let _ = reply.send(result);
+            }
+            SftpCommand::ListDirDetailed { path, reply } => {
+                let full = if path.is_empty() {
+                    root.to_owned()
+                } else {
+                    format!("{root}/{path}")
+                };
+                let result = async {
+                    let entries = match sftp.read_dir(&full).await {
+                        Ok(entries) => entries,
+                        Err(e) => {
+                            return match sftp_path_err(&path, e) {
+                                // Missing directories list as empty.
+                                VfsError::NotFound(_) => Ok(Vec::new()),
+                                e => Err(e),
+                            };
+                        }
+                    };
+                    let mut entries: Vec<VfsDirEntry> = entries
+                        .into_iter()
+                        .filter(|entry| {
+                            let name = entry.file_name();
+                            name != "." && name != ".."
+                        })
+                        .map(|entry| {
+                            VfsDirEntry::new(entry.file_name(), convert_metadata(&entry.metadata()))
+                        })
+                        .collect();
+                    entries.sort_by(|a, b| a.name.cmp(&b.name));
+                    Ok(entries)
+                }
+                .await;
+                let _ = reply.send(result);
+            }
+            SftpCommand::ReadRange {
+                path,
+                offset,
+                len,
+                reply,
+            } => {
+                let full = format!("{root}/{path}");
+                let result = async {
+                    let mut file = sftp
+                        .open(&full)
+                        .await
+                        .map_err(|e| sftp_path_err(&path, e))?;
+                    file.seek(std::io::SeekFrom::Start(offset))
+                        .await
+                        .map_err(sftp_err)?;
+                    let mut data = Vec::new();
+                    file.take(len)
+                        .read_to_end(&mut data)
+                        .await
+                        .map_err(sftp_err)?;
+                    Ok(data)
+                }
+                .await;
+                let _ = reply.send(result);
+            }
             SftpCommand::Write { path, data, reply } => {
                 let full = format!("{root}/{path}");
                 let result = async {
@@ -367,7 +604,10 @@ async fn run_command_loop(
             }
             SftpCommand::Delete { path, reply } => {
                 let full = format!("{root}/{path}");
-                let result = sftp.remove_file(&full).await.map_err(sftp_err);
+                let result = sftp
+                    .remove_file(&full)
+                    .await
+                    .map_err(|e| sftp_path_err(&path, e));
                 let _ = reply.send(result);
             }
             SftpCommand::CreateDir { path, reply } => {
@@ -382,3 +622,13 @@ async fn run_command_loop(
     let _ = sftp.close().await;
     log::info!("SFTP worker shut down");
 }
+
+/// Convert SFTP file attributes to VFS metadata.
+fn convert_metadata(metadata: &russh_sftp::client::fs::Metadata) -> VfsMetadata {
+    let modified = metadata.modified().ok();
+    if metadata.file_type().is_dir() {
+        VfsMetadata::directory(modified)
+    } else {
+        VfsMetadata::file(metadata.len(), modified)
+    }
+}
@@ -1,9 +1,14 @@
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
 
 use crate::error::VfsError;
+use crate::metadata::{VfsDirEntry, VfsMetadata};
+use crate::overlay::OverlayProvider;
 use crate::path;
 use crate::provider::{VfsFuture, VfsProvider};
+use crate::reader::VfsReader;
+use crate::watch::{VfsChange, VfsWatcher};
 
 /// Virtual file system that routes paths to mounted providers.
 ///
@@ -11,12 +16,20 @@ use crate::provider::{VfsFuture, VfsProvider};
 /// segment selects the provider. If no source name matches, the default
 /// source (if set) is tried with the full path.
 ///
-/// `Clone` is cheap (Arc internals). Thread-safe (`Send + Sync`).
+/// Several providers can be stacked under one source name with
+/// [`mount_layer()`](Self::mount_layer), e.g. a patch pack over the base
+/// game pack, or an editor scratch directory over shared assets. See
+/// [`OverlayProvider`] for how layers are merged.
+///
+/// `Clone` is cheap (Arc internals) and clones share their mounts: sources
+/// can be mounted and unmounted at any time through any clone. Operations
+/// already in flight keep using the providers they started with.
+/// Thread-safe (`Send + Sync`).
 ///
 /// # Example
 ///
 /// ```ignore
-/// let mut vfs = Vfs::new();
+/// let vfs = Vfs::new();
 /// vfs.mount("assets", FileSystemProvider::new("./assets"));
 /// vfs.mount("builtin", MemoryProvider::new());
 /// vfs.set_default("assets");
@@ -26,6 +39,9 @@ use crate::provider::{VfsFuture, VfsProvider};
 ///
 /// // With default source, also reads from assets:
 /// let bytes = io.run(vfs.read("textures/brick.png")).await;
+///
+/// // Files in the mod shadow the ones in ./assets
+/// let layer = vfs.mount_layer("assets", FileSystemProvider::new("./mods/hd"), 10);
 /// ```
 #[derive(Clone)]
 pub struct Vfs {
@@ -33,8 +49,39 @@ pub struct Vfs {
 }
 
 struct VfsInner {
-    sources: HashMap<String, Box<dyn VfsProvider>>,
-    default_source: Option<String>,
+    sources: RwLock<HashMap<String, Source>>,
+    default_source: RwLock<Option<String>>,
+    next_layer_id: AtomicU64,
+}
+
+/// Identifies a layer mounted with [`Vfs::mount_layer()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VfsLayerId(u64);
+
+/// Layers mounted under one source name.
+struct Source {
+    /// Sorted from top to bottom.
+    layers: Vec<Layer>,
+    /// The single layer, or an overlay of all of them.
+    provider: Arc<dyn VfsProvider>,
+}
+
+struct Layer {
+    id: VfsLayerId,
+    priority: i32,
+    provider: Arc<dyn VfsProvider>,
+}
+
+impl Source {
+    fn new(layers: Vec<Layer>) -> Self {
+        let provider = match layers.as_slice() {
+            [layer] => Arc::clone(&layer.provider),
+            _ => Arc::new(OverlayProvider::new(
+                layers.iter().map(|l| Arc::clone(&l.provider)).collect(),
+            )),
+        };
+        Self { layers, provider }
+    }
 }
 
 impl Vfs {
@@ -42,34 +89,94 @@ impl Vfs {
     pub fn new() -> Self {
         Self {
             inner: Arc::new(VfsInner {
-                sources: HashMap::new(),
-                default_source: None,
+                sources: RwLock::new(HashMap::new()),
+                default_source: RwLock::new(None),
+                next_layer_id: AtomicU64::new(0),
             }),
         }
     }
 
     /// Mount a provider under the given source name.
     ///
-    /// Replaces any previously mounted provider with the same name.
-    ///
-    /// # Panics
+    /// Replaces all layers previously mounted with the same name.
+    pub fn mount(&self, name: impl Into<String>, provider: impl VfsProvider) -> VfsLayerId {
+        let layer = self.new_layer(provider, 0);
+        let id = layer.id;
+        let mut sources = self.inner.sources.write().unwrap();
+        sources.insert(name.into(), Source::new(vec![layer]));
+        id
+    }
+
+    /// Stack a provider on the layers already mounted under `name`.
     ///
-    /// Panics if the `Vfs` has already been cloned. All mounting must
-    /// happen during the configuration phase before sharing the `Vfs`.
-    pub fn mount(&mut self, name: impl Into<String>, provider: impl VfsProvider) {
-        let inner = Arc::get_mut(&mut self.inner).expect("cannot mount after Vfs has been cloned");
-        inner.sources.insert(name.into(), Box::new(provider));
+    /// Layers with a higher `priority` sit above lower ones; among equal
+    /// priorities the most recently mounted layer is on top. Reads fall
+    /// through to lower layers, writes go to the top writable layer.
+    pub fn mount_layer(
+        &self,
+        name: impl Into<String>,
+        provider: impl VfsProvider,
+        priority: i32,
+    ) -> VfsLayerId {
+        let layer = self.new_layer(provider, priority);
+        let id = layer.id;
+        let mut sources = self.inner.sources.write().unwrap();
+        let name = name.into();
+        let mut layers = sources.remove(&name).map(|s| s.layers).unwrap_or_default();
+        let index = layers
+            .iter()
+            .position(|l| l.priority <= priority)
+            .unwrap_or(layers.len());
+        layers.insert(index, layer);
+        sources.insert(name, Source::new(layers));
+        id
     }
 
-    /// Set the default source name used when a path does not match any mount.
+    /// Remove a source and all of its layers.
     ///
-    /// # Panics
+    /// Returns `false` if nothing was mounted under `name`.
+    pub fn unmount(&self, name: &str) -> bool {
+        self.inner.sources.write().unwrap().remove(name).is_some()
+    }
+
+    /// Remove a single layer. The source is removed with its last layer.
     ///
-    /// Panics if the `Vfs` has already been cloned.
-    pub fn set_default(&mut self, name: impl Into<String>) {
-        let inner =
-            Arc::get_mut(&mut self.inner).expect("cannot set default after Vfs has been cloned");
-        inner.default_source = Some(name.into());
+    /// Returns `false` if the layer is no longer mounted.
+    pub fn unmount_layer(&self, id: VfsLayerId) -> bool {
+        let mut sources = self.inner.sources.write().unwrap();
+        let Some(name) = sources
+            .iter()
+            .find(|(_, source)| source.layers.iter().any(|l| l.id == id))
+            .map(|(name, _)| name.clone())
+        else {
+            return false;
+        };
+        let mut layers = sources.remove(&name).unwrap().layers;
+        layers.retain(|l| l.id != id);
+        if !layers.is_empty() {
+            sources.insert(name, Source::new(layers));
+        }
+        true
+    }
+
+    /// Names of all mounted sources, sorted.
+    pub fn sources(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.inner.sources.read().unwrap().keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Set the default source name used when a path does not match any mount.
+    pub fn set_default(&self, name: impl Into<String>) {
+        *self.inner.default_source.write().unwrap() = Some(name.into());
+    }
+
+    fn new_layer(&self, provider: impl VfsProvider, priority: i32) -> Layer {
+        Layer {
+            id: VfsLayerId(self.inner.next_layer_id.fetch_add(1, Ordering::Relaxed)),
+            priority,
+            provider: Arc::new(provider),
+        }
     }
 
     /// Read the entire contents of a file.
@@ -102,6 +209,54 @@ impl Vfs {
         provider.list_dir(&resolved_path)
     }
 
+    /// Get the kind, size and modification time of a file or directory.
+    pub fn metadata(&self, raw_path: &str) -> VfsFuture<VfsMetadata> {
+        let (provider, resolved_path) = match self.resolve(raw_path) {
+            Ok(v) => v,
+            Err(e) => return Box::pin(async move { Err(e) }),
+        };
+        provider.metadata(&resolved_path)
+    }
+
+    /// List the immediate children of a directory with their metadata.
+    pub fn list_dir_detailed(&self, raw_path: &str) -> VfsFuture<Vec<VfsDirEntry>> {
+        let (provider, resolved_path) = match self.resolve(raw_path) {
+            Ok(v) => v,
+            Err(e) => return Box::pin(async move { Err(e) }),
+        };
+        provider.list_dir_detailed(&resolved_path)
+    }
+
+    /// Read up to `len` bytes of a file starting at `offset`.
+    ///
+    /// The result is shorter than `len` if the range extends past the end
+    /// of the file.
+    pub fn read_range(&self, raw_path: &str, offset: u64, len: u64) -> VfsFuture<Vec<u8>> {
+        let (provider, resolved_path) = match self.resolve(raw_path) {
+            Ok(v) => v,
+            Err(e) => return Box::pin(async move { Err(e) }),
+        };
+        provider.read_range(&resolved_path, offset, len)
+    }
+
+    /// Open a file for chunked streaming reads.
+    ///
+    /// Only the file's metadata is fetched up front; data is read on demand
+    /// with [`VfsReader::next_chunk()`]. Returns [`VfsError::NotFound`] if
+    /// the path is a directory.
+    pub fn open_reader(&self, raw_path: &str) -> VfsFuture<VfsReader> {
+        let vfs = self.clone();
+        let path = raw_path.to_owned();
+        let metadata = self.metadata(raw_path);
+        Box::pin(async move {
+            let metadata = metadata.await?;
+            if !metadata.is_file() {
+                return Err(VfsError::NotFound(path));
+            }
+            Ok(VfsReader::new(vfs, path, metadata.size))
+        })
+    }
+
     /// Write data to a file.
     ///
     /// Returns [`VfsError::ReadOnly`] if the resolved provider does not
@@ -140,21 +295,95 @@ impl Vfs {
         Ok(provider.is_read_only())
     }
 
+    /// Watch all mounted sources for changes.
+    ///
+    /// Sources whose provider does not support watching are skipped.
+    /// Reported paths are prefixed with the source name, i.e. they have the
+    /// form returned by [`canonical_path()`](Self::canonical_path).
+    ///
+    /// The watcher follows later mounts: on each poll, sources mounted,
+    /// unmounted or restacked since the last poll get their provider watch
+    /// re-created, after the changes still pending on the old one are
+    /// drained. Changes made between the mount and that poll are not
+    /// reported.
+    pub fn watch(&self) -> VfsWatcher {
+        let vfs = self.clone();
+        let watched = Mutex::new(HashMap::new());
+        vfs.sync_watched(&mut watched.lock().unwrap(), &mut Vec::new());
+        VfsWatcher::follow(move |changes| {
+            let mut watched = watched.lock().unwrap();
+            vfs.sync_watched(&mut watched, changes);
+            for (_, watcher) in watched.values() {
+                watcher.drain_into(changes);
+            }
+        })
+    }
+
+    /// Bring the per-source watchers of [`watch()`](Self::watch) in line with
+    /// the current mounts, draining the ones being replaced into `changes`.
+    fn sync_watched(
+        &self,
+        watched: &mut HashMap<String, (Arc<dyn VfsProvider>, VfsWatcher)>,
+        changes: &mut Vec<VfsChange>,
+    ) {
+        let sources = self.inner.sources.read().unwrap();
+        watched.retain(|name, (provider, watcher)| {
+            let current = sources
+                .get(name)
+                .is_some_and(|source| Arc::ptr_eq(&source.provider, provider));
+            if !current {
+                watcher.drain_into(changes);
+            }
+            current
+        });
+        for (name, source) in sources.iter() {
+            if !watched.contains_key(name) {
+                let watcher = match source.provider.watch() {
+                    Some(watcher) => watcher.with_prefix(name),
+                    None => VfsWatcher::empty(),
+                };
+                watched.insert(name.clone(), (Arc::clone(&source.provider), watcher));
+            }
+        }
+    }
+
+    /// Normalize a path and make its source explicit.
+    ///
+    /// Paths that rely on the default source are prefixed with its name, so
+    /// `"textures/brick.png"` and `"assets/textures/brick.png"` map to the same
+    /// canonical path when `"assets"` is the default. This is the form used by
+    /// change notifications from [`watch()`](Self::watch).
+    pub fn canonical_path(&self, raw_path: &str) -> Result<String, VfsError> {
+        let normalized = path::normalize(raw_path)?;
+        let (source, _) = path::split_source(&normalized);
+        let sources = self.inner.sources.read().unwrap();
+        if sources.contains_key(source) {
+            return Ok(normalized);
+        }
+        match &*self.inner.default_source.read().unwrap() {
+            Some(default_name) if sources.contains_key(default_name) => {
+                Ok(format!("{default_name}/{normalized}"))
+            }
+            _ => Err(VfsError::NoSuchSource(source.to_owned())),
+        }
+    }
+
     /// Resolve a raw path to a provider reference and the path within that provider.
-    fn resolve(&self, raw_path: &str) -> Result<(&dyn VfsProvider, String), VfsError> {
+    fn resolve(&self, raw_path: &str) -> Result<(Arc<dyn VfsProvider>, String), VfsError> {
         let normalized = path::normalize(raw_path)?;
         let (source, rest) = path::split_source(&normalized);
+        let sources = self.inner.sources.read().unwrap();
 
         // Try matching the first segment as a source name
-        if let Some(provider) = self.inner.sources.get(source) {
-            return Ok((provider.as_ref(), rest.to_owned()));
+        if let Some(source) = sources.get(source) {
+            return Ok((Arc::clone(&source.provider), rest.to_owned()));
         }
 
         // Fall back to default source with the full path
-        if let Some(default_name) = &self.inner.default_source
-            && let Some(provider) = self.inner.sources.get(default_name)
+        if let Some(default_name) = &*self.inner.default_source.read().unwrap()
+            && let Some(source) = sources.get(default_name)
         {
-            return Ok((provider.as_ref(), normalized));
+            return Ok((Arc::clone(&source.provider), normalized));
         }
 
         Err(VfsError::NoSuchSource(source.to_owned()))
@@ -197,7 +426,7 @@ mod tests {
         let mem = MemoryProvider::new();
         mem.insert("hello.txt", b"world".to_vec());
 
-        let mut vfs = Vfs::new();
+        let vfs = Vfs::new();
         vfs.mount("data", mem);
 
         let result = poll_ready(vfs.read("data/hello.txt")).unwrap();
@@ -209,7 +438,7 @@ mod tests {
         let mem = MemoryProvider::new();
         mem.insert("hello.txt", b"world".to_vec());
 
-        let mut vfs = Vfs::new();
+        let vfs = Vfs::new();
         vfs.mount("data", mem);
         vfs.set_default("data");
 
@@ -230,7 +459,7 @@ mod tests {
         let mem = MemoryProvider::new();
         mem.insert("a/b.txt", b"ok".to_vec());
 
-        let mut vfs = Vfs::new();
+        let vfs = Vfs::new();
         vfs.mount("data", mem);
 
         let result = poll_ready(vfs.read("data//a/./b.txt")).unwrap();
@@ -239,7 +468,7 @@ mod tests {
 
     #[test]
     fn invalid_path_rejected() {
-        let mut vfs = Vfs::new();
+        let vfs = Vfs::new();
         vfs.mount("data", MemoryProvider::new());
 
         let result = poll_ready(vfs.read("data/../secret.txt"));
@@ -251,7 +480,7 @@ mod tests {
         let mem = MemoryProvider::new();
         mem.insert("file.txt", vec![]);
 
-        let mut vfs = Vfs::new();
+        let vfs = Vfs::new();
         vfs.mount("m", mem);
 
         assert!(poll_ready(vfs.exists("m/file.txt")).unwrap());
@@ -264,7 +493,7 @@ mod tests {
         mem.insert("a.txt", vec![]);
         mem.insert("sub/b.txt", vec![]);
 
-        let mut vfs = Vfs::new();
+        let vfs = Vfs::new();
         vfs.mount("m", mem);
 
         let entries = poll_ready(vfs.list_dir("m")).unwrap();
@@ -272,11 +501,53 @@ mod tests {
         assert!(entries.contains(&"sub".to_owned()));
     }
 
+    #[test]
+    fn metadata_via_vfs() {
+        let mem = MemoryProvider::new();
+        mem.insert("dir/file.txt", b"data".to_vec());
+
+        let vfs = Vfs::new();
+        vfs.mount("data", mem);
+
+        assert!(poll_ready(vfs.metadata("data/dir")).unwrap().is_dir());
+        assert_eq!(
+            poll_ready(vfs.metadata("data/dir/file.txt")).unwrap().size,
+            4
+        );
+        let entries = poll_ready(vfs.list_dir_detailed("data/dir")).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert!(!entries[0].is_dir());
+    }
+
+    #[test]
+    fn reader_streams_chunks() {
+        let mem = MemoryProvider::new();
+        mem.insert("big.bin", (0..10u8).collect());
+
+        let vfs = Vfs::new();
+        vfs.mount("data", mem);
+
+        let mut reader = poll_ready(vfs.open_reader("data/big.bin"))
+            .unwrap()
+            .with_chunk_size(4);
+        assert_eq!(reader.len(), 10);
+
+        let mut chunks = Vec::new();
+        while let Some(chunk) = poll_ready(reader.next_chunk()).unwrap() {
+            chunks.push(chunk);
+        }
+        assert_eq!(chunks, vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7], vec![8, 9]]);
+
+        reader.seek(8);
+        assert_eq!(poll_ready(reader.next_chunk()).unwrap(), Some(vec![8, 9]));
+        assert!(poll_ready(vfs.open_reader("data")).is_err());
+    }
+
     #[test]
     fn write_via_vfs() {
         let mem = MemoryProvider::new();
 
-        let mut vfs = Vfs::new();
+        let vfs = Vfs::new();
         vfs.mount("m", mem);
 
         poll_ready(vfs.write("m/new.txt", b"hello".to_vec())).unwrap();
@@ -289,7 +560,7 @@ mod tests {
         let mem = MemoryProvider::new();
         mem.insert("file.txt", b"data".to_vec());
 
-        let mut vfs = Vfs::new();
+        let vfs = Vfs::new();
         vfs.mount("m", mem);
 
         poll_ready(vfs.delete("m/file.txt")).unwrap();
@@ -298,7 +569,7 @@ mod tests {
 
     #[test]
     fn is_read_only_check() {
-        let mut vfs = Vfs::new();
+        let vfs = Vfs::new();
         vfs.mount("mem", MemoryProvider::new());
 
         assert!(!vfs.is_read_only("mem/anything").unwrap());
@@ -312,7 +583,7 @@ mod tests {
         let mem2 = MemoryProvider::new();
         mem2.insert("b.txt", b"from_2".to_vec());
 
-        let mut vfs = Vfs::new();
+        let vfs = Vfs::new();
         vfs.mount("src1", mem1);
         vfs.mount("src2", mem2);
 
@@ -322,11 +593,120 @@ mod tests {
 
     #[test]
     fn clone_is_cheap() {
-        let mut vfs = Vfs::new();
+        let vfs = Vfs::new();
         vfs.mount("m", MemoryProvider::new());
 
         let vfs2 = vfs.clone();
         // Both share the same inner data
         poll_ready(vfs2.exists("m/anything")).unwrap();
     }
+
+    #[test]
+    fn mount_after_clone_is_shared() {
+        let vfs = Vfs::new();
+        let vfs2 = vfs.clone();
+
+        let mem = MemoryProvider::new();
+        mem.insert("a.txt", b"a".to_vec());
+        vfs2.mount("late", mem);
+        assert_eq!(poll_ready(vfs.read("late/a.txt")).unwrap(), b"a");
+
+        assert!(vfs.unmount("late"));
+        assert!(!vfs.unmount("late"));
+        assert!(matches!(
+            poll_ready(vfs2.read("late/a.txt")),
+            Err(VfsError::NoSuchSource(_))
+        ));
+    }
+
+    #[test]
+    fn layers_by_priority() {
+        let base = MemoryProvider::new();
+        base.insert("a.txt", b"base".to_vec());
+        base.insert("b.txt", b"base".to_vec());
+        let patch = MemoryProvider::new();
+        patch.insert("a.txt", b"patch".to_vec());
+        let scratch = MemoryProvider::new();
+
+        let vfs = Vfs::new();
+        vfs.mount("data", base);
+        let scratch_id = vfs.mount_layer("data", scratch.clone(), 10);
+        let patch_id = vfs.mount_layer("data", patch, 5);
+
+        assert_eq!(poll_ready(vfs.read("data/a.txt")).unwrap(), b"patch");
+        assert_eq!(poll_ready(vfs.read("data/b.txt")).unwrap(), b"base");
+        assert_eq!(
+            poll_ready(vfs.list_dir("data")).unwrap(),
+            vec!["a.txt", "b.txt"]
+        );
+
+        // Writes land in the top writable layer.
+        poll_ready(vfs.write("data/c.txt", b"new".to_vec())).unwrap();
+        assert!(poll_ready(scratch.exists("c.txt")).unwrap());
+
+        assert!(vfs.unmount_layer(patch_id));
+        assert!(!vfs.unmount_layer(patch_id));
+        assert_eq!(poll_ready(vfs.read("data/a.txt")).unwrap(), b"base");
+
+        assert!(vfs.unmount_layer(scratch_id));
+        assert!(poll_ready(vfs.read("data/c.txt")).is_err());
+        assert_eq!(vfs.sources(), vec!["data"]);
+    }
+
+    #[test]
+    fn canonical_path_adds_default_source() {
+        let vfs = Vfs::new();
+        vfs.mount("data", MemoryProvider::new());
+        vfs.set_default("data");
+
+        assert_eq!(vfs.canonical_path("data//a.txt").unwrap(), "data/a.txt");
+        assert_eq!(vfs.canonical_path("sub/a.txt").unwrap(), "data/sub/a.txt");
+        assert!(matches!(
+            Vfs::new().canonical_path("x/a.txt"),
+            Err(VfsError::NoSuchSource(_))
+        ));
+    }
+
+    #[test]
+    fn watch_prefixes_source_name() {
+        let mem = MemoryProvider::new();
+        let vfs = Vfs::new();
+        vfs.mount("m", mem.clone());
+
+        let watcher = vfs.watch();
+        poll_ready(vfs.write("m/sub/file.txt", b"data".to_vec())).unwrap();
+        mem.insert("other.txt", vec![]);
+
+        let paths: Vec<String> = watcher.poll().into_iter().map(|c| c.path).collect();
+        assert_eq!(paths, vec!["m/sub/file.txt", "m/other.txt"]);
+    }
+
+    #[test]
+    fn watch_follows_mounts() {
+        let first = MemoryProvider::new();
+        let vfs = Vfs::new();
+        vfs.mount("m", first.clone());
+        let watcher = vfs.watch();
+
+        let layer = MemoryProvider::new();
+        vfs.mount_layer("m", layer.clone(), 10);
+        let late = MemoryProvider::new();
+        vfs.mount("late", late.clone());
+        // Still pending on the replaced watcher, so reported.
+        first.insert("before_sync.txt", vec![]);
+        let paths: Vec<String> = watcher.poll().into_iter().map(|c| c.path).collect();
+        assert_eq!(paths, vec!["m/before_sync.txt"]);
+
+        first.insert("base.txt", vec![]);
+        layer.insert("patch.txt", vec![]);
+        late.insert("new.txt", vec![]);
+        let mut paths: Vec<String> = watcher.poll().into_iter().map(|c| c.path).collect();
+        paths.sort();
+        assert_eq!(paths, vec!["late/new.txt", "m/base.txt", "m/patch.txt"]);
+
+        vfs.unmount("late");
+        watcher.poll();
+        late.insert("gone.txt", vec![]);
+        assert!(watcher.poll().is_empty());
+    }
 }
@@ -0,0 +1,315 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::mpsc;
+
+/// The kind of change reported for a path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VfsChangeKind {
+    /// A file was created (or renamed into place).
+    Created,
+    /// The contents of an existing file changed.
+    Modified,
+    /// A file was deleted (or renamed away).
+    Removed,
+}
+
+/// A single change notification.
+///
+/// The path uses the same form as the rest of the VFS API: relative to the
+/// provider root when produced by a [`VfsProvider`](crate::VfsProvider), and
+/// prefixed with the source name when produced by [`Vfs::watch`](crate::Vfs::watch).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VfsChange {
+    /// Path of the changed file.
+    pub path: String,
+    /// What happened to the file.
+    pub kind: VfsChangeKind,
+}
+
+impl VfsChange {
+    /// Create a change notification.
+    pub fn new(path: impl Into<String>, kind: VfsChangeKind) -> Self {
+        Self {
+            path: path.into(),
+            kind,
+        }
+    }
+}
+
+/// A receiver of change notifications plus whatever keeps its source alive.
+struct WatchStream {
+    /// Prepended (with a `/`) to every received path. Empty for none.
+    prefix: String,
+    /// The channel and the guard object (e.g. an OS watcher handle).
+    ///
+    /// Behind a mutex so that the watcher is `Sync` and can live in an ECS
+    /// resource; polling happens from a single thread in practice.
+    channel: Mutex<(mpsc::Receiver<VfsChange>, Box<dyn Any + Send>)>,
+}
+
+/// Handle that receives change notifications from one or more providers.
+///
+/// Watching stops when the watcher is dropped. Notifications are buffered
+/// until [`poll`](Self::poll) is called, so a watcher that is never polled
+/// accumulates events.
+///
+/// # Example
+///
+/// ```ignore
+/// let watcher = vfs.watch();
+///
+/// // Once per frame:
+/// for change in watcher.poll() {
+///     log::info!("{} was {:?}", change.path, change.kind);
+/// }
+/// ```
+pub struct VfsWatcher {
+    streams: Vec<WatchStream>,
+    /// Drains changes from watchers that are created and dropped over the
+    /// watcher's lifetime, such as those following [`Vfs`](crate::Vfs) mounts.
+    followers: Vec<Follower>,
+}
+
+/// Appends the changes it has received since the last call.
+type Follower = Box<dyn Fn(&mut Vec<VfsChange>) + Send + Sync>;
+
+impl VfsWatcher {
+    /// Create a watcher from a channel of changes.
+    ///
+    /// `guard` is kept alive for as long as the watcher and dropped with it;
+    /// providers use it to stop the underlying OS watcher or polling thread.
+    pub fn new(receiver: mpsc::Receiver<VfsChange>, guard: impl Any + Send) -> Self {
+        Self {
+            streams: vec![WatchStream {
+                prefix: String::new(),
+                channel: Mutex::new((receiver, Box::new(guard))),
+            }],
+            followers: Vec::new(),
+        }
+    }
+
+    /// Create a watcher that never reports any change.
+    pub fn empty() -> Self {
+        Self {
+            streams: Vec::new(),
+            followers: Vec::new(),
+        }
+    }
+
+    /// Create a watcher that drains its changes from `follower` on every poll.
+    pub(crate) fn follow(follower: impl Fn(&mut Vec<VfsChange>) + Send + Sync + 'static) -> Self {
+        Self {
+            streams: Vec::new(),
+            followers: vec![Box::new(follower)],
+        }
+    }
+
+    /// Drain all pending changes.
+    ///
+    /// Repeated notifications for the same path are coalesced into one,
+    /// reported in the order the path first changed with the latest kind.
+    pub fn poll(&self) -> Vec<VfsChange> {
+        let mut received = Vec::new();
+        self.drain_into(&mut received);
+
+        let mut changes: Vec<VfsChange> = Vec::new();
+        let mut index: HashMap<String, usize> = HashMap::new();
+        for change in received {
+            match index.get(&change.path) {
+                Some(&i) => changes[i].kind = coalesce(changes[i].kind, change.kind),
+                None => {
+                    index.insert(change.path.clone(), changes.len());
+                    changes.push(change);
+                }
+            }
+        }
+
+        changes
+    }
+
+    /// Append all pending changes, in the order received, without coalescing.
+    pub(crate) fn drain_into(&self, changes: &mut Vec<VfsChange>) {
+        for stream in &self.streams {
+            let channel = stream.channel.lock().unwrap();
+            while let Ok(mut change) = channel.0.try_recv() {
+                if !stream.prefix.is_empty() {
+                    change.path = if change.path.is_empty() {
+                        stream.prefix.clone()
+                    } else {
+                        format!("{}/{}", stream.prefix, change.path)
+                    };
+                }
+                changes.push(change);
+            }
+        }
+        for follower in &self.followers {
+            follower(changes);
+        }
+    }
+
+    /// Prefix every path reported by this watcher with a source name.
+    ///
+    /// Only applies to the watcher's own streams, not to followers.
+    pub(crate) fn with_prefix(mut self, prefix: &str) -> Self {
+        for stream in &mut self.streams {
+            stream.prefix = if stream.prefix.is_empty() {
+                prefix.to_owned()
+            } else {
+                format!("{prefix}/{}", stream.prefix)
+            };
+        }
+        self
+    }
+
+    /// Merge the streams of another watcher into this one.
+    pub(crate) fn merge(&mut self, other: VfsWatcher) {
+        self.streams.extend(other.streams);
+        self.followers.extend(other.followers);
+    }
+}
+
+/// Combine two consecutive change kinds for the same path.
+fn coalesce(previous: VfsChangeKind, next: VfsChangeKind) -> VfsChangeKind {
+    match (previous, next) {
+        // Still a new file from the observer's point of view
+        (VfsChangeKind::Created, VfsChangeKind::Modified) => VfsChangeKind::Created,
+        // Deleted and recreated: the contents changed
+        (VfsChangeKind::Removed, VfsChangeKind::Created) => VfsChangeKind::Modified,
+        (_, next) => next,
+    }
+}
+
+/// Size and modification time of a file, used by polling watchers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(any(not(feature = "sftp"), target_arch = "wasm32"), allow(dead_code))]
+pub(crate) struct FileStamp {
+    pub size: u64,
+    pub mtime: u64,
+}
+
+/// Compute the changes between two recursive directory snapshots.
+///
+/// Results are sorted by path so that polling watchers report changes in a
+/// stable order.
+#[cfg_attr(any(not(feature = "sftp"), target_arch = "wasm32"), allow(dead_code))]
+pub(crate) fn diff_snapshots(
+    old: &HashMap<String, FileStamp>,
+    new: &HashMap<String, FileStamp>,
+) -> Vec<VfsChange> {
+    let mut changes = Vec::new();
+    for (path, stamp) in new {
+        match old.get(path) {
+            None => changes.push(VfsChange::new(path.clone(), VfsChangeKind::Created)),
+            Some(previous) if previous != stamp => {
+                changes.push(VfsChange::new(path.clone(), VfsChangeKind::Modified))
+            }
+            Some(_) => {}
+        }
+    }
+    for path in old.keys() {
+        if !new.contains_key(path) {
+            changes.push(VfsChange::new(path.clone(), VfsChangeKind::Removed));
+        }
+    }
+    changes.sort_by(|a, b| a.path.cmp(&b.path));
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn poll_drains_changes() {
+        let (tx, rx) = mpsc::channel();
+        let watcher = VfsWatcher::new(rx, ());
+        tx.send(VfsChange::new("a.txt", VfsChangeKind::Created))
+            .unwrap();
+
+        assert_eq!(
+            watcher.poll(),
+            vec![VfsChange::new("a.txt", VfsChangeKind::Created)]
+        );
+        assert!(watcher.poll().is_empty());
+    }
+
+    #[test]
+    fn poll_coalesces_repeated_paths() {
+        let (tx, rx) = mpsc::channel();
+        let watcher = VfsWatcher::new(rx, ());
+        tx.send(VfsChange::new("a.txt", VfsChangeKind::Created))
+            .unwrap();
+        tx.send(VfsChange::new("b.txt", VfsChangeKind::Modified))
+            .unwrap();
+        tx.send(VfsChange::new("a.txt", VfsChangeKind::Modified))
+            .unwrap();
+        tx.send(VfsChange::new("b.txt", VfsChangeKind::Removed))
+            .unwrap();
+
+        assert_eq!(
+            watcher.poll(),
+            vec![
+                VfsChange::new("a.txt", VfsChangeKind::Created),
+                VfsChange::new("b.txt", VfsChangeKind::Removed),
+            ]
+        );
+    }
+
+    #[test]
+    fn prefix_and_merge() {
+        let (tx1, rx1) = mpsc::channel();
+        let (tx2, rx2) = mpsc::channel();
+        let mut watcher = VfsWatcher::new(rx1, ()).with_prefix("one");
+        watcher.merge(VfsWatcher::new(rx2, ()).with_prefix("two"));
+
+        tx1.send(VfsChange::new("a.txt", VfsChangeKind::Modified))
+            .unwrap();
+        tx2.send(VfsChange::new("sub/b.txt", VfsChangeKind::Modified))
+            .unwrap();
+
+        let paths: Vec<String> = watcher.poll().into_iter().map(|c| c.path).collect();
+        assert_eq!(paths, vec!["one/a.txt", "two/sub/b.txt"]);
+    }
+
+    #[test]
+    fn guard_dropped_with_watcher() {
+        struct Guard(std::sync::Arc<std::sync::atomic::AtomicBool>);
+        impl Drop for Guard {
+            fn drop(&mut self) {
+                self.0.store(true, std::sync::atomic::Ordering::SeqCst);
+            }
+        }
+
+        let dropped = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let (_tx, rx) = mpsc::channel();
+        let watcher = VfsWatcher::new(rx, Guard(dropped.clone()));
+        assert!(!dropped.load(std::sync::atomic::Ordering::SeqCst));
+        drop(watcher);
+        assert!(dropped.load(std::sync::atomic::Ordering::SeqCst));
+    }
+
+    #[test]
+    fn diff_detects_all_kinds() {
+        let stamp = |size, mtime| FileStamp { size, mtime };
+        let old = HashMap::from([
+            ("kept.txt".to_owned(), stamp(1, 1)),
+            ("changed.txt".to_owned(), stamp(1, 1)),
+            ("gone.txt".to_owned(), stamp(1, 1)),
+        ]);
+        let new = HashMap::from([
+            ("kept.txt".to_owned(), stamp(1, 1)),
+            ("changed.txt".to_owned(), stamp(1, 2)),
+            ("added.txt".to_owned(), stamp(1, 1)),
+        ]);
+
+        assert_eq!(
+            diff_snapshots(&old, &new),
+            vec![
+                VfsChange::new("added.txt", VfsChangeKind::Created),
+                VfsChange::new("changed.txt", VfsChangeKind::Modified),
+                VfsChange::new("gone.txt", VfsChangeKind::Removed),
+            ]
+        );
+    }
+}